@@ -0,0 +1,421 @@
+use crate::errors::TsqError;
+use crate::store::lock::with_write_lock;
+use crate::store::paths::get_paths;
+use crate::types::{BackupFile, BackupManifest, BackupResult, RestoreResult};
+use sha2::{Digest, Sha256};
+use std::fs::{File, create_dir_all, read_dir, remove_dir_all, remove_file, rename};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+pub const BACKUP_MANIFEST_NAME: &str = "tasque-backup.json";
+const BACKUP_SCHEMA_VERSION: u32 = 1;
+const TASQUE_DIR: &str = ".tasque";
+/// Where the live `.tasque/` waits while a restore swaps the backup in.
+const RESTORE_OLD_DIR: &str = ".tasque.restore-old";
+
+/// `.tasque/`-relative paths and their contents.
+type BackupEntries = Vec<(String, Vec<u8>)>;
+
+/// Archives every file under `.tasque/` except the write lock and stray temp
+/// files, plus a manifest of their checksums. `.zst` outputs are compressed.
+pub fn create_backup(
+    repo_root: impl AsRef<Path>,
+    out: Option<&str>,
+    now: &dyn Fn() -> String,
+) -> Result<BackupResult, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let created_at = now();
+    let out = PathBuf::from(match out {
+        Some(out) => out.to_string(),
+        None => format!(
+            "tasque-backup-{}.tar.zst",
+            created_at.replace([':', '.'], "-")
+        ),
+    });
+
+    with_write_lock(repo_root, || {
+        let tasque_dir = get_paths(repo_root).tasque_dir;
+        let mut entries = Vec::new();
+        collect_files(&tasque_dir, &tasque_dir, &mut entries)?;
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let manifest = BackupManifest {
+            schema_version: BACKUP_SCHEMA_VERSION,
+            created_at: created_at.clone(),
+            tsq_version: env!("CARGO_PKG_VERSION").to_string(),
+            files: entries
+                .iter()
+                .map(|(path, bytes)| BackupFile {
+                    path: path.clone(),
+                    bytes: bytes.len() as u64,
+                    sha256: sha256_hex(bytes),
+                })
+                .collect(),
+        };
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(|error| {
+            TsqError::new("BACKUP_FAILED", "Failed writing backup manifest", 2)
+                .with_details(serde_json::json!({"message": error.to_string()}))
+        })?;
+
+        let mut archive = Vec::new();
+        {
+            let mut builder = tar::Builder::new(&mut archive);
+            append_entry(&mut builder, BACKUP_MANIFEST_NAME, &manifest_bytes)?;
+            for (path, bytes) in &entries {
+                append_entry(&mut builder, &format!("{}/{}", TASQUE_DIR, path), bytes)?;
+            }
+            builder.finish().map_err(backup_io_error)?;
+        }
+        if is_zstd(&out) {
+            archive = zstd::encode_all(archive.as_slice(), 3).map_err(backup_io_error)?;
+        }
+        let temp = PathBuf::from(format!("{}.tmp-{}", out.display(), std::process::id()));
+        File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(&archive)?;
+                file.sync_all()
+            })
+            .and_then(|_| rename(&temp, &out))
+            .map_err(|error| {
+                let _ = remove_file(&temp);
+                backup_io_error(error)
+            })?;
+
+        Ok(BackupResult {
+            path: out.display().to_string(),
+            created_at: created_at.clone(),
+            files: manifest.files.len(),
+            bytes: archive.len() as u64,
+            sha256: sha256_hex(&archive),
+        })
+    })
+}
+
+/// Verifies every checksum in the archive before touching `.tasque/`, then
+/// swaps its contents in. A repo that already has events is only replaced
+/// with `force`.
+pub fn restore_backup(
+    repo_root: impl AsRef<Path>,
+    archive_path: &str,
+    force: bool,
+) -> Result<RestoreResult, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let (manifest, files) = read_backup(Path::new(archive_path))?;
+    let paths = get_paths(repo_root);
+    create_dir_all(&paths.tasque_dir).map_err(restore_io_error)?;
+
+    with_write_lock(repo_root, || {
+        let has_events = std::fs::metadata(&paths.events_file).is_ok_and(|meta| meta.len() > 0)
+            || paths.segment_manifest_file.exists();
+        if has_events && !force {
+            return Err(TsqError::new(
+                "RESTORE_WOULD_OVERWRITE",
+                "the repo already has events; pass --force to replace them with the backup",
+                1,
+            ));
+        }
+
+        let staging = repo_root.join(format!(".tasque.restore-{}", std::process::id()));
+        let staged = stage_files(&staging, &files)
+            .and_then(|_| carry_lock(&paths.lock_file, &staging))
+            .and_then(|_| {
+                swap_tasque_dir(
+                    &paths.tasque_dir,
+                    &staging,
+                    &repo_root.join(RESTORE_OLD_DIR),
+                    &|from, to| rename(from, to),
+                )
+            });
+        let _ = remove_dir_all(&staging);
+        staged?;
+
+        Ok(RestoreResult {
+            path: archive_path.to_string(),
+            created_at: manifest.created_at.clone(),
+            files: files.len(),
+            replaced: has_events,
+        })
+    })
+}
+
+/// The manifest and the `.tasque/`-relative files of an archive, once every
+/// file matches its listed size and checksum and nothing is missing or extra.
+fn read_backup(path: &Path) -> Result<(BackupManifest, BackupEntries), TsqError> {
+    let mut raw = Vec::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut raw))
+        .map_err(|error| {
+            TsqError::new("BACKUP_READ_FAILED", "Failed reading backup archive", 2).with_details(
+                serde_json::json!({
+                    "path": path.display().to_string(),
+                    "message": error.to_string(),
+                }),
+            )
+        })?;
+    if is_zstd(path) {
+        raw =
+            zstd::decode_all(raw.as_slice()).map_err(|error| invalid_backup(error.to_string()))?;
+    }
+
+    let mut manifest = None;
+    let mut files: BackupEntries = Vec::new();
+    let mut archive = tar::Archive::new(raw.as_slice());
+    for entry in archive
+        .entries()
+        .map_err(|error| invalid_backup(error.to_string()))?
+    {
+        let mut entry = entry.map_err(|error| invalid_backup(error.to_string()))?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|error| invalid_backup(error.to_string()))?
+            .to_string_lossy()
+            .to_string();
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|error| invalid_backup(error.to_string()))?;
+        if name == BACKUP_MANIFEST_NAME {
+            manifest = Some(
+                serde_json::from_slice::<BackupManifest>(&bytes)
+                    .map_err(|error| invalid_backup(error.to_string()))?,
+            );
+            continue;
+        }
+        let relative = name
+            .strip_prefix(&format!("{}/", TASQUE_DIR))
+            .filter(|relative| is_safe_relative(relative))
+            .ok_or_else(|| invalid_backup(format!("unexpected archive entry {}", name)))?;
+        files.push((relative.to_string(), bytes));
+    }
+
+    let manifest =
+        manifest.ok_or_else(|| invalid_backup(format!("missing {}", BACKUP_MANIFEST_NAME)))?;
+    let mut mismatched = Vec::new();
+    for listed in &manifest.files {
+        match files.iter().find(|(path, _)| *path == listed.path) {
+            Some((_, bytes))
+                if bytes.len() as u64 == listed.bytes && sha256_hex(bytes) == listed.sha256 => {}
+            _ => mismatched.push(listed.path.clone()),
+        }
+    }
+    for (path, _) in &files {
+        if !manifest.files.iter().any(|listed| listed.path == *path) {
+            mismatched.push(path.clone());
+        }
+    }
+    if !mismatched.is_empty() {
+        mismatched.sort();
+        return Err(TsqError::new(
+            "BACKUP_VERIFY_FAILED",
+            "backup contents do not match their manifest checksums",
+            2,
+        )
+        .with_details(serde_json::json!({"files": mismatched})));
+    }
+    Ok((manifest, files))
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut BackupEntries) -> Result<(), TsqError> {
+    let entries = match read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(backup_io_error(error)),
+    };
+    for entry in entries {
+        let entry = entry.map_err(backup_io_error)?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == ".lock" || name.contains(".tmp") {
+            continue;
+        }
+        if entry.file_type().map_err(backup_io_error)?.is_dir() {
+            collect_files(root, &path, out)?;
+            continue;
+        }
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        let bytes = std::fs::read(&path).map_err(backup_io_error)?;
+        out.push((relative, bytes));
+    }
+    Ok(())
+}
+
+fn append_entry(
+    builder: &mut tar::Builder<&mut Vec<u8>>,
+    path: &str,
+    bytes: &[u8],
+) -> Result<(), TsqError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, path, bytes)
+        .map_err(backup_io_error)
+}
+
+fn stage_files(staging: &Path, files: &[(String, Vec<u8>)]) -> Result<(), TsqError> {
+    for (path, bytes) in files {
+        let target = staging.join(path);
+        if let Some(parent) = target.parent() {
+            create_dir_all(parent).map_err(restore_io_error)?;
+        }
+        std::fs::write(&target, bytes).map_err(restore_io_error)?;
+    }
+    Ok(())
+}
+
+/// Copies the write lock the restore is holding into the staged tree, so the
+/// lock is still held once that tree becomes `.tasque/`.
+fn carry_lock(lock_file: &Path, staging: &Path) -> Result<(), TsqError> {
+    std::fs::copy(lock_file, staging.join(".lock"))
+        .map(|_| ())
+        .map_err(restore_io_error)
+}
+
+/// Moves the live `.tasque/` to `old`, renames `staging` into its place, and
+/// only then deletes `old`. If the second rename fails, `old` is moved back,
+/// so a failed restore leaves the repo as it was.
+fn swap_tasque_dir(
+    tasque_dir: &Path,
+    staging: &Path,
+    old: &Path,
+    rename: &dyn Fn(&Path, &Path) -> std::io::Result<()>,
+) -> Result<(), TsqError> {
+    if old.exists() {
+        return Err(TsqError::new(
+            "RESTORE_FAILED",
+            format!(
+                "{} is left over from an earlier restore; move it away first",
+                RESTORE_OLD_DIR
+            ),
+            2,
+        )
+        .with_details(serde_json::json!({"path": old.display().to_string()})));
+    }
+    rename(tasque_dir, old).map_err(restore_io_error)?;
+    if let Err(error) = rename(staging, tasque_dir) {
+        return Err(match rename(old, tasque_dir) {
+            Ok(()) => restore_io_error(error),
+            Err(rollback) => TsqError::new(
+                "RESTORE_FAILED",
+                format!(
+                    "Failed restoring backup and moving the previous data back; it is in {}",
+                    RESTORE_OLD_DIR
+                ),
+                2,
+            )
+            .with_details(serde_json::json!({
+                "path": old.display().to_string(),
+                "message": error.to_string(),
+                "rollback_message": rollback.to_string(),
+            })),
+        });
+    }
+    if let Err(error) = remove_dir_all(old) {
+        tracing::warn!(
+            "restored backup but could not remove {}: {}",
+            old.display(),
+            error
+        );
+    }
+    Ok(())
+}
+
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn is_zstd(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "zst")
+}
+
+fn invalid_backup(message: String) -> TsqError {
+    TsqError::new("BACKUP_INVALID", "backup archive is unreadable", 2)
+        .with_details(serde_json::json!({"message": message}))
+}
+
+fn backup_io_error(error: std::io::Error) -> TsqError {
+    TsqError::new("BACKUP_FAILED", "Failed writing backup archive", 2).with_details(
+        serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}),
+    )
+}
+
+fn restore_io_error(error: std::io::Error) -> TsqError {
+    TsqError::new("RESTORE_FAILED", "Failed restoring backup", 2).with_details(
+        serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}),
+    )
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn swap_rolls_back_when_the_staged_tree_cannot_be_moved_in() {
+        let root = TempDir::new().expect("tempdir");
+        let tasque_dir = root.path().join(TASQUE_DIR);
+        let staging = root.path().join(".tasque.restore-test");
+        let old = root.path().join(RESTORE_OLD_DIR);
+        fs::create_dir_all(&tasque_dir).expect("tasque dir");
+        fs::write(tasque_dir.join("events.jsonl"), "live\n").expect("live events");
+        fs::create_dir_all(&staging).expect("staging");
+        fs::write(staging.join("events.jsonl"), "backup\n").expect("staged events");
+
+        let error = swap_tasque_dir(&tasque_dir, &staging, &old, &|from, to| {
+            if from == staging {
+                return Err(std::io::Error::other("injected"));
+            }
+            rename(from, to)
+        })
+        .expect_err("injected failure");
+
+        assert_eq!(error.code, "RESTORE_FAILED");
+        assert_eq!(
+            fs::read_to_string(tasque_dir.join("events.jsonl")).expect("events"),
+            "live\n"
+        );
+        assert!(!old.exists());
+        assert!(staging.exists());
+    }
+
+    #[test]
+    fn swap_replaces_the_live_tree_and_drops_the_old_one() {
+        let root = TempDir::new().expect("tempdir");
+        let tasque_dir = root.path().join(TASQUE_DIR);
+        let staging = root.path().join(".tasque.restore-test");
+        let old = root.path().join(RESTORE_OLD_DIR);
+        fs::create_dir_all(&tasque_dir).expect("tasque dir");
+        fs::write(tasque_dir.join("events.jsonl"), "live\n").expect("live events");
+        fs::create_dir_all(&staging).expect("staging");
+        fs::write(staging.join("events.jsonl"), "backup\n").expect("staged events");
+
+        swap_tasque_dir(&tasque_dir, &staging, &old, &|from, to| rename(from, to)).expect("swap");
+
+        assert_eq!(
+            fs::read_to_string(tasque_dir.join("events.jsonl")).expect("events"),
+            "backup\n"
+        );
+        assert!(!old.exists());
+        assert!(!staging.exists());
+    }
+}
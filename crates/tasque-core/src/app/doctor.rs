@@ -0,0 +1,305 @@
+use crate::app::repair::scan_orphaned_graph;
+use crate::app::service_types::{DoctorFinding, DoctorSeverity};
+use crate::domain::deps::normalize_dependency_edges;
+use crate::domain::event_payload_codecs::event_type_from_str;
+use crate::domain::projector::StatusChange;
+use crate::skills::types::SkillStatusEntry;
+use crate::store::events::RawEventLine;
+use crate::types::{DependencyType, RelationType, State, StatusWorkflowConfig, TaskStatus};
+use chrono::DateTime;
+use std::collections::{HashMap, HashSet};
+
+fn finding(code: &str, severity: DoctorSeverity, message: String) -> DoctorFinding {
+    DoctorFinding {
+        code: code.to_string(),
+        severity,
+        message,
+        task_id: None,
+        event_id: None,
+        line: None,
+    }
+}
+
+/// Duplicate ids, unknown event types, and timestamps that go backwards.
+pub fn event_log_findings(lines: &[RawEventLine]) -> Vec<DoctorFinding> {
+    let mut findings = Vec::new();
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+    let mut previous_ts = None;
+
+    for raw in lines {
+        if let Some(id) = raw.id.as_deref() {
+            if let Some(first) = first_seen.get(id) {
+                findings.push(DoctorFinding {
+                    event_id: Some(id.to_string()),
+                    line: Some(raw.line),
+                    ..finding(
+                        "DUPLICATE_EVENT_ID",
+                        DoctorSeverity::Error,
+                        format!(
+                            "event id {} at line {} was already used at line {}",
+                            id, raw.line, first
+                        ),
+                    )
+                });
+            } else {
+                first_seen.insert(id, raw.line);
+            }
+        }
+
+        let event_type = raw.event_type.as_deref().unwrap_or("");
+        if event_type_from_str(event_type).is_none() {
+            findings.push(DoctorFinding {
+                event_id: raw.id.clone(),
+                line: Some(raw.line),
+                ..finding(
+                    "UNKNOWN_EVENT_TYPE",
+                    DoctorSeverity::Error,
+                    format!("unknown event type \"{}\" at line {}", event_type, raw.line),
+                )
+            });
+        }
+
+        let ts = raw
+            .ts
+            .as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok());
+        if let (Some(ts), Some((prev, prev_line))) = (ts, previous_ts)
+            && ts < prev
+        {
+            findings.push(DoctorFinding {
+                event_id: raw.id.clone(),
+                line: Some(raw.line),
+                ..finding(
+                    "NON_MONOTONIC_TIMESTAMP",
+                    DoctorSeverity::Warning,
+                    format!(
+                        "event at line {} is timestamped before line {}",
+                        raw.line, prev_line
+                    ),
+                )
+            });
+        }
+        if let Some(ts) = ts {
+            previous_ts = Some((ts, raw.line));
+        }
+    }
+    findings
+}
+
+/// Logged status moves that the current `status_workflow` forbids, e.g. from
+/// a clone without the workflow or before `transitions` was tightened. Replay
+/// accepts them, so they only surface here.
+pub fn workflow_findings(
+    changes: &[StatusChange<'_>],
+    workflow: &StatusWorkflowConfig,
+) -> Vec<DoctorFinding> {
+    changes
+        .iter()
+        .filter(|change| !workflow.allows(&change.from, &change.to))
+        .map(|change| DoctorFinding {
+            task_id: Some(change.event.task_id.clone()),
+            event_id: change.event.id.clone().or(change.event.event_id.clone()),
+            ..finding(
+                "INVALID_TRANSITION",
+                DoctorSeverity::Warning,
+                format!(
+                    "task {} moved from {} to {}, which status_workflow does not allow",
+                    change.event.task_id,
+                    change.from.as_str(),
+                    change.to.as_str()
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Dangling graph references plus suspicious but valid task states.
+pub fn state_findings(state: &State) -> Vec<DoctorFinding> {
+    let mut findings = Vec::new();
+    let task_finding = |code: &str, severity, task_id: &str, message: String| DoctorFinding {
+        task_id: Some(task_id.to_string()),
+        ..finding(code, severity, message)
+    };
+
+    for (child, blockers) in &state.deps {
+        if !state.tasks.contains_key(child) {
+            findings.push(task_finding(
+                "DEP_SOURCE_MISSING",
+                DoctorSeverity::Error,
+                child,
+                format!("dependency source missing: {}", child),
+            ));
+        }
+        for edge in normalize_dependency_edges(Some(blockers)) {
+            if !state.tasks.contains_key(&edge.blocker) {
+                findings.push(task_finding(
+                    "DEP_BLOCKER_MISSING",
+                    DoctorSeverity::Error,
+                    child,
+                    format!(
+                        "dependency blocker missing: {} -> {} ({})",
+                        child,
+                        edge.blocker,
+                        dep_type_name(edge.dep_type)
+                    ),
+                ));
+            }
+        }
+    }
+
+    for (src, rels) in &state.links {
+        if !state.tasks.contains_key(src) {
+            findings.push(task_finding(
+                "RELATION_SOURCE_MISSING",
+                DoctorSeverity::Error,
+                src,
+                format!("relation source missing: {}", src),
+            ));
+        }
+        for (kind, targets) in rels {
+            for target in targets.iter().filter(|id| !state.tasks.contains_key(*id)) {
+                findings.push(task_finding(
+                    "RELATION_TARGET_MISSING",
+                    DoctorSeverity::Error,
+                    src,
+                    format!(
+                        "relation target missing: {} -[{}]-> {}",
+                        src,
+                        relation_type_name(*kind),
+                        target
+                    ),
+                ));
+            }
+        }
+    }
+
+    for issue in scan_orphaned_graph(state).invalid_direct_refs {
+        findings.push(task_finding(
+            "INVALID_TASK_REF",
+            DoctorSeverity::Error,
+            &issue.task_id,
+            format!(
+                "direct task ref {}: {}.{} -> {}",
+                issue.reason, issue.task_id, issue.field, issue.target
+            ),
+        ));
+    }
+
+    let mut tasks: Vec<_> = state.tasks.values().collect();
+    tasks.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut reported_cycles: HashSet<String> = HashSet::new();
+    for task in &tasks {
+        let active = !matches!(task.status, TaskStatus::Closed | TaskStatus::Canceled);
+        if active
+            && let Some(parent) = task.parent_id.as_deref().and_then(|id| state.tasks.get(id))
+            && matches!(parent.status, TaskStatus::Closed | TaskStatus::Canceled)
+        {
+            findings.push(task_finding(
+                "CHILD_OF_CLOSED_PARENT",
+                DoctorSeverity::Warning,
+                &task.id,
+                format!(
+                    "task {} is still open under {} parent {}",
+                    task.id,
+                    if parent.status == TaskStatus::Closed {
+                        "closed"
+                    } else {
+                        "canceled"
+                    },
+                    parent.id
+                ),
+            ));
+        }
+
+        if let Some(cycle) = duplicate_cycle(state, &task.id)
+            && reported_cycles.insert(cycle[0].clone())
+        {
+            findings.push(task_finding(
+                "DUPLICATE_CYCLE",
+                DoctorSeverity::Error,
+                &cycle[0],
+                format!("duplicate_of chain loops: {}", cycle.join(" -> ")),
+            ));
+        }
+
+        if task.status == TaskStatus::InProgress
+            && task
+                .assignee
+                .as_deref()
+                .is_none_or(|assignee| assignee.trim().is_empty())
+        {
+            findings.push(task_finding(
+                "IN_PROGRESS_UNASSIGNED",
+                DoctorSeverity::Warning,
+                &task.id,
+                format!("task {} is in_progress with no assignee", task.id),
+            ));
+        }
+    }
+    findings
+}
+
+/// The loop reached by following `duplicate_of` from `start`, rotated to
+/// begin at its smallest id so each loop is reported once.
+/// Managed skill installs that are behind the source version.
+pub fn skill_findings(entries: &[SkillStatusEntry]) -> Vec<DoctorFinding> {
+    entries
+        .iter()
+        .filter(|entry| entry.upgrade_available)
+        .map(|entry| {
+            finding(
+                "SKILL_OUTDATED",
+                DoctorSeverity::Warning,
+                format!(
+                    "{} skill at {} is {} but {} is available; run `tsq skills update --all`",
+                    entry.target,
+                    entry.path,
+                    entry.installed_version.as_deref().unwrap_or("unversioned"),
+                    entry.source_version.as_deref().unwrap_or("a newer copy"),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn duplicate_cycle(state: &State, start: &str) -> Option<Vec<String>> {
+    let mut path: Vec<String> = Vec::new();
+    let mut cursor = Some(start.to_string());
+    while let Some(current) = cursor {
+        if let Some(pos) = path.iter().position(|id| id == &current) {
+            let mut cycle = path.split_off(pos);
+            let min = cycle
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1.cmp(b.1))
+                .map(|(index, _)| index)
+                .unwrap_or(0);
+            cycle.rotate_left(min);
+            return Some(cycle);
+        }
+        path.push(current.clone());
+        cursor = state
+            .tasks
+            .get(&current)
+            .and_then(|task| task.duplicate_of.clone());
+    }
+    None
+}
+
+fn dep_type_name(dep_type: DependencyType) -> &'static str {
+    match dep_type {
+        DependencyType::Blocks => "blocks",
+        DependencyType::StartsAfter => "starts_after",
+        DependencyType::FinishToFinish => "finish_to_finish",
+        DependencyType::Informs => "informs",
+    }
+}
+
+fn relation_type_name(rel_type: RelationType) -> &'static str {
+    match rel_type {
+        RelationType::RelatesTo => "relates_to",
+        RelationType::RepliesTo => "replies_to",
+        RelationType::Duplicates => "duplicates",
+        RelationType::Supersedes => "supersedes",
+    }
+}
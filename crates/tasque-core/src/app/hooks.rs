@@ -0,0 +1,201 @@
+use crate::app::storage::read_config;
+use crate::errors::TsqError;
+use crate::store::lock::IN_BLOCKING_HOOK_ENV;
+use crate::types::{EventRecord, EventType, HookConfig, HookMode, HooksConfig};
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const STDERR_TAIL_CHARS: usize = 2000;
+
+/// Runs the blocking hooks configured for `events`, before they are appended.
+/// The first failing hook aborts the mutation with `HOOK_FAILED`.
+pub fn run_blocking_hooks(repo_root: &str, events: &[EventRecord]) -> Result<(), TsqError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let hooks = read_config(repo_root)?.hooks;
+    for event in events {
+        for (name, hook) in hooks_for_event(&hooks, event, HookMode::Blocking) {
+            run_hook(repo_root, name, hook, event)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the advisory hooks configured for committed `events`. Failures are
+/// logged as warnings and never change the command result.
+pub fn run_advisory_hooks(repo_root: &str, events: &[EventRecord]) {
+    if events.is_empty() {
+        return;
+    }
+    let Ok(config) = read_config(repo_root) else {
+        return;
+    };
+    for event in events {
+        for (name, hook) in hooks_for_event(&config.hooks, event, HookMode::Advisory) {
+            if let Err(error) = run_hook(repo_root, name, hook, event) {
+                tracing::warn!("{}", error.message);
+            }
+        }
+    }
+}
+
+fn hooks_for_event<'a>(
+    hooks: &'a HooksConfig,
+    event: &EventRecord,
+    mode: HookMode,
+) -> Vec<(&'static str, &'a HookConfig)> {
+    let mut matched = Vec::new();
+    match event.event_type {
+        EventType::TaskCreated => matched.push(("on_create", hooks.on_create.as_ref())),
+        EventType::TaskClaimed => matched.push(("on_claim", hooks.on_claim.as_ref())),
+        EventType::TaskStatusSet => {
+            matched.push(("on_status_change", hooks.on_status_change.as_ref()));
+            if event.payload.get("status").and_then(Value::as_str) == Some("closed") {
+                matched.push(("on_close", hooks.on_close.as_ref()));
+            }
+        }
+        _ => {}
+    }
+    matched
+        .into_iter()
+        .filter_map(|(name, hook)| hook.map(|hook| (name, hook)))
+        .filter(|(_, hook)| hook.mode == mode)
+        .collect()
+}
+
+fn run_hook(
+    repo_root: &str,
+    name: &'static str,
+    hook: &HookConfig,
+    event: &EventRecord,
+) -> Result<(), TsqError> {
+    let span = tracing::debug_span!(
+        "hooks.run",
+        hook = name,
+        task = %event.task_id,
+        ok = tracing::field::Empty
+    )
+    .entered();
+
+    let failure = |message: String, details: Value| {
+        TsqError::new(
+            "HOOK_FAILED",
+            format!(
+                "{} hook {} failed for {}: {}",
+                mode_label(hook.mode),
+                name,
+                event.task_id,
+                message
+            ),
+            1,
+        )
+        .with_details(serde_json::json!({
+            "hook": name,
+            "command": hook.command,
+            "mode": hook.mode,
+            "task_id": event.task_id,
+            "result": details,
+        }))
+    };
+
+    let payload = serde_json::to_string(event).map_err(|error| {
+        TsqError::new("INTERNAL_ERROR", "failed serializing hook payload", 2)
+            .with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+    let mut command = shell_command(&hook.command);
+    if hook.mode == HookMode::Blocking {
+        command.env(IN_BLOCKING_HOOK_ENV, "1");
+    }
+    let mut child = command
+        .current_dir(Path::new(repo_root))
+        .env("TSQ_HOOK", name)
+        .env("TSQ_REPO_ROOT", repo_root)
+        .env("TSQ_EVENT_TYPE", event_type_name(event))
+        .env("TSQ_TASK_ID", &event.task_id)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| {
+            failure(
+                format!("could not start: {}", error),
+                serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}),
+            )
+        })?;
+    // Fed from its own thread so a hook that fills stdout before reading a
+    // large payload cannot deadlock against `wait_with_output`.
+    let writer = child.stdin.take().map(|mut stdin| {
+        std::thread::spawn(move || {
+            // A hook that ignores stdin may exit before reading it; that is not a failure.
+            let _ = stdin.write_all(format!("{}\n", payload).as_bytes());
+        })
+    });
+    let output = child.wait_with_output().map_err(|error| {
+        failure(
+            format!("could not wait: {}", error),
+            serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}),
+        )
+    })?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    span.record("ok", output.status.success());
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = tail(&String::from_utf8_lossy(&output.stderr));
+    let exit = output
+        .status
+        .code()
+        .map(|code| format!("exit {}", code))
+        .unwrap_or_else(|| "terminated by signal".to_string());
+    let message = if stderr.trim().is_empty() {
+        exit
+    } else {
+        format!("{}: {}", exit, stderr.trim())
+    };
+    Err(failure(
+        message,
+        serde_json::json!({"exit_code": output.status.code(), "stderr": stderr}),
+    ))
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+fn event_type_name(event: &EventRecord) -> String {
+    serde_json::to_value(event.event_type)
+        .ok()
+        .and_then(|value| value.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+fn mode_label(mode: HookMode) -> &'static str {
+    match mode {
+        HookMode::Advisory => "advisory",
+        HookMode::Blocking => "blocking",
+    }
+}
+
+fn tail(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= STDERR_TAIL_CHARS {
+        return text.to_string();
+    }
+    text.chars().skip(count - STDERR_TAIL_CHARS).collect()
+}
@@ -0,0 +1,116 @@
+use crate::app::service_types::InitSeed;
+use crate::domain::labels::normalize_label;
+use crate::errors::TsqError;
+use crate::store::config::is_valid_policy;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+const STANDARD_PRESET: &str = include_str!("../../../../presets/standard.json");
+const FULL_PRESET: &str = include_str!("../../../../presets/full.json");
+
+/// The seed bundled for a preset name (`standard`, `full`); `minimal` and
+/// unknown names seed nothing.
+pub fn builtin_seed(preset: &str) -> Result<Option<InitSeed>, TsqError> {
+    let raw = match preset {
+        "standard" => STANDARD_PRESET,
+        "full" => FULL_PRESET,
+        _ => return Ok(None),
+    };
+    parse_seed(raw, preset).map(Some)
+}
+
+/// Reads a team-maintained preset definition (same shape as `presets/*.json`).
+pub fn load_seed_file(path: &str) -> Result<InitSeed, TsqError> {
+    let raw = fs::read_to_string(path).map_err(|error| {
+        TsqError::new(
+            "IO_ERROR",
+            format!("failed reading preset file {}", path),
+            2,
+        )
+        .with_details(serde_json::json!({
+            "kind": format!("{:?}", error.kind()),
+            "message": error.to_string(),
+        }))
+    })?;
+    parse_seed(&raw, path)
+}
+
+/// Parses and validates a preset definition; labels come back normalized.
+pub fn parse_seed(raw: &str, source: &str) -> Result<InitSeed, TsqError> {
+    let invalid = |message: String| {
+        TsqError::new("VALIDATION_ERROR", message, 1)
+            .with_details(serde_json::json!({ "preset": source }))
+    };
+    let seed: InitSeed = serde_json::from_str(raw)
+        .map_err(|error| invalid(format!("invalid preset definition: {}", error)))?;
+
+    let mut labels = BTreeMap::new();
+    for (label, color) in seed.labels {
+        labels.insert(normalize_label(&label)?, color);
+    }
+    let mut titles = HashSet::new();
+    for epic in &seed.epics {
+        if epic.title.trim().is_empty() {
+            return Err(invalid("preset epic titles must not be empty".to_string()));
+        }
+        if !titles.insert(epic.title.trim()) {
+            return Err(invalid(format!("duplicate preset epic '{}'", epic.title)));
+        }
+        if epic.priority > 3 {
+            return Err(invalid(format!(
+                "preset epic '{}' priority must be 0..3",
+                epic.title
+            )));
+        }
+    }
+    let mut names = HashSet::new();
+    for policy in &seed.policies {
+        if !is_valid_policy(policy) {
+            return Err(invalid(format!(
+                "preset policy '{}' needs a name, a status or planning_state trigger, and requirements",
+                policy.name
+            )));
+        }
+        if !names.insert(policy.name.as_str()) {
+            return Err(invalid(format!(
+                "duplicate preset policy '{}'",
+                policy.name
+            )));
+        }
+    }
+    Ok(InitSeed { labels, ..seed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_presets_parse_and_minimal_seeds_nothing() {
+        let standard = builtin_seed("standard").unwrap().unwrap();
+        assert!(standard.labels.contains_key("bug"));
+        assert_eq!(standard.epics[0].title, "Backlog");
+        assert_eq!(standard.policies[0].name, "feature-needs-spec");
+        let full = builtin_seed("full").unwrap().unwrap();
+        assert!(full.policies.len() > standard.policies.len());
+        assert!(builtin_seed("minimal").unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_seed_rejects_invalid_policies_and_duplicate_epics() {
+        let error = parse_seed(
+            r#"{"policies": [{"name": "p", "when": {"kind": "task"}, "require": ["spec"]}]}"#,
+            "team.json",
+        )
+        .unwrap_err();
+        assert_eq!(error.code, "VALIDATION_ERROR");
+        let error = parse_seed(
+            r#"{"epics": [{"title": "Backlog"}, {"title": "Backlog"}]}"#,
+            "team.json",
+        )
+        .unwrap_err();
+        assert_eq!(error.code, "VALIDATION_ERROR");
+        let seed = parse_seed(r#"{"labels": {" Bug ": "red"}}"#, "team.json").unwrap();
+        assert!(seed.labels.contains_key("bug"));
+    }
+}
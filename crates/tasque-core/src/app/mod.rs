@@ -1,6 +1,13 @@
+pub mod backup;
+pub mod doctor;
+pub mod hooks;
+pub mod init_preset;
+pub mod permissions;
 pub mod repair;
 pub mod runtime;
+pub mod selftest;
 pub mod service;
+pub mod service_async;
 pub mod service_lifecycle;
 pub mod service_query;
 pub mod service_types;
@@ -9,6 +16,10 @@ pub mod state;
 pub mod stdin;
 pub mod storage;
 pub mod sync;
+pub mod sync_daemon;
+pub mod transaction;
+pub mod workflow;
 
 pub use service::TasqueService;
+pub use service_async::AsyncTasqueService;
 pub use service_types::*;
@@ -0,0 +1,109 @@
+use crate::app::service_types::ServiceContext;
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::types::{ActorRole, RolesConfig};
+
+/// Role granted to `actor`; the highest list naming the actor wins.
+pub fn role_for(roles: &RolesConfig, actor: &str) -> ActorRole {
+    let listed = |names: &[String]| names.iter().any(|name| name == actor);
+    if listed(&roles.admins) {
+        ActorRole::Admin
+    } else if listed(&roles.writers) {
+        ActorRole::Writer
+    } else if listed(&roles.readers) {
+        ActorRole::Reader
+    } else {
+        roles.default
+    }
+}
+
+/// The caller's role, or `None` when config.json has no `roles` block and
+/// every actor is unrestricted.
+pub fn actor_role(ctx: &ServiceContext) -> Result<Option<ActorRole>, TsqError> {
+    Ok(read_config(&ctx.repo_root)?
+        .roles
+        .map(|roles| role_for(&roles, &ctx.actor)))
+}
+
+pub fn can_write(ctx: &ServiceContext) -> Result<bool, TsqError> {
+    Ok(actor_role(ctx)?.is_none_or(|role| role >= ActorRole::Writer))
+}
+
+/// Fails with PERMISSION_DENIED unless the caller holds at least `required`.
+pub fn require_role(
+    ctx: &ServiceContext,
+    required: ActorRole,
+    action: &str,
+) -> Result<(), TsqError> {
+    let Some(role) = actor_role(ctx)? else {
+        return Ok(());
+    };
+    if role >= required {
+        return Ok(());
+    }
+    Err(TsqError::new(
+        "PERMISSION_DENIED",
+        format!(
+            "{} requires the {} role; actor {} is a {}",
+            action,
+            role_name(required),
+            ctx.actor,
+            role_name(role)
+        ),
+        1,
+    )
+    .with_details(serde_json::json!({
+        "actor": ctx.actor,
+        "role": role,
+        "required": required,
+        "action": action,
+    })))
+}
+
+/// With `strict_actors` on, fails with UNKNOWN_ACTOR unless `name` is
+/// registered in the actor directory.
+pub fn ensure_known_actor(ctx: &ServiceContext, name: &str) -> Result<(), TsqError> {
+    let config = read_config(&ctx.repo_root)?;
+    if !config.strict_actors || config.actors.contains_key(name) {
+        return Ok(());
+    }
+    Err(TsqError::new(
+        "UNKNOWN_ACTOR",
+        format!(
+            "actor {} is not registered; add it with tsq actor add",
+            name
+        ),
+        1,
+    )
+    .with_details(serde_json::json!({
+        "actor": name,
+        "known": config.actors.keys().collect::<Vec<_>>(),
+    })))
+}
+
+fn role_name(role: ActorRole) -> &'static str {
+    match role {
+        ActorRole::Reader => "reader",
+        ActorRole::Writer => "writer",
+        ActorRole::Admin => "admin",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highest_listed_role_wins_and_unlisted_actors_get_default() {
+        let roles = RolesConfig {
+            admins: vec!["ana".to_string()],
+            writers: vec!["ana".to_string(), "wes".to_string()],
+            readers: vec!["rui".to_string()],
+            default: ActorRole::Reader,
+        };
+        assert_eq!(role_for(&roles, "ana"), ActorRole::Admin);
+        assert_eq!(role_for(&roles, "wes"), ActorRole::Writer);
+        assert_eq!(role_for(&roles, "rui"), ActorRole::Reader);
+        assert_eq!(role_for(&roles, "zed"), ActorRole::Reader);
+    }
+}
@@ -1,13 +1,24 @@
 use crate::app::state::{load_projected_state, persist_projection};
 use crate::domain::deps::normalize_dependency_edges;
 use crate::domain::events::make_event;
-use crate::domain::projector::apply_events;
+use crate::domain::projector::{apply_event, apply_events};
+use crate::domain::state::create_empty_state;
 use crate::errors::TsqError;
-use crate::store::events::append_events;
+use crate::store::config::read_config;
+use crate::store::events::{
+    RejectedLine, append_events, append_quarantine, event_log_prefix, read_events,
+    rewrite_event_lines, salvage_event_lines,
+};
 use crate::store::lock::{force_remove_lock, lock_exists, with_write_lock};
 use crate::store::paths::get_paths;
-use crate::store::snapshots::SNAPSHOT_RETAIN_COUNT;
-use crate::types::{EventRecord, RepairDep, RepairLink, RepairPlan, RepairResult, State};
+use crate::store::snapshots::{
+    SNAPSHOT_RETAIN_COUNT, is_snapshot_file, list_snapshots, load_latest_snapshot,
+    remove_snapshots, write_snapshot,
+};
+use crate::types::{
+    EventRecord, QuarantinedEvent, RepairDep, RepairLink, RepairPlan, RepairResult,
+    STATE_CACHE_SCHEMA_VERSION, SalvageReport, Snapshot, SnapshotRebuild, State,
+};
 use serde_json::{Map, Value};
 use std::fs::read_dir;
 use std::path::Path;
@@ -28,6 +39,8 @@ pub struct DirectTaskRefIssue {
 pub struct RepairOptions {
     pub fix: bool,
     pub force_unlock: bool,
+    pub salvage: bool,
+    pub rebuild_snapshots: bool,
 }
 
 pub fn scan_orphaned_graph(state: &State) -> OrphanedGraph {
@@ -169,7 +182,7 @@ fn scan_filesystem(
         let mut snapshots: Vec<String> = entries
             .flatten()
             .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
-            .filter(|name| name.ends_with(".json"))
+            .filter(|name| is_snapshot_file(name))
             .collect();
         snapshots.sort();
         if snapshots.len() > SNAPSHOT_RETAIN_COUNT {
@@ -180,6 +193,160 @@ fn scan_filesystem(
     Ok((stale_temps, stale_lock, old_snapshots))
 }
 
+/// Event-log lines that survive decoding, validation, and replay, and the
+/// projection rebuilt from them.
+struct Salvaged {
+    state: State,
+    kept: Vec<String>,
+    rejected: Vec<RejectedLine>,
+}
+
+fn salvage_log(repo_root: &Path) -> Result<Salvaged, TsqError> {
+    let (lines, mut rejected) = salvage_event_lines(repo_root)?;
+    let events: Vec<EventRecord> = lines.iter().map(|line| line.event.clone()).collect();
+    let (state, kept) = match apply_events(&create_empty_state(), &events) {
+        Ok(state) => (state, lines),
+        Err(_) => {
+            let mut state = create_empty_state();
+            let mut kept = Vec::new();
+            for line in lines {
+                match apply_event(&state, &line.event) {
+                    Ok(next) => {
+                        state = next;
+                        kept.push(line);
+                    }
+                    Err(error) => rejected.push(RejectedLine {
+                        line: line.line,
+                        raw: line.raw,
+                        reason: error.message,
+                        value: serde_json::to_value(&line.event).ok(),
+                    }),
+                }
+            }
+            (state, kept)
+        }
+    };
+    rejected.sort_by_key(|line| line.line);
+    Ok(Salvaged {
+        state,
+        kept: kept.into_iter().map(|line| line.raw).collect(),
+        rejected,
+    })
+}
+
+fn salvage_report(salvaged: &Salvaged) -> SalvageReport {
+    let field = |value: &Option<Value>, key: &str| {
+        value
+            .as_ref()
+            .and_then(|value| value.get(key))
+            .and_then(Value::as_str)
+            .map(String::from)
+    };
+    SalvageReport {
+        kept_events: salvaged.kept.len(),
+        quarantined: salvaged
+            .rejected
+            .iter()
+            .map(|rejected| QuarantinedEvent {
+                line: rejected.line,
+                reason: rejected.reason.clone(),
+                event_id: field(&rejected.value, "id")
+                    .or_else(|| field(&rejected.value, "event_id")),
+                event_type: field(&rejected.value, "type"),
+                task_id: field(&rejected.value, "task_id"),
+            })
+            .collect(),
+        quarantine_file: ".tasque/quarantine.jsonl".to_string(),
+    }
+}
+
+/// Moves rejected lines into the quarantine file, rewrites the log with the
+/// kept lines, and persists the rebuilt projection.
+fn apply_salvage(repo_root: &Path, salvaged: &mut Salvaged, now: &str) -> Result<(), TsqError> {
+    if !salvaged.rejected.is_empty() {
+        let entries: Vec<Value> = salvaged
+            .rejected
+            .iter()
+            .map(|rejected| {
+                serde_json::json!({
+                  "line": rejected.line,
+                  "reason": rejected.reason,
+                  "raw": rejected.raw,
+                  "quarantined_at": now,
+                })
+            })
+            .collect();
+        append_quarantine(repo_root, &entries)?;
+        rewrite_event_lines(repo_root, &salvaged.kept)?;
+    }
+    persist_projection(repo_root, &mut salvaged.state, salvaged.kept.len(), None)
+}
+
+/// The event count of the newest snapshot the log would have produced.
+fn snapshot_checkpoint(repo_root: &Path, event_count: usize) -> Result<Option<usize>, TsqError> {
+    let every = read_config(repo_root)?.snapshot_every;
+    if every == 0 {
+        return Ok(None);
+    }
+    let checkpoint = event_count / every * every;
+    Ok((checkpoint > 0).then_some(checkpoint))
+}
+
+/// Replaces every snapshot with one regenerated from the event log, then
+/// reloads it and checks that snapshot plus tail equals a full replay.
+fn rebuild_snapshots(repo_root: &Path, now: &str) -> Result<SnapshotRebuild, TsqError> {
+    let events = read_events(repo_root)?.events;
+    let full = apply_events(&create_empty_state(), &events)?;
+    let removed = remove_snapshots(repo_root)?;
+    let Some(checkpoint) = snapshot_checkpoint(repo_root, events.len())? else {
+        return Ok(SnapshotRebuild {
+            removed,
+            rebuilt_at_event: None,
+            verified: true,
+        });
+    };
+
+    let event_log = event_log_prefix(repo_root, checkpoint)?.ok_or_else(|| {
+        TsqError::new(
+            "SNAPSHOT_WRITE_FAILED",
+            "Event log changed while rebuilding snapshots",
+            2,
+        )
+    })?;
+    write_snapshot(
+        repo_root,
+        &Snapshot {
+            taken_at: now.to_string(),
+            event_count: checkpoint,
+            projection_version: STATE_CACHE_SCHEMA_VERSION,
+            event_log: Some(event_log),
+            state: apply_events(&create_empty_state(), &events[..checkpoint])?,
+        },
+    )?;
+
+    let verified = match load_latest_snapshot(repo_root)? {
+        Some(snapshot) if snapshot.event_count == checkpoint => {
+            apply_events(&snapshot.state, &events[checkpoint..])? == full
+        }
+        _ => false,
+    };
+    if !verified {
+        let _ = remove_snapshots(repo_root);
+        return Err(TsqError::new(
+            "SNAPSHOT_REBUILD_MISMATCH",
+            "Rebuilt snapshot does not match a full replay of the event log",
+            2,
+        )
+        .with_details(serde_json::json!({ "event_count": checkpoint })));
+    }
+
+    Ok(SnapshotRebuild {
+        removed,
+        rebuilt_at_event: Some(checkpoint),
+        verified,
+    })
+}
+
 pub fn execute_repair(
     repo_root: impl AsRef<Path>,
     actor: &str,
@@ -197,8 +364,24 @@ pub fn execute_repair(
     }
 
     if !opts.fix {
-        let loaded = load_projected_state(&repo_root)?;
-        let graph = scan_orphaned_graph(&loaded.state);
+        let (state, event_count, salvage) = if opts.salvage {
+            let salvaged = salvage_log(&repo_root)?;
+            let report = salvage_report(&salvaged);
+            (salvaged.state, salvaged.kept.len(), Some(report))
+        } else {
+            let loaded = load_projected_state(&repo_root)?;
+            (loaded.state, loaded.event_count, None)
+        };
+        let snapshots = if opts.rebuild_snapshots {
+            Some(SnapshotRebuild {
+                removed: list_snapshots(&get_paths(&repo_root).snapshots_dir)?,
+                rebuilt_at_event: snapshot_checkpoint(&repo_root, event_count)?,
+                verified: false,
+            })
+        } else {
+            None
+        };
+        let graph = scan_orphaned_graph(&state);
         let (stale_temps, stale_lock, old_snapshots) = scan_filesystem(&repo_root)?;
         let plan = RepairPlan {
             orphaned_deps: graph.orphaned_deps,
@@ -212,6 +395,8 @@ pub fn execute_repair(
             applied: false,
             events_appended: 0,
             files_removed: 0,
+            salvage,
+            snapshots,
         });
     }
 
@@ -220,8 +405,16 @@ pub fn execute_repair(
     }
 
     with_write_lock(&repo_root, || {
-        let loaded = load_projected_state(&repo_root)?;
-        let graph = scan_orphaned_graph(&loaded.state);
+        let (state, event_count, salvage) = if opts.salvage {
+            let mut salvaged = salvage_log(&repo_root)?;
+            apply_salvage(&repo_root, &mut salvaged, &now())?;
+            let report = salvage_report(&salvaged);
+            (salvaged.state, salvaged.kept.len(), Some(report))
+        } else {
+            let loaded = load_projected_state(&repo_root)?;
+            (loaded.state, loaded.event_count, None)
+        };
+        let graph = scan_orphaned_graph(&state);
         let (stale_temps, stale_lock, old_snapshots) = scan_filesystem(&repo_root)?;
         let plan = RepairPlan {
             orphaned_deps: graph.orphaned_deps,
@@ -260,12 +453,12 @@ pub fn execute_repair(
         }
 
         if !events.is_empty() {
-            let mut next_state = apply_events(&loaded.state, &events)?;
+            let mut next_state = apply_events(&state, &events)?;
             append_events(&repo_root, &events)?;
             persist_projection(
                 &repo_root,
                 &mut next_state,
-                loaded.event_count + events.len(),
+                event_count + events.len(),
                 None,
             )?;
         }
@@ -285,11 +478,19 @@ pub fn execute_repair(
             }
         }
 
+        let snapshots = if opts.rebuild_snapshots {
+            Some(rebuild_snapshots(&repo_root, &now())?)
+        } else {
+            None
+        };
+
         Ok(RepairResult {
             plan,
             applied: true,
             events_appended: events.len(),
             files_removed,
+            salvage,
+            snapshots,
         })
     })
 }
@@ -0,0 +1,159 @@
+use crate::app::service_types::{ActorCandidate, ActorResolution};
+use crate::errors::TsqError;
+use crate::store::config::read_actor_resolution;
+use crate::types::{ActorResolutionConfig, ActorSource, Priority, TaskStatus};
+use chrono::{SecondsFormat, Utc};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const DEFAULT_SNAPSHOT_EVERY: usize = 100;
+
+pub fn now_iso() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+pub fn find_tasque_root() -> Option<PathBuf> {
+    let mut dir = std::env::current_dir().ok()?;
+    loop {
+        if dir.join(".tasque").exists() {
+            return Some(dir);
+        }
+        let parent = dir.parent()?.to_path_buf();
+        if parent == dir {
+            return None;
+        }
+        dir = parent;
+    }
+}
+
+pub fn get_repo_root() -> PathBuf {
+    find_tasque_root()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
+pub fn get_actor(repo_root: impl AsRef<Path>) -> String {
+    let repo_root = repo_root.as_ref();
+    let resolution = read_actor_resolution(repo_root);
+    actor_order(resolution.as_ref())
+        .iter()
+        .find_map(|source| actor_source_value(repo_root, *source, resolution.as_ref()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Walks the whole `actor_resolution` chain, unlike [`get_actor`], which
+/// stops at the first source with a value.
+pub fn resolve_actor(repo_root: impl AsRef<Path>) -> ActorResolution {
+    let repo_root = repo_root.as_ref();
+    let resolution = read_actor_resolution(repo_root);
+    let candidates: Vec<ActorCandidate> = actor_order(resolution.as_ref())
+        .iter()
+        .map(|source| ActorCandidate {
+            source: *source,
+            value: actor_source_value(repo_root, *source, resolution.as_ref()),
+        })
+        .collect();
+    let winner = candidates
+        .iter()
+        .find_map(|candidate| Some((candidate.source, candidate.value.clone()?)));
+    ActorResolution {
+        actor: winner
+            .as_ref()
+            .map_or_else(|| "unknown".to_string(), |(_, actor)| actor.clone()),
+        source: winner.map(|(source, _)| source),
+        candidates,
+        identity: None,
+    }
+}
+
+fn actor_order(resolution: Option<&ActorResolutionConfig>) -> &[ActorSource] {
+    resolution.map_or(&ActorSource::DEFAULT_ORDER[..], |resolution| {
+        &resolution.order[..]
+    })
+}
+
+fn actor_source_value(
+    repo_root: &Path,
+    source: ActorSource,
+    resolution: Option<&ActorResolutionConfig>,
+) -> Option<String> {
+    match source {
+        ActorSource::Env => env_value("TSQ_ACTOR"),
+        ActorSource::GitEmail => read_git_user(repo_root, "user.email"),
+        ActorSource::GitName => read_git_user(repo_root, "user.name"),
+        ActorSource::OsUser => env_value("USERNAME").or_else(|| env_value("USER")),
+        ActorSource::Config => resolution.and_then(|resolution| resolution.actor.clone()),
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    let value = std::env::var(name).ok()?;
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+pub fn parse_priority(raw: &str) -> Result<Priority, TsqError> {
+    let value = raw
+        .parse::<u8>()
+        .map_err(|_| TsqError::new("VALIDATION_ERROR", "priority must be one of: 0, 1, 2, 3", 1))?;
+    if matches!(value, 0..=3) {
+        return Ok(value);
+    }
+    Err(TsqError::new(
+        "VALIDATION_ERROR",
+        "priority must be one of: 0, 1, 2, 3",
+        1,
+    ))
+}
+
+/// Accepts the builtin statuses, the `todo`/`done` aliases, and any valid
+/// custom name; whether a custom status is configured is checked on write.
+pub fn normalize_status(raw: &str) -> Result<TaskStatus, TsqError> {
+    let normalized = match raw {
+        "done" => "closed",
+        "todo" => "open",
+        _ => raw,
+    };
+    TaskStatus::from_name(normalized).ok_or_else(|| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            "status must be one of: open, todo, in_progress, blocked, closed, done, canceled, deferred",
+            1,
+        )
+    })
+}
+
+/// Commit time of a git tag or ref as an RFC 3339 UTC timestamp, or `None`
+/// when the ref does not resolve.
+pub fn git_ref_timestamp(repo_root: impl AsRef<Path>, reference: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%cI"])
+        .arg(format!("{}^{{commit}}", reference))
+        .arg("--")
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    chrono::DateTime::parse_from_rfc3339(&value)
+        .ok()
+        .map(|ts| ts.to_utc().to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+fn read_git_user(repo_root: &Path, key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", key])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if value.is_empty() { None } else { Some(value) }
+}
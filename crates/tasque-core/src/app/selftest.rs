@@ -0,0 +1,393 @@
+use crate::domain::projector::apply_events;
+use crate::domain::state::create_empty_state;
+use crate::domain::state_invariants::validate_projected_state;
+use crate::domain::task_index::{build_task_indexes, reindex_state};
+use crate::errors::TsqError;
+use crate::store::merge_driver::interleave_sources;
+use crate::types::{EventRecord, EventType, State, TaskStatus};
+use chrono::{DateTime, Duration, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+const LABELS: [&str; 3] = ["bug", "ui", "infra"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelftestInput {
+    /// Case `n` replays the log generated from `seed + n`; `None` picks a
+    /// seed from the clock.
+    pub seed: Option<u64>,
+    pub cases: usize,
+    pub events: usize,
+}
+
+/// The first broken invariant of one generated log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelftestViolation {
+    pub invariant: String,
+    pub message: String,
+}
+
+/// A failed case; `tsq selftest --seed <seed> --cases 1` replays it alone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelftestFailure {
+    pub case: usize,
+    pub seed: u64,
+    pub invariant: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelftestResult {
+    pub seed: u64,
+    pub cases: usize,
+    pub events: usize,
+    pub failures: Vec<SelftestFailure>,
+}
+
+/// Generates `input.cases` random valid event logs and checks each one with
+/// [`check_events`].
+pub fn run_selftest(input: &SelftestInput) -> SelftestResult {
+    let seed = input.seed.unwrap_or_else(clock_seed);
+    let mut failures = Vec::new();
+    for case in 0..input.cases {
+        let case_seed = seed.wrapping_add(case as u64);
+        let mut rng = SplitMix64(case_seed);
+        let events = generate_events(&mut |bound| rng.below(bound), input.events);
+        if let Err(violation) = check_events(&events) {
+            failures.push(SelftestFailure {
+                case,
+                seed: case_seed,
+                invariant: violation.invariant,
+                message: violation.message,
+            });
+        }
+    }
+    SelftestResult {
+        seed,
+        cases: input.cases,
+        events: input.events,
+        failures,
+    }
+}
+
+/// Builds a log of `count` events that the projector must accept: tasks
+/// (some nested under older tasks), status changes, claims, notes, label and
+/// priority edits, and deps that always point from a newer task to an older
+/// one so they never form a cycle. `choose(n)` picks a value below `n`.
+pub fn generate_events(choose: &mut dyn FnMut(usize) -> usize, count: usize) -> Vec<EventRecord> {
+    let mut log = GeneratedLog::default();
+    let mut pick = |bound: usize| choose(bound) % bound;
+    while log.events.len() < count {
+        if log.tasks.is_empty() {
+            log.create(None, pick(4));
+            continue;
+        }
+        let task = pick(log.tasks.len());
+        match pick(8) {
+            0 | 1 => {
+                let parent = (pick(3) == 0).then_some(task);
+                log.create(parent, pick(4));
+            }
+            2 => {
+                let status = TaskStatus::BUILTIN[pick(TaskStatus::BUILTIN.len())].clone();
+                log.set_status(task, status);
+            }
+            3 => log.claim(task),
+            4 => log.note(task),
+            5 => {
+                let labels = LABELS
+                    .iter()
+                    .filter(|_| pick(2) == 0)
+                    .map(|label| Value::from(*label))
+                    .collect::<Vec<_>>();
+                log.update(task, labels, pick(4));
+            }
+            6 => log.add_dep(task, pick(log.tasks.len())),
+            _ if !log.deps.is_empty() => log.remove_dep(pick(log.deps.len())),
+            _ => log.note(task),
+        }
+    }
+    log.events
+}
+
+/// Replays `events` and checks the projector invariants: a valid projected
+/// state, incremental indexes equal to a rebuild, one applied event per
+/// record, `closed_at` on every closed task, child counters covering every
+/// child id, a log merged with copies of itself projecting the same state,
+/// and snapshot + tail replay matching a full replay.
+pub fn check_events(events: &[EventRecord]) -> Result<(), SelftestViolation> {
+    let full = replay("replay", &create_empty_state(), events)?;
+    validate_projected_state(&full).map_err(|error| violation("projection", error.message))?;
+    if build_task_indexes(full.tasks.values()) != full.indexes {
+        return Err(violation(
+            "indexes",
+            "task indexes do not match the projected tasks",
+        ));
+    }
+    if full.applied_events != events.len() {
+        return Err(violation(
+            "applied_events",
+            format!(
+                "applied_events is {} after {} events",
+                full.applied_events,
+                events.len()
+            ),
+        ));
+    }
+    check_closed_at(&full)?;
+    check_child_counters(&full)?;
+    check_dedupe(events, &full)?;
+    check_snapshot_replay(events, &full)
+}
+
+fn check_closed_at(state: &State) -> Result<(), SelftestViolation> {
+    match state
+        .tasks
+        .values()
+        .find(|task| task.status == TaskStatus::Closed && task.closed_at.is_none())
+    {
+        Some(task) => Err(violation(
+            "closed_at",
+            format!("closed task {} has no closed_at", task.id),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn check_child_counters(state: &State) -> Result<(), SelftestViolation> {
+    for task in state.tasks.values() {
+        let Some(parent) = task.parent_id.as_deref() else {
+            continue;
+        };
+        let Some(child) = task
+            .id
+            .strip_prefix(parent)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .and_then(|segment| segment.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let counter = state.child_counters.get(parent).copied().unwrap_or(0);
+        if counter < child {
+            return Err(violation(
+                "child_counters",
+                format!(
+                    "child counter {} for {} is behind child {}",
+                    counter, parent, task.id
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Merging a log with itself and with its own prefix, as the merge driver
+/// does for clones that already share history, must keep every event once.
+fn check_dedupe(events: &[EventRecord], full: &State) -> Result<(), SelftestViolation> {
+    let prefix = events[..events.len() / 2].to_vec();
+    let merged = interleave_sources(&[events.to_vec(), prefix, events.to_vec()]);
+    if merged != events {
+        return Err(violation(
+            "dedupe",
+            format!(
+                "merging the log with itself kept {} of {} events",
+                merged.len(),
+                events.len()
+            ),
+        ));
+    }
+    let deduped = replay("dedupe", &create_empty_state(), &merged)?;
+    if deduped != *full {
+        return Err(violation(
+            "dedupe",
+            "the merged log projects a different state",
+        ));
+    }
+    Ok(())
+}
+
+/// Restores a serialized prefix state the way a snapshot load does, then
+/// replays the tail.
+fn check_snapshot_replay(events: &[EventRecord], full: &State) -> Result<(), SelftestViolation> {
+    for split in [events.len() / 4, events.len() / 2, events.len() * 3 / 4] {
+        let prefix = replay("snapshot_replay", &create_empty_state(), &events[..split])?;
+        let mut restored = serde_json::to_string(&prefix)
+            .and_then(|raw| serde_json::from_str::<State>(&raw))
+            .map_err(|error| violation("snapshot_replay", error.to_string()))?;
+        reindex_state(&mut restored);
+        let resumed = replay("snapshot_replay", &restored, &events[split..])?;
+        if resumed != *full {
+            return Err(violation(
+                "snapshot_replay",
+                format!(
+                    "snapshot at event {} plus tail replay differs from full replay",
+                    split
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn replay(
+    invariant: &str,
+    base: &State,
+    events: &[EventRecord],
+) -> Result<State, SelftestViolation> {
+    apply_events(base, events).map_err(|error: TsqError| {
+        violation(invariant, format!("{}: {}", error.code, error.message))
+    })
+}
+
+fn violation(invariant: &str, message: impl Into<String>) -> SelftestViolation {
+    SelftestViolation {
+        invariant: invariant.to_string(),
+        message: message.into(),
+    }
+}
+
+fn clock_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        ((z ^ (z >> 31)) % bound.max(1) as u64) as usize
+    }
+}
+
+struct GeneratedTask {
+    id: String,
+    status: TaskStatus,
+    children: u32,
+}
+
+#[derive(Default)]
+struct GeneratedLog {
+    tasks: Vec<GeneratedTask>,
+    /// `(child, blocker)` task indexes; the child is always the newer task.
+    deps: Vec<(usize, usize)>,
+    events: Vec<EventRecord>,
+}
+
+impl GeneratedLog {
+    fn create(&mut self, parent: Option<usize>, priority: usize) {
+        let mut payload = Map::new();
+        let id = match parent {
+            Some(parent) => {
+                let parent = &mut self.tasks[parent];
+                parent.children += 1;
+                payload.insert("parent_id".to_string(), Value::from(parent.id.clone()));
+                format!("{}.{}", parent.id, parent.children)
+            }
+            None => format!("tsq-st{}", self.tasks.len() + 1),
+        };
+        payload.insert("title".to_string(), Value::from(format!("Task {}", id)));
+        payload.insert("priority".to_string(), Value::from(priority));
+        self.push(EventType::TaskCreated, &id, payload);
+        self.tasks.push(GeneratedTask {
+            id,
+            status: TaskStatus::Open,
+            children: 0,
+        });
+    }
+
+    fn set_status(&mut self, task: usize, status: TaskStatus) {
+        let current = &self.tasks[task].status;
+        // The projector refuses to restart closed or canceled work.
+        let status = if status == TaskStatus::InProgress && is_terminal(current) {
+            TaskStatus::Open
+        } else {
+            status
+        };
+        let mut payload = Map::new();
+        payload.insert("status".to_string(), Value::from(status.as_str()));
+        let id = self.tasks[task].id.clone();
+        self.push(EventType::TaskStatusSet, &id, payload);
+        self.tasks[task].status = status;
+    }
+
+    fn claim(&mut self, task: usize) {
+        if is_terminal(&self.tasks[task].status) {
+            return self.note(task);
+        }
+        let id = self.tasks[task].id.clone();
+        self.push(EventType::TaskClaimed, &id, Map::new());
+        if self.tasks[task].status == TaskStatus::Open {
+            self.tasks[task].status = TaskStatus::InProgress;
+        }
+    }
+
+    fn note(&mut self, task: usize) {
+        let mut payload = Map::new();
+        payload.insert(
+            "text".to_string(),
+            Value::from(format!("note {}", self.events.len())),
+        );
+        let id = self.tasks[task].id.clone();
+        self.push(EventType::TaskNoted, &id, payload);
+    }
+
+    fn update(&mut self, task: usize, labels: Vec<Value>, priority: usize) {
+        let mut payload = Map::new();
+        payload.insert("labels".to_string(), Value::Array(labels));
+        payload.insert("priority".to_string(), Value::from(priority));
+        let id = self.tasks[task].id.clone();
+        self.push(EventType::TaskUpdated, &id, payload);
+    }
+
+    fn add_dep(&mut self, first: usize, second: usize) {
+        if first == second {
+            return self.note(first);
+        }
+        let (child, blocker) = (first.max(second), first.min(second));
+        let mut payload = Map::new();
+        payload.insert(
+            "blocker".to_string(),
+            Value::from(self.tasks[blocker].id.clone()),
+        );
+        let id = self.tasks[child].id.clone();
+        self.push(EventType::DepAdded, &id, payload);
+        if !self.deps.contains(&(child, blocker)) {
+            self.deps.push((child, blocker));
+        }
+    }
+
+    fn remove_dep(&mut self, dep: usize) {
+        let (child, blocker) = self.deps.swap_remove(dep);
+        let mut payload = Map::new();
+        payload.insert(
+            "blocker".to_string(),
+            Value::from(self.tasks[blocker].id.clone()),
+        );
+        let id = self.tasks[child].id.clone();
+        self.push(EventType::DepRemoved, &id, payload);
+    }
+
+    fn push(&mut self, event_type: EventType, task_id: &str, payload: Map<String, Value>) {
+        let index = self.events.len();
+        let ts = DateTime::UNIX_EPOCH + Duration::milliseconds(index as i64);
+        self.events.push(EventRecord {
+            id: Some(format!("01SELFTEST{:016}", index)),
+            event_id: None,
+            ts: ts.to_rfc3339_opts(SecondsFormat::Millis, true),
+            actor: "selftest".to_string(),
+            event_type,
+            task_id: task_id.to_string(),
+            payload,
+        });
+    }
+}
+
+fn is_terminal(status: &TaskStatus) -> bool {
+    matches!(status, TaskStatus::Closed | TaskStatus::Canceled)
+}
@@ -0,0 +1,871 @@
+#[path = "service_actors.rs"]
+mod service_actors;
+#[path = "service_batch.rs"]
+mod service_batch;
+#[path = "service_checklist.rs"]
+mod service_checklist;
+#[path = "service_clone.rs"]
+mod service_clone;
+#[path = "service_create_update.rs"]
+mod service_create_update;
+#[path = "service_criteria.rs"]
+mod service_criteria;
+#[path = "service_labels.rs"]
+mod service_labels;
+#[path = "service_notes.rs"]
+mod service_notes;
+#[path = "service_order.rs"]
+mod service_order;
+#[path = "service_specs.rs"]
+mod service_specs;
+#[path = "service_stale.rs"]
+mod service_stale;
+
+use crate::app::doctor::skill_findings;
+use crate::app::permissions::require_role;
+use crate::app::repair::{RepairOptions, execute_repair};
+use crate::app::service_types::*;
+use crate::app::service_utils::must_resolve_existing;
+use crate::app::storage::{
+    ensure_events_file, ensure_tasque_gitignore, load_projected_state, write_default_config,
+};
+use crate::app::sync::DEFAULT_SYNC_BRANCH;
+use crate::app::transaction::{StagedHandle, StagedTransaction};
+use crate::domain::dep_tree::{DepGraph, build_dep_tree, flatten_dep_tree};
+use crate::skills::types::{SkillAction, SkillDiffEntry, SkillListEntry, SkillStatusEntry};
+use crate::skills::{apply_skill_operation, list_skills, skill_diff, skill_status};
+use crate::store::config::{read_config, write_config};
+use crate::store::git;
+use crate::store::paths::get_paths;
+use crate::types::{
+    ActorRole, DependencyType, LabelColor, RelationType, RepairResult, SkillRegistry, Task,
+    TaskKind, TaskTreeNode,
+};
+use crate::{app::service_lifecycle, app::service_query, errors::TsqError};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Arc;
+
+pub use crate::app::service_query::ShowResult;
+
+const DEFAULT_SKILL_TARGETS: [SkillTarget; 7] = [
+    SkillTarget::Claude,
+    SkillTarget::Codex,
+    SkillTarget::Copilot,
+    SkillTarget::Opencode,
+    SkillTarget::Cursor,
+    SkillTarget::Windsurf,
+    SkillTarget::Gemini,
+];
+
+pub struct TasqueService {
+    ctx: ServiceContext,
+}
+
+impl TasqueService {
+    pub fn new(
+        repo_root: impl Into<String>,
+        actor: impl Into<String>,
+        now: impl Fn() -> String + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            ctx: ServiceContext {
+                repo_root: repo_root.into(),
+                actor: actor.into(),
+                now: Arc::new(now),
+                staged: None,
+                as_of: None,
+            },
+        }
+    }
+
+    pub fn init(&self, input: InitInput) -> Result<InitResult, TsqError> {
+        if input.install_skill && input.uninstall_skill {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "cannot combine --install-skill with --uninstall-skill",
+                1,
+            ));
+        }
+        if input.no_sync_branch && input.sync_branch.is_some() {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "cannot combine --sync-branch with --no-sync-branch",
+                1,
+            ));
+        }
+
+        if (input.skill_source.is_some() || input.skill_checksum.is_some()) && !input.install_skill
+        {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "--skill-source and --skill-checksum require --install-skill",
+                1,
+            ));
+        }
+        if input.dry_run && !(input.install_skill || input.uninstall_skill) {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "tsq init supports --dry-run only with --install-skill or --uninstall-skill",
+                1,
+            ));
+        }
+        if input.dry_run {
+            return self.init_skill_dry_run(&input);
+        }
+
+        write_default_config(&self.ctx.repo_root)?;
+        ensure_events_file(&self.ctx.repo_root)?;
+        fs::create_dir_all(format!("{}/.tasque/snapshots", self.ctx.repo_root)).map_err(|e| {
+            TsqError::new("IO_ERROR", "failed to initialize .tasque/snapshots", 2)
+                .with_details(io_error_value(&e))
+        })?;
+        ensure_tasque_gitignore(&self.ctx.repo_root)?;
+
+        let files = vec![
+            ".tasque/config.json".to_string(),
+            ".tasque/events.jsonl".to_string(),
+            ".tasque/.gitignore".to_string(),
+        ];
+
+        let skill_action = if input.install_skill {
+            Some(SkillAction::Install)
+        } else if input.uninstall_skill {
+            Some(SkillAction::Uninstall)
+        } else {
+            None
+        };
+
+        let default_sync_branch = if input.sync_branch.is_none()
+            && !input.no_sync_branch
+            && skill_action.is_none()
+            && should_default_to_sync_branch(&self.ctx.repo_root)
+        {
+            Some(DEFAULT_SYNC_BRANCH.to_string())
+        } else {
+            None
+        };
+        let sync_branch = input.sync_branch.as_ref().or(default_sync_branch.as_ref());
+        let sync_setup = if let Some(branch) = sync_branch {
+            Some(crate::app::sync::setup_sync_branch(
+                &self.ctx.repo_root,
+                branch,
+                &self.ctx.actor,
+            )?)
+        } else {
+            None
+        };
+
+        let seeded = input
+            .seed
+            .as_ref()
+            .map(|seed| self.apply_init_seed(seed))
+            .transpose()?;
+
+        if let Some(action) = skill_action {
+            let skill_operation =
+                apply_skill_operation(init_skill_options(action, &input, self.skill_registries()))?;
+            return Ok(InitResult {
+                initialized: true,
+                files,
+                skill_operation: Some(skill_operation),
+                sync_setup,
+                seeded,
+            });
+        }
+
+        Ok(InitResult {
+            initialized: true,
+            files,
+            skill_operation: None,
+            sync_setup,
+            seeded,
+        })
+    }
+
+    /// Merges preset labels and policies into config without replacing ones
+    /// already set, and creates each epic unless one with that title exists,
+    /// so re-running init with the same preset changes nothing.
+    fn apply_init_seed(&self, seed: &InitSeed) -> Result<InitSeedResult, TsqError> {
+        let mut result = InitSeedResult::default();
+        let mut config = read_config(&self.ctx.repo_root)?;
+        for (label, color) in &seed.labels {
+            if !config.label_colors.contains_key(label) {
+                config.label_colors.insert(label.clone(), *color);
+                result.labels.push(label.clone());
+            }
+        }
+        for policy in &seed.policies {
+            if !config.policies.iter().any(|p| p.name == policy.name) {
+                config.policies.push(policy.clone());
+                result.policies.push(policy.name.clone());
+            }
+        }
+        if !result.labels.is_empty() || !result.policies.is_empty() {
+            write_config(&self.ctx.repo_root, &config)?;
+        }
+        for epic in &seed.epics {
+            let task = self.create(CreateInput {
+                title: epic.title.clone(),
+                kind: TaskKind::Epic,
+                priority: epic.priority,
+                description: epic.description.clone(),
+                external_ref: None,
+                discovered_from: None,
+                parent: None,
+                exact_id: false,
+                planning_state: None,
+                explicit_id: None,
+                body_file: None,
+                ensure: true,
+                force: false,
+                skip_duplicate_check: true,
+            })?;
+            result.epics.push(task.id);
+        }
+        Ok(result)
+    }
+
+    /// The skill half of `init` with nothing written: no `.tasque` files,
+    /// no sync worktree, and only a per-target report of skill changes.
+    fn init_skill_dry_run(&self, input: &InitInput) -> Result<InitResult, TsqError> {
+        let action = if input.install_skill {
+            SkillAction::Install
+        } else {
+            SkillAction::Uninstall
+        };
+        Ok(InitResult {
+            initialized: false,
+            files: Vec::new(),
+            skill_operation: Some(apply_skill_operation(init_skill_options(
+                action,
+                input,
+                self.skill_registries(),
+            ))?),
+            sync_setup: None,
+            seeded: None,
+        })
+    }
+
+    pub fn repo_root(&self) -> &str {
+        &self.ctx.repo_root
+    }
+
+    pub fn actor(&self) -> &str {
+        &self.ctx.actor
+    }
+
+    /// Returns a service whose mutations stage into a fresh in-memory
+    /// transaction instead of the event log, plus the handle to inspect it.
+    pub fn preview(&self) -> (TasqueService, StagedHandle) {
+        let handle = StagedTransaction::handle();
+        let preview = TasqueService {
+            ctx: ServiceContext {
+                staged: Some(handle.clone()),
+                ..self.ctx.clone()
+            },
+        };
+        (preview, handle)
+    }
+
+    /// Returns a read-only view of the backlog as it stood at `as_of`: queries
+    /// replay events up to that timestamp and treat it as "now".
+    pub fn as_of(&self, as_of: String) -> TasqueService {
+        let now = as_of.clone();
+        TasqueService {
+            ctx: ServiceContext {
+                now: Arc::new(move || now.clone()),
+                as_of: Some(as_of),
+                ..self.ctx.clone()
+            },
+        }
+    }
+
+    pub fn create(&self, input: CreateInput) -> Result<Task, TsqError> {
+        service_create_update::create(&self.ctx, &input)
+    }
+
+    pub fn create_batch(
+        &self,
+        input: crate::app::service_types::CreateBatchInput,
+    ) -> Result<Vec<Task>, TsqError> {
+        service_create_update::create_batch(&self.ctx, &input)
+    }
+
+    pub fn batch(&self, input: BatchInput) -> Result<BatchResult, TsqError> {
+        service_batch::batch(&self.ctx, &input)
+    }
+
+    pub fn show(&self, id_raw: &str, exact_id: bool) -> Result<ShowResult, TsqError> {
+        service_query::show(&self.ctx, id_raw, exact_id)
+    }
+
+    pub fn list(&self, filter: &ListFilter) -> Result<Vec<Task>, TsqError> {
+        service_query::list(&self.ctx, filter)
+    }
+
+    pub fn stale(&self, input: &StaleInput) -> Result<StaleResult, TsqError> {
+        service_stale::stale(&self.ctx, input)
+    }
+
+    pub fn stale_enforce(&self) -> Result<StaleEnforceResult, TsqError> {
+        service_stale::stale_enforce(&self.ctx)
+    }
+
+    pub fn list_tree(&self, filter: &ListFilter) -> Result<Vec<TaskTreeNode>, TsqError> {
+        service_query::list_tree(&self.ctx, filter)
+    }
+
+    /// Ready tasks, narrowed to a builtin or configured lane when given.
+    pub fn ready(&self, lane: Option<&str>) -> Result<Vec<Task>, TsqError> {
+        service_query::ready(&self.ctx, lane)
+    }
+
+    pub fn metrics(&self) -> Result<MetricsResult, TsqError> {
+        service_query::metrics(&self.ctx)
+    }
+
+    pub fn subtree(&self, input: SubtreeInput) -> Result<SubtreeResult, TsqError> {
+        service_query::subtree(&self.ctx, &input)
+    }
+
+    pub fn rollup(&self, tasks: Vec<Task>) -> Result<Vec<RollupTask>, TsqError> {
+        service_query::rollup(&self.ctx, tasks)
+    }
+
+    pub fn externally_blocked(&self) -> Result<Vec<Task>, TsqError> {
+        service_query::externally_blocked(&self.ctx)
+    }
+
+    pub fn escalations(&self) -> Result<EscalationsResult, TsqError> {
+        service_query::escalations(&self.ctx)
+    }
+
+    pub fn report(&self) -> Result<ReportResult, TsqError> {
+        service_query::report(&self.ctx)
+    }
+
+    pub fn audit(&self, input: AuditInput) -> Result<AuditResult, TsqError> {
+        service_query::audit(&self.ctx, &input)
+    }
+
+    pub fn log(&self, input: LogInput) -> Result<LogResult, TsqError> {
+        service_query::log(&self.ctx, &input)
+    }
+
+    pub fn task_activity(&self, input: TaskActivityInput) -> Result<TaskActivityResult, TsqError> {
+        service_query::task_activity(&self.ctx, &input)
+    }
+
+    pub fn release_notes(&self, input: ReleaseNotesInput) -> Result<ReleaseNotesResult, TsqError> {
+        service_query::release_notes(&self.ctx, &input)
+    }
+
+    pub fn git_links(&self, input: GitLinksInput) -> Result<GitLinksResult, TsqError> {
+        service_query::git_links(&self.ctx, &input)
+    }
+
+    pub fn export(&self, input: ExportInput) -> Result<ExportResult, TsqError> {
+        service_query::export(&self.ctx, &input)
+    }
+
+    pub fn doctor(&self) -> Result<DoctorResult, TsqError> {
+        let mut result = service_query::doctor(&self.ctx)?;
+        // Skill installs live outside the repo; an unreadable home or source
+        // should not fail the repo checks.
+        if let Ok(entries) = self.skills_status(SkillsRefreshInput::default()) {
+            for finding in skill_findings(&entries) {
+                result.issues.push(finding.message.clone());
+                result.findings.push(finding);
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn orphans(&self) -> Result<OrphansResult, TsqError> {
+        service_query::orphans(&self.ctx)
+    }
+
+    pub fn orphans_adopt(&self, input: OrphansAdoptInput) -> Result<OrphansAdoptResult, TsqError> {
+        service_lifecycle::orphans_adopt(&self.ctx, &input)
+    }
+
+    pub fn ids(&self, input: IdsInput) -> Result<IdsResult, TsqError> {
+        service_query::ids(&self.ctx, &input)
+    }
+
+    pub fn update(&self, input: UpdateInput) -> Result<Task, TsqError> {
+        service_create_update::update(&self.ctx, &input)
+    }
+
+    pub fn note_add(&self, input: NoteAddInput) -> Result<NoteAddResult, TsqError> {
+        service_notes::note_add(&self.ctx, &input)
+    }
+
+    pub fn note_list(&self, input: NoteListInput) -> Result<NoteListResult, TsqError> {
+        service_notes::note_list(&self.ctx, &input)
+    }
+
+    pub fn order_child(&self, input: OrderChildInput) -> Result<OrderChildResult, TsqError> {
+        service_order::order_child(&self.ctx, &input)
+    }
+
+    pub fn checklist_add(&self, input: ChecklistAddInput) -> Result<ChecklistResult, TsqError> {
+        service_checklist::checklist_add(&self.ctx, &input)
+    }
+
+    pub fn checklist_set(&self, input: ChecklistSetInput) -> Result<ChecklistResult, TsqError> {
+        service_checklist::checklist_set(&self.ctx, &input)
+    }
+
+    pub fn checklist_list(&self, input: ChecklistListInput) -> Result<ChecklistResult, TsqError> {
+        service_checklist::checklist_list(&self.ctx, &input)
+    }
+
+    pub fn criteria_list(&self, input: CriteriaListInput) -> Result<CriteriaResult, TsqError> {
+        service_criteria::criteria_list(&self.ctx, &input)
+    }
+
+    pub fn criteria_check(&self, input: CriteriaCheckInput) -> Result<CriteriaResult, TsqError> {
+        service_criteria::criteria_check(&self.ctx, &input)
+    }
+
+    pub fn spec_attach(&self, input: SpecAttachInput) -> Result<SpecAttachResult, TsqError> {
+        service_specs::spec_attach(&self.ctx, &input)
+    }
+
+    pub fn spec_update(&self, input: SpecUpdateInput) -> Result<SpecUpdateResult, TsqError> {
+        service_specs::spec_update(&self.ctx, &input)
+    }
+
+    pub fn spec_patch(&self, input: SpecPatchInput) -> Result<SpecUpdateResult, TsqError> {
+        service_specs::spec_patch(&self.ctx, &input)
+    }
+
+    pub fn spec_check(&self, input: SpecCheckInput) -> Result<SpecCheckResult, TsqError> {
+        service_specs::spec_check(&self.ctx, &input)
+    }
+
+    pub fn claim(&self, input: ClaimInput) -> Result<Task, TsqError> {
+        service_lifecycle::claim(&self.ctx, &input)
+    }
+
+    pub fn handoff(&self, input: HandoffInput) -> Result<HandoffResult, TsqError> {
+        service_lifecycle::handoff(&self.ctx, &input)
+    }
+
+    pub fn dep_add(&self, input: DepInput) -> Result<(String, String, DependencyType), TsqError> {
+        service_lifecycle::dep_add(&self.ctx, &input)
+    }
+
+    pub fn dep_import(&self, input: DepImportInput) -> Result<DepImportResult, TsqError> {
+        service_lifecycle::dep_import(&self.ctx, &input)
+    }
+
+    pub fn dep_remove(
+        &self,
+        input: DepInput,
+    ) -> Result<(String, String, DependencyType), TsqError> {
+        service_lifecycle::dep_remove(&self.ctx, &input)
+    }
+
+    pub fn link_add(&self, input: LinkInput) -> Result<(String, String, RelationType), TsqError> {
+        service_lifecycle::link_add(&self.ctx, &input)
+    }
+
+    pub fn link_remove(
+        &self,
+        input: LinkInput,
+    ) -> Result<(String, String, RelationType), TsqError> {
+        service_lifecycle::link_remove(&self.ctx, &input)
+    }
+
+    pub fn custom_link_add(
+        &self,
+        input: CustomLinkInput,
+    ) -> Result<(String, String, String), TsqError> {
+        service_lifecycle::custom_link_add(&self.ctx, &input)
+    }
+
+    pub fn custom_link_remove(
+        &self,
+        input: CustomLinkInput,
+    ) -> Result<(String, String, String), TsqError> {
+        service_lifecycle::custom_link_remove(&self.ctx, &input)
+    }
+
+    pub fn supersede(&self, input: SupersedeInput) -> Result<Task, TsqError> {
+        service_lifecycle::supersede(&self.ctx, &input)
+    }
+
+    pub fn duplicate(&self, input: DuplicateInput) -> Result<Task, TsqError> {
+        service_lifecycle::duplicate(&self.ctx, &input)
+    }
+
+    pub fn convert(&self, input: ConvertInput) -> Result<Task, TsqError> {
+        service_create_update::convert(&self.ctx, &input)
+    }
+
+    pub fn split(&self, input: SplitInput) -> Result<SplitResult, TsqError> {
+        service_create_update::split(&self.ctx, &input)
+    }
+
+    pub fn merge(&self, input: MergeInput) -> Result<MergeResult, TsqError> {
+        service_lifecycle::merge(&self.ctx, &input)
+    }
+
+    pub fn duplicate_candidates(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<DuplicateCandidatesResult, TsqError> {
+        service_lifecycle::duplicate_candidates(&self.ctx, limit.unwrap_or(20))
+    }
+
+    pub fn repair(
+        &self,
+        fix: bool,
+        force_unlock: bool,
+        salvage: bool,
+        rebuild_snapshots: bool,
+    ) -> Result<RepairResult, TsqError> {
+        if fix || force_unlock {
+            require_role(&self.ctx, ActorRole::Admin, "tsq repair --fix")?;
+        }
+        execute_repair(
+            &self.ctx.repo_root,
+            &self.ctx.actor,
+            self.ctx.now.as_ref(),
+            RepairOptions {
+                fix,
+                force_unlock,
+                salvage,
+                rebuild_snapshots,
+            },
+        )
+    }
+
+    pub fn close(&self, input: CloseInput) -> Result<Vec<Task>, TsqError> {
+        service_lifecycle::close(&self.ctx, &input)
+    }
+
+    pub fn reopen(&self, input: ReopenInput) -> Result<Vec<Task>, TsqError> {
+        service_lifecycle::reopen(&self.ctx, &input)
+    }
+
+    pub fn set_lifecycle_status(
+        &self,
+        input: LifecycleStatusInput,
+    ) -> Result<LifecycleStatusResult, TsqError> {
+        service_lifecycle::set_lifecycle_status(&self.ctx, &input)
+    }
+
+    pub fn bench(&self, input: BenchInput) -> Result<BenchResult, TsqError> {
+        crate::app::state::bench_replay(&self.ctx.repo_root, input.runs)
+    }
+
+    pub fn history(&self, input: HistoryInput) -> Result<HistoryResult, TsqError> {
+        service_query::history(&self.ctx, &input)
+    }
+
+    pub fn label_add(&self, input: LabelInput) -> Result<Task, TsqError> {
+        service_labels::label_add(&self.ctx, &input)
+    }
+
+    pub fn label_remove(&self, input: LabelInput) -> Result<Task, TsqError> {
+        service_labels::label_remove(&self.ctx, &input)
+    }
+
+    pub fn label_list(&self) -> Result<Vec<LabelCount>, TsqError> {
+        service_labels::label_list(&self.ctx)
+    }
+
+    pub fn label_color(&self, input: LabelColorInput) -> Result<LabelColorResult, TsqError> {
+        service_labels::label_color(&self.ctx, &input)
+    }
+
+    /// Configured label colors for rendering; empty when config is unreadable.
+    pub fn label_colors(&self) -> BTreeMap<String, LabelColor> {
+        if !get_paths(&self.ctx.repo_root).config_file.exists() {
+            return BTreeMap::new();
+        }
+        read_config(&self.ctx.repo_root)
+            .map(|config| config.label_colors)
+            .unwrap_or_default()
+    }
+
+    pub fn dep_tree(
+        &self,
+        input: DepTreeInput,
+    ) -> Result<crate::domain::dep_tree::DepTreeNode, TsqError> {
+        let loaded = load_projected_state(&self.ctx.repo_root)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        build_dep_tree(
+            &loaded.state,
+            &id,
+            input
+                .direction
+                .unwrap_or(crate::domain::dep_tree::DepDirection::Both),
+            input.depth.unwrap_or(10),
+            input.statuses.as_deref(),
+        )
+    }
+
+    pub fn dep_graph(&self, input: DepTreeInput) -> Result<DepGraph, TsqError> {
+        self.dep_tree(input).map(|root| flatten_dep_tree(&root))
+    }
+
+    pub fn dep_why(&self, input: DepWhyInput) -> Result<DepWhyResult, TsqError> {
+        service_query::dep_why(&self.ctx, &input)
+    }
+
+    pub fn search(&self, input: &SearchInput) -> Result<Vec<Task>, TsqError> {
+        service_query::search(&self.ctx, input)
+    }
+
+    pub fn similar(
+        &self,
+        input: &crate::app::service_types::SimilarInput,
+    ) -> Result<Vec<crate::domain::similarity::SimilarTaskCandidate>, TsqError> {
+        service_query::similar(&self.ctx, input)
+    }
+
+    pub fn migrate(&self, branch: &str) -> Result<crate::types::MigrateResult, TsqError> {
+        crate::app::sync::migrate_to_sync_branch(&self.ctx.repo_root, branch, &self.ctx.actor)
+    }
+
+    pub fn sync(&self, push: bool) -> Result<crate::types::SyncRunResult, TsqError> {
+        crate::app::sync::sync_worktree(&self.ctx.repo_root, push)
+    }
+
+    pub fn sync_pull(&self) -> Result<crate::types::SyncRemoteResult, TsqError> {
+        crate::app::sync::pull_sync_branch(&self.ctx.repo_root)
+    }
+
+    pub fn sync_push(&self) -> Result<crate::types::SyncRemoteResult, TsqError> {
+        crate::app::sync::push_sync_branch(&self.ctx.repo_root)
+    }
+
+    /// One service per workspace shard, labeled by its sync branch.
+    pub fn workspace_services(&self) -> Result<Vec<(String, TasqueService)>, TsqError> {
+        let roots = crate::app::sync::workspace_roots(&self.ctx.repo_root)?;
+        Ok(roots
+            .into_iter()
+            .map(|(branch, repo_root)| {
+                let ctx = ServiceContext {
+                    repo_root,
+                    ..self.ctx.clone()
+                };
+                (branch, TasqueService { ctx })
+            })
+            .collect())
+    }
+
+    pub fn sync_disable(
+        &self,
+        delete_branch: bool,
+    ) -> Result<crate::types::SyncDisableResult, TsqError> {
+        crate::app::sync::disable_sync(&self.ctx.repo_root, delete_branch)
+    }
+
+    pub fn sync_status(&self, fetch: bool) -> Result<crate::types::SyncStatusResult, TsqError> {
+        crate::app::sync::sync_status(&self.ctx.repo_root, fetch)
+    }
+
+    pub fn sync_daemon(
+        &self,
+        options: crate::app::sync_daemon::SyncDaemonOptions,
+        on_cycle: impl FnMut(&crate::types::SyncDaemonStatus),
+    ) -> Result<crate::types::SyncDaemonStatus, TsqError> {
+        crate::app::sync_daemon::run_sync_daemon(&self.ctx.repo_root, options, on_cycle)
+    }
+
+    pub fn actor_add(&self, input: ActorAddInput) -> Result<ActorEntry, TsqError> {
+        service_actors::actor_add(&self.ctx, &input)
+    }
+
+    pub fn actor_remove(&self, name: &str) -> Result<ActorEntry, TsqError> {
+        service_actors::actor_remove(&self.ctx, name)
+    }
+
+    pub fn actor_list(&self) -> Result<Vec<ActorEntry>, TsqError> {
+        service_actors::actor_list(&self.ctx)
+    }
+
+    pub fn lock_status(&self) -> Result<crate::store::lock::LockStatus, TsqError> {
+        crate::store::lock::lock_status(&self.ctx.repo_root)
+    }
+
+    pub fn lock_break(&self, force: bool) -> Result<crate::store::lock::LockBreakResult, TsqError> {
+        if force {
+            require_role(&self.ctx, ActorRole::Admin, "tsq lock break --force")?;
+        }
+        crate::store::lock::break_lock(&self.ctx.repo_root, force)
+    }
+
+    pub fn backup_create(&self, out: Option<&str>) -> Result<crate::types::BackupResult, TsqError> {
+        crate::app::backup::create_backup(&self.ctx.repo_root, out, self.ctx.now.as_ref())
+    }
+
+    pub fn backup_restore(
+        &self,
+        path: &str,
+        force: bool,
+    ) -> Result<crate::types::RestoreResult, TsqError> {
+        require_role(&self.ctx, ActorRole::Admin, "tsq backup restore")?;
+        crate::app::backup::restore_backup(&self.ctx.repo_root, path, force)
+    }
+
+    pub fn hooks_install(&self, force: bool) -> Result<crate::types::HookInstallResult, TsqError> {
+        crate::app::sync::install_hooks(&self.ctx.repo_root, force)
+    }
+
+    pub fn hooks_uninstall(&self) -> Result<crate::types::HookUninstallResult, TsqError> {
+        crate::app::sync::uninstall_hooks(&self.ctx.repo_root)
+    }
+
+    /// Refreshes the managed `tasque` skill across all default agent targets.
+    ///
+    /// This is intentionally fixed-scope: refresh does not support custom skill
+    /// names, selective targets, or directory overrides because install-time
+    /// refresh should only update existing managed Tasque-owned skill dirs.
+    pub fn skills_refresh(
+        &self,
+        input: SkillsRefreshInput,
+    ) -> Result<SkillOperationSummary, TsqError> {
+        apply_skill_operation(self.default_skill_options("tasque", input))
+    }
+
+    pub fn skills_list(&self, input: SkillsRefreshInput) -> Result<Vec<SkillListEntry>, TsqError> {
+        list_skills(&self.default_skill_options("tasque", input))
+    }
+
+    pub fn skills_status(
+        &self,
+        input: SkillsRefreshInput,
+    ) -> Result<Vec<SkillStatusEntry>, TsqError> {
+        skill_status(&self.default_skill_options("tasque", input))
+    }
+
+    pub fn skills_diff(&self, input: SkillsDiffInput) -> Result<Vec<SkillDiffEntry>, TsqError> {
+        let skill_name = input.skill_name.trim().to_string();
+        if skill_name.is_empty() {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "skill name must not be empty",
+                1,
+            ));
+        }
+        skill_diff(&self.default_skill_options(
+            &skill_name,
+            SkillsRefreshInput {
+                source_root_dir: input.source_root_dir,
+                home_dir: input.home_dir,
+                codex_home: input.codex_home,
+                dry_run: false,
+                source: input.source,
+                checksum: input.checksum,
+                only_outdated: false,
+            },
+        ))
+    }
+
+    fn default_skill_options(
+        &self,
+        skill_name: &str,
+        input: SkillsRefreshInput,
+    ) -> crate::skills::types::SkillOperationOptions {
+        crate::skills::types::SkillOperationOptions {
+            action: SkillAction::Refresh,
+            skill_name: skill_name.to_string(),
+            targets: DEFAULT_SKILL_TARGETS.to_vec(),
+            force: false,
+            source_root_dir: input.source_root_dir,
+            home_dir: input.home_dir,
+            codex_home: input.codex_home,
+            target_dir_overrides: None,
+            dry_run: input.dry_run,
+            source: input.source,
+            checksum: input.checksum,
+            registries: self.skill_registries(),
+            only_outdated: input.only_outdated,
+        }
+    }
+
+    /// Configured skill registries; empty outside an initialized repo so the
+    /// skills commands stay repo-independent.
+    fn skill_registries(&self) -> BTreeMap<String, SkillRegistry> {
+        if !get_paths(&self.ctx.repo_root).config_file.exists() {
+            return BTreeMap::new();
+        }
+        read_config(&self.ctx.repo_root)
+            .map(|config| config.skill_registries)
+            .unwrap_or_default()
+    }
+}
+
+fn init_skill_options(
+    action: SkillAction,
+    input: &InitInput,
+    registries: BTreeMap<String, SkillRegistry>,
+) -> crate::skills::types::SkillOperationOptions {
+    crate::skills::types::SkillOperationOptions {
+        action,
+        skill_name: input
+            .skill_name
+            .clone()
+            .unwrap_or_else(|| "tasque".to_string()),
+        targets: input
+            .skill_targets
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SKILL_TARGETS.to_vec()),
+        force: input.force_skill_overwrite,
+        source_root_dir: None,
+        home_dir: None,
+        codex_home: None,
+        target_dir_overrides: build_target_overrides(input),
+        dry_run: input.dry_run,
+        source: input.skill_source.clone(),
+        checksum: input.skill_checksum.clone(),
+        registries,
+        only_outdated: false,
+    }
+}
+
+fn build_target_overrides(
+    input: &InitInput,
+) -> Option<std::collections::HashMap<SkillTarget, String>> {
+    let mut map = std::collections::HashMap::new();
+    if let Some(path) = input.skill_dir_claude.as_ref() {
+        map.insert(SkillTarget::Claude, path.clone());
+    }
+    if let Some(path) = input.skill_dir_codex.as_ref() {
+        map.insert(SkillTarget::Codex, path.clone());
+    }
+    if let Some(path) = input.skill_dir_copilot.as_ref() {
+        map.insert(SkillTarget::Copilot, path.clone());
+    }
+    if let Some(path) = input.skill_dir_opencode.as_ref() {
+        map.insert(SkillTarget::Opencode, path.clone());
+    }
+    if let Some(path) = input.skill_dir_cursor.as_ref() {
+        map.insert(SkillTarget::Cursor, path.clone());
+    }
+    if let Some(path) = input.skill_dir_windsurf.as_ref() {
+        map.insert(SkillTarget::Windsurf, path.clone());
+    }
+    if let Some(path) = input.skill_dir_gemini.as_ref() {
+        map.insert(SkillTarget::Gemini, path.clone());
+    }
+    if map.is_empty() { None } else { Some(map) }
+}
+
+fn io_error_value(error: &std::io::Error) -> serde_json::Value {
+    serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()})
+}
+
+fn should_default_to_sync_branch(repo_root: &str) -> bool {
+    let path = std::path::Path::new(repo_root);
+    git::is_git_repo(path)
+        && !git::is_sync_worktree_path(path)
+        && !read_config(repo_root).is_ok_and(|config| config.sync_disabled)
+}
@@ -0,0 +1,84 @@
+use crate::app::permissions::require_role;
+use crate::app::service_types::{ActorAddInput, ActorEntry, ServiceContext};
+use crate::app::transaction::with_service_lock;
+use crate::errors::TsqError;
+use crate::store::config::{is_actor_name, read_config, write_config};
+use crate::types::{ActorRole, Config};
+
+pub fn actor_add(ctx: &ServiceContext, input: &ActorAddInput) -> Result<ActorEntry, TsqError> {
+    require_role(ctx, ActorRole::Admin, "tsq actor add")?;
+    let name = input.name.trim().to_string();
+    if !is_actor_name(&name) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "actor name must be non-empty and contain no whitespace",
+            1,
+        ));
+    }
+    let email = input
+        .email
+        .as_deref()
+        .map(str::trim)
+        .filter(|email| !email.is_empty());
+    if email.is_some_and(|email| !email.contains('@')) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "actor email must contain '@'",
+            1,
+        ));
+    }
+    with_service_lock(ctx, || {
+        let mut config = read_config(&ctx.repo_root)?;
+        let identity = config.actors.entry(name.clone()).or_default();
+        if let Some(display_name) = input.display_name.as_deref().map(str::trim) {
+            identity.display_name = (!display_name.is_empty()).then(|| display_name.to_string());
+        }
+        if let Some(email) = email {
+            identity.email = Some(email.to_string());
+        }
+        if let Some(actor_type) = input.actor_type {
+            identity.actor_type = actor_type;
+        }
+        let entry = ActorEntry {
+            name: name.clone(),
+            identity: identity.clone(),
+        };
+        save(ctx, &config)?;
+        Ok(entry)
+    })
+}
+
+pub fn actor_remove(ctx: &ServiceContext, name: &str) -> Result<ActorEntry, TsqError> {
+    require_role(ctx, ActorRole::Admin, "tsq actor remove")?;
+    with_service_lock(ctx, || {
+        let mut config = read_config(&ctx.repo_root)?;
+        let Some(identity) = config.actors.remove(name.trim()) else {
+            return Err(TsqError::new(
+                "NOT_FOUND",
+                format!("actor not found: {}", name.trim()),
+                1,
+            ));
+        };
+        save(ctx, &config)?;
+        Ok(ActorEntry {
+            name: name.trim().to_string(),
+            identity,
+        })
+    })
+}
+
+pub fn actor_list(ctx: &ServiceContext) -> Result<Vec<ActorEntry>, TsqError> {
+    Ok(read_config(&ctx.repo_root)?
+        .actors
+        .into_iter()
+        .map(|(name, identity)| ActorEntry { name, identity })
+        .collect())
+}
+
+/// Staged (dry-run) contexts report the change without touching config.
+fn save(ctx: &ServiceContext, config: &Config) -> Result<(), TsqError> {
+    if ctx.staged.is_none() {
+        write_config(&ctx.repo_root, config)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Runtime-agnostic async facade over [`TasqueService`].
+//!
+//! Every service call takes the repo lock and may replay the whole event log,
+//! so calling it from an async task stalls the executor. [`AsyncTasqueService`]
+//! runs calls on a small pool of dedicated threads and hands back a plain
+//! [`Future`], which tokio, async-std, or a hand-rolled executor can await.
+
+use crate::app::service::{ShowResult, TasqueService};
+use crate::app::service_types::*;
+use crate::errors::TsqError;
+use crate::types::Task;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// `Send + Sync` and cheap to clone; clones share the service and the pool.
+/// Worker threads exit once the last clone is dropped and queued calls finish.
+#[derive(Clone)]
+pub struct AsyncTasqueService {
+    service: Arc<TasqueService>,
+    jobs: Sender<Job>,
+}
+
+impl AsyncTasqueService {
+    /// Wraps `service` with `workers` threads (at least one). Calls beyond
+    /// that queue in order; the repo lock serializes writers anyway.
+    pub fn new(service: TasqueService, workers: usize) -> Result<Self, TsqError> {
+        let (jobs, queue) = channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        for index in 0..workers.max(1) {
+            let queue = Arc::clone(&queue);
+            thread::Builder::new()
+                .name(format!("tsq-service-{}", index))
+                .spawn(move || work(&queue))
+                .map_err(|error| {
+                    TsqError::new("IO_ERROR", "failed starting service worker thread", 2)
+                        .with_details(serde_json::json!({"message": error.to_string()}))
+                })?;
+        }
+        Ok(Self {
+            service: Arc::new(service),
+            jobs,
+        })
+    }
+
+    pub fn service(&self) -> &TasqueService {
+        &self.service
+    }
+
+    /// Runs `call` on a worker thread. Use this for service methods without a
+    /// wrapper below.
+    pub fn run<T, F>(&self, call: F) -> ServiceFuture<T>
+    where
+        F: FnOnce(&TasqueService) -> Result<T, TsqError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let slot = Arc::new(Mutex::new(Slot {
+            result: None,
+            waker: None,
+        }));
+        let future = ServiceFuture {
+            slot: Arc::clone(&slot),
+        };
+        let service = Arc::clone(&self.service);
+        let job: Job = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(|| call(&service))).unwrap_or_else(|_| {
+                Err(TsqError::new("INTERNAL_ERROR", "service call panicked", 2))
+            });
+            complete(&slot, result);
+        });
+        if self.jobs.send(job).is_err() {
+            // Every worker has exited; fail the call instead of hanging it.
+            complete(
+                &future.slot,
+                Err(TsqError::new(
+                    "INTERNAL_ERROR",
+                    "service worker pool is not running",
+                    2,
+                )),
+            );
+        }
+        future
+    }
+
+    pub fn show(&self, id: String, exact_id: bool) -> ServiceFuture<ShowResult> {
+        self.run(move |service| service.show(&id, exact_id))
+    }
+
+    pub fn list(&self, filter: ListFilter) -> ServiceFuture<Vec<Task>> {
+        self.run(move |service| service.list(&filter))
+    }
+
+    pub fn ready(&self, lane: Option<String>) -> ServiceFuture<Vec<Task>> {
+        self.run(move |service| service.ready(lane.as_deref()))
+    }
+
+    pub fn search(&self, input: SearchInput) -> ServiceFuture<Vec<Task>> {
+        self.run(move |service| service.search(&input))
+    }
+
+    pub fn metrics(&self) -> ServiceFuture<MetricsResult> {
+        self.run(|service| service.metrics())
+    }
+
+    pub fn create(&self, input: CreateInput) -> ServiceFuture<Task> {
+        self.run(move |service| service.create(input))
+    }
+
+    pub fn update(&self, input: UpdateInput) -> ServiceFuture<Task> {
+        self.run(move |service| service.update(input))
+    }
+
+    pub fn claim(&self, input: ClaimInput) -> ServiceFuture<Task> {
+        self.run(move |service| service.claim(input))
+    }
+
+    pub fn close(&self, input: CloseInput) -> ServiceFuture<Vec<Task>> {
+        self.run(move |service| service.close(input))
+    }
+
+    pub fn note_add(&self, input: NoteAddInput) -> ServiceFuture<NoteAddResult> {
+        self.run(move |service| service.note_add(input))
+    }
+}
+
+struct Slot<T> {
+    result: Option<Result<T, TsqError>>,
+    waker: Option<Waker>,
+}
+
+/// Resolves to the service call's result once a worker has run it.
+pub struct ServiceFuture<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Future for ServiceFuture<T> {
+    type Output = Result<T, TsqError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = lock(&self.slot);
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn complete<T>(slot: &Mutex<Slot<T>>, result: Result<T, TsqError>) {
+    let waker = {
+        let mut slot = lock(slot);
+        slot.result = Some(result);
+        slot.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+fn work(queue: &Mutex<Receiver<Job>>) {
+    loop {
+        // Hold the receiver lock only while waiting, not while running.
+        let job = match lock(queue).recv() {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        job();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::Wake;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn facade_is_send_and_sync() {
+        assert_send_sync::<AsyncTasqueService>();
+    }
+
+    #[test]
+    fn runs_calls_on_workers_and_reports_panics() {
+        let service = AsyncTasqueService::new(
+            TasqueService::new("/nonexistent", "tester", || {
+                "2026-01-01T00:00:00.000Z".to_string()
+            }),
+            2,
+        )
+        .expect("pool");
+
+        let caller = thread::current().id();
+        let worker = block_on(service.run(|_| Ok(thread::current().id())));
+        assert_ne!(worker.expect("worker id"), caller);
+
+        let actor = block_on(service.run(|service| Ok(service.actor().to_string())));
+        assert_eq!(actor.expect("actor"), "tester");
+
+        let error = block_on(service.run(|_| -> Result<(), TsqError> { panic!("boom") }))
+            .expect_err("panic surfaces as error");
+        assert_eq!(error.code, "INTERNAL_ERROR");
+
+        // The pool survives a panicking call.
+        assert!(block_on(service.run(|_| Ok(1))).is_ok());
+    }
+}
@@ -0,0 +1,303 @@
+use super::{service_create_update, service_labels, service_notes};
+use crate::app::hooks::run_blocking_hooks;
+use crate::app::runtime::parse_priority;
+use crate::app::service_lifecycle;
+use crate::app::service_types::*;
+use crate::app::storage::{append_events, persist_projection};
+use crate::app::transaction::{
+    StagedTransaction, lock_staged, queue_advisory_hooks, with_service_lock,
+};
+use crate::errors::TsqError;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Fields whose string values may reference earlier batch creates via `$ref`.
+const REF_FIELDS: [&str; 7] = [
+    "id",
+    "parent",
+    "discovered_from",
+    "child",
+    "blocker",
+    "src",
+    "dst",
+];
+
+/// Applies every operation against one staged state under a single write
+/// lock. Nothing is appended unless all operations succeed.
+pub fn batch(ctx: &ServiceContext, input: &BatchInput) -> Result<BatchResult, TsqError> {
+    if input.operations.is_empty() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "batch requires at least one operation",
+            1,
+        ));
+    }
+
+    // Under an outer transaction (dry-run) the batch stages into it and the
+    // owner decides what to persist.
+    let outer = ctx.staged.is_some();
+    with_service_lock(ctx, || {
+        let handle = ctx.staged.clone().unwrap_or_else(StagedTransaction::handle);
+        let events_before = lock_staged(&handle).events().len();
+        let staged_ctx = ServiceContext {
+            staged: Some(handle.clone()),
+            ..ctx.clone()
+        };
+        let mut refs: HashMap<String, String> = HashMap::new();
+        let mut results = Vec::with_capacity(input.operations.len());
+
+        for (index, raw) in input.operations.iter().enumerate() {
+            let op_name = raw
+                .get("op")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown")
+                .to_string();
+            let outcome = substitute_refs(raw, &refs)
+                .and_then(|resolved| parse_operation(&resolved))
+                .and_then(|operation| apply_operation(&staged_ctx, &operation, input.exact_id));
+            let (ref_name, result) =
+                outcome.map_err(|error| annotate_operation_error(error, index, &op_name))?;
+
+            if let Some(name) = ref_name.as_ref()
+                && let Some(id) = result
+                    .get("task")
+                    .and_then(|task| task.get("id"))
+                    .and_then(Value::as_str)
+            {
+                refs.insert(name.clone(), id.to_string());
+            }
+            results.push(BatchOperationResult {
+                index,
+                op: op_name,
+                ref_name,
+                result,
+            });
+        }
+
+        let staged = lock_staged(&handle);
+        let events = staged.events()[events_before..].to_vec();
+        if !outer && !events.is_empty() {
+            let mut next_state = staged
+                .state()
+                .cloned()
+                .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "batch state missing", 2))?;
+            run_blocking_hooks(&ctx.repo_root, &events)?;
+            append_events(&ctx.repo_root, &events)?;
+            persist_projection(&ctx.repo_root, &mut next_state, staged.event_count(), None)?;
+            queue_advisory_hooks(&events);
+        }
+
+        Ok(BatchResult {
+            applied: results.len(),
+            events: events.len(),
+            results,
+        })
+    })
+}
+
+fn parse_operation(raw: &Value) -> Result<BatchOperation, TsqError> {
+    serde_json::from_value(raw.clone()).map_err(|error| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            format!("invalid batch operation: {}", error),
+            1,
+        )
+    })
+}
+
+fn substitute_refs(raw: &Value, refs: &HashMap<String, String>) -> Result<Value, TsqError> {
+    let mut resolved = raw.clone();
+    let Some(object) = resolved.as_object_mut() else {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "batch operation must be a JSON object",
+            1,
+        ));
+    };
+    for field in REF_FIELDS {
+        let Some(Value::String(value)) = object.get(field) else {
+            continue;
+        };
+        let Some(name) = value.strip_prefix('$') else {
+            continue;
+        };
+        let id = refs.get(name).ok_or_else(|| {
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!("unknown batch ref: ${}", name),
+                1,
+            )
+        })?;
+        object.insert(field.to_string(), Value::String(id.clone()));
+    }
+    Ok(resolved)
+}
+
+fn apply_operation(
+    ctx: &ServiceContext,
+    operation: &BatchOperation,
+    exact_id: bool,
+) -> Result<(Option<String>, Value), TsqError> {
+    let result = match operation {
+        BatchOperation::Create(op) => {
+            let priority = validate_priority(op.priority.unwrap_or(2))?;
+            let mut task = service_create_update::create(
+                ctx,
+                &CreateInput {
+                    title: op.title.clone(),
+                    kind: op.kind.unwrap_or(crate::types::TaskKind::Task),
+                    priority,
+                    description: op.description.clone(),
+                    external_ref: op.external_ref.clone(),
+                    discovered_from: op.discovered_from.clone(),
+                    parent: op.parent.clone(),
+                    exact_id,
+                    planning_state: op.planning_state.clone(),
+                    explicit_id: op.id.clone(),
+                    body_file: None,
+                    ensure: op.ensure,
+                    force: op.force,
+                    skip_duplicate_check: false,
+                },
+            )?;
+            for label in &op.labels {
+                task = service_labels::label_add(
+                    ctx,
+                    &LabelInput {
+                        id: task.id.clone(),
+                        label: label.clone(),
+                        exact_id: true,
+                    },
+                )?;
+            }
+            return Ok((op.ref_name.clone(), serde_json::json!({ "task": task })));
+        }
+        BatchOperation::Update(op) => {
+            let priority = op.priority.map(validate_priority).transpose()?;
+            let task = service_create_update::update(
+                ctx,
+                &UpdateInput {
+                    id: op.id.clone(),
+                    title: op.title.clone(),
+                    description: op.description.clone(),
+                    clear_description: op.clear_description,
+                    external_ref: op.external_ref.clone(),
+                    discovered_from: op.discovered_from.clone(),
+                    clear_discovered_from: op.clear_discovered_from,
+                    clear_external_ref: op.clear_external_ref,
+                    status: op.status.clone(),
+                    priority,
+                    exact_id,
+                    planning_state: op.planning_state.clone(),
+                    assignee: op.assignee.clone(),
+                    blocked_on: op.blocked_on.clone(),
+                },
+            )?;
+            serde_json::json!({ "task": task })
+        }
+        BatchOperation::DepAdd(op) | BatchOperation::DepRemove(op) => {
+            let dep_input = DepInput {
+                child: op.child.clone(),
+                blocker: op.blocker.clone(),
+                dep_type: op.dep_type,
+                exact_id,
+            };
+            let (child, blocker, dep_type) = if matches!(operation, BatchOperation::DepAdd(_)) {
+                service_lifecycle::dep_add(ctx, &dep_input)?
+            } else {
+                service_lifecycle::dep_remove(ctx, &dep_input)?
+            };
+            serde_json::json!({ "child": child, "blocker": blocker, "dep_type": dep_type })
+        }
+        BatchOperation::LinkAdd(op) | BatchOperation::LinkRemove(op) => {
+            let link_input = LinkInput {
+                src: op.src.clone(),
+                dst: op.dst.clone(),
+                rel_type: op.rel_type,
+                exact_id,
+            };
+            let (src, dst, rel_type) = if matches!(operation, BatchOperation::LinkAdd(_)) {
+                service_lifecycle::link_add(ctx, &link_input)?
+            } else {
+                service_lifecycle::link_remove(ctx, &link_input)?
+            };
+            serde_json::json!({ "src": src, "dst": dst, "type": rel_type })
+        }
+        BatchOperation::LabelAdd(op) | BatchOperation::LabelRemove(op) => {
+            let label_input = LabelInput {
+                id: op.id.clone(),
+                label: op.label.clone(),
+                exact_id,
+            };
+            let task = if matches!(operation, BatchOperation::LabelAdd(_)) {
+                service_labels::label_add(ctx, &label_input)?
+            } else {
+                service_labels::label_remove(ctx, &label_input)?
+            };
+            serde_json::json!({ "task": task })
+        }
+        BatchOperation::Note(op) => {
+            let note = service_notes::note_add(
+                ctx,
+                &NoteAddInput {
+                    id: op.id.clone(),
+                    text: op.text.clone(),
+                    exact_id,
+                },
+            )?;
+            serde_json::to_value(note).unwrap_or(Value::Null)
+        }
+        BatchOperation::Claim(op) => {
+            let task = service_lifecycle::claim(
+                ctx,
+                &ClaimInput {
+                    id: op.id.clone(),
+                    assignee: op.assignee.clone(),
+                    require_spec: op.require_spec,
+                    exact_id,
+                },
+            )?;
+            serde_json::json!({ "task": task })
+        }
+        BatchOperation::Status(op) => {
+            let result = service_lifecycle::set_lifecycle_status(
+                ctx,
+                &LifecycleStatusInput {
+                    ids: vec![op.id.clone()],
+                    status: op.status.clone(),
+                    note: op.note.clone(),
+                    reason: None,
+                    require_criteria: false,
+                    defer_until: None,
+                    reopen_from: None,
+                    filter: None,
+                    cascade_defer: false,
+                    exact_id,
+                },
+            )?;
+            serde_json::json!({ "task": result.tasks.into_iter().next(), "notes": result.notes })
+        }
+    };
+    Ok((None, result))
+}
+
+fn validate_priority(priority: u8) -> Result<u8, TsqError> {
+    parse_priority(&priority.to_string())
+}
+
+fn annotate_operation_error(error: TsqError, index: usize, op: &str) -> TsqError {
+    let details = serde_json::json!({
+        "index": index,
+        "op": op,
+        "details": error.details.clone(),
+    });
+    TsqError::new(
+        error.code.clone(),
+        format!(
+            "batch operation {} ({}) failed: {}",
+            index, op, error.message
+        ),
+        error.exit_code,
+    )
+    .with_details(details)
+}
@@ -0,0 +1,104 @@
+use crate::app::service_types::{
+    ChecklistAddInput, ChecklistListInput, ChecklistResult, ChecklistSetInput, ServiceContext,
+};
+use crate::app::service_utils::{must_resolve_existing, must_task};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::events::make_event;
+use crate::domain::projector::apply_events;
+use crate::errors::TsqError;
+use crate::types::{EventType, Task};
+
+pub fn checklist_add(
+    ctx: &ServiceContext,
+    input: &ChecklistAddInput,
+) -> Result<ChecklistResult, TsqError> {
+    let text = input.text.trim();
+    if text.is_empty() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "checklist item text must not be empty",
+            1,
+        ));
+    }
+
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::TaskChecklistAdded,
+            &id,
+            serde_json::json!({ "text": text })
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok(checklist_result(&must_task(&next_state, &id)?))
+    })
+}
+
+pub fn checklist_set(
+    ctx: &ServiceContext,
+    input: &ChecklistSetInput,
+) -> Result<ChecklistResult, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let task = must_task(&loaded.state, &id)?;
+        let item = input
+            .index
+            .checked_sub(1)
+            .and_then(|position| task.checklist.get(position))
+            .ok_or_else(|| {
+                TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!(
+                        "checklist item {} does not exist; task {} has {} items",
+                        input.index,
+                        id,
+                        task.checklist.len()
+                    ),
+                    1,
+                )
+            })?;
+
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::TaskChecklistSet,
+            &id,
+            serde_json::json!({
+                "item": item.event_id,
+                "index": input.index,
+                "done": input.done,
+            })
+            .as_object()
+            .cloned()
+            .unwrap_or_default(),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok(checklist_result(&must_task(&next_state, &id)?))
+    })
+}
+
+pub fn checklist_list(
+    ctx: &ServiceContext,
+    input: &ChecklistListInput,
+) -> Result<ChecklistResult, TsqError> {
+    let loaded = load_service_state(ctx)?;
+    let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+    Ok(checklist_result(&must_task(&loaded.state, &id)?))
+}
+
+fn checklist_result(task: &Task) -> ChecklistResult {
+    ChecklistResult {
+        task_id: task.id.clone(),
+        checklist: task.checklist.clone(),
+        done: task.checklist.iter().filter(|item| item.done).count(),
+        total: task.checklist.len(),
+    }
+}
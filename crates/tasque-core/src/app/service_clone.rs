@@ -0,0 +1,116 @@
+use super::TasqueService;
+use super::service_specs::{make_spec_attached_event, read_attached_spec_content, write_spec_file};
+use crate::app::service_lifecycle::enforce_policies;
+use crate::app::service_types::{CloneInput, CloneResult, ServiceContext};
+use crate::app::service_utils::{must_resolve_existing, must_task};
+use crate::app::storage::sha256;
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::alias::allocate_alias;
+use crate::domain::events::make_event;
+use crate::domain::ids::{RootIdAllocator, next_child_id};
+use crate::domain::projector::apply_events;
+use crate::errors::TsqError;
+use crate::types::{EventRecord, EventType, State, TaskStatus};
+use std::collections::VecDeque;
+
+impl TasqueService {
+    pub fn clone_task(&self, input: CloneInput) -> Result<CloneResult, TsqError> {
+        clone_task(&self.ctx, &input)
+    }
+}
+
+/// Copies a task (and optionally its subtree) as fresh open tasks. Attached
+/// specs are rewritten under the new ids and fingerprinted again.
+pub fn clone_task(ctx: &ServiceContext, input: &CloneInput) -> Result<CloneResult, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let source_id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let source = must_task(&loaded.state, &source_id)?;
+        let root_parent = match input.into.as_ref() {
+            Some(raw) => Some(must_resolve_existing(&loaded.state, raw, input.exact_id)?),
+            None => source.parent_id.clone(),
+        };
+
+        let mut events: Vec<EventRecord> = Vec::new();
+        let mut working_state = loaded.state.clone();
+        let mut clone_ids = Vec::new();
+        let mut queue = VecDeque::from([(source_id.clone(), root_parent)]);
+        while let Some((original_id, parent_id)) = queue.pop_front() {
+            let original = must_task(&loaded.state, &original_id)?;
+            let id = match parent_id.as_ref() {
+                Some(parent) => next_child_id(&working_state, parent),
+                None => RootIdAllocator::new(&working_state)?.next_id()?,
+            };
+            let ts = ctx.now.as_ref()();
+            let mut task_events = vec![make_event(
+                &ctx.actor,
+                &ts,
+                EventType::TaskCreated,
+                &id,
+                serde_json::json!({
+                  "id": id,
+                  "title": original.title,
+                  "alias": allocate_alias(&working_state, &original.title)?,
+                  "description": original.description,
+                  "discovered_from": original_id,
+                  "kind": original.kind,
+                  "priority": original.priority,
+                  "status": TaskStatus::Open,
+                  "parent_id": parent_id,
+                  "labels": original.labels,
+                  "planning_state": original.planning_state,
+                })
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+            )];
+            if let Some(spec_path) = original.spec_path.as_deref() {
+                let content = read_attached_spec_content(&ctx.repo_root, &original_id, spec_path)?;
+                let spec_file = write_spec_file(ctx, &id, &content)?;
+                task_events.push(make_spec_attached_event(
+                    ctx,
+                    &id,
+                    &spec_file.spec_path,
+                    &sha256(&spec_file.content),
+                    &ts,
+                    &ctx.actor,
+                ));
+            }
+            working_state = apply_events(&working_state, &task_events)?;
+            events.extend(task_events);
+            if input.with_children {
+                for child in children_in_creation_order(&loaded.state, &original_id) {
+                    queue.push_back((child, Some(id.clone())));
+                }
+            }
+            clone_ids.push(id);
+        }
+
+        enforce_policies(ctx, &loaded.state, &events, &working_state)?;
+        commit_service_events(
+            ctx,
+            &events,
+            &mut working_state,
+            loaded.event_count + events.len(),
+        )?;
+        Ok(CloneResult {
+            source: source_id,
+            tasks: clone_ids
+                .iter()
+                .map(|id| must_task(&working_state, id))
+                .collect::<Result<_, _>>()?,
+        })
+    })
+}
+
+/// Canceled children are dropped; closed ones are copied as open work.
+fn children_in_creation_order(state: &State, parent_id: &str) -> Vec<String> {
+    state
+        .created_order
+        .iter()
+        .filter_map(|id| state.tasks.get(id))
+        .filter(|task| task.parent_id.as_deref() == Some(parent_id))
+        .filter(|task| task.status != TaskStatus::Canceled)
+        .map(|task| task.id.clone())
+        .collect()
+}
@@ -1,10 +1,14 @@
-use crate::app::service_types::{CreateBatchInput, CreateInput, ServiceContext, UpdateInput};
+use crate::app::permissions::{ensure_known_actor, require_role};
+use crate::app::service_lifecycle::enforce_policies;
+use crate::app::service_types::{
+    ConvertInput, CreateBatchInput, CreateInput, ServiceContext, SplitInput, SplitResult,
+    UpdateInput,
+};
 use crate::app::service_utils::{
     must_resolve_existing, must_task, normalize_duplicate_title, unique_root_id,
 };
-use crate::app::storage::{
-    append_events, load_projected_state, persist_projection, with_write_lock,
-};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::app::workflow::ensure_planning_transition;
 use crate::domain::alias::allocate_alias;
 use crate::domain::events::make_event;
 use crate::domain::ids::{RootIdAllocator, is_valid_root_id, next_child_id};
@@ -14,7 +18,10 @@ use crate::domain::similarity::{
     find_similar_candidates, is_blocking_duplicate, is_blocking_title_pair,
 };
 use crate::errors::TsqError;
-use crate::types::{EventRecord, EventType, PlanningState, State, Task, TaskStatus};
+use crate::types::{
+    ActorRole, EventRecord, EventType, PlanningState, RelationType, State, Task, TaskKind,
+    TaskStatus,
+};
 use serde_json::{Map, Value};
 
 pub fn create(ctx: &ServiceContext, input: &CreateInput) -> Result<Task, TsqError> {
@@ -47,8 +54,14 @@ pub fn create(ctx: &ServiceContext, input: &CreateInput) -> Result<Task, TsqErro
         ));
     }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    let planning_state = input
+        .planning_state
+        .clone()
+        .unwrap_or(PlanningState::NeedsPlanning);
+    ensure_planning_transition(ctx, None, &planning_state)?;
+
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
 
         let (id, parent_id) = if let Some(explicit_id) = input.explicit_id.as_ref() {
             if !is_valid_root_id(explicit_id) {
@@ -144,7 +157,7 @@ pub fn create(ctx: &ServiceContext, input: &CreateInput) -> Result<Task, TsqErro
           "priority": input.priority,
           "status": TaskStatus::Open,
           "parent_id": parent_id,
-          "planning_state": input.planning_state.unwrap_or(PlanningState::NeedsPlanning),
+          "planning_state": planning_state,
         });
         if let Some(candidates) = duplicate_candidates {
             payload["duplicate_candidates"] = candidates;
@@ -159,13 +172,13 @@ pub fn create(ctx: &ServiceContext, input: &CreateInput) -> Result<Task, TsqErro
         );
 
         let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
-        append_events(&ctx.repo_root, &[event])?;
-        persist_projection(
-            &ctx.repo_root,
-            &mut next_state,
-            loaded.event_count + 1,
-            None,
+        enforce_policies(
+            ctx,
+            &loaded.state,
+            std::slice::from_ref(&event),
+            &next_state,
         )?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
         must_task(&next_state, &id)
     })
 }
@@ -192,9 +205,29 @@ pub fn update(ctx: &ServiceContext, input: &UpdateInput) -> Result<Task, TsqErro
             1,
         ));
     }
+    let blocked_on = input
+        .blocked_on
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(ToString::to_string);
+    if input.blocked_on.is_some() && blocked_on.is_none() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "blocked_on must not be empty",
+            1,
+        ));
+    }
+    if blocked_on.is_some() && input.status != Some(TaskStatus::Blocked) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "blocked_on is only valid when blocking a task",
+            1,
+        ));
+    }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
         let existing = must_task(&loaded.state, &id)?;
         let mut patch = Map::new();
@@ -223,13 +256,15 @@ pub fn update(ctx: &ServiceContext, input: &UpdateInput) -> Result<Task, TsqErro
         if input.clear_external_ref {
             patch.insert("clear_external_ref".to_string(), Value::Bool(true));
         }
-        if let Some(planning_state) = input.planning_state {
+        if let Some(planning_state) = input.planning_state.clone() {
+            ensure_planning_transition(ctx, existing.planning_state.as_ref(), &planning_state)?;
             patch.insert(
                 "planning_state".to_string(),
                 serde_json::json!(planning_state),
             );
         }
         if let Some(assignee) = input.assignee.as_ref() {
+            ensure_known_actor(ctx, assignee)?;
             patch.insert("assignee".to_string(), Value::String(assignee.clone()));
         }
         if let Some(discovered_from_raw) = input.discovered_from.as_ref() {
@@ -272,32 +307,36 @@ pub fn update(ctx: &ServiceContext, input: &UpdateInput) -> Result<Task, TsqErro
                 patch,
             ));
         }
-        if let Some(status) = input.status {
+        if let Some(status) = input.status.clone() {
             let ts = ctx.now.as_ref()();
             let closed_at = if status == TaskStatus::Closed {
                 Some(ts.clone())
             } else {
                 None
             };
+            let mut payload = payload_map(serde_json::json!({
+              "status": status,
+              "closed_at": closed_at,
+            }));
+            if let Some(blocked_on) = blocked_on.as_ref() {
+                payload.insert("blocked_on".to_string(), Value::String(blocked_on.clone()));
+            }
             events.push(make_event(
                 &ctx.actor,
                 &ts,
                 EventType::TaskStatusSet,
                 &id,
-                payload_map(serde_json::json!({
-                  "status": status,
-                  "closed_at": closed_at,
-                })),
+                payload,
             ));
         }
 
         let mut next_state = apply_events(&loaded.state, &events)?;
-        append_events(&ctx.repo_root, &events)?;
-        persist_projection(
-            &ctx.repo_root,
+        enforce_policies(ctx, &loaded.state, &events, &next_state)?;
+        commit_service_events(
+            ctx,
+            &events,
             &mut next_state,
             loaded.event_count + events.len(),
-            None,
         )?;
         must_task(&next_state, &id)
     })
@@ -331,7 +370,7 @@ fn check_duplicate_gate(
     let candidates: Vec<_> = state
         .tasks
         .values()
-        .filter(|task| blocking_status(task.status))
+        .filter(|task| blocking_status(&task.status))
         .filter_map(|task| is_blocking_duplicate(title, task))
         .collect();
     if candidates.is_empty() {
@@ -364,8 +403,14 @@ pub fn create_batch(ctx: &ServiceContext, input: &CreateBatchInput) -> Result<Ve
         ));
     }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    let planning_state = input
+        .planning_state
+        .clone()
+        .unwrap_or(PlanningState::NeedsPlanning);
+    ensure_planning_transition(ctx, None, &planning_state)?;
+
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
 
         // Resolve CLI --parent once.
         let cli_parent_id: Option<String> = input
@@ -494,7 +539,7 @@ pub fn create_batch(ctx: &ServiceContext, input: &CreateBatchInput) -> Result<Ve
                         "priority": input.priority,
                         "status": TaskStatus::Open,
                         "parent_id": parent_id,
-                        "planning_state": input.planning_state.unwrap_or(PlanningState::NeedsPlanning),
+                        "planning_state": planning_state,
                     });
                     if let Some(candidates) = duplicate_candidates {
                         payload["duplicate_candidates"] = candidates;
@@ -524,12 +569,12 @@ pub fn create_batch(ctx: &ServiceContext, input: &CreateBatchInput) -> Result<Ve
 
         // Phase 5: Persist all events atomically.
         if !events.is_empty() {
-            append_events(&ctx.repo_root, &events)?;
-            persist_projection(
-                &ctx.repo_root,
+            enforce_policies(ctx, &loaded.state, &events, &working_state)?;
+            commit_service_events(
+                ctx,
+                &events,
                 &mut working_state,
                 loaded.event_count + events.len(),
-                None,
             )?;
         }
 
@@ -758,7 +803,7 @@ fn check_existing_duplicates(
         let candidates: Vec<_> = state
             .tasks
             .values()
-            .filter(|task| blocking_status(task.status))
+            .filter(|task| blocking_status(&task.status))
             .filter_map(|task| is_blocking_duplicate(title, task))
             .filter(|candidate| {
                 if !ensure {
@@ -817,3 +862,246 @@ fn find_existing_by_parent_and_title(
     });
     matches.first().map(|task| (*task).clone())
 }
+
+/// Changes a task's kind. A task with children can only become an epic or
+/// feature, since plain tasks are leaves.
+pub fn convert(ctx: &ServiceContext, input: &ConvertInput) -> Result<Task, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let task = must_task(&loaded.state, &id)?;
+        if task.kind == input.kind {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!("task {} is already a {}", id, kind_name(input.kind)),
+                1,
+            ));
+        }
+        if input.kind == TaskKind::Task {
+            let mut children: Vec<String> = loaded
+                .state
+                .tasks
+                .values()
+                .filter(|child| child.parent_id.as_deref() == Some(id.as_str()))
+                .map(|child| child.id.clone())
+                .collect();
+            if !children.is_empty() {
+                children.sort();
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!(
+                        "{} {} has {} child task(s) and cannot become a task",
+                        kind_name(task.kind),
+                        id,
+                        children.len()
+                    ),
+                    1,
+                )
+                .with_details(serde_json::json!({ "task_id": id, "children": children })));
+            }
+        }
+
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::TaskUpdated,
+            &id,
+            payload_map(serde_json::json!({
+                "kind": input.kind,
+                "converted_from": task.kind,
+            })),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        enforce_policies(
+            ctx,
+            &loaded.state,
+            std::slice::from_ref(&event),
+            &next_state,
+        )?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        must_task(&next_state, &id)
+    })
+}
+
+fn kind_name(kind: TaskKind) -> &'static str {
+    match kind {
+        TaskKind::Task => "task",
+        TaskKind::Feature => "feature",
+        TaskKind::Epic => "epic",
+    }
+}
+
+/// Splits a task into new parts that inherit its labels, assignee, priority,
+/// planning state, and relations, recording the original as `discovered_from`.
+pub fn split(ctx: &ServiceContext, input: &SplitInput) -> Result<SplitResult, TsqError> {
+    let titles: Vec<String> = input
+        .parts
+        .iter()
+        .map(|title| title.trim().to_string())
+        .collect();
+    if titles.is_empty() || titles.iter().any(String::is_empty) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "split requires at least one non-empty --into title",
+            1,
+        ));
+    }
+    if input.close {
+        require_role(ctx, ActorRole::Admin, "tsq split --close")?;
+    }
+
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let source_id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let source = must_task(&loaded.state, &source_id)?;
+        if matches!(source.status, TaskStatus::Closed | TaskStatus::Canceled) {
+            return Err(TsqError::new(
+                "INVALID_STATUS",
+                format!(
+                    "cannot split task {}; it is already closed or canceled",
+                    source_id
+                ),
+                1,
+            ));
+        }
+        let parent_id = if input.siblings {
+            source.parent_id.clone()
+        } else {
+            Some(source_id.clone())
+        };
+        // Children of the original are plain tasks; siblings keep its kind.
+        let kind = if input.siblings {
+            source.kind
+        } else {
+            TaskKind::Task
+        };
+
+        let mut events: Vec<EventRecord> = Vec::new();
+        let mut working_state = loaded.state.clone();
+        let mut root_id_allocator: Option<RootIdAllocator> = None;
+        let mut part_ids = Vec::with_capacity(titles.len());
+        for title in &titles {
+            let id = match parent_id.as_ref() {
+                Some(parent) => next_child_id(&working_state, parent),
+                None => {
+                    if root_id_allocator.is_none() {
+                        root_id_allocator = Some(RootIdAllocator::new(&loaded.state)?);
+                    }
+                    root_id_allocator
+                        .as_mut()
+                        .expect("root id allocator initialized")
+                        .next_id()?
+                }
+            };
+            let mut part_events = vec![make_event(
+                &ctx.actor,
+                &ctx.now.as_ref()(),
+                EventType::TaskCreated,
+                &id,
+                payload_map(serde_json::json!({
+                  "id": id,
+                  "title": title,
+                  "alias": allocate_alias(&working_state, title)?,
+                  "discovered_from": source_id,
+                  "kind": kind,
+                  "priority": source.priority,
+                  "status": TaskStatus::Open,
+                  "parent_id": parent_id,
+                  "labels": source.labels,
+                  "assignee": source.assignee,
+                  "planning_state": source.planning_state,
+                })),
+            )];
+            part_events.extend(carried_link_events(ctx, &loaded.state, &source_id, &id));
+            working_state = apply_events(&working_state, &part_events)?;
+            events.extend(part_events);
+            part_ids.push(id);
+        }
+
+        if input.close {
+            let mut payload = payload_map(serde_json::json!({ "with": part_ids[0] }));
+            let reason = input
+                .reason
+                .clone()
+                .unwrap_or_else(|| format!("split into {}", part_ids.join(", ")));
+            payload.insert("reason".to_string(), Value::String(reason));
+            let event = make_event(
+                &ctx.actor,
+                &ctx.now.as_ref()(),
+                EventType::TaskSuperseded,
+                &source_id,
+                payload,
+            );
+            working_state = apply_events(&working_state, std::slice::from_ref(&event))?;
+            events.push(event);
+        }
+
+        enforce_policies(ctx, &loaded.state, &events, &working_state)?;
+        commit_service_events(
+            ctx,
+            &events,
+            &mut working_state,
+            loaded.event_count + events.len(),
+        )?;
+        Ok(SplitResult {
+            source: must_task(&working_state, &source_id)?,
+            parts: part_ids
+                .iter()
+                .map(|id| must_task(&working_state, id))
+                .collect::<Result<_, _>>()?,
+        })
+    })
+}
+
+/// `relates_to` and config-defined links on the original, re-recorded on a
+/// split part. Custom links carry the inverse name stored on the other task.
+fn carried_link_events(
+    ctx: &ServiceContext,
+    state: &State,
+    source_id: &str,
+    part_id: &str,
+) -> Vec<EventRecord> {
+    let mut payloads = Vec::new();
+    if let Some(targets) = state
+        .links
+        .get(source_id)
+        .and_then(|by_type| by_type.get(&RelationType::RelatesTo))
+    {
+        for target in targets {
+            payloads.push(serde_json::json!({
+                "type": RelationType::RelatesTo,
+                "target": target,
+            }));
+        }
+    }
+    for (rel_type, targets) in state.custom_links.get(source_id).into_iter().flatten() {
+        for target in targets {
+            let inverse = state
+                .custom_links
+                .get(target)
+                .into_iter()
+                .flatten()
+                .find(|(_, back)| back.iter().any(|id| id == source_id))
+                .map(|(name, _)| name.clone());
+            if let Some(inverse) = inverse {
+                payloads.push(serde_json::json!({
+                    "type": rel_type,
+                    "inverse": inverse,
+                    "target": target,
+                }));
+            }
+        }
+    }
+    payloads
+        .into_iter()
+        .map(|payload| {
+            make_event(
+                &ctx.actor,
+                &ctx.now.as_ref()(),
+                EventType::LinkAdded,
+                part_id,
+                payload_map(payload),
+            )
+        })
+        .collect()
+}
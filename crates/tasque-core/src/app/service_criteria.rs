@@ -0,0 +1,90 @@
+use crate::app::service_types::{
+    CriteriaCheckInput, CriteriaListInput, CriteriaResult, ServiceContext,
+};
+use crate::app::service_utils::{must_resolve_existing, must_task};
+use crate::app::storage::read_task_acceptance_criteria;
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::events::make_event;
+use crate::domain::projector::apply_events;
+use crate::errors::TsqError;
+use crate::types::{EventType, Task};
+
+pub fn criteria_list(
+    ctx: &ServiceContext,
+    input: &CriteriaListInput,
+) -> Result<CriteriaResult, TsqError> {
+    let loaded = load_service_state(ctx)?;
+    let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+    let task = must_task(&loaded.state, &id)?;
+    criteria_result(ctx, &task)
+}
+
+pub fn criteria_check(
+    ctx: &ServiceContext,
+    input: &CriteriaCheckInput,
+) -> Result<CriteriaResult, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let task = must_task(&loaded.state, &id)?;
+        let criteria = read_task_acceptance_criteria(&ctx.repo_root, &task)?;
+        if criteria.is_empty() {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "task {} has no acceptance criteria; attach a spec with an \"Acceptance criteria\" checklist",
+                    id
+                ),
+                1,
+            ));
+        }
+        let criterion = criteria
+            .iter()
+            .find(|criterion| criterion.index == input.index)
+            .ok_or_else(|| {
+                TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!(
+                        "criterion {} does not exist; task {} has {} acceptance criteria",
+                        input.index,
+                        id,
+                        criteria.len()
+                    ),
+                    1,
+                )
+            })?;
+        let checked = input.checked.unwrap_or(!criterion.checked);
+
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::TaskCriterionSet,
+            &id,
+            serde_json::json!({
+                "index": criterion.index,
+                "text": criterion.text,
+                "checked": checked,
+            })
+            .as_object()
+            .cloned()
+            .unwrap_or_default(),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        let task = must_task(&next_state, &id)?;
+        criteria_result(ctx, &task)
+    })
+}
+
+fn criteria_result(ctx: &ServiceContext, task: &Task) -> Result<CriteriaResult, TsqError> {
+    let criteria = read_task_acceptance_criteria(&ctx.repo_root, task)?;
+    let open = criteria
+        .iter()
+        .filter(|criterion| !criterion.checked)
+        .count();
+    Ok(CriteriaResult {
+        task_id: task.id.clone(),
+        criteria,
+        open,
+    })
+}
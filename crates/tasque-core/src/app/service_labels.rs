@@ -0,0 +1,118 @@
+use crate::app::service_types::{
+    LabelColorInput, LabelColorResult, LabelCount, LabelInput, ServiceContext,
+};
+use crate::app::service_utils::{must_resolve_existing, must_task};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::events::make_event;
+use crate::domain::labels::{add_label, normalize_label, remove_label};
+use crate::domain::projector::apply_events;
+use crate::errors::TsqError;
+use crate::store::config::{read_config, write_config};
+use crate::types::{EventType, LabelColor, Task};
+use std::collections::HashMap;
+
+pub fn label_add(ctx: &ServiceContext, input: &LabelInput) -> Result<Task, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let existing = must_task(&loaded.state, &id)?;
+        let labels = add_label(&existing.labels, &input.label)?;
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::TaskUpdated,
+            &id,
+            serde_json::json!({ "labels": labels })
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        must_task(&next_state, &id)
+    })
+}
+
+pub fn label_remove(ctx: &ServiceContext, input: &LabelInput) -> Result<Task, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let existing = must_task(&loaded.state, &id)?;
+        let labels = remove_label(&existing.labels, &input.label)?;
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::TaskUpdated,
+            &id,
+            serde_json::json!({ "labels": labels })
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        must_task(&next_state, &id)
+    })
+}
+
+pub fn label_list(ctx: &ServiceContext) -> Result<Vec<LabelCount>, TsqError> {
+    let loaded = load_service_state(ctx)?;
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for task in loaded.state.tasks.values() {
+        for label in &task.labels {
+            let count = counts.entry(label.clone()).or_insert(0);
+            *count += 1;
+        }
+    }
+    let label_colors = read_config(&ctx.repo_root)?.label_colors;
+    for label in label_colors.keys() {
+        counts.entry(label.clone()).or_insert(0);
+    }
+    let mut result: Vec<LabelCount> = counts
+        .into_iter()
+        .map(|(label, count)| LabelCount {
+            color: label_colors.get(&label).copied(),
+            label,
+            count,
+        })
+        .collect();
+    result.sort_by(|a, b| a.label.cmp(&b.label));
+    Ok(result)
+}
+
+pub fn label_color(
+    ctx: &ServiceContext,
+    input: &LabelColorInput,
+) -> Result<LabelColorResult, TsqError> {
+    let label = normalize_label(&input.label)?;
+    let color = match input.color.as_deref() {
+        None => None,
+        Some(raw) => Some(LabelColor::parse(raw).ok_or_else(|| {
+            let allowed: Vec<&str> = LabelColor::ALL.iter().map(|color| color.as_str()).collect();
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "unknown label color {}; expected one of {}",
+                    raw,
+                    allowed.join(", ")
+                ),
+                1,
+            )
+        })?),
+    };
+    with_service_lock(ctx, || {
+        let mut config = read_config(&ctx.repo_root)?;
+        match color {
+            Some(color) => config.label_colors.insert(label.clone(), color),
+            None => config.label_colors.remove(&label),
+        };
+        // Staged (dry-run) contexts report the color without touching config.
+        if ctx.staged.is_none() {
+            write_config(&ctx.repo_root, &config)?;
+        }
+        Ok(LabelColorResult {
+            label: label.clone(),
+            color,
+        })
+    })
+}
@@ -0,0 +1,23 @@
+#[path = "service_lifecycle_claim.rs"]
+mod service_lifecycle_claim;
+#[path = "service_lifecycle_helpers.rs"]
+mod service_lifecycle_helpers;
+#[path = "service_lifecycle_links.rs"]
+mod service_lifecycle_links;
+#[path = "service_lifecycle_merge.rs"]
+mod service_lifecycle_merge;
+#[path = "service_lifecycle_policy.rs"]
+mod service_lifecycle_policy;
+#[path = "service_lifecycle_status.rs"]
+mod service_lifecycle_status;
+
+pub use service_lifecycle_claim::{claim, close, duplicate, handoff, reopen, supersede};
+pub use service_lifecycle_links::{
+    custom_link_add, custom_link_remove, dep_add, dep_import, dep_remove, link_add, link_remove,
+    orphans_adopt,
+};
+pub use service_lifecycle_merge::{duplicate_candidates, merge};
+pub use service_lifecycle_policy::enforce_policies;
+pub use service_lifecycle_status::{
+    complete_parents, set_lifecycle_status, wake_snoozed_tasks, with_due_wakes,
+};
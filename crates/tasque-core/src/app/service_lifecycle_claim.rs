@@ -1,22 +1,24 @@
-use super::service_lifecycle_helpers::{payload_map, status_to_string};
+use super::service_lifecycle_helpers::{payload_map, validate_reopen};
+use super::service_lifecycle_policy::enforce_policies;
+use crate::app::permissions::{ensure_known_actor, require_role};
 use crate::app::service_types::{
-    ClaimInput, CloseInput, DuplicateInput, ReopenInput, ServiceContext, SupersedeInput,
+    ClaimInput, CloseInput, DuplicateInput, HandoffInput, HandoffResult, ReopenInput,
+    ServiceContext, SupersedeInput,
 };
 use crate::app::service_utils::{
     creates_duplicate_cycle, has_duplicate_link, must_resolve_existing, must_task,
 };
-use crate::app::storage::{
-    append_events, evaluate_task_spec, load_projected_state, persist_projection, with_write_lock,
-};
+use crate::app::storage::evaluate_task_spec;
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
 use crate::domain::events::make_event;
 use crate::domain::projector::apply_events;
 use crate::errors::TsqError;
-use crate::types::{EventRecord, EventType, RelationType, Task, TaskStatus};
+use crate::types::{ActorRole, EventRecord, EventType, RelationType, Task, TaskStatus};
 use serde_json::Value;
 
 pub fn claim(ctx: &ServiceContext, input: &ClaimInput) -> Result<Task, TsqError> {
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
         let existing = must_task(&loaded.state, &id)?;
         let claimable = matches!(existing.status, TaskStatus::Open | TaskStatus::InProgress);
@@ -25,7 +27,7 @@ pub fn claim(ctx: &ServiceContext, input: &ClaimInput) -> Result<Task, TsqError>
                 "INVALID_STATUS",
                 format!(
                     "cannot claim task with status '{}'",
-                    status_to_string(existing.status)
+                    existing.status.as_str()
                 ),
                 1,
             ));
@@ -52,6 +54,7 @@ pub fn claim(ctx: &ServiceContext, input: &ClaimInput) -> Result<Task, TsqError>
             }
         }
         let assignee = input.assignee.clone().unwrap_or_else(|| ctx.actor.clone());
+        ensure_known_actor(ctx, &assignee)?;
         let event = make_event(
             &ctx.actor,
             &ctx.now.as_ref()(),
@@ -60,20 +63,99 @@ pub fn claim(ctx: &ServiceContext, input: &ClaimInput) -> Result<Task, TsqError>
             payload_map(serde_json::json!({"assignee": assignee})),
         );
         let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
-        append_events(&ctx.repo_root, &[event])?;
-        persist_projection(
-            &ctx.repo_root,
-            &mut next_state,
-            loaded.event_count + 1,
-            None,
+        enforce_policies(
+            ctx,
+            &loaded.state,
+            std::slice::from_ref(&event),
+            &next_state,
         )?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
         must_task(&next_state, &id)
     })
 }
 
+/// Moves a claimed task to another actor: a `task.claimed` event carrying
+/// `handoff_from`, followed by a note recording who passed it on and why.
+pub fn handoff(ctx: &ServiceContext, input: &HandoffInput) -> Result<HandoffResult, TsqError> {
+    let to = input.to.trim().to_string();
+    if to.is_empty() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "handoff target must not be empty",
+            1,
+        ));
+    }
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let existing = must_task(&loaded.state, &id)?;
+        let Some(from) = existing.assignee.clone() else {
+            return Err(TsqError::new(
+                "NOT_CLAIMED",
+                "cannot hand off a task nobody has claimed; use tsq claim",
+                1,
+            ));
+        };
+        if matches!(existing.status, TaskStatus::Closed | TaskStatus::Canceled) {
+            return Err(TsqError::new(
+                "INVALID_STATUS",
+                format!(
+                    "cannot hand off task with status '{}'",
+                    existing.status.as_str()
+                ),
+                1,
+            ));
+        }
+        if from == to {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!("task is already assigned to {}", to),
+                1,
+            ));
+        }
+        ensure_known_actor(ctx, &to)?;
+        let ts = ctx.now.as_ref()();
+        let text = match input.note.as_deref().map(str::trim) {
+            Some(note) if !note.is_empty() => format!("handoff {} -> {}: {}", from, to, note),
+            _ => format!("handoff {} -> {}", from, to),
+        };
+        let events = vec![
+            make_event(
+                &ctx.actor,
+                &ts,
+                EventType::TaskClaimed,
+                &id,
+                payload_map(serde_json::json!({"assignee": to, "handoff_from": from})),
+            ),
+            make_event(
+                &ctx.actor,
+                &ts,
+                EventType::TaskNoted,
+                &id,
+                payload_map(serde_json::json!({"text": text})),
+            ),
+        ];
+        let mut next_state = apply_events(&loaded.state, &events)?;
+        enforce_policies(ctx, &loaded.state, &events, &next_state)?;
+        let event_count = loaded.event_count + events.len();
+        commit_service_events(ctx, &events, &mut next_state, event_count)?;
+        let task = must_task(&next_state, &id)?;
+        let note =
+            task.notes.last().cloned().ok_or_else(|| {
+                TsqError::new("INTERNAL_ERROR", "handoff note was not persisted", 2)
+            })?;
+        Ok(HandoffResult {
+            task,
+            from,
+            to: to.clone(),
+            note,
+        })
+    })
+}
+
 pub fn close(ctx: &ServiceContext, input: &CloseInput) -> Result<Vec<Task>, TsqError> {
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let resolved_ids: Vec<String> = input
             .ids
             .iter()
@@ -115,12 +197,12 @@ pub fn close(ctx: &ServiceContext, input: &CloseInput) -> Result<Vec<Task>, TsqE
         }
 
         let mut next_state = apply_events(&loaded.state, &events)?;
-        append_events(&ctx.repo_root, &events)?;
-        persist_projection(
-            &ctx.repo_root,
+        enforce_policies(ctx, &loaded.state, &events, &next_state)?;
+        commit_service_events(
+            ctx,
+            &events,
             &mut next_state,
             loaded.event_count + events.len(),
-            None,
         )?;
         resolved_ids
             .iter()
@@ -130,8 +212,8 @@ pub fn close(ctx: &ServiceContext, input: &CloseInput) -> Result<Vec<Task>, TsqE
 }
 
 pub fn reopen(ctx: &ServiceContext, input: &ReopenInput) -> Result<Vec<Task>, TsqError> {
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let resolved_ids: Vec<String> = input
             .ids
             .iter()
@@ -141,33 +223,26 @@ pub fn reopen(ctx: &ServiceContext, input: &ReopenInput) -> Result<Vec<Task>, Ts
 
         for id in &resolved_ids {
             let existing = must_task(&loaded.state, id)?;
-            if existing.status != TaskStatus::Closed {
-                return Err(TsqError::new(
-                    "VALIDATION_ERROR",
-                    format!(
-                        "cannot reopen task {} with status {}",
-                        id,
-                        status_to_string(existing.status)
-                    ),
-                    1,
-                ));
-            }
+            validate_reopen(id, &existing, input.from.as_ref())?;
             events.push(make_event(
                 &ctx.actor,
                 &ctx.now.as_ref()(),
                 EventType::TaskStatusSet,
                 id,
-                payload_map(serde_json::json!({"status": TaskStatus::Open})),
+                payload_map(serde_json::json!({
+                    "status": TaskStatus::Open,
+                    "reopened_from": existing.status,
+                })),
             ));
         }
 
         let mut next_state = apply_events(&loaded.state, &events)?;
-        append_events(&ctx.repo_root, &events)?;
-        persist_projection(
-            &ctx.repo_root,
+        enforce_policies(ctx, &loaded.state, &events, &next_state)?;
+        commit_service_events(
+            ctx,
+            &events,
             &mut next_state,
             loaded.event_count + events.len(),
-            None,
         )?;
         resolved_ids
             .iter()
@@ -177,8 +252,9 @@ pub fn reopen(ctx: &ServiceContext, input: &ReopenInput) -> Result<Vec<Task>, Ts
 }
 
 pub fn supersede(ctx: &ServiceContext, input: &SupersedeInput) -> Result<Task, TsqError> {
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    require_role(ctx, ActorRole::Admin, "tsq supersede")?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let source = must_resolve_existing(&loaded.state, &input.source, input.exact_id)?;
         let with_id = must_resolve_existing(&loaded.state, &input.with_id, input.exact_id)?;
         if source == with_id {
@@ -203,20 +279,14 @@ pub fn supersede(ctx: &ServiceContext, input: &SupersedeInput) -> Result<Task, T
             payload,
         );
         let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
-        append_events(&ctx.repo_root, &[event])?;
-        persist_projection(
-            &ctx.repo_root,
-            &mut next_state,
-            loaded.event_count + 1,
-            None,
-        )?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
         must_task(&next_state, &source)
     })
 }
 
 pub fn duplicate(ctx: &ServiceContext, input: &DuplicateInput) -> Result<Task, TsqError> {
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let source = must_resolve_existing(&loaded.state, &input.source, input.exact_id)?;
         let canonical = must_resolve_existing(&loaded.state, &input.canonical, input.exact_id)?;
         if source == canonical {
@@ -301,12 +371,11 @@ pub fn duplicate(ctx: &ServiceContext, input: &DuplicateInput) -> Result<Task, T
         ));
 
         let mut next_state = apply_events(&loaded.state, &events)?;
-        append_events(&ctx.repo_root, &events)?;
-        persist_projection(
-            &ctx.repo_root,
+        commit_service_events(
+            ctx,
+            &events,
             &mut next_state,
             loaded.event_count + events.len(),
-            None,
         )?;
         must_task(&next_state, &source)
     })
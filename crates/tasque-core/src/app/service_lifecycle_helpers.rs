@@ -0,0 +1,43 @@
+use crate::errors::TsqError;
+use crate::types::{Task, TaskStatus};
+use serde_json::{Map, Value};
+
+pub fn payload_map(value: Value) -> Map<String, Value> {
+    match value.as_object() {
+        Some(map) => map.clone(),
+        None => Map::new(),
+    }
+}
+
+/// Checks that `task` can be reopened from `from` (closed unless canceled is
+/// asked for explicitly, so a premature cancel is never revived by accident).
+pub fn validate_reopen(id: &str, task: &Task, from: Option<&TaskStatus>) -> Result<(), TsqError> {
+    let from = from.unwrap_or(&TaskStatus::Closed);
+    if !matches!(from, TaskStatus::Closed | TaskStatus::Canceled) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!(
+                "reopen from must be closed or canceled, got {}",
+                from.as_str()
+            ),
+            1,
+        ));
+    }
+    if task.status == *from {
+        return Ok(());
+    }
+    let message = if task.status == TaskStatus::Canceled {
+        format!(
+            "cannot reopen canceled task {}; pass --from canceled to revive it",
+            id
+        )
+    } else {
+        format!(
+            "cannot reopen task {} with status {}",
+            id,
+            task.status.as_str()
+        )
+    };
+    let details = serde_json::json!({ "task_id": id, "status": task.status, "from": from });
+    Err(TsqError::new("VALIDATION_ERROR", message, 1).with_details(details))
+}
@@ -0,0 +1,424 @@
+use super::service_lifecycle_helpers::payload_map;
+use crate::app::repair::scan_orphaned_graph;
+use crate::app::service_types::{
+    CustomLinkInput, DepImportEdge, DepImportInput, DepImportResult, DepInput, LinkInput,
+    OrphansAdoptInput, OrphansAdoptResult, ServiceContext,
+};
+use crate::app::service_utils::must_resolve_existing;
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::deps::is_cycle_checked;
+use crate::domain::events::make_event;
+use crate::domain::projector::apply_events;
+use crate::domain::validate::assert_no_dependency_cycle;
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::types::{DependencyType, EventType, RelationType, RepairDep, RepairLink, State};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub fn dep_add(
+    ctx: &ServiceContext,
+    input: &DepInput,
+) -> Result<(String, String, DependencyType), TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let child = must_resolve_existing(&loaded.state, &input.child, input.exact_id)?;
+        let blocker = must_resolve_existing(&loaded.state, &input.blocker, input.exact_id)?;
+        let dep_type = input.dep_type.unwrap_or(DependencyType::Blocks);
+        if child == blocker {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "task cannot depend on itself",
+                1,
+            ));
+        }
+        if is_cycle_checked(dep_type) {
+            assert_no_dependency_cycle(&loaded.state, &child, &blocker)?;
+        }
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::DepAdded,
+            &child,
+            payload_map(serde_json::json!({"blocker": blocker, "dep_type": dep_type})),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok((child, blocker, dep_type))
+    })
+}
+
+/// Validate every edge against the state as it grows, then append them all
+/// in one commit. Any failing line aborts the whole import.
+pub fn dep_import(
+    ctx: &ServiceContext,
+    input: &DepImportInput,
+) -> Result<DepImportResult, TsqError> {
+    if input.edges.is_empty() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "dependency import requires at least one edge",
+            1,
+        ));
+    }
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let mut state = loaded.state.clone();
+        let mut events = Vec::new();
+        let mut added = Vec::new();
+        let mut skipped = 0;
+        let mut failures = Vec::new();
+
+        for edge in &input.edges {
+            let outcome = resolve_import_edge(&state, edge, input.exact_id).and_then(|resolved| {
+                let exists = state.deps.get(&resolved.child).is_some_and(|edges| {
+                    edges.iter().any(|existing| {
+                        existing.blocker == resolved.blocker
+                            && existing.dep_type == resolved.dep_type
+                    })
+                });
+                if exists {
+                    return Ok(None);
+                }
+                let event = make_event(
+                    &ctx.actor,
+                    &ctx.now.as_ref()(),
+                    EventType::DepAdded,
+                    &resolved.child,
+                    payload_map(serde_json::json!({
+                        "blocker": resolved.blocker,
+                        "dep_type": resolved.dep_type,
+                    })),
+                );
+                state = apply_events(&state, std::slice::from_ref(&event))?;
+                Ok(Some((resolved, event)))
+            });
+            match outcome {
+                Ok(Some((resolved, event))) => {
+                    events.push(event);
+                    added.push(resolved);
+                }
+                Ok(None) => skipped += 1,
+                Err(error) => failures.push(serde_json::json!({
+                    "line": edge.line,
+                    "code": error.code,
+                    "message": error.message,
+                })),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "dependency import rejected: {} of {} edges failed validation",
+                    failures.len(),
+                    input.edges.len()
+                ),
+                1,
+            )
+            .with_details(serde_json::json!({ "errors": failures })));
+        }
+        if !events.is_empty() {
+            let event_count = loaded.event_count + events.len();
+            commit_service_events(ctx, &events, &mut state, event_count)?;
+        }
+        Ok(DepImportResult { added, skipped })
+    })
+}
+
+fn resolve_import_edge(
+    state: &State,
+    edge: &DepImportEdge,
+    exact_id: bool,
+) -> Result<DepImportEdge, TsqError> {
+    let child = must_resolve_existing(state, &edge.child, exact_id)?;
+    let blocker = must_resolve_existing(state, &edge.blocker, exact_id)?;
+    if child == blocker {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "task cannot depend on itself",
+            1,
+        ));
+    }
+    if is_cycle_checked(edge.dep_type) {
+        assert_no_dependency_cycle(state, &child, &blocker)?;
+    }
+    Ok(DepImportEdge {
+        line: edge.line,
+        child,
+        blocker,
+        dep_type: edge.dep_type,
+    })
+}
+
+pub fn dep_remove(
+    ctx: &ServiceContext,
+    input: &DepInput,
+) -> Result<(String, String, DependencyType), TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let child = must_resolve_existing(&loaded.state, &input.child, input.exact_id)?;
+        let blocker = must_resolve_existing(&loaded.state, &input.blocker, input.exact_id)?;
+        let dep_type = input.dep_type.unwrap_or(DependencyType::Blocks);
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::DepRemoved,
+            &child,
+            payload_map(serde_json::json!({"blocker": blocker, "dep_type": dep_type})),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok((child, blocker, dep_type))
+    })
+}
+
+pub fn link_add(
+    ctx: &ServiceContext,
+    input: &LinkInput,
+) -> Result<(String, String, RelationType), TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let src = must_resolve_existing(&loaded.state, &input.src, input.exact_id)?;
+        let dst = must_resolve_existing(&loaded.state, &input.dst, input.exact_id)?;
+        if src == dst {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "self-edge not allowed",
+                1,
+            ));
+        }
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::LinkAdded,
+            &src,
+            payload_map(serde_json::json!({"type": input.rel_type, "target": dst})),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok((src, dst, input.rel_type))
+    })
+}
+
+pub fn link_remove(
+    ctx: &ServiceContext,
+    input: &LinkInput,
+) -> Result<(String, String, RelationType), TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let src = must_resolve_existing(&loaded.state, &input.src, input.exact_id)?;
+        let dst = must_resolve_existing(&loaded.state, &input.dst, input.exact_id)?;
+        if src == dst {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "self-edge not allowed",
+                1,
+            ));
+        }
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            EventType::LinkRemoved,
+            &src,
+            payload_map(serde_json::json!({"type": input.rel_type, "target": dst})),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok((src, dst, input.rel_type))
+    })
+}
+
+pub fn custom_link_add(
+    ctx: &ServiceContext,
+    input: &CustomLinkInput,
+) -> Result<(String, String, String), TsqError> {
+    custom_link_mutation(ctx, input, EventType::LinkAdded)
+}
+
+pub fn custom_link_remove(
+    ctx: &ServiceContext,
+    input: &CustomLinkInput,
+) -> Result<(String, String, String), TsqError> {
+    custom_link_mutation(ctx, input, EventType::LinkRemoved)
+}
+
+fn custom_link_mutation(
+    ctx: &ServiceContext,
+    input: &CustomLinkInput,
+    event_type: EventType,
+) -> Result<(String, String, String), TsqError> {
+    let relation_types = read_config(&ctx.repo_root)?.relation_types;
+    let inverse = custom_relation_inverse(&relation_types, &input.rel_type).ok_or_else(|| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            format!(
+                "unknown relation type {}; define it under relation_types in .tasque/config.json",
+                input.rel_type
+            ),
+            1,
+        )
+        .with_details(serde_json::json!({ "relation_types": relation_types }))
+    })?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let src = must_resolve_existing(&loaded.state, &input.src, input.exact_id)?;
+        let dst = must_resolve_existing(&loaded.state, &input.dst, input.exact_id)?;
+        if src == dst {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "self-edge not allowed",
+                1,
+            ));
+        }
+        let event = make_event(
+            &ctx.actor,
+            &ctx.now.as_ref()(),
+            event_type,
+            &src,
+            payload_map(serde_json::json!({
+                "type": input.rel_type,
+                "inverse": inverse,
+                "target": dst,
+            })),
+        );
+        let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
+        Ok((src, dst, input.rel_type.clone()))
+    })
+}
+
+/// Rewrites orphaned deps and links onto the surviving tasks named in
+/// `map` (missing id -> surviving task), then removes every orphaned edge
+/// that is still dangling, all in one locked batch.
+pub fn orphans_adopt(
+    ctx: &ServiceContext,
+    input: &OrphansAdoptInput,
+) -> Result<OrphansAdoptResult, TsqError> {
+    if input.map.is_empty() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "at least one old-id=new-id mapping is required",
+            1,
+        ));
+    }
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let state = &loaded.state;
+        let scan = scan_orphaned_graph(state);
+        let missing: BTreeSet<&str> = scan
+            .orphaned_deps
+            .iter()
+            .flat_map(|dep| [dep.child.as_str(), dep.blocker.as_str()])
+            .chain(
+                scan.orphaned_links
+                    .iter()
+                    .flat_map(|link| [link.src.as_str(), link.dst.as_str()]),
+            )
+            .filter(|id| !state.tasks.contains_key(*id))
+            .collect();
+        let mut map = BTreeMap::new();
+        for (old, new) in &input.map {
+            if !missing.contains(old.as_str()) {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("no orphaned dep or link points at missing task {}", old),
+                    1,
+                )
+                .with_details(serde_json::json!({ "old": old, "missing": missing })));
+            }
+            map.insert(
+                old.clone(),
+                must_resolve_existing(state, new, input.exact_id)?,
+            );
+        }
+        let adopt = |id: &str| map.get(id).cloned().unwrap_or_else(|| id.to_string());
+        let survives = |src: &str, dst: &str| {
+            src != dst && state.tasks.contains_key(src) && state.tasks.contains_key(dst)
+        };
+
+        let now = ctx.now.as_ref()();
+        let mut events = Vec::new();
+        let mut result = OrphansAdoptResult {
+            map: map.clone(),
+            adopted_deps: Vec::new(),
+            adopted_links: Vec::new(),
+            removed_deps: Vec::new(),
+            removed_links: Vec::new(),
+        };
+        for dep in scan.orphaned_deps {
+            events.push(make_event(
+                &ctx.actor,
+                &now,
+                EventType::DepRemoved,
+                &dep.child,
+                payload_map(serde_json::json!({"blocker": dep.blocker, "dep_type": dep.dep_type})),
+            ));
+            let (child, blocker) = (adopt(&dep.child), adopt(&dep.blocker));
+            if !survives(&child, &blocker) {
+                result.removed_deps.push(dep);
+                continue;
+            }
+            events.push(make_event(
+                &ctx.actor,
+                &now,
+                EventType::DepAdded,
+                &child,
+                payload_map(serde_json::json!({"blocker": blocker, "dep_type": dep.dep_type})),
+            ));
+            result.adopted_deps.push(RepairDep {
+                child,
+                blocker,
+                dep_type: dep.dep_type,
+            });
+        }
+        for link in scan.orphaned_links {
+            events.push(make_event(
+                &ctx.actor,
+                &now,
+                EventType::LinkRemoved,
+                &link.src,
+                payload_map(serde_json::json!({"type": link.rel_type, "target": link.dst})),
+            ));
+            let (src, dst) = (adopt(&link.src), adopt(&link.dst));
+            if !survives(&src, &dst) {
+                result.removed_links.push(link);
+                continue;
+            }
+            events.push(make_event(
+                &ctx.actor,
+                &now,
+                EventType::LinkAdded,
+                &src,
+                payload_map(serde_json::json!({"type": link.rel_type, "target": dst})),
+            ));
+            result.adopted_links.push(RepairLink {
+                src,
+                dst,
+                rel_type: link.rel_type,
+            });
+        }
+        if events.is_empty() {
+            return Ok(result);
+        }
+        let mut next_state = apply_events(state, &events)?;
+        commit_service_events(
+            ctx,
+            &events,
+            &mut next_state,
+            loaded.event_count + events.len(),
+        )?;
+        Ok(result)
+    })
+}
+
+/// Custom types can be used by either configured name; the other one is the
+/// inverse recorded on the target.
+fn custom_relation_inverse(types: &BTreeMap<String, String>, name: &str) -> Option<String> {
+    types.get(name).cloned().or_else(|| {
+        types
+            .iter()
+            .find(|(_, inverse)| inverse.as_str() == name)
+            .map(|(forward, _)| forward.clone())
+    })
+}
@@ -1,21 +1,22 @@
-use super::service_lifecycle_helpers::{payload_map, status_to_string};
+use super::service_lifecycle_helpers::payload_map;
+use crate::app::permissions::require_role;
 use crate::app::service_types::{
-    DuplicateCandidateGroup, DuplicateCandidatesResult, MergeInput, MergeItem, MergeProjected,
-    MergeResult, MergeSummary, MergeTarget, ServiceContext,
+    DuplicateCandidateGroup, DuplicateCandidatesResult, MergeField, MergeFieldStrategy, MergeInput,
+    MergeItem, MergeProjected, MergeResult, MergeSummary, MergeTarget, ServiceContext,
 };
 use crate::app::service_utils::{
     creates_duplicate_cycle, has_duplicate_link, must_resolve_existing, must_task,
     normalize_duplicate_title, sort_tasks,
 };
-use crate::app::storage::{
-    append_events, load_projected_state, persist_projection, with_write_lock,
-};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
 use crate::domain::events::make_event;
 use crate::domain::projector::apply_events;
 use crate::errors::TsqError;
-use crate::types::{EventRecord, EventType, RelationType, Task, TaskStatus};
+use crate::types::{ActorRole, EventRecord, EventType, RelationType, Task, TaskStatus};
+use std::collections::BTreeMap;
 
 pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, TsqError> {
+    require_role(ctx, ActorRole::Admin, "tsq merge")?;
     if input.sources.is_empty() {
         return Err(TsqError::new(
             "VALIDATION_ERROR",
@@ -23,9 +24,10 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
             1,
         ));
     }
+    validate_merge_strategy(&input.strategy)?;
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
 
         let target_id = must_resolve_existing(&loaded.state, &input.into, input.exact_id)?;
         let target_task = must_task(&loaded.state, &target_id)?;
@@ -41,7 +43,7 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
                 format!(
                     "target task {} is {}; use --force to merge anyway",
                     target_id,
-                    status_to_string(target_task.status)
+                    target_task.status.as_str()
                 ),
                 1,
             ));
@@ -54,7 +56,7 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
             warnings.push(format!(
                 "target {} is {} (forced)",
                 target_id,
-                status_to_string(target_task.status)
+                target_task.status.as_str()
             ));
         }
 
@@ -87,7 +89,7 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
                 warnings.push(format!(
                     "{} already {}, skipped",
                     source_id,
-                    status_to_string(source_task.status)
+                    source_task.status.as_str()
                 ));
                 continue;
             }
@@ -112,12 +114,19 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
                 ));
             }
 
+            let mut duplicate_payload = payload_map(serde_json::json!({"duplicate_of": target_id}));
+            if !input.strategy.is_empty() {
+                duplicate_payload.insert(
+                    "merge_strategy".to_string(),
+                    serde_json::json!(input.strategy),
+                );
+            }
             events.push(make_event(
                 &ctx.actor,
                 &ctx.now.as_ref()(),
                 EventType::TaskUpdated,
                 source_id,
-                payload_map(serde_json::json!({"duplicate_of": target_id})),
+                duplicate_payload,
             ));
 
             let ts = ctx.now.as_ref()();
@@ -145,12 +154,43 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
             });
         }
 
+        let merged_tasks = merged
+            .iter()
+            .map(|item| must_task(&loaded.state, &item.id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut target_fields = merged_target_fields(&target_task, &merged_tasks, &input.strategy);
+        if !target_fields.is_empty() {
+            target_fields.insert(
+                "merge_strategy".to_string(),
+                serde_json::json!(input.strategy),
+            );
+            target_fields.insert(
+                "merged_from".to_string(),
+                serde_json::json!(merged.iter().map(|item| &item.id).collect::<Vec<_>>()),
+            );
+            events.push(make_event(
+                &ctx.actor,
+                &ctx.now.as_ref()(),
+                EventType::TaskUpdated,
+                &target_id,
+                target_fields,
+            ));
+        }
+
         if input.dry_run {
-            let projected_state = if events.is_empty() {
+            let mut projected_state = if events.is_empty() {
                 loaded.state.clone()
             } else {
                 apply_events(&loaded.state, &events)?
             };
+            if ctx.staged.is_some() && !events.is_empty() {
+                commit_service_events(
+                    ctx,
+                    &events,
+                    &mut projected_state,
+                    loaded.event_count + events.len(),
+                )?;
+            }
             let proj_target = must_task(&projected_state, &target_id)?;
             let projected_sources: Vec<Task> = resolved_sources
                 .iter()
@@ -163,10 +203,11 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
                 target: MergeTarget {
                     id: target_id.clone(),
                     title: proj_target.title.clone(),
-                    status: status_to_string(proj_target.status).to_string(),
+                    status: proj_target.status.as_str().to_string(),
                 },
                 dry_run: true,
                 warnings,
+                strategy: input.strategy.clone(),
                 plan_summary: Some(MergeSummary {
                     requested_sources: resolved_sources.len(),
                     merged_sources,
@@ -182,12 +223,11 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
 
         if !events.is_empty() {
             let mut next_state = apply_events(&loaded.state, &events)?;
-            append_events(&ctx.repo_root, &events)?;
-            persist_projection(
-                &ctx.repo_root,
+            commit_service_events(
+                ctx,
+                &events,
                 &mut next_state,
                 loaded.event_count + events.len(),
-                None,
             )?;
             let final_target = must_task(&next_state, &target_id)?;
             return Ok(MergeResult {
@@ -195,10 +235,11 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
                 target: MergeTarget {
                     id: target_id,
                     title: final_target.title,
-                    status: status_to_string(final_target.status).to_string(),
+                    status: final_target.status.as_str().to_string(),
                 },
                 dry_run: false,
                 warnings,
+                strategy: input.strategy.clone(),
                 plan_summary: None,
                 projected: None,
             });
@@ -209,16 +250,117 @@ pub fn merge(ctx: &ServiceContext, input: &MergeInput) -> Result<MergeResult, Ts
             target: MergeTarget {
                 id: target_id,
                 title: target_task.title,
-                status: status_to_string(target_task.status).to_string(),
+                status: target_task.status.as_str().to_string(),
             },
             dry_run: false,
             warnings,
+            strategy: input.strategy.clone(),
             plan_summary: None,
             projected: None,
         })
     })
 }
 
+fn validate_merge_strategy(
+    strategy: &BTreeMap<MergeField, MergeFieldStrategy>,
+) -> Result<(), TsqError> {
+    for (field, chosen) in strategy {
+        let allowed: &[MergeFieldStrategy] = match field {
+            MergeField::Description => &[MergeFieldStrategy::Keep, MergeFieldStrategy::Concat],
+            MergeField::Labels => &[MergeFieldStrategy::Keep, MergeFieldStrategy::Union],
+            MergeField::Priority => &[
+                MergeFieldStrategy::Keep,
+                MergeFieldStrategy::Min,
+                MergeFieldStrategy::Max,
+            ],
+        };
+        if !allowed.contains(chosen) {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "merge strategy {} is not valid for {}",
+                    enum_name(chosen),
+                    enum_name(field)
+                ),
+                1,
+            )
+            .with_details(serde_json::json!({
+                "field": field,
+                "strategy": chosen,
+                "allowed": allowed,
+            })));
+        }
+    }
+    Ok(())
+}
+
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(ToString::to_string))
+        .unwrap_or_default()
+}
+
+/// Target fields that change once merged sources are folded in per strategy.
+fn merged_target_fields(
+    target: &Task,
+    sources: &[Task],
+    strategy: &BTreeMap<MergeField, MergeFieldStrategy>,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut fields = serde_json::Map::new();
+    if sources.is_empty() {
+        return fields;
+    }
+    for (field, chosen) in strategy {
+        match (field, chosen) {
+            (MergeField::Description, MergeFieldStrategy::Concat) => {
+                let mut parts: Vec<&str> = Vec::new();
+                for description in std::iter::once(target)
+                    .chain(sources)
+                    .filter_map(|task| task.description.as_deref())
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                {
+                    if !parts.contains(&description) {
+                        parts.push(description);
+                    }
+                }
+                let joined = parts.join("\n\n");
+                if !joined.is_empty() && target.description.as_deref() != Some(joined.as_str()) {
+                    fields.insert("description".to_string(), serde_json::json!(joined));
+                }
+            }
+            (MergeField::Labels, MergeFieldStrategy::Union) => {
+                let mut labels = target.labels.clone();
+                for label in sources.iter().flat_map(|task| &task.labels) {
+                    if !labels.contains(label) {
+                        labels.push(label.clone());
+                    }
+                }
+                labels.sort();
+                if labels.len() != target.labels.len() {
+                    fields.insert("labels".to_string(), serde_json::json!(labels));
+                }
+            }
+            (MergeField::Priority, MergeFieldStrategy::Min | MergeFieldStrategy::Max) => {
+                let priorities = std::iter::once(target)
+                    .chain(sources)
+                    .map(|task| task.priority);
+                let picked = if *chosen == MergeFieldStrategy::Min {
+                    priorities.min()
+                } else {
+                    priorities.max()
+                };
+                if let Some(priority) = picked.filter(|priority| *priority != target.priority) {
+                    fields.insert("priority".to_string(), serde_json::json!(priority));
+                }
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
 pub fn duplicate_candidates(
     ctx: &ServiceContext,
     limit: usize,
@@ -231,7 +373,7 @@ pub fn duplicate_candidates(
         ));
     }
 
-    let loaded = load_projected_state(&ctx.repo_root)?;
+    let loaded = load_service_state(ctx)?;
     let candidates: Vec<Task> = loaded
         .state
         .tasks
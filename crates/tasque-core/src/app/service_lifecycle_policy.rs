@@ -0,0 +1,177 @@
+use crate::app::service_types::ServiceContext;
+use crate::app::storage::read_config;
+use crate::app::workflow::ensure_status_transitions;
+use crate::errors::TsqError;
+use crate::types::{EventRecord, EventType, PolicyConfig, PolicyRequirement, State, Task};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Checks the configured status workflow and policies for every task whose
+/// status or planning state changes between `before` and `after`. The first
+/// task that misses a required field fails with `POLICY_VIOLATION`.
+pub fn enforce_policies(
+    ctx: &ServiceContext,
+    before: &State,
+    events: &[EventRecord],
+    after: &State,
+) -> Result<(), TsqError> {
+    let touched: BTreeSet<&str> = events
+        .iter()
+        .filter(|event| {
+            matches!(
+                event.event_type,
+                EventType::TaskCreated
+                    | EventType::TaskUpdated
+                    | EventType::TaskStatusSet
+                    | EventType::TaskClaimed
+            )
+        })
+        .map(|event| event.task_id.as_str())
+        .collect();
+    if touched.is_empty() {
+        return Ok(());
+    }
+    let config = read_config(&ctx.repo_root)?;
+    ensure_status_transitions(&config, before, &touched, after)?;
+    let policies = config.policies;
+    if policies.is_empty() {
+        return Ok(());
+    }
+
+    for id in touched {
+        let Some(task) = after.tasks.get(id) else {
+            continue;
+        };
+        let previous = before.tasks.get(id);
+        for policy in &policies {
+            let Some(transition) = triggered_transition(policy, previous, task) else {
+                continue;
+            };
+            let missing: Vec<&PolicyRequirement> = policy
+                .require
+                .iter()
+                .filter(|requirement| !is_satisfied(requirement, task, events))
+                .collect();
+            if !missing.is_empty() {
+                return Err(violation(policy, task, &transition, &missing));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn triggered_transition(
+    policy: &PolicyConfig,
+    previous: Option<&Task>,
+    task: &Task,
+) -> Option<String> {
+    if policy.when.kind.is_some_and(|kind| kind != task.kind) {
+        return None;
+    }
+    if let Some(status) = policy.when.status.as_ref()
+        && task.status == *status
+        && previous.is_none_or(|previous| previous.status != *status)
+    {
+        return Some(format!("status={}", status.as_str()));
+    }
+    if let Some(planning_state) = policy.when.planning_state.as_ref()
+        && task.planning_state.as_ref() == Some(planning_state)
+        && previous.is_none_or(|previous| previous.planning_state.as_ref() != Some(planning_state))
+    {
+        return Some(format!("planning_state={}", planning_state.as_str()));
+    }
+    None
+}
+
+fn is_satisfied(requirement: &PolicyRequirement, task: &Task, events: &[EventRecord]) -> bool {
+    match requirement {
+        PolicyRequirement::Label(None) => !task.labels.is_empty(),
+        PolicyRequirement::Label(Some(pattern)) => task
+            .labels
+            .iter()
+            .any(|label| label_matches(pattern, label)),
+        PolicyRequirement::Description => task
+            .description
+            .as_deref()
+            .is_some_and(|description| !description.trim().is_empty()),
+        PolicyRequirement::Spec => task.spec_path.is_some(),
+        PolicyRequirement::Assignee => task.assignee.is_some(),
+        PolicyRequirement::ExternalRef => task.external_ref.is_some(),
+        PolicyRequirement::Parent => task.parent_id.is_some(),
+        PolicyRequirement::Reason => events.iter().any(|event| {
+            event.task_id == task.id
+                && match event.event_type {
+                    EventType::TaskStatusSet => has_text(event, "reason"),
+                    EventType::TaskNoted => has_text(event, "text"),
+                    _ => false,
+                }
+        }),
+    }
+}
+
+fn label_matches(pattern: &str, label: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => label.starts_with(prefix),
+        None => label == pattern,
+    }
+}
+
+fn has_text(event: &EventRecord, key: &str) -> bool {
+    event
+        .payload
+        .get(key)
+        .and_then(Value::as_str)
+        .is_some_and(|text| !text.trim().is_empty())
+}
+
+fn violation(
+    policy: &PolicyConfig,
+    task: &Task,
+    transition: &str,
+    missing: &[&PolicyRequirement],
+) -> TsqError {
+    let names: Vec<String> = missing
+        .iter()
+        .map(|requirement| String::from((*requirement).clone()))
+        .collect();
+    let hints: Vec<String> = missing
+        .iter()
+        .map(|requirement| requirement_hint(requirement, &task.id))
+        .collect();
+    TsqError::new(
+        "POLICY_VIOLATION",
+        format!(
+            "policy '{}' blocks {} moving to {}: missing {}",
+            policy.name,
+            task.id,
+            transition,
+            names.join(", ")
+        ),
+        1,
+    )
+    .with_details(serde_json::json!({
+        "policy": policy.name,
+        "task_id": task.id,
+        "transition": transition,
+        "missing": names,
+        "hints": hints,
+    }))
+}
+
+fn requirement_hint(requirement: &PolicyRequirement, id: &str) -> String {
+    match requirement {
+        PolicyRequirement::Label(None) => format!("tsq label {} <label>", id),
+        PolicyRequirement::Label(Some(pattern)) => match pattern.strip_suffix('*') {
+            Some(prefix) => format!("tsq label {} {}<value>", id, prefix),
+            None => format!("tsq label {} {}", id, pattern),
+        },
+        PolicyRequirement::Description => format!("tsq edit {} --description \"<text>\"", id),
+        PolicyRequirement::Spec => format!("tsq spec {} --file <path>", id),
+        PolicyRequirement::Assignee => format!("tsq assign {} --assignee <name>", id),
+        PolicyRequirement::ExternalRef => format!("tsq edit {} --external-ref <ref>", id),
+        PolicyRequirement::Parent => {
+            "recreate under a parent with `tsq create --parent <id>`".to_string()
+        }
+        PolicyRequirement::Reason => "pass --reason \"<why>\" or --note \"<why>\"".to_string(),
+    }
+}
@@ -0,0 +1,479 @@
+use super::service_lifecycle_helpers::{payload_map, validate_reopen};
+use super::service_lifecycle_policy::enforce_policies;
+use crate::app::service_query::query_tasks;
+use crate::app::service_types::{
+    CriteriaResult, LifecycleStatusInput, LifecycleStatusResult, NoteAddResult, ServiceContext,
+};
+use crate::app::service_utils::{must_resolve_existing, must_task};
+use crate::app::storage::{LoadedState, read_task_acceptance_criteria};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::dep_tree::{DependentEffect, dependent_impacts};
+use crate::domain::events::make_event;
+use crate::domain::labels::add_label;
+use crate::domain::projector::apply_events;
+use crate::domain::query::parse_query;
+use crate::domain::rollup::completed_parents;
+use crate::domain::validate::unfinished_finish_blockers;
+use crate::errors::TsqError;
+use crate::store::config::{read_config, read_status_workflow};
+use crate::store::paths::get_paths;
+use crate::types::{EventRecord, EventType, Task, TaskStatus};
+use serde_json::Value;
+
+pub fn set_lifecycle_status(
+    ctx: &ServiceContext,
+    input: &LifecycleStatusInput,
+) -> Result<LifecycleStatusResult, TsqError> {
+    if input.ids.is_empty() && input.filter.is_none() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "at least one task id is required",
+            1,
+        ));
+    }
+    if !input.ids.is_empty() && input.filter.is_some() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "pass task ids or a filter, not both",
+            1,
+        ));
+    }
+    let filter = input.filter.as_deref().map(parse_query).transpose()?;
+
+    let note = input
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(ToString::to_string);
+
+    if input.reopen_from.is_some() && input.status != TaskStatus::Open {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "reopen_from is only valid when reopening a task",
+            1,
+        ));
+    }
+    if input.cascade_defer && input.status != TaskStatus::Canceled {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "cascade_defer is only valid when canceling a task",
+            1,
+        ));
+    }
+    if input.defer_until.is_some() && input.status != TaskStatus::Deferred {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "defer_until is only valid when deferring a task",
+            1,
+        ));
+    }
+
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let resolved_ids: Vec<String> = match filter.as_ref() {
+            Some(filter) => {
+                let mut ids: Vec<String> = query_tasks(ctx, &loaded.state, filter)?
+                    .into_iter()
+                    .filter(|task| {
+                        !matches!(task.status, TaskStatus::Closed | TaskStatus::Canceled)
+                    })
+                    .map(|task| task.id)
+                    .collect();
+                ids.sort();
+                ids
+            }
+            None => input
+                .ids
+                .iter()
+                .map(|id| must_resolve_existing(&loaded.state, id, input.exact_id))
+                .collect::<Result<_, _>>()?,
+        };
+        if resolved_ids.is_empty() {
+            return Ok(LifecycleStatusResult {
+                tasks: Vec::new(),
+                notes: Vec::new(),
+                open_criteria: Vec::new(),
+                dependents: Vec::new(),
+                deferred: Vec::new(),
+            });
+        }
+
+        let mut open_criteria = Vec::new();
+        for id in &resolved_ids {
+            let task = must_task(&loaded.state, id)?;
+            if input.status == TaskStatus::Open {
+                validate_reopen(id, &task, input.reopen_from.as_ref())?;
+            } else {
+                validate_lifecycle_status(id, &task, &input.status)?;
+            }
+            if input.status == TaskStatus::Closed {
+                let criteria = read_task_acceptance_criteria(&ctx.repo_root, &task)?;
+                let open = criteria
+                    .iter()
+                    .filter(|criterion| !criterion.checked)
+                    .count();
+                if open > 0 {
+                    open_criteria.push(CriteriaResult {
+                        task_id: id.clone(),
+                        criteria,
+                        open,
+                    });
+                }
+            }
+        }
+        if input.status == TaskStatus::Closed {
+            for id in &resolved_ids {
+                // Blockers closed by this same call count as finished.
+                let unfinished = unfinished_finish_blockers(&loaded.state, id)
+                    .into_iter()
+                    .filter(|blocker| !resolved_ids.contains(blocker))
+                    .collect::<Vec<_>>();
+                if !unfinished.is_empty() {
+                    return Err(TsqError::new(
+                        "FINISH_DEPENDENCY_OPEN",
+                        format!(
+                            "task {} cannot close before its finish_to_finish blockers: {}",
+                            id,
+                            unfinished.join(", ")
+                        ),
+                        1,
+                    )
+                    .with_details(serde_json::json!({
+                        "task_id": id,
+                        "blockers": unfinished,
+                    })));
+                }
+            }
+        }
+        if input.require_criteria
+            && let Some(first) = open_criteria.first()
+        {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "task {} has {} open acceptance criteria; check them with tsq criteria check or drop --require-criteria",
+                    first.task_id, first.open
+                ),
+                1,
+            )
+            .with_details(serde_json::json!({ "open_criteria": open_criteria })));
+        }
+
+        let mut events: Vec<EventRecord> = Vec::with_capacity(
+            resolved_ids.len() + note.as_ref().map(|_| resolved_ids.len()).unwrap_or(0),
+        );
+        for id in &resolved_ids {
+            let ts = ctx.now.as_ref()();
+            let mut payload = payload_map(serde_json::json!({
+                "status": input.status,
+                "closed_at": if input.status == TaskStatus::Closed {
+                    Some(ts.clone())
+                } else {
+                    None
+                },
+            }));
+            if input.status == TaskStatus::Closed
+                && let Some(reason) = input.reason.as_ref()
+            {
+                payload.insert("reason".to_string(), Value::String(reason.clone()));
+            }
+            if let Some(defer_until) = input.defer_until.as_ref() {
+                payload.insert(
+                    "defer_until".to_string(),
+                    Value::String(defer_until.clone()),
+                );
+            }
+            if input.status == TaskStatus::Open {
+                let prior = must_task(&loaded.state, id)?.status;
+                payload.insert("reopened_from".to_string(), serde_json::json!(prior));
+            }
+            events.push(make_event(
+                &ctx.actor,
+                &ts,
+                EventType::TaskStatusSet,
+                id,
+                payload,
+            ));
+        }
+        if let Some(note) = note.as_ref() {
+            for id in &resolved_ids {
+                events.push(make_event(
+                    &ctx.actor,
+                    &ctx.now.as_ref()(),
+                    EventType::TaskNoted,
+                    id,
+                    payload_map(serde_json::json!({ "text": note })),
+                ));
+            }
+        }
+
+        let mut next_state = apply_events(&loaded.state, &events)?;
+        let dependents = if matches!(input.status, TaskStatus::Closed | TaskStatus::Canceled) {
+            dependent_impacts(&next_state, &resolved_ids)
+        } else {
+            Vec::new()
+        };
+        let mut deferred = Vec::new();
+        if input.cascade_defer {
+            for impact in &dependents {
+                let dependent = must_task(&next_state, &impact.task_id)?;
+                if impact.effect != DependentEffect::Orphaned
+                    || dependent.status == TaskStatus::Deferred
+                {
+                    continue;
+                }
+                events.push(make_event(
+                    &ctx.actor,
+                    &ctx.now.as_ref()(),
+                    EventType::TaskStatusSet,
+                    &impact.task_id,
+                    payload_map(serde_json::json!({
+                        "status": TaskStatus::Deferred,
+                        "deferred_by": impact.blocker,
+                    })),
+                ));
+                deferred.push(impact.task_id.clone());
+            }
+            if !deferred.is_empty() {
+                next_state = apply_events(&loaded.state, &events)?;
+            }
+        }
+        enforce_policies(ctx, &loaded.state, &events, &next_state)?;
+        commit_service_events(
+            ctx,
+            &events,
+            &mut next_state,
+            loaded.event_count + events.len(),
+        )?;
+
+        let tasks = resolved_ids
+            .iter()
+            .map(|id| must_task(&next_state, id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let notes = note_results(&next_state, &events)?;
+        Ok(LifecycleStatusResult {
+            tasks,
+            notes,
+            open_criteria,
+            dependents,
+            deferred,
+        })
+    })
+}
+
+/// Reopens deferred tasks whose `defer_until` has passed, recording a
+/// `task.status_set` per task. Runs at the start of every write, with the
+/// lock already held; returns the ids that were woken.
+pub fn wake_snoozed_tasks(ctx: &ServiceContext) -> Result<Vec<String>, TsqError> {
+    let loaded = load_service_state(ctx)?;
+    let now = ctx.now.as_ref()();
+    let events = wake_events(ctx, &loaded.state, &now);
+    if events.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut next_state = apply_events(&loaded.state, &events)?;
+    commit_service_events(
+        ctx,
+        &events,
+        &mut next_state,
+        loaded.event_count + events.len(),
+    )?;
+    Ok(events.into_iter().map(|event| event.task_id).collect())
+}
+
+/// Shows due snoozes as already woken without writing anything; the events
+/// are recorded by the next write. Time-travel views are left as they were.
+pub fn with_due_wakes(ctx: &ServiceContext, loaded: LoadedState) -> Result<LoadedState, TsqError> {
+    if ctx.as_of.is_some() {
+        return Ok(loaded);
+    }
+    let events = wake_events(ctx, &loaded.state, &ctx.now.as_ref()());
+    if events.is_empty() {
+        return Ok(loaded);
+    }
+    Ok(LoadedState {
+        state: apply_events(&loaded.state, &events)?,
+        ..loaded
+    })
+}
+
+/// A `status_workflow` that forbids `deferred -> open` keeps snoozes asleep.
+fn wake_events(ctx: &ServiceContext, state: &crate::types::State, now: &str) -> Vec<EventRecord> {
+    let workflow = read_status_workflow(&ctx.repo_root);
+    if workflow
+        .as_ref()
+        .is_some_and(|workflow| !workflow.allows(&TaskStatus::Deferred, &TaskStatus::Open))
+    {
+        return Vec::new();
+    }
+    due_snoozed_tasks(state, now)
+        .iter()
+        .map(|task| {
+            make_event(
+                &ctx.actor,
+                now,
+                EventType::TaskStatusSet,
+                &task.id,
+                payload_map(serde_json::json!({
+                    "status": TaskStatus::Open,
+                    "woke_from": task.defer_until,
+                })),
+            )
+        })
+        .collect()
+}
+
+/// Applies the `parent_completion` policy to features and epics whose last
+/// open child has closed, recording a status change, label, and note as
+/// configured. Runs at the end of every write, with the lock already held, so
+/// the close or cancel that finishes a parent also completes it. Returns the
+/// ids of the parents it touched.
+pub fn complete_parents(ctx: &ServiceContext) -> Result<Vec<String>, TsqError> {
+    if !get_paths(&ctx.repo_root).config_file.exists() {
+        return Ok(Vec::new());
+    }
+    let config = read_config(&ctx.repo_root)?;
+    let Some(policy) = config.parent_completion.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let loaded = load_service_state(ctx)?;
+    let now = ctx.now.as_ref()();
+    let mut events: Vec<EventRecord> = Vec::new();
+    let mut completed = Vec::new();
+    for parent in completed_parents(&loaded.state) {
+        // The status move is skipped when the workflow does not allow it.
+        if let Some(status) = policy.status.as_ref().filter(|status| {
+            **status != parent.status
+                && config
+                    .status_workflow
+                    .as_ref()
+                    .is_none_or(|workflow| workflow.allows(&parent.status, status))
+        }) {
+            events.push(make_event(
+                &ctx.actor,
+                &now,
+                EventType::TaskStatusSet,
+                &parent.id,
+                payload_map(serde_json::json!({
+                    "status": status,
+                    "closed_at": (*status == TaskStatus::Closed).then(|| now.clone()),
+                    "children_closed": true,
+                })),
+            ));
+        }
+        if let Some(label) = policy.label.as_deref() {
+            let labels = add_label(&parent.labels, label)?;
+            if labels != parent.labels {
+                events.push(make_event(
+                    &ctx.actor,
+                    &now,
+                    EventType::TaskUpdated,
+                    &parent.id,
+                    payload_map(serde_json::json!({ "labels": labels })),
+                ));
+            }
+        }
+        if let Some(note) = policy.note.as_deref() {
+            events.push(make_event(
+                &ctx.actor,
+                &now,
+                EventType::TaskNoted,
+                &parent.id,
+                payload_map(serde_json::json!({ "text": note })),
+            ));
+        }
+        completed.push(parent.id.clone());
+    }
+    if events.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut next_state = apply_events(&loaded.state, &events)?;
+    commit_service_events(
+        ctx,
+        &events,
+        &mut next_state,
+        loaded.event_count + events.len(),
+    )?;
+    Ok(completed)
+}
+
+fn due_snoozed_tasks(state: &crate::types::State, now: &str) -> Vec<Task> {
+    let Ok(now) = chrono::DateTime::parse_from_rfc3339(now) else {
+        return Vec::new();
+    };
+    let mut due: Vec<Task> = state
+        .tasks
+        .values()
+        .filter(|task| task.status == TaskStatus::Deferred)
+        .filter(|task| {
+            task.defer_until
+                .as_deref()
+                .and_then(|until| chrono::DateTime::parse_from_rfc3339(until).ok())
+                .is_some_and(|until| until <= now)
+        })
+        .cloned()
+        .collect();
+    due.sort_by(|a, b| a.id.cmp(&b.id));
+    due
+}
+
+fn validate_lifecycle_status(id: &str, task: &Task, status: &TaskStatus) -> Result<(), TsqError> {
+    match status {
+        TaskStatus::Closed => {
+            if task.status == TaskStatus::Closed {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("task {} is already closed", id),
+                    1,
+                ));
+            }
+            if task.status == TaskStatus::Canceled {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("cannot close canceled task {}", id),
+                    1,
+                ));
+            }
+        }
+        TaskStatus::InProgress if task.status == TaskStatus::Canceled => {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "cannot move canceled task to in_progress",
+                1,
+            ));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn note_results(
+    state: &crate::types::State,
+    events: &[EventRecord],
+) -> Result<Vec<NoteAddResult>, TsqError> {
+    let mut notes = Vec::new();
+    for event in events
+        .iter()
+        .filter(|event| event.event_type == EventType::TaskNoted)
+    {
+        let event_id = event
+            .id
+            .as_deref()
+            .or(event.event_id.as_deref())
+            .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "task note event missing id", 2))?;
+        let task = must_task(state, &event.task_id)?;
+        let note = task
+            .notes
+            .iter()
+            .find(|note| note.event_id == event_id)
+            .cloned()
+            .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "task note was not persisted", 2))?;
+        notes.push(NoteAddResult {
+            task_id: event.task_id.clone(),
+            note,
+            notes_count: task.notes.len(),
+        });
+    }
+    Ok(notes)
+}
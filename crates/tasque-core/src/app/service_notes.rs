@@ -2,9 +2,7 @@ use crate::app::service_types::{
     NoteAddInput, NoteAddResult, NoteListInput, NoteListResult, ServiceContext,
 };
 use crate::app::service_utils::{must_resolve_existing, must_task};
-use crate::app::storage::{
-    append_events, load_projected_state, persist_projection, with_write_lock,
-};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
 use crate::domain::events::make_event;
 use crate::domain::projector::apply_events;
 use crate::errors::TsqError;
@@ -20,8 +18,8 @@ pub fn note_add(ctx: &ServiceContext, input: &NoteAddInput) -> Result<NoteAddRes
         ));
     }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
         let event = make_event(
             &ctx.actor,
@@ -34,13 +32,7 @@ pub fn note_add(ctx: &ServiceContext, input: &NoteAddInput) -> Result<NoteAddRes
                 .unwrap_or_default(),
         );
         let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
-        append_events(&ctx.repo_root, &[event])?;
-        persist_projection(
-            &ctx.repo_root,
-            &mut next_state,
-            loaded.event_count + 1,
-            None,
-        )?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
         let task = must_task(&next_state, &id)?;
         let note = task
             .notes
@@ -57,7 +49,7 @@ pub fn note_add(ctx: &ServiceContext, input: &NoteAddInput) -> Result<NoteAddRes
 }
 
 pub fn note_list(ctx: &ServiceContext, input: &NoteListInput) -> Result<NoteListResult, TsqError> {
-    let loaded = load_projected_state(&ctx.repo_root)?;
+    let loaded = load_service_state(ctx)?;
     let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
     let task = must_task(&loaded.state, &id)?;
     Ok(NoteListResult {
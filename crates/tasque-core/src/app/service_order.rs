@@ -0,0 +1,132 @@
+use crate::app::service_types::{
+    ChildPlacement, OrderChildInput, OrderChildResult, ServiceContext,
+};
+use crate::app::service_utils::{must_resolve_existing, must_task, sort_child_tasks};
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::events::make_event;
+use crate::domain::projector::apply_events;
+use crate::errors::TsqError;
+use crate::types::{EventRecord, EventType, State, Task};
+
+/// Spacing between renumbered ranks, leaving room for later moves to land
+/// between two siblings with a single event.
+const RANK_GAP: i64 = 1024;
+
+pub fn order_child(
+    ctx: &ServiceContext,
+    input: &OrderChildInput,
+) -> Result<OrderChildResult, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let parent_id = must_resolve_existing(&loaded.state, &input.parent, input.exact_id)?;
+        let child_id = must_resolve_existing(&loaded.state, &input.child, input.exact_id)?;
+        let anchor_id = must_resolve_existing(&loaded.state, &input.anchor, input.exact_id)?;
+        if child_id == anchor_id {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "cannot order a task relative to itself",
+                1,
+            ));
+        }
+        for id in [&child_id, &anchor_id] {
+            let task = must_task(&loaded.state, id)?;
+            if task.parent_id.as_deref() != Some(parent_id.as_str()) {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("task {} is not a child of {}", id, parent_id),
+                    1,
+                ));
+            }
+        }
+
+        let mut order: Vec<Task> = sort_child_tasks(&children_of(&loaded.state, &parent_id))
+            .into_iter()
+            .filter(|task| task.id != child_id)
+            .collect();
+        let anchor_index = order
+            .iter()
+            .position(|task| task.id == anchor_id)
+            .unwrap_or(order.len());
+        let child_index = match input.placement {
+            ChildPlacement::Before => anchor_index,
+            ChildPlacement::After => anchor_index + 1,
+        };
+        order.insert(child_index, must_task(&loaded.state, &child_id)?);
+
+        let events = rank_events(ctx, &order, child_index);
+        let mut next_state = apply_events(&loaded.state, &events)?;
+        if !events.is_empty() {
+            commit_service_events(
+                ctx,
+                &events,
+                &mut next_state,
+                loaded.event_count + events.len(),
+            )?;
+        }
+
+        Ok(OrderChildResult {
+            children: sort_child_tasks(&children_of(&next_state, &parent_id))
+                .into_iter()
+                .map(|task| task.id)
+                .collect(),
+            parent_id,
+            child_id,
+        })
+    })
+}
+
+fn children_of(state: &State, parent_id: &str) -> Vec<Task> {
+    state
+        .tasks
+        .values()
+        .filter(|task| task.parent_id.as_deref() == Some(parent_id))
+        .cloned()
+        .collect()
+}
+
+/// Ranks the moved child between its new neighbours when they leave room;
+/// otherwise renumbers every sibling so the order is fully explicit.
+fn rank_events(ctx: &ServiceContext, order: &[Task], child_index: usize) -> Vec<EventRecord> {
+    let siblings_ranked = order
+        .iter()
+        .enumerate()
+        .all(|(index, task)| index == child_index || task.rank.is_some());
+    let previous = child_index
+        .checked_sub(1)
+        .and_then(|index| order.get(index))
+        .and_then(|task| task.rank);
+    let next = order.get(child_index + 1).and_then(|task| task.rank);
+    let slot = match (previous, next) {
+        (None, Some(next)) => Some(next - RANK_GAP),
+        (Some(previous), None) => Some(previous + RANK_GAP),
+        (Some(previous), Some(next)) if next - previous > 1 => {
+            Some(previous + (next - previous) / 2)
+        }
+        _ => None,
+    };
+
+    let ranks: Vec<(&Task, i64)> = match slot {
+        Some(rank) if siblings_ranked => vec![(&order[child_index], rank)],
+        _ => order
+            .iter()
+            .zip(1..)
+            .map(|(task, position)| (task, position * RANK_GAP))
+            .collect(),
+    };
+    ranks
+        .into_iter()
+        .filter(|(task, rank)| task.rank != Some(*rank))
+        .map(|(task, rank)| {
+            make_event(
+                &ctx.actor,
+                &ctx.now.as_ref()(),
+                EventType::TaskRanked,
+                &task.id,
+                serde_json::json!({ "rank": rank })
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+        })
+        .collect()
+}
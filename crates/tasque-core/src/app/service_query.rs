@@ -0,0 +1,451 @@
+#[path = "service_query_history.rs"]
+mod service_query_history;
+#[path = "service_query_report.rs"]
+mod service_query_report;
+#[path = "service_query_tree.rs"]
+mod service_query_tree;
+
+use crate::app::repair::scan_orphaned_graph;
+use crate::app::service_types::{
+    DepDirectionFilter, IdCandidate, IdResolution, IdResolutionStatus, IdsInput, IdsResult,
+    ListFilter, OrphanedLinkResult, OrphansResult, SearchInput, ServiceContext, SpecDriftCheck,
+    SpecStateFilter,
+};
+use crate::app::service_utils::{
+    apply_list_filter, indexed_candidates, must_resolve_existing, must_task, sort_tasks,
+};
+use crate::app::storage::{read_task_acceptance_criteria, spec_has_drifted};
+use crate::app::transaction::{load_query_state, load_query_state_with_events};
+use crate::domain::criteria::AcceptanceCriterion;
+use crate::domain::dep_tree::build_dependents_by_blocker;
+use crate::domain::deps::normalize_dependency_edges;
+use crate::domain::escalation::apply_escalation;
+use crate::domain::query::{QueryFilter, evaluate_query_with_drift, parse_query, uses_spec_drift};
+use crate::domain::resolve::{TaskLookup, lookup_task_ref};
+use crate::domain::validate::{
+    BUILTIN_LANES, builtin_lane, is_ready, list_ready, list_ready_in_lane,
+};
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::trace;
+use crate::types::{
+    DependencyRef, DependencyType, EventRecord, LaneFilter, RelationType, State, Task, TaskStatus,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub use service_query_history::{audit, doctor, history, log, task_activity};
+pub use service_query_report::{
+    dep_why, escalations, export, git_links, metrics, release_notes, report, stale,
+};
+pub use service_query_tree::{list_tree, rollup, subtree};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShowResult {
+    pub task: Task,
+    pub blockers: Vec<String>,
+    pub dependents: Vec<String>,
+    pub blocker_edges: Vec<DependencyRef>,
+    pub dependent_edges: Vec<DependencyRef>,
+    pub ready: bool,
+    pub links: HashMap<String, Vec<String>>,
+    pub history: Vec<EventRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+}
+
+pub fn show(ctx: &ServiceContext, id_raw: &str, exact_id: bool) -> Result<ShowResult, TsqError> {
+    let loaded = load_query_state_with_events(ctx)?;
+    let id = must_resolve_existing(&loaded.state, id_raw, exact_id)?;
+    let task = must_task(&loaded.state, &id)?;
+
+    let blocker_edges = sort_dependency_refs(
+        normalize_dependency_edges(loaded.state.deps.get(&id))
+            .into_iter()
+            .map(|edge| DependencyRef {
+                id: edge.blocker,
+                dep_type: edge.dep_type,
+            })
+            .collect(),
+    );
+    let dependents_by_blocker = build_dependents_by_blocker(&loaded.state.deps);
+    let dependent_edges = sort_dependency_refs(
+        dependents_by_blocker
+            .get(&id)
+            .map(|edges| {
+                edges
+                    .iter()
+                    .map(|edge| DependencyRef {
+                        id: edge.id.clone(),
+                        dep_type: edge.dep_type,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default(),
+    );
+
+    let blockers = unique_ids(&blocker_edges);
+    let dependents = unique_ids(&dependent_edges);
+
+    let mut links: HashMap<String, Vec<String>> = HashMap::new();
+    if let Some(links_raw) = loaded.state.links.get(&id) {
+        for (kind, values) in links_raw {
+            links.insert(relation_type_to_string(*kind).to_string(), values.clone());
+        }
+    }
+    if let Some(custom_raw) = loaded.state.custom_links.get(&id) {
+        for (kind, values) in custom_raw {
+            links.insert(kind.clone(), values.clone());
+        }
+    }
+
+    let history: Vec<EventRecord> = loaded
+        .all_events
+        .into_iter()
+        .filter(|evt| {
+            if evt.task_id == id {
+                return true;
+            }
+            for value in evt.payload.values() {
+                if let Some(value) = value.as_str()
+                    && value.starts_with("tsq-")
+                    && value == id
+                {
+                    return true;
+                }
+            }
+            false
+        })
+        .collect();
+
+    let acceptance_criteria = read_task_acceptance_criteria(&ctx.repo_root, &task)?;
+
+    Ok(ShowResult {
+        acceptance_criteria,
+        task,
+        blockers,
+        dependents,
+        blocker_edges,
+        dependent_edges,
+        ready: is_ready(&loaded.state, &id),
+        links,
+        history,
+    })
+}
+
+pub fn list(ctx: &ServiceContext, filter: &ListFilter) -> Result<Vec<Task>, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let base = filter_state_tasks(ctx, &loaded.state, filter)?;
+    let base = with_escalation(ctx, base)?;
+    let dep_type = filter.dep_type;
+    if dep_type.is_none() {
+        return Ok(sort_tasks(&base));
+    }
+    let direction = filter.dep_direction.unwrap_or(DepDirectionFilter::Any);
+    let dependents_by_blocker = build_dependents_by_blocker(&loaded.state.deps);
+    let filtered: Vec<Task> = base
+        .into_iter()
+        .filter(|task| {
+            matches_dep_type_filter(
+                &loaded.state,
+                &dependents_by_blocker,
+                &task.id,
+                dep_type.unwrap(),
+                direction,
+            )
+        })
+        .collect();
+    Ok(sort_tasks(&filtered))
+}
+
+/// Apply the list filter, then the optional search query, to every task in
+/// state, starting from the indexed candidates when the filter allows it.
+/// Spec files are only read when the filter or query asks for drift, and
+/// only for tasks that passed the other filters.
+pub(super) fn filter_state_tasks(
+    ctx: &ServiceContext,
+    state: &State,
+    filter: &ListFilter,
+) -> Result<Vec<Task>, TsqError> {
+    let _phase = trace::phase("filter");
+    let base = match indexed_candidates(state, filter) {
+        Some(ids) => {
+            apply_list_filter(ids.into_iter().filter_map(|id| state.tasks.get(id)), filter)
+        }
+        None => apply_list_filter(state.tasks.values(), filter),
+    };
+    let query = filter.query.as_deref().map(parse_query).transpose()?;
+    let wants_drift = filter.spec == Some(SpecStateFilter::Drifted)
+        || query.as_ref().is_some_and(uses_spec_drift);
+    let drifted = if wants_drift {
+        drifted_spec_ids(ctx, &base, filter.drift_check)?
+    } else {
+        HashSet::new()
+    };
+    let base = if filter.spec == Some(SpecStateFilter::Drifted) {
+        base.into_iter()
+            .filter(|task| drifted.contains(&task.id))
+            .collect()
+    } else {
+        base
+    };
+    match query {
+        Some(query) => Ok(evaluate_query_with_drift(
+            &base,
+            &query,
+            state,
+            &drifted,
+            current_time(ctx)?,
+        )),
+        None => Ok(base),
+    }
+}
+
+fn drifted_spec_ids(
+    ctx: &ServiceContext,
+    tasks: &[Task],
+    check: SpecDriftCheck,
+) -> Result<HashSet<String>, TsqError> {
+    let mut drifted = HashSet::new();
+    for task in tasks {
+        if spec_has_drifted(&ctx.repo_root, task, check)? {
+            drifted.insert(task.id.clone());
+        }
+    }
+    Ok(drifted)
+}
+
+pub fn ready(ctx: &ServiceContext, lane: Option<&str>) -> Result<Vec<Task>, TsqError> {
+    let filter = lane.map(|name| resolve_lane(ctx, name)).transpose()?;
+    let loaded = load_query_state(ctx)?;
+    let ready = match filter {
+        Some(filter) => list_ready_in_lane(&loaded.state, &filter),
+        None => list_ready(&loaded.state),
+    };
+    Ok(sort_tasks(&with_escalation(ctx, ready)?))
+}
+
+/// Configured lanes win over the builtin `planning`/`coding` of the same name.
+fn resolve_lane(ctx: &ServiceContext, name: &str) -> Result<LaneFilter, TsqError> {
+    let name = name.trim().to_lowercase();
+    let config = read_config(&ctx.repo_root)?;
+    let planning_states = config.planning_states();
+    let lanes = config.lanes;
+    if let Some(lane) = lanes.iter().find(|lane| lane.name == name) {
+        return Ok(lane.when.clone());
+    }
+    builtin_lane(&name, &planning_states).ok_or_else(|| {
+        let mut available: Vec<String> =
+            BUILTIN_LANES.iter().map(|lane| lane.to_string()).collect();
+        available.extend(
+            lanes
+                .into_iter()
+                .map(|lane| lane.name)
+                .filter(|lane| !BUILTIN_LANES.contains(&lane.as_str())),
+        );
+        TsqError::new(
+            "VALIDATION_ERROR",
+            format!("lane must be one of {}", available.join("|")),
+            1,
+        )
+        .with_details(serde_json::json!({ "lane": name, "available": available }))
+    })
+}
+
+/// Blocked tasks that record what they are waiting on via `blocked_on`.
+pub fn externally_blocked(ctx: &ServiceContext) -> Result<Vec<Task>, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let tasks: Vec<Task> = loaded
+        .state
+        .tasks
+        .values()
+        .filter(|task| task.status == TaskStatus::Blocked && task.blocked_on.is_some())
+        .cloned()
+        .collect();
+    Ok(sort_tasks(&with_escalation(ctx, tasks)?))
+}
+
+/// Applies the configured escalation policy, if any, to query results.
+fn with_escalation(ctx: &ServiceContext, mut tasks: Vec<Task>) -> Result<Vec<Task>, TsqError> {
+    if let Some(policy) = read_config(&ctx.repo_root)?.escalation {
+        apply_escalation(&mut tasks, &policy, current_time(ctx)?);
+    }
+    Ok(tasks)
+}
+
+pub(super) fn current_time(ctx: &ServiceContext) -> Result<DateTime<Utc>, TsqError> {
+    let now_value = ctx.now.as_ref()();
+    DateTime::parse_from_rfc3339(&now_value)
+        .map(|value| value.with_timezone(&Utc))
+        .map_err(|_| {
+            TsqError::new(
+                "INTERNAL_ERROR",
+                format!("invalid current timestamp: {}", now_value),
+                2,
+            )
+        })
+}
+
+pub fn search(ctx: &ServiceContext, input: &SearchInput) -> Result<Vec<Task>, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let filter = parse_query(&input.query)?;
+    let tasks = query_tasks(ctx, &loaded.state, &filter)?;
+    Ok(crate::domain::query::rank_search_results(tasks, &filter))
+}
+
+/// Every task in `state` matching `filter`, unranked. Mutators use this to
+/// select tasks by query inside their own lock.
+pub(crate) fn query_tasks(
+    ctx: &ServiceContext,
+    state: &State,
+    filter: &QueryFilter,
+) -> Result<Vec<Task>, TsqError> {
+    let _phase = trace::phase("filter");
+    let tasks = state.tasks.values().cloned().collect::<Vec<_>>();
+    let drifted = if uses_spec_drift(filter) {
+        drifted_spec_ids(ctx, &tasks, SpecDriftCheck::Hash)?
+    } else {
+        HashSet::new()
+    };
+    Ok(evaluate_query_with_drift(
+        &tasks,
+        filter,
+        state,
+        &drifted,
+        current_time(ctx)?,
+    ))
+}
+
+pub fn similar(
+    ctx: &ServiceContext,
+    input: &crate::app::service_types::SimilarInput,
+) -> Result<Vec<crate::domain::similarity::SimilarTaskCandidate>, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    Ok(crate::domain::similarity::find_similar_candidates(
+        loaded.state.tasks.values(),
+        &input.query,
+        crate::domain::similarity::DEFAULT_SIMILARITY_MIN_SCORE,
+        crate::domain::similarity::DEFAULT_SIMILARITY_LIMIT,
+    ))
+}
+
+pub fn orphans(ctx: &ServiceContext) -> Result<OrphansResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let scan = scan_orphaned_graph(&loaded.state);
+    let orphaned_links: Vec<OrphanedLinkResult> = scan
+        .orphaned_links
+        .into_iter()
+        .map(|link| OrphanedLinkResult {
+            src: link.src,
+            dst: link.dst,
+            rel_type: relation_type_to_string(link.rel_type).to_string(),
+        })
+        .collect();
+    let total = scan.orphaned_deps.len() + orphaned_links.len();
+    Ok(OrphansResult {
+        orphaned_deps: scan.orphaned_deps,
+        orphaned_links,
+        total,
+    })
+}
+
+/// Resolves every reference against one loaded state; misses are reported
+/// per input instead of failing the whole call.
+pub fn ids(ctx: &ServiceContext, input: &IdsInput) -> Result<IdsResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let results: Vec<IdResolution> = input
+        .refs
+        .iter()
+        .map(|raw| {
+            let (status, id, candidates) = match lookup_task_ref(&loaded.state, raw, input.exact_id)
+            {
+                TaskLookup::Found(id) => (IdResolutionStatus::Resolved, Some(id), Vec::new()),
+                TaskLookup::NotFound => (IdResolutionStatus::NotFound, None, Vec::new()),
+                TaskLookup::Ambiguous(matches) => (
+                    IdResolutionStatus::Ambiguous,
+                    None,
+                    matches
+                        .into_iter()
+                        .map(|(id, alias)| IdCandidate { id, alias })
+                        .collect(),
+                ),
+            };
+            IdResolution {
+                input: raw.clone(),
+                status,
+                id,
+                candidates,
+            }
+        })
+        .collect();
+    let resolved = results
+        .iter()
+        .filter(|result| result.status == IdResolutionStatus::Resolved)
+        .count();
+    Ok(IdsResult {
+        unresolved: results.len() - resolved,
+        resolved,
+        results,
+    })
+}
+
+pub(super) fn sort_dependency_refs(mut refs: Vec<DependencyRef>) -> Vec<DependencyRef> {
+    refs.sort_by(|a, b| {
+        if a.id == b.id {
+            return dep_type_to_string(a.dep_type).cmp(dep_type_to_string(b.dep_type));
+        }
+        a.id.cmp(&b.id)
+    });
+    refs
+}
+
+fn matches_dep_type_filter(
+    state: &crate::types::State,
+    dependents_by_blocker: &HashMap<String, Vec<crate::domain::dep_tree::DependentEdge>>,
+    task_id: &str,
+    dep_type: DependencyType,
+    direction: DepDirectionFilter,
+) -> bool {
+    let has_out = normalize_dependency_edges(state.deps.get(task_id))
+        .into_iter()
+        .any(|edge| edge.dep_type == dep_type);
+    let has_in = dependents_by_blocker
+        .get(task_id)
+        .map(|edges| edges.iter().any(|edge| edge.dep_type == dep_type))
+        .unwrap_or(false);
+    match direction {
+        DepDirectionFilter::Out => has_out,
+        DepDirectionFilter::In => has_in,
+        DepDirectionFilter::Any => has_out || has_in,
+    }
+}
+
+pub(super) fn unique_ids(edges: &[DependencyRef]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut ids = Vec::new();
+    for edge in edges {
+        if seen.insert(edge.id.clone()) {
+            ids.push(edge.id.clone());
+        }
+    }
+    ids
+}
+
+fn relation_type_to_string(rel_type: RelationType) -> &'static str {
+    match rel_type {
+        RelationType::RelatesTo => "relates_to",
+        RelationType::RepliesTo => "replies_to",
+        RelationType::Duplicates => "duplicates",
+        RelationType::Supersedes => "supersedes",
+    }
+}
+
+fn dep_type_to_string(dep_type: DependencyType) -> &'static str {
+    match dep_type {
+        DependencyType::Blocks => "blocks",
+        DependencyType::StartsAfter => "starts_after",
+        DependencyType::FinishToFinish => "finish_to_finish",
+        DependencyType::Informs => "informs",
+    }
+}
@@ -0,0 +1,353 @@
+use crate::app::doctor::{event_log_findings, state_findings, workflow_findings};
+use crate::app::service_types::{
+    AuditCount, AuditEntry, AuditInput, AuditResult, DoctorFinding, DoctorResult, DoctorSeverity,
+    HistoryInput, HistoryResult, LogInput, LogResult, ServiceContext, TaskActivityEntry,
+    TaskActivityInput, TaskActivityResult,
+};
+use crate::app::service_utils::must_resolve_existing;
+use crate::app::transaction::{load_query_state, load_query_state_with_events};
+use crate::domain::event_payload_codecs::event_type_from_str;
+use crate::domain::projector::status_changes;
+use crate::errors::TsqError;
+use crate::store::config::{read_config, read_status_workflow};
+use crate::store::events::{read_events, scan_event_lines};
+use crate::types::{EventRecord, EventType};
+use std::collections::HashSet;
+
+pub fn doctor(ctx: &ServiceContext) -> Result<DoctorResult, TsqError> {
+    let raw_lines = scan_event_lines(&ctx.repo_root)?;
+    let mut findings = event_log_findings(&raw_lines);
+    // An unknown event type stops replay; still report what the raw scan found.
+    let loaded = match load_query_state(ctx) {
+        Ok(loaded) => Some(loaded),
+        Err(error) if error.code == "EVENTS_CORRUPT" && !findings.is_empty() => {
+            findings.push(DoctorFinding {
+                code: "EVENTS_UNREADABLE".to_string(),
+                severity: DoctorSeverity::Error,
+                message: error.message,
+                task_id: None,
+                event_id: None,
+                line: None,
+            });
+            None
+        }
+        Err(error) => return Err(error),
+    };
+    if let Some(loaded) = loaded.as_ref() {
+        findings.extend(state_findings(&loaded.state));
+        if let Some(workflow) = read_status_workflow(&ctx.repo_root) {
+            let events = read_events(&ctx.repo_root)?.events;
+            findings.extend(workflow_findings(&status_changes(&events)?, &workflow));
+        }
+    }
+
+    Ok(DoctorResult {
+        tasks: loaded.as_ref().map_or(0, |loaded| loaded.state.tasks.len()),
+        events: loaded
+            .as_ref()
+            .map_or(raw_lines.len(), |loaded| loaded.event_count),
+        snapshot_loaded: loaded
+            .as_ref()
+            .is_some_and(|loaded| loaded.snapshot.is_some()),
+        warning: loaded.and_then(|loaded| loaded.warning),
+        issues: findings
+            .iter()
+            .map(|finding| finding.message.clone())
+            .collect(),
+        findings,
+    })
+}
+
+pub fn history(ctx: &ServiceContext, input: &HistoryInput) -> Result<HistoryResult, TsqError> {
+    let loaded = load_query_state_with_events(ctx)?;
+    let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+
+    let mut events: Vec<EventRecord> = loaded
+        .all_events
+        .into_iter()
+        .filter(|evt| {
+            if evt.task_id == id {
+                return true;
+            }
+            for value in evt.payload.values() {
+                if let Some(value) = value.as_str()
+                    && value == id
+                {
+                    return true;
+                }
+            }
+            false
+        })
+        .collect();
+
+    if let Some(event_type) = input.event_type.as_deref() {
+        events.retain(|evt| event_type_to_string(evt.event_type) == event_type);
+    }
+    if let Some(actor) = input.actor.as_deref() {
+        events.retain(|evt| evt.actor == actor);
+    }
+    if let Some(since) = input.since.as_deref() {
+        events.retain(|evt| evt.ts.as_str() >= since);
+    }
+
+    events.sort_by(|a, b| b.ts.cmp(&a.ts));
+
+    let limit = input.limit.unwrap_or(50);
+    let truncated = events.len() > limit;
+    let limited = events.into_iter().take(limit).collect::<Vec<_>>();
+    let directory = read_config(&ctx.repo_root)?.actors;
+    let actors = limited
+        .iter()
+        .filter_map(|evt| {
+            directory
+                .get(&evt.actor)
+                .map(|identity| (evt.actor.clone(), identity.clone()))
+        })
+        .collect();
+
+    Ok(HistoryResult {
+        events: limited.clone(),
+        count: limited.len(),
+        truncated,
+        actors,
+    })
+}
+
+/// Audit categories in report order; events map onto them in `audit_category`.
+const AUDIT_CATEGORIES: [&str; 9] = [
+    "created",
+    "claimed",
+    "status_changes",
+    "updates",
+    "notes",
+    "deps",
+    "links",
+    "specs",
+    "other",
+];
+
+pub fn audit(ctx: &ServiceContext, input: &AuditInput) -> Result<AuditResult, TsqError> {
+    let loaded = load_query_state_with_events(ctx)?;
+    let mut events: Vec<EventRecord> = loaded
+        .all_events
+        .into_iter()
+        .filter(|evt| evt.actor == input.actor)
+        .filter(|evt| {
+            input
+                .since
+                .as_deref()
+                .is_none_or(|since| evt.ts.as_str() >= since)
+        })
+        .collect();
+    events.sort_by(|a, b| a.ts.cmp(&b.ts));
+
+    let counts = AUDIT_CATEGORIES
+        .iter()
+        .map(|category| AuditCount {
+            category: category.to_string(),
+            count: events
+                .iter()
+                .filter(|evt| audit_category(evt.event_type) == *category)
+                .count(),
+        })
+        .filter(|entry| entry.count > 0)
+        .collect();
+    let tasks_touched = events
+        .iter()
+        .map(|evt| evt.task_id.as_str())
+        .collect::<HashSet<_>>()
+        .len();
+    let entries = events
+        .iter()
+        .map(|evt| AuditEntry {
+            ts: evt.ts.clone(),
+            event_type: evt.event_type,
+            category: audit_category(evt.event_type).to_string(),
+            task_id: evt.task_id.clone(),
+            title: loaded
+                .state
+                .tasks
+                .get(&evt.task_id)
+                .map(|task| task.title.clone()),
+            summary: audit_summary(evt),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(AuditResult {
+        actor: input.actor.clone(),
+        identity: read_config(&ctx.repo_root)?.actors.remove(&input.actor),
+        since: input.since.clone(),
+        total: entries.len(),
+        tasks_touched,
+        counts,
+        events: entries,
+    })
+}
+
+pub fn task_activity(
+    ctx: &ServiceContext,
+    input: &TaskActivityInput,
+) -> Result<TaskActivityResult, TsqError> {
+    let loaded = load_query_state_with_events(ctx)?;
+    let mut task_ids: Vec<String> = Vec::new();
+    for raw in &input.ids {
+        let id = must_resolve_existing(&loaded.state, raw, input.exact_id)?;
+        if !task_ids.contains(&id) {
+            task_ids.push(id);
+        }
+    }
+    let mut events: Vec<EventRecord> = loaded
+        .all_events
+        .into_iter()
+        .filter(|evt| task_ids.contains(&evt.task_id))
+        .collect();
+    events.sort_by(|a, b| a.ts.cmp(&b.ts));
+    let events = events.iter().map(activity_entry).collect();
+    Ok(TaskActivityResult { task_ids, events })
+}
+
+pub fn log(ctx: &ServiceContext, input: &LogInput) -> Result<LogResult, TsqError> {
+    let event_type = input
+        .event_type
+        .as_deref()
+        .map(|raw| {
+            event_type_from_str(raw).ok_or_else(|| {
+                TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("unknown event type: {}", raw),
+                    1,
+                )
+            })
+        })
+        .transpose()?;
+    let loaded = load_query_state_with_events(ctx)?;
+    let mut events = loaded.all_events;
+    if let Some(cursor) = input.before_cursor.as_deref() {
+        let position = events
+            .iter()
+            .position(|evt| event_key(evt) == cursor)
+            .ok_or_else(|| {
+                TsqError::new("VALIDATION_ERROR", format!("unknown cursor: {}", cursor), 1)
+            })?;
+        events.truncate(position);
+    }
+    // Newest first, in log order rather than by timestamp so pages never
+    // skip events whose clocks disagree.
+    let mut matching = events
+        .iter()
+        .rev()
+        .filter(|evt| event_type.is_none_or(|event_type| evt.event_type == event_type))
+        .filter(|evt| {
+            input
+                .actor
+                .as_deref()
+                .is_none_or(|actor| evt.actor == actor)
+        });
+    let limit = input.limit.unwrap_or(50);
+    let page: Vec<TaskActivityEntry> = matching.by_ref().take(limit).map(activity_entry).collect();
+    let next_cursor = match matching.next() {
+        Some(_) => page.last().map(|entry| entry.event_id.clone()),
+        None => None,
+    };
+    Ok(LogResult {
+        count: page.len(),
+        events: page,
+        next_cursor,
+    })
+}
+
+fn event_key(evt: &EventRecord) -> &str {
+    evt.id
+        .as_deref()
+        .or(evt.event_id.as_deref())
+        .unwrap_or_default()
+}
+
+fn activity_entry(evt: &EventRecord) -> TaskActivityEntry {
+    TaskActivityEntry {
+        event_id: event_key(evt).to_string(),
+        ts: evt.ts.clone(),
+        event_type: evt.event_type,
+        actor: evt.actor.clone(),
+        task_id: evt.task_id.clone(),
+        summary: audit_summary(evt),
+    }
+}
+
+fn audit_category(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::TaskCreated => "created",
+        EventType::TaskClaimed => "claimed",
+        EventType::TaskStatusSet => "status_changes",
+        EventType::TaskUpdated | EventType::TaskRanked => "updates",
+        EventType::TaskNoted => "notes",
+        EventType::DepAdded | EventType::DepRemoved => "deps",
+        EventType::LinkAdded | EventType::LinkRemoved | EventType::TaskSuperseded => "links",
+        EventType::TaskSpecAttached => "specs",
+        EventType::TaskCriterionSet
+        | EventType::TaskChecklistAdded
+        | EventType::TaskChecklistSet => "other",
+    }
+}
+
+/// One-line description of what the event changed, built from its payload.
+fn audit_summary(evt: &EventRecord) -> String {
+    let field = |key: &str| {
+        evt.payload
+            .get(key)
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    match evt.event_type {
+        EventType::TaskCreated => format!("created \"{}\"", field("title")),
+        EventType::TaskClaimed => format!("claimed as {}", field("assignee")),
+        EventType::TaskStatusSet => format!("status -> {}", field("status")),
+        EventType::TaskUpdated => {
+            let mut keys = evt.payload.keys().cloned().collect::<Vec<_>>();
+            keys.sort();
+            format!("updated {}", keys.join(", "))
+        }
+        EventType::TaskRanked => "reordered".to_string(),
+        EventType::TaskNoted => format!("note: {}", truncate_summary(&field("text"))),
+        EventType::DepAdded => format!("blocked by {}", field("blocker")),
+        EventType::DepRemoved => format!("unblocked from {}", field("blocker")),
+        EventType::LinkAdded => format!("linked {} {}", field("type"), field("target")),
+        EventType::LinkRemoved => format!("unlinked {} {}", field("type"), field("target")),
+        EventType::TaskSuperseded => format!("superseded by {}", field("with")),
+        EventType::TaskSpecAttached => format!("attached spec {}", field("spec_path")),
+        EventType::TaskCriterionSet => format!("criterion: {}", truncate_summary(&field("text"))),
+        EventType::TaskChecklistAdded => {
+            format!("checklist: {}", truncate_summary(&field("text")))
+        }
+        EventType::TaskChecklistSet => format!("checklist item {}", field("item")),
+    }
+}
+
+fn truncate_summary(text: &str) -> String {
+    let line = text.lines().next().unwrap_or("");
+    if line.chars().count() <= 80 {
+        return line.to_string();
+    }
+    format!("{}...", line.chars().take(77).collect::<String>())
+}
+
+fn event_type_to_string(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::TaskCreated => "task.created",
+        EventType::TaskUpdated => "task.updated",
+        EventType::TaskStatusSet => "task.status_set",
+        EventType::TaskClaimed => "task.claimed",
+        EventType::TaskNoted => "task.noted",
+        EventType::TaskSpecAttached => "task.spec_attached",
+        EventType::TaskSuperseded => "task.superseded",
+        EventType::TaskCriterionSet => "task.criterion_set",
+        EventType::TaskChecklistAdded => "task.checklist_added",
+        EventType::TaskChecklistSet => "task.checklist_set",
+        EventType::TaskRanked => "task.ranked",
+        EventType::DepAdded => "dep.added",
+        EventType::DepRemoved => "dep.removed",
+        EventType::LinkAdded => "link.added",
+        EventType::LinkRemoved => "link.removed",
+    }
+}
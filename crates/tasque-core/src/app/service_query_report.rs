@@ -0,0 +1,464 @@
+use super::current_time;
+use crate::app::service_types::{
+    DepWhyInput, DepWhyResult, Escalation, EscalationsResult, ExportDependency, ExportInput,
+    ExportResult, GitCommitRef, GitLinkedTask, GitLinksInput, GitLinksResult, GitUnknownId,
+    GitUnlinkedTask, MetricsResult, ReleaseNotesEntry, ReleaseNotesGroup, ReleaseNotesGrouping,
+    ReleaseNotesInput, ReleaseNotesResult, ReportResult, ReportStatusCount, ServiceContext,
+    StaleInput, StaleResult,
+};
+use crate::app::service_utils::{
+    DEFAULT_STALE_STATUSES, must_resolve_existing, sort_stale_tasks, sort_tasks,
+};
+use crate::app::transaction::load_query_state;
+use crate::domain::dep_path::find_dep_paths;
+use crate::domain::deps::normalize_dependency_edges;
+use crate::domain::escalation::{apply_escalation, task_age_days};
+use crate::domain::ids::mentioned_task_ids;
+use crate::domain::redact::Redactor;
+use crate::domain::validate::list_ready;
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::store::events::read_event_log_metadata;
+use crate::types::{Task, TaskKind, TaskStatus};
+use chrono::{Duration, SecondsFormat};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+pub fn stale(ctx: &ServiceContext, input: &StaleInput) -> Result<StaleResult, TsqError> {
+    if input.days < 0 {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "days must be an integer >= 0",
+            1,
+        ));
+    }
+    if let Some(limit) = input.limit
+        && limit < 1
+    {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "limit must be an integer >= 1",
+            1,
+        ));
+    }
+
+    let loaded = load_query_state(ctx)?;
+    let now_dt = current_time(ctx)?;
+    let cutoff_dt = now_dt - Duration::days(input.days);
+    let cutoff = cutoff_dt.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    let statuses: Vec<TaskStatus> = match input.status.clone() {
+        Some(status) => vec![status],
+        None => DEFAULT_STALE_STATUSES.to_vec(),
+    };
+
+    let tasks: Vec<Task> = loaded
+        .state
+        .tasks
+        .values()
+        .filter(|task| statuses.contains(&task.status))
+        .filter(|task| match input.assignee.as_deref() {
+            Some(assignee) => task.assignee.as_deref() == Some(assignee),
+            None => true,
+        })
+        .filter(|task| task.updated_at <= cutoff)
+        .cloned()
+        .collect();
+
+    let sorted = sort_stale_tasks(&tasks);
+    let limited = match input.limit {
+        Some(limit) => sorted.into_iter().take(limit).collect(),
+        None => sorted,
+    };
+
+    Ok(StaleResult {
+        tasks: limited,
+        days: input.days,
+        cutoff,
+        statuses,
+        labeled: Vec::new(),
+    })
+}
+
+/// Report statuses in reading order: active work first, finished work last.
+/// Configured statuses sit between blocked and open work.
+const REPORT_ACTIVE_STATUSES: [TaskStatus; 2] = [TaskStatus::InProgress, TaskStatus::Blocked];
+const REPORT_WAITING_STATUSES: [TaskStatus; 4] = [
+    TaskStatus::Open,
+    TaskStatus::Deferred,
+    TaskStatus::Closed,
+    TaskStatus::Canceled,
+];
+
+pub fn report(ctx: &ServiceContext) -> Result<ReportResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let all: Vec<Task> = loaded.state.tasks.values().cloned().collect();
+    let sorted = sort_tasks(&all);
+
+    // Custom statuses in config order, then any still used by tasks after
+    // being dropped from config.
+    let mut custom: Vec<TaskStatus> = read_config(&ctx.repo_root)?
+        .statuses()
+        .into_iter()
+        .filter(|status| matches!(status, TaskStatus::Custom(_)))
+        .collect();
+    let mut unlisted: Vec<TaskStatus> = sorted
+        .iter()
+        .map(|task| task.status.clone())
+        .filter(|status| matches!(status, TaskStatus::Custom(_)) && !custom.contains(status))
+        .collect();
+    unlisted.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    unlisted.dedup();
+    custom.extend(unlisted);
+    let order = REPORT_ACTIVE_STATUSES
+        .into_iter()
+        .chain(custom)
+        .chain(REPORT_WAITING_STATUSES);
+
+    let mut tasks = Vec::with_capacity(sorted.len());
+    let mut status_counts = Vec::new();
+    for status in order {
+        let group: Vec<Task> = sorted
+            .iter()
+            .filter(|task| task.status == status)
+            .cloned()
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        status_counts.push(ReportStatusCount {
+            status,
+            count: group.len(),
+        });
+        tasks.extend(group);
+    }
+
+    Ok(ReportResult {
+        generated_at: ctx.now.as_ref()(),
+        total: tasks.len(),
+        status_counts,
+        tasks,
+    })
+}
+
+const DEP_WHY_MAX_DEPTH: usize = 10;
+const DEP_WHY_DEFAULT_LIMIT: usize = 5;
+
+pub fn dep_why(ctx: &ServiceContext, input: &DepWhyInput) -> Result<DepWhyResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let from = must_resolve_existing(&loaded.state, &input.from, input.exact_id)?;
+    let to = must_resolve_existing(&loaded.state, &input.to, input.exact_id)?;
+    if from == to {
+        return Err(
+            TsqError::new("VALIDATION_ERROR", "dep why needs two different tasks", 1)
+                .with_details(serde_json::json!({ "id": from })),
+        );
+    }
+    let limit = input.limit.unwrap_or(DEP_WHY_DEFAULT_LIMIT);
+    let mut reversed = false;
+    let mut paths = find_dep_paths(&loaded.state, &from, &to, DEP_WHY_MAX_DEPTH, limit);
+    if paths.is_empty() {
+        paths = find_dep_paths(&loaded.state, &to, &from, DEP_WHY_MAX_DEPTH, limit);
+        reversed = !paths.is_empty();
+    }
+    Ok(DepWhyResult {
+        connected: !paths.is_empty(),
+        from,
+        to,
+        reversed,
+        paths,
+    })
+}
+
+/// Cross-checks commit messages against the task graph. A closed task counts
+/// as covered when it or any descendant is mentioned, so epics closed through
+/// their children are not flagged; duplicates and superseded tasks never are.
+pub fn git_links(ctx: &ServiceContext, input: &GitLinksInput) -> Result<GitLinksResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let state = &loaded.state;
+    let mut by_task: HashMap<String, Vec<GitCommitRef>> = HashMap::new();
+    let mut unknown: HashMap<String, Vec<GitCommitRef>> = HashMap::new();
+    for commit in &input.commits {
+        for id in mentioned_task_ids(&commit.message) {
+            let target = if state.tasks.contains_key(&id) {
+                &mut by_task
+            } else {
+                &mut unknown
+            };
+            target.entry(id).or_default().push(commit.clone());
+        }
+    }
+
+    let mut covered: HashSet<&str> = HashSet::new();
+    for id in by_task.keys() {
+        let mut current = state.tasks.get(id);
+        while let Some(task) = current {
+            if !covered.insert(task.id.as_str()) {
+                break;
+            }
+            current = task
+                .parent_id
+                .as_ref()
+                .and_then(|parent| state.tasks.get(parent));
+        }
+    }
+
+    let mut linked: Vec<GitLinkedTask> = by_task
+        .into_iter()
+        .filter_map(|(id, commits)| {
+            let task = state.tasks.get(&id)?;
+            Some(GitLinkedTask {
+                title: task.title.clone(),
+                status: task.status.clone(),
+                id,
+                commits,
+            })
+        })
+        .collect();
+    linked.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut closed_without_commits: Vec<GitUnlinkedTask> = state
+        .tasks
+        .values()
+        .filter(|task| task.status == TaskStatus::Closed)
+        .filter(|task| task.duplicate_of.is_none() && task.superseded_by.is_none())
+        .filter(|task| !covered.contains(task.id.as_str()))
+        .filter(|task| match input.since.as_deref() {
+            Some(since) => task
+                .closed_at
+                .as_deref()
+                .is_some_and(|closed_at| closed_at >= since),
+            None => true,
+        })
+        .map(|task| GitUnlinkedTask {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            closed_at: task.closed_at.clone(),
+        })
+        .collect();
+    closed_without_commits.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut unknown_ids: Vec<GitUnknownId> = unknown
+        .into_iter()
+        .map(|(id, commits)| GitUnknownId { id, commits })
+        .collect();
+    unknown_ids.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(GitLinksResult {
+        since: input.since_ref.clone(),
+        commits_scanned: input.commits.len(),
+        linked,
+        closed_without_commits,
+        unknown_ids,
+    })
+}
+
+const RELEASE_NOTES_KIND_ORDER: [TaskKind; 3] = [TaskKind::Epic, TaskKind::Feature, TaskKind::Task];
+
+pub fn release_notes(
+    ctx: &ServiceContext,
+    input: &ReleaseNotesInput,
+) -> Result<ReleaseNotesResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let closed: Vec<Task> = loaded
+        .state
+        .tasks
+        .values()
+        .filter(|task| task.status == TaskStatus::Closed)
+        .filter(|task| {
+            task.closed_at
+                .as_deref()
+                .is_some_and(|closed_at| closed_at >= input.since.as_str())
+        })
+        .cloned()
+        .collect();
+    let (excluded, mut included): (Vec<Task>, Vec<Task>) = closed
+        .into_iter()
+        .partition(|task| task.duplicate_of.is_some() || task.superseded_by.is_some());
+    included.sort_by(|a, b| a.closed_at.cmp(&b.closed_at).then_with(|| a.id.cmp(&b.id)));
+
+    let groups = match input.group_by {
+        ReleaseNotesGrouping::Kind => RELEASE_NOTES_KIND_ORDER
+            .iter()
+            .map(|kind| ReleaseNotesGroup {
+                name: kind_label(*kind).to_string(),
+                tasks: included
+                    .iter()
+                    .filter(|task| task.kind == *kind)
+                    .map(release_notes_entry)
+                    .collect(),
+            })
+            .filter(|group| !group.tasks.is_empty())
+            .collect(),
+        ReleaseNotesGrouping::Label => {
+            let mut labels = included
+                .iter()
+                .flat_map(|task| task.labels.iter().cloned())
+                .collect::<Vec<_>>();
+            labels.sort();
+            labels.dedup();
+            let mut groups = labels
+                .into_iter()
+                .map(|label| ReleaseNotesGroup {
+                    tasks: included
+                        .iter()
+                        .filter(|task| task.labels.contains(&label))
+                        .map(release_notes_entry)
+                        .collect(),
+                    name: label,
+                })
+                .collect::<Vec<_>>();
+            let unlabeled = included
+                .iter()
+                .filter(|task| task.labels.is_empty())
+                .map(release_notes_entry)
+                .collect::<Vec<_>>();
+            if !unlabeled.is_empty() {
+                groups.push(ReleaseNotesGroup {
+                    name: "unlabeled".to_string(),
+                    tasks: unlabeled,
+                });
+            }
+            groups
+        }
+    };
+
+    Ok(ReleaseNotesResult {
+        since: input.since.clone(),
+        tag: input.tag.clone(),
+        generated_at: ctx.now.as_ref()(),
+        group_by: input.group_by,
+        total: included.len(),
+        excluded: excluded.len(),
+        groups,
+    })
+}
+
+fn kind_label(kind: TaskKind) -> &'static str {
+    match kind {
+        TaskKind::Epic => "epic",
+        TaskKind::Feature => "feature",
+        TaskKind::Task => "task",
+    }
+}
+
+fn release_notes_entry(task: &Task) -> ReleaseNotesEntry {
+    ReleaseNotesEntry {
+        id: task.id.clone(),
+        title: task.title.clone(),
+        kind: task.kind,
+        labels: task.labels.clone(),
+        external_ref: task.external_ref.clone(),
+        closed_at: task.closed_at.clone().unwrap_or_default(),
+    }
+}
+
+pub fn export(ctx: &ServiceContext, input: &ExportInput) -> Result<ExportResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let all: Vec<Task> = loaded.state.tasks.values().cloned().collect();
+    let redactor = if input.redact {
+        let config = read_config(&ctx.repo_root)?.redaction.unwrap_or_default();
+        Some(Redactor::new(&config)?)
+    } else {
+        None
+    };
+    let tasks = sort_tasks(&all)
+        .into_iter()
+        .map(|task| {
+            let value = serde_json::to_value(task).unwrap_or_default();
+            match &redactor {
+                Some(redactor) => redactor.apply(value),
+                None => value,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut dependencies = loaded
+        .state
+        .deps
+        .iter()
+        .flat_map(|(task_id, edges)| {
+            normalize_dependency_edges(Some(edges))
+                .into_iter()
+                .map(|edge| ExportDependency {
+                    task_id: task_id.clone(),
+                    blocker: edge.blocker,
+                    dep_type: edge.dep_type,
+                })
+        })
+        .collect::<Vec<_>>();
+    dependencies.sort_by(|a, b| (&a.task_id, &a.blocker).cmp(&(&b.task_id, &b.blocker)));
+
+    Ok(ExportResult {
+        exported_at: ctx.now.as_ref()(),
+        redacted: input.redact,
+        total: tasks.len(),
+        tasks,
+        dependencies,
+    })
+}
+
+pub fn metrics(ctx: &ServiceContext) -> Result<MetricsResult, TsqError> {
+    let started = Instant::now();
+    let loaded = load_query_state(ctx)?;
+    let projection_seconds = started.elapsed().as_secs_f64();
+    let count = |status: TaskStatus| {
+        loaded
+            .state
+            .tasks
+            .values()
+            .filter(|task| task.status == status)
+            .count()
+    };
+    let log = read_event_log_metadata(&ctx.repo_root, loaded.event_count)?;
+    Ok(MetricsResult {
+        open: count(TaskStatus::Open),
+        in_progress: count(TaskStatus::InProgress),
+        blocked: count(TaskStatus::Blocked),
+        ready: list_ready(&loaded.state).len(),
+        event_count: log.event_count,
+        event_log_bytes: log.byte_len,
+        projection_seconds,
+    })
+}
+
+pub fn escalations(ctx: &ServiceContext) -> Result<EscalationsResult, TsqError> {
+    let Some(policy) = read_config(&ctx.repo_root)?.escalation else {
+        return Ok(EscalationsResult {
+            enabled: false,
+            policy: None,
+            escalations: Vec::new(),
+        });
+    };
+    let loaded = load_query_state(ctx)?;
+    let now = current_time(ctx)?;
+    let mut tasks: Vec<Task> = loaded.state.tasks.values().cloned().collect();
+    apply_escalation(&mut tasks, &policy, now);
+
+    let mut escalations: Vec<Escalation> = sort_tasks(&tasks)
+        .into_iter()
+        .filter_map(|task| {
+            Some(Escalation {
+                effective_priority: task.effective_priority?,
+                age_days: task_age_days(&task, now)?,
+                id: task.id,
+                title: task.title,
+                status: task.status.clone(),
+                priority: task.priority,
+                assignee: task.assignee,
+            })
+        })
+        .collect();
+    escalations.sort_by(|a, b| {
+        a.effective_priority
+            .cmp(&b.effective_priority)
+            .then_with(|| b.age_days.cmp(&a.age_days))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    Ok(EscalationsResult {
+        enabled: true,
+        policy: Some(policy),
+        escalations,
+    })
+}
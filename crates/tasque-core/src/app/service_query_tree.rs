@@ -0,0 +1,191 @@
+use super::{filter_state_tasks, sort_dependency_refs, unique_ids};
+use crate::app::service_types::{
+    ListFilter, RollupTask, ServiceContext, SubtreeInput, SubtreeResult,
+};
+use crate::app::service_utils::{
+    must_resolve_existing, must_task, sort_child_tasks, sort_task_ids, sort_tasks,
+};
+use crate::app::transaction::load_query_state;
+use crate::domain::dep_tree::build_dependents_by_blocker;
+use crate::domain::deps::normalize_dependency_edges;
+use crate::domain::rollup::child_progress;
+use crate::errors::TsqError;
+use crate::types::{DependencyRef, State, Task, TaskKind, TaskTreeNode};
+use std::collections::HashMap;
+
+pub fn list_tree(ctx: &ServiceContext, filter: &ListFilter) -> Result<Vec<TaskTreeNode>, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let filtered_tasks = filter_state_tasks(ctx, &loaded.state, filter)?;
+    let tasks_by_id: HashMap<String, Task> = filtered_tasks
+        .iter()
+        .cloned()
+        .map(|task| (task.id.clone(), task))
+        .collect();
+    let mut children_by_parent: HashMap<String, Vec<Task>> = HashMap::new();
+    let mut roots: Vec<Task> = Vec::new();
+
+    for task in &filtered_tasks {
+        if let Some(parent_id) = task.parent_id.as_ref()
+            && tasks_by_id.contains_key(parent_id)
+        {
+            children_by_parent
+                .entry(parent_id.clone())
+                .or_default()
+                .push(task.clone());
+            continue;
+        }
+        roots.push(task.clone());
+    }
+
+    let dependents_by_blocker = build_dependents_by_blocker(&loaded.state.deps);
+    let mut sorted_roots = sort_tasks(&roots);
+    Ok(sorted_roots
+        .drain(..)
+        .map(|task| {
+            build_tree_node(
+                &task,
+                &loaded.state,
+                &children_by_parent,
+                &dependents_by_blocker,
+                None,
+            )
+        })
+        .collect())
+}
+
+/// One task and its descendants, stopping `depth` levels below the root
+/// when set. Every status is kept so `rollup` counts the whole initiative.
+pub fn subtree(ctx: &ServiceContext, input: &SubtreeInput) -> Result<SubtreeResult, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let state = &loaded.state;
+    let root_id = must_resolve_existing(state, &input.id, input.exact_id)?;
+    let mut children_by_parent: HashMap<String, Vec<Task>> = HashMap::new();
+    for task in state.tasks.values() {
+        if let Some(parent_id) = task.parent_id.as_ref() {
+            children_by_parent
+                .entry(parent_id.clone())
+                .or_default()
+                .push(task.clone());
+        }
+    }
+    let mut descendants: Vec<&Task> = Vec::new();
+    let mut pending = vec![root_id.as_str()];
+    while let Some(id) = pending.pop() {
+        for child in children_by_parent.get(id).into_iter().flatten() {
+            descendants.push(child);
+            pending.push(&child.id);
+        }
+    }
+    let root = must_task(state, &root_id)?;
+    let tree = build_tree_node(
+        &root,
+        state,
+        &children_by_parent,
+        &build_dependents_by_blocker(&state.deps),
+        input.depth,
+    );
+    let hidden = descendants.len() + 1 - count_tree_nodes(&tree);
+    Ok(SubtreeResult {
+        rollup: child_progress(descendants),
+        depth: input.depth,
+        hidden,
+        tree,
+    })
+}
+
+fn count_tree_nodes(node: &TaskTreeNode) -> usize {
+    1 + node.children.iter().map(count_tree_nodes).sum::<usize>()
+}
+
+fn build_tree_node(
+    task: &Task,
+    state: &State,
+    children_by_parent: &HashMap<String, Vec<Task>>,
+    dependents_by_blocker: &HashMap<String, Vec<crate::domain::dep_tree::DependentEdge>>,
+    depth: Option<usize>,
+) -> TaskTreeNode {
+    let blocker_edges = sort_dependency_refs(
+        normalize_dependency_edges(state.deps.get(&task.id))
+            .into_iter()
+            .map(|edge| DependencyRef {
+                id: edge.blocker,
+                dep_type: edge.dep_type,
+            })
+            .collect(),
+    );
+    let dependent_edges = sort_dependency_refs(
+        dependents_by_blocker
+            .get(&task.id)
+            .map(|edges| {
+                edges
+                    .iter()
+                    .map(|edge| DependencyRef {
+                        id: edge.id.clone(),
+                        dep_type: edge.dep_type,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default(),
+    );
+    let blockers = sort_task_ids(&unique_ids(&blocker_edges));
+    let dependents = sort_task_ids(&unique_ids(&dependent_edges));
+    let child_tasks = if depth == Some(0) {
+        Vec::new()
+    } else {
+        sort_child_tasks(
+            children_by_parent
+                .get(&task.id)
+                .map(|v| v.as_slice())
+                .unwrap_or(&[]),
+        )
+    };
+    TaskTreeNode {
+        task: task.clone(),
+        blockers,
+        dependents,
+        blocker_edges: Some(blocker_edges),
+        dependent_edges: Some(dependent_edges),
+        children: child_tasks
+            .iter()
+            .map(|child| {
+                build_tree_node(
+                    child,
+                    state,
+                    children_by_parent,
+                    dependents_by_blocker,
+                    depth.map(|depth| depth - 1),
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Annotates epics and features with progress over their direct children.
+pub fn rollup(ctx: &ServiceContext, tasks: Vec<Task>) -> Result<Vec<RollupTask>, TsqError> {
+    let loaded = load_query_state(ctx)?;
+    let mut children_by_parent: HashMap<&str, Vec<&Task>> = HashMap::new();
+    for task in loaded.state.tasks.values() {
+        if let Some(parent_id) = task.parent_id.as_deref() {
+            children_by_parent.entry(parent_id).or_default().push(task);
+        }
+    }
+    Ok(tasks
+        .into_iter()
+        .map(|task| {
+            let rollup = matches!(task.kind, TaskKind::Epic | TaskKind::Feature).then(|| {
+                child_progress(
+                    children_by_parent
+                        .get(task.id.as_str())
+                        .into_iter()
+                        .flatten()
+                        .copied(),
+                )
+            });
+            RollupTask {
+                task,
+                rollup,
+                workspace: None,
+            }
+        })
+        .collect())
+}
@@ -1,19 +1,23 @@
 use super::TasqueService;
 use crate::app::service_types::{
     ServiceContext, SpecAttachInput, SpecAttachResult, SpecAttachSpec, SpecCheckInput,
-    SpecCheckResult, SpecContentInput, SpecContentResult, SpecPatchInput, SpecUpdateInput,
-    SpecUpdateResult, SpecUpdateSpec,
+    SpecCheckResult, SpecContentInput, SpecContentResult, SpecDiffInput, SpecDiffResult,
+    SpecHistoryInput, SpecHistoryResult, SpecPatchInput, SpecReattachInput, SpecTemplateInput,
+    SpecTemplateResult, SpecUpdateInput, SpecUpdateResult, SpecUpdateSpec, SpecVersion,
 };
 use crate::app::service_utils::{must_resolve_existing, must_task};
 use crate::app::storage::{
-    append_events, evaluate_task_spec, load_projected_state, normalize_optional_input,
-    persist_projection, read_spec_attach_content, resolve_spec_attach_source, sha256,
-    with_write_lock, write_task_spec_atomic,
+    SpecWriteResult, evaluate_task_spec, load_projected_state_with_events,
+    normalize_optional_input, read_spec_attach_content, read_task_spec_version,
+    render_spec_template, resolve_spec_attach_source, sha256, task_spec_relative_path,
+    task_spec_version_file, write_task_spec_atomic, write_task_spec_version,
 };
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
 use crate::domain::events::make_event;
 use crate::domain::projector::apply_events;
 use crate::errors::TsqError;
-use crate::types::{EventRecord, EventType, State, Task};
+use crate::store::crypto;
+use crate::types::{EventRecord, EventType, State, Task, TaskKind};
 use diffy::patch_set::{FileOperation, ParseOptions, PatchKind, PatchSet};
 use std::path::PathBuf;
 
@@ -21,6 +25,22 @@ impl TasqueService {
     pub fn spec_content(&self, input: SpecContentInput) -> Result<SpecContentResult, TsqError> {
         spec_content(&self.ctx, &input)
     }
+
+    pub fn spec_template(&self, input: SpecTemplateInput) -> Result<SpecTemplateResult, TsqError> {
+        spec_template(&self.ctx, &input)
+    }
+
+    pub fn spec_history(&self, input: SpecHistoryInput) -> Result<SpecHistoryResult, TsqError> {
+        spec_history(&self.ctx, &input)
+    }
+
+    pub fn spec_diff(&self, input: SpecDiffInput) -> Result<SpecDiffResult, TsqError> {
+        spec_diff(&self.ctx, &input)
+    }
+
+    pub fn spec_reattach(&self, input: SpecReattachInput) -> Result<SpecUpdateResult, TsqError> {
+        spec_reattach(&self.ctx, &input)
+    }
 }
 
 pub fn spec_attach(
@@ -38,8 +58,8 @@ pub fn spec_attach(
         return Err(empty_spec_error("spec markdown content must not be empty"));
     }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
         let existing = must_task(&loaded.state, &id)?;
         let new_fingerprint = sha256(&source_content);
@@ -64,7 +84,7 @@ pub fn spec_attach(
             })));
         }
 
-        let spec_file = write_task_spec_atomic(&ctx.repo_root, &id, &source_content)?;
+        let spec_file = write_spec_file(ctx, &id, &source_content)?;
         let fingerprint = sha256(&spec_file.content);
         let attached_at = ctx.now.as_ref()();
         let attached_by = ctx.actor.clone();
@@ -77,13 +97,7 @@ pub fn spec_attach(
             &attached_by,
         );
         let mut next_state = apply_events(&loaded.state, std::slice::from_ref(&event))?;
-        append_events(&ctx.repo_root, &[event])?;
-        persist_projection(
-            &ctx.repo_root,
-            &mut next_state,
-            loaded.event_count + 1,
-            None,
-        )?;
+        commit_service_events(ctx, &[event], &mut next_state, loaded.event_count + 1)?;
 
         Ok(SpecAttachResult {
             task: must_task(&next_state, &id)?,
@@ -113,8 +127,8 @@ pub fn spec_update(
         return Err(empty_spec_error("spec markdown content must not be empty"));
     }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
         let existing = must_task(&loaded.state, &id)?;
         let attached = validate_attached_spec_current(&ctx.repo_root, &id, &existing)?;
@@ -144,8 +158,8 @@ pub fn spec_patch(
         return Err(empty_spec_error("spec patch content must not be empty"));
     }
 
-    with_write_lock(&ctx.repo_root, || {
-        let loaded = load_projected_state(&ctx.repo_root)?;
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
         let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
         let existing = must_task(&loaded.state, &id)?;
         let (attached, current_content) =
@@ -172,7 +186,7 @@ pub fn spec_check(
     ctx: &ServiceContext,
     input: &SpecCheckInput,
 ) -> Result<SpecCheckResult, TsqError> {
-    let loaded = load_projected_state(&ctx.repo_root)?;
+    let loaded = load_service_state(ctx)?;
     let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
     let task = must_task(&loaded.state, &id)?;
     evaluate_task_spec(&ctx.repo_root, &id, &task)
@@ -182,7 +196,10 @@ pub fn spec_content(
     ctx: &ServiceContext,
     input: &SpecContentInput,
 ) -> Result<SpecContentResult, TsqError> {
-    let loaded = load_projected_state(&ctx.repo_root)?;
+    if let Some(version) = input.version {
+        return spec_version_content(ctx, input, version);
+    }
+    let loaded = load_service_state(ctx)?;
     let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
     let task = must_task(&loaded.state, &id)?;
     let attached = require_attached_spec(&task)?;
@@ -193,9 +210,193 @@ pub fn spec_content(
         spec_path: attached.spec_path,
         spec_fingerprint: attached.spec_fingerprint,
         content,
+        version: None,
+    })
+}
+
+/// Lists every spec attachment for a task in event-log order, numbered from 1.
+pub fn spec_history(
+    ctx: &ServiceContext,
+    input: &SpecHistoryInput,
+) -> Result<SpecHistoryResult, TsqError> {
+    let loaded = load_projected_state_with_events(&ctx.repo_root)?;
+    let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+    let task = must_task(&loaded.state, &id)?;
+    let current_fingerprint = normalize_optional_input(task.spec_fingerprint.as_deref());
+    let attachments: Vec<&EventRecord> = loaded
+        .all_events
+        .iter()
+        .filter(|event| event.task_id == id && event.event_type == EventType::TaskSpecAttached)
+        .collect();
+    let last_index = attachments.len().saturating_sub(1);
+
+    let mut versions = Vec::with_capacity(attachments.len());
+    for (index, event) in attachments.into_iter().enumerate() {
+        let payload_str = |key: &str| {
+            event
+                .payload
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)
+        };
+        let fingerprint = payload_str("spec_fingerprint").unwrap_or_default();
+        versions.push(SpecVersion {
+            version: index + 1,
+            content_available: task_spec_version_file(&ctx.repo_root, &id, &fingerprint).is_file(),
+            current: index == last_index
+                && current_fingerprint.as_deref() == Some(fingerprint.as_str()),
+            attached_at: payload_str("spec_attached_at").unwrap_or_else(|| event.ts.clone()),
+            attached_by: payload_str("spec_attached_by").unwrap_or_else(|| event.actor.clone()),
+            fingerprint,
+        });
+    }
+
+    Ok(SpecHistoryResult {
+        task_id: id,
+        versions,
     })
 }
 
+fn spec_version_content(
+    ctx: &ServiceContext,
+    input: &SpecContentInput,
+    version: usize,
+) -> Result<SpecContentResult, TsqError> {
+    let history = spec_history(
+        ctx,
+        &SpecHistoryInput {
+            id: input.id.clone(),
+            exact_id: input.exact_id,
+        },
+    )?;
+    let entry = version
+        .checked_sub(1)
+        .and_then(|index| history.versions.get(index))
+        .ok_or_else(|| {
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "task {} has no spec version {}; it has {} recorded",
+                    history.task_id,
+                    version,
+                    history.versions.len()
+                ),
+                1,
+            )
+        })?;
+    let content = read_task_spec_version(&ctx.repo_root, &history.task_id, &entry.fingerprint)?
+        .ok_or_else(|| {
+            TsqError::new(
+                "NOT_FOUND",
+                format!(
+                    "content for spec version {} of task {} was not retained",
+                    version, history.task_id
+                ),
+                1,
+            )
+            .with_details(serde_json::json!({"fingerprint": entry.fingerprint}))
+        })?;
+
+    Ok(SpecContentResult {
+        spec_path: task_spec_relative_path(&history.task_id),
+        task_id: history.task_id,
+        spec_fingerprint: entry.fingerprint.clone(),
+        content,
+        version: Some(version),
+    })
+}
+
+/// Diffs the attached (fingerprinted) spec revision against the file on disk.
+pub fn spec_diff(ctx: &ServiceContext, input: &SpecDiffInput) -> Result<SpecDiffResult, TsqError> {
+    let loaded = load_service_state(ctx)?;
+    let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+    let task = must_task(&loaded.state, &id)?;
+    let attached = require_attached_spec(&task)?;
+    let current = read_attached_spec_content(&ctx.repo_root, &id, &attached.spec_path)?;
+    let actual_fingerprint = sha256(&current);
+    let drifted = actual_fingerprint != attached.spec_fingerprint;
+    let baseline = read_task_spec_version(&ctx.repo_root, &id, &attached.spec_fingerprint)?;
+    let diff = match (drifted, baseline.as_deref()) {
+        (true, Some(baseline)) => Some(
+            diffy::DiffOptions::new()
+                .set_original_filename(format!("a/{}", attached.spec_path))
+                .set_modified_filename(format!("b/{}", attached.spec_path))
+                .create_patch(baseline, &current)
+                .to_string(),
+        ),
+        _ => None,
+    };
+
+    Ok(SpecDiffResult {
+        task_id: id,
+        spec_path: attached.spec_path,
+        expected_fingerprint: attached.spec_fingerprint,
+        actual_fingerprint,
+        drifted,
+        baseline_available: baseline.is_some(),
+        diff,
+    })
+}
+
+/// Accepts the drifted on-disk spec as the new attached revision.
+pub fn spec_reattach(
+    ctx: &ServiceContext,
+    input: &SpecReattachInput,
+) -> Result<SpecUpdateResult, TsqError> {
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+        let existing = must_task(&loaded.state, &id)?;
+        let attached = require_attached_spec(&existing)?;
+        let current = read_attached_spec_content(&ctx.repo_root, &id, &attached.spec_path)?;
+        if sha256(&current) == attached.spec_fingerprint {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!("attached spec for task {} has not drifted", id),
+                1,
+            ));
+        }
+        if current.trim().is_empty() {
+            return Err(empty_spec_error("spec markdown content must not be empty"));
+        }
+        write_updated_spec(
+            ctx,
+            &loaded.state,
+            loaded.event_count,
+            &id,
+            &current,
+            attached.spec_fingerprint,
+        )
+    })
+}
+
+/// Renders the scaffold for a new spec; the template defaults to the task kind.
+pub fn spec_template(
+    ctx: &ServiceContext,
+    input: &SpecTemplateInput,
+) -> Result<SpecTemplateResult, TsqError> {
+    let loaded = load_service_state(ctx)?;
+    let id = must_resolve_existing(&loaded.state, &input.id, input.exact_id)?;
+    let task = must_task(&loaded.state, &id)?;
+    let template = normalize_optional_input(input.template.as_deref())
+        .unwrap_or_else(|| task_kind_template(task.kind).to_string());
+    let content = render_spec_template(&ctx.repo_root, &template, &task)?;
+
+    Ok(SpecTemplateResult {
+        task_id: id,
+        template,
+        content,
+    })
+}
+
+fn task_kind_template(kind: TaskKind) -> &'static str {
+    match kind {
+        TaskKind::Task => "task",
+        TaskKind::Feature => "feature",
+        TaskKind::Epic => "epic",
+    }
+}
+
 struct AttachedSpec {
     spec_path: String,
     spec_fingerprint: String,
@@ -262,13 +463,13 @@ fn read_current_attached_spec(
     Ok((attached, content))
 }
 
-fn read_attached_spec_content(
+pub(super) fn read_attached_spec_content(
     repo_root: &str,
     task_id: &str,
     spec_path: &str,
 ) -> Result<String, TsqError> {
     let resolved_path = resolve_spec_path(repo_root, spec_path);
-    std::fs::read_to_string(&resolved_path).map_err(|error| {
+    crypto::read_text(&resolved_path, crypto::SPEC).map_err(|error| {
         let (code, exit_code, message) = if error.kind() == std::io::ErrorKind::NotFound {
             (
                 "VALIDATION_ERROR",
@@ -300,7 +501,7 @@ fn write_updated_spec(
     content: &str,
     old_fingerprint: String,
 ) -> Result<SpecUpdateResult, TsqError> {
-    let spec_file = write_task_spec_atomic(&ctx.repo_root, id, content)?;
+    let spec_file = write_spec_file(ctx, id, content)?;
     let new_fingerprint = sha256(&spec_file.content);
     let attached_at = ctx.now.as_ref()();
     let attached_by = ctx.actor.clone();
@@ -313,8 +514,7 @@ fn write_updated_spec(
         &attached_by,
     );
     let mut next_state = apply_events(state, std::slice::from_ref(&event))?;
-    append_events(&ctx.repo_root, &[event])?;
-    persist_projection(&ctx.repo_root, &mut next_state, event_count + 1, None)?;
+    commit_service_events(ctx, &[event], &mut next_state, event_count + 1)?;
 
     Ok(SpecUpdateResult {
         task: must_task(&next_state, id)?,
@@ -329,7 +529,30 @@ fn write_updated_spec(
     })
 }
 
-fn make_spec_attached_event(
+/// Staged (dry-run) contexts leave the spec file untouched and report the
+/// content that would have been written.
+pub(super) fn write_spec_file(
+    ctx: &ServiceContext,
+    id: &str,
+    content: &str,
+) -> Result<SpecWriteResult, TsqError> {
+    if ctx.staged.is_some() {
+        return Ok(SpecWriteResult {
+            spec_path: task_spec_relative_path(id),
+            content: content.to_string(),
+        });
+    }
+    let written = write_task_spec_atomic(&ctx.repo_root, id, content)?;
+    write_task_spec_version(
+        &ctx.repo_root,
+        id,
+        &sha256(&written.content),
+        &written.content,
+    )?;
+    Ok(written)
+}
+
+pub(super) fn make_spec_attached_event(
     ctx: &ServiceContext,
     id: &str,
     spec_path: &str,
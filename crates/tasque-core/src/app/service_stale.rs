@@ -0,0 +1,145 @@
+use crate::app::service_lifecycle::enforce_policies;
+use crate::app::service_query;
+use crate::app::service_types::{ServiceContext, StaleEnforceResult, StaleInput, StaleResult};
+use crate::app::service_utils::must_task;
+use crate::app::transaction::{commit_service_events, load_service_state, with_service_lock};
+use crate::domain::events::make_event;
+use crate::domain::labels::add_label;
+use crate::domain::projector::apply_events;
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::types::{EventRecord, EventType, State, TaskStatus};
+
+/// Lists stale tasks and, with `apply_label`, tags the ones missing it.
+pub fn stale(ctx: &ServiceContext, input: &StaleInput) -> Result<StaleResult, TsqError> {
+    let mut result = service_query::stale(ctx, input)?;
+    let Some(label) = input.apply_label.as_deref() else {
+        return Ok(result);
+    };
+
+    let ids: Vec<String> = result.tasks.iter().map(|task| task.id.clone()).collect();
+    let sweep = apply_stale_actions(ctx, &ids, Some(label), false)?;
+    if let Some(state) = sweep.state {
+        result.tasks = ids
+            .iter()
+            .map(|id| must_task(&state, id))
+            .collect::<Result<_, _>>()?;
+    }
+    result.labeled = sweep.labeled;
+    Ok(result)
+}
+
+/// Applies the configured stale policy to every task past its window.
+pub fn stale_enforce(ctx: &ServiceContext) -> Result<StaleEnforceResult, TsqError> {
+    let policy = read_config(&ctx.repo_root)?.stale.ok_or_else(|| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            "no stale policy configured (config.json \"stale\")",
+            1,
+        )
+    })?;
+
+    let found = service_query::stale(
+        ctx,
+        &StaleInput {
+            days: i64::from(policy.stale_days),
+            status: None,
+            assignee: None,
+            limit: None,
+            apply_label: None,
+        },
+    )?;
+    let stale: Vec<String> = found.tasks.iter().map(|task| task.id.clone()).collect();
+    let sweep = apply_stale_actions(ctx, &stale, policy.label.as_deref(), policy.auto_defer)?;
+
+    Ok(StaleEnforceResult {
+        policy,
+        cutoff: found.cutoff,
+        stale,
+        labeled: sweep.labeled,
+        deferred: sweep.deferred,
+    })
+}
+
+#[derive(Default)]
+struct StaleSweep {
+    labeled: Vec<String>,
+    deferred: Vec<String>,
+    /// Projected state after the sweep; `None` when nothing was written.
+    state: Option<State>,
+}
+
+/// Emits the label and defer events a stale sweep needs, skipping tasks that
+/// already carry the label or are already deferred.
+fn apply_stale_actions(
+    ctx: &ServiceContext,
+    ids: &[String],
+    label: Option<&str>,
+    defer: bool,
+) -> Result<StaleSweep, TsqError> {
+    if ids.is_empty() {
+        return Ok(StaleSweep::default());
+    }
+
+    with_service_lock(ctx, || {
+        let loaded = load_service_state(ctx)?;
+        let mut events: Vec<EventRecord> = Vec::new();
+        let mut labeled = Vec::new();
+        let mut deferred = Vec::new();
+        for id in ids {
+            let Some(task) = loaded.state.tasks.get(id) else {
+                continue;
+            };
+            if let Some(label) = label {
+                let labels = add_label(&task.labels, label)?;
+                if labels != task.labels {
+                    events.push(make_event(
+                        &ctx.actor,
+                        &ctx.now.as_ref()(),
+                        EventType::TaskUpdated,
+                        id,
+                        serde_json::json!({ "labels": labels })
+                            .as_object()
+                            .cloned()
+                            .unwrap_or_default(),
+                    ));
+                    labeled.push(id.clone());
+                }
+            }
+            if defer && task.status != TaskStatus::Deferred {
+                events.push(make_event(
+                    &ctx.actor,
+                    &ctx.now.as_ref()(),
+                    EventType::TaskStatusSet,
+                    id,
+                    serde_json::json!({ "status": TaskStatus::Deferred, "closed_at": null })
+                        .as_object()
+                        .cloned()
+                        .unwrap_or_default(),
+                ));
+                deferred.push(id.clone());
+            }
+        }
+        if events.is_empty() {
+            return Ok(StaleSweep {
+                labeled,
+                deferred,
+                state: None,
+            });
+        }
+
+        let mut next_state = apply_events(&loaded.state, &events)?;
+        enforce_policies(ctx, &loaded.state, &events, &next_state)?;
+        commit_service_events(
+            ctx,
+            &events,
+            &mut next_state,
+            loaded.event_count + events.len(),
+        )?;
+        Ok(StaleSweep {
+            labeled,
+            deferred,
+            state: Some(next_state),
+        })
+    })
+}
@@ -0,0 +1,442 @@
+#[path = "service_types_batch.rs"]
+mod service_types_batch;
+#[path = "service_types_content.rs"]
+mod service_types_content;
+#[path = "service_types_history.rs"]
+mod service_types_history;
+#[path = "service_types_merge.rs"]
+mod service_types_merge;
+#[path = "service_types_query.rs"]
+mod service_types_query;
+
+pub use crate::app::storage::{SpecCheckDiagnostic, SpecCheckResult};
+pub use crate::domain::criteria::AcceptanceCriterion;
+use crate::domain::dep_path::DepPath;
+use crate::domain::dep_tree::{DepDirection, DependentImpact};
+pub use crate::skills::types::{SkillOperationSummary, SkillTarget};
+use crate::types::{
+    ActorIdentity, ActorSource, ActorType, DependencyType, LabelColor, PlanningState, PolicyConfig,
+    Priority, RelationType, Task, TaskKind, TaskNote, TaskStatus,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub use service_types_batch::{
+    BatchClaimOp, BatchCreateOp, BatchDepOp, BatchInput, BatchLabelOp, BatchLinkOp, BatchNoteOp,
+    BatchOperation, BatchOperationResult, BatchResult, BatchStatusOp, BatchUpdateOp,
+    CreateBatchInput, CreateBatchItem,
+};
+pub use service_types_content::{
+    ChecklistAddInput, ChecklistListInput, ChecklistResult, ChecklistSetInput, ChildPlacement,
+    CriteriaCheckInput, CriteriaListInput, CriteriaResult, NoteAddInput, NoteAddResult,
+    NoteListInput, NoteListResult, OrderChildInput, OrderChildResult, SpecAttachInput,
+    SpecAttachResult, SpecAttachSpec, SpecCheckInput, SpecContentInput, SpecContentResult,
+    SpecDiffInput, SpecDiffResult, SpecHistoryInput, SpecHistoryResult, SpecPatchInput,
+    SpecReattachInput, SpecTemplateInput, SpecTemplateResult, SpecUpdateInput, SpecUpdateResult,
+    SpecUpdateSpec, SpecVersion,
+};
+pub use service_types_history::{
+    AuditCount, AuditEntry, AuditInput, AuditResult, DuplicateCandidateGroup,
+    DuplicateCandidatesResult, GitCommitRef, GitLinkedTask, GitLinksInput, GitLinksResult,
+    GitUnknownId, GitUnlinkedTask, HistoryInput, HistoryResult, LogInput, LogResult, MetricsResult,
+    ReleaseNotesEntry, ReleaseNotesGroup, ReleaseNotesGrouping, ReleaseNotesInput,
+    ReleaseNotesResult, TaskActivityEntry, TaskActivityInput, TaskActivityResult,
+};
+pub use service_types_merge::{
+    CloneInput, CloneResult, ConvertInput, MergeField, MergeFieldStrategy, MergeInput, MergeItem,
+    MergeProjected, MergeResult, MergeSummary, MergeTarget, SplitInput, SplitResult,
+};
+pub use service_types_query::{
+    DepDirectionFilter, DoctorFinding, DoctorResult, DoctorSeverity, Escalation, EscalationsResult,
+    ExportDependency, ExportInput, ExportResult, IdCandidate, IdResolution, IdResolutionStatus,
+    IdsInput, IdsResult, ListFilter, OrphanedLinkResult, OrphansAdoptInput, OrphansAdoptResult,
+    OrphansResult, ReadyInput, ReportResult, ReportStatusCount, RollupTask, SearchInput,
+    SimilarInput, SkillsDiffInput, SkillsRefreshInput, SpecDriftCheck, SpecStateFilter,
+    StaleEnforceResult, StaleInput, StaleResult, SubtreeInput, SubtreeResult,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitResult {
+    pub initialized: bool,
+    pub files: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_operation: Option<SkillOperationSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_setup: Option<crate::types::SyncSetupResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seeded: Option<InitSeedResult>,
+}
+
+/// Starter content an init preset seeds into the repo: label colors, epics
+/// created once by title, and policies added by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InitSeed {
+    #[serde(default)]
+    pub labels: BTreeMap<String, LabelColor>,
+    #[serde(default)]
+    pub epics: Vec<InitSeedEpic>,
+    #[serde(default)]
+    pub policies: Vec<PolicyConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InitSeedEpic {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default = "default_seed_priority")]
+    pub priority: Priority,
+}
+
+fn default_seed_priority() -> Priority {
+    2
+}
+
+/// What seeding added; entries that already existed are left out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InitSeedResult {
+    pub labels: Vec<String>,
+    /// Ids of the seeded epics, including ones reused by title.
+    pub epics: Vec<String>,
+    pub policies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InitInput {
+    pub install_skill: bool,
+    pub uninstall_skill: bool,
+    pub skill_targets: Option<Vec<SkillTarget>>,
+    pub skill_name: Option<String>,
+    pub force_skill_overwrite: bool,
+    pub skill_dir_claude: Option<String>,
+    pub skill_dir_codex: Option<String>,
+    pub skill_dir_copilot: Option<String>,
+    pub skill_dir_opencode: Option<String>,
+    pub skill_dir_cursor: Option<String>,
+    pub skill_dir_windsurf: Option<String>,
+    pub skill_dir_gemini: Option<String>,
+    pub sync_branch: Option<String>,
+    /// Skip the sync branch that init otherwise sets up in a git repository.
+    #[serde(default)]
+    pub no_sync_branch: bool,
+    /// Preview the skill operation only; no repo files, sync setup, or skill writes.
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub skill_source: Option<String>,
+    #[serde(default)]
+    pub skill_checksum: Option<String>,
+    /// Preset content to seed after the repo files are written.
+    #[serde(default)]
+    pub seed: Option<InitSeed>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInput {
+    pub title: String,
+    pub kind: TaskKind,
+    pub priority: Priority,
+    pub description: Option<String>,
+    pub external_ref: Option<String>,
+    pub discovered_from: Option<String>,
+    pub parent: Option<String>,
+    pub exact_id: bool,
+    pub planning_state: Option<PlanningState>,
+    pub explicit_id: Option<String>,
+    pub body_file: Option<String>,
+    pub ensure: bool,
+    pub force: bool,
+    pub skip_duplicate_check: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInput {
+    pub id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub clear_description: bool,
+    pub external_ref: Option<String>,
+    pub discovered_from: Option<String>,
+    pub clear_discovered_from: bool,
+    pub clear_external_ref: bool,
+    pub status: Option<TaskStatus>,
+    pub priority: Option<Priority>,
+    pub exact_id: bool,
+    pub planning_state: Option<PlanningState>,
+    pub assignee: Option<String>,
+    pub blocked_on: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimInput {
+    pub id: String,
+    pub assignee: Option<String>,
+    pub require_spec: bool,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffInput {
+    pub id: String,
+    pub to: String,
+    pub note: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffResult {
+    pub task: Task,
+    pub from: String,
+    pub to: String,
+    pub note: TaskNote,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkInput {
+    pub src: String,
+    pub dst: String,
+    #[serde(rename = "type")]
+    pub rel_type: RelationType,
+    pub exact_id: bool,
+}
+
+/// Link of a relation type defined under `relation_types` in config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomLinkInput {
+    pub src: String,
+    pub dst: String,
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepInput {
+    pub child: String,
+    pub blocker: String,
+    pub dep_type: Option<DependencyType>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepImportEdge {
+    /// 1-based source line, echoed back in validation errors.
+    pub line: usize,
+    pub child: String,
+    pub blocker: String,
+    pub dep_type: DependencyType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepImportInput {
+    pub edges: Vec<DepImportEdge>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepImportResult {
+    pub added: Vec<DepImportEdge>,
+    /// Edges that were already present and so appended nothing.
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupersedeInput {
+    pub source: String,
+    pub with_id: String,
+    pub reason: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateInput {
+    pub source: String,
+    pub canonical: String,
+    pub reason: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseInput {
+    pub ids: Vec<String>,
+    pub reason: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReopenInput {
+    pub ids: Vec<String>,
+    /// Status the tasks must currently have: closed (default) or canceled.
+    #[serde(default)]
+    pub from: Option<TaskStatus>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleStatusInput {
+    pub ids: Vec<String>,
+    pub status: TaskStatus,
+    pub note: Option<String>,
+    pub reason: Option<String>,
+    /// Refuse to close tasks whose acceptance criteria are still open.
+    pub require_criteria: bool,
+    /// Snooze deadline; only valid when deferring.
+    pub defer_until: Option<String>,
+    /// Status a reopen starts from: closed (default) or canceled.
+    #[serde(default)]
+    pub reopen_from: Option<TaskStatus>,
+    /// Search query selecting the tasks instead of `ids`; matches are
+    /// resolved under the write lock and skip closed and canceled tasks.
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Defer dependents orphaned by canceling their blocker; only valid when
+    /// canceling.
+    #[serde(default)]
+    pub cascade_defer: bool,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleStatusResult {
+    pub tasks: Vec<Task>,
+    pub notes: Vec<NoteAddResult>,
+    /// Closed tasks that still had unchecked acceptance criteria.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub open_criteria: Vec<CriteriaResult>,
+    /// Unfinished dependents the close or cancel unblocked or orphaned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependents: Vec<DependentImpact>,
+    /// Orphaned dependents deferred by `cascade_defer`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deferred: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchInput {
+    pub runs: usize,
+}
+
+/// Per-run milliseconds for one `tsq bench` phase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+/// `tsq bench`: the whole event log read and replayed `runs` times,
+/// bypassing the state cache and snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub runs: usize,
+    pub events: usize,
+    pub tasks: usize,
+    pub read_events: BenchStats,
+    pub apply: BenchStats,
+    /// Events read and replayed per second over all runs.
+    pub events_per_sec: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelInput {
+    pub id: String,
+    pub label: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelCount {
+    pub label: String,
+    pub count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<LabelColor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelColorInput {
+    pub label: String,
+    /// `None` clears the label's color.
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelColorResult {
+    pub label: String,
+    pub color: Option<LabelColor>,
+}
+
+/// Registers an actor or updates the fields given for an existing one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActorAddInput {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub actor_type: Option<ActorType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActorEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub identity: ActorIdentity,
+}
+
+/// What one actor source held; `value` is `None` when it was unset or empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActorCandidate {
+    pub source: ActorSource,
+    pub value: Option<String>,
+}
+
+/// `tsq whoami`: the acting name, the source that supplied it (`None` when
+/// every source was empty and the actor fell back to `unknown`), and what
+/// each source in the chain held.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActorResolution {
+    pub actor: String,
+    pub source: Option<ActorSource>,
+    pub candidates: Vec<ActorCandidate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<ActorIdentity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepTreeInput {
+    pub id: String,
+    pub direction: Option<DepDirection>,
+    pub depth: Option<usize>,
+    pub statuses: Option<Vec<TaskStatus>>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DepWhyInput {
+    pub from: String,
+    pub to: String,
+    pub limit: Option<usize>,
+    pub exact_id: bool,
+}
+
+/// Paths explaining how `from` reaches `to` through deps and relations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepWhyResult {
+    pub from: String,
+    pub to: String,
+    pub connected: bool,
+    /// Set when no path leads from `from` to `to` and the paths shown run
+    /// the other way, from `to` back to `from`.
+    pub reversed: bool,
+    pub paths: Vec<DepPath>,
+}
+
+#[derive(Clone)]
+pub struct ServiceContext {
+    pub repo_root: String,
+    pub actor: String,
+    pub now: Arc<dyn Fn() -> String + Send + Sync>,
+    /// When set, mutations stage into this transaction instead of the log.
+    pub staged: Option<crate::app::transaction::StagedHandle>,
+    /// When set, queries replay only events at or before this timestamp.
+    pub as_of: Option<String>,
+}
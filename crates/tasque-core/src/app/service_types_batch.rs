@@ -0,0 +1,159 @@
+use crate::types::{DependencyType, PlanningState, Priority, RelationType, TaskKind, TaskStatus};
+use serde::{Deserialize, Serialize};
+
+/// A single item in a batch create request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBatchItem {
+    pub title: String,
+    /// For `--from-file`: nesting depth (0 = root-level in file).
+    /// For positional titles: always 0 (parent comes from `parent` field).
+    pub depth: usize,
+    /// Source line number or positional index (1-based) for error messages.
+    pub marker: Option<usize>,
+}
+
+/// Input for atomic batch create.  All items are validated and written under a
+/// single write lock so preflight and persistence cannot race.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBatchInput {
+    pub items: Vec<CreateBatchItem>,
+    pub kind: TaskKind,
+    pub priority: Priority,
+    pub description: Option<String>,
+    pub external_ref: Option<String>,
+    pub discovered_from: Option<String>,
+    /// CLI-level `--parent` (existing task reference).
+    pub parent: Option<String>,
+    pub exact_id: bool,
+    pub planning_state: Option<PlanningState>,
+    pub body_file: Option<String>,
+    pub ensure: bool,
+    pub force: bool,
+    /// Whether items came from `--from-file` (depth-based parent stacking)
+    /// vs positional titles (all share `parent`).
+    pub from_file: bool,
+}
+
+/// One operation in a `tsq batch` request, tagged by `op`.
+///
+/// String fields that reference tasks accept `$<ref>` to point at a task
+/// created earlier in the same batch with a matching `ref`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOperation {
+    Create(BatchCreateOp),
+    Update(BatchUpdateOp),
+    DepAdd(BatchDepOp),
+    DepRemove(BatchDepOp),
+    LinkAdd(BatchLinkOp),
+    LinkRemove(BatchLinkOp),
+    LabelAdd(BatchLabelOp),
+    LabelRemove(BatchLabelOp),
+    Note(BatchNoteOp),
+    Claim(BatchClaimOp),
+    Status(BatchStatusOp),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchCreateOp {
+    #[serde(rename = "ref")]
+    pub ref_name: Option<String>,
+    pub title: String,
+    pub kind: Option<TaskKind>,
+    pub priority: Option<Priority>,
+    pub parent: Option<String>,
+    pub description: Option<String>,
+    pub external_ref: Option<String>,
+    pub discovered_from: Option<String>,
+    pub planning_state: Option<PlanningState>,
+    pub id: Option<String>,
+    pub labels: Vec<String>,
+    pub ensure: bool,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchUpdateOp {
+    pub id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub clear_description: bool,
+    pub external_ref: Option<String>,
+    pub clear_external_ref: bool,
+    pub discovered_from: Option<String>,
+    pub clear_discovered_from: bool,
+    pub status: Option<TaskStatus>,
+    pub priority: Option<Priority>,
+    pub planning_state: Option<PlanningState>,
+    pub assignee: Option<String>,
+    pub blocked_on: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchDepOp {
+    pub child: String,
+    pub blocker: String,
+    #[serde(default, rename = "type")]
+    pub dep_type: Option<DependencyType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchLinkOp {
+    pub src: String,
+    pub dst: String,
+    #[serde(rename = "type")]
+    pub rel_type: RelationType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchLabelOp {
+    pub id: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchNoteOp {
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchClaimOp {
+    pub id: String,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub require_spec: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchStatusOp {
+    pub id: String,
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchInput {
+    pub operations: Vec<serde_json::Value>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchOperationResult {
+    pub index: usize,
+    pub op: String,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub ref_name: Option<String>,
+    pub result: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub applied: usize,
+    pub events: usize,
+    pub results: Vec<BatchOperationResult>,
+}
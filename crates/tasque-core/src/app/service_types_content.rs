@@ -0,0 +1,253 @@
+use crate::domain::criteria::AcceptanceCriterion;
+use crate::types::{ChecklistItem, Task, TaskNote};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteAddInput {
+    pub id: String,
+    pub text: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteListInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChildPlacement {
+    Before,
+    After,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderChildInput {
+    pub parent: String,
+    pub child: String,
+    /// Sibling the child is placed next to.
+    pub anchor: String,
+    pub placement: ChildPlacement,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderChildResult {
+    pub parent_id: String,
+    pub child_id: String,
+    /// Children of the parent in their new order.
+    pub children: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistAddInput {
+    pub id: String,
+    pub text: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistSetInput {
+    pub id: String,
+    /// 1-based item number as shown by `tsq check list`.
+    pub index: usize,
+    pub done: bool,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistListInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriteriaListInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriteriaCheckInput {
+    pub id: String,
+    /// 1-based criterion number as shown by `tsq criteria list`.
+    pub index: usize,
+    /// Explicit state; `None` toggles the current state.
+    pub checked: Option<bool>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecAttachInput {
+    pub id: String,
+    pub file: Option<String>,
+    pub source: Option<String>,
+    pub text: Option<String>,
+    pub stdin: bool,
+    pub force: bool,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecCheckInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecContentInput {
+    pub id: String,
+    /// 1-based attachment number from `tsq spec history`; `None` reads the live file.
+    pub version: Option<usize>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecHistoryInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecUpdateInput {
+    pub id: String,
+    pub file: Option<String>,
+    pub text: Option<String>,
+    pub stdin: bool,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecPatchInput {
+    pub id: String,
+    pub file: Option<String>,
+    pub text: Option<String>,
+    pub stdin: bool,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecTemplateInput {
+    pub id: String,
+    pub template: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecDiffInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReattachInput {
+    pub id: String,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteAddResult {
+    pub task_id: String,
+    pub note: TaskNote,
+    pub notes_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteListResult {
+    pub task_id: String,
+    pub notes: Vec<TaskNote>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistResult {
+    pub task_id: String,
+    pub checklist: Vec<ChecklistItem>,
+    pub done: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriteriaResult {
+    pub task_id: String,
+    pub criteria: Vec<AcceptanceCriterion>,
+    pub open: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecAttachResult {
+    pub task: Task,
+    pub spec: SpecAttachSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecAttachSpec {
+    pub spec_path: String,
+    pub spec_fingerprint: String,
+    pub spec_attached_at: String,
+    pub spec_attached_by: String,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecContentResult {
+    pub task_id: String,
+    pub spec_path: String,
+    pub spec_fingerprint: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecHistoryResult {
+    pub task_id: String,
+    pub versions: Vec<SpecVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecVersion {
+    pub version: usize,
+    pub fingerprint: String,
+    pub attached_at: String,
+    pub attached_by: String,
+    pub current: bool,
+    /// False for attachments recorded before spec content was retained.
+    pub content_available: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecTemplateResult {
+    pub task_id: String,
+    pub template: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecDiffResult {
+    pub task_id: String,
+    pub spec_path: String,
+    pub expected_fingerprint: String,
+    pub actual_fingerprint: String,
+    pub drifted: bool,
+    /// False when the spec was attached before attached content was retained.
+    pub baseline_available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecUpdateResult {
+    pub task: Task,
+    pub spec: SpecUpdateSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecUpdateSpec {
+    pub spec_path: String,
+    pub old_fingerprint: String,
+    pub new_fingerprint: String,
+    pub spec_attached_at: String,
+    pub spec_attached_by: String,
+    pub bytes: usize,
+}
@@ -0,0 +1,234 @@
+use crate::types::{ActorIdentity, EventRecord, EventType, Task, TaskKind, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryInput {
+    pub id: String,
+    pub limit: Option<usize>,
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    pub actor: Option<String>,
+    pub since: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryResult {
+    pub events: Vec<EventRecord>,
+    pub count: usize,
+    pub truncated: bool,
+    /// Directory entries for the actors in `events` that are registered.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub actors: BTreeMap<String, ActorIdentity>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditInput {
+    pub actor: String,
+    /// Inclusive lower bound, already normalized to an RFC 3339 timestamp.
+    pub since: Option<String>,
+}
+
+/// Everything one actor recorded, for reviewing an agent's work.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditResult {
+    pub actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<ActorIdentity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    pub total: usize,
+    pub tasks_touched: usize,
+    pub counts: Vec<AuditCount>,
+    pub events: Vec<AuditEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditCount {
+    pub category: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub ts: String,
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    pub category: String,
+    pub task_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskActivityInput {
+    pub ids: Vec<String>,
+    pub exact_id: bool,
+}
+
+/// Events recorded against the followed tasks, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskActivityResult {
+    pub task_ids: Vec<String>,
+    pub events: Vec<TaskActivityEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskActivityEntry {
+    pub event_id: String,
+    pub ts: String,
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    pub actor: String,
+    pub task_id: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogInput {
+    pub limit: Option<usize>,
+    /// Event id from a previous page's `next_cursor`; only older events follow.
+    pub before_cursor: Option<String>,
+    pub event_type: Option<String>,
+    pub actor: Option<String>,
+}
+
+/// Repo-wide events, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogResult {
+    pub events: Vec<TaskActivityEntry>,
+    pub count: usize,
+    /// Pass to `--before-cursor` for the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Point-in-time health gauges exported at `/metrics`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsResult {
+    pub open: usize,
+    pub in_progress: usize,
+    pub blocked: usize,
+    /// Depth of the `tsq ready` queue, without waking snoozed tasks.
+    pub ready: usize,
+    pub event_count: usize,
+    pub event_log_bytes: u64,
+    /// Wall time spent loading the projected state for this snapshot.
+    pub projection_seconds: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseNotesGrouping {
+    Kind,
+    Label,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReleaseNotesInput {
+    /// Inclusive lower bound on `closed_at`, already normalized to RFC 3339.
+    pub since: String,
+    /// Git tag or ref `since` was resolved from, echoed back in the result.
+    pub tag: Option<String>,
+    pub group_by: ReleaseNotesGrouping,
+}
+
+/// A commit read from git history for `tsq git links`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommitRef {
+    pub sha: String,
+    pub subject: String,
+    /// Full message, scanned for task ids; not echoed in results.
+    #[serde(skip)]
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitLinksInput {
+    pub commits: Vec<GitCommitRef>,
+    /// Ref the scan started after, echoed back in the result.
+    pub since_ref: Option<String>,
+    /// Commit time of `since_ref`; only tasks closed at or after it are
+    /// checked for missing commits.
+    pub since: Option<String>,
+}
+
+/// Which tasks commit messages mention, which closed tasks none mention, and
+/// which mentioned ids do not exist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLinksResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    pub commits_scanned: usize,
+    pub linked: Vec<GitLinkedTask>,
+    pub closed_without_commits: Vec<GitUnlinkedTask>,
+    pub unknown_ids: Vec<GitUnknownId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLinkedTask {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub commits: Vec<GitCommitRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitUnlinkedTask {
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitUnknownId {
+    pub id: String,
+    pub commits: Vec<GitCommitRef>,
+}
+
+/// Tasks closed since a date or tag, grouped for a changelog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNotesResult {
+    pub since: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    pub generated_at: String,
+    pub group_by: ReleaseNotesGrouping,
+    pub total: usize,
+    /// Closed in the window but left out as duplicates or superseded work.
+    pub excluded: usize,
+    pub groups: Vec<ReleaseNotesGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNotesGroup {
+    pub name: String,
+    pub tasks: Vec<ReleaseNotesEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNotesEntry {
+    pub id: String,
+    pub title: String,
+    pub kind: TaskKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_ref: Option<String>,
+    pub closed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCandidateGroup {
+    pub key: String,
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCandidatesResult {
+    pub scanned: usize,
+    pub groups: Vec<DuplicateCandidateGroup>,
+}
@@ -0,0 +1,119 @@
+use crate::types::{Task, TaskKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeInput {
+    pub sources: Vec<String>,
+    pub into: String,
+    pub reason: Option<String>,
+    pub force: bool,
+    pub dry_run: bool,
+    pub exact_id: bool,
+    /// Per-field strategies; fields left out keep the target's value.
+    #[serde(default)]
+    pub strategy: BTreeMap<MergeField, MergeFieldStrategy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneInput {
+    pub id: String,
+    /// Parent for the copy; defaults to the original's parent.
+    pub into: Option<String>,
+    pub with_children: bool,
+    pub exact_id: bool,
+}
+
+/// Copies in creation order; the clone of the requested task comes first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneResult {
+    pub source: String,
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertInput {
+    pub id: String,
+    pub kind: TaskKind,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitInput {
+    pub id: String,
+    pub parts: Vec<String>,
+    /// Create parts under the original's parent instead of under the original.
+    pub siblings: bool,
+    /// Close the original as superseded by the first part.
+    pub close: bool,
+    pub reason: Option<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitResult {
+    pub source: Task,
+    pub parts: Vec<Task>,
+}
+
+/// Target field `tsq merge --strategy` can fold source values into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeField {
+    Description,
+    Labels,
+    Priority,
+}
+
+/// How a merge combines a field: `concat` appends source descriptions,
+/// `union` adds source labels, `min`/`max` pick across priorities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeFieldStrategy {
+    Keep,
+    Concat,
+    Union,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeSummary {
+    pub requested_sources: usize,
+    pub merged_sources: usize,
+    pub skipped_sources: usize,
+    pub planned_events: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeProjected {
+    pub target: Task,
+    pub sources: Vec<Task>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeItem {
+    pub id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeTarget {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub merged: Vec<MergeItem>,
+    pub target: MergeTarget,
+    pub dry_run: bool,
+    pub warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub strategy: BTreeMap<MergeField, MergeFieldStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_summary: Option<MergeSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected: Option<MergeProjected>,
+}
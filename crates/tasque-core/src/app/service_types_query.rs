@@ -0,0 +1,326 @@
+use crate::domain::rollup::ChildProgress;
+use crate::types::{
+    DependencyType, EscalationConfig, PlanningState, Priority, RepairDep, RepairLink,
+    StalePolicyConfig, Task, TaskKind, TaskStatus, TaskTreeNode,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchInput {
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarInput {
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFilter {
+    pub statuses: Option<Vec<TaskStatus>>,
+    pub assignee: Option<String>,
+    pub external_ref: Option<String>,
+    pub discovered_from: Option<String>,
+    pub kind: Option<TaskKind>,
+    pub label: Option<String>,
+    pub label_any: Option<Vec<String>>,
+    pub created_after: Option<String>,
+    pub updated_after: Option<String>,
+    pub closed_after: Option<String>,
+    pub unassigned: bool,
+    pub ids: Option<Vec<String>>,
+    pub planning_state: Option<PlanningState>,
+    pub dep_type: Option<DependencyType>,
+    pub dep_direction: Option<DepDirectionFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec: Option<SpecStateFilter>,
+    /// How `spec: Some(Drifted)` and `spec:drifted` queries check spec files.
+    #[serde(default)]
+    pub drift_check: SpecDriftCheck,
+    /// Search query syntax (see `tsq find search`) applied after the other filters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepDirectionFilter {
+    In,
+    Out,
+    Any,
+}
+
+/// `attached` and `missing` read only task metadata; `drifted` also reads the
+/// attached spec file and compares it to the recorded fingerprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecStateFilter {
+    Attached,
+    Missing,
+    Drifted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecDriftCheck {
+    /// Hash every attached spec file.
+    #[default]
+    Hash,
+    /// Trust files not modified since the spec was attached; hash the rest.
+    Mtime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadyInput {
+    pub lane: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleInput {
+    pub days: i64,
+    pub status: Option<TaskStatus>,
+    pub assignee: Option<String>,
+    pub limit: Option<usize>,
+    pub apply_label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleResult {
+    pub tasks: Vec<Task>,
+    pub days: i64,
+    pub cutoff: String,
+    pub statuses: Vec<TaskStatus>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labeled: Vec<String>,
+}
+
+/// A listed task plus child progress when it is an epic or feature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupTask {
+    #[serde(flatten)]
+    pub task: Task,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup: Option<ChildProgress>,
+    /// Sync branch the task lives on, set by `find --workspace`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleEnforceResult {
+    pub policy: StalePolicyConfig,
+    pub cutoff: String,
+    pub stale: Vec<String>,
+    pub labeled: Vec<String>,
+    pub deferred: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportInput {
+    pub redact: bool,
+}
+
+/// Full task dump for sharing outside the repo. Tasks are plain JSON so
+/// redaction can drop fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub exported_at: String,
+    pub redacted: bool,
+    pub total: usize,
+    pub tasks: Vec<Value>,
+    pub dependencies: Vec<ExportDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportDependency {
+    pub task_id: String,
+    pub blocker: String,
+    pub dep_type: DependencyType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportResult {
+    pub generated_at: String,
+    pub total: usize,
+    pub status_counts: Vec<ReportStatusCount>,
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationsResult {
+    /// False when config.json has no `escalation` policy.
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<EscalationConfig>,
+    pub escalations: Vec<Escalation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Escalation {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub priority: Priority,
+    pub effective_priority: Priority,
+    pub age_days: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStatusCount {
+    pub status: TaskStatus,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorResult {
+    pub tasks: usize,
+    pub events: usize,
+    pub snapshot_loaded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    /// Messages of every finding, kept for older consumers.
+    pub issues: Vec<String>,
+    pub findings: Vec<DoctorFinding>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorSeverity {
+    Error,
+    Warning,
+}
+
+/// One doctor check result with a stable machine-readable `code`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorFinding {
+    pub code: String,
+    pub severity: DoctorSeverity,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdsInput {
+    pub refs: Vec<String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdResolutionStatus {
+    Resolved,
+    NotFound,
+    Ambiguous,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdCandidate {
+    pub id: String,
+    pub alias: String,
+}
+
+/// One input reference (id, id prefix, alias, or external ref) and what it
+/// resolved to; `candidates` is only set when `status` is `ambiguous`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdResolution {
+    pub input: String,
+    pub status: IdResolutionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<IdCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdsResult {
+    pub results: Vec<IdResolution>,
+    pub resolved: usize,
+    pub unresolved: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedLinkResult {
+    pub src: String,
+    pub dst: String,
+    #[serde(rename = "type")]
+    pub rel_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphansResult {
+    pub orphaned_deps: Vec<RepairDep>,
+    pub orphaned_links: Vec<OrphanedLinkResult>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtreeInput {
+    pub id: String,
+    pub exact_id: bool,
+    /// Levels of descendants to include below the root; `None` for all.
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtreeResult {
+    pub tree: TaskTreeNode,
+    /// Completion across every descendant, not only direct children, and
+    /// regardless of `depth`.
+    pub rollup: ChildProgress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+    /// Descendants left out of `tree` by `depth`.
+    pub hidden: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphansAdoptInput {
+    /// Missing task id -> task (id, prefix, or alias) that takes over its edges.
+    pub map: BTreeMap<String, String>,
+    pub exact_id: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphansAdoptResult {
+    /// The mapping with surviving tasks resolved to canonical ids.
+    pub map: BTreeMap<String, String>,
+    /// Edges as rewritten onto surviving tasks.
+    pub adopted_deps: Vec<RepairDep>,
+    pub adopted_links: Vec<RepairLink>,
+    /// Orphaned edges with no mapping (or mapped onto a self-edge), removed.
+    pub removed_deps: Vec<RepairDep>,
+    pub removed_links: Vec<RepairLink>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillsRefreshInput {
+    pub source_root_dir: Option<String>,
+    pub home_dir: Option<String>,
+    pub codex_home: Option<String>,
+    pub dry_run: bool,
+    /// Local skills root, git URL (`url#path`), or `registry:<name>`.
+    pub source: Option<String>,
+    pub checksum: Option<String>,
+    /// `--all`: upgrade only managed installs that are behind the source version.
+    #[serde(default)]
+    pub only_outdated: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillsDiffInput {
+    pub skill_name: String,
+    pub source_root_dir: Option<String>,
+    pub home_dir: Option<String>,
+    pub codex_home: Option<String>,
+    pub source: Option<String>,
+    pub checksum: Option<String>,
+}
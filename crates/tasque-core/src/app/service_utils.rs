@@ -1,10 +1,13 @@
-use crate::app::service_types::ListFilter;
+use crate::app::service_types::{ListFilter, SpecStateFilter};
 use crate::domain::ids::make_root_id;
+use crate::domain::query::has_attached_spec;
 use crate::domain::resolve::resolve_task_id;
+pub use crate::domain::sort::{compare_tasks, sort_child_tasks, sort_tasks};
 use crate::errors::TsqError;
 use crate::types::{RelationType, State, Task, TaskStatus};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::BTreeSet;
 
 static DUPLICATE_TITLE_NON_ALNUM: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"[^a-z0-9]+").expect("valid duplicate title non-alnum regex"));
@@ -42,20 +45,6 @@ pub fn must_resolve_existing(state: &State, raw: &str, exact_id: bool) -> Result
     Ok(id)
 }
 
-pub fn sort_tasks(tasks: &[Task]) -> Vec<Task> {
-    let mut sorted = tasks.to_vec();
-    sorted.sort_by(|a, b| {
-        if a.priority != b.priority {
-            return a.priority.cmp(&b.priority);
-        }
-        if a.created_at == b.created_at {
-            return a.id.cmp(&b.id);
-        }
-        a.created_at.cmp(&b.created_at)
-    });
-    sorted
-}
-
 pub fn sort_stale_tasks(tasks: &[Task]) -> Vec<Task> {
     let mut sorted = tasks.to_vec();
     sorted.sort_by(|a, b| {
@@ -70,9 +59,53 @@ pub fn sort_stale_tasks(tasks: &[Task]) -> Vec<Task> {
     sorted
 }
 
-pub fn apply_list_filter(tasks: &[Task], filter: &ListFilter) -> Vec<Task> {
+/// Ids that can satisfy the status, assignee, and label clauses of `filter`,
+/// read from the state indexes; `None` when the filter sets none of them.
+pub fn indexed_candidates<'a>(state: &'a State, filter: &ListFilter) -> Option<BTreeSet<&'a str>> {
+    let indexes = &state.indexes;
+    let union = |sets: Vec<Option<&'a BTreeSet<String>>>| -> BTreeSet<&'a str> {
+        sets.into_iter()
+            .flatten()
+            .flat_map(|ids| ids.iter().map(String::as_str))
+            .collect()
+    };
+    let mut clauses: Vec<BTreeSet<&'a str>> = Vec::new();
+    if let Some(statuses) = &filter.statuses {
+        clauses.push(union(
+            statuses
+                .iter()
+                .map(|status| indexes.by_status.get(status))
+                .collect(),
+        ));
+    }
+    if let Some(assignee) = &filter.assignee {
+        clauses.push(union(vec![indexes.by_assignee.get(assignee)]));
+    }
+    if let Some(label) = &filter.label {
+        clauses.push(union(vec![indexes.by_label.get(label)]));
+    }
+    if let Some(labels) = &filter.label_any {
+        clauses.push(union(
+            labels
+                .iter()
+                .map(|label| indexes.by_label.get(label))
+                .collect(),
+        ));
+    }
+    clauses.sort_by_key(BTreeSet::len);
+    let mut clauses = clauses.into_iter();
+    let first = clauses.next()?;
+    Some(clauses.fold(first, |narrowed, clause| {
+        narrowed.intersection(&clause).copied().collect()
+    }))
+}
+
+pub fn apply_list_filter<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    filter: &ListFilter,
+) -> Vec<Task> {
     tasks
-        .iter()
+        .into_iter()
         .filter(|task| {
             if let Some(statuses) = &filter.statuses
                 && !statuses.contains(&task.status)
@@ -143,6 +176,16 @@ pub fn apply_list_filter(tasks: &[Task], filter: &ListFilter) -> Vec<Task> {
             {
                 return false;
             }
+            // Drift is checked by the caller; only attached specs can drift.
+            match filter.spec {
+                Some(SpecStateFilter::Attached | SpecStateFilter::Drifted)
+                    if !has_attached_spec(task) =>
+                {
+                    return false;
+                }
+                Some(SpecStateFilter::Missing) if has_attached_spec(task) => return false,
+                _ => {}
+            }
             true
         })
         .cloned()
@@ -1,15 +1,17 @@
+use crate::app::service_types::{BenchResult, BenchStats};
 use crate::app::sync;
 use crate::domain::projector::apply_events;
 use crate::domain::state::create_empty_state;
 use crate::errors::TsqError;
 use crate::store::config::read_config;
-use crate::store::events::{read_event_log_metadata, read_events, read_events_tail_from_path};
-use crate::store::paths::get_paths;
+use crate::store::events::{read_event_log_metadata, read_events, read_events_tail};
 use crate::store::snapshots::{load_latest_snapshot_with_warning, write_snapshot};
 use crate::store::state::{read_state_cache, write_state_cache};
+use crate::trace;
 use crate::types::{EventRecord, STATE_CACHE_SCHEMA_VERSION, Snapshot, State};
 use chrono::{SecondsFormat, Utc};
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 pub struct LoadedState {
     pub state: State,
@@ -39,25 +41,44 @@ fn load_projected_state_inner(
     include_events: bool,
 ) -> Result<LoadedState, TsqError> {
     let repo_root = repo_root.as_ref();
+    let span = tracing::debug_span!(
+        "storage.load",
+        source = tracing::field::Empty,
+        events = tracing::field::Empty
+    )
+    .entered();
     let mut shortcut_warning = None;
 
     if !include_events {
         if let Some(loaded) = load_from_state_cache(repo_root)? {
+            span.record("source", "cache");
+            span.record("events", loaded.event_count);
             return Ok(loaded);
         }
         let snapshot_result = load_from_snapshot(repo_root)?;
         if let Some(loaded) = snapshot_result.loaded {
+            span.record("source", "snapshot");
+            span.record("events", loaded.event_count);
             return Ok(loaded);
         }
         shortcut_warning = snapshot_result.warning;
     }
 
-    let read = read_events(repo_root)?;
+    let read = {
+        let _phase = trace::phase("read_events");
+        read_events(repo_root)?
+    };
     let event_count = read.metadata.event_count;
     let events = read.events;
     let event_warning = read.warning;
+    span.record("source", "replay");
+    span.record("events", event_count);
 
-    let mut projected = apply_events(&create_empty_state(), &events)?;
+    let mut projected = {
+        let _replay = tracing::debug_span!("projection.replay", events = events.len()).entered();
+        let _phase = trace::phase("apply");
+        apply_events(&create_empty_state(), &events)?
+    };
     projected.applied_events = events.len();
 
     Ok(LoadedState {
@@ -70,6 +91,7 @@ fn load_projected_state_inner(
 }
 
 fn load_from_state_cache(repo_root: &Path) -> Result<Option<LoadedState>, TsqError> {
+    let read_phase = trace::phase("read_events");
     let Some(cache) = read_state_cache(repo_root)? else {
         return Ok(None);
     };
@@ -80,13 +102,15 @@ fn load_from_state_cache(repo_root: &Path) -> Result<Option<LoadedState>, TsqErr
         return Ok(None);
     }
 
-    let events_file = get_paths(repo_root).events_file;
-    let Some(tail) = read_events_tail_from_path(&events_file, metadata)? else {
+    let Some(tail) = read_events_tail(repo_root, metadata)? else {
         return Ok(None);
     };
+    drop(read_phase);
     let mut state = if tail.events.is_empty() {
         cache.state
     } else {
+        let _replay = tracing::debug_span!("projection.tail", events = tail.events.len()).entered();
+        let _phase = trace::phase("apply");
         apply_events(&cache.state, &tail.events)?
     };
     state.applied_events = tail.metadata.event_count;
@@ -101,6 +125,7 @@ fn load_from_state_cache(repo_root: &Path) -> Result<Option<LoadedState>, TsqErr
 }
 
 fn load_from_snapshot(repo_root: &Path) -> Result<SnapshotLoadResult, TsqError> {
+    let read_phase = trace::phase("read_events");
     let loaded = load_latest_snapshot_with_warning(repo_root)?;
     let Some(snapshot) = loaded.snapshot else {
         return Ok(SnapshotLoadResult {
@@ -123,16 +148,18 @@ fn load_from_snapshot(repo_root: &Path) -> Result<SnapshotLoadResult, TsqError>
         });
     }
 
-    let events_file = get_paths(repo_root).events_file;
-    let Some(tail) = read_events_tail_from_path(&events_file, metadata)? else {
+    let Some(tail) = read_events_tail(repo_root, metadata)? else {
         return Ok(SnapshotLoadResult {
             loaded: None,
             warning: loaded.warning,
         });
     };
+    drop(read_phase);
     let mut state = if tail.events.is_empty() {
         snapshot.state.clone()
     } else {
+        let _replay = tracing::debug_span!("projection.tail", events = tail.events.len()).entered();
+        let _phase = trace::phase("apply");
         apply_events(&snapshot.state, &tail.events)?
     };
     state.applied_events = tail.metadata.event_count;
@@ -149,6 +176,50 @@ fn load_from_snapshot(repo_root: &Path) -> Result<SnapshotLoadResult, TsqError>
     })
 }
 
+/// Reads and replays the full event log `runs` times, skipping the state
+/// cache and snapshots, and reports per-phase timings and throughput.
+pub fn bench_replay(repo_root: impl AsRef<Path>, runs: usize) -> Result<BenchResult, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let mut read_times = Vec::with_capacity(runs);
+    let mut apply_times = Vec::with_capacity(runs);
+    let mut events = 0;
+    let mut tasks = 0;
+    for _ in 0..runs {
+        let started = Instant::now();
+        let read = read_events(repo_root)?;
+        read_times.push(started.elapsed());
+        let started = Instant::now();
+        let state = apply_events(&create_empty_state(), &read.events)?;
+        apply_times.push(started.elapsed());
+        events = read.events.len();
+        tasks = state.tasks.len();
+    }
+    let elapsed: Duration = read_times.iter().chain(&apply_times).sum();
+    let events_per_sec = if elapsed.is_zero() {
+        0.0
+    } else {
+        ((events * runs) as f64 / elapsed.as_secs_f64()).round()
+    };
+    Ok(BenchResult {
+        runs,
+        events,
+        tasks,
+        read_events: bench_stats(read_times),
+        apply: bench_stats(apply_times),
+        events_per_sec,
+    })
+}
+
+fn bench_stats(mut times: Vec<Duration>) -> BenchStats {
+    times.sort();
+    let at = |index: usize| times.get(index).copied().map(trace::round_ms);
+    BenchStats {
+        min_ms: at(0).unwrap_or_default(),
+        median_ms: at(times.len() / 2).unwrap_or_default(),
+        max_ms: at(times.len().saturating_sub(1)).unwrap_or_default(),
+    }
+}
+
 pub fn persist_projection(
     repo_root: impl AsRef<Path>,
     state: &mut State,
@@ -156,13 +227,19 @@ pub fn persist_projection(
     now: Option<&dyn Fn() -> String>,
 ) -> Result<(), TsqError> {
     let repo_path = repo_root.as_ref();
+    let span = tracing::debug_span!(
+        "storage.persist",
+        events = event_count,
+        snapshot = tracing::field::Empty
+    )
+    .entered();
     state.applied_events = event_count;
     let event_log = read_event_log_metadata(repo_path, event_count)?;
     write_state_cache(repo_path, state, event_log.clone())?;
 
     let config = read_config(repo_path)?;
     if config.snapshot_every == 0 {
-        sync::auto_commit_if_sync_worktree(repo_path)?;
+        sync::auto_commit_if_sync_worktree(repo_path, config.auto_sync.as_ref())?;
         return Ok(());
     }
 
@@ -179,9 +256,10 @@ pub fn persist_projection(
             state: state.clone(),
         };
         write_snapshot(repo_path, &snapshot)?;
+        span.record("snapshot", true);
     }
 
-    sync::auto_commit_if_sync_worktree(repo_path)?;
+    sync::auto_commit_if_sync_worktree(repo_path, config.auto_sync.as_ref())?;
     Ok(())
 }
 
@@ -1,7 +1,12 @@
+use crate::app::service_types::SpecDriftCheck;
 use crate::app::stdin::read_stdin_content;
+use crate::domain::criteria::{
+    AcceptanceCriterion, apply_criteria_marks, parse_acceptance_criteria,
+};
 use crate::errors::TsqError;
+use crate::store::crypto;
 use crate::types::Task;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -19,6 +24,7 @@ pub use crate::store::events::{append_events, read_events};
 pub use crate::store::lock::with_write_lock;
 pub use crate::store::paths::{
     get_paths, is_task_spec_relative_path, task_spec_file, task_spec_relative_path,
+    task_spec_version_file,
 };
 pub use crate::store::snapshots::{load_latest_snapshot, write_snapshot};
 pub use crate::store::state::{read_state_cache, write_state_cache};
@@ -122,6 +128,8 @@ const REQUIRED_SPEC_SECTIONS: &[SpecSection] = &[
     },
 ];
 
+const BUILTIN_SPEC_TEMPLATES: &[&str] = &["task", "feature", "epic"];
+
 pub fn ensure_events_file(repo_root: impl AsRef<Path>) -> Result<(), TsqError> {
     let paths = get_paths(repo_root);
     create_dir_all(&paths.tasque_dir).map_err(|error| {
@@ -173,6 +181,7 @@ pub fn write_task_spec_atomic(
     task_id: &str,
     content: &str,
 ) -> Result<SpecWriteResult, TsqError> {
+    let sealed = crypto::seal_for_repo(repo_root.as_ref(), crypto::SPEC, content.to_string())?;
     let spec_file = task_spec_file(repo_root, task_id);
     let spec_path = task_spec_relative_path(task_id);
     if let Some(parent) = spec_file.parent() {
@@ -198,7 +207,7 @@ pub fn write_task_spec_atomic(
                 TsqError::new("IO_ERROR", "failed writing attached spec", 2)
                     .with_details(io_error_value(&error))
             })?;
-        handle.write_all(content.as_bytes()).map_err(|error| {
+        handle.write_all(sealed.as_bytes()).map_err(|error| {
             TsqError::new("IO_ERROR", "failed writing attached spec", 2)
                 .with_details(io_error_value(&error))
         })?;
@@ -210,7 +219,7 @@ pub fn write_task_spec_atomic(
             TsqError::new("IO_ERROR", "failed writing attached spec", 2)
                 .with_details(io_error_value(&error))
         })?;
-        let loaded = read_to_string(&spec_file).map_err(|error| {
+        let loaded = crypto::read_text(&spec_file, crypto::SPEC).map_err(|error| {
             TsqError::new("IO_ERROR", "failed writing attached spec", 2)
                 .with_details(io_error_value(&error))
         })?;
@@ -227,6 +236,60 @@ pub fn write_task_spec_atomic(
     result
 }
 
+/// Keeps a copy of attached spec content so later drift can be diffed
+/// against exactly what was fingerprinted. Versions are immutable, so an
+/// existing file is left in place.
+pub fn write_task_spec_version(
+    repo_root: impl AsRef<Path>,
+    task_id: &str,
+    fingerprint: &str,
+    content: &str,
+) -> Result<(), TsqError> {
+    let version_file = task_spec_version_file(repo_root.as_ref(), task_id, fingerprint);
+    if version_file.exists() {
+        return Ok(());
+    }
+    let content = crypto::seal_for_repo(repo_root, crypto::SPEC, content.to_string())?;
+    if let Some(parent) = version_file.parent() {
+        create_dir_all(parent).map_err(|error| {
+            TsqError::new("IO_ERROR", "failed writing spec version", 2)
+                .with_details(io_error_value(&error))
+        })?;
+    }
+    let temp = format!(
+        "{}.tmp-{}-{}",
+        version_file.display(),
+        std::process::id(),
+        Utc::now().timestamp_millis()
+    );
+    let result = std::fs::write(&temp, content.as_bytes())
+        .and_then(|_| rename(&temp, &version_file))
+        .map_err(|error| {
+            TsqError::new("IO_ERROR", "failed writing spec version", 2)
+                .with_details(io_error_value(&error))
+        });
+    if result.is_err() {
+        let _ = remove_file(&temp);
+    }
+    result
+}
+
+/// Returns the stored content for an attached fingerprint, or `None` when the
+/// spec was attached before versions were recorded.
+pub fn read_task_spec_version(
+    repo_root: impl AsRef<Path>,
+    task_id: &str,
+    fingerprint: &str,
+) -> Result<Option<String>, TsqError> {
+    let version_file = task_spec_version_file(repo_root, task_id, fingerprint);
+    match crypto::read_text(&version_file, crypto::SPEC) {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(TsqError::new("IO_ERROR", "failed reading spec version", 2)
+            .with_details(io_error_value(&error))),
+    }
+}
+
 pub fn evaluate_task_spec(
     repo_root: impl AsRef<Path>,
     task_id: &str,
@@ -276,7 +339,7 @@ pub fn evaluate_task_spec(
 
     if metadata_path_valid && let Some(spec_path_value) = spec_path.clone() {
         let resolved = resolve_spec_path(repo_root, &spec_path_value);
-        match read_to_string(&resolved) {
+        match crypto::read_text(&resolved, crypto::SPEC) {
             Ok(value) => content = Some(value),
             Err(error) => {
                 if error.kind() == std::io::ErrorKind::NotFound {
@@ -355,6 +418,96 @@ pub fn evaluate_task_spec(
     })
 }
 
+/// Renders a spec scaffold for `template`. A markdown file under
+/// `.tasque/spec-templates/<template>.md` wins over the built-in scaffold,
+/// which emits one heading per required section. `{{title}}` and `{{id}}`
+/// placeholders are substituted in both.
+pub fn render_spec_template(
+    repo_root: impl AsRef<Path>,
+    template: &str,
+    task: &Task,
+) -> Result<String, TsqError> {
+    let template = template.trim();
+    if template.is_empty()
+        || !template
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "spec template name must contain only letters, digits, '-' or '_'",
+            1,
+        )
+        .with_details(serde_json::json!({"template": template})));
+    }
+
+    let custom_path = get_paths(repo_root)
+        .spec_templates_dir
+        .join(format!("{}.md", template));
+    let raw = match read_to_string(&custom_path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            if !BUILTIN_SPEC_TEMPLATES.contains(&template) {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("unknown spec template: {}", template),
+                    1,
+                )
+                .with_details(serde_json::json!({
+                    "template": template,
+                    "builtin": BUILTIN_SPEC_TEMPLATES,
+                    "custom_dir": ".tasque/spec-templates",
+                })));
+            }
+            builtin_spec_template()
+        }
+        Err(error) => {
+            return Err(TsqError::new(
+                "IO_ERROR",
+                format!("failed reading spec template: {}", custom_path.display()),
+                2,
+            )
+            .with_details(io_error_value(&error)));
+        }
+    };
+
+    Ok(raw
+        .replace("{{title}}", &task.title)
+        .replace("{{id}}", &task.id))
+}
+
+fn builtin_spec_template() -> String {
+    let mut content = String::from("# {{title}}\n");
+    for section in REQUIRED_SPEC_SECTIONS {
+        content.push_str(&format!("\n## {}\n\n", section.label));
+    }
+    content
+}
+
+/// Reads acceptance criteria from the task's attached spec with recorded
+/// completion applied. Tasks without a readable spec have no criteria.
+pub fn read_task_acceptance_criteria(
+    repo_root: impl AsRef<Path>,
+    task: &Task,
+) -> Result<Vec<AcceptanceCriterion>, TsqError> {
+    let Some(spec_path) = normalize_optional_input(task.spec_path.as_deref()) else {
+        return Ok(Vec::new());
+    };
+    match crypto::read_text(resolve_spec_path(repo_root, &spec_path), crypto::SPEC) {
+        Ok(content) => Ok(apply_criteria_marks(
+            task,
+            parse_acceptance_criteria(&content),
+        )),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(TsqError::new(
+            "IO_ERROR",
+            format!("failed reading attached spec file: {}", spec_path),
+            2,
+        )
+        .with_details(io_error_value(&error))),
+    }
+}
+
 pub fn resolve_spec_attach_source(input: &SpecAttachInput) -> Result<SpecAttachSource, TsqError> {
     let file = normalize_optional_input(input.file.as_deref());
     let positional = normalize_optional_input(input.source.as_deref());
@@ -401,6 +554,52 @@ pub fn read_spec_attach_content(source: &SpecAttachSource) -> Result<String, Tsq
     }
 }
 
+const MTIME_SLACK: chrono::Duration = chrono::Duration::seconds(2);
+
+/// Whether the attached spec file no longer matches the recorded fingerprint.
+/// A missing file counts as drifted. `Mtime` skips hashing files last written
+/// more than `MTIME_SLACK` before `spec_attached_at`, since filesystem clocks
+/// tick coarser than event timestamps; anything newer, or without a usable
+/// timestamp, is hashed.
+pub fn spec_has_drifted(
+    repo_root: impl AsRef<Path>,
+    task: &Task,
+    check: SpecDriftCheck,
+) -> Result<bool, TsqError> {
+    let (Some(spec_path), Some(expected)) = (
+        normalize_optional_input(task.spec_path.as_deref()),
+        normalize_optional_input(task.spec_fingerprint.as_deref()),
+    ) else {
+        return Ok(false);
+    };
+    let resolved = resolve_spec_path(repo_root, &spec_path);
+    if check == SpecDriftCheck::Mtime {
+        let modified = std::fs::metadata(&resolved)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        let attached_at = task
+            .spec_attached_at
+            .as_deref()
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok());
+        if let (Some(modified), Some(attached_at)) = (modified, attached_at)
+            && modified + MTIME_SLACK < attached_at
+        {
+            return Ok(false);
+        }
+    }
+    match crypto::read_text(&resolved, crypto::SPEC) {
+        Ok(content) => Ok(sha256(&content) != expected),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(true),
+        Err(error) => Err(TsqError::new(
+            "IO_ERROR",
+            format!("failed reading attached spec file: {}", spec_path),
+            2,
+        )
+        .with_details(io_error_value(&error))),
+    }
+}
+
 pub fn sha256(content: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(content.as_bytes());
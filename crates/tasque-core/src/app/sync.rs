@@ -2,10 +2,14 @@ use crate::errors::TsqError;
 use crate::store::config::{read_config, write_config};
 use crate::store::events::{append_events, read_events};
 use crate::store::git;
+use crate::store::lock::with_write_lock;
 use crate::store::paths::get_paths;
 use crate::types::{
-    HookInstallResult, HookUninstallResult, MigrateResult, SyncRunResult, SyncSetupResult,
+    AutoSyncConfig, EventRecord, HookInstallResult, HookUninstallResult, MigrateResult,
+    SyncDisableResult, SyncMergeDriverStatus, SyncRemoteResult, SyncRunResult, SyncSetupResult,
+    SyncStatusResult,
 };
+use chrono::Utc;
 use std::collections::HashSet;
 use std::path::Path;
 use std::time::{Duration, Instant};
@@ -18,8 +22,9 @@ pub const DEFAULT_SYNC_BRANCH: &str = "tsq-sync";
 /// Resolve the effective root directory for data operations.
 ///
 /// If the config specifies a `sync_branch`, the data root is redirected to the
-/// worktree for that branch. Legacy git repos without `sync_branch` are migrated
-/// to the default sync worktree automatically.
+/// worktree for that branch, or for the `sync_routes` entry whose project path
+/// contains the current directory. Legacy git repos without `sync_branch` are migrated
+/// to the default sync worktree automatically unless sync was disabled.
 pub fn resolve_effective_root(repo_root: &str) -> Result<String, TsqError> {
     let config = read_config(repo_root)?;
 
@@ -27,7 +32,10 @@ pub fn resolve_effective_root(repo_root: &str) -> Result<String, TsqError> {
         Some(branch) => branch,
         None => {
             let repo_path = Path::new(repo_root);
-            if git::is_git_repo(repo_path) && !git::is_sync_worktree_path(repo_path) {
+            if !config.sync_disabled
+                && git::is_git_repo(repo_path)
+                && !git::is_sync_worktree_path(repo_path)
+            {
                 let migrated = migrate_to_sync_branch(repo_root, DEFAULT_SYNC_BRANCH, "tsq")?;
                 return Ok(migrated.worktree_path);
             }
@@ -36,10 +44,46 @@ pub fn resolve_effective_root(repo_root: &str) -> Result<String, TsqError> {
     };
 
     let repo_path = Path::new(repo_root);
-    if let Some(quick_path) = git::quick_worktree_path(repo_path, &branch)
-        && git::worktree_is_valid(&quick_path, &branch)
+    let cwd = std::env::current_dir().unwrap_or_else(|_| repo_path.to_path_buf());
+    let (branch, routed) = match routed_branch(&config.sync_routes, repo_path, &cwd) {
+        Some(route) => (route.to_string(), true),
+        None => (branch, false),
+    };
+    Ok(ensure_branch_worktree(repo_root, &branch, routed)?
+        .to_string_lossy()
+        .to_string())
+}
+
+/// The branch of the route with the longest project path containing `cwd`.
+pub fn routed_branch<'a>(
+    routes: &'a [crate::types::SyncRoute],
+    repo_root: &Path,
+    cwd: &Path,
+) -> Option<&'a str> {
+    let root = repo_root
+        .canonicalize()
+        .unwrap_or_else(|_| repo_root.to_path_buf());
+    let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+    let relative = cwd.strip_prefix(&root).ok()?;
+    routes
+        .iter()
+        .filter(|route| relative.starts_with(&route.path))
+        .max_by_key(|route| Path::new(&route.path).components().count())
+        .map(|route| route.branch.as_str())
+}
+
+/// Worktree for `branch`, created on demand. A routed shard branch that
+/// exists neither locally nor on the remote starts as an empty event log.
+fn ensure_branch_worktree(
+    repo_root: &str,
+    branch: &str,
+    create_missing: bool,
+) -> Result<std::path::PathBuf, TsqError> {
+    let repo_path = Path::new(repo_root);
+    if let Some(quick_path) = git::quick_worktree_path(repo_path, branch)
+        && git::worktree_is_valid(&quick_path, branch)
     {
-        return Ok(quick_path.to_string_lossy().to_string());
+        return Ok(quick_path);
     }
 
     if !git::is_git_repo(repo_path) {
@@ -50,8 +94,52 @@ pub fn resolve_effective_root(repo_root: &str) -> Result<String, TsqError> {
         ));
     }
 
-    let worktree = with_setup_lock(repo_root, || git::ensure_worktree(repo_path, &branch))?;
-    Ok(worktree.to_string_lossy().to_string())
+    with_setup_lock(repo_root, || {
+        if create_missing
+            && !git::branch_exists(repo_path, branch)?
+            && !git::remote_tracking_branch_exists(repo_path, branch)?
+            && !git::remote_branch_exists(repo_path, branch)?
+        {
+            let seed = tempfile::tempdir().map_err(|error| {
+                TsqError::new("IO_ERROR", "failed creating shard seed directory", 2)
+                    .with_details(serde_json::json!({"message": error.to_string()}))
+            })?;
+            let seed_dir = seed.path().join(".tasque");
+            ensure_seed_tasque_dir(&seed_dir)?;
+            git::create_orphan_branch(repo_path, branch, &seed_dir)?;
+        }
+        git::ensure_worktree(repo_path, branch)
+    })
+}
+
+/// Every shard of the workspace as `(branch, worktree)`: the `sync_branch`
+/// followed by each routed branch. Outside a sync worktree the local
+/// `.tasque/` is the only shard.
+pub fn workspace_roots(repo_root: &str) -> Result<Vec<(String, String)>, TsqError> {
+    let path = Path::new(repo_root);
+    if !git::is_git_repo(path) || !git::is_sync_worktree_path(path) {
+        return Ok(vec![("local".to_string(), repo_root.to_string())]);
+    }
+    let main_root = main_worktree_root(path)?;
+    let main_root_str = main_root.to_string_lossy().to_string();
+    let config = read_config(&main_root_str)?;
+    let mut branches: Vec<(String, bool)> = config
+        .sync_branch
+        .into_iter()
+        .map(|branch| (branch, false))
+        .collect();
+    for route in config.sync_routes {
+        if !branches.iter().any(|(branch, _)| *branch == route.branch) {
+            branches.push((route.branch, true));
+        }
+    }
+    branches
+        .into_iter()
+        .map(|(branch, routed)| {
+            let worktree = ensure_branch_worktree(&main_root_str, &branch, routed)?;
+            Ok((branch, worktree.to_string_lossy().to_string()))
+        })
+        .collect()
 }
 
 /// Set up sync branch infrastructure for a repository.
@@ -107,6 +195,12 @@ fn setup_sync_branch_locked(repo_root: &str, branch: &str) -> Result<SyncSetupRe
 
     let updated_config = crate::types::Config {
         sync_branch: Some(branch.to_string()),
+        hooks: crate::types::HooksConfig::default(),
+        policies: Vec::new(),
+        escalation: None,
+        stale: None,
+        auto_sync: None,
+        sync_disabled: false,
         ..config
     };
     write_config(repo_root, &updated_config)?;
@@ -133,25 +227,7 @@ pub fn migrate_to_sync_branch(
     let setup = setup_sync_branch(repo_root, branch, actor)?;
 
     let worktree_existing = read_events(&setup.worktree_path)?;
-    let mut seen_ids = HashSet::new();
-    for event in &worktree_existing.events {
-        if let Some(id) = event.id.as_deref().or(event.event_id.as_deref()) {
-            seen_ids.insert(id.to_string());
-        }
-    }
-
-    let mut to_append = Vec::new();
-    for event in &existing.events {
-        let id = event.id.as_deref().or(event.event_id.as_deref());
-        if let Some(id) = id {
-            if !seen_ids.contains(id) {
-                to_append.push(event.clone());
-            }
-        } else {
-            to_append.push(event.clone());
-        }
-    }
-
+    let to_append = events_missing_from(&worktree_existing.events, &existing.events);
     if !to_append.is_empty() {
         append_events(&setup.worktree_path, &to_append)?;
     }
@@ -172,6 +248,278 @@ pub fn migrate_to_sync_branch(
 
 pub fn sync_worktree(repo_root: &str, push: bool) -> Result<SyncRunResult, TsqError> {
     let path = Path::new(repo_root);
+    let branch = require_sync_worktree(path)?;
+    let committed = git::commit_worktree(path, SYNC_COMMIT_MESSAGE)?;
+    let (pushed, has_upstream) = if push {
+        push_worktree(path, &branch)?
+    } else {
+        (false, git::has_upstream(path)?)
+    };
+    Ok(SyncRunResult {
+        branch,
+        worktree_path: path.to_string_lossy().to_string(),
+        committed,
+        pushed,
+        has_upstream,
+    })
+}
+
+/// Commit pending events, fetch the sync branch, and rebase local commits
+/// onto the remote, resolving event-log merges with the merge driver.
+pub fn pull_sync_branch(repo_root: &str) -> Result<SyncRemoteResult, TsqError> {
+    sync_with_remote(repo_root, false)
+}
+
+/// Like [`pull_sync_branch`], then push the rebased branch to the remote.
+pub fn push_sync_branch(repo_root: &str) -> Result<SyncRemoteResult, TsqError> {
+    sync_with_remote(repo_root, true)
+}
+
+fn sync_with_remote(repo_root: &str, push: bool) -> Result<SyncRemoteResult, TsqError> {
+    let path = Path::new(repo_root);
+    let branch = require_sync_worktree(path)?;
+    // The rebase rewrites events.jsonl, so hold the write lock like a mutation.
+    with_write_lock(repo_root, || sync_with_remote_locked(path, branch, push))
+}
+
+fn sync_with_remote_locked(
+    path: &Path,
+    branch: String,
+    push: bool,
+) -> Result<SyncRemoteResult, TsqError> {
+    let committed = git::commit_worktree(path, SYNC_COMMIT_MESSAGE)?;
+    let remote = git::current_upstream_remote(path)?.ok_or_else(|| {
+        TsqError::new(
+            "SYNC_NO_REMOTE",
+            "no remote configured for the sync branch; add an origin remote",
+            1,
+        )
+    })?;
+
+    let pulled_commits = if git::fetch_branch_from(path, &remote, &branch)? {
+        git::rebase_onto_remote(path, &remote, &branch, &merge_driver_command())?
+    } else {
+        0
+    };
+    if push {
+        git::push_current_set_upstream(path, &remote, &branch)?;
+    }
+
+    Ok(SyncRemoteResult {
+        branch,
+        worktree_path: path.to_string_lossy().to_string(),
+        remote,
+        committed,
+        pulled_commits,
+        pushed: push,
+    })
+}
+
+/// Undo [`setup_sync_branch`]: move events, specs, and policy config from the
+/// sync worktree back into the repo root `.tasque/`, remove the worktree, and
+/// mark the root config `sync_disabled` so it is not migrated again.
+/// Pending events are committed to the branch first so nothing is lost if
+/// the branch is kept.
+pub fn disable_sync(repo_root: &str, delete_branch: bool) -> Result<SyncDisableResult, TsqError> {
+    let worktree = Path::new(repo_root);
+    let branch = require_sync_worktree(worktree)?;
+    let main_root = main_worktree_root(worktree)?;
+    let main_root_str = main_root.to_string_lossy().to_string();
+
+    let (events_migrated, specs_copied) = with_write_lock(repo_root, || {
+        git::commit_worktree(worktree, SYNC_COMMIT_MESSAGE)?;
+        let worktree_events = read_events(repo_root)?;
+        let root_events = read_events(&main_root_str)?;
+        let to_append = events_missing_from(&root_events.events, &worktree_events.events);
+        if !to_append.is_empty() {
+            append_events(&main_root_str, &to_append)?;
+        }
+
+        let from = get_paths(worktree);
+        let to = get_paths(&main_root);
+        let mut specs_copied = copy_dir_files(&from.specs_dir, &to.specs_dir)?;
+        specs_copied += copy_dir_files(&from.spec_templates_dir, &to.spec_templates_dir)?;
+        // The root cache and snapshots predate the worktree; rebuild from events.
+        let _ = std::fs::remove_file(&to.state_file);
+        let _ = std::fs::remove_dir_all(&to.snapshots_dir);
+
+        let config = crate::types::Config {
+            sync_branch: None,
+            auto_sync: None,
+            sync_disabled: true,
+            ..read_config(repo_root)?
+        };
+        write_config(&main_root_str, &config)?;
+        Ok((to_append.len(), specs_copied))
+    })?;
+
+    with_setup_lock(&main_root_str, || {
+        git::remove_worktree(&main_root, worktree)?;
+        if delete_branch {
+            git::delete_branch(&main_root, &branch)?;
+        }
+        Ok(())
+    })?;
+
+    Ok(SyncDisableResult {
+        branch,
+        worktree_path: repo_root.to_string(),
+        repo_root: main_root_str,
+        events_migrated,
+        specs_copied,
+        branch_deleted: delete_branch,
+    })
+}
+
+/// The main checkout that owns the sync worktree: the parent of the shared
+/// git dir.
+fn main_worktree_root(worktree: &Path) -> Result<std::path::PathBuf, TsqError> {
+    let common = git::git_common_dir(worktree)?;
+    let common = common.canonicalize().unwrap_or(common);
+    common
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| TsqError::new("GIT_ERROR", "failed locating main worktree", 2))
+}
+
+/// Copy every file under `from` into `to`, overwriting; returns the count.
+fn copy_dir_files(from: &Path, to: &Path) -> Result<usize, TsqError> {
+    let entries = match std::fs::read_dir(from) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(copy_error(error)),
+    };
+    std::fs::create_dir_all(to).map_err(copy_error)?;
+    let mut copied = 0;
+    for entry in entries {
+        let entry = entry.map_err(copy_error)?;
+        let target = to.join(entry.file_name());
+        if entry.file_type().map_err(copy_error)?.is_dir() {
+            copied += copy_dir_files(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target).map_err(copy_error)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+fn copy_error(error: std::io::Error) -> TsqError {
+    TsqError::new("IO_ERROR", "failed copying sync worktree files", 2)
+        .with_details(serde_json::json!({"message": error.to_string()}))
+}
+
+/// Report sync health without changing anything except, with `fetch`, the
+/// remote-tracking ref. Problems are collected rather than returned as errors
+/// so CI can inspect the whole picture.
+pub fn sync_status(repo_root: &str, fetch: bool) -> Result<SyncStatusResult, TsqError> {
+    let path = Path::new(repo_root);
+    let mut status = SyncStatusResult {
+        configured: false,
+        branch: None,
+        worktree_path: None,
+        worktree_valid: false,
+        uncommitted_events: 0,
+        dirty: false,
+        remote: None,
+        ahead: None,
+        behind: None,
+        merge_driver: SyncMergeDriverStatus {
+            gitattributes: false,
+            driver: None,
+        },
+        daemon: None,
+        healthy: false,
+        problems: Vec::new(),
+    };
+    if !git::is_git_repo(path) || !git::is_sync_worktree_path(path) {
+        status
+            .problems
+            .push("sync branch is not configured for this repository".to_string());
+        return Ok(status);
+    }
+    let branch = require_sync_worktree(path)?;
+    status.configured = true;
+    status.worktree_path = Some(repo_root.to_string());
+    status.worktree_valid = git::worktree_is_valid(path, &branch);
+    if !status.worktree_valid {
+        status
+            .problems
+            .push(format!("worktree is not checked out on '{branch}'"));
+    }
+
+    let events_file = get_paths(repo_root).events_file;
+    let current = std::fs::read_to_string(&events_file).unwrap_or_default();
+    let committed = git::show_head_file(path, ".tasque/events.jsonl")?.unwrap_or_default();
+    status.uncommitted_events =
+        count_event_lines(&current).saturating_sub(count_event_lines(&committed));
+    status.dirty = git::has_uncommitted_changes(path)?;
+
+    status.remote = git::current_upstream_remote(path)?;
+    match status.remote.as_deref() {
+        Some(remote) => {
+            if fetch && !git::fetch_branch_from(path, remote, &branch)? {
+                status
+                    .problems
+                    .push(format!("'{branch}' does not exist on {remote}"));
+            }
+            if let Some((ahead, behind)) = git::ahead_behind(path, remote, &branch)? {
+                status.ahead = Some(ahead);
+                status.behind = Some(behind);
+            }
+        }
+        None => status
+            .problems
+            .push("no remote configured for the sync branch".to_string()),
+    }
+
+    let attributes = std::fs::read_to_string(path.join(".gitattributes")).unwrap_or_default();
+    status.merge_driver.gitattributes = attributes
+        .lines()
+        .any(|line| line.trim() == ".tasque/events.jsonl merge=tasque-events");
+    status.merge_driver.driver = git::config_value(path, "merge.tasque-events.driver")?;
+    if !status.merge_driver.gitattributes {
+        status.problems.push(
+            ".gitattributes does not route .tasque/events.jsonl to the tasque-events driver"
+                .to_string(),
+        );
+    }
+    if status.merge_driver.driver.is_none() {
+        status
+            .problems
+            .push("merge.tasque-events.driver is not set in git config".to_string());
+    }
+
+    status.daemon = crate::app::sync_daemon::read_daemon_status(path)?;
+    if let Some(error) = status
+        .daemon
+        .as_ref()
+        .and_then(|daemon| daemon.last_error.as_deref())
+    {
+        status
+            .problems
+            .push(format!("sync daemon failing: {error}"));
+    }
+    status.branch = Some(branch);
+    status.healthy = status.problems.is_empty();
+    Ok(status)
+}
+
+fn count_event_lines(raw: &str) -> usize {
+    raw.lines().filter(|line| !line.trim().is_empty()).count()
+}
+
+/// The merge driver invocation for this binary, so rebases resolve event
+/// merges even when `tsq` is not on PATH.
+fn merge_driver_command() -> String {
+    match std::env::current_exe() {
+        Ok(exe) => format!("\"{}\" merge-driver %O %A %B", exe.display()),
+        Err(_) => "tsq merge-driver %O %A %B".to_string(),
+    }
+}
+
+/// Returns the sync branch name when `path` is a sync worktree.
+pub(crate) fn require_sync_worktree(path: &Path) -> Result<String, TsqError> {
     if !git::is_git_repo(path) {
         return Err(TsqError::new(
             "GIT_NOT_AVAILABLE",
@@ -186,38 +534,55 @@ pub fn sync_worktree(repo_root: &str, push: bool) -> Result<SyncRunResult, TsqEr
             1,
         ));
     }
+    git::current_branch(path)?
+        .ok_or_else(|| TsqError::new("GIT_ERROR", "failed determining current branch", 2))
+}
 
-    let branch = git::current_branch(path)?
-        .ok_or_else(|| TsqError::new("GIT_ERROR", "failed determining current branch", 2))?;
-    let committed = git::commit_worktree(path, SYNC_COMMIT_MESSAGE)?;
-    let mut has_upstream = git::has_upstream(path)?;
-    let pushed = if !push {
-        false
-    } else if has_upstream {
+/// Push to the upstream, or to `origin` with upstream tracking when none is
+/// set. Returns `(pushed, has_upstream)`.
+fn push_worktree(path: &Path, branch: &str) -> Result<(bool, bool), TsqError> {
+    if git::has_upstream(path)? {
         git::push_current(path)?;
-        true
+        Ok((true, true))
     } else if git::has_remote(path, "origin")? {
-        git::push_current_set_upstream(path, "origin", &branch)?;
-        has_upstream = true;
-        true
+        git::push_current_set_upstream(path, "origin", branch)?;
+        Ok((true, true))
     } else {
-        false
-    };
-    Ok(SyncRunResult {
-        branch,
-        worktree_path: path.to_string_lossy().to_string(),
-        committed,
-        pushed,
-        has_upstream,
-    })
+        Ok((false, false))
+    }
 }
 
-pub fn auto_commit_if_sync_worktree(repo_root: impl AsRef<Path>) -> Result<(), TsqError> {
+/// Commit (and optionally push) pending events after a mutation, following
+/// the `auto_sync` config. A failed push only logs a warning: the events are
+/// already durable locally and the next sync retries.
+pub fn auto_commit_if_sync_worktree(
+    repo_root: impl AsRef<Path>,
+    auto_sync: Option<&AutoSyncConfig>,
+) -> Result<(), TsqError> {
     let path = repo_root.as_ref();
     if !git::is_sync_worktree_path(path) {
         return Ok(());
     }
-    let _ = git::commit_worktree(path, SYNC_COMMIT_MESSAGE)?;
+    let policy = auto_sync.cloned().unwrap_or_default();
+    if !policy.commit {
+        return Ok(());
+    }
+    if policy.throttle_secs > 0
+        && let Some(last_commit) = git::head_commit_epoch(path)?
+    {
+        let elapsed = Utc::now().timestamp().saturating_sub(last_commit);
+        if elapsed < policy.throttle_secs as i64 {
+            return Ok(());
+        }
+    }
+    let committed = git::commit_worktree(path, SYNC_COMMIT_MESSAGE)?;
+    if committed
+        && policy.push
+        && let Some(branch) = git::current_branch(path)?
+        && let Err(error) = push_worktree(path, &branch)
+    {
+        tracing::warn!("auto-sync push failed: {}", error.message);
+    }
     Ok(())
 }
 
@@ -340,11 +705,7 @@ fn ensure_seed_tasque_dir(tasque_dir: &Path) -> Result<(), TsqError> {
 
     let config_file = tasque_dir.join("config.json");
     if !config_file.exists() {
-        let default = crate::types::Config {
-            schema_version: crate::types::SCHEMA_VERSION,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let default = crate::types::Config::default();
         let json = serde_json::to_string_pretty(&default).map_err(|e| {
             TsqError::new("IO_ERROR", "failed serializing seed config", 2)
                 .with_details(serde_json::json!({"message": e.to_string()}))
@@ -367,6 +728,26 @@ fn ensure_seed_tasque_dir(tasque_dir: &Path) -> Result<(), TsqError> {
     Ok(())
 }
 
+/// Events from `incoming` whose id is not already in `existing`; events
+/// without an id are always kept.
+fn events_missing_from(existing: &[EventRecord], incoming: &[EventRecord]) -> Vec<EventRecord> {
+    let seen_ids: HashSet<&str> = existing
+        .iter()
+        .filter_map(|event| event.id.as_deref().or(event.event_id.as_deref()))
+        .collect();
+    incoming
+        .iter()
+        .filter(|event| {
+            event
+                .id
+                .as_deref()
+                .or(event.event_id.as_deref())
+                .is_none_or(|id| !seen_ids.contains(id))
+        })
+        .cloned()
+        .collect()
+}
+
 fn clear_repo_events(repo_root: &str) -> Result<(), TsqError> {
     let paths = get_paths(repo_root);
     std::fs::write(&paths.events_file, "").map_err(|e| {
@@ -492,16 +873,42 @@ mod tests {
     use super::*;
     use std::sync::Arc;
 
+    #[test]
+    fn routed_branch_prefers_longest_matching_project_path() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("apps/web/src")).expect("mkdir");
+        std::fs::create_dir_all(root.join("apps-legacy")).expect("mkdir");
+        let routes = vec![
+            crate::types::SyncRoute {
+                branch: "tsq-apps".to_string(),
+                path: "apps".to_string(),
+            },
+            crate::types::SyncRoute {
+                branch: "tsq-web".to_string(),
+                path: "apps/web".to_string(),
+            },
+        ];
+
+        let web = routed_branch(&routes, root, &root.join("apps/web/src"));
+        assert_eq!(web, Some("tsq-web"));
+        assert_eq!(
+            routed_branch(&routes, root, &root.join("apps")),
+            Some("tsq-apps")
+        );
+        assert_eq!(
+            routed_branch(&routes, root, &root.join("apps-legacy")),
+            None
+        );
+        assert_eq!(routed_branch(&routes, root, root), None);
+    }
+
     #[test]
     fn resolve_effective_root_returns_repo_root_when_no_sync_branch() {
         let dir = tempfile::TempDir::new().expect("tempdir");
         let repo = dir.path();
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
-        let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = crate::types::Config::default();
         write_config(repo, &config).expect("write_config");
 
         let result =
@@ -515,9 +922,8 @@ mod tests {
         let repo = dir.path();
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
         let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
             sync_branch: Some(DEFAULT_SYNC_BRANCH.to_string()),
+            ..crate::types::Config::default()
         };
         write_config(repo, &config).expect("write_config");
 
@@ -553,11 +959,7 @@ mod tests {
             .expect("git config");
 
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
-        let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = crate::types::Config::default();
         write_config(repo, &config).expect("write_config");
 
         let mut created_payload = serde_json::Map::new();
@@ -599,11 +1001,7 @@ mod tests {
         let dir = tempfile::TempDir::new().expect("tempdir");
         let repo = dir.path();
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
-        let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = crate::types::Config::default();
         write_config(repo, &config).expect("write_config");
 
         let err = setup_sync_branch(&repo.to_string_lossy(), DEFAULT_SYNC_BRANCH, "test")
@@ -623,11 +1021,7 @@ mod tests {
             .expect("git init");
 
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
-        let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = crate::types::Config::default();
         write_config(repo, &config).expect("write_config");
 
         let err = setup_sync_branch(&repo.to_string_lossy(), "bad branch name", "test")
@@ -679,11 +1073,7 @@ mod tests {
             .expect("git init");
 
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
-        let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = crate::types::Config::default();
         write_config(repo, &config).expect("write_config");
 
         let mut created_payload = serde_json::Map::new();
@@ -763,11 +1153,7 @@ mod tests {
             .expect("git commit");
 
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
-        let config = crate::types::Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = crate::types::Config::default();
         write_config(&repo, &config).expect("write_config");
 
         let repo1 = Arc::new(repo);
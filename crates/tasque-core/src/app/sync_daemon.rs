@@ -0,0 +1,165 @@
+use crate::app::sync::{push_sync_branch, require_sync_worktree};
+use crate::errors::TsqError;
+use crate::store::git;
+use crate::store::paths::get_paths;
+use crate::types::SyncDaemonStatus;
+use chrono::{SecondsFormat, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const STATUS_FILE: &str = "tsq-sync-status.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+pub const MAX_BACKOFF_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SyncDaemonOptions {
+    /// Seconds between syncs when the event log is unchanged.
+    pub interval_secs: u64,
+    /// Run a single sync cycle and return.
+    pub once: bool,
+}
+
+/// Delay before the next attempt: the interval, doubled per consecutive
+/// failure and capped at [`MAX_BACKOFF_SECS`].
+pub fn backoff_secs(interval_secs: u64, consecutive_failures: u32) -> u64 {
+    if consecutive_failures == 0 {
+        return interval_secs;
+    }
+    let factor = 1u64.checked_shl(consecutive_failures).unwrap_or(u64::MAX);
+    interval_secs
+        .saturating_mul(factor)
+        .min(MAX_BACKOFF_SECS.max(interval_secs))
+}
+
+/// The daemon status lives in the worktree's private git dir so it is never
+/// committed to the sync branch.
+pub fn status_file_path(worktree: &Path) -> Result<PathBuf, TsqError> {
+    Ok(git::git_dir(worktree)?.join(STATUS_FILE))
+}
+
+pub fn read_daemon_status(worktree: &Path) -> Result<Option<SyncDaemonStatus>, TsqError> {
+    let path = status_file_path(worktree)?;
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(
+                TsqError::new("IO_ERROR", "failed reading sync daemon status", 2)
+                    .with_details(serde_json::json!({"message": error.to_string()})),
+            );
+        }
+    };
+    // A torn or foreign file is treated as "no status" rather than an error.
+    Ok(serde_json::from_str(&raw).ok())
+}
+
+fn write_daemon_status(worktree: &Path, status: &SyncDaemonStatus) -> Result<(), TsqError> {
+    let path = status_file_path(worktree)?;
+    let tmp = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(status).map_err(|error| {
+        TsqError::new("IO_ERROR", "failed serializing sync daemon status", 2)
+            .with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+    fs::write(&tmp, format!("{}\n", json))
+        .and_then(|_| fs::rename(&tmp, &path))
+        .map_err(|error| {
+            TsqError::new("IO_ERROR", "failed writing sync daemon status", 2)
+                .with_details(serde_json::json!({"message": error.to_string()}))
+        })
+}
+
+/// Commit, pull, and push the sync branch whenever the event log changes or
+/// `interval_secs` passes, backing off exponentially on failures. Each cycle
+/// rewrites the status file and is reported to `on_cycle`.
+pub fn run_sync_daemon(
+    repo_root: &str,
+    options: SyncDaemonOptions,
+    mut on_cycle: impl FnMut(&SyncDaemonStatus),
+) -> Result<SyncDaemonStatus, TsqError> {
+    let worktree = Path::new(repo_root);
+    let branch = require_sync_worktree(worktree)?;
+    let interval_secs = options.interval_secs.max(1);
+    let previous = read_daemon_status(worktree)?;
+    let mut status = SyncDaemonStatus {
+        branch,
+        worktree_path: repo_root.to_string(),
+        pid: std::process::id(),
+        updated_at: now_iso(),
+        last_sync_at: previous.and_then(|status| status.last_sync_at),
+        ahead: 0,
+        behind: 0,
+        consecutive_failures: 0,
+        next_attempt_secs: interval_secs,
+        last_error: None,
+    };
+
+    loop {
+        match push_sync_branch(repo_root) {
+            Ok(_) => {
+                status.last_sync_at = Some(now_iso());
+                status.consecutive_failures = 0;
+                status.last_error = None;
+            }
+            Err(error) => {
+                status.consecutive_failures = status.consecutive_failures.saturating_add(1);
+                status.last_error = Some(format!("{}: {}", error.code, error.message));
+            }
+        }
+        // Counts come from the last fetch, so they stay meaningful offline.
+        if let Ok(Some(remote)) = git::current_upstream_remote(worktree)
+            && let Ok(Some((ahead, behind))) = git::ahead_behind(worktree, &remote, &status.branch)
+        {
+            status.ahead = ahead;
+            status.behind = behind;
+        }
+        status.updated_at = now_iso();
+        status.next_attempt_secs = backoff_secs(interval_secs, status.consecutive_failures);
+        write_daemon_status(worktree, &status)?;
+        on_cycle(&status);
+
+        if options.once {
+            return Ok(status);
+        }
+        wait_for_next_cycle(worktree, &status);
+    }
+}
+
+/// Sleep until the next attempt is due, waking early when the event log
+/// changes unless the last attempt failed (failures always wait out the backoff).
+fn wait_for_next_cycle(worktree: &Path, status: &SyncDaemonStatus) {
+    let events_file = get_paths(worktree).events_file;
+    let baseline = event_log_fingerprint(&events_file);
+    let deadline = Instant::now() + Duration::from_secs(status.next_attempt_secs);
+    while Instant::now() < deadline {
+        thread::sleep(POLL_INTERVAL);
+        if status.consecutive_failures == 0 && event_log_fingerprint(&events_file) != baseline {
+            return;
+        }
+    }
+}
+
+fn event_log_fingerprint(path: &Path) -> Option<(u64, SystemTime)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+fn now_iso() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_per_failure_and_caps() {
+        assert_eq!(backoff_secs(30, 0), 30);
+        assert_eq!(backoff_secs(30, 1), 60);
+        assert_eq!(backoff_secs(30, 2), 120);
+        assert_eq!(backoff_secs(30, 4), MAX_BACKOFF_SECS);
+        assert_eq!(backoff_secs(30, 200), MAX_BACKOFF_SECS);
+        assert_eq!(backoff_secs(600, 3), 600);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::app::hooks::{run_advisory_hooks, run_blocking_hooks};
+use crate::app::permissions::require_role;
+use crate::app::service_lifecycle::{complete_parents, wake_snoozed_tasks, with_due_wakes};
+use crate::app::service_types::ServiceContext;
+use crate::app::storage::{
+    LoadedState, append_events, load_projected_state, load_projected_state_with_events,
+    persist_projection, with_write_lock,
+};
+use crate::domain::projector::apply_events;
+use crate::domain::state::create_empty_state;
+use crate::errors::TsqError;
+use crate::store::blobs;
+use crate::store::events::read_events;
+use crate::types::{ActorRole, EventRecord, State};
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// In-memory staging area shared by service calls that run inside one
+/// transaction. Mutations project onto the staged state and buffer their
+/// events instead of touching the log; the owner decides what to persist.
+#[derive(Debug, Default)]
+pub struct StagedTransaction {
+    state: Option<State>,
+    event_count: usize,
+    events: Vec<EventRecord>,
+}
+
+pub type StagedHandle = Arc<Mutex<StagedTransaction>>;
+
+thread_local! {
+    static PENDING_ADVISORY_HOOKS: RefCell<Vec<EventRecord>> = const { RefCell::new(Vec::new()) };
+}
+
+impl StagedTransaction {
+    pub fn handle() -> StagedHandle {
+        Arc::new(Mutex::new(StagedTransaction::default()))
+    }
+
+    pub fn events(&self) -> &[EventRecord] {
+        &self.events
+    }
+
+    pub fn state(&self) -> Option<&State> {
+        self.state.as_ref()
+    }
+
+    pub fn event_count(&self) -> usize {
+        self.event_count
+    }
+}
+
+/// Runs `f` under the repo write lock, unless the context is staged, in which
+/// case the transaction owner already holds (or does not need) the lock.
+/// Advisory hooks queued while the lock was held run once it is released.
+/// Every mutation passes through here, so it is where writers are enforced
+/// and where derived transitions are recorded: due snoozes wake before `f`,
+/// and finished parents complete after it.
+pub fn with_service_lock<T, F>(ctx: &ServiceContext, f: F) -> Result<T, TsqError>
+where
+    F: FnOnce() -> Result<T, TsqError>,
+{
+    require_role(ctx, ActorRole::Writer, "writing task data")?;
+    if ctx.staged.is_some() {
+        return f();
+    }
+    let result = with_write_lock(&ctx.repo_root, || {
+        wake_snoozed_tasks(ctx)?;
+        let value = f()?;
+        complete_parents(ctx)?;
+        Ok(value)
+    });
+    let committed = PENDING_ADVISORY_HOOKS.with(|pending| pending.take());
+    if result.is_ok() {
+        run_advisory_hooks(&ctx.repo_root, &committed);
+    }
+    result
+}
+
+/// Defers advisory hooks for committed `events` until the write lock is
+/// released, so hook scripts may call back into `tsq`.
+pub fn queue_advisory_hooks(events: &[EventRecord]) {
+    PENDING_ADVISORY_HOOKS.with(|pending| pending.borrow_mut().extend_from_slice(events));
+}
+
+/// Loads the projected state, preferring the staged state when present.
+pub fn load_service_state(ctx: &ServiceContext) -> Result<LoadedState, TsqError> {
+    let Some(handle) = ctx.staged.as_ref() else {
+        return load_projected_state(&ctx.repo_root);
+    };
+    let mut staged = lock_staged(handle);
+    if staged.state.is_none() {
+        let loaded = load_projected_state(&ctx.repo_root)?;
+        staged.event_count = loaded.event_count;
+        staged.state = Some(loaded.state);
+    }
+    Ok(LoadedState {
+        state: staged.state.clone().unwrap_or_else(create_empty_state),
+        all_events: Vec::new(),
+        event_count: staged.event_count,
+        warning: None,
+        snapshot: None,
+    })
+}
+
+/// Loads state for read-only queries: the projection cache normally, or a
+/// replay of the events at or before `ctx.as_of` for time-travel reads.
+/// Queries never write; due snoozes only appear woken until the next write.
+pub fn load_query_state(ctx: &ServiceContext) -> Result<LoadedState, TsqError> {
+    match ctx.as_of.as_deref() {
+        Some(as_of) => replay_until(ctx, as_of),
+        None => with_due_wakes(ctx, load_projected_state(&ctx.repo_root)?),
+    }
+}
+
+/// Like `load_query_state`, but always carries the full event list.
+pub fn load_query_state_with_events(ctx: &ServiceContext) -> Result<LoadedState, TsqError> {
+    match ctx.as_of.as_deref() {
+        Some(as_of) => replay_until(ctx, as_of),
+        None => with_due_wakes(ctx, load_projected_state_with_events(&ctx.repo_root)?),
+    }
+}
+
+fn replay_until(ctx: &ServiceContext, as_of: &str) -> Result<LoadedState, TsqError> {
+    let read = read_events(&ctx.repo_root)?;
+    let events = read
+        .events
+        .into_iter()
+        .filter(|event| event.ts.as_str() <= as_of)
+        .collect::<Vec<_>>();
+    let state = apply_events(&create_empty_state(), &events)?;
+    Ok(LoadedState {
+        state,
+        event_count: events.len(),
+        all_events: events,
+        warning: read.warning,
+        snapshot: None,
+    })
+}
+
+/// Appends `events` and persists `next_state` (gated by blocking hooks), or
+/// records both in the staged transaction when the context is staged.
+pub fn commit_service_events(
+    ctx: &ServiceContext,
+    events: &[EventRecord],
+    next_state: &mut State,
+    event_count: usize,
+) -> Result<(), TsqError> {
+    blobs::check_payload_limits(&blobs::payload_limits(&ctx.repo_root)?, events)?;
+    let Some(handle) = ctx.staged.as_ref() else {
+        run_blocking_hooks(&ctx.repo_root, events)?;
+        append_events(&ctx.repo_root, events)?;
+        persist_projection(&ctx.repo_root, next_state, event_count, None)?;
+        queue_advisory_hooks(events);
+        return Ok(());
+    };
+    let mut staged = lock_staged(handle);
+    next_state.applied_events = event_count;
+    staged.events.extend_from_slice(events);
+    staged.event_count = event_count;
+    staged.state = Some(next_state.clone());
+    Ok(())
+}
+
+pub fn lock_staged(handle: &StagedHandle) -> MutexGuard<'_, StagedTransaction> {
+    handle
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
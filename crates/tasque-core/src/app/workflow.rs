@@ -0,0 +1,121 @@
+use crate::app::service_types::ServiceContext;
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::types::{Config, PlanningState, State, TaskStatus};
+use std::collections::BTreeSet;
+
+/// Checks a planning-state change against `planning_workflow`. `from` is
+/// `None` for new tasks, which may start in any configured state.
+pub fn ensure_planning_transition(
+    ctx: &ServiceContext,
+    from: Option<&PlanningState>,
+    to: &PlanningState,
+) -> Result<(), TsqError> {
+    let config = read_config(&ctx.repo_root)?;
+    let states = config.planning_states();
+    if !states.contains(to) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!(
+                "unknown planning state {}; configured states are {}",
+                to.as_str(),
+                join_states(states.iter().map(PlanningState::as_str))
+            ),
+            1,
+        )
+        .with_details(serde_json::json!({ "planning_state": to, "states": states })));
+    }
+    let (Some(workflow), Some(from)) = (config.planning_workflow.as_ref(), from) else {
+        return Ok(());
+    };
+    if workflow.allows(from, to) {
+        return Ok(());
+    }
+    let allowed = workflow
+        .transitions
+        .get(from.as_str())
+        .cloned()
+        .unwrap_or_default();
+    Err(TsqError::new(
+        "INVALID_TRANSITION",
+        format!(
+            "cannot move planning state from {} to {}",
+            from.as_str(),
+            to.as_str()
+        ),
+        1,
+    )
+    .with_details(serde_json::json!({
+        "field": "planning_state",
+        "from": from,
+        "to": to,
+        "allowed": allowed,
+    })))
+}
+
+/// Checks every status change between `before` and `after` for the touched
+/// tasks: the new status must be configured and, for existing tasks under a
+/// `status_workflow`, reachable from the old one.
+pub fn ensure_status_transitions(
+    config: &Config,
+    before: &State,
+    touched: &BTreeSet<&str>,
+    after: &State,
+) -> Result<(), TsqError> {
+    let statuses = config.statuses();
+    for id in touched {
+        let Some(task) = after.tasks.get(*id) else {
+            continue;
+        };
+        let from = before.tasks.get(*id).map(|previous| &previous.status);
+        if from == Some(&task.status) {
+            continue;
+        }
+        let to = &task.status;
+        if !statuses.contains(to) {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "unknown status {}; configured statuses are {}",
+                    to.as_str(),
+                    join_states(statuses.iter().map(TaskStatus::as_str))
+                ),
+                1,
+            )
+            .with_details(serde_json::json!({ "status": to, "statuses": statuses })));
+        }
+        let (Some(workflow), Some(from)) = (config.status_workflow.as_ref(), from) else {
+            continue;
+        };
+        if workflow.allows(from, to) {
+            continue;
+        }
+        let allowed = workflow
+            .transitions
+            .get(from.as_str())
+            .cloned()
+            .unwrap_or_default();
+        return Err(TsqError::new(
+            "INVALID_TRANSITION",
+            format!(
+                "task {} cannot move from {} to {}",
+                id,
+                from.as_str(),
+                to.as_str()
+            ),
+            1,
+        )
+        .with_details(serde_json::json!({
+            "task_id": id,
+            "field": "status",
+            "from": from,
+            "to": to,
+            "allowed": allowed,
+        })));
+    }
+    Ok(())
+}
+
+fn join_states<'a>(names: impl IntoIterator<Item = &'a str>) -> String {
+    names.into_iter().collect::<Vec<_>>().join("|")
+}
@@ -0,0 +1,113 @@
+use crate::errors::TsqError;
+use crate::types::{Task, TaskKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Task fields that `--count-by` and a query's `| count by <field>` group on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CountField {
+    Status,
+    Label,
+    Kind,
+    Assignee,
+    Priority,
+    Planning,
+}
+
+impl CountField {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CountField::Status => "status",
+            CountField::Label => "label",
+            CountField::Kind => "kind",
+            CountField::Assignee => "assignee",
+            CountField::Priority => "priority",
+            CountField::Planning => "planning",
+        }
+    }
+}
+
+/// One bucket of a count; `key` is `None` for tasks without the field (no
+/// labels, unassigned, no planning state).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountGroup {
+    pub key: Option<String>,
+    pub count: usize,
+}
+
+/// `total` counts tasks once, so with `label` the groups can sum past it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountResult {
+    pub count_by: CountField,
+    pub total: usize,
+    pub groups: Vec<CountGroup>,
+}
+
+pub fn parse_count_field(raw: &str) -> Result<CountField, TsqError> {
+    match raw.trim().to_lowercase().as_str() {
+        "status" => Ok(CountField::Status),
+        "label" => Ok(CountField::Label),
+        "kind" => Ok(CountField::Kind),
+        "assignee" => Ok(CountField::Assignee),
+        "priority" => Ok(CountField::Priority),
+        "planning" => Ok(CountField::Planning),
+        _ => Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "count by must be status|label|kind|assignee|priority|planning",
+            1,
+        )),
+    }
+}
+
+/// Groups tasks by `field`, largest group first and then by key, with the
+/// missing-value group last.
+pub fn count_by<'a>(tasks: impl IntoIterator<Item = &'a Task>, field: CountField) -> CountResult {
+    let mut counts: BTreeMap<Option<String>, usize> = BTreeMap::new();
+    let mut total = 0;
+    for task in tasks {
+        total += 1;
+        for key in task_keys(task, field) {
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+    let mut groups: Vec<CountGroup> = counts
+        .into_iter()
+        .map(|(key, count)| CountGroup { key, count })
+        .collect();
+    groups.sort_by(|a, b| {
+        a.key
+            .is_none()
+            .cmp(&b.key.is_none())
+            .then_with(|| b.count.cmp(&a.count))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    CountResult {
+        count_by: field,
+        total,
+        groups,
+    }
+}
+
+fn task_keys(task: &Task, field: CountField) -> Vec<Option<String>> {
+    match field {
+        CountField::Status => vec![Some(task.status.as_str().to_string())],
+        CountField::Label if task.labels.is_empty() => vec![None],
+        CountField::Label => task.labels.iter().cloned().map(Some).collect(),
+        CountField::Kind => vec![Some(
+            match task.kind {
+                TaskKind::Task => "task",
+                TaskKind::Feature => "feature",
+                TaskKind::Epic => "epic",
+            }
+            .to_string(),
+        )],
+        CountField::Assignee => vec![task.assignee.clone()],
+        CountField::Priority => vec![Some(task.priority.to_string())],
+        CountField::Planning => vec![
+            task.planning_state
+                .as_ref()
+                .map(|state| state.as_str().to_string()),
+        ],
+    }
+}
@@ -0,0 +1,83 @@
+use crate::types::Task;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+static HEADING_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(#{1,6})[ \t]+(.+?)[ \t#]*$").expect("valid heading pattern"));
+static CHECKBOX_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*[-*+][ \t]+\[([ xX])\][ \t]+(.+?)\s*$").expect("valid checkbox pattern")
+});
+
+/// One checkbox item from the "Acceptance criteria" section of a spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptanceCriterion {
+    /// 1-based position within the section.
+    pub index: usize,
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Extracts checkbox items under the first "Acceptance criteria" heading,
+/// stopping at the next heading of the same or a higher level.
+pub fn parse_acceptance_criteria(markdown: &str) -> Vec<AcceptanceCriterion> {
+    let mut criteria = Vec::new();
+    let mut section_level: Option<usize> = None;
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(capture) = HEADING_PATTERN.captures(line) {
+            let level = capture[1].len();
+            let title = capture[2].trim().trim_end_matches(':').trim();
+            match section_level {
+                Some(current) if level <= current => break,
+                Some(_) => {}
+                None if title.eq_ignore_ascii_case("acceptance criteria") => {
+                    section_level = Some(level);
+                }
+                None => {}
+            }
+            continue;
+        }
+        if section_level.is_none() {
+            continue;
+        }
+        if let Some(capture) = CHECKBOX_PATTERN.captures(line) {
+            criteria.push(AcceptanceCriterion {
+                index: criteria.len() + 1,
+                text: capture[2].to_string(),
+                checked: capture[1] != *" ",
+            });
+        }
+    }
+
+    criteria
+}
+
+/// Key used to match a criterion across spec edits in `Task::criteria_marks`.
+pub fn criterion_key(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Applies completion recorded through events on top of the spec checkboxes.
+pub fn apply_criteria_marks(
+    task: &Task,
+    mut criteria: Vec<AcceptanceCriterion>,
+) -> Vec<AcceptanceCriterion> {
+    for criterion in &mut criteria {
+        if let Some(checked) = task.criteria_marks.get(&criterion_key(&criterion.text)) {
+            criterion.checked = *checked;
+        }
+    }
+    criteria
+}
@@ -0,0 +1,139 @@
+use crate::domain::deps::normalize_dependency_edges;
+use crate::types::{DependencyType, RelationType, State};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Upper bound on partial paths expanded per search so dense graphs stay fast.
+const MAX_EXPANSIONS: usize = 10_000;
+
+/// One edge on a path: `from` waits on `to` (dependency) or links to it (relation).
+/// Example: PathHop { from: "tsq-a", to: "tsq-b", via: "blocks", dependency: true }.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathHop {
+    pub from: String,
+    pub to: String,
+    pub via: String,
+    pub dependency: bool,
+}
+
+/// A chain of hops connecting two tasks.
+/// Example: find_dep_paths returns DepPath values shortest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepPath {
+    pub hops: Vec<PathHop>,
+    /// True when every hop is a gating dependency (anything but `informs`),
+    /// so `to` transitively gates `from`.
+    pub gating: bool,
+}
+
+/// Find up to `limit` simple paths from `from_id` to `to_id`, shortest first.
+/// Dependency edges are followed from a task to its blockers; relation links
+/// are followed in the direction they were recorded.
+/// Example: find_dep_paths(state, "tsq-a", "tsq-b", 10, 5).
+pub fn find_dep_paths(
+    state: &State,
+    from_id: &str,
+    to_id: &str,
+    max_depth: usize,
+    limit: usize,
+) -> Vec<DepPath> {
+    let mut paths = Vec::new();
+    let mut queue: VecDeque<Vec<PathHop>> = VecDeque::new();
+    queue.push_back(Vec::new());
+    let mut expansions = 0;
+
+    while let Some(hops) = queue.pop_front() {
+        if paths.len() >= limit || expansions >= MAX_EXPANSIONS {
+            break;
+        }
+        expansions += 1;
+        let current = hops.last().map_or(from_id, |hop| hop.to.as_str());
+        for hop in outgoing_hops(state, current) {
+            let revisits = hop.to == from_id || hops.iter().any(|prev| prev.to == hop.to);
+            if revisits {
+                continue;
+            }
+            let mut next = hops.clone();
+            let reached = hop.to == to_id;
+            next.push(hop);
+            if reached {
+                paths.push(DepPath {
+                    gating: next
+                        .iter()
+                        .all(|hop| hop.dependency && hop.via != "informs"),
+                    hops: next,
+                });
+                if paths.len() >= limit {
+                    break;
+                }
+            } else if next.len() < max_depth {
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+fn outgoing_hops(state: &State, id: &str) -> Vec<PathHop> {
+    let mut hops: Vec<PathHop> = normalize_dependency_edges(state.deps.get(id))
+        .into_iter()
+        .filter(|edge| state.tasks.contains_key(&edge.blocker))
+        .map(|edge| PathHop {
+            from: id.to_string(),
+            to: edge.blocker,
+            via: dependency_type_name(edge.dep_type).to_string(),
+            dependency: true,
+        })
+        .collect();
+    let mut links: Vec<PathHop> = state
+        .links
+        .get(id)
+        .into_iter()
+        .flat_map(|by_type| by_type.iter())
+        .flat_map(|(rel_type, targets)| {
+            targets.iter().map(move |target| PathHop {
+                from: id.to_string(),
+                to: target.clone(),
+                via: relation_type_name(*rel_type).to_string(),
+                dependency: false,
+            })
+        })
+        .chain(
+            state
+                .custom_links
+                .get(id)
+                .into_iter()
+                .flat_map(|by_type| by_type.iter())
+                .flat_map(|(rel_type, targets)| {
+                    targets.iter().map(move |target| PathHop {
+                        from: id.to_string(),
+                        to: target.clone(),
+                        via: rel_type.clone(),
+                        dependency: false,
+                    })
+                }),
+        )
+        .filter(|hop| state.tasks.contains_key(&hop.to))
+        .collect();
+    links.sort_by(|a, b| a.via.cmp(&b.via).then_with(|| a.to.cmp(&b.to)));
+    hops.extend(links);
+    hops
+}
+
+fn dependency_type_name(dep_type: DependencyType) -> &'static str {
+    match dep_type {
+        DependencyType::Blocks => "blocks",
+        DependencyType::StartsAfter => "starts_after",
+        DependencyType::FinishToFinish => "finish_to_finish",
+        DependencyType::Informs => "informs",
+    }
+}
+
+fn relation_type_name(rel_type: RelationType) -> &'static str {
+    match rel_type {
+        RelationType::RelatesTo => "relates_to",
+        RelationType::RepliesTo => "replies_to",
+        RelationType::Duplicates => "duplicates",
+        RelationType::Supersedes => "supersedes",
+    }
+}
@@ -0,0 +1,336 @@
+use crate::domain::deps::normalize_dependency_edges;
+use crate::errors::TsqError;
+use crate::types::{DependencyEdge, DependencyType, State, Task, TaskKind, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Direction to walk the dependency graph from the root task.
+/// Example: DepDirection::Up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepDirection {
+    Up,
+    Down,
+    Both,
+}
+
+/// A node in a dependency tree with task data and traversal metadata.
+/// Example: build_dep_tree returns a DepTreeNode for the requested root task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepTreeNode {
+    pub id: String,
+    pub task: Task,
+    pub direction: DepDirection,
+    pub depth: usize,
+    pub dep_type: Option<DependencyType>,
+    /// Already expanded elsewhere in this direction; children are omitted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub seen: bool,
+    pub children: Vec<DepTreeNode>,
+}
+
+/// One task in a flattened dependency graph.
+/// Example: flatten_dep_tree lists each task in the tree once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepGraphNode {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub kind: TaskKind,
+    pub depth: usize,
+}
+
+/// One dependency in a flattened graph: `child` waits on `blocker`.
+/// Example: DepGraphEdge { child: "tsq-2", blocker: "tsq-1", .. }.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepGraphEdge {
+    pub child: String,
+    pub blocker: String,
+    pub dep_type: DependencyType,
+}
+
+/// A dependency tree as an adjacency list, so shared blockers appear once.
+/// Example: flatten_dep_tree(&build_dep_tree(state, "tsq-1", DepDirection::Both, 10, None)?).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepGraph {
+    pub root: String,
+    pub direction: DepDirection,
+    pub nodes: Vec<DepGraphNode>,
+    pub edges: Vec<DepGraphEdge>,
+}
+
+/// A dependent edge used when building a reverse index.
+/// Example: build_dependents_by_blocker collects Dependents for each blocker.
+#[derive(Debug, Clone)]
+pub struct DependentEdge {
+    pub id: String,
+    pub dep_type: DependencyType,
+}
+
+/// What finishing a blocker means for one of its unfinished dependents.
+/// Example: canceling a blocker leaves its dependents Orphaned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependentEffect {
+    /// Every `blocks`/`starts_after` blocker is now closed or canceled.
+    Unblocked,
+    /// The blocker was canceled, so the work the dependent waited on never landed.
+    Orphaned,
+}
+
+/// A dependent affected by closing or canceling one of its blockers.
+/// Example: dependent_impacts reports one per affected dependent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependentImpact {
+    pub task_id: String,
+    pub blocker: String,
+    pub dep_type: DependencyType,
+    pub effect: DependentEffect,
+}
+
+/// Build a dependency tree starting from root_id. A task reached a second time
+/// is listed again with `seen` set and no children, so shared blockers and
+/// cycles stay finite. With `statuses`, other tasks are pruned along with
+/// everything beyond them; the root is always kept.
+/// Example: build_dep_tree(state, "tsq-123", DepDirection::Both, 10, None).
+pub fn build_dep_tree(
+    state: &State,
+    root_id: &str,
+    direction: DepDirection,
+    max_depth: usize,
+    statuses: Option<&[TaskStatus]>,
+) -> Result<DepTreeNode, TsqError> {
+    let root_task = match state.tasks.get(root_id) {
+        Some(task) => task.clone(),
+        None => {
+            return Err(TsqError::new(
+                "NOT_FOUND",
+                format!("task not found: {}", root_id),
+                1,
+            ));
+        }
+    };
+
+    let walk = DepWalk {
+        state,
+        dependents_by_blocker: build_dependents_by_blocker(&state.deps),
+        max_depth,
+        statuses,
+    };
+    let mut children = Vec::new();
+    if matches!(direction, DepDirection::Up | DepDirection::Both) {
+        let mut visited = HashSet::from([root_id.to_string()]);
+        children.extend(walk.walk(root_id, 1, &mut visited, DepDirection::Up));
+    }
+    if matches!(direction, DepDirection::Down | DepDirection::Both) {
+        let mut visited = HashSet::from([root_id.to_string()]);
+        children.extend(walk.walk(root_id, 1, &mut visited, DepDirection::Down));
+    }
+    Ok(DepTreeNode {
+        id: root_id.to_string(),
+        task: root_task,
+        direction,
+        depth: 0,
+        dep_type: None,
+        seen: false,
+        children,
+    })
+}
+
+struct DepWalk<'a> {
+    state: &'a State,
+    dependents_by_blocker: HashMap<String, Vec<DependentEdge>>,
+    max_depth: usize,
+    statuses: Option<&'a [TaskStatus]>,
+}
+
+impl DepWalk<'_> {
+    fn walk(
+        &self,
+        node_id: &str,
+        depth: usize,
+        visited: &mut HashSet<String>,
+        direction: DepDirection,
+    ) -> Vec<DepTreeNode> {
+        if depth > self.max_depth {
+            return Vec::new();
+        }
+        let edges: Vec<(String, DependencyType)> = match direction {
+            DepDirection::Up => normalize_dependency_edges(self.state.deps.get(node_id))
+                .into_iter()
+                .map(|edge| (edge.blocker, edge.dep_type))
+                .collect(),
+            _ => self
+                .dependents_by_blocker
+                .get(node_id)
+                .into_iter()
+                .flatten()
+                .map(|edge| (edge.id.clone(), edge.dep_type))
+                .collect(),
+        };
+        let mut nodes = Vec::new();
+        for (id, dep_type) in edges {
+            let Some(task) = self.state.tasks.get(&id) else {
+                continue;
+            };
+            if self
+                .statuses
+                .is_some_and(|statuses| !statuses.contains(&task.status))
+            {
+                continue;
+            }
+            let seen = !visited.insert(id.clone());
+            let children = if seen {
+                Vec::new()
+            } else {
+                self.walk(&id, depth + 1, visited, direction)
+            };
+            nodes.push(DepTreeNode {
+                id,
+                task: task.clone(),
+                direction,
+                depth,
+                dep_type: Some(dep_type),
+                seen,
+                children,
+            });
+        }
+        nodes
+    }
+}
+
+/// Flatten a dependency tree into unique nodes (at their shallowest depth,
+/// in first-visit order) and unique edges, including edges into `seen` nodes.
+/// Example: flatten_dep_tree(&root).
+pub fn flatten_dep_tree(root: &DepTreeNode) -> DepGraph {
+    let mut graph = DepGraph {
+        root: root.id.clone(),
+        direction: root.direction,
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let mut node_index: HashMap<String, usize> = HashMap::new();
+    let mut edge_keys: HashSet<(String, String, DependencyType)> = HashSet::new();
+    let mut stack = vec![(root, None::<&str>)];
+    while let Some((node, parent)) = stack.pop() {
+        match node_index.get(&node.id) {
+            Some(index) => {
+                let existing = &mut graph.nodes[*index];
+                existing.depth = existing.depth.min(node.depth);
+            }
+            None => {
+                node_index.insert(node.id.clone(), graph.nodes.len());
+                graph.nodes.push(DepGraphNode {
+                    id: node.id.clone(),
+                    title: node.task.title.clone(),
+                    status: node.task.status.clone(),
+                    kind: node.task.kind,
+                    depth: node.depth,
+                });
+            }
+        }
+        if let (Some(parent), Some(dep_type)) = (parent, node.dep_type) {
+            let (child, blocker) = match node.direction {
+                DepDirection::Up => (parent, node.id.as_str()),
+                _ => (node.id.as_str(), parent),
+            };
+            if edge_keys.insert((child.to_string(), blocker.to_string(), dep_type)) {
+                graph.edges.push(DepGraphEdge {
+                    child: child.to_string(),
+                    blocker: blocker.to_string(),
+                    dep_type,
+                });
+            }
+        }
+        for child in node.children.iter().rev() {
+            stack.push((child, Some(node.id.as_str())));
+        }
+    }
+    graph
+}
+
+/// Build a reverse index mapping blockers to their dependents.
+/// Example: build_dependents_by_blocker(&state.deps).
+pub fn build_dependents_by_blocker(
+    deps: &HashMap<String, Vec<DependencyEdge>>,
+) -> HashMap<String, Vec<DependentEdge>> {
+    let mut map: HashMap<String, Vec<DependentEdge>> = HashMap::new();
+    for (child, blockers) in deps {
+        for edge in normalize_dependency_edges(Some(blockers)) {
+            let dependent = DependentEdge {
+                id: child.clone(),
+                dep_type: edge.dep_type,
+            };
+            map.entry(edge.blocker)
+                .and_modify(|list| list.push(dependent.clone()))
+                .or_insert_with(|| vec![dependent]);
+        }
+    }
+    map
+}
+
+/// Unfinished `blocks`/`starts_after` dependents of the `finished` tasks in
+/// `after`. Dependents of a canceled blocker are orphaned; dependents left
+/// with no open gating blocker are unblocked; the rest are not reported.
+/// Example: dependent_impacts(&next_state, &["tsq-1".to_string()]).
+pub fn dependent_impacts(after: &State, finished: &[String]) -> Vec<DependentImpact> {
+    let dependents_by_blocker = build_dependents_by_blocker(&after.deps);
+    let mut reported: HashSet<String> = HashSet::new();
+    let mut impacts = Vec::new();
+    for blocker_id in finished {
+        let Some(blocker) = after.tasks.get(blocker_id) else {
+            continue;
+        };
+        let mut dependents: Vec<&DependentEdge> = dependents_by_blocker
+            .get(blocker_id)
+            .map(|edges| {
+                edges
+                    .iter()
+                    .filter(|edge| is_gating(edge.dep_type))
+                    .collect()
+            })
+            .unwrap_or_default();
+        dependents.sort_by(|a, b| a.id.cmp(&b.id));
+        for dependent in dependents {
+            let Some(task) = after.tasks.get(&dependent.id) else {
+                continue;
+            };
+            if is_finished(task) || reported.contains(&task.id) {
+                continue;
+            }
+            let effect = if blocker.status == TaskStatus::Canceled {
+                DependentEffect::Orphaned
+            } else if gating_blockers_finished(after, &task.id) {
+                DependentEffect::Unblocked
+            } else {
+                continue;
+            };
+            reported.insert(task.id.clone());
+            impacts.push(DependentImpact {
+                task_id: task.id.clone(),
+                blocker: blocker_id.clone(),
+                dep_type: dependent.dep_type,
+                effect,
+            });
+        }
+    }
+    impacts
+}
+
+fn is_gating(dep_type: DependencyType) -> bool {
+    matches!(
+        dep_type,
+        DependencyType::Blocks | DependencyType::StartsAfter
+    )
+}
+
+fn is_finished(task: &Task) -> bool {
+    matches!(task.status, TaskStatus::Closed | TaskStatus::Canceled)
+}
+
+fn gating_blockers_finished(state: &State, task_id: &str) -> bool {
+    normalize_dependency_edges(state.deps.get(task_id))
+        .iter()
+        .filter(|edge| is_gating(edge.dep_type))
+        .all(|edge| state.tasks.get(&edge.blocker).is_some_and(is_finished))
+}
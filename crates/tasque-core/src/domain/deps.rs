@@ -5,10 +5,21 @@ pub fn normalize_dependency_type(value: &str) -> Option<DependencyType> {
     match value {
         "blocks" => Some(DependencyType::Blocks),
         "starts_after" => Some(DependencyType::StartsAfter),
+        "finish_to_finish" => Some(DependencyType::FinishToFinish),
+        "informs" => Some(DependencyType::Informs),
         _ => None,
     }
 }
 
+/// Edges whose cycles would deadlock: `blocks` gates starting and
+/// `finish_to_finish` gates closing, so a loop mixing them can never finish.
+pub fn is_cycle_checked(dep_type: DependencyType) -> bool {
+    matches!(
+        dep_type,
+        DependencyType::Blocks | DependencyType::FinishToFinish
+    )
+}
+
 pub fn normalize_dependency_edges(edges: Option<&Vec<DependencyEdge>>) -> Vec<DependencyEdge> {
     let mut seen = HashSet::new();
     let mut normalized = Vec::new();
@@ -33,6 +44,8 @@ pub fn edge_key(blocker: &str, dep_type: DependencyType) -> String {
     let dep_value = match dep_type {
         DependencyType::Blocks => "blocks",
         DependencyType::StartsAfter => "starts_after",
+        DependencyType::FinishToFinish => "finish_to_finish",
+        DependencyType::Informs => "informs",
     };
     format!("{blocker}\u{0000}{dep_value}")
 }
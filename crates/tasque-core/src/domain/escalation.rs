@@ -0,0 +1,41 @@
+use crate::types::{EscalationConfig, Priority, Task, TaskStatus};
+use chrono::{DateTime, Utc};
+
+/// Statuses that age; deferred and terminal tasks never escalate.
+const ESCALATING_STATUSES: [TaskStatus; 3] = [
+    TaskStatus::Open,
+    TaskStatus::InProgress,
+    TaskStatus::Blocked,
+];
+
+/// Whole days since the task was created, or `None` for unparseable timestamps.
+pub fn task_age_days(task: &Task, now: DateTime<Utc>) -> Option<i64> {
+    let created = DateTime::parse_from_rfc3339(&task.created_at).ok()?;
+    Some((now - created.with_timezone(&Utc)).num_days())
+}
+
+/// Effective priority under the policy, or `None` when the task is not escalated.
+pub fn escalated_priority(
+    task: &Task,
+    policy: &EscalationConfig,
+    now: DateTime<Utc>,
+) -> Option<Priority> {
+    if !ESCALATING_STATUSES.contains(&task.status) {
+        return None;
+    }
+    let overdue = task_age_days(task, now)? - i64::from(policy.after_days);
+    if overdue < 0 {
+        return None;
+    }
+    let steps = 1 + overdue / i64::from(policy.every_days.max(1));
+    let boost = steps.min(i64::from(policy.max_boost)) as Priority;
+    let effective = task.priority.saturating_sub(boost);
+    (effective < task.priority).then_some(effective)
+}
+
+/// Fills in `effective_priority` on every task the policy escalates.
+pub fn apply_escalation(tasks: &mut [Task], policy: &EscalationConfig, now: DateTime<Utc>) {
+    for task in tasks {
+        task.effective_priority = escalated_priority(task, policy, now);
+    }
+}
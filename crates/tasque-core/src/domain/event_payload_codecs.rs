@@ -9,6 +9,10 @@ pub fn event_type_from_str(raw: &str) -> Option<EventType> {
         "task.noted" => Some(EventType::TaskNoted),
         "task.spec_attached" => Some(EventType::TaskSpecAttached),
         "task.superseded" => Some(EventType::TaskSuperseded),
+        "task.criterion_set" => Some(EventType::TaskCriterionSet),
+        "task.checklist_added" => Some(EventType::TaskChecklistAdded),
+        "task.checklist_set" => Some(EventType::TaskChecklistSet),
+        "task.ranked" => Some(EventType::TaskRanked),
         "dep.added" => Some(EventType::DepAdded),
         "dep.removed" => Some(EventType::DepRemoved),
         "link.added" => Some(EventType::LinkAdded),
@@ -26,6 +30,10 @@ pub fn event_type_as_str(event_type: EventType) -> &'static str {
         EventType::TaskNoted => "task.noted",
         EventType::TaskSpecAttached => "task.spec_attached",
         EventType::TaskSuperseded => "task.superseded",
+        EventType::TaskCriterionSet => "task.criterion_set",
+        EventType::TaskChecklistAdded => "task.checklist_added",
+        EventType::TaskChecklistSet => "task.checklist_set",
+        EventType::TaskRanked => "task.ranked",
         EventType::DepAdded => "dep.added",
         EventType::DepRemoved => "dep.removed",
         EventType::LinkAdded => "link.added",
@@ -42,41 +50,24 @@ pub fn task_kind_from_str(raw: &str) -> Option<TaskKind> {
     }
 }
 
+/// Accepts custom names too; whether a status is configured is checked by
+/// the service, since replay must not depend on config.
 pub fn task_status_from_str(raw: &str) -> Option<TaskStatus> {
-    match raw {
-        "open" => Some(TaskStatus::Open),
-        "in_progress" => Some(TaskStatus::InProgress),
-        "blocked" => Some(TaskStatus::Blocked),
-        "closed" => Some(TaskStatus::Closed),
-        "canceled" => Some(TaskStatus::Canceled),
-        "deferred" => Some(TaskStatus::Deferred),
-        _ => None,
-    }
-}
-
-pub fn task_status_as_str(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Open => "open",
-        TaskStatus::InProgress => "in_progress",
-        TaskStatus::Blocked => "blocked",
-        TaskStatus::Closed => "closed",
-        TaskStatus::Canceled => "canceled",
-        TaskStatus::Deferred => "deferred",
-    }
+    TaskStatus::from_name(raw)
 }
 
+/// Accepts custom names too; whether a state is configured is checked by the
+/// service, since replay must not depend on config.
 pub fn planning_state_from_str(raw: &str) -> Option<PlanningState> {
-    match raw {
-        "needs_planning" => Some(PlanningState::NeedsPlanning),
-        "planned" => Some(PlanningState::Planned),
-        _ => None,
-    }
+    PlanningState::from_name(raw)
 }
 
 pub fn dependency_type_from_str(raw: &str) -> Option<DependencyType> {
     match raw {
         "blocks" => Some(DependencyType::Blocks),
         "starts_after" => Some(DependencyType::StartsAfter),
+        "finish_to_finish" => Some(DependencyType::FinishToFinish),
+        "informs" => Some(DependencyType::Informs),
         _ => None,
     }
 }
@@ -90,3 +81,11 @@ pub fn relation_type_from_str(raw: &str) -> Option<RelationType> {
         _ => None,
     }
 }
+
+/// Config-defined relation names are snake_case and never shadow a builtin.
+pub fn is_custom_relation_name(raw: &str) -> bool {
+    let mut chars = raw.chars();
+    chars.next().is_some_and(|first| first.is_ascii_lowercase())
+        && chars.all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+        && relation_type_from_str(raw).is_none()
+}
@@ -18,6 +18,23 @@ pub fn make_root_id(state: &State) -> Result<String, TsqError> {
     }
 }
 
+static MENTIONED_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\btsq-(?:[1-9][0-9]*|[0-9a-hjkmnp-tv-z]{8})(?:\.[1-9][0-9]*)*\b")
+        .expect("mentioned id regex")
+});
+
+/// Task ids (root or child) mentioned in free text such as a commit message,
+/// in order of first appearance.
+pub fn mentioned_task_ids(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for found in MENTIONED_ID.find_iter(text) {
+        if !ids.iter().any(|id| id == found.as_str()) {
+            ids.push(found.as_str().to_string());
+        }
+    }
+    ids
+}
+
 pub struct RootIdAllocator {
     next: u64,
     reserved_ids: HashSet<String>,
@@ -91,3 +108,18 @@ fn sequential_number(raw: &str) -> Option<u64> {
     let suffix = raw.strip_prefix("tsq-")?;
     suffix.parse::<u64>().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentioned_task_ids_finds_root_child_and_legacy_ids() {
+        assert_eq!(
+            mentioned_task_ids(
+                "Fix tsq-12 (see tsq-3.1, tsq-12, tsq-a1b2c3d4); not tsq-0 or tsq-12x"
+            ),
+            vec!["tsq-12", "tsq-3.1", "tsq-a1b2c3d4"]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+pub mod aggregate;
+pub mod alias;
+pub mod criteria;
+pub mod dep_path;
+pub mod dep_tree;
+pub mod deps;
+pub mod escalation;
+pub mod event_payload_codecs;
+pub mod event_records;
+// Minting event ids needs OS randomness through ulid.
+#[cfg(feature = "fs")]
+pub mod events;
+pub mod ids;
+pub mod labels;
+pub mod projector;
+pub mod query;
+pub mod redact;
+pub mod resolve;
+pub mod rollup;
+pub mod similarity;
+pub mod sort;
+pub mod state;
+// Only the file store validates cached and snapshot state.
+#[cfg(feature = "fs")]
+pub(crate) mod state_invariants;
+pub mod task_index;
+pub mod validate;
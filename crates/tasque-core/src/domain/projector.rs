@@ -0,0 +1,119 @@
+#[path = "projector_deps_links.rs"]
+mod projector_deps_links;
+#[path = "projector_helpers.rs"]
+mod projector_helpers;
+#[path = "projector_tasks.rs"]
+mod projector_tasks;
+
+use crate::domain::state::create_empty_state;
+use crate::domain::task_index::{IndexKeys, index_task, unindex_task};
+use crate::errors::TsqError;
+use crate::types::{EventRecord, EventType, State, TaskStatus};
+use projector_deps_links::{
+    apply_dep_added, apply_dep_removed, apply_link_added, apply_link_removed,
+};
+use projector_helpers::{clone_state, event_id_value, event_type_to_string};
+use projector_tasks::{
+    apply_task_checklist_added, apply_task_checklist_set, apply_task_claimed, apply_task_created,
+    apply_task_criterion_set, apply_task_noted, apply_task_ranked, apply_task_spec_attached,
+    apply_task_status_set, apply_task_superseded, apply_task_updated,
+};
+
+/// Every projector handler only rewrites the task named by `event.task_id`,
+/// so re-indexing that one task keeps `state.indexes` current.
+fn apply_event_mut(state: &mut State, event: &EventRecord) -> Result<(), TsqError> {
+    let before = state.tasks.get(&event.task_id).map(IndexKeys::of);
+    apply_event_to_state(state, event)?;
+    let after = state.tasks.get(&event.task_id).map(IndexKeys::of);
+    if before != after {
+        if let Some(keys) = &before {
+            unindex_task(&mut state.indexes, &event.task_id, keys);
+        }
+        if let Some(keys) = &after {
+            index_task(&mut state.indexes, &event.task_id, keys);
+        }
+    }
+    Ok(())
+}
+
+fn apply_event_to_state(state: &mut State, event: &EventRecord) -> Result<(), TsqError> {
+    #[allow(unreachable_patterns)]
+    match event.event_type {
+        EventType::TaskCreated => apply_task_created(state, event)?,
+        EventType::TaskUpdated => apply_task_updated(state, event)?,
+        EventType::TaskStatusSet => apply_task_status_set(state, event)?,
+        EventType::TaskClaimed => apply_task_claimed(state, event)?,
+        EventType::TaskNoted => apply_task_noted(state, event)?,
+        EventType::TaskSpecAttached => apply_task_spec_attached(state, event)?,
+        EventType::TaskSuperseded => apply_task_superseded(state, event)?,
+        EventType::TaskCriterionSet => apply_task_criterion_set(state, event)?,
+        EventType::TaskChecklistAdded => apply_task_checklist_added(state, event)?,
+        EventType::TaskChecklistSet => apply_task_checklist_set(state, event)?,
+        EventType::TaskRanked => apply_task_ranked(state, event)?,
+        EventType::DepAdded => apply_dep_added(state, event)?,
+        EventType::DepRemoved => apply_dep_removed(state, event)?,
+        EventType::LinkAdded => apply_link_added(state, event)?,
+        EventType::LinkRemoved => apply_link_removed(state, event)?,
+        _ => {
+            return Err(
+                TsqError::new("INVALID_EVENT_TYPE", "Unknown event type", 1).with_details(
+                    serde_json::json!({
+                      "event_id": event_id_value(event),
+                      "type": event_type_to_string(&event.event_type),
+                    }),
+                ),
+            );
+        }
+    }
+    state.applied_events += 1;
+    Ok(())
+}
+
+pub fn apply_event(state: &State, event: &EventRecord) -> Result<State, TsqError> {
+    let mut next = clone_state(state);
+    apply_event_mut(&mut next, event)?;
+    Ok(next)
+}
+
+pub fn apply_events(base: &State, events: &[EventRecord]) -> Result<State, TsqError> {
+    if events.is_empty() {
+        return Ok(base.clone());
+    }
+    let mut state = clone_state(base);
+    for event in events {
+        apply_event_mut(&mut state, event)?;
+    }
+    Ok(state)
+}
+
+/// A task status change made by one logged event.
+pub struct StatusChange<'a> {
+    pub event: &'a EventRecord,
+    pub from: TaskStatus,
+    pub to: TaskStatus,
+}
+
+/// Replays `events` from an empty state and lists every status change in log
+/// order, so callers can audit history against a workflow that replay itself
+/// never consults.
+pub fn status_changes(events: &[EventRecord]) -> Result<Vec<StatusChange<'_>>, TsqError> {
+    let mut state = create_empty_state();
+    let mut changes = Vec::new();
+    for event in events {
+        let from = state
+            .tasks
+            .get(&event.task_id)
+            .map(|task| task.status.clone());
+        apply_event_mut(&mut state, event)?;
+        if let (Some(from), Some(task)) = (from, state.tasks.get(&event.task_id))
+            && from != task.status
+        {
+            changes.push(StatusChange {
+                event,
+                from,
+                to: task.status.clone(),
+            });
+        }
+    }
+    Ok(changes)
+}
@@ -2,7 +2,10 @@ use super::projector_helpers::{
     as_relation_type, as_string, event_id_value, remove_directed_link, require_task,
     upsert_directed_link,
 };
-use crate::domain::deps::{edge_key, normalize_dependency_edges, normalize_dependency_type};
+use crate::domain::deps::{
+    edge_key, is_cycle_checked, normalize_dependency_edges, normalize_dependency_type,
+};
+use crate::domain::event_payload_codecs::is_custom_relation_name;
 use crate::domain::validate::assert_no_dependency_cycle;
 use crate::errors::TsqError;
 use crate::types::{DependencyEdge, DependencyType, EventRecord, RelationType, State};
@@ -37,7 +40,7 @@ pub(crate) fn apply_dep_added(state: &mut State, event: &EventRecord) -> Result<
         .unwrap_or(DEFAULT_DEP_TYPE);
     require_task(state, &event.task_id)?;
     require_task(state, &blocker)?;
-    if dep_type == DependencyType::Blocks {
+    if is_cycle_checked(dep_type) {
         assert_no_dependency_cycle(state, &event.task_id, &blocker)?;
     }
     let deps = normalize_dependency_edges(state.deps.get(&event.task_id));
@@ -92,8 +95,69 @@ fn relation_target(payload: &serde_json::Map<String, Value>) -> Option<String> {
     as_string(payload.get("target"))
 }
 
+/// Config-defined relation types carry their inverse in the payload so replay
+/// never depends on the current config.
+fn custom_relation(payload: &serde_json::Map<String, Value>) -> Option<(String, String)> {
+    let name = as_string(payload.get("type"))?;
+    let inverse = as_string(payload.get("inverse"))?;
+    (is_custom_relation_name(&name) && is_custom_relation_name(&inverse)).then_some((name, inverse))
+}
+
+fn upsert_custom_link(state: &mut State, src: &str, dst: &str, rel_type: &str) {
+    let targets = state
+        .custom_links
+        .entry(src.to_string())
+        .or_default()
+        .entry(rel_type.to_string())
+        .or_default();
+    if !targets.iter().any(|candidate| candidate == dst) {
+        targets.push(dst.to_string());
+    }
+}
+
+fn remove_custom_link(state: &mut State, src: &str, dst: &str, rel_type: &str) {
+    let Some(by_type) = state.custom_links.get_mut(src) else {
+        return;
+    };
+    if let Some(targets) = by_type.get_mut(rel_type) {
+        targets.retain(|candidate| candidate != dst);
+        if targets.is_empty() {
+            by_type.remove(rel_type);
+        }
+    }
+    if by_type.is_empty() {
+        state.custom_links.remove(src);
+    }
+}
+
 pub(crate) fn apply_link_added(state: &mut State, event: &EventRecord) -> Result<(), TsqError> {
     let payload = &event.payload;
+    if let Some((name, inverse)) = custom_relation(payload) {
+        let target = relation_target(payload).unwrap_or_default();
+        if target.is_empty() {
+            return Err(
+                TsqError::new("INVALID_EVENT", "link.added requires target and type", 1)
+                    .with_details(serde_json::json!({
+                      "event_id": event_id_value(event),
+                    })),
+            );
+        }
+        if target == event.task_id {
+            return Err(TsqError::new(
+                "RELATION_SELF_EDGE",
+                "Relation self-edge is not allowed",
+                1,
+            )
+            .with_details(serde_json::json!({
+              "task_id": &event.task_id,
+            })));
+        }
+        require_task(state, &event.task_id)?;
+        require_task(state, &target)?;
+        upsert_custom_link(state, &event.task_id, &target, &name);
+        upsert_custom_link(state, &target, &event.task_id, &inverse);
+        return Ok(());
+    }
     let rel_type = as_relation_type(payload.get("type"));
     let target = relation_target(payload);
     let (Some(rel_type), Some(target)) = (rel_type, target) else {
@@ -134,6 +198,19 @@ pub(crate) fn apply_link_added(state: &mut State, event: &EventRecord) -> Result
 
 pub(crate) fn apply_link_removed(state: &mut State, event: &EventRecord) -> Result<(), TsqError> {
     let payload = &event.payload;
+    if let Some((name, inverse)) = custom_relation(payload) {
+        let Some(target) = relation_target(payload).filter(|target| !target.is_empty()) else {
+            return Err(
+                TsqError::new("INVALID_EVENT", "link.removed requires target and type", 1)
+                    .with_details(serde_json::json!({
+                      "event_id": event_id_value(event),
+                    })),
+            );
+        };
+        remove_custom_link(state, &event.task_id, &target, &name);
+        remove_custom_link(state, &target, &event.task_id, &inverse);
+        return Ok(());
+    }
     let rel_type = as_relation_type(payload.get("type"));
     let target = relation_target(payload);
     let (Some(rel_type), Some(target)) = (rel_type, target) else {
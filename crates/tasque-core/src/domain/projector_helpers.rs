@@ -1,7 +1,7 @@
 use crate::domain::deps::normalize_dependency_edges;
 use crate::domain::event_payload_codecs::{
     event_type_as_str, planning_state_from_str, relation_type_from_str, task_kind_from_str,
-    task_status_as_str, task_status_from_str,
+    task_status_from_str,
 };
 use crate::errors::TsqError;
 use crate::types::{
@@ -182,10 +182,6 @@ pub(crate) fn event_type_to_string(event_type: &EventType) -> &'static str {
     event_type_as_str(*event_type)
 }
 
-pub(crate) fn task_status_to_string(status: TaskStatus) -> &'static str {
-    task_status_as_str(status)
-}
-
 pub(crate) fn event_id_value(event: &EventRecord) -> Option<String> {
     event
         .id
@@ -238,9 +234,11 @@ pub(crate) fn clone_state(state: &State) -> State {
         tasks: state.tasks.clone(),
         deps,
         links,
+        custom_links: state.custom_links.clone(),
         child_counters: state.child_counters.clone(),
         created_order: state.created_order.clone(),
         applied_events: state.applied_events,
+        indexes: state.indexes.clone(),
     }
 }
 
@@ -267,6 +265,8 @@ pub(crate) fn set_task_closed_state(task: &Task, ts: &str) -> Task {
     next.status = TaskStatus::Closed;
     next.updated_at = ts.to_string();
     next.closed_at = Some(ts.to_string());
+    next.defer_until = None;
+    next.blocked_on = None;
     next
 }
 
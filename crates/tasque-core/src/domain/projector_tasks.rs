@@ -2,12 +2,15 @@ use super::projector_helpers::{
     as_bool, as_string, as_task_status, event_id_value, event_identifier,
     optional_planning_state_field, optional_priority_field, optional_string_array_field,
     optional_task_kind_field, optional_task_ref_field, optional_task_status_field, require_task,
-    set_child_counter, set_task_closed_state, task_status_to_string,
+    set_child_counter, set_task_closed_state,
 };
 use crate::domain::alias::{allocate_alias, is_alias_or_id_taken, normalize_alias};
+use crate::domain::criteria::criterion_key;
+use crate::domain::validate::is_task_spec_relative_path;
 use crate::errors::TsqError;
-use crate::store::paths::is_task_spec_relative_path;
-use crate::types::{EventRecord, PlanningState, Task, TaskKind, TaskNote, TaskStatus};
+use crate::types::{
+    ChecklistItem, EventRecord, PlanningState, Task, TaskKind, TaskNote, TaskStatus,
+};
 
 pub(crate) fn apply_task_created(
     state: &mut crate::types::State,
@@ -91,6 +94,7 @@ pub(crate) fn apply_task_created(
         spec_fingerprint: None,
         spec_attached_at: None,
         spec_attached_by: None,
+        closed_at: (status == TaskStatus::Closed).then(|| event.ts.clone()),
         status,
         priority,
         assignee: as_string(payload.get("assignee")),
@@ -102,13 +106,14 @@ pub(crate) fn apply_task_created(
         planning_state: Some(planning_state),
         replies_to,
         labels,
+        criteria_marks: Default::default(),
+        checklist: Vec::new(),
+        rank: None,
+        effective_priority: None,
+        defer_until: None,
+        blocked_on: None,
         created_at: event.ts.clone(),
         updated_at: event.ts.clone(),
-        closed_at: if status == TaskStatus::Closed {
-            Some(event.ts.clone())
-        } else {
-            None
-        },
     };
 
     state.tasks.insert(event.task_id.clone(), task);
@@ -175,12 +180,18 @@ pub(crate) fn apply_task_updated(
     }
 
     if let Some(status) = optional_task_status_field(payload, "status", event, "task.updated")? {
-        next.status = status;
         next.closed_at = if status == TaskStatus::Closed {
             as_string(payload.get("closed_at")).or_else(|| Some(event.ts.clone()))
         } else {
             None
         };
+        if status != TaskStatus::Deferred {
+            next.defer_until = None;
+        }
+        if status != TaskStatus::Blocked {
+            next.blocked_on = None;
+        }
+        next.status = status;
     }
 
     let assignee = as_string(payload.get("assignee"));
@@ -322,14 +333,14 @@ pub(crate) fn apply_task_status_set(
             "INVALID_TRANSITION",
             format!(
                 "cannot transition from {} to in_progress",
-                task_status_to_string(current.status)
+                current.status.as_str()
             ),
             1,
         )
         .with_details(serde_json::json!({
           "event_id": event_id_value(event),
-          "from": task_status_to_string(current.status),
-          "to": task_status_to_string(status),
+          "from": current.status.as_str(),
+          "to": status.as_str(),
         })));
     }
 
@@ -338,10 +349,22 @@ pub(crate) fn apply_task_status_set(
     } else {
         None
     };
+    let defer_until = if status == TaskStatus::Deferred {
+        as_string(payload.get("defer_until"))
+    } else {
+        None
+    };
+    let blocked_on = if status == TaskStatus::Blocked {
+        as_string(payload.get("blocked_on"))
+    } else {
+        None
+    };
     state.tasks.insert(
         event.task_id.clone(),
         Task {
             status,
+            defer_until,
+            blocked_on,
             updated_at: event.ts.clone(),
             closed_at,
             ..current
@@ -359,15 +382,12 @@ pub(crate) fn apply_task_claimed(
     if matches!(current.status, TaskStatus::Closed | TaskStatus::Canceled) {
         return Err(TsqError::new(
             "INVALID_TRANSITION",
-            format!(
-                "cannot claim task with status {}",
-                task_status_to_string(current.status)
-            ),
+            format!("cannot claim task with status {}", current.status.as_str()),
             1,
         )
         .with_details(serde_json::json!({
           "event_id": event_id_value(event),
-          "status": task_status_to_string(current.status),
+          "status": current.status.as_str(),
         })));
     }
     let payload = &event.payload;
@@ -492,6 +512,150 @@ pub(crate) fn apply_task_spec_attached(
     Ok(())
 }
 
+pub(crate) fn apply_task_criterion_set(
+    state: &mut crate::types::State,
+    event: &EventRecord,
+) -> Result<(), TsqError> {
+    let current = require_task(state, &event.task_id)?.clone();
+    let payload = &event.payload;
+    let key = as_string(payload.get("text"))
+        .map(|text| criterion_key(&text))
+        .filter(|key| !key.is_empty());
+    let checked = payload.get("checked").and_then(serde_json::Value::as_bool);
+    let (Some(key), Some(checked)) = (key, checked) else {
+        return Err(TsqError::new(
+            "INVALID_EVENT",
+            "task.criterion_set requires text and checked",
+            1,
+        )
+        .with_details(serde_json::json!({
+          "event_id": event_id_value(event),
+        })));
+    };
+
+    let mut criteria_marks = current.criteria_marks.clone();
+    criteria_marks.insert(key, checked);
+    state.tasks.insert(
+        event.task_id.clone(),
+        Task {
+            criteria_marks,
+            updated_at: event.ts.clone(),
+            ..current
+        },
+    );
+
+    Ok(())
+}
+
+pub(crate) fn apply_task_checklist_added(
+    state: &mut crate::types::State,
+    event: &EventRecord,
+) -> Result<(), TsqError> {
+    let current = require_task(state, &event.task_id)?.clone();
+    let Some(text) = as_string(event.payload.get("text")).filter(|text| !text.is_empty()) else {
+        return Err(
+            TsqError::new("INVALID_EVENT", "task.checklist_added requires text", 1).with_details(
+                serde_json::json!({
+                  "event_id": event_id_value(event),
+                }),
+            ),
+        );
+    };
+
+    let mut checklist = current.checklist.clone();
+    checklist.push(ChecklistItem {
+        event_id: event_identifier(event)?,
+        text,
+        done: false,
+        ts: event.ts.clone(),
+        actor: event.actor.clone(),
+    });
+    state.tasks.insert(
+        event.task_id.clone(),
+        Task {
+            checklist,
+            updated_at: event.ts.clone(),
+            ..current
+        },
+    );
+
+    Ok(())
+}
+
+pub(crate) fn apply_task_checklist_set(
+    state: &mut crate::types::State,
+    event: &EventRecord,
+) -> Result<(), TsqError> {
+    let current = require_task(state, &event.task_id)?.clone();
+    let payload = &event.payload;
+    let item = as_string(payload.get("item"));
+    let done = payload.get("done").and_then(serde_json::Value::as_bool);
+    let (Some(item), Some(done)) = (item, done) else {
+        return Err(TsqError::new(
+            "INVALID_EVENT",
+            "task.checklist_set requires item and done",
+            1,
+        )
+        .with_details(serde_json::json!({
+          "event_id": event_id_value(event),
+        })));
+    };
+
+    let mut checklist = current.checklist.clone();
+    let Some(entry) = checklist.iter_mut().find(|entry| entry.event_id == item) else {
+        return Err(TsqError::new(
+            "INVALID_EVENT",
+            format!("task.checklist_set references unknown item {}", item),
+            1,
+        )
+        .with_details(serde_json::json!({
+          "event_id": event_id_value(event),
+        })));
+    };
+    entry.done = done;
+    state.tasks.insert(
+        event.task_id.clone(),
+        Task {
+            checklist,
+            updated_at: event.ts.clone(),
+            ..current
+        },
+    );
+
+    Ok(())
+}
+
+pub(crate) fn apply_task_ranked(
+    state: &mut crate::types::State,
+    event: &EventRecord,
+) -> Result<(), TsqError> {
+    let current = require_task(state, &event.task_id)?.clone();
+    let Some(rank) = event
+        .payload
+        .get("rank")
+        .and_then(serde_json::Value::as_i64)
+    else {
+        return Err(
+            TsqError::new("INVALID_EVENT", "task.ranked requires integer rank", 1).with_details(
+                serde_json::json!({
+                  "event_id": event_id_value(event),
+                }),
+            ),
+        );
+    };
+
+    state.tasks.insert(
+        event.task_id.clone(),
+        Task {
+            rank: Some(rank),
+            updated_at: event.ts.clone(),
+            ..current
+        },
+    );
+
+    Ok(())
+}
+
 fn assert_no_parent_cycle(
     state: &crate::types::State,
     task_id: &str,
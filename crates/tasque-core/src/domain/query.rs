@@ -1,9 +1,14 @@
+use crate::domain::aggregate::{CountField, parse_count_field};
 use crate::domain::dep_tree::{DependentEdge, build_dependents_by_blocker};
 use crate::domain::deps::{normalize_dependency_edges, normalize_dependency_type};
 use crate::domain::validate::is_ready;
 use crate::errors::TsqError;
 use crate::types::{State, Task, TaskKind, TaskStatus};
-use std::collections::HashMap;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Fields compared against dates, as in `updated<2024-01-01`.
+const DATE_FIELDS: [&str; 3] = ["created", "updated", "closed"];
 
 /// A single parsed search term with optional field qualifier and negation.
 /// Example: status:open becomes field="status" value="open" negated=false.
@@ -29,7 +34,9 @@ pub fn parse_query(q: &str) -> Result<QueryFilter, TsqError> {
     let mut bare_words: Vec<String> = Vec::new();
 
     for token in tokens {
-        if let Some((negated, raw_field, raw_value)) = parse_field_term(&token) {
+        if let Some((negated, raw_field, raw_value)) =
+            parse_field_term(&token).or_else(|| parse_comparison_term(&token))
+        {
             if !bare_words.is_empty() {
                 terms.push(QueryTerm {
                     field: "text".to_string(),
@@ -46,6 +53,41 @@ pub fn parse_query(q: &str) -> Result<QueryFilter, TsqError> {
                 ));
             }
             let value = unquote(raw_value);
+            if raw_field == "priority" && priority_comparison(&value).is_none() {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "priority must be 0..3, optionally prefixed by <, <=, >, or >=, or a range such as 0..1",
+                    1,
+                ));
+            }
+            if raw_field == "estimate"
+                && value != "none"
+                && NumericComparison::parse(&value).is_none()
+            {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "estimate must be none or a whole number, optionally prefixed by <, <=, >, or >=, or a range such as 2..5",
+                    1,
+                ));
+            }
+            if DATE_FIELDS.contains(&raw_field) && date_comparison(&value, Utc::now()).is_none() {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!(
+                        "{} must be a date (YYYY-MM-DD), RFC3339 timestamp, offset like -14d, or period like this-month, optionally prefixed by <, <=, >, or >=",
+                        raw_field
+                    ),
+                    1,
+                ));
+            }
+            if raw_field == "spec" && !matches!(value.as_str(), "attached" | "missing" | "drifted")
+            {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "spec must be attached|missing|drifted",
+                    1,
+                ));
+            }
             let field = if is_supported_field(raw_field) {
                 raw_field.to_string()
             } else {
@@ -86,13 +128,62 @@ pub fn parse_query(q: &str) -> Result<QueryFilter, TsqError> {
     Ok(QueryFilter { terms })
 }
 
+/// Split a trailing `| count by <field>` off a search query, returning the
+/// filter part and the field to count by. A `|` inside quotes is not a pipe.
+pub fn split_count_clause(q: &str) -> Result<(&str, Option<CountField>), TsqError> {
+    let mut in_quotes = false;
+    let mut pipe = None;
+    for (idx, ch) in q.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '|' if !in_quotes => pipe = Some(idx),
+            _ => {}
+        }
+    }
+    let Some(pipe) = pipe else {
+        return Ok((q, None));
+    };
+    let clause: Vec<&str> = q[pipe + 1..].split_whitespace().collect();
+    let [count, by, field] = clause.as_slice() else {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "a query pipe must be `| count by <field>`",
+            1,
+        ));
+    };
+    if !count.eq_ignore_ascii_case("count") || !by.eq_ignore_ascii_case("by") {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "a query pipe must be `| count by <field>`",
+            1,
+        ));
+    }
+    Ok((&q[..pipe], Some(parse_count_field(field)?)))
+}
+
 /// Evaluate a query filter against tasks using implicit AND logic.
 /// Example: evaluate_query(tasks, &filter, state).
+///
+/// Spec files are not read here, so `spec:drifted` matches nothing; callers
+/// with file access use [`evaluate_query_with_drift`]. Relative dates such
+/// as `updated<-14d` are measured from the wall clock.
 pub fn evaluate_query(tasks: &[Task], filter: &QueryFilter, state: &State) -> Vec<Task> {
+    evaluate_query_with_drift(tasks, filter, state, &HashSet::new(), Utc::now())
+}
+
+/// [`evaluate_query`] with the ids of tasks whose attached spec has drifted,
+/// resolving relative dates against `now`.
+pub fn evaluate_query_with_drift(
+    tasks: &[Task],
+    filter: &QueryFilter,
+    state: &State,
+    drifted_specs: &HashSet<String>,
+    now: DateTime<Utc>,
+) -> Vec<Task> {
     if filter.terms.is_empty() {
         return tasks.to_vec();
     }
-    let context = QueryEvalContext::new(filter, state);
+    let context = QueryEvalContext::new(filter, state, drifted_specs, now);
     tasks
         .iter()
         .filter(|&task| matches_all(task, &filter.terms, state, &context))
@@ -100,12 +191,32 @@ pub fn evaluate_query(tasks: &[Task], filter: &QueryFilter, state: &State) -> Ve
         .collect()
 }
 
-struct QueryEvalContext {
+/// Whether evaluating `filter` needs spec drift, i.e. has a `spec:drifted` term.
+pub fn uses_spec_drift(filter: &QueryFilter) -> bool {
+    filter
+        .terms
+        .iter()
+        .any(|term| term.field == "spec" && term.value == "drifted")
+}
+
+/// Both halves of the spec metadata are set, as `tsq spec attach` records them.
+pub fn has_attached_spec(task: &Task) -> bool {
+    task.spec_path.is_some() && task.spec_fingerprint.is_some()
+}
+
+struct QueryEvalContext<'a> {
     dependents_by_blocker: Option<HashMap<String, Vec<DependentEdge>>>,
+    drifted_specs: &'a HashSet<String>,
+    now: DateTime<Utc>,
 }
 
-impl QueryEvalContext {
-    fn new(filter: &QueryFilter, state: &State) -> Self {
+impl<'a> QueryEvalContext<'a> {
+    fn new(
+        filter: &QueryFilter,
+        state: &State,
+        drifted_specs: &'a HashSet<String>,
+        now: DateTime<Utc>,
+    ) -> Self {
         let dependents_by_blocker = if filter.terms.iter().any(|term| term.field == "dep_type_in") {
             Some(build_dependents_by_blocker(&state.deps))
         } else {
@@ -114,6 +225,8 @@ impl QueryEvalContext {
 
         Self {
             dependents_by_blocker,
+            drifted_specs,
+            now,
         }
     }
 
@@ -172,9 +285,20 @@ fn match_term(task: &Task, term: &QueryTerm, state: &State, context: &QueryEvalC
                 .to_lowercase()
                 .contains(&term.value.to_lowercase())
         }),
-        "status" => matches_status(task.status, &term.value),
+        "status" => matches_status(&task.status, &term.value),
         "kind" => matches_kind(task.kind, &term.value),
-        "priority" => task.priority.to_string() == term.value,
+        "priority" => priority_comparison(&term.value)
+            .is_some_and(|comparison| comparison.matches(u32::from(task.priority))),
+        "estimate" => match term.value.as_str() {
+            "none" => task_estimate(task).is_none(),
+            value => task_estimate(task).is_some_and(|estimate| {
+                NumericComparison::parse(value)
+                    .is_some_and(|comparison| comparison.matches(estimate))
+            }),
+        },
+        "created" => matches_date(Some(&task.created_at), &term.value, context.now),
+        "updated" => matches_date(Some(&task.updated_at), &term.value, context.now),
+        "closed" => matches_date(task.closed_at.as_deref(), &term.value, context.now),
         "assignee" => task.assignee.as_deref() == Some(term.value.as_str()),
         "external_ref" => task.external_ref.as_deref() == Some(term.value.as_str()),
         "discovered_from" => task.discovered_from.as_deref() == Some(term.value.as_str()),
@@ -186,24 +310,176 @@ fn match_term(task: &Task, term: &QueryTerm, state: &State, context: &QueryEvalC
         "ready" => is_ready(state, &task.id) == (term.value == "true"),
         "dep_type_in" => has_incoming_dep_type(context, &task.id, &term.value),
         "dep_type_out" => has_outgoing_dep_type(state, &task.id, &term.value),
+        "spec" => match term.value.as_str() {
+            "attached" => has_attached_spec(task),
+            "missing" => !has_attached_spec(task),
+            _ => context.drifted_specs.contains(&task.id),
+        },
         _ => match_task_text(task, &term.value),
     }
 }
 
-fn matches_status(status: TaskStatus, value: &str) -> bool {
+fn matches_status(status: &TaskStatus, value: &str) -> bool {
     match value {
-        "done" => status == TaskStatus::Closed,
-        "todo" => status == TaskStatus::Open,
-        "open" => status == TaskStatus::Open,
-        "in_progress" => status == TaskStatus::InProgress,
-        "blocked" => status == TaskStatus::Blocked,
-        "closed" => status == TaskStatus::Closed,
-        "canceled" => status == TaskStatus::Canceled,
-        "deferred" => status == TaskStatus::Deferred,
-        _ => false,
+        "done" => *status == TaskStatus::Closed,
+        "todo" => *status == TaskStatus::Open,
+        _ => status.as_str() == value,
     }
 }
 
+/// A numeric filter value: `<=1`, `>5`, a bare `2` for equality, or an
+/// inclusive range such as `1..3`.
+#[derive(Debug, Clone, Copy)]
+enum NumericComparison {
+    Compare(&'static str, u32),
+    Range(u32, u32),
+}
+
+impl NumericComparison {
+    fn parse(value: &str) -> Option<Self> {
+        if let Some((low, high)) = value.split_once("..") {
+            let (low, high) = (parse_whole_number(low)?, parse_whole_number(high)?);
+            return (low <= high).then_some(Self::Range(low, high));
+        }
+        let (op, raw) = split_comparison(value);
+        Some(Self::Compare(op, parse_whole_number(raw)?))
+    }
+
+    fn upper_bound(self) -> u32 {
+        match self {
+            Self::Compare(_, bound) | Self::Range(_, bound) => bound,
+        }
+    }
+
+    fn matches(self, actual: u32) -> bool {
+        match self {
+            Self::Compare("<=", bound) => actual <= bound,
+            Self::Compare("<", bound) => actual < bound,
+            Self::Compare(">=", bound) => actual >= bound,
+            Self::Compare(">", bound) => actual > bound,
+            Self::Compare(_, bound) => actual == bound,
+            Self::Range(low, high) => (low..=high).contains(&actual),
+        }
+    }
+}
+
+fn parse_whole_number(raw: &str) -> Option<u32> {
+    if raw.is_empty() || !raw.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    raw.parse().ok()
+}
+
+/// Parse a priority value such as `<=1` or `0..1`; bounds must be 0..3.
+fn priority_comparison(value: &str) -> Option<NumericComparison> {
+    NumericComparison::parse(value).filter(|comparison| comparison.upper_bound() <= 3)
+}
+
+/// The leading number of a task's `estimate:<n>` label, so `estimate:3d`
+/// reads as 3. Tasks without one are unestimated.
+fn task_estimate(task: &Task) -> Option<u32> {
+    task.labels.iter().find_map(|label| {
+        let value = label.strip_prefix("estimate:")?;
+        let end = value
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(value.len());
+        parse_whole_number(&value[..end])
+    })
+}
+
+/// Match a task timestamp against `updated<2024-01-01`-style bounds. A date
+/// covers its whole UTC day, so `<=2024-01-01` includes that day and a bare
+/// date matches anything on it. Tasks without the timestamp never match.
+fn matches_date(timestamp: Option<&str>, value: &str, now: DateTime<Utc>) -> bool {
+    let Some(at) = timestamp.and_then(|raw| DateTime::parse_from_rfc3339(raw).ok()) else {
+        return false;
+    };
+    let at = at.with_timezone(&Utc);
+    match date_comparison(value, now) {
+        Some(("<=", (_, end))) => at < end,
+        Some(("<", (start, _))) => at < start,
+        Some((">=", (start, _))) => at >= start,
+        Some((">", (_, end))) => at >= end,
+        Some((_, (start, end))) => at >= start && at < end,
+        None => false,
+    }
+}
+
+/// Half-open `[start, end)` span of time named by a date filter value.
+type DateSpan = (DateTime<Utc>, DateTime<Utc>);
+
+/// Split a date value into its operator and the span it names: a whole day
+/// for `YYYY-MM-DD`, a single instant for RFC3339 or an offset from `now`
+/// such as `-14d`, and a calendar period for names such as `this-month`.
+fn date_comparison(value: &str, now: DateTime<Utc>) -> Option<(&'static str, DateSpan)> {
+    let (op, raw) = split_comparison(value);
+    if let Some(at) = DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|| relative_instant(raw, now))
+    {
+        return Some((op, (at, at + Duration::nanoseconds(1))));
+    }
+    if let Some(span) = named_period(raw, now) {
+        return Some((op, span));
+    }
+    let day = NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()?;
+    Some((op, day_span(day)?))
+}
+
+/// `-14d`, `+2h`, or `-1w`: hours, days, or weeks before or after `now`.
+fn relative_instant(raw: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (sign, rest) = match raw.split_at_checked(1)? {
+        ("-", rest) => (-1, rest),
+        ("+", rest) => (1, rest),
+        _ => return None,
+    };
+    let (digits, unit) = rest.split_at_checked(rest.len().checked_sub(1)?)?;
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let amount = sign * digits.parse::<i64>().ok()?;
+    let offset = match unit {
+        "h" => Duration::try_hours(amount)?,
+        "d" => Duration::try_days(amount)?,
+        "w" => Duration::try_weeks(amount)?,
+        _ => return None,
+    };
+    now.checked_add_signed(offset)
+}
+
+/// Calendar periods in UTC; weeks start on Monday.
+fn named_period(raw: &str, now: DateTime<Utc>) -> Option<DateSpan> {
+    let today = now.date_naive();
+    let week = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+    let month = today.with_day(1)?;
+    let year = NaiveDate::from_ymd_opt(today.year(), 1, 1)?;
+    let (start, end) = match raw {
+        "today" => (today, today.succ_opt()?),
+        "yesterday" => (today.pred_opt()?, today),
+        "this-week" => (week, week + Duration::days(7)),
+        "last-week" => (week - Duration::days(7), week),
+        "this-month" => (month, month.checked_add_months(chrono::Months::new(1))?),
+        "last-month" => (month.checked_sub_months(chrono::Months::new(1))?, month),
+        "this-year" => (year, year.with_year(year.year() + 1)?),
+        "last-year" => (year.with_year(year.year() - 1)?, year),
+        _ => return None,
+    };
+    Some((day_span(start)?.0, day_span(end)?.0))
+}
+
+fn day_span(day: NaiveDate) -> Option<DateSpan> {
+    let start = day.and_hms_opt(0, 0, 0)?.and_utc();
+    Some((start, start + Duration::days(1)))
+}
+
+fn split_comparison(value: &str) -> (&'static str, &str) {
+    ["<=", ">=", "<", ">"]
+        .into_iter()
+        .find_map(|op| value.strip_prefix(op).map(|rest| (op, rest)))
+        .unwrap_or(("", value))
+}
+
 fn matches_kind(kind: TaskKind, value: &str) -> bool {
     match value {
         "task" => kind == TaskKind::Task,
@@ -346,6 +622,23 @@ fn parse_field_term(token: &str) -> Option<(bool, &str, &str)> {
     Some((negated, field, value))
 }
 
+/// Parse `priority<=1`-style comparisons; the operator stays in the value.
+fn parse_comparison_term(token: &str) -> Option<(bool, &str, &str)> {
+    let (negated, rest) = if let Some(stripped) = token.strip_prefix('-') {
+        (true, stripped)
+    } else {
+        (false, token)
+    };
+    let op_idx = rest.find(['<', '>'])?;
+    let field = &rest[..op_idx];
+    if !(matches!(field, "priority" | "estimate") || DATE_FIELDS.contains(&field))
+        || op_idx + 1 >= rest.len()
+    {
+        return None;
+    }
+    Some((negated, field, &rest[op_idx..]))
+}
+
 fn unquote(value: &str) -> String {
     if value.starts_with('"') && value.ends_with('"') && value.len() >= 2 {
         return value[1..value.len() - 1].to_string();
@@ -364,6 +657,10 @@ fn is_supported_field(field: &str) -> bool {
             | "status"
             | "kind"
             | "priority"
+            | "estimate"
+            | "created"
+            | "updated"
+            | "closed"
             | "assignee"
             | "external_ref"
             | "discovered_from"
@@ -372,6 +669,7 @@ fn is_supported_field(field: &str) -> bool {
             | "ready"
             | "dep_type_in"
             | "dep_type_out"
+            | "spec"
     )
 }
 
@@ -0,0 +1,77 @@
+use crate::errors::TsqError;
+use crate::types::RedactionConfig;
+use regex::Regex;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Compiled form of `RedactionConfig`, applied to serialized tasks.
+pub struct Redactor {
+    strip: Vec<String>,
+    hash: Vec<String>,
+    drop: Vec<Regex>,
+}
+
+impl Redactor {
+    pub fn new(config: &RedactionConfig) -> Result<Self, TsqError> {
+        let drop = config
+            .drop
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|error| {
+                    TsqError::new(
+                        "VALIDATION_ERROR",
+                        format!("invalid redaction drop pattern: {}", pattern),
+                        1,
+                    )
+                    .with_details(serde_json::json!({"message": error.to_string()}))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            strip: config.strip.clone(),
+            hash: config.hash.clone(),
+            drop,
+        })
+    }
+
+    /// Removes stripped and dropped fields and pseudonymizes hashed ones.
+    /// Non-object values pass through untouched.
+    pub fn apply(&self, value: Value) -> Value {
+        let Value::Object(fields) = value else {
+            return value;
+        };
+        let redacted = fields
+            .into_iter()
+            .filter(|(key, _)| {
+                !self.strip.contains(key) && !self.drop.iter().any(|regex| regex.is_match(key))
+            })
+            .map(|(key, value)| {
+                if self.hash.contains(&key) {
+                    (key, hash_value(value))
+                } else {
+                    (key, value)
+                }
+            })
+            .collect();
+        Value::Object(redacted)
+    }
+}
+
+/// Stable pseudonym so the same name maps to the same token across exports.
+pub fn pseudonym(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    let hex = digest
+        .iter()
+        .take(5)
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    format!("anon-{}", hex)
+}
+
+fn hash_value(value: Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(pseudonym(&text)),
+        Value::Array(items) => Value::Array(items.into_iter().map(hash_value).collect()),
+        other => other,
+    }
+}
@@ -0,0 +1,103 @@
+use crate::errors::TsqError;
+use crate::types::{State, Task};
+use serde_json::json;
+
+/// Outcome of looking up one task reference without turning misses into errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskLookup {
+    Found(String),
+    NotFound,
+    /// `(id, alias)` pairs sorted by id.
+    Ambiguous(Vec<(String, String)>),
+}
+
+pub fn resolve_task_id(state: &State, raw: &str, exact_id: bool) -> Result<String, TsqError> {
+    lookup_result(state, raw, lookup_task_id(state, raw, exact_id))
+}
+
+pub fn lookup_task_id(state: &State, raw: &str, exact_id: bool) -> TaskLookup {
+    if state.tasks.contains_key(raw) {
+        return TaskLookup::Found(raw.to_string());
+    }
+    if exact_id {
+        return TaskLookup::NotFound;
+    }
+
+    let raw_alias = raw.to_lowercase();
+    if let Some(task) = state
+        .tasks
+        .values()
+        .find(|task| task.alias.to_lowercase() == raw_alias)
+    {
+        return TaskLookup::Found(task.id.clone());
+    }
+
+    let id_matches = matching(state, |task| task.id.starts_with(raw));
+    if !matches!(id_matches, TaskLookup::NotFound) {
+        return id_matches;
+    }
+    matching(state, |task| {
+        task.alias.to_lowercase().starts_with(&raw_alias)
+    })
+}
+
+/// Like `lookup_task_id`, but an input matching no id or alias is also tried
+/// as an exact `external_ref`.
+pub fn lookup_task_ref(state: &State, raw: &str, exact_id: bool) -> TaskLookup {
+    match lookup_task_id(state, raw, exact_id) {
+        TaskLookup::NotFound => matching(state, |task| {
+            task.external_ref.as_deref() == Some(raw.trim())
+        }),
+        found => found,
+    }
+}
+
+fn matching(state: &State, predicate: impl Fn(&Task) -> bool) -> TaskLookup {
+    let mut matches: Vec<(String, String)> = state
+        .tasks
+        .values()
+        .filter(|task| predicate(task))
+        .map(|task| (task.id.clone(), task.alias.clone()))
+        .collect();
+    matches.sort_by(|a, b| a.0.cmp(&b.0));
+
+    match matches.len() {
+        0 => TaskLookup::NotFound,
+        1 => TaskLookup::Found(matches.remove(0).0),
+        _ => TaskLookup::Ambiguous(matches),
+    }
+}
+
+/// Ambiguity details list each candidate's `id`, `alias`, `title`, and
+/// `status` so callers can offer a pick list.
+fn lookup_result(state: &State, raw: &str, lookup: TaskLookup) -> Result<String, TsqError> {
+    match lookup {
+        TaskLookup::Found(id) => Ok(id),
+        TaskLookup::NotFound => Err(not_found(raw)),
+        TaskLookup::Ambiguous(candidates) => {
+            Err(
+                TsqError::new("TASK_ID_AMBIGUOUS", "Task ID is ambiguous", 1).with_details(json!({
+                    "input": raw,
+                    "candidates": candidates
+                        .into_iter()
+                        .map(|(id, alias)| {
+                            let task = state.tasks.get(&id);
+                            json!({
+                                "id": id,
+                                "alias": alias,
+                                "title": task.map(|task| task.title.as_str()),
+                                "status": task.map(|task| task.status.clone()),
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })),
+            )
+        }
+    }
+}
+
+fn not_found(raw: &str) -> TsqError {
+    TsqError::new("TASK_NOT_FOUND", "Task ID not found", 1).with_details(json!({
+      "input": raw
+    }))
+}
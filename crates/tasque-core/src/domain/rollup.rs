@@ -0,0 +1,72 @@
+use crate::types::{State, Task, TaskKind, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Completion of a parent's direct children; closed and canceled count as done,
+/// and configured statuses count as in progress.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChildProgress {
+    pub done: usize,
+    pub total: usize,
+    pub open: usize,
+    pub in_progress: usize,
+    /// Whole percent of children done; `0` for a parent without children.
+    pub percent: u8,
+}
+
+pub fn child_progress<'a>(children: impl IntoIterator<Item = &'a Task>) -> ChildProgress {
+    let mut progress = ChildProgress::default();
+    for task in children {
+        progress.total += 1;
+        match task.status {
+            TaskStatus::Closed | TaskStatus::Canceled => progress.done += 1,
+            TaskStatus::InProgress | TaskStatus::Blocked | TaskStatus::Custom(_) => {
+                progress.in_progress += 1
+            }
+            _ => progress.open += 1,
+        }
+    }
+    progress.percent = (progress.done * 100)
+        .checked_div(progress.total)
+        .unwrap_or(0) as u8;
+    progress
+}
+
+/// Features and epics, sorted by id, whose children are all closed or
+/// canceled and whose last child closed after the parent was last updated.
+/// Reading the projected state rather than the closing command means synced
+/// or replayed histories qualify too, and a parent touched since (say,
+/// reopened by hand) is left alone.
+pub fn completed_parents(state: &State) -> Vec<&Task> {
+    let mut children: HashMap<&str, Vec<&Task>> = HashMap::new();
+    for task in state.tasks.values() {
+        if let Some(parent_id) = task.parent_id.as_deref() {
+            children.entry(parent_id).or_default().push(task);
+        }
+    }
+    let mut parents: Vec<&Task> = children
+        .into_iter()
+        .filter_map(|(parent_id, children)| {
+            let parent = state.tasks.get(parent_id)?;
+            if parent.kind == TaskKind::Task
+                || matches!(
+                    parent.status,
+                    TaskStatus::Closed | TaskStatus::Canceled | TaskStatus::Deferred
+                )
+            {
+                return None;
+            }
+            let progress = child_progress(children.iter().copied());
+            if progress.done < progress.total {
+                return None;
+            }
+            let last_closed = children
+                .iter()
+                .filter_map(|child| child.closed_at.as_deref())
+                .max()?;
+            (last_closed > parent.updated_at.as_str()).then_some(parent)
+        })
+        .collect();
+    parents.sort_by(|a, b| a.id.cmp(&b.id));
+    parents
+}
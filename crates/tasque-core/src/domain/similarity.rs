@@ -13,11 +13,9 @@ pub struct SimilarTaskCandidate {
     pub reason: String,
 }
 
-pub fn blocking_status(status: TaskStatus) -> bool {
-    matches!(
-        status,
-        TaskStatus::Open | TaskStatus::InProgress | TaskStatus::Blocked | TaskStatus::Deferred
-    )
+/// Unfinished work, including configured custom statuses.
+pub fn blocking_status(status: &TaskStatus) -> bool {
+    !matches!(status, TaskStatus::Closed | TaskStatus::Canceled)
 }
 
 pub fn find_similar_candidates<'a>(
@@ -0,0 +1,35 @@
+use crate::types::Task;
+
+pub fn sort_tasks(tasks: &[Task]) -> Vec<Task> {
+    let mut sorted = tasks.to_vec();
+    sorted.sort_by(compare_tasks);
+    sorted
+}
+
+/// List order: effective priority, stored priority, creation time, id.
+pub fn compare_tasks(a: &Task, b: &Task) -> std::cmp::Ordering {
+    let (pa, pb) = (
+        a.effective_priority.unwrap_or(a.priority),
+        b.effective_priority.unwrap_or(b.priority),
+    );
+    if pa != pb {
+        return pa.cmp(&pb);
+    }
+    if a.priority != b.priority {
+        return a.priority.cmp(&b.priority);
+    }
+    if a.created_at == b.created_at {
+        return a.id.cmp(&b.id);
+    }
+    a.created_at.cmp(&b.created_at)
+}
+
+/// Sibling order: explicitly ranked children first by rank, then the rest in
+/// `sort_tasks` order.
+pub fn sort_child_tasks(tasks: &[Task]) -> Vec<Task> {
+    let (mut ranked, unranked): (Vec<Task>, Vec<Task>) =
+        tasks.iter().cloned().partition(|task| task.rank.is_some());
+    ranked.sort_by(|a, b| a.rank.cmp(&b.rank).then_with(|| a.id.cmp(&b.id)));
+    ranked.extend(sort_tasks(&unranked));
+    ranked
+}
@@ -86,14 +86,22 @@ fn validate_deps(state: &State) -> Result<(), TsqError> {
 }
 
 fn validate_links(state: &State) -> Result<(), TsqError> {
-    for (source, by_type) in &state.links {
+    let builtin = state
+        .links
+        .iter()
+        .map(|(source, by_type)| (source, by_type.values().collect::<Vec<_>>()));
+    let custom = state
+        .custom_links
+        .iter()
+        .map(|(source, by_type)| (source, by_type.values().collect::<Vec<_>>()));
+    for (source, target_lists) in builtin.chain(custom) {
         if !state.tasks.contains_key(source) {
             return Err(invalid_state(format!(
                 "link source {} references missing task",
                 source
             )));
         }
-        for targets in by_type.values() {
+        for targets in target_lists {
             for target in targets {
                 if !state.tasks.contains_key(target) {
                     return Err(invalid_state(format!(
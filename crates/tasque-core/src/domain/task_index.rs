@@ -0,0 +1,112 @@
+use crate::types::{State, Task, TaskIndexes, TaskStatus};
+use std::collections::{BTreeSet, HashMap};
+
+/// The fields of a task that `TaskIndexes` is keyed on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexKeys {
+    pub status: TaskStatus,
+    pub assignee: Option<String>,
+    pub labels: Vec<String>,
+}
+
+impl IndexKeys {
+    pub fn of(task: &Task) -> Self {
+        Self {
+            status: task.status.clone(),
+            assignee: task.assignee.clone(),
+            labels: task.labels.clone(),
+        }
+    }
+}
+
+/// Groups ids per key first and bulk-builds each set, which is several times
+/// faster than inserting one id at a time on large repos.
+pub fn build_task_indexes<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> TaskIndexes {
+    let mut by_status: HashMap<&TaskStatus, Vec<&str>> = HashMap::new();
+    let mut by_assignee: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut by_label: HashMap<&str, Vec<&str>> = HashMap::new();
+    for task in tasks {
+        let id = task.id.as_str();
+        by_status.entry(&task.status).or_default().push(id);
+        if let Some(assignee) = task.assignee.as_deref() {
+            by_assignee.entry(assignee).or_default().push(id);
+        }
+        for label in &task.labels {
+            by_label.entry(label.as_str()).or_default().push(id);
+        }
+    }
+    TaskIndexes {
+        by_status: collect_sets(by_status, TaskStatus::clone),
+        by_assignee: collect_sets(by_assignee, str::to_string),
+        by_label: collect_sets(by_label, str::to_string),
+    }
+}
+
+fn collect_sets<Q, K>(
+    grouped: HashMap<&Q, Vec<&str>>,
+    key: impl Fn(&Q) -> K,
+) -> HashMap<K, BTreeSet<String>>
+where
+    Q: ?Sized,
+    K: std::hash::Hash + Eq,
+{
+    grouped
+        .into_iter()
+        .map(|(group, mut ids)| {
+            ids.sort_unstable();
+            (key(group), ids.into_iter().map(str::to_string).collect())
+        })
+        .collect()
+}
+
+/// Indexes are not persisted, so a state read back from a cache or snapshot
+/// gets them here once; replay then keeps them current.
+pub fn reindex_state(state: &mut State) {
+    state.indexes = build_task_indexes(state.tasks.values());
+}
+
+pub fn index_task(indexes: &mut TaskIndexes, id: &str, keys: &IndexKeys) {
+    indexes
+        .by_status
+        .entry(keys.status.clone())
+        .or_default()
+        .insert(id.to_string());
+    if let Some(assignee) = keys.assignee.as_deref() {
+        indexes
+            .by_assignee
+            .entry(assignee.to_string())
+            .or_default()
+            .insert(id.to_string());
+    }
+    for label in &keys.labels {
+        indexes
+            .by_label
+            .entry(label.clone())
+            .or_default()
+            .insert(id.to_string());
+    }
+}
+
+pub fn unindex_task(indexes: &mut TaskIndexes, id: &str, keys: &IndexKeys) {
+    remove_id(&mut indexes.by_status, &keys.status, id);
+    if let Some(assignee) = keys.assignee.as_deref() {
+        remove_id(&mut indexes.by_assignee, assignee, id);
+    }
+    for label in &keys.labels {
+        remove_id(&mut indexes.by_label, label.as_str(), id);
+    }
+}
+
+/// Drops the key once its last id is gone so a rebuilt index compares equal.
+fn remove_id<K, Q>(index: &mut HashMap<K, BTreeSet<String>>, key: &Q, id: &str)
+where
+    K: std::borrow::Borrow<Q> + std::hash::Hash + Eq,
+    Q: std::hash::Hash + Eq + ?Sized,
+{
+    if let Some(ids) = index.get_mut(key) {
+        ids.remove(id);
+        if ids.is_empty() {
+            index.remove(key);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::domain::deps::{is_cycle_checked, normalize_dependency_edges};
+use crate::errors::TsqError;
+use crate::types::{DependencyType, LaneFilter, PlanningState, State, Task, TaskStatus};
+use serde_json::json;
+use std::collections::HashSet;
+
+pub const BUILTIN_LANES: [&str; 2] = ["planning", "coding"];
+
+fn blocking_dep_ids(state: &State, task_id: &str) -> Vec<String> {
+    normalize_dependency_edges(state.deps.get(task_id))
+        .into_iter()
+        .filter(|edge| edge.dep_type == DependencyType::Blocks)
+        .map(|edge| edge.blocker)
+        .collect()
+}
+
+fn cycle_checked_dep_ids(state: &State, task_id: &str) -> Vec<String> {
+    normalize_dependency_edges(state.deps.get(task_id))
+        .into_iter()
+        .filter(|edge| is_cycle_checked(edge.dep_type))
+        .map(|edge| edge.blocker)
+        .collect()
+}
+
+/// `finish_to_finish` blockers of `task_id` that are not yet closed or canceled.
+pub fn unfinished_finish_blockers(state: &State, task_id: &str) -> Vec<String> {
+    normalize_dependency_edges(state.deps.get(task_id))
+        .into_iter()
+        .filter(|edge| edge.dep_type == DependencyType::FinishToFinish)
+        .filter(|edge| {
+            state.tasks.get(&edge.blocker).is_some_and(|blocker| {
+                !matches!(blocker.status, TaskStatus::Closed | TaskStatus::Canceled)
+            })
+        })
+        .map(|edge| edge.blocker)
+        .collect()
+}
+
+pub fn assert_no_dependency_cycle(
+    state: &State,
+    child: &str,
+    blocker: &str,
+) -> Result<(), TsqError> {
+    if child == blocker {
+        return Err(
+            TsqError::new("DEPENDENCY_CYCLE", "Dependency cycle detected", 1).with_details(json!({
+              "child": child,
+              "blocker": blocker
+            })),
+        );
+    }
+
+    let mut stack = vec![blocker.to_string()];
+    let mut visited: HashSet<String> = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if visited.contains(&current) {
+            continue;
+        }
+        if current == child {
+            return Err(
+                TsqError::new("DEPENDENCY_CYCLE", "Dependency cycle detected", 1).with_details(
+                    json!({
+                      "child": child,
+                      "blocker": blocker
+                    }),
+                ),
+            );
+        }
+        visited.insert(current.clone());
+        for next in cycle_checked_dep_ids(state, &current) {
+            if !visited.contains(&next) {
+                stack.push(next);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn is_ready(state: &State, task_id: &str) -> bool {
+    let Some(task) = state.tasks.get(task_id) else {
+        return false;
+    };
+    if !matches!(task.status, TaskStatus::Open | TaskStatus::InProgress) {
+        return false;
+    }
+
+    for blocker_id in blocking_dep_ids(state, task_id) {
+        let Some(blocker) = state.tasks.get(&blocker_id) else {
+            return false;
+        };
+        if !matches!(blocker.status, TaskStatus::Closed | TaskStatus::Canceled) {
+            return false;
+        }
+    }
+
+    true
+}
+
+pub fn list_ready(state: &State) -> Vec<Task> {
+    let mut ready = Vec::new();
+
+    for id in &state.created_order {
+        let Some(task) = state.tasks.get(id) else {
+            continue;
+        };
+        if is_ready(state, id) {
+            ready.push(task.clone());
+        }
+    }
+
+    ready
+}
+
+/// The filter behind a builtin lane: `planning` takes every configured state
+/// short of `planned`, and `coding` takes planned tasks.
+pub fn builtin_lane(name: &str, planning_states: &[PlanningState]) -> Option<LaneFilter> {
+    let planning_state = match name {
+        "planning" => planning_states
+            .iter()
+            .filter(|state| **state != PlanningState::Planned)
+            .cloned()
+            .collect(),
+        "coding" => vec![PlanningState::Planned],
+        _ => return None,
+    };
+    Some(LaneFilter {
+        planning_state,
+        ..LaneFilter::default()
+    })
+}
+
+pub fn lane_matches(task: &Task, filter: &LaneFilter) -> bool {
+    let planning_state = task
+        .planning_state
+        .clone()
+        .unwrap_or(PlanningState::NeedsPlanning);
+    (filter.planning_state.is_empty() || filter.planning_state.contains(&planning_state))
+        && (filter.kind.is_empty() || filter.kind.contains(&task.kind))
+        && filter
+            .labels
+            .iter()
+            .all(|label| task.labels.contains(label))
+}
+
+pub fn list_ready_in_lane(state: &State, filter: &LaneFilter) -> Vec<Task> {
+    list_ready(state)
+        .into_iter()
+        .filter(|task| lane_matches(task, filter))
+        .collect()
+}
+
+pub fn task_spec_relative_path(task_id: &str) -> String {
+    format!(".tasque/specs/{}/spec.md", task_id)
+}
+
+pub fn is_task_spec_relative_path(task_id: &str, spec_path: &str) -> bool {
+    spec_path == task_spec_relative_path(task_id)
+}
@@ -0,0 +1,49 @@
+//! Event-sourced task tracking engine behind the `tsq` CLI.
+//!
+//! Everything goes through [`TasqueService`]: it appends events to
+//! `.tasque/events.jsonl` under the repo lock and answers queries from the
+//! projected state, so an embedding tool sees exactly what `tsq` sees.
+//! Inputs and results live in [`app::service_types`]; failures are
+//! [`TsqError`]s carrying the same stable `code` the CLI prints.
+//!
+//! ```no_run
+//! use tasque_core::app::runtime::now_iso;
+//! use tasque_core::{TasqueService, TsqError};
+//!
+//! let service = TasqueService::new("/path/to/repo", "my-tool", now_iso);
+//! for task in service.ready(None)? {
+//!     println!("{} {}", task.id, task.title);
+//! }
+//! # Ok::<(), TsqError>(())
+//! ```
+//!
+//! Async servers wrap it in [`AsyncTasqueService`], which runs calls on
+//! worker threads and returns futures that any runtime can await.
+//!
+//! The default `fs` feature provides the file store, [`TasqueService`], and
+//! skills. Without it (`default-features = false`) only the domain layer and
+//! [`replay`] build, which is what the wasm32 build uses to project a fetched
+//! `events.jsonl` in the browser.
+//!
+//! The crate follows semver together with the CLI: [`TasqueService`],
+//! [`AsyncTasqueService`], the service types, and [`types`] are the supported
+//! surface. The `domain` and `store` modules are public for tooling but may
+//! change in minor releases.
+
+#[cfg(feature = "fs")]
+pub mod app;
+pub mod domain;
+pub mod errors;
+pub mod output;
+pub mod replay;
+#[cfg(feature = "fs")]
+pub mod skills;
+#[cfg(feature = "fs")]
+pub mod store;
+pub mod trace;
+pub mod types;
+
+#[cfg(feature = "fs")]
+pub use app::{AsyncTasqueService, TasqueService};
+pub use errors::TsqError;
+pub use types::*;
@@ -0,0 +1,80 @@
+use crate::types::{Envelope, EnvelopeErr, EnvelopeError, EnvelopeOk, LabelColor, SCHEMA_VERSION};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub fn ok_envelope<T>(command: impl Into<String>, data: T) -> Envelope<T> {
+    Envelope::Ok(EnvelopeOk {
+        schema_version: SCHEMA_VERSION,
+        command: command.into(),
+        ok: true,
+        data,
+        meta: None,
+    })
+}
+
+pub fn err_envelope(
+    command: impl Into<String>,
+    code: impl Into<String>,
+    message: impl Into<String>,
+    details: Option<Value>,
+) -> Envelope<Value> {
+    Envelope::Err(EnvelopeErr {
+        schema_version: SCHEMA_VERSION,
+        command: command.into(),
+        ok: false,
+        error: EnvelopeError {
+            code: code.into(),
+            message: message.into(),
+            details,
+        },
+    })
+}
+
+/// Listing payloads carry `note_count` instead of each task's `notes` array;
+/// `--with-notes` skips this and keeps the full notes.
+pub fn compact_task_notes(task: &mut Value) {
+    let Some(obj) = task.as_object_mut() else {
+        return;
+    };
+    let count = match obj.remove("notes") {
+        Some(Value::Array(notes)) => notes.len(),
+        _ => 0,
+    };
+    obj.insert("note_count".to_string(), Value::from(count));
+}
+
+/// Adds the repo's `label_colors` next to a task payload so downstream UIs
+/// can paint labels the same way; omitted when no colors are configured.
+pub fn with_label_colors(payload: &mut Value, colors: &BTreeMap<String, LabelColor>) {
+    if colors.is_empty() {
+        return;
+    }
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert(
+            "label_colors".to_string(),
+            serde_json::to_value(colors).unwrap_or_default(),
+        );
+    }
+}
+
+/// `compact_task_notes` for every task in a JSON array.
+pub fn compact_task_list(tasks: &mut Value) {
+    if let Some(tasks) = tasks.as_array_mut() {
+        tasks.iter_mut().for_each(compact_task_notes);
+    }
+}
+
+/// `compact_task_notes` for every node of a `TaskTreeNode` forest.
+pub fn compact_task_tree(nodes: &mut Value) {
+    let Some(nodes) = nodes.as_array_mut() else {
+        return;
+    };
+    for node in nodes {
+        if let Some(task) = node.get_mut("task") {
+            compact_task_notes(task);
+        }
+        if let Some(children) = node.get_mut("children") {
+            compact_task_tree(children);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Projection of an event log that only needs the log's bytes, so it runs
+//! anywhere the domain layer builds, including wasm32 without the `fs`
+//! feature.
+
+use crate::domain::event_records::{is_sealed, parse_event_record};
+use crate::domain::projector::apply_events;
+use crate::domain::state::create_empty_state;
+use crate::errors::TsqError;
+use crate::types::{EventRecord, State};
+use serde_json::Value;
+
+/// Where [`replay`] reads the raw JSONL log from. A repo on disk is
+/// `store::segments::RepoEventLog`; a browser passes the fetched text.
+pub trait EventLogSource {
+    fn read_event_log(&self) -> Result<Vec<u8>, TsqError>;
+}
+
+impl EventLogSource for str {
+    fn read_event_log(&self) -> Result<Vec<u8>, TsqError> {
+        Ok(self.as_bytes().to_vec())
+    }
+}
+
+pub struct Replayed {
+    pub state: State,
+    pub events: Vec<EventRecord>,
+    /// Set when a torn trailing line was skipped.
+    pub warning: Option<String>,
+}
+
+pub fn replay(source: &(impl EventLogSource + ?Sized)) -> Result<Replayed, TsqError> {
+    let raw = String::from_utf8(source.read_event_log()?)
+        .map_err(|_| TsqError::new("EVENTS_CORRUPT", "Events file is not valid UTF-8", 2))?;
+    let (events, warning) = parse_event_lines(&raw)?;
+    let mut state = apply_events(&create_empty_state(), &events)?;
+    state.applied_events = events.len();
+    Ok(Replayed {
+        state,
+        events,
+        warning,
+    })
+}
+
+/// Plaintext lines only: opening sealed lines needs the repo's key, which
+/// replay never has, so an encrypted log fails with EVENTS_SEALED.
+pub fn parse_event_lines(raw: &str) -> Result<(Vec<EventRecord>, Option<String>), TsqError> {
+    let lines: Vec<(usize, &str)> = raw
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+    let mut events = Vec::with_capacity(lines.len());
+    for (position, (index, line)) in lines.iter().enumerate() {
+        let line_number = index + 1;
+        if is_sealed(line) {
+            return Err(TsqError::new(
+                "EVENTS_SEALED",
+                format!(
+                    "Event at line {} is encrypted; replay reads plaintext logs only",
+                    line_number
+                ),
+                2,
+            ));
+        }
+        let value = match serde_json::from_str::<Value>(line) {
+            Ok(value) => value,
+            // A torn final write is tolerated, as when reading from disk.
+            Err(_) if position == lines.len() - 1 => {
+                let warning = format!("Ignored malformed trailing JSONL line {}", line_number);
+                return Ok((events, Some(warning)));
+            }
+            Err(_) => {
+                return Err(TsqError::new(
+                    "EVENTS_CORRUPT",
+                    format!("Malformed events JSONL at line {}", line_number),
+                    2,
+                ));
+            }
+        };
+        events.push(parse_event_record(&value, line_number)?);
+    }
+    Ok((events, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_plain_lines_and_skips_a_torn_tail() {
+        let log = concat!(
+            r#"{"id":"01A","ts":"2026-01-01T00:00:00.000Z","actor":"a","type":"task.created","task_id":"tsq-aaaaaaaa","payload":{"title":"One"}}"#,
+            "\n",
+            r#"{"id":"01B","ts":"2026-01-01T00:00:01.000Z","actor":"a","type":"task.status_set","task_id":"tsq-aaaaaaaa","payload":{"status":"in_progress"}}"#,
+            "\n",
+            r#"{"id":"01C","ts":"#,
+        );
+        let replayed = replay(log).expect("replay");
+        assert_eq!(replayed.events.len(), 2);
+        assert_eq!(replayed.state.applied_events, 2);
+        assert!(replayed.warning.is_some());
+        let task = &replayed.state.tasks["tsq-aaaaaaaa"];
+        assert_eq!(task.title, "One");
+        assert_eq!(task.status, crate::types::TaskStatus::InProgress);
+    }
+
+    #[test]
+    fn rejects_sealed_lines() {
+        let error = replay("tsq-enc:v1:00\n").err().expect("sealed");
+        assert_eq!(error.code, "EVENTS_SEALED");
+    }
+}
@@ -5,7 +5,7 @@ use std::path::{Path, PathBuf};
 use ulid::Ulid;
 
 #[derive(RustEmbed)]
-#[folder = "SKILLS"]
+#[folder = "../../SKILLS"]
 struct EmbeddedSkills;
 
 pub struct EmbeddedSkillMaterialization {
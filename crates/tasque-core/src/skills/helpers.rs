@@ -1,4 +1,5 @@
 use crate::errors::TsqError;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -49,6 +50,7 @@ pub(super) fn inspect_path(path: &Path) -> Result<PathKind, TsqError> {
     }
 }
 
+/// Copies a skill tree, leaving out any `.git` directory.
 pub(super) fn copy_directory_recursive(
     source_directory: &Path,
     destination_directory: &Path,
@@ -67,6 +69,9 @@ pub(super) fn copy_directory_recursive(
             TsqError::new("IO_ERROR", "failed reading source directory entry", 2)
                 .with_details(io_error_value(&e))
         })?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
         let source_path = entry.path();
         let destination_path = destination_directory.join(entry.file_name());
         let file_type = entry.file_type().map_err(|e| {
@@ -104,6 +109,67 @@ pub(super) fn copy_directory_recursive(
     Ok(())
 }
 
+/// Regular files under `directory` keyed by `/`-separated relative path,
+/// skipping `.git` (a git source's checkout root can itself be the skill).
+/// Symlinks are recorded by their target so a retargeted link counts as a change.
+pub(super) fn collect_skill_files(
+    root: &Path,
+    directory: &Path,
+    out: &mut BTreeMap<String, Vec<u8>>,
+) -> Result<(), TsqError> {
+    let entries = fs::read_dir(directory).map_err(|e| {
+        TsqError::new("IO_ERROR", "failed reading skill directory", 2)
+            .with_details(io_error_value(&e))
+    })?;
+    for entry in entries {
+        let entry = entry.map_err(|e| {
+            TsqError::new("IO_ERROR", "failed reading skill directory entry", 2)
+                .with_details(io_error_value(&e))
+        })?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| {
+            TsqError::new("IO_ERROR", "failed reading skill entry file type", 2)
+                .with_details(io_error_value(&e))
+        })?;
+        if file_type.is_dir() {
+            collect_skill_files(root, &path, out)?;
+            continue;
+        }
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        // Reading a FIFO or socket would block, so reject them like install does.
+        if !file_type.is_file() && !file_type.is_symlink() {
+            return Err(TsqError::new(
+                "IO_ERROR",
+                format!(
+                    "unsupported entry in managed skill source: {}",
+                    path.display()
+                ),
+                2,
+            ));
+        }
+        let content = if file_type.is_symlink() {
+            fs::read_link(&path).map(|target| target.to_string_lossy().as_bytes().to_vec())
+        } else {
+            fs::read(&path)
+        }
+        .map_err(|e| {
+            TsqError::new("IO_ERROR", "failed reading skill file", 2)
+                .with_details(io_error_value(&e))
+        })?;
+        out.insert(relative, content);
+    }
+    Ok(())
+}
+
 #[cfg(unix)]
 fn copy_symlink(source_path: &Path, destination_path: &Path) -> Result<(), TsqError> {
     let target = fs::read_link(source_path).map_err(|e| {
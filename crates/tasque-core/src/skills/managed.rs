@@ -0,0 +1,59 @@
+use std::cmp::Ordering;
+
+pub const MANAGED_MARKER: &str = "tsq-managed-skill:v1";
+
+/// The `version:` key from SKILL.md's YAML frontmatter, top-level or nested
+/// under `metadata:`. `None` for copies installed before skills were versioned.
+pub fn skill_version(skill_md: &str) -> Option<String> {
+    let mut lines = skill_md.lines();
+    if lines.next()?.trim_end() != "---" {
+        return None;
+    }
+    for line in lines {
+        let line = line.trim();
+        if line == "---" {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("version:") {
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            return (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+    None
+}
+
+/// Compares dotted versions numerically segment by segment (`1.10.0` > `1.9.2`);
+/// a missing segment counts as `0` and a non-numeric one falls back to text order.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |value: &str| -> Vec<String> {
+        value
+            .trim_start_matches('v')
+            .split('.')
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (split(a), split(b));
+    for index in 0..a.len().max(b.len()) {
+        let left = a.get(index).map(String::as_str).unwrap_or("0");
+        let right = b.get(index).map(String::as_str).unwrap_or("0");
+        let order = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(left), Ok(right)) => left.cmp(&right),
+            _ => left.cmp(right),
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Whether a managed install should be upgraded: the source is versioned and
+/// the install is older (or unversioned). Without a source version only a
+/// content difference counts.
+pub fn needs_upgrade(installed: Option<&str>, source: Option<&str>, content_differs: bool) -> bool {
+    match (installed, source) {
+        (Some(installed), Some(source)) => compare_versions(installed, source) == Ordering::Less,
+        (None, Some(_)) => true,
+        (_, None) => content_differs,
+    }
+}
@@ -0,0 +1,908 @@
+pub mod embedded;
+pub mod helpers;
+pub mod managed;
+pub mod remote;
+pub mod types;
+
+use crate::errors::TsqError;
+use crate::skills::embedded::materialize_embedded_skill;
+use crate::skills::helpers::{
+    PathKind, collect_skill_files, copy_directory_recursive, inspect_path, io_error_value,
+    normalize_directory,
+};
+use crate::skills::managed::{MANAGED_MARKER, needs_upgrade, skill_version};
+use crate::skills::types::{
+    SkillAction, SkillDiffEntry, SkillDriftStatus, SkillFileChange, SkillFileDiff, SkillListEntry,
+    SkillOperationOptions, SkillOperationResult, SkillOperationSummary, SkillResultStatus,
+    SkillStatusEntry, SkillTarget,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use ulid::Ulid;
+
+pub fn apply_skill_operation(
+    options: SkillOperationOptions,
+) -> Result<SkillOperationSummary, TsqError> {
+    let target_directories = resolve_target_directories(&options)?;
+    let needs_source = matches!(options.action, SkillAction::Install | SkillAction::Refresh);
+    let mut embedded_temp_root: Option<PathBuf> = None;
+    let mut source_checksum = None;
+    let skill_source_directory = if needs_source {
+        let resolved = resolve_skill_source(&options)?;
+        embedded_temp_root = resolved.temp_root;
+        source_checksum = Some(resolved.checksum);
+        Some(resolved.directory)
+    } else {
+        None
+    };
+    let source_version = match skill_source_directory.as_deref() {
+        Some(source) => read_skill_version(source)?,
+        None => None,
+    };
+
+    let mut results = Vec::new();
+    for target in &options.targets {
+        let target_directory = target_directories
+            .get(target)
+            .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "missing target directory", 2))?;
+        let skill_directory = target_directory.join(&options.skill_name);
+        match options.action {
+            SkillAction::Install => {
+                let source = skill_source_directory.as_ref().ok_or_else(|| {
+                    TsqError::new("INTERNAL_ERROR", "missing managed skill source", 2)
+                })?;
+                results.push(install_skill(
+                    *target,
+                    &options.skill_name,
+                    source,
+                    &skill_directory,
+                    options.force,
+                    options.dry_run,
+                )?);
+            }
+            SkillAction::Uninstall => {
+                results.push(uninstall_skill(
+                    *target,
+                    &skill_directory,
+                    options.force,
+                    options.dry_run,
+                )?);
+            }
+            SkillAction::Refresh => {
+                let source = skill_source_directory.as_ref().ok_or_else(|| {
+                    TsqError::new("INTERNAL_ERROR", "missing managed skill source", 2)
+                })?;
+                results.push(refresh_skill(
+                    *target,
+                    source,
+                    &skill_directory,
+                    options.dry_run,
+                    options.only_outdated,
+                    source_version.as_deref(),
+                )?);
+            }
+        }
+    }
+
+    if let Some(temp_root) = embedded_temp_root {
+        let _ = fs::remove_dir_all(temp_root);
+    }
+    for result in &mut results {
+        if matches!(
+            result.status,
+            SkillResultStatus::Installed | SkillResultStatus::Updated
+        ) {
+            result.version = source_version.clone();
+        }
+    }
+
+    Ok(SkillOperationSummary {
+        action: options.action,
+        skill_name: options.skill_name,
+        results,
+        dry_run: options.dry_run,
+        source: options.source,
+        source_checksum,
+    })
+}
+
+/// Every target's skill path and whether a (managed) skill is installed there.
+pub fn list_skills(options: &SkillOperationOptions) -> Result<Vec<SkillListEntry>, TsqError> {
+    let target_directories = resolve_target_directories(options)?;
+    let mut entries = Vec::new();
+    for target in &options.targets {
+        let skill_directory = target_directories
+            .get(target)
+            .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "missing target directory", 2))?
+            .join(&options.skill_name);
+        let installed = inspect_path(&skill_directory)? != PathKind::Missing;
+        entries.push(SkillListEntry {
+            target: *target,
+            path: skill_directory.display().to_string(),
+            installed,
+            managed: installed && is_managed_skill(&skill_directory)?,
+            version: if installed {
+                read_skill_version(&skill_directory)?
+            } else {
+                None
+            },
+        });
+    }
+    Ok(entries)
+}
+
+/// Compares each installed copy file by file against the bundled source
+/// (disk `SKILLS/` first, then the copy embedded in the binary), and its
+/// SKILL.md version against the source's.
+pub fn skill_status(options: &SkillOperationOptions) -> Result<Vec<SkillStatusEntry>, TsqError> {
+    let target_directories = resolve_target_directories(options)?;
+    let ResolvedSkillSource {
+        directory: source,
+        temp_root,
+        ..
+    } = resolve_skill_source(options)?;
+    let result = (|| {
+        let mut source_files = BTreeMap::new();
+        collect_skill_files(&source, &source, &mut source_files)?;
+        let source_version = read_skill_version(&source)?;
+        let mut entries = Vec::new();
+        for target in &options.targets {
+            let skill_directory = target_directories
+                .get(target)
+                .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "missing target directory", 2))?
+                .join(&options.skill_name);
+            let mut entry = SkillStatusEntry {
+                target: *target,
+                path: skill_directory.display().to_string(),
+                status: SkillDriftStatus::Unmanaged,
+                changes: Vec::new(),
+                installed_version: None,
+                source_version: source_version.clone(),
+                upgrade_available: false,
+            };
+            match inspect_path(&skill_directory)? {
+                PathKind::Missing => entry.status = SkillDriftStatus::NotInstalled,
+                PathKind::Directory if is_managed_skill(&skill_directory)? => {
+                    let mut installed = BTreeMap::new();
+                    collect_skill_files(&skill_directory, &skill_directory, &mut installed)?;
+                    entry.changes = diff_skill_files(&source_files, &installed);
+                    let changed = !entry.changes.is_empty();
+                    entry.status = if changed {
+                        SkillDriftStatus::Outdated
+                    } else {
+                        SkillDriftStatus::Current
+                    };
+                    entry.installed_version = read_skill_version(&skill_directory)?;
+                    entry.upgrade_available = changed
+                        && needs_upgrade(
+                            entry.installed_version.as_deref(),
+                            source_version.as_deref(),
+                            changed,
+                        );
+                }
+                _ => entry.installed_version = read_skill_version(&skill_directory)?,
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    })();
+    if let Some(temp_root) = temp_root {
+        let _ = fs::remove_dir_all(temp_root);
+    }
+    result
+}
+
+/// Per-target content diff between whatever is installed under the skill
+/// name (managed or not) and the managed source for that name.
+pub fn skill_diff(options: &SkillOperationOptions) -> Result<Vec<SkillDiffEntry>, TsqError> {
+    let target_directories = resolve_target_directories(options)?;
+    let ResolvedSkillSource {
+        directory: source,
+        temp_root,
+        ..
+    } = resolve_skill_source(options)?;
+    let result = (|| {
+        let mut source_files = BTreeMap::new();
+        collect_skill_files(&source, &source, &mut source_files)?;
+        let mut entries = Vec::new();
+        for target in &options.targets {
+            let skill_directory = target_directories
+                .get(target)
+                .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "missing target directory", 2))?
+                .join(&options.skill_name);
+            let path_kind = inspect_path(&skill_directory)?;
+            let mut entry = SkillDiffEntry {
+                target: *target,
+                path: skill_directory.display().to_string(),
+                installed: path_kind != PathKind::Missing,
+                managed: false,
+                changes: Vec::new(),
+                patch: String::new(),
+            };
+            if path_kind == PathKind::Directory {
+                entry.managed = is_managed_skill(&skill_directory)?;
+                let mut installed = BTreeMap::new();
+                collect_skill_files(&skill_directory, &skill_directory, &mut installed)?;
+                entry.changes = diff_skill_files(&source_files, &installed);
+                entry.patch = skill_patch(*target, &entry.changes, &source_files, &installed);
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    })();
+    if let Some(temp_root) = temp_root {
+        let _ = fs::remove_dir_all(temp_root);
+    }
+    result
+}
+
+fn skill_patch(
+    target: SkillTarget,
+    changes: &[SkillFileDiff],
+    source: &BTreeMap<String, Vec<u8>>,
+    installed: &BTreeMap<String, Vec<u8>>,
+) -> String {
+    let mut patch = String::new();
+    for change in changes {
+        let text = |files: &BTreeMap<String, Vec<u8>>| match files.get(&change.path) {
+            Some(bytes) => std::str::from_utf8(bytes).map(str::to_string).ok(),
+            None => Some(String::new()),
+        };
+        let (Some(before), Some(after)) = (text(installed), text(source)) else {
+            continue;
+        };
+        if before == after {
+            continue;
+        }
+        patch.push_str(
+            &diffy::DiffOptions::new()
+                .set_original_filename(format!("a/{}/{}", target, change.path))
+                .set_modified_filename(format!("b/{}/{}", target, change.path))
+                .create_patch(&before, &after)
+                .to_string(),
+        );
+    }
+    patch
+}
+
+fn diff_skill_files(
+    source: &BTreeMap<String, Vec<u8>>,
+    installed: &BTreeMap<String, Vec<u8>>,
+) -> Vec<SkillFileDiff> {
+    let mut changes = Vec::new();
+    for (path, content) in source {
+        match installed.get(path) {
+            None => changes.push(SkillFileDiff {
+                path: path.clone(),
+                change: SkillFileChange::Added,
+            }),
+            Some(existing) if existing != content => changes.push(SkillFileDiff {
+                path: path.clone(),
+                change: SkillFileChange::Modified,
+            }),
+            Some(_) => {}
+        }
+    }
+    for path in installed.keys() {
+        if !source.contains_key(path) {
+            changes.push(SkillFileDiff {
+                path: path.clone(),
+                change: SkillFileChange::Removed,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// A managed skill source ready to copy from.
+struct ResolvedSkillSource {
+    directory: PathBuf,
+    /// Set when the source was materialized from the embedded copy; delete afterwards.
+    temp_root: Option<PathBuf>,
+    checksum: String,
+}
+
+/// An explicit `--source` (local root, git URL, or registry) is used as is;
+/// otherwise the local search roots, then the embedded copy. The result must
+/// match `--checksum` or the registry's pinned checksum when either is set.
+fn resolve_skill_source(options: &SkillOperationOptions) -> Result<ResolvedSkillSource, TsqError> {
+    let expected = options
+        .checksum
+        .as_deref()
+        .map(remote::parse_checksum)
+        .transpose()?;
+    let (directory, temp_root, pinned) = match options.source.as_deref() {
+        Some(raw) => {
+            let parsed = remote::parse_source(raw, &options.skill_name, &options.registries)?;
+            let directory = remote::locate_skill(&parsed.source, &options.skill_name)?;
+            (directory, None, parsed.pinned_checksum)
+        }
+        None => {
+            let (directory, temp_root) = resolve_default_skill_source(
+                &options.skill_name,
+                options.source_root_dir.as_deref(),
+            )?;
+            (directory, temp_root, None)
+        }
+    };
+    let checked = (|| {
+        let checksum = remote::skill_checksum(&directory)?;
+        if let Some(expected) = expected.as_deref().or(pinned.as_deref()) {
+            let source = options
+                .source
+                .as_deref()
+                .unwrap_or(options.skill_name.as_str());
+            remote::verify_checksum(expected, &checksum, source)?;
+        }
+        Ok(checksum)
+    })();
+    match checked {
+        Ok(checksum) => Ok(ResolvedSkillSource {
+            directory,
+            temp_root,
+            checksum,
+        }),
+        Err(error) => {
+            if let Some(temp_root) = temp_root {
+                let _ = fs::remove_dir_all(temp_root);
+            }
+            Err(error)
+        }
+    }
+}
+
+/// The managed skill source directory, plus the temp root to delete afterwards
+/// when it had to be materialized from the embedded copy.
+fn resolve_default_skill_source(
+    skill_name: &str,
+    source_root_dir: Option<&str>,
+) -> Result<(PathBuf, Option<PathBuf>), TsqError> {
+    match resolve_managed_skill_source_directory(skill_name, source_root_dir) {
+        Ok(path) => Ok((path, None)),
+        Err(error) => {
+            if error.code != "VALIDATION_ERROR" {
+                return Err(error);
+            }
+            let searched_details = error.details.clone();
+            match materialize_embedded_skill(skill_name) {
+                Ok(materialized) => Ok((materialized.skill_root, Some(materialized.temp_root))),
+                Err(embed_error) => match searched_details {
+                    Some(details) => Err(embed_error.with_details(details)),
+                    None => Err(embed_error),
+                },
+            }
+        }
+    }
+}
+
+fn resolve_target_directories(
+    options: &SkillOperationOptions,
+) -> Result<HashMap<SkillTarget, PathBuf>, TsqError> {
+    let default_home =
+        dirs::home_dir().ok_or_else(|| TsqError::new("IO_ERROR", "home directory not found", 2))?;
+    let resolved_home = normalize_directory(
+        options
+            .home_dir
+            .as_deref()
+            .map(PathBuf::from)
+            .unwrap_or(default_home.clone()),
+        &default_home,
+    )?;
+
+    let raw_codex_home = options
+        .codex_home
+        .as_deref()
+        .map(PathBuf::from)
+        .or_else(|| env::var("CODEX_HOME").ok().map(PathBuf::from))
+        .unwrap_or_else(|| resolved_home.join(".codex"));
+    let resolved_codex_home = normalize_directory(raw_codex_home, &resolved_home)?;
+
+    let defaults: HashMap<SkillTarget, PathBuf> = HashMap::from([
+        (
+            SkillTarget::Claude,
+            resolved_home.join(".claude").join("skills"),
+        ),
+        (SkillTarget::Codex, resolved_codex_home.join("skills")),
+        (
+            SkillTarget::Copilot,
+            resolved_home.join(".copilot").join("skills"),
+        ),
+        (
+            SkillTarget::Opencode,
+            resolved_home.join(".opencode").join("skills"),
+        ),
+        (
+            SkillTarget::Cursor,
+            resolved_home.join(".cursor").join("skills"),
+        ),
+        (
+            SkillTarget::Windsurf,
+            resolved_home.join(".windsurf").join("skills"),
+        ),
+        (
+            SkillTarget::Gemini,
+            resolved_home.join(".gemini").join("skills"),
+        ),
+    ]);
+
+    let mut result = HashMap::new();
+    for target in [
+        SkillTarget::Claude,
+        SkillTarget::Codex,
+        SkillTarget::Copilot,
+        SkillTarget::Opencode,
+        SkillTarget::Cursor,
+        SkillTarget::Windsurf,
+        SkillTarget::Gemini,
+    ] {
+        let override_dir = options
+            .target_dir_overrides
+            .as_ref()
+            .and_then(|map| map.get(&target))
+            .map(PathBuf::from);
+        let directory = override_dir.unwrap_or_else(|| defaults[&target].clone());
+        result.insert(target, normalize_directory(directory, &resolved_home)?);
+    }
+
+    Ok(result)
+}
+
+fn skill_result(
+    target: SkillTarget,
+    path: &Path,
+    status: SkillResultStatus,
+    message: &str,
+) -> SkillOperationResult {
+    SkillOperationResult {
+        target,
+        path: path.display().to_string(),
+        status,
+        message: Some(message.to_string()),
+        changes: Vec::new(),
+        version: None,
+    }
+}
+
+fn planned_result(
+    target: SkillTarget,
+    path: &Path,
+    status: SkillResultStatus,
+    message: &str,
+    changes: Vec<SkillFileDiff>,
+) -> Result<SkillOperationResult, TsqError> {
+    let mut result = skill_result(target, path, status, message);
+    result.changes = changes;
+    Ok(result)
+}
+
+/// Per-file changes turning `installed` into `source`; either side may be
+/// absent (fresh install or removal).
+fn file_changes(
+    source: Option<&Path>,
+    installed: Option<&Path>,
+) -> Result<Vec<SkillFileDiff>, TsqError> {
+    let mut source_files = BTreeMap::new();
+    if let Some(source) = source {
+        collect_skill_files(source, source, &mut source_files)?;
+    }
+    let mut installed_files = BTreeMap::new();
+    if let Some(installed) = installed {
+        collect_skill_files(installed, installed, &mut installed_files)?;
+    }
+    Ok(diff_skill_files(&source_files, &installed_files))
+}
+
+fn install_skill(
+    target: SkillTarget,
+    _skill_name: &str,
+    skill_source_directory: &Path,
+    skill_directory: &Path,
+    force: bool,
+    dry_run: bool,
+) -> Result<SkillOperationResult, TsqError> {
+    let path_kind = inspect_path(skill_directory)?;
+    if path_kind == PathKind::Missing {
+        if dry_run {
+            return planned_result(
+                target,
+                skill_directory,
+                SkillResultStatus::Installed,
+                "would install new managed skill",
+                file_changes(Some(skill_source_directory), None)?,
+            );
+        }
+        copy_directory_recursive(skill_source_directory, skill_directory)?;
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Installed,
+            "installed new managed skill",
+        ));
+    }
+
+    if matches!(path_kind, PathKind::File | PathKind::Symlink) {
+        if !force {
+            return Ok(skill_result(
+                target,
+                skill_directory,
+                SkillResultStatus::Skipped,
+                "path exists as a non-directory and force is disabled",
+            ));
+        }
+        if dry_run {
+            return planned_result(
+                target,
+                skill_directory,
+                SkillResultStatus::Updated,
+                "would replace non-directory path with managed skill due to force",
+                file_changes(Some(skill_source_directory), None)?,
+            );
+        }
+        fs::remove_file(skill_directory).map_err(|e| {
+            TsqError::new("IO_ERROR", "failed removing existing skill path", 2)
+                .with_details(io_error_value(&e))
+        })?;
+        copy_directory_recursive(skill_source_directory, skill_directory)?;
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Updated,
+            "replaced non-directory path with managed skill due to force",
+        ));
+    }
+
+    let managed = is_managed_skill(skill_directory)?;
+    if !managed && !force {
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Skipped,
+            "existing skill is not managed and force is disabled",
+        ));
+    }
+
+    if dry_run {
+        return planned_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Updated,
+            if managed {
+                "would update managed skill"
+            } else {
+                "would overwrite non-managed skill due to force"
+            },
+            file_changes(Some(skill_source_directory), Some(skill_directory))?,
+        );
+    }
+    fs::remove_dir_all(skill_directory).map_err(|e| {
+        TsqError::new("IO_ERROR", "failed removing existing skill directory", 2)
+            .with_details(io_error_value(&e))
+    })?;
+    copy_directory_recursive(skill_source_directory, skill_directory)?;
+    Ok(skill_result(
+        target,
+        skill_directory,
+        SkillResultStatus::Updated,
+        if managed {
+            "updated managed skill"
+        } else {
+            "overwrote non-managed skill due to force"
+        },
+    ))
+}
+
+fn uninstall_skill(
+    target: SkillTarget,
+    skill_directory: &Path,
+    force: bool,
+    dry_run: bool,
+) -> Result<SkillOperationResult, TsqError> {
+    let path_kind = inspect_path(skill_directory)?;
+    if path_kind == PathKind::Missing {
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::NotFound,
+            "skill directory not found",
+        ));
+    }
+
+    if matches!(path_kind, PathKind::File | PathKind::Symlink) {
+        if !force {
+            return Ok(skill_result(
+                target,
+                skill_directory,
+                SkillResultStatus::Skipped,
+                "path exists as a non-directory and force is disabled",
+            ));
+        }
+        if dry_run {
+            return planned_result(
+                target,
+                skill_directory,
+                SkillResultStatus::Removed,
+                "would remove non-directory path due to force",
+                Vec::new(),
+            );
+        }
+        fs::remove_file(skill_directory).map_err(|e| {
+            TsqError::new("IO_ERROR", "failed removing non-directory skill path", 2)
+                .with_details(io_error_value(&e))
+        })?;
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Removed,
+            "removed non-directory path due to force",
+        ));
+    }
+
+    let managed = is_managed_skill(skill_directory)?;
+    if !managed && !force {
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Skipped,
+            "existing skill is not managed and force is disabled",
+        ));
+    }
+
+    if dry_run {
+        return planned_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Removed,
+            if managed {
+                "would remove managed skill"
+            } else {
+                "would remove non-managed skill due to force"
+            },
+            file_changes(None, Some(skill_directory))?,
+        );
+    }
+    fs::remove_dir_all(skill_directory).map_err(|e| {
+        TsqError::new("IO_ERROR", "failed removing skill directory", 2)
+            .with_details(io_error_value(&e))
+    })?;
+    Ok(skill_result(
+        target,
+        skill_directory,
+        SkillResultStatus::Removed,
+        if managed {
+            "removed managed skill"
+        } else {
+            "removed non-managed skill due to force"
+        },
+    ))
+}
+
+/// With `only_outdated`, managed installs that already match the source or
+/// are not behind `source_version` are skipped instead of rewritten.
+fn refresh_skill(
+    target: SkillTarget,
+    skill_source_directory: &Path,
+    skill_directory: &Path,
+    dry_run: bool,
+    only_outdated: bool,
+    source_version: Option<&str>,
+) -> Result<SkillOperationResult, TsqError> {
+    let path_kind = inspect_path(skill_directory)?;
+
+    if path_kind == PathKind::Missing {
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::NotFound,
+            "skill directory not found",
+        ));
+    }
+    if matches!(path_kind, PathKind::File | PathKind::Symlink) {
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Skipped,
+            "target is a file, not a managed skill directory",
+        ));
+    }
+
+    let managed = is_managed_skill(skill_directory)?;
+    if !managed {
+        return Ok(skill_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Skipped,
+            "target is not a managed skill",
+        ));
+    }
+    if only_outdated {
+        let changed =
+            !file_changes(Some(skill_source_directory), Some(skill_directory))?.is_empty();
+        let installed_version = read_skill_version(skill_directory)?;
+        if !(changed && needs_upgrade(installed_version.as_deref(), source_version, changed)) {
+            return Ok(skill_result(
+                target,
+                skill_directory,
+                SkillResultStatus::Skipped,
+                "managed skill is up to date",
+            ));
+        }
+    }
+    if dry_run {
+        let changes = file_changes(Some(skill_source_directory), Some(skill_directory))?;
+        let message = if changes.is_empty() {
+            "managed skill already matches source"
+        } else {
+            "would refresh managed skill"
+        };
+        return planned_result(
+            target,
+            skill_directory,
+            SkillResultStatus::Updated,
+            message,
+            changes,
+        );
+    }
+
+    // Atomic-ish refresh via backup sibling. The individual renames below are
+    // atomic on POSIX filesystems, but the whole sequence is not transactional:
+    // a process crash between steps can require manual cleanup of temp/backup dirs.
+    //   1. Copy source to temp sibling
+    //   2. Rename existing skill dir to backup
+    //   3a. Happy path: rename temp into final location
+    //   3b. Rename failure: clean temp and restore backup before returning error
+    //   4. On success, remove backup
+    let ulid = Ulid::new();
+    let parent = skill_directory
+        .parent()
+        .ok_or_else(|| TsqError::new("INTERNAL_ERROR", "skill directory has no parent", 2))?;
+    let temp_path = parent.join(format!(".tsq-refresh-{}", ulid));
+    let backup_path = parent.join(format!(".tsq-refresh-backup-{}", ulid));
+
+    // Step 1: Copy source to temp. Old dir untouched if this fails.
+    if let Err(e) = copy_directory_recursive(skill_source_directory, &temp_path) {
+        let _ = fs::remove_dir_all(&temp_path);
+        return Err(e);
+    }
+    let existing_permissions = fs::metadata(skill_directory)
+        .map_err(|e| {
+            TsqError::new("IO_ERROR", "failed reading existing skill permissions", 2)
+                .with_details(io_error_value(&e))
+        })?
+        .permissions();
+    if let Err(e) = fs::set_permissions(&temp_path, existing_permissions) {
+        let _ = fs::remove_dir_all(&temp_path);
+        return Err(
+            TsqError::new("IO_ERROR", "failed setting refreshed skill permissions", 2)
+                .with_details(io_error_value(&e)),
+        );
+    }
+
+    // Step 2: Rename existing skill dir to backup.
+    if let Err(e) = fs::rename(skill_directory, &backup_path) {
+        let _ = fs::remove_dir_all(&temp_path);
+        return Err(
+            TsqError::new("IO_ERROR", "failed backing up existing skill directory", 2)
+                .with_details(io_error_value(&e)),
+        );
+    }
+
+    // Step 3: Rename temp into final location.
+    if let Err(e) = fs::rename(&temp_path, skill_directory) {
+        // Step 3 failed — restore backup to original location before returning error.
+        let cleanup_error = fs::remove_dir_all(&temp_path).err();
+        let restore_error = fs::rename(&backup_path, skill_directory).err();
+        return Err(
+            TsqError::new("IO_ERROR", "failed replacing managed skill directory", 2).with_details(
+                serde_json::json!({
+                    "backup_path": backup_path.display().to_string(),
+                    "skill_directory": skill_directory.display().to_string(),
+                    "recovery": "if restore_error is present, manually move backup_path back to skill_directory",
+                    "replace_error": io_error_value(&e),
+                    "cleanup_error": cleanup_error.as_ref().map(io_error_value),
+                    "restore_error": restore_error.as_ref().map(io_error_value),
+                }),
+            ),
+        );
+    }
+
+    // Step 4: Success — remove backup best-effort.
+    let _ = fs::remove_dir_all(&backup_path);
+
+    Ok(skill_result(
+        target,
+        skill_directory,
+        SkillResultStatus::Updated,
+        "refreshed managed skill",
+    ))
+}
+
+fn read_skill_version(skill_directory: &Path) -> Result<Option<String>, TsqError> {
+    match fs::read_to_string(skill_directory.join("SKILL.md")) {
+        Ok(content) => Ok(skill_version(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(TsqError::new("IO_ERROR", "failed reading skill file", 2)
+            .with_details(io_error_value(&e))),
+    }
+}
+
+fn is_managed_skill(skill_directory: &Path) -> Result<bool, TsqError> {
+    file_contains_managed_marker(&skill_directory.join("SKILL.md"))
+}
+
+fn file_contains_managed_marker(file_path: &Path) -> Result<bool, TsqError> {
+    match fs::read_to_string(file_path) {
+        Ok(content) => Ok(content.contains(MANAGED_MARKER)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(TsqError::new("IO_ERROR", "failed reading skill file", 2)
+            .with_details(io_error_value(&e))),
+    }
+}
+
+fn resolve_managed_skill_source_directory(
+    skill_name: &str,
+    source_root_dir: Option<&str>,
+) -> Result<PathBuf, TsqError> {
+    let default_home =
+        dirs::home_dir().ok_or_else(|| TsqError::new("IO_ERROR", "home directory not found", 2))?;
+
+    let mut roots: Vec<PathBuf> = Vec::new();
+
+    if let Some(dir) = source_root_dir {
+        roots.push(normalize_directory(PathBuf::from(dir), &default_home)?);
+    }
+    if let Ok(dir) = env::var("TSQ_SKILLS_DIR") {
+        roots.push(normalize_directory(PathBuf::from(dir), &default_home)?);
+    }
+    if let Ok(cwd) = env::current_dir() {
+        roots.push(cwd.join("SKILLS"));
+    }
+    if let Ok(exe_path) = env::current_exe()
+        && let Some(exe_dir) = exe_path.parent()
+    {
+        roots.push(exe_dir.join("SKILLS"));
+        roots.push(exe_dir.join("..").join("SKILLS"));
+        roots.push(exe_dir.join("..").join("share").join("tsq").join("SKILLS"));
+    }
+
+    let mut unique_roots = Vec::new();
+    let mut seen = HashSet::new();
+    for root in roots {
+        let key = root.to_string_lossy().to_string();
+        if seen.insert(key) {
+            unique_roots.push(root);
+        }
+    }
+
+    for root in &unique_roots {
+        let candidate_directory = root.join(skill_name);
+        if inspect_path(&candidate_directory)? != PathKind::Directory {
+            continue;
+        }
+        if inspect_path(&candidate_directory.join("SKILL.md"))? == PathKind::File {
+            return Ok(candidate_directory);
+        }
+    }
+
+    let searched_roots: Vec<String> = unique_roots
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    Err(TsqError::new(
+        "VALIDATION_ERROR",
+        format!(
+            "skill source not found for '{}' (expected SKILLS/{}/SKILL.md)",
+            skill_name, skill_name
+        ),
+        1,
+    )
+    .with_details(serde_json::json!({
+      "searched_roots": searched_roots,
+      "skill_name": skill_name,
+    })))
+}
+
+#[cfg(test)]
+mod tests;
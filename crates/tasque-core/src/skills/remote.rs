@@ -0,0 +1,333 @@
+use crate::errors::TsqError;
+use crate::skills::helpers::{PathKind, collect_skill_files, inspect_path, normalize_directory};
+use crate::store::git;
+use crate::types::SkillRegistry;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// Where `--source` points: a local skills root, or a git repository
+/// (optionally narrowed to a subdirectory with `#path`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum SkillSource {
+    Local(PathBuf),
+    Git {
+        url: String,
+        subpath: Option<String>,
+    },
+}
+
+/// A `--source` value resolved against the configured registries, plus the
+/// checksum a registry pins for the skill, if any.
+#[derive(Debug, Clone)]
+pub(super) struct ParsedSource {
+    pub source: SkillSource,
+    pub pinned_checksum: Option<String>,
+}
+
+pub fn is_skill_checksum(value: &str) -> bool {
+    value.strip_prefix(CHECKSUM_PREFIX).is_some_and(|hex| {
+        hex.len() == 64
+            && hex
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    })
+}
+
+pub(super) fn parse_checksum(raw: &str) -> Result<String, TsqError> {
+    let value = raw.trim().to_ascii_lowercase();
+    if !is_skill_checksum(&value) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!(
+                "invalid skill checksum '{}' (expected sha256:<64 hex>)",
+                raw
+            ),
+            1,
+        ));
+    }
+    Ok(value)
+}
+
+pub(super) fn parse_source(
+    raw: &str,
+    skill_name: &str,
+    registries: &BTreeMap<String, SkillRegistry>,
+) -> Result<ParsedSource, TsqError> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.starts_with('-') {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!("invalid skill source '{}'", raw),
+            1,
+        ));
+    }
+    if let Some(name) = raw.strip_prefix("registry:") {
+        let registry = registries.get(name).ok_or_else(|| {
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!("unknown skill registry '{}'", name),
+                1,
+            )
+            .with_details(serde_json::json!({
+                "registries": registries.keys().collect::<Vec<_>>(),
+            }))
+        })?;
+        let pinned_checksum = registry
+            .checksums
+            .get(skill_name)
+            .map(|sum| parse_checksum(sum))
+            .transpose()?;
+        return Ok(ParsedSource {
+            source: parse_git_source(&registry.url)?,
+            pinned_checksum,
+        });
+    }
+    let source = if is_git_url(raw) {
+        parse_git_source(raw)?
+    } else {
+        let home = dirs::home_dir()
+            .ok_or_else(|| TsqError::new("IO_ERROR", "home directory not found", 2))?;
+        SkillSource::Local(normalize_directory(PathBuf::from(raw), &home)?)
+    };
+    Ok(ParsedSource {
+        source,
+        pinned_checksum: None,
+    })
+}
+
+fn is_git_url(raw: &str) -> bool {
+    let location = raw.split('#').next().unwrap_or(raw);
+    ["https://", "http://", "ssh://", "git://", "file://", "git@"]
+        .iter()
+        .any(|prefix| location.starts_with(prefix))
+        || location.ends_with(".git")
+}
+
+fn parse_git_source(raw: &str) -> Result<SkillSource, TsqError> {
+    let (url, subpath) = match raw.split_once('#') {
+        Some((url, path)) => (url.trim(), Some(path.trim().trim_matches('/'))),
+        None => (raw.trim(), None),
+    };
+    if url.is_empty() || url.starts_with('-') {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!("invalid skill source url '{}'", raw),
+            1,
+        ));
+    }
+    let subpath = subpath.filter(|path| !path.is_empty());
+    if let Some(path) = subpath
+        && Path::new(path)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!(
+                "skill source path '{}' must be relative and stay inside the repository",
+                path
+            ),
+            1,
+        ));
+    }
+    Ok(SkillSource::Git {
+        url: url.to_string(),
+        subpath: subpath.map(str::to_string),
+    })
+}
+
+/// The skill directory for `skill_name` under a source root: either
+/// `<root>/<skill_name>/SKILL.md` or a root that is itself the skill.
+pub(super) fn locate_skill(source: &SkillSource, skill_name: &str) -> Result<PathBuf, TsqError> {
+    let root = match source {
+        SkillSource::Local(path) => path.clone(),
+        SkillSource::Git { url, subpath } => {
+            let checkout = fetch_git_source(url)?;
+            match subpath {
+                Some(path) => checkout.join(path),
+                None => checkout,
+            }
+        }
+    };
+    for candidate in [root.join(skill_name), root.clone()] {
+        if inspect_path(&candidate.join("SKILL.md"))? == PathKind::File {
+            return Ok(candidate);
+        }
+    }
+    Err(TsqError::new(
+        "VALIDATION_ERROR",
+        format!(
+            "skill source not found for '{}' (expected {}/SKILL.md)",
+            skill_name, skill_name
+        ),
+        1,
+    )
+    .with_details(serde_json::json!({
+        "searched_roots": [root.display().to_string()],
+        "skill_name": skill_name,
+    })))
+}
+
+/// Clones `url` into the skill cache on first use and fast-forwards the cached
+/// clone to the remote's default branch afterwards.
+fn fetch_git_source(url: &str) -> Result<PathBuf, TsqError> {
+    let cache_root = cache_root()?;
+    fs::create_dir_all(&cache_root).map_err(|e| {
+        TsqError::new("IO_ERROR", "failed creating skill cache directory", 2)
+            .with_details(crate::skills::helpers::io_error_value(&e))
+    })?;
+    let checkout = cache_root.join(&sha256_hex(url.as_bytes())[..16]);
+    if checkout.join(".git").exists() {
+        git::update_shallow_clone(&checkout)?;
+    } else {
+        if checkout.exists() {
+            let _ = fs::remove_dir_all(&checkout);
+        }
+        git::clone_shallow(url, &checkout)?;
+    }
+    Ok(checkout)
+}
+
+/// `TSQ_SKILL_CACHE_DIR`, else the platform cache dir under `tsq/skills`.
+fn cache_root() -> Result<PathBuf, TsqError> {
+    if let Ok(dir) = env::var("TSQ_SKILL_CACHE_DIR")
+        && !dir.trim().is_empty()
+    {
+        let home = dirs::home_dir()
+            .ok_or_else(|| TsqError::new("IO_ERROR", "home directory not found", 2))?;
+        return normalize_directory(PathBuf::from(dir), &home);
+    }
+    dirs::cache_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cache")))
+        .map(|dir| dir.join("tsq").join("skills"))
+        .ok_or_else(|| TsqError::new("IO_ERROR", "cache directory not found", 2))
+}
+
+/// Content checksum of a skill directory: every file's relative path and
+/// bytes, in path order, so the same tree always hashes the same.
+pub(super) fn skill_checksum(directory: &Path) -> Result<String, TsqError> {
+    let mut files = BTreeMap::new();
+    collect_skill_files(directory, directory, &mut files)?;
+    let mut hasher = Sha256::new();
+    for (path, content) in &files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(content);
+    }
+    Ok(format!("{}{:x}", CHECKSUM_PREFIX, hasher.finalize()))
+}
+
+pub(super) fn verify_checksum(expected: &str, actual: &str, source: &str) -> Result<(), TsqError> {
+    if expected == actual {
+        return Ok(());
+    }
+    Err(TsqError::new(
+        "SKILL_CHECKSUM_MISMATCH",
+        "skill source does not match the pinned checksum",
+        2,
+    )
+    .with_details(serde_json::json!({
+        "source": source,
+        "expected": expected,
+        "actual": actual,
+    })))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_source_distinguishes_git_local_and_registry() {
+        let mut registries = BTreeMap::new();
+        registries.insert(
+            "team".to_string(),
+            SkillRegistry {
+                url: "https://example.com/skills.git#agents".to_string(),
+                checksums: BTreeMap::from([(
+                    "tasque".to_string(),
+                    format!("sha256:{}", "A".repeat(64)),
+                )]),
+            },
+        );
+
+        let parsed = parse_source(
+            "https://github.com/org/skills.git#skills/tasque",
+            "tasque",
+            &registries,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.source,
+            SkillSource::Git {
+                url: "https://github.com/org/skills.git".to_string(),
+                subpath: Some("skills/tasque".to_string()),
+            }
+        );
+
+        let parsed = parse_source("registry:team", "tasque", &registries).unwrap();
+        assert_eq!(
+            parsed.source,
+            SkillSource::Git {
+                url: "https://example.com/skills.git".to_string(),
+                subpath: Some("agents".to_string()),
+            }
+        );
+        assert_eq!(
+            parsed.pinned_checksum,
+            Some(format!("sha256:{}", "a".repeat(64)))
+        );
+
+        let parsed = parse_source("/opt/skills", "tasque", &registries).unwrap();
+        assert_eq!(
+            parsed.source,
+            SkillSource::Local(PathBuf::from("/opt/skills"))
+        );
+
+        for bad in [
+            "registry:missing",
+            "--upload-pack=x",
+            "https://example.com/s.git#../escape",
+        ] {
+            assert_eq!(
+                parse_source(bad, "tasque", &registries).unwrap_err().code,
+                "VALIDATION_ERROR",
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn checksum_tracks_paths_and_contents() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("skill");
+        fs::create_dir_all(dir.join("refs")).unwrap();
+        fs::write(dir.join("SKILL.md"), "# Skill\n").unwrap();
+        fs::write(dir.join("refs").join("a.md"), "a\n").unwrap();
+
+        let first = skill_checksum(&dir).unwrap();
+        assert!(is_skill_checksum(&first), "{}", first);
+        assert_eq!(skill_checksum(&dir).unwrap(), first);
+
+        fs::write(dir.join("refs").join("a.md"), "b\n").unwrap();
+        let second = skill_checksum(&dir).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            verify_checksum(&first, &second, "src").unwrap_err().code,
+            "SKILL_CHECKSUM_MISMATCH"
+        );
+    }
+}
@@ -40,6 +40,11 @@ fn refresh_options<'a>(target_dir: &'a str, source_dir: &'a str) -> SkillOperati
         ),
         codex_home: None,
         target_dir_overrides: Some(overrides),
+        dry_run: false,
+        source: None,
+        checksum: None,
+        registries: Default::default(),
+        only_outdated: false,
     }
 }
 
@@ -189,6 +194,11 @@ fn refresh_embedded_fallback_updates_managed_target() {
         ),
         codex_home: None,
         target_dir_overrides: Some(overrides),
+        dry_run: false,
+        source: None,
+        checksum: None,
+        registries: Default::default(),
+        only_outdated: false,
     };
 
     let result = apply_skill_operation(opts).unwrap();
@@ -260,3 +270,147 @@ fn skill_action_refresh_serialization_roundtrip() {
     let deserialized: SkillAction = serde_json::from_str("\"refresh\"").unwrap();
     assert_eq!(deserialized, SkillAction::Refresh);
 }
+
+#[test]
+fn install_dry_run_reports_files_without_writing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source = tmp.path().join("source").join("test-skill");
+    make_source(&source, &source_with_nested());
+    let targets = tmp.path().join("targets");
+
+    let mut opts = refresh_options(
+        &targets.display().to_string(),
+        &tmp.path().join("source").display().to_string(),
+    );
+    opts.action = SkillAction::Install;
+    opts.dry_run = true;
+    let result = apply_skill_operation(opts).unwrap();
+
+    assert!(result.dry_run);
+    assert_eq!(result.results[0].status, SkillResultStatus::Installed);
+    let paths: Vec<&str> = result.results[0]
+        .changes
+        .iter()
+        .map(|c| c.path.as_str())
+        .collect();
+    assert_eq!(paths, vec!["SKILL.md", "sub/deep.txt"]);
+    assert!(!targets.exists());
+}
+
+#[test]
+fn refresh_and_uninstall_dry_run_leave_install_untouched() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source = tmp.path().join("source").join("test-skill");
+    make_source(&source, &source_with_nested());
+    let targets = tmp.path().join("targets");
+    let skill_dir = targets.join("test-skill");
+    make_source(
+        &skill_dir,
+        &[
+            ("SKILL.md", "# Old\ntsq-managed-skill:v1\n"),
+            ("stale.txt", "old"),
+        ],
+    );
+
+    let mut opts = refresh_options(
+        &targets.display().to_string(),
+        &tmp.path().join("source").display().to_string(),
+    );
+    opts.dry_run = true;
+    let result = apply_skill_operation(opts.clone()).unwrap();
+    let changes: Vec<(&str, SkillFileChange)> = result.results[0]
+        .changes
+        .iter()
+        .map(|c| (c.path.as_str(), c.change))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("SKILL.md", SkillFileChange::Modified),
+            ("stale.txt", SkillFileChange::Removed),
+            ("sub/deep.txt", SkillFileChange::Added),
+        ]
+    );
+
+    opts.action = SkillAction::Uninstall;
+    let result = apply_skill_operation(opts).unwrap();
+    assert_eq!(result.results[0].status, SkillResultStatus::Removed);
+    assert_eq!(result.results[0].changes.len(), 2);
+
+    assert_eq!(
+        fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+        "# Old\ntsq-managed-skill:v1\n"
+    );
+    assert!(skill_dir.join("stale.txt").exists());
+}
+
+fn versioned_skill_md(version: &str, body: &str) -> String {
+    format!(
+        "---\nname: test-skill\nmetadata:\n  version: \"{}\"\n---\ntsq-managed-skill:v1\n{}\n",
+        version, body
+    )
+}
+
+#[test]
+fn skill_version_reads_frontmatter_and_compares_numerically() {
+    use crate::skills::managed::{compare_versions, skill_version};
+    use std::cmp::Ordering;
+
+    assert_eq!(
+        skill_version(&versioned_skill_md("1.2.0", "# Skill")),
+        Some("1.2.0".to_string())
+    );
+    assert_eq!(
+        skill_version("---\nversion: 2\n---\n"),
+        Some("2".to_string())
+    );
+    assert_eq!(skill_version("# Skill\nversion: 1.0.0\n"), None);
+    assert_eq!(compare_versions("1.10.0", "1.9.2"), Ordering::Greater);
+    assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+    assert_eq!(compare_versions("v0.9", "1.0"), Ordering::Less);
+}
+
+#[test]
+fn refresh_only_outdated_skips_current_and_newer_installs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source = tmp.path().join("source").join("test-skill");
+    let source_md = versioned_skill_md("1.1.0", "# New");
+    make_source(&source, &[("SKILL.md", source_md.as_str())]);
+    let targets = tmp.path().join("targets");
+    let skill_dir = targets.join("test-skill");
+    let mut opts = refresh_options(
+        &targets.display().to_string(),
+        &tmp.path().join("source").display().to_string(),
+    );
+    opts.only_outdated = true;
+
+    // Same version with local edits is not out of date.
+    let edited = versioned_skill_md("1.1.0", "# Local edit");
+    make_source(&skill_dir, &[("SKILL.md", edited.as_str())]);
+    let result = apply_skill_operation(opts.clone()).unwrap();
+    assert_eq!(result.results[0].status, SkillResultStatus::Skipped);
+    assert_eq!(
+        fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+        edited
+    );
+
+    // An older (or unversioned) install is upgraded and reports the new version.
+    for old in [
+        versioned_skill_md("1.0.3", "# Old"),
+        managed_skill_md()[0].1.to_string(),
+    ] {
+        make_source(&skill_dir, &[("SKILL.md", old.as_str())]);
+        let result = apply_skill_operation(opts.clone()).unwrap();
+        assert_eq!(result.results[0].status, SkillResultStatus::Updated);
+        assert_eq!(result.results[0].version.as_deref(), Some("1.1.0"));
+        assert_eq!(
+            fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+            source_md
+        );
+    }
+
+    let status = skill_status(&opts).unwrap();
+    assert_eq!(status[0].status, SkillDriftStatus::Current);
+    assert_eq!(status[0].installed_version.as_deref(), Some("1.1.0"));
+    assert!(!status[0].upgrade_available);
+}
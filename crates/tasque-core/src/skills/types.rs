@@ -0,0 +1,214 @@
+use crate::types::SkillRegistry;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillTarget {
+    Claude,
+    Codex,
+    Copilot,
+    Opencode,
+    Cursor,
+    Windsurf,
+    Gemini,
+}
+
+impl SkillTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkillTarget::Claude => "claude",
+            SkillTarget::Codex => "codex",
+            SkillTarget::Copilot => "copilot",
+            SkillTarget::Opencode => "opencode",
+            SkillTarget::Cursor => "cursor",
+            SkillTarget::Windsurf => "windsurf",
+            SkillTarget::Gemini => "gemini",
+        }
+    }
+}
+
+impl fmt::Display for SkillTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillAction {
+    Install,
+    Uninstall,
+    Refresh,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillResultStatus {
+    Installed,
+    Updated,
+    Skipped,
+    Removed,
+    NotFound,
+}
+
+impl SkillResultStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkillResultStatus::Installed => "installed",
+            SkillResultStatus::Updated => "updated",
+            SkillResultStatus::Skipped => "skipped",
+            SkillResultStatus::Removed => "removed",
+            SkillResultStatus::NotFound => "not_found",
+        }
+    }
+}
+
+impl fmt::Display for SkillResultStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillOperationOptions {
+    pub action: SkillAction,
+    pub skill_name: String,
+    pub targets: Vec<SkillTarget>,
+    pub force: bool,
+    pub source_root_dir: Option<String>,
+    pub home_dir: Option<String>,
+    pub codex_home: Option<String>,
+    pub target_dir_overrides: Option<HashMap<SkillTarget, String>>,
+    /// Report what would change per target without touching the filesystem.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// `--source`: a local skills root, a git URL (`url#path`), or `registry:<name>`.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// `sha256:` content checksum the resolved source must match.
+    #[serde(default)]
+    pub checksum: Option<String>,
+    #[serde(default)]
+    pub registries: BTreeMap<String, SkillRegistry>,
+    /// Refresh only managed installs whose version is behind the source.
+    #[serde(default)]
+    pub only_outdated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillOperationResult {
+    pub target: SkillTarget,
+    pub path: String,
+    pub status: SkillResultStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Files the operation writes or deletes; only filled in for dry runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<SkillFileDiff>,
+    /// Skill version installed (or, for dry runs, that would be installed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillOperationSummary {
+    pub action: SkillAction,
+    pub skill_name: String,
+    pub results: Vec<SkillOperationResult>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Checksum of the source that was installed; pass it to `--checksum` to pin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_checksum: Option<String>,
+}
+
+/// What sits at a target's skill path, for `tsq skills list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillListEntry {
+    pub target: SkillTarget,
+    pub path: String,
+    pub installed: bool,
+    pub managed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillDriftStatus {
+    /// Managed and identical to the bundled source.
+    Current,
+    /// Managed but differs from the bundled source; `update` reinstalls it.
+    Outdated,
+    /// Present but without the managed marker; never touched by `update`.
+    Unmanaged,
+    NotInstalled,
+}
+
+impl SkillDriftStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkillDriftStatus::Current => "current",
+            SkillDriftStatus::Outdated => "outdated",
+            SkillDriftStatus::Unmanaged => "unmanaged",
+            SkillDriftStatus::NotInstalled => "not_installed",
+        }
+    }
+}
+
+impl fmt::Display for SkillDriftStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillFileChange {
+    /// In the bundled source but missing from the installed copy.
+    Added,
+    /// In the installed copy but not in the bundled source.
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillFileDiff {
+    pub path: String,
+    pub change: SkillFileChange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillStatusEntry {
+    pub target: SkillTarget,
+    pub path: String,
+    pub status: SkillDriftStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<SkillFileDiff>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_version: Option<String>,
+    /// Managed and behind the source version; `tsq skills update --all` upgrades it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub upgrade_available: bool,
+}
+
+/// Content differences between one target's installed copy and the managed source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillDiffEntry {
+    pub target: SkillTarget,
+    pub path: String,
+    pub installed: bool,
+    pub managed: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<SkillFileDiff>,
+    /// Unified diff from the installed copy (`a/`) to the source (`b/`);
+    /// files that are not UTF-8 are listed in `changes` only.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub patch: String,
+}
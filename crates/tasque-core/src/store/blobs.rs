@@ -0,0 +1,188 @@
+//! Overflow storage for oversized note and description bodies.
+//!
+//! A body over the configured `overflow_bytes` is written once to
+//! `.tasque/blobs/<sha256>` and the event keeps a short preview plus a
+//! `<field>_blob` fingerprint. Reading the log puts the full body back, so
+//! the projection, queries, and history never see the difference; the log
+//! itself stays small for replay, git diffs, and sync.
+
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::store::crypto;
+use crate::store::paths::get_paths;
+use crate::types::{EventRecord, PayloadLimitsConfig};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs::{create_dir_all, read_to_string, rename, write};
+use std::path::Path;
+
+/// Payload fields that carry free-form bodies.
+const BODY_FIELDS: [&str; 2] = ["description", "text"];
+const PREVIEW_CHARS: usize = 200;
+
+fn blob_field(field: &str) -> String {
+    format!("{}_blob", field)
+}
+
+pub fn payload_limits(repo_root: impl AsRef<Path>) -> Result<PayloadLimitsConfig, TsqError> {
+    let repo_root = repo_root.as_ref();
+    if !get_paths(repo_root).config_file.exists() {
+        return Ok(PayloadLimitsConfig::default());
+    }
+    Ok(read_config(repo_root)?.payload_limits.unwrap_or_default())
+}
+
+/// Fails with PAYLOAD_TOO_LARGE when any body exceeds `max_bytes`.
+pub fn check_payload_limits(
+    limits: &PayloadLimitsConfig,
+    events: &[EventRecord],
+) -> Result<(), TsqError> {
+    let max_bytes = limits.max_bytes();
+    for event in events {
+        for field in BODY_FIELDS {
+            let Some(Value::String(body)) = event.payload.get(field) else {
+                continue;
+            };
+            if body.len() > max_bytes {
+                return Err(TsqError::new(
+                    "PAYLOAD_TOO_LARGE",
+                    format!(
+                        "{} is {} bytes; the limit is {} (payload_limits.max_bytes)",
+                        field,
+                        body.len(),
+                        max_bytes
+                    ),
+                    1,
+                )
+                .with_details(serde_json::json!({
+                    "task_id": event.task_id,
+                    "event_type": event.event_type,
+                    "field": field,
+                    "bytes": body.len(),
+                    "max_bytes": max_bytes,
+                })));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Moves bodies over `overflow_bytes` into blobs and returns the events as
+/// they should be written to the log.
+pub fn offload_bodies(
+    repo_root: impl AsRef<Path>,
+    limits: &PayloadLimitsConfig,
+    events: &[EventRecord],
+) -> Result<Vec<EventRecord>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let overflow_bytes = limits.overflow_bytes();
+    let mut written = Vec::with_capacity(events.len());
+    for event in events {
+        let mut event = event.clone();
+        for field in BODY_FIELDS {
+            let body = match event.payload.get(field) {
+                Some(Value::String(body)) if body.len() > overflow_bytes => body.clone(),
+                _ => continue,
+            };
+            let fingerprint = write_blob(repo_root, &body)?;
+            event
+                .payload
+                .insert(field.to_string(), Value::String(preview(&body)));
+            event
+                .payload
+                .insert(blob_field(field), Value::String(fingerprint));
+        }
+        written.push(event);
+    }
+    Ok(written)
+}
+
+/// Restores offloaded bodies in place. A missing or damaged blob keeps the
+/// preview and is reported as a warning rather than failing the read.
+pub fn hydrate_bodies(
+    repo_root: impl AsRef<Path>,
+    events: &mut [EventRecord],
+) -> Result<Option<String>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let mut warning = None;
+    for event in events {
+        for field in BODY_FIELDS {
+            let Some(Value::String(fingerprint)) = event.payload.get(&blob_field(field)) else {
+                continue;
+            };
+            match read_blob(repo_root, fingerprint)? {
+                Some(body) => {
+                    event.payload.remove(&blob_field(field));
+                    event.payload.insert(field.to_string(), Value::String(body));
+                }
+                None => {
+                    warning.get_or_insert_with(|| {
+                        format!(
+                            "Blob {} for task {} is missing or damaged; showing its preview",
+                            fingerprint, event.task_id
+                        )
+                    });
+                }
+            }
+        }
+    }
+    Ok(warning)
+}
+
+/// Writes `body` under its sha256 and returns the fingerprint. Identical
+/// bodies share one file.
+pub fn write_blob(repo_root: impl AsRef<Path>, body: &str) -> Result<String, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let fingerprint = sha256_hex(body);
+    let blobs_dir = get_paths(repo_root).blobs_dir;
+    let path = blobs_dir.join(&fingerprint);
+    if path.exists() {
+        return Ok(fingerprint);
+    }
+    let sealed = crypto::seal_for_repo(repo_root, crypto::BLOB, body.to_string())?;
+    let temp = blobs_dir.join(format!("{}.tmp-{}", fingerprint, std::process::id()));
+    create_dir_all(&blobs_dir)
+        .and_then(|_| write(&temp, sealed))
+        .and_then(|_| rename(&temp, &path))
+        .map_err(|error| {
+            TsqError::new("IO_ERROR", "failed writing payload blob", 2).with_details(
+                serde_json::json!({"fingerprint": fingerprint, "message": error.to_string()}),
+            )
+        })?;
+    Ok(fingerprint)
+}
+
+/// The blob's body, or `None` when it is absent or no longer matches its
+/// fingerprint.
+pub fn read_blob(
+    repo_root: impl AsRef<Path>,
+    fingerprint: &str,
+) -> Result<Option<String>, TsqError> {
+    if fingerprint.len() != 64 || !fingerprint.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Ok(None);
+    }
+    let path = get_paths(repo_root).blobs_dir.join(fingerprint);
+    let raw = match read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(
+                TsqError::new("IO_ERROR", "failed reading payload blob", 2).with_details(
+                    serde_json::json!({"fingerprint": fingerprint, "message": error.to_string()}),
+                ),
+            );
+        }
+    };
+    let body = crypto::open(crypto::BLOB, raw.trim_end_matches('\n'))?;
+    Ok((sha256_hex(&body) == fingerprint).then_some(body))
+}
+
+fn preview(body: &str) -> String {
+    let mut preview: String = body.chars().take(PREVIEW_CHARS).collect();
+    preview.push('…');
+    preview
+}
+
+fn sha256_hex(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
@@ -0,0 +1,55 @@
+use crate::errors::TsqError;
+use crate::store::config::read_config;
+use crate::store::crypto;
+use crate::store::paths::get_paths;
+use crate::types::Compression;
+use std::path::Path;
+
+pub const ZSTD_EXTENSION: &str = ".zst";
+const ZSTD_LEVEL: i32 = 3;
+
+/// The codec configured for new files, if any.
+pub fn repo_compression(repo_root: impl AsRef<Path>) -> Result<Option<Compression>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    if !get_paths(repo_root).config_file.exists() {
+        return Ok(None);
+    }
+    Ok(read_config(repo_root)?.compression)
+}
+
+/// Suffix appended to a file name written with `codec`.
+pub fn extension(codec: Option<Compression>) -> &'static str {
+    match codec {
+        Some(Compression::Zstd) => ZSTD_EXTENSION,
+        None => "",
+    }
+}
+
+/// Strips a known compression suffix so callers can match the inner name.
+pub fn strip_extension(name: &str) -> &str {
+    name.strip_suffix(ZSTD_EXTENSION).unwrap_or(name)
+}
+
+pub fn compress(codec: Option<Compression>, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    match codec {
+        Some(Compression::Zstd) => zstd::encode_all(data, ZSTD_LEVEL),
+        None => Ok(data.to_vec()),
+    }
+}
+
+/// [`crypto::read_text`] that first decompresses by file extension, so
+/// compressed and plain files can sit side by side.
+pub fn read_text(path: impl AsRef<Path>, context: &str) -> std::io::Result<String> {
+    let path = path.as_ref();
+    let is_zstd = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(ZSTD_EXTENSION));
+    if !is_zstd {
+        return crypto::read_text(path, context);
+    }
+    let bytes = zstd::decode_all(std::fs::File::open(path)?)?;
+    let raw = String::from_utf8(bytes)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    crypto::open_text(raw, context)
+}
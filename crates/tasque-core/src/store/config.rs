@@ -1,37 +1,17 @@
+#[path = "config_validate.rs"]
+mod config_validate;
+
 use crate::errors::TsqError;
 use crate::store::paths::get_paths;
-use crate::types::{Config, SCHEMA_VERSION};
+use crate::types::{ActorResolutionConfig, Config, StatusWorkflowConfig};
 use chrono::Utc;
 use serde_json::Value;
 use std::fs::{OpenOptions, create_dir_all, read_to_string, remove_file, rename};
 use std::io::Write;
 use std::path::Path;
 
-fn is_config(value: &Value) -> Option<Config> {
-    let obj = value.as_object()?;
-    let schema_version = obj.get("schema_version")?.as_u64()? as u32;
-    let snapshot_every = obj.get("snapshot_every")?.as_i64()?;
-    if snapshot_every <= 0 {
-        return None;
-    }
-    let sync_branch = obj
-        .get("sync_branch")
-        .and_then(Value::as_str)
-        .map(String::from);
-    Some(Config {
-        schema_version,
-        snapshot_every: snapshot_every as usize,
-        sync_branch,
-    })
-}
-
-fn default_config() -> Config {
-    Config {
-        schema_version: SCHEMA_VERSION,
-        snapshot_every: 200,
-        sync_branch: None,
-    }
-}
+pub use config_validate::{is_actor_name, is_valid_policy};
+use config_validate::{is_actor_resolution, is_config, is_status_workflow};
 
 pub fn write_default_config(repo_root: impl AsRef<Path>) -> Result<(), TsqError> {
     let paths = get_paths(repo_root);
@@ -58,7 +38,7 @@ pub fn write_default_config(repo_root: impl AsRef<Path>) -> Result<(), TsqError>
         std::process::id(),
         Utc::now().timestamp_millis()
     );
-    let payload = serde_json::to_string_pretty(&default_config()).map_err(|error| {
+    let payload = serde_json::to_string_pretty(&Config::default()).map_err(|error| {
         TsqError::new("CONFIG_WRITE_FAILED", "Failed writing default config", 2)
             .with_details(any_error_value(&error))
     })?;
@@ -149,6 +129,30 @@ pub fn write_config(repo_root: impl AsRef<Path>, config: &Config) -> Result<(),
     Ok(())
 }
 
+/// `lock_ttl_ms` from the config file, read without writing a default config
+/// so taking the write lock never touches anything but the lock file.
+pub fn read_lock_ttl_ms(repo_root: impl AsRef<Path>) -> Option<u64> {
+    let raw = read_to_string(get_paths(repo_root).config_file).ok()?;
+    let parsed: Value = serde_json::from_str(&raw).ok()?;
+    parsed.get("lock_ttl_ms")?.as_u64().filter(|ttl| *ttl > 0)
+}
+
+/// `actor_resolution` from the config file, read without writing a default
+/// config so resolving the actor works outside initialized repos.
+pub fn read_actor_resolution(repo_root: impl AsRef<Path>) -> Option<ActorResolutionConfig> {
+    let raw = read_to_string(get_paths(repo_root).config_file).ok()?;
+    let parsed: Value = serde_json::from_str(&raw).ok()?;
+    is_actor_resolution(parsed.get("actor_resolution")?)
+}
+
+/// The configured `status_workflow`, for replay. Like `read_actor_resolution`
+/// it never writes a default config; a missing or invalid block is `None`.
+pub fn read_status_workflow(repo_root: impl AsRef<Path>) -> Option<StatusWorkflowConfig> {
+    let raw = read_to_string(get_paths(repo_root).config_file).ok()?;
+    let parsed: Value = serde_json::from_str(&raw).ok()?;
+    is_status_workflow(parsed.get("status_workflow")?)
+}
+
 pub fn read_config(repo_root: impl AsRef<Path>) -> Result<Config, TsqError> {
     let paths = get_paths(repo_root.as_ref());
 
@@ -157,7 +161,7 @@ pub fn read_config(repo_root: impl AsRef<Path>) -> Result<Config, TsqError> {
         Err(error) => {
             if error.kind() == std::io::ErrorKind::NotFound {
                 write_default_config(repo_root)?;
-                return Ok(default_config());
+                return Ok(Config::default());
             }
             return Err(
                 TsqError::new("CONFIG_READ_FAILED", "Failed reading config", 2)
@@ -189,6 +193,10 @@ fn any_error_value(error: &impl std::fmt::Display) -> Value {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{
+        AutoSyncConfig, EscalationConfig, HookConfig, HookMode, PolicyRequirement, SCHEMA_VERSION,
+        StalePolicyConfig,
+    };
     use tempfile::TempDir;
 
     #[test]
@@ -203,6 +211,81 @@ mod tests {
         assert_eq!(config.sync_branch, None);
     }
 
+    #[test]
+    fn is_config_parses_escalation_with_defaults() {
+        let value: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "escalation": {"after_days": 14}
+        });
+        let config = is_config(&value).expect("should parse escalation");
+        assert_eq!(
+            config.escalation,
+            Some(EscalationConfig {
+                after_days: 14,
+                every_days: 7,
+                max_boost: 1,
+            })
+        );
+
+        let invalid: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "escalation": {"after_days": 14, "every_days": 0}
+        });
+        assert!(is_config(&invalid).is_none());
+    }
+
+    #[test]
+    fn is_config_requires_a_stale_policy_action() {
+        let value: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "stale": {"label": "stale", "auto_defer": true}
+        });
+        let config = is_config(&value).expect("should parse stale policy");
+        assert_eq!(
+            config.stale,
+            Some(StalePolicyConfig {
+                stale_days: 30,
+                label: Some("stale".to_string()),
+                auto_defer: true,
+            })
+        );
+
+        let inert: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "stale": {"stale_days": 14}
+        });
+        assert!(is_config(&inert).is_none());
+    }
+
+    #[test]
+    fn is_config_parses_auto_sync_and_rejects_push_without_commit() {
+        let value: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "auto_sync": {"push": true, "throttle_secs": 30}
+        });
+        let config = is_config(&value).expect("should parse auto_sync");
+        assert_eq!(
+            config.auto_sync,
+            Some(AutoSyncConfig {
+                commit: true,
+                push: true,
+                throttle_secs: 30,
+            })
+        );
+
+        let invalid: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "auto_sync": {"commit": false, "push": true}
+        });
+        assert!(is_config(&invalid).is_none());
+    }
+
     #[test]
     fn is_config_parses_config_with_sync_branch() {
         let value: Value = serde_json::json!({
@@ -237,9 +320,89 @@ mod tests {
         assert_eq!(config.sync_branch, None);
     }
 
+    #[test]
+    fn is_config_parses_hook_shorthand_and_objects() {
+        let value: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "hooks": {
+                "on_create": "scripts/notify.sh",
+                "on_close": {"command": "scripts/gate.sh", "mode": "blocking"}
+            }
+        });
+        let config = is_config(&value).expect("should parse config with hooks");
+        assert_eq!(
+            config.hooks.on_create,
+            Some(HookConfig {
+                command: "scripts/notify.sh".to_string(),
+                mode: HookMode::Advisory,
+            })
+        );
+        assert_eq!(
+            config.hooks.on_close.map(|hook| hook.mode),
+            Some(HookMode::Blocking)
+        );
+        assert_eq!(config.hooks.on_claim, None);
+    }
+
+    #[test]
+    fn is_config_rejects_unknown_hooks_and_modes() {
+        let unknown_hook: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "hooks": {"on_delete": "x.sh"}
+        });
+        assert!(is_config(&unknown_hook).is_none());
+        let unknown_mode: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "hooks": {"on_create": {"command": "x.sh", "mode": "sometimes"}}
+        });
+        assert!(is_config(&unknown_mode).is_none());
+    }
+
+    #[test]
+    fn is_config_parses_policies() {
+        let value: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "policies": [{
+                "name": "feature-planning",
+                "when": {"kind": "feature", "planning_state": "planned"},
+                "require": ["label", "label:estimate:*", "description"]
+            }]
+        });
+        let config = is_config(&value).expect("should parse config with policies");
+        assert_eq!(config.policies.len(), 1);
+        assert_eq!(
+            config.policies[0].require,
+            vec![
+                PolicyRequirement::Label(None),
+                PolicyRequirement::Label(Some("estimate:*".to_string())),
+                PolicyRequirement::Description,
+            ]
+        );
+    }
+
+    #[test]
+    fn is_config_rejects_policies_without_trigger_or_known_requirements() {
+        let no_trigger: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "policies": [{"name": "p", "when": {"kind": "task"}, "require": ["label"]}]
+        });
+        assert!(is_config(&no_trigger).is_none());
+        let unknown_requirement: Value = serde_json::json!({
+            "schema_version": 1,
+            "snapshot_every": 200,
+            "policies": [{"name": "p", "when": {"status": "closed"}, "require": ["estimate"]}]
+        });
+        assert!(is_config(&unknown_requirement).is_none());
+    }
+
     #[test]
     fn default_config_has_no_sync_branch() {
-        let config = default_config();
+        let config = Config::default();
         assert_eq!(config.sync_branch, None);
         assert_eq!(config.schema_version, SCHEMA_VERSION);
         assert_eq!(config.snapshot_every, 200);
@@ -252,9 +415,9 @@ mod tests {
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
 
         let config = Config {
-            schema_version: 1,
             snapshot_every: 100,
             sync_branch: Some("my-sync".to_string()),
+            ..Config::default()
         };
         write_config(repo, &config).expect("write_config");
 
@@ -269,9 +432,8 @@ mod tests {
         std::fs::create_dir_all(repo.join(".tasque")).expect("mkdir");
 
         let config = Config {
-            schema_version: 1,
             snapshot_every: 300,
-            sync_branch: None,
+            ..Config::default()
         };
         write_config(repo, &config).expect("write_config");
 
@@ -281,11 +443,7 @@ mod tests {
 
     #[test]
     fn config_serialization_omits_none_sync_branch() {
-        let config = Config {
-            schema_version: 1,
-            snapshot_every: 200,
-            sync_branch: None,
-        };
+        let config = Config::default();
         let json = serde_json::to_string(&config).expect("serialize");
         assert!(!json.contains("sync_branch"));
     }
@@ -293,9 +451,8 @@ mod tests {
     #[test]
     fn config_serialization_includes_sync_branch_when_set() {
         let config = Config {
-            schema_version: 1,
-            snapshot_every: 200,
             sync_branch: Some("test-branch".to_string()),
+            ..Config::default()
         };
         let json = serde_json::to_string(&config).expect("serialize");
         assert!(json.contains("\"sync_branch\":\"test-branch\""));
@@ -0,0 +1,474 @@
+use crate::domain::event_payload_codecs::is_custom_relation_name;
+use crate::domain::labels::normalize_label;
+use crate::skills::remote::is_skill_checksum;
+use crate::store::git::validate_branch_name;
+use crate::types::{
+    ActorIdentity, ActorResolutionConfig, ActorSource, AutoSyncConfig, Compression, Config,
+    EscalationConfig, EventSegmentation, HookConfig, HookMode, HooksConfig, LabelColor, LaneConfig,
+    ParentCompletionConfig, PayloadLimitsConfig, PlanningState, PlanningWorkflowConfig,
+    PolicyConfig, RedactionConfig, RolesConfig, SkillRegistry, StalePolicyConfig,
+    StatusWorkflowConfig, SyncRoute, TaskStatus,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub(super) fn is_config(value: &Value) -> Option<Config> {
+    let obj = value.as_object()?;
+    let schema_version = obj.get("schema_version")?.as_u64()? as u32;
+    let snapshot_every = obj.get("snapshot_every")?.as_i64()?;
+    if snapshot_every <= 0 {
+        return None;
+    }
+    let sync_branch = obj
+        .get("sync_branch")
+        .and_then(Value::as_str)
+        .map(String::from);
+    let hooks = match obj.get("hooks") {
+        None | Some(Value::Null) => HooksConfig::default(),
+        Some(value) => is_hooks(value)?,
+    };
+    let policies = match obj.get("policies") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(value) => value
+            .as_array()?
+            .iter()
+            .map(is_policy)
+            .collect::<Option<Vec<_>>>()?,
+    };
+    let escalation = match obj.get("escalation") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_escalation(value)?),
+    };
+    let stale = match obj.get("stale") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_stale_policy(value)?),
+    };
+    let auto_sync = match obj.get("auto_sync") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_auto_sync(value)?),
+    };
+    let sync_routes = match obj.get("sync_routes") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(value) => value
+            .as_array()?
+            .iter()
+            .map(is_sync_route)
+            .collect::<Option<Vec<_>>>()?,
+    };
+    let encryption_salt = match obj.get("encryption_salt") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(
+            value
+                .as_str()
+                .filter(|salt| salt.len() == 32 && salt.bytes().all(|b| b.is_ascii_hexdigit()))?
+                .to_string(),
+        ),
+    };
+    let redaction = match obj.get("redaction") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_redaction(value)?),
+    };
+    let roles = match obj.get("roles") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(serde_json::from_value::<RolesConfig>(value.clone()).ok()?),
+    };
+    let relation_types = match obj.get("relation_types") {
+        None | Some(Value::Null) => BTreeMap::new(),
+        Some(value) => is_relation_types(value)?,
+    };
+    let lock_ttl_ms = match obj.get("lock_ttl_ms") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(value.as_u64().filter(|ttl| *ttl > 0)?),
+    };
+    let compression = match obj.get("compression") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(serde_json::from_value::<Compression>(value.clone()).ok()?),
+    };
+    let event_segments = match obj.get("event_segments") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(serde_json::from_value::<EventSegmentation>(value.clone()).ok()?),
+    };
+    let label_colors = match obj.get("label_colors") {
+        None | Some(Value::Null) => BTreeMap::new(),
+        Some(value) => is_label_colors(value)?,
+    };
+    let skill_registries = match obj.get("skill_registries") {
+        None | Some(Value::Null) => BTreeMap::new(),
+        Some(value) => is_skill_registries(value)?,
+    };
+    let lanes = match obj.get("lanes") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(value) => is_lanes(value)?,
+    };
+    let actors = match obj.get("actors") {
+        None | Some(Value::Null) => BTreeMap::new(),
+        Some(value) => is_actors(value)?,
+    };
+    let actor_resolution = match obj.get("actor_resolution") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_actor_resolution(value)?),
+    };
+    let payload_limits = match obj.get("payload_limits") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_payload_limits(value)?),
+    };
+    let planning_workflow = match obj.get("planning_workflow") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_planning_workflow(value)?),
+    };
+    if let Some(workflow) = &planning_workflow {
+        let known = |state: &PlanningState| workflow.states.contains(state);
+        let lanes_known = lanes
+            .iter()
+            .all(|lane| lane.when.planning_state.iter().all(known));
+        let policies_known = policies
+            .iter()
+            .all(|policy| policy.when.planning_state.as_ref().is_none_or(known));
+        if !lanes_known || !policies_known {
+            return None;
+        }
+    }
+    let status_workflow = match obj.get("status_workflow") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_status_workflow(value)?),
+    };
+    let statuses = status_workflow
+        .as_ref()
+        .map_or(&TaskStatus::BUILTIN[..], |workflow| &workflow.statuses[..]);
+    let policies_known = policies.iter().all(|policy| {
+        policy
+            .when
+            .status
+            .as_ref()
+            .is_none_or(|status| statuses.contains(status))
+    });
+    if !policies_known {
+        return None;
+    }
+    let parent_completion = match obj.get("parent_completion") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(is_parent_completion(value, statuses)?),
+    };
+    Some(Config {
+        schema_version,
+        snapshot_every: snapshot_every as usize,
+        sync_branch,
+        hooks,
+        policies,
+        escalation,
+        stale,
+        auto_sync,
+        sync_disabled: obj
+            .get("sync_disabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        sync_routes,
+        encrypt: obj.get("encrypt").and_then(Value::as_bool).unwrap_or(false),
+        encryption_salt,
+        redaction,
+        roles,
+        relation_types,
+        lock_ttl_ms,
+        compression,
+        event_segments,
+        label_colors,
+        skill_registries,
+        lanes,
+        actors,
+        strict_actors: obj
+            .get("strict_actors")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        actor_resolution,
+        payload_limits,
+        planning_workflow,
+        status_workflow,
+        parent_completion,
+    })
+}
+
+/// Needs at least one action; the status must be configured and cannot be
+/// `canceled`, and the label must be valid.
+fn is_parent_completion(value: &Value, statuses: &[TaskStatus]) -> Option<ParentCompletionConfig> {
+    let policy: ParentCompletionConfig = serde_json::from_value(value.clone()).ok()?;
+    if policy.status.is_none() && policy.label.is_none() && policy.note.is_none() {
+        return None;
+    }
+    let status_ok = policy
+        .status
+        .as_ref()
+        .is_none_or(|status| statuses.contains(status) && *status != TaskStatus::Canceled);
+    let label_ok = policy
+        .label
+        .as_deref()
+        .is_none_or(|label| normalize_label(label).is_ok());
+    let note_ok = policy
+        .note
+        .as_deref()
+        .is_none_or(|note| !note.trim().is_empty());
+    (status_ok && label_ok && note_ok).then_some(policy)
+}
+
+/// Statuses must be unique and include all six builtins, and transitions may
+/// only name listed statuses.
+pub(super) fn is_status_workflow(value: &Value) -> Option<StatusWorkflowConfig> {
+    let workflow: StatusWorkflowConfig = serde_json::from_value(value.clone()).ok()?;
+    let statuses = &workflow.statuses;
+    let unique = statuses
+        .iter()
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+        == statuses.len();
+    if !unique
+        || !TaskStatus::BUILTIN
+            .iter()
+            .all(|builtin| statuses.contains(builtin))
+    {
+        return None;
+    }
+    let transitions_known = workflow.transitions.iter().all(|(from, targets)| {
+        statuses.iter().any(|status| status.as_str() == from)
+            && targets.iter().all(|target| statuses.contains(target))
+    });
+    transitions_known.then_some(workflow)
+}
+
+/// States must be unique and include both builtins, and transitions may only
+/// name listed states.
+fn is_planning_workflow(value: &Value) -> Option<PlanningWorkflowConfig> {
+    let workflow: PlanningWorkflowConfig = serde_json::from_value(value.clone()).ok()?;
+    let states = &workflow.states;
+    let unique = states
+        .iter()
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+        == states.len();
+    if !unique
+        || !states.contains(&PlanningState::NeedsPlanning)
+        || !states.contains(&PlanningState::Planned)
+    {
+        return None;
+    }
+    let transitions_known = workflow.transitions.iter().all(|(from, targets)| {
+        states.iter().any(|state| state.as_str() == from)
+            && targets.iter().all(|target| states.contains(target))
+    });
+    transitions_known.then_some(workflow)
+}
+
+/// Both limits must be positive, and overflow must kick in at or below the
+/// rejection limit.
+fn is_payload_limits(value: &Value) -> Option<PayloadLimitsConfig> {
+    let limits: PayloadLimitsConfig = serde_json::from_value(value.clone()).ok()?;
+    if limits.max_bytes == Some(0) || limits.overflow_bytes == Some(0) {
+        return None;
+    }
+    (limits.overflow_bytes() <= limits.max_bytes()).then_some(limits)
+}
+
+/// Actor names must be single non-empty tokens, and an email needs an `@`.
+fn is_actors(value: &Value) -> Option<BTreeMap<String, ActorIdentity>> {
+    let actors: BTreeMap<String, ActorIdentity> = serde_json::from_value(value.clone()).ok()?;
+    let valid = actors.iter().all(|(name, identity)| {
+        is_actor_name(name)
+            && identity
+                .email
+                .as_deref()
+                .is_none_or(|email| email.contains('@'))
+    });
+    valid.then_some(actors)
+}
+
+/// The order must be non-empty without repeats, and lists `config` exactly
+/// when a valid `actor` name is set.
+pub(super) fn is_actor_resolution(value: &Value) -> Option<ActorResolutionConfig> {
+    let resolution: ActorResolutionConfig = serde_json::from_value(value.clone()).ok()?;
+    let order = &resolution.order;
+    let unique = order.iter().collect::<std::collections::HashSet<_>>().len() == order.len();
+    let actor_ok = match resolution.actor.as_deref() {
+        Some(actor) => is_actor_name(actor) && order.contains(&ActorSource::Config),
+        None => !order.contains(&ActorSource::Config),
+    };
+    (!order.is_empty() && unique && actor_ok).then_some(resolution)
+}
+
+pub fn is_actor_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(char::is_whitespace)
+}
+
+/// Lane names must be unique and usable as a `--lane` value, and labels must
+/// already be normalized so they compare equal to task labels.
+fn is_lanes(value: &Value) -> Option<Vec<LaneConfig>> {
+    let lanes: Vec<LaneConfig> = serde_json::from_value(value.clone()).ok()?;
+    let mut names = std::collections::HashSet::new();
+    for lane in &lanes {
+        if normalize_label(&lane.name).ok().as_ref() != Some(&lane.name)
+            || !names.insert(lane.name.as_str())
+        {
+            return None;
+        }
+        if lane
+            .when
+            .labels
+            .iter()
+            .any(|label| normalize_label(label).ok().as_ref() != Some(label))
+        {
+            return None;
+        }
+    }
+    Some(lanes)
+}
+
+/// Registry URLs must be non-empty and every pinned checksum must be `sha256:<64 hex>`.
+fn is_skill_registries(value: &Value) -> Option<BTreeMap<String, SkillRegistry>> {
+    let registries: BTreeMap<String, SkillRegistry> = serde_json::from_value(value.clone()).ok()?;
+    let valid = registries.iter().all(|(name, registry)| {
+        !name.trim().is_empty()
+            && !registry.url.trim().is_empty()
+            && registry
+                .checksums
+                .values()
+                .all(|sum| is_skill_checksum(sum))
+    });
+    valid.then_some(registries)
+}
+
+/// Keys must already be normalized labels so lookups by task label match.
+fn is_label_colors(value: &Value) -> Option<BTreeMap<String, LabelColor>> {
+    let colors: BTreeMap<String, LabelColor> = serde_json::from_value(value.clone()).ok()?;
+    if colors
+        .keys()
+        .any(|label| normalize_label(label).ok().as_ref() != Some(label))
+    {
+        return None;
+    }
+    Some(colors)
+}
+
+/// Every custom relation and its inverse must be a valid, non-builtin name,
+/// and a type listed from both sides must name the same pair.
+fn is_relation_types(value: &Value) -> Option<BTreeMap<String, String>> {
+    let types: BTreeMap<String, String> = serde_json::from_value(value.clone()).ok()?;
+    for (name, inverse) in &types {
+        if !is_custom_relation_name(name) || !is_custom_relation_name(inverse) {
+            return None;
+        }
+        if types.get(inverse).is_some_and(|back| back != name) {
+            return None;
+        }
+    }
+    Some(types)
+}
+
+/// A route needs a valid branch name and a non-empty project path.
+fn is_sync_route(value: &Value) -> Option<SyncRoute> {
+    let route: SyncRoute = serde_json::from_value(value.clone()).ok()?;
+    let path = route.path.trim().trim_matches('/');
+    if path.is_empty() || validate_branch_name(&route.branch).is_err() {
+        return None;
+    }
+    Some(SyncRoute {
+        branch: route.branch,
+        path: path.to_string(),
+    })
+}
+
+/// A policy needs a name, a status or planning-state trigger, and at least
+/// one known requirement.
+fn is_policy(value: &Value) -> Option<PolicyConfig> {
+    let policy: PolicyConfig = serde_json::from_value(value.clone()).ok()?;
+    is_valid_policy(&policy).then_some(policy)
+}
+
+pub fn is_valid_policy(policy: &PolicyConfig) -> bool {
+    let triggered = policy.when.status.is_some() || policy.when.planning_state.is_some();
+    !policy.name.trim().is_empty() && triggered && !policy.require.is_empty()
+}
+
+fn is_escalation(value: &Value) -> Option<EscalationConfig> {
+    let escalation: EscalationConfig = serde_json::from_value(value.clone()).ok()?;
+    if escalation.every_days == 0 || escalation.max_boost == 0 {
+        return None;
+    }
+    Some(escalation)
+}
+
+/// A stale policy needs a positive window and at least one action.
+fn is_stale_policy(value: &Value) -> Option<StalePolicyConfig> {
+    let policy: StalePolicyConfig = serde_json::from_value(value.clone()).ok()?;
+    if policy.stale_days == 0 || (policy.label.is_none() && !policy.auto_defer) {
+        return None;
+    }
+    if policy
+        .label
+        .as_deref()
+        .is_some_and(|label| label.trim().is_empty())
+    {
+        return None;
+    }
+    Some(policy)
+}
+
+/// Every `drop` entry must compile as a regex.
+fn is_redaction(value: &Value) -> Option<RedactionConfig> {
+    let redaction: RedactionConfig = serde_json::from_value(value.clone()).ok()?;
+    if redaction
+        .drop
+        .iter()
+        .any(|pattern| regex::Regex::new(pattern).is_err())
+    {
+        return None;
+    }
+    Some(redaction)
+}
+
+/// Pushing only happens after an auto-commit, so `push` requires `commit`.
+fn is_auto_sync(value: &Value) -> Option<AutoSyncConfig> {
+    let auto_sync: AutoSyncConfig = serde_json::from_value(value.clone()).ok()?;
+    if auto_sync.push && !auto_sync.commit {
+        return None;
+    }
+    Some(auto_sync)
+}
+
+fn is_hooks(value: &Value) -> Option<HooksConfig> {
+    let obj = value.as_object()?;
+    let mut hooks = HooksConfig::default();
+    for (name, raw) in obj {
+        let hook = is_hook(raw)?;
+        let slot = match name.as_str() {
+            "on_create" => &mut hooks.on_create,
+            "on_status_change" => &mut hooks.on_status_change,
+            "on_claim" => &mut hooks.on_claim,
+            "on_close" => &mut hooks.on_close,
+            _ => return None,
+        };
+        *slot = hook;
+    }
+    Some(hooks)
+}
+
+/// A hook is either a bare command string (advisory) or
+/// `{"command": "...", "mode": "advisory" | "blocking"}`.
+fn is_hook(value: &Value) -> Option<Option<HookConfig>> {
+    let (command, mode) = match value {
+        Value::Null => return Some(None),
+        Value::String(command) => (command.as_str(), HookMode::Advisory),
+        Value::Object(obj) => {
+            let command = obj.get("command")?.as_str()?;
+            let mode = match obj.get("mode") {
+                None | Some(Value::Null) => HookMode::Advisory,
+                Some(mode) => serde_json::from_value(mode.clone()).ok()?,
+            };
+            (command, mode)
+        }
+        _ => return None,
+    };
+    if command.trim().is_empty() {
+        return None;
+    }
+    Some(Some(HookConfig {
+        command: command.to_string(),
+        mode,
+    }))
+}
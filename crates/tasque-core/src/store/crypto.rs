@@ -0,0 +1,313 @@
+pub use crate::domain::event_records::{SEALED_PREFIX, is_sealed};
+use crate::errors::TsqError;
+use crate::store::config::{read_config, write_config};
+use crate::store::paths::get_paths;
+use crate::types::Config;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use once_cell::sync::OnceCell;
+use rand::Rng;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+
+pub const EVENTS: &str = "events";
+pub const SNAPSHOT: &str = "snapshot";
+pub const SPEC: &str = "spec";
+pub const BLOB: &str = "blob";
+
+const KEY_ENV: &str = "TSQ_ENCRYPTION_KEY";
+const KEY_COMMAND_ENV: &str = "TSQ_ENCRYPTION_KEY_COMMAND";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+
+/// XChaCha20-Poly1305 key stretched from the user's passphrase with Argon2id
+/// over `salt`.
+pub struct EncryptionKey {
+    salt: [u8; SALT_LEN],
+    cipher: XChaCha20Poly1305,
+}
+
+impl EncryptionKey {
+    pub fn derive(secret: &str, salt: [u8; SALT_LEN]) -> Result<Self, TsqError> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(secret.as_bytes(), &salt, &mut key)
+            .map_err(|error| {
+                TsqError::new(
+                    "ENCRYPTION_KEY_INVALID",
+                    "Failed deriving encryption key",
+                    2,
+                )
+                .with_details(serde_json::json!({"message": error.to_string()}))
+            })?;
+        Ok(Self {
+            salt,
+            cipher: XChaCha20Poly1305::new(&key.into()),
+        })
+    }
+}
+
+/// Seals `plaintext` under a random nonce, binding `context` as associated
+/// data so a sealed spec cannot be replayed as an event. The payload is
+/// `salt || nonce || ciphertext`, so any sealed text names the salt its key
+/// was derived from.
+pub fn seal_with(key: &EncryptionKey, context: &str, plaintext: &str) -> Result<String, TsqError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce);
+    let body = key
+        .cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext.as_bytes(),
+                aad: context.as_bytes(),
+            },
+        )
+        .map_err(|_| TsqError::new("ENCRYPT_FAILED", "Failed encrypting task data", 2))?;
+    Ok(format!(
+        "{}{}{}{}",
+        SEALED_PREFIX,
+        to_hex(&key.salt),
+        to_hex(&nonce),
+        to_hex(&body)
+    ))
+}
+
+pub fn open_with(key: &EncryptionKey, context: &str, sealed: &str) -> Result<String, TsqError> {
+    let (salt, nonce, body) = split_sealed(sealed).ok_or_else(decrypt_failed)?;
+    if salt != key.salt {
+        return Err(decrypt_failed());
+    }
+    let plain = key
+        .cipher
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &body,
+                aad: context.as_bytes(),
+            },
+        )
+        .map_err(|_| decrypt_failed())?;
+    String::from_utf8(plain).map_err(|_| decrypt_failed())
+}
+
+/// Decrypts `text` when it is sealed and passes plaintext through unchanged.
+pub fn open(context: &str, text: &str) -> Result<String, TsqError> {
+    if !is_sealed(text) {
+        return Ok(text.to_string());
+    }
+    open_with(key_for(text)?, context, text)
+}
+
+/// The key that opens `sealed`, derived for the salt it carries.
+pub fn key_for(sealed: &str) -> Result<&'static EncryptionKey, TsqError> {
+    let (salt, _, _) = split_sealed(sealed).ok_or_else(decrypt_failed)?;
+    key_for_salt(salt)
+}
+
+/// Returns the key when the repo's config has `encrypt` enabled. A missing
+/// config means the repo is not encrypted; it is never created here. The
+/// first call on an encrypted repo without `encryption_salt` generates one
+/// and writes it back, so every later write derives the same key. Two clones
+/// racing to do so stay readable, because each sealed payload names its salt.
+pub fn repo_key(repo_root: impl AsRef<Path>) -> Result<Option<&'static EncryptionKey>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    if !get_paths(repo_root).config_file.exists() {
+        return Ok(None);
+    }
+    let config = read_config(repo_root)?;
+    if !config.encrypt {
+        return Ok(None);
+    }
+    let salt = match config.encryption_salt.as_deref().and_then(parse_salt) {
+        Some(salt) => salt,
+        None => {
+            let mut salt = [0u8; SALT_LEN];
+            rand::rng().fill_bytes(&mut salt);
+            // Fail before persisting a salt when no key is configured.
+            require_secret()?;
+            write_config(
+                repo_root,
+                &Config {
+                    encryption_salt: Some(to_hex(&salt)),
+                    ..config
+                },
+            )?;
+            salt
+        }
+    };
+    key_for_salt(salt).map(Some)
+}
+
+/// Seals `plaintext` when the repo has `encrypt` enabled.
+pub fn seal_for_repo(
+    repo_root: impl AsRef<Path>,
+    context: &str,
+    plaintext: String,
+) -> Result<String, TsqError> {
+    match repo_key(repo_root)? {
+        Some(key) => seal_with(key, context, &plaintext),
+        None => Ok(plaintext),
+    }
+}
+
+/// `read_to_string` that decrypts sealed files; decryption failures surface
+/// as `InvalidData` so callers keep their existing io error mapping.
+pub fn read_text(path: impl AsRef<Path>, context: &str) -> std::io::Result<String> {
+    open_text(std::fs::read_to_string(path)?, context)
+}
+
+/// [`read_text`] for contents already in memory.
+pub fn open_text(raw: String, context: &str) -> std::io::Result<String> {
+    if !is_sealed(&raw) {
+        return Ok(raw);
+    }
+    open(context, raw.trim_end())
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error.message))
+}
+
+/// Derives each salt's key once per process; Argon2 is deliberately slow and
+/// a repo normally has a single salt.
+fn key_for_salt(salt: [u8; SALT_LEN]) -> Result<&'static EncryptionKey, TsqError> {
+    static KEYS: Mutex<Vec<&'static EncryptionKey>> = Mutex::new(Vec::new());
+    let secret = require_secret()?;
+    let mut keys = KEYS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(key) = keys.iter().find(|key| key.salt == salt) {
+        return Ok(key);
+    }
+    let key: &'static EncryptionKey = Box::leak(Box::new(EncryptionKey::derive(secret, salt)?));
+    keys.push(key);
+    Ok(key)
+}
+
+/// Loads the passphrase once per process from the environment or the key
+/// command.
+fn require_secret() -> Result<&'static str, TsqError> {
+    static SECRET: OnceCell<Result<String, TsqError>> = OnceCell::new();
+    SECRET
+        .get_or_init(load_secret)
+        .as_deref()
+        .map_err(Clone::clone)
+}
+
+fn load_secret() -> Result<String, TsqError> {
+    if let Some(secret) = std::env::var(KEY_ENV)
+        .ok()
+        .filter(|value| !value.is_empty())
+    {
+        return Ok(secret);
+    }
+    let Some(command) = std::env::var(KEY_COMMAND_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+    else {
+        return Err(TsqError::new(
+            "ENCRYPTION_KEY_MISSING",
+            format!(
+                "Encrypted task data needs {} or {} (e.g. a keyring lookup)",
+                KEY_ENV, KEY_COMMAND_ENV
+            ),
+            2,
+        ));
+    };
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .output()
+        .map_err(|error| {
+            TsqError::new(
+                "ENCRYPTION_KEY_MISSING",
+                format!("Failed running {}", KEY_COMMAND_ENV),
+                2,
+            )
+            .with_details(serde_json::json!({"message": error.to_string()}))
+        })?;
+    let secret = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || secret.is_empty() {
+        return Err(TsqError::new(
+            "ENCRYPTION_KEY_MISSING",
+            format!("{} did not print a key", KEY_COMMAND_ENV),
+            2,
+        )
+        .with_details(serde_json::json!({
+            "exit_code": output.status.code(),
+            "stderr": String::from_utf8_lossy(&output.stderr).trim(),
+        })));
+    }
+    Ok(secret)
+}
+
+/// Splits a sealed payload into its salt, nonce, and ciphertext.
+fn split_sealed(sealed: &str) -> Option<([u8; SALT_LEN], [u8; NONCE_LEN], Vec<u8>)> {
+    let bytes = from_hex(sealed.strip_prefix(SEALED_PREFIX)?)?;
+    if bytes.len() < SALT_LEN + NONCE_LEN + TAG_LEN {
+        return None;
+    }
+    let (salt, rest) = bytes.split_at(SALT_LEN);
+    let (nonce, body) = rest.split_at(NONCE_LEN);
+    Some((salt.try_into().ok()?, nonce.try_into().ok()?, body.to_vec()))
+}
+
+fn parse_salt(hex: &str) -> Option<[u8; SALT_LEN]> {
+    from_hex(hex)?.try_into().ok()
+}
+
+fn decrypt_failed() -> TsqError {
+    TsqError::new(
+        "DECRYPT_FAILED",
+        "Failed decrypting task data (wrong key or tampered content)",
+        2,
+    )
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_round_trips_under_fresh_nonces_and_rejects_tampering() {
+        let key = EncryptionKey::derive("correct horse", [7; SALT_LEN]).unwrap();
+        let sealed = seal_with(&key, EVENTS, "{\"title\":\"secret\"}").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("secret"));
+        assert_ne!(
+            sealed,
+            seal_with(&key, EVENTS, "{\"title\":\"secret\"}").unwrap()
+        );
+        assert_eq!(split_sealed(&sealed).unwrap().0, [7; SALT_LEN]);
+        assert_eq!(
+            open_with(&key, EVENTS, &sealed).unwrap(),
+            "{\"title\":\"secret\"}"
+        );
+
+        let other = EncryptionKey::derive("wrong horse", [7; SALT_LEN]).unwrap();
+        assert_eq!(
+            open_with(&other, EVENTS, &sealed).unwrap_err().code,
+            "DECRYPT_FAILED"
+        );
+        let resalted = EncryptionKey::derive("correct horse", [8; SALT_LEN]).unwrap();
+        assert!(open_with(&resalted, EVENTS, &sealed).is_err());
+        assert!(open_with(&key, SPEC, &sealed).is_err());
+        let mut tampered = sealed.clone();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == '0' { '1' } else { '0' });
+        assert!(open_with(&key, EVENTS, &tampered).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+use crate::domain::event_records::parse_event_record;
+use crate::errors::TsqError;
+use crate::store::blobs;
+use crate::store::crypto;
+use crate::store::paths::get_paths;
+use crate::store::segments;
+use crate::types::{EventLogMetadata, EventRecord};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs::{OpenOptions, create_dir_all, read, read_to_string};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+pub struct ReadEventsResult {
+    pub events: Vec<EventRecord>,
+    pub warning: Option<String>,
+    pub metadata: EventLogMetadata,
+}
+
+fn validate_event_for_append(event: &EventRecord) -> Result<(), TsqError> {
+    let value = serde_json::to_value(event).map_err(|error| {
+        TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+            .with_details(any_error_value(&error))
+    })?;
+    parse_event_record(&value, 0).map(|_| ()).map_err(|error| {
+        TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2).with_details(
+            serde_json::json!({
+                "validation_code": error.code,
+                "message": error.message,
+            }),
+        )
+    })
+}
+
+fn prepare_event_file_for_append(
+    handle: &mut std::fs::File,
+    path: &Path,
+) -> Result<bool, TsqError> {
+    let bytes = read(path).map_err(|error| {
+        TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+            .with_details(io_error_value(&error))
+    })?;
+    if bytes.is_empty() {
+        handle.seek(SeekFrom::End(0)).map_err(|error| {
+            TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                .with_details(io_error_value(&error))
+        })?;
+        return Ok(false);
+    }
+
+    let raw = std::str::from_utf8(&bytes).map_err(|error| {
+        TsqError::new("EVENTS_CORRUPT", "Events file is not valid UTF-8", 2)
+            .with_details(any_error_value(&error))
+    })?;
+    let mut nonempty_lines: Vec<(usize, &str, usize)> = Vec::new();
+    let mut offset = 0;
+    for (line_index, raw_line) in raw.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += raw_line.len();
+        let line = raw_line
+            .strip_suffix('\n')
+            .unwrap_or(raw_line)
+            .trim_end_matches('\r');
+        if !line.trim().is_empty() {
+            nonempty_lines.push((start, line, line_index + 1));
+        }
+    }
+
+    let Some(final_index) = nonempty_lines.len().checked_sub(1) else {
+        handle.seek(SeekFrom::End(0)).map_err(|error| {
+            TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                .with_details(io_error_value(&error))
+        })?;
+        return Ok(false);
+    };
+
+    let mut key_verified = false;
+    for (index, (_start, line, line_number)) in nonempty_lines.iter().enumerate() {
+        match decode_event_line(line, *line_number) {
+            Ok(parsed) => {
+                key_verified |= crypto::is_sealed(line);
+                parse_event_record(&parsed, *line_number)?;
+            }
+            Err(error) if index == final_index && is_torn_tail(&error, key_verified) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    let (last_start, final_line, line_number) = nonempty_lines[final_index];
+    match decode_event_line(final_line, line_number) {
+        Ok(_) => {
+            handle.seek(SeekFrom::End(0)).map_err(|error| {
+                TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                    .with_details(io_error_value(&error))
+            })?;
+            Ok(!bytes.ends_with(b"\n"))
+        }
+        Err(_) => {
+            handle.set_len(last_start as u64).map_err(|error| {
+                TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                    .with_details(io_error_value(&error))
+            })?;
+            handle.seek(SeekFrom::End(0)).map_err(|error| {
+                TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                    .with_details(io_error_value(&error))
+            })?;
+            Ok(false)
+        }
+    }
+}
+
+pub fn append_events(repo_root: impl AsRef<Path>, events: &[EventRecord]) -> Result<(), TsqError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    for event in events {
+        validate_event_for_append(event)?;
+    }
+
+    let _span = tracing::debug_span!("storage.append", events = events.len()).entered();
+    let repo_root = repo_root.as_ref();
+    let events = &blobs::offload_bodies(repo_root, &blobs::payload_limits(repo_root)?, events)?;
+    let key = crypto::repo_key(repo_root)?;
+    let paths = get_paths(repo_root);
+    create_dir_all(&paths.tasque_dir).map_err(|error| {
+        TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+            .with_details(io_error_value(&error))
+    })?;
+
+    let payload = events
+        .iter()
+        .map(|event| {
+            let line = serde_json::to_string(event).map_err(|error| {
+                TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                    .with_details(any_error_value(&error))
+            })?;
+            match key {
+                Some(key) => crypto::seal_with(key, crypto::EVENTS, &line),
+                None => Ok(line),
+            }
+        })
+        .collect::<Result<Vec<String>, TsqError>>()?
+        .join("\n")
+        + "\n";
+
+    let mut handle = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&paths.events_file)
+        .map_err(|error| {
+            TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                .with_details(io_error_value(&error))
+        })?;
+    let needs_separator = prepare_event_file_for_append(&mut handle, &paths.events_file)?;
+    if needs_separator {
+        handle.write_all(b"\n").map_err(|error| {
+            TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                .with_details(io_error_value(&error))
+        })?;
+    }
+    if let Err(error) = handle.write_all(payload.as_bytes()) {
+        return Err(
+            TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                .with_details(io_error_value(&error)),
+        );
+    }
+    if let Err(error) = handle.sync_all() {
+        return Err(
+            TsqError::new("EVENT_APPEND_FAILED", "Failed appending events", 2)
+                .with_details(io_error_value(&error)),
+        );
+    }
+    drop(handle);
+
+    // The events are durable at this point, so a failed rotation only logs a
+    // warning and is retried on the next append.
+    if segments::segmentation_enabled(repo_root)?
+        && let Err(error) = segments::rotate_event_segments(repo_root)
+    {
+        tracing::warn!("failed to rotate event segments: {}", error.message);
+    }
+
+    Ok(())
+}
+
+pub fn read_events_from_path(path: &Path) -> Result<ReadEventsResult, TsqError> {
+    let raw = match read_to_string(path) {
+        Ok(raw) => raw,
+        Err(error) => {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                return Ok(ReadEventsResult {
+                    events: Vec::new(),
+                    warning: None,
+                    metadata: EventLogMetadata {
+                        event_count: 0,
+                        byte_len: 0,
+                        sha256: sha256_hex(&[]),
+                    },
+                });
+            }
+            return Err(
+                TsqError::new("EVENT_READ_FAILED", "Failed reading events", 2)
+                    .with_details(io_error_value(&error)),
+            );
+        }
+    };
+    read_events_from_raw(&raw, path)
+}
+
+fn read_events_from_raw(raw: &str, path: &Path) -> Result<ReadEventsResult, TsqError> {
+    let byte_len = raw.len() as u64;
+    let sha256 = sha256_hex(raw.as_bytes());
+    let (events, warning) = parse_events_raw(raw, path, 0)?;
+
+    Ok(ReadEventsResult {
+        metadata: EventLogMetadata {
+            event_count: events.len(),
+            byte_len,
+            sha256,
+        },
+        events,
+        warning,
+    })
+}
+
+pub fn read_event_log_metadata(
+    repo_root: impl AsRef<Path>,
+    event_count: usize,
+) -> Result<EventLogMetadata, TsqError> {
+    let raw = segments::read_event_log(repo_root)?;
+    Ok(EventLogMetadata {
+        event_count,
+        byte_len: raw.len() as u64,
+        sha256: sha256_hex(&raw),
+    })
+}
+
+/// Events appended after `prefix`, or `None` when the log no longer starts
+/// with it.
+pub fn read_events_tail(
+    repo_root: impl AsRef<Path>,
+    prefix: &EventLogMetadata,
+) -> Result<Option<ReadEventsResult>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let path = get_paths(repo_root).events_file;
+    let raw = segments::read_event_log(repo_root)?;
+    if raw.len() < prefix.byte_len as usize {
+        return Ok(None);
+    }
+
+    let prefix_len = prefix.byte_len as usize;
+    if sha256_hex(&raw[..prefix_len]) != prefix.sha256 {
+        return Ok(None);
+    }
+
+    let tail = std::str::from_utf8(&raw[prefix_len..]).map_err(|error| {
+        TsqError::new("EVENTS_CORRUPT", "Events file is not valid UTF-8", 2)
+            .with_details(any_error_value(&error))
+    })?;
+    let (mut events, warning) = parse_events_raw(tail, &path, prefix.event_count)?;
+    let warning = blobs::hydrate_bodies(repo_root, &mut events)?.or(warning);
+    let metadata = EventLogMetadata {
+        event_count: prefix.event_count + events.len(),
+        byte_len: raw.len() as u64,
+        sha256: sha256_hex(&raw),
+    };
+
+    Ok(Some(ReadEventsResult {
+        events,
+        warning,
+        metadata,
+    }))
+}
+
+fn parse_events_raw(
+    raw: &str,
+    path: &Path,
+    line_offset: usize,
+) -> Result<(Vec<EventRecord>, Option<String>), TsqError> {
+    let mut lines: Vec<&str> = raw.split('\n').collect();
+    if matches!(lines.last(), Some(value) if value.is_empty()) {
+        lines.pop();
+    }
+
+    let mut events = Vec::new();
+    let mut warning = None;
+    let mut key_verified = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match decode_event_line(line, line_offset + index + 1) {
+            Ok(parsed) => match parse_event_record(&parsed, line_offset + index + 1) {
+                Ok(record) => {
+                    key_verified |= crypto::is_sealed(line);
+                    events.push(record);
+                }
+                Err(error) => return Err(error),
+            },
+            Err(error) => {
+                if index == lines.len() - 1 && is_torn_tail(&error, key_verified) {
+                    warning = Some(format!(
+                        "Ignored malformed trailing JSONL line in {}",
+                        path.display()
+                    ));
+                    break;
+                }
+                return Err(error);
+            }
+        }
+    }
+
+    Ok((events, warning))
+}
+
+/// Parses one JSONL line, decrypting it first when it was sealed.
+pub(crate) fn decode_event_line(line: &str, line_number: usize) -> Result<Value, TsqError> {
+    let opened;
+    let line = if crypto::is_sealed(line) {
+        opened = crypto::open(crypto::EVENTS, line)?;
+        opened.as_str()
+    } else {
+        line
+    };
+    serde_json::from_str::<Value>(line).map_err(|_| {
+        TsqError::new(
+            "EVENTS_CORRUPT",
+            format!("Malformed events JSONL at line {}", line_number),
+            2,
+        )
+    })
+}
+
+/// A torn final write is tolerated. A sealed line that fails to open only
+/// counts as torn once an earlier line proved the key right; otherwise a wrong
+/// key would get the last event dropped (and truncated on the next append).
+fn is_torn_tail(error: &TsqError, key_verified: bool) -> bool {
+    error.code == "EVENTS_CORRUPT" || (error.code == "DECRYPT_FAILED" && key_verified)
+}
+
+/// Identity fields of one event-log line, read without validation so
+/// `tsq doctor` can report problems that would stop a normal load.
+#[derive(Debug, Clone)]
+pub struct RawEventLine {
+    pub line: usize,
+    pub id: Option<String>,
+    pub ts: Option<String>,
+    pub event_type: Option<String>,
+}
+
+/// Lines that fail to decode (or decrypt) are skipped.
+pub fn scan_event_lines(repo_root: impl AsRef<Path>) -> Result<Vec<RawEventLine>, TsqError> {
+    let raw = segments::read_event_log(repo_root)?;
+    let raw = String::from_utf8_lossy(&raw);
+    let field = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+    Ok(raw
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(index, line)| {
+            let value = decode_event_line(line.trim_end_matches('\r'), index + 1).ok()?;
+            Some(RawEventLine {
+                line: index + 1,
+                id: field(&value, "id").or_else(|| field(&value, "event_id")),
+                ts: field(&value, "ts"),
+                event_type: field(&value, "type"),
+            })
+        })
+        .collect())
+}
+
+/// One event-log line that `tsq repair --salvage` keeps, with its original
+/// (possibly sealed) text so the rewritten log is byte-for-byte the same.
+#[derive(Debug, Clone)]
+pub struct SalvagedLine {
+    pub line: usize,
+    pub raw: String,
+    pub event: EventRecord,
+}
+
+/// One event-log line that could not be decoded or validated.
+#[derive(Debug, Clone)]
+pub struct RejectedLine {
+    pub line: usize,
+    pub raw: String,
+    pub reason: String,
+    pub value: Option<Value>,
+}
+
+/// Splits the event log into lines that parse and lines that do not. A
+/// decrypt failure is only treated as a bad line once another sealed line
+/// proved the key right; with the wrong key every line would be rejected.
+pub fn salvage_event_lines(
+    repo_root: impl AsRef<Path>,
+) -> Result<(Vec<SalvagedLine>, Vec<RejectedLine>), TsqError> {
+    let raw = segments::read_event_log(repo_root)?;
+    let raw = String::from_utf8_lossy(&raw);
+
+    let mut kept = Vec::new();
+    let mut rejected = Vec::new();
+    let mut key_verified = false;
+    let mut decrypt_error = None;
+    for (index, line) in raw.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+        let reject = |reason: String, value: Option<Value>| RejectedLine {
+            line: line_number,
+            raw: line.to_string(),
+            reason,
+            value,
+        };
+        match decode_event_line(line, line_number) {
+            Ok(value) => {
+                key_verified |= crypto::is_sealed(line);
+                match parse_event_record(&value, line_number) {
+                    Ok(event) => kept.push(SalvagedLine {
+                        line: line_number,
+                        raw: line.to_string(),
+                        event,
+                    }),
+                    Err(error) => rejected.push(reject(error.message, Some(value))),
+                }
+            }
+            Err(error) => {
+                if error.code == "DECRYPT_FAILED" && decrypt_error.is_none() {
+                    decrypt_error = Some(error.clone());
+                }
+                rejected.push(reject(error.message, None));
+            }
+        }
+    }
+
+    if let Some(error) = decrypt_error
+        && !key_verified
+    {
+        return Err(error);
+    }
+    Ok((kept, rejected))
+}
+
+/// Atomically replaces the event log with `lines`, folding any segments back
+/// into `events.jsonl`.
+pub fn rewrite_event_lines(repo_root: impl AsRef<Path>, lines: &[String]) -> Result<(), TsqError> {
+    let repo_root = repo_root.as_ref();
+    let paths = get_paths(repo_root);
+    let temp = paths
+        .events_file
+        .with_extension(format!("jsonl.tmp-{}", std::process::id()));
+    let mut payload = lines.join("\n");
+    if !payload.is_empty() {
+        payload.push('\n');
+    }
+    std::fs::write(&temp, payload)
+        .and_then(|_| std::fs::rename(&temp, &paths.events_file))
+        .map_err(|error| {
+            let _ = std::fs::remove_file(&temp);
+            TsqError::new("EVENT_APPEND_FAILED", "Failed rewriting events", 2)
+                .with_details(io_error_value(&error))
+        })?;
+    segments::clear_segments(repo_root)
+}
+
+/// Appends one JSON record per entry to `.tasque/quarantine.jsonl`.
+pub fn append_quarantine(repo_root: impl AsRef<Path>, entries: &[Value]) -> Result<(), TsqError> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let paths = get_paths(repo_root);
+    let mut payload = String::new();
+    for entry in entries {
+        payload.push_str(&entry.to_string());
+        payload.push('\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&paths.quarantine_file)
+        .and_then(|mut handle| {
+            handle.write_all(payload.as_bytes())?;
+            handle.sync_all()
+        })
+        .map_err(|error| {
+            TsqError::new("EVENT_APPEND_FAILED", "Failed writing quarantine file", 2)
+                .with_details(io_error_value(&error))
+        })
+}
+
+/// Metadata for the log prefix that ends with its `event_count`-th event, or
+/// `None` when the log holds fewer events.
+pub fn event_log_prefix(
+    repo_root: impl AsRef<Path>,
+    event_count: usize,
+) -> Result<Option<EventLogMetadata>, TsqError> {
+    let raw = segments::read_event_log(repo_root)?;
+    if event_count == 0 {
+        return Ok(Some(EventLogMetadata {
+            event_count,
+            byte_len: 0,
+            sha256: sha256_hex(&[]),
+        }));
+    }
+    let mut seen = 0;
+    let mut offset = 0;
+    for line in raw.split_inclusive(|byte| *byte == b'\n') {
+        offset += line.len();
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        seen += 1;
+        if seen == event_count {
+            return Ok(Some(EventLogMetadata {
+                event_count,
+                byte_len: offset as u64,
+                sha256: sha256_hex(&raw[..offset]),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Reads the whole log: every segment in manifest order, then `events.jsonl`.
+pub fn read_events(repo_root: impl AsRef<Path>) -> Result<ReadEventsResult, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let path = get_paths(repo_root).events_file;
+    let raw = segments::read_event_log(repo_root)?;
+    let raw = String::from_utf8(raw).map_err(|error| {
+        TsqError::new("EVENT_READ_FAILED", "Failed reading events", 2)
+            .with_details(any_error_value(&error))
+    })?;
+    let mut result = read_events_from_raw(&raw, &path)?;
+    if let Some(warning) = blobs::hydrate_bodies(repo_root, &mut result.events)? {
+        result.warning.get_or_insert(warning);
+    }
+    Ok(result)
+}
+
+fn io_error_value(error: &std::io::Error) -> Value {
+    serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()})
+}
+
+fn any_error_value(error: &impl std::fmt::Display) -> Value {
+    serde_json::json!({"message": error.to_string()})
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
@@ -20,12 +20,15 @@ fn git_error(message: impl Into<String>, stderr: impl Into<String>) -> TsqError
 }
 
 fn run_git(repo: &Path, args: &[&str]) -> Result<String, TsqError> {
+    let span = tracing::debug_span!("git.run", args = %args.join(" "), ok = tracing::field::Empty)
+        .entered();
     let output = Command::new("git")
         .args(args)
         .current_dir(repo)
         .output()
         .map_err(|_| git_not_available())?;
 
+    span.record("ok", output.status.success());
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
         return Err(git_error(
@@ -38,11 +41,14 @@ fn run_git(repo: &Path, args: &[&str]) -> Result<String, TsqError> {
 }
 
 fn run_git_status(repo: &Path, args: &[&str]) -> Result<bool, TsqError> {
+    let span = tracing::debug_span!("git.run", args = %args.join(" "), ok = tracing::field::Empty)
+        .entered();
     let output = Command::new("git")
         .args(args)
         .current_dir(repo)
         .output()
         .map_err(|_| git_not_available())?;
+    span.record("ok", output.status.success());
 
     Ok(output.status.success())
 }
@@ -200,6 +206,80 @@ pub fn push_current_set_upstream(
     Ok(())
 }
 
+/// Fetch `branch` from `remote` into its remote-tracking ref.
+/// Returns false when the remote does not have the branch yet.
+pub fn fetch_branch_from(repo_root: &Path, remote: &str, branch: &str) -> Result<bool, TsqError> {
+    validate_branch_name(branch)?;
+    let refspec = format!("refs/heads/{}", branch);
+    if !run_git_status(
+        repo_root,
+        &["ls-remote", "--exit-code", "--heads", remote, &refspec],
+    )? {
+        return Ok(false);
+    }
+    let remote_ref = format!("+refs/heads/{branch}:refs/remotes/{remote}/{branch}");
+    run_git(repo_root, &["fetch", remote, &remote_ref])?;
+    Ok(true)
+}
+
+/// Rebase the current branch onto `<remote>/<branch>`, resolving event-log
+/// merges with `merge_driver`. Aborts the rebase on failure so the worktree
+/// is left as it was. Returns the number of incoming remote commits.
+pub fn rebase_onto_remote(
+    repo_root: &Path,
+    remote: &str,
+    branch: &str,
+    merge_driver: &str,
+) -> Result<usize, TsqError> {
+    validate_branch_name(branch)?;
+    let upstream = format!("refs/remotes/{remote}/{branch}");
+    let incoming = run_git(
+        repo_root,
+        &["rev-list", "--count", &format!("HEAD..{upstream}")],
+    )?
+    .parse::<usize>()
+    .unwrap_or(0);
+    if incoming == 0 {
+        return Ok(0);
+    }
+
+    let driver = format!("merge.tasque-events.driver={merge_driver}");
+    if let Err(error) = run_git(repo_root, &["-c", &driver, "rebase", &upstream]) {
+        let _ = run_git_status(repo_root, &["rebase", "--abort"]);
+        let mut rebase_error = TsqError::new(
+            "SYNC_REBASE_FAILED",
+            format!("failed rebasing '{}' onto {}/{}", branch, remote, branch),
+            2,
+        );
+        if let Some(details) = error.details {
+            rebase_error = rebase_error.with_details(details);
+        }
+        return Err(rebase_error);
+    }
+    Ok(incoming)
+}
+
+/// Shallow-clones `url` into `dest`; `dest` must not exist yet.
+pub fn clone_shallow(url: &str, dest: &Path) -> Result<(), TsqError> {
+    let parent = dest.parent().unwrap_or(Path::new("."));
+    let dest = dest.to_string_lossy();
+    run_git(
+        parent,
+        &["clone", "--depth", "1", "--quiet", "--", url, dest.as_ref()],
+    )?;
+    Ok(())
+}
+
+/// Moves a shallow clone to the tip of its origin's default branch.
+pub fn update_shallow_clone(repo: &Path) -> Result<(), TsqError> {
+    run_git(
+        repo,
+        &["fetch", "--depth", "1", "--quiet", "origin", "HEAD"],
+    )?;
+    run_git(repo, &["reset", "--hard", "--quiet", "FETCH_HEAD"])?;
+    Ok(())
+}
+
 /// Returns true if the path is inside a git working tree.
 pub fn is_git_repo(repo_root: &Path) -> bool {
     run_git_status(repo_root, &["rev-parse", "--is-inside-work-tree"]).unwrap_or(false)
@@ -444,8 +524,11 @@ pub fn create_orphan_branch(
 
     // Create .gitattributes
     let gitattributes = tmp_path.join(".gitattributes");
-    std::fs::write(&gitattributes, ".tasque/events.jsonl merge=tasque-events\n")
-        .map_err(|e| git_error("Failed writing .gitattributes", e.to_string()))?;
+    std::fs::write(
+        &gitattributes,
+        format!("{}\n", EVENT_LOG_ATTRIBUTES.join("\n")),
+    )
+    .map_err(|e| git_error("Failed writing .gitattributes", e.to_string()))?;
 
     // Stage and commit
     run_git(tmp_path, &["add", "."])?;
@@ -478,9 +561,133 @@ pub fn commit_worktree(wt_path: &Path, message: &str) -> Result<bool, TsqError>
     Ok(true)
 }
 
+/// Commits on HEAD missing from `<remote>/<branch>` and vice versa, or
+/// `None` when the remote-tracking ref has not been fetched.
+pub fn ahead_behind(
+    repo_root: &Path,
+    remote: &str,
+    branch: &str,
+) -> Result<Option<(usize, usize)>, TsqError> {
+    validate_branch_name(branch)?;
+    let upstream = format!("refs/remotes/{remote}/{branch}");
+    if !run_git_status(repo_root, &["show-ref", "--verify", "--quiet", &upstream])? {
+        return Ok(None);
+    }
+    let out = run_git(
+        repo_root,
+        &[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("HEAD...{upstream}"),
+        ],
+    )?;
+    let mut counts = out.split_whitespace().map(|value| value.parse::<usize>());
+    match (counts.next(), counts.next()) {
+        (Some(Ok(ahead)), Some(Ok(behind))) => Ok(Some((ahead, behind))),
+        _ => Err(git_error("unexpected rev-list output", out)),
+    }
+}
+
+/// Contents of `rel_path` at HEAD, or `None` when HEAD or the file is missing.
+pub fn show_head_file(repo_root: &Path, rel_path: &str) -> Result<Option<String>, TsqError> {
+    let spec = format!("HEAD:{rel_path}");
+    if !run_git_status(repo_root, &["cat-file", "-e", &spec])? {
+        return Ok(None);
+    }
+    Ok(Some(run_git(repo_root, &["show", &spec])?))
+}
+
+/// Remove a linked worktree, discarding untracked files, and prune its
+/// administrative entry.
+pub fn remove_worktree(repo_root: &Path, worktree: &Path) -> Result<(), TsqError> {
+    let wt = worktree.to_string_lossy().to_string();
+    run_git(repo_root, &["worktree", "remove", "--force", &wt])?;
+    let _ = run_git_status(repo_root, &["worktree", "prune"]);
+    Ok(())
+}
+
+/// Force-delete a local branch. Remote branches are left alone.
+pub fn delete_branch(repo_root: &Path, branch: &str) -> Result<(), TsqError> {
+    validate_branch_name(branch)?;
+    run_git(repo_root, &["branch", "-D", branch])?;
+    Ok(())
+}
+
+/// True when the working tree has staged, unstaged, or untracked changes.
+pub fn has_uncommitted_changes(repo_root: &Path) -> Result<bool, TsqError> {
+    Ok(!run_git(repo_root, &["status", "--porcelain"])?.is_empty())
+}
+
+/// Value of a git config key, or `None` when unset.
+pub fn config_value(repo_root: &Path, key: &str) -> Result<Option<String>, TsqError> {
+    if !run_git_status(repo_root, &["config", "--get", key])? {
+        return Ok(None);
+    }
+    Ok(Some(run_git(repo_root, &["config", "--get", key])?))
+}
+
+/// Full SHA and subject line of the commit `rev` names.
+pub fn commit_summary(repo_root: &Path, rev: &str) -> Result<(String, String), TsqError> {
+    let spec = format!("{}^{{commit}}", rev);
+    if rev.starts_with('-')
+        || !run_git_status(repo_root, &["rev-parse", "--verify", "--quiet", &spec])?
+    {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!("{} does not name a commit", rev),
+            1,
+        ));
+    }
+    let sha = run_git(repo_root, &["rev-parse", "--verify", &spec])?;
+    let subject = run_git(repo_root, &["log", "-1", "--format=%s", &sha])?;
+    Ok((sha, subject))
+}
+
+/// SHA and full message of every commit reachable from HEAD, newest first,
+/// stopping at `since` when given. Empty when the repo has no commits.
+pub fn commit_messages(
+    repo_root: &Path,
+    since: Option<&str>,
+) -> Result<Vec<(String, String)>, TsqError> {
+    if !run_git_status(repo_root, &["rev-parse", "--verify", "--quiet", "HEAD"])? {
+        return Ok(Vec::new());
+    }
+    let mut args = vec!["log".to_string(), "--format=%H%x1f%B%x1e".to_string()];
+    if let Some(since) = since {
+        let (sha, _) = commit_summary(repo_root, since)?;
+        args.push(format!("{}..HEAD", sha));
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let out = run_git(repo_root, &args)?;
+    Ok(out
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let (sha, message) = record.trim().split_once('\u{1f}')?;
+            Some((sha.to_string(), message.trim().to_string()))
+        })
+        .collect())
+}
+
+/// Committer timestamp (unix seconds) of HEAD, or `None` without commits.
+pub fn head_commit_epoch(repo_root: &Path) -> Result<Option<i64>, TsqError> {
+    if !run_git_status(repo_root, &["rev-parse", "--verify", "--quiet", "HEAD"])? {
+        return Ok(None);
+    }
+    let out = run_git(repo_root, &["log", "-1", "--format=%ct"])?;
+    Ok(out.parse::<i64>().ok())
+}
+
+/// Routes the active log, its monthly segments, and the segment manifest to
+/// the tasque-events merge driver.
+pub const EVENT_LOG_ATTRIBUTES: [&str; 3] = [
+    ".tasque/events.jsonl merge=tasque-events",
+    ".tasque/events-*.jsonl merge=tasque-events",
+    ".tasque/events.manifest.json merge=tasque-events",
+];
+
 pub fn ensure_gitattributes_entry(repo_root: &Path) -> Result<bool, TsqError> {
     let path = repo_root.join(".gitattributes");
-    let line = ".tasque/events.jsonl merge=tasque-events";
     let existing = match std::fs::read_to_string(&path) {
         Ok(value) => value,
         Err(error) => {
@@ -495,7 +702,11 @@ pub fn ensure_gitattributes_entry(repo_root: &Path) -> Result<bool, TsqError> {
         }
     };
 
-    if existing.lines().any(|value| value.trim() == line) {
+    let missing: Vec<&str> = EVENT_LOG_ATTRIBUTES
+        .into_iter()
+        .filter(|line| !existing.lines().any(|value| value.trim() == *line))
+        .collect();
+    if missing.is_empty() {
         return Ok(false);
     }
 
@@ -503,8 +714,10 @@ pub fn ensure_gitattributes_entry(repo_root: &Path) -> Result<bool, TsqError> {
     if !updated.is_empty() && !updated.ends_with('\n') {
         updated.push('\n');
     }
-    updated.push_str(line);
-    updated.push('\n');
+    for line in missing {
+        updated.push_str(line);
+        updated.push('\n');
+    }
     std::fs::write(&path, updated)
         .map_err(|error| git_error("Failed writing .gitattributes", error.to_string()))?;
     Ok(true)
@@ -713,10 +926,11 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let path = tmp.path().join(".gitattributes");
         std::fs::write(&path, ".tasque/events.jsonl merge=tasque-events\n").unwrap();
+        assert!(ensure_gitattributes_entry(tmp.path()).unwrap());
         let updated = ensure_gitattributes_entry(tmp.path()).unwrap();
         assert!(!updated);
         let content = std::fs::read_to_string(path).unwrap();
-        assert_eq!(content, ".tasque/events.jsonl merge=tasque-events\n");
+        assert_eq!(content, format!("{}\n", EVENT_LOG_ATTRIBUTES.join("\n")));
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use crate::errors::TsqError;
+use crate::store::config::read_lock_ttl_ms;
 use crate::store::paths::get_paths;
 use chrono::{DateTime, Utc};
 use rand::RngExt;
@@ -11,6 +12,10 @@ use std::thread::sleep;
 use std::time::{Duration, SystemTime};
 use sysinfo::{Pid, System};
 
+/// Set for blocking hooks, which run while the parent tsq holds the write
+/// lock; a write from inside one fails fast instead of timing out on it.
+pub const IN_BLOCKING_HOOK_ENV: &str = "TSQ_IN_BLOCKING_HOOK";
+
 const STALE_LOCK_MS: i64 = 30_000;
 const JITTER_MIN_MS: u64 = 20;
 const JITTER_MAX_MS: u64 = 80;
@@ -37,6 +42,10 @@ fn parse_lock_payload(raw: &str) -> Option<LockPayload> {
     serde_json::from_str(raw).ok()
 }
 
+fn current_host() -> String {
+    System::host_name().unwrap_or_else(|| "unknown".to_string())
+}
+
 fn is_process_dead(pid: u32) -> bool {
     if pid == std::process::id() {
         return false;
@@ -46,7 +55,28 @@ fn is_process_dead(pid: u32) -> bool {
     system.process(Pid::from_u32(pid)).is_none()
 }
 
-fn try_cleanup_stale_lock(lock_file: &Path, current_host: &str) -> bool {
+fn lock_ttl_ms(tasque_dir: &Path) -> i64 {
+    tasque_dir
+        .parent()
+        .and_then(read_lock_ttl_ms)
+        .map(|ttl| i64::try_from(ttl).unwrap_or(i64::MAX))
+        .unwrap_or(STALE_LOCK_MS)
+}
+
+fn lock_age_ms(payload: &LockPayload) -> Option<i64> {
+    let created_at = DateTime::parse_from_rfc3339(&payload.created_at).ok()?;
+    Some(Utc::now().timestamp_millis() - created_at.with_timezone(&Utc).timestamp_millis())
+}
+
+/// Only a lock from this host can be checked for a live owner, so locks from
+/// other hosts never expire on their own.
+fn is_stale_payload(payload: &LockPayload, current_host: &str, ttl_ms: i64) -> bool {
+    payload.host == current_host
+        && lock_age_ms(payload).is_some_and(|age| age >= ttl_ms)
+        && is_process_dead(payload.pid)
+}
+
+fn try_cleanup_stale_lock(lock_file: &Path, current_host: &str, ttl_ms: i64) -> bool {
     let raw = match read_to_string(lock_file) {
         Ok(raw) => raw,
         Err(error) => return error.kind() == std::io::ErrorKind::NotFound,
@@ -57,21 +87,7 @@ fn try_cleanup_stale_lock(lock_file: &Path, current_host: &str) -> bool {
         None => return false,
     };
 
-    if payload.host != current_host {
-        return false;
-    }
-
-    let created_at = match DateTime::parse_from_rfc3339(&payload.created_at) {
-        Ok(value) => value.with_timezone(&Utc),
-        Err(_) => return false,
-    };
-
-    let now = Utc::now();
-    if now.timestamp_millis() - created_at.timestamp_millis() < STALE_LOCK_MS {
-        return false;
-    }
-
-    if !is_process_dead(payload.pid) {
+    if !is_stale_payload(&payload, current_host, ttl_ms) {
         return false;
     }
 
@@ -114,7 +130,8 @@ fn acquire_write_lock_with_timeout(
     let deadline = SystemTime::now()
         .checked_add(Duration::from_millis(timeout_ms))
         .unwrap_or(SystemTime::now());
-    let host = System::host_name().unwrap_or_else(|| "unknown".to_string());
+    let host = current_host();
+    let ttl_ms = lock_ttl_ms(tasque_dir);
 
     loop {
         let payload = LockPayload {
@@ -174,7 +191,8 @@ fn acquire_write_lock_with_timeout(
             }
         }
 
-        if try_cleanup_stale_lock(lock_file, &host) {
+        if try_cleanup_stale_lock(lock_file, &host, ttl_ms) {
+            tracing::info!(path = %lock_file.display(), "lock.stale_removed");
             continue;
         }
 
@@ -272,6 +290,102 @@ pub fn force_remove_lock(repo_root: impl AsRef<Path>) -> Result<Option<LockPaylo
     Ok(payload)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockStatus {
+    pub locked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<LockPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_ms: Option<i64>,
+    /// `None` when the owner is on another host and cannot be checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_alive: Option<bool>,
+    pub ttl_ms: i64,
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockBreakResult {
+    pub broken: bool,
+    pub forced: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<LockPayload>,
+}
+
+pub fn lock_status(repo_root: impl AsRef<Path>) -> Result<LockStatus, TsqError> {
+    let paths = get_paths(repo_root);
+    let ttl_ms = lock_ttl_ms(&paths.tasque_dir);
+    let raw = match read_to_string(&paths.lock_file) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(LockStatus {
+                locked: false,
+                owner: None,
+                age_ms: None,
+                owner_alive: None,
+                ttl_ms,
+                stale: false,
+            });
+        }
+        Err(error) => {
+            return Err(
+                TsqError::new("LOCK_CHECK_FAILED", "Failed reading lock file", 2)
+                    .with_details(io_error_value(&error)),
+            );
+        }
+    };
+    let host = current_host();
+    let owner = parse_lock_payload(&raw);
+    Ok(LockStatus {
+        locked: true,
+        age_ms: owner.as_ref().and_then(lock_age_ms),
+        owner_alive: owner
+            .as_ref()
+            .filter(|owner| owner.host == host)
+            .map(|owner| !is_process_dead(owner.pid)),
+        stale: owner
+            .as_ref()
+            .is_some_and(|owner| is_stale_payload(owner, &host, ttl_ms)),
+        owner,
+        ttl_ms,
+    })
+}
+
+/// Removes a stale lock, or any lock when `force` is set.
+pub fn break_lock(repo_root: impl AsRef<Path>, force: bool) -> Result<LockBreakResult, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let status = lock_status(repo_root)?;
+    if !status.locked {
+        return Ok(LockBreakResult {
+            broken: false,
+            forced: force,
+            owner: None,
+        });
+    }
+    if force {
+        let owner = force_remove_lock(repo_root)?;
+        return Ok(LockBreakResult {
+            broken: true,
+            forced: true,
+            owner,
+        });
+    }
+    let paths = get_paths(repo_root);
+    if !status.stale || !try_cleanup_stale_lock(&paths.lock_file, &current_host(), status.ttl_ms) {
+        return Err(TsqError::new(
+            "LOCK_HELD",
+            "Write lock is not stale; use --force to break it",
+            3,
+        )
+        .with_details(serde_json::to_value(&status).unwrap_or(Value::Null)));
+    }
+    Ok(LockBreakResult {
+        broken: true,
+        forced: false,
+        owner: status.owner,
+    })
+}
+
 pub fn lock_exists(repo_root: impl AsRef<Path>) -> Result<bool, TsqError> {
     let paths = get_paths(repo_root);
     match read_to_string(&paths.lock_file) {
@@ -298,10 +412,22 @@ pub fn with_write_lock<T, F>(repo_root: impl AsRef<Path>, f: F) -> Result<T, Tsq
 where
     F: FnOnce() -> Result<T, TsqError>,
 {
+    if std::env::var_os(IN_BLOCKING_HOOK_ENV).is_some_and(|value| !value.is_empty()) {
+        return Err(TsqError::new(
+            "HOOK_WRITE_FORBIDDEN",
+            "blocking hooks run while tsq holds the write lock and cannot write; use an advisory hook for follow-up writes",
+            1,
+        ));
+    }
     let paths = get_paths(repo_root);
-    let lock = acquire_write_lock(&paths.lock_file, &paths.tasque_dir)?;
+    let lock = {
+        let _span = tracing::debug_span!("lock.acquire").entered();
+        acquire_write_lock(&paths.lock_file, &paths.tasque_dir)?
+    };
+    let held = tracing::trace_span!("lock.held").entered();
 
     let result = f();
+    drop(held);
     match result {
         Ok(value) => {
             release_write_lock(&paths.lock_file, &lock)?;
@@ -0,0 +1,586 @@
+use crate::domain::projector::apply_events;
+use crate::domain::state::create_empty_state;
+use crate::errors::TsqError;
+use crate::store::crypto;
+use crate::store::events::{decode_event_line, read_events_from_path};
+use crate::store::segments::{SEGMENT_MANIFEST_SCHEMA_VERSION, parse_manifest};
+use crate::types::{EventRecord, EventSegmentManifest, MergeDriverOutcome};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Extract the canonical event ID from an EventRecord.
+/// Prefers `id`, falls back to `event_id`.
+fn event_id(record: &EventRecord) -> Option<&str> {
+    record.id.as_deref().or(record.event_id.as_deref())
+}
+
+/// Serialize an EventRecord to its canonical JSON string for comparison.
+/// We use serde_json's deterministic serialization (keys in struct order).
+fn canonical_json(record: &EventRecord) -> Result<String, TsqError> {
+    serde_json::to_string(record).map_err(|e| {
+        TsqError::new(
+            "MERGE_SERIALIZE_FAILED",
+            format!("Failed serializing event for merge comparison: {}", e),
+            2,
+        )
+    })
+}
+
+/// Merge three versions of an events.jsonl file (ancestor, ours, theirs).
+///
+/// Algorithm:
+/// 1. Read all three files
+/// 2. Detect conflicts: same ID but different payload across files
+/// 3. Interleave the three logs by (ts, id), never reordering events within a
+///    single log, and emit each event ID once (identical events dedupe)
+/// 4. Replay the merged events to validate causal ordering. A later segment
+///    of a rotated log builds on earlier segments and cannot replay alone, so
+///    this only applies when every input replays on its own
+/// 5. Write merged result to `ours` (git merge convention: result goes to %A),
+///    keeping each encrypted event's sealed line and sealing plaintext events
+///    when any input was encrypted
+///
+/// Swapping `ours` and `theirs` yields the same merged log, so concurrent
+/// appends on two branches merge cleanly regardless of merge direction.
+pub fn merge_events_files(
+    ancestor: &Path,
+    ours: &Path,
+    theirs: &Path,
+) -> Result<MergeDriverOutcome, TsqError> {
+    let ancestor_result = read_events_from_path(ancestor)?;
+    let ours_result = read_events_from_path(ours)?;
+    let theirs_result = read_events_from_path(theirs)?;
+
+    // Map: event_id -> canonical_json
+    let mut seen: HashMap<String, String> = HashMap::new();
+    let mut conflicting_ids: Vec<String> = Vec::new();
+    let mut total_input = 0usize;
+
+    let all_sources = [
+        ancestor_result.events,
+        ours_result.events,
+        theirs_result.events,
+    ];
+
+    for events in &all_sources {
+        for record in events {
+            total_input += 1;
+            let id = match event_id(record) {
+                Some(id) => id.to_string(),
+                None => {
+                    return Err(TsqError::new(
+                        "MERGE_MISSING_ID",
+                        "Event missing id field during merge",
+                        2,
+                    ));
+                }
+            };
+
+            let json = canonical_json(record)?;
+
+            match seen.get(&id) {
+                Some(existing_json) => {
+                    if *existing_json != json && !conflicting_ids.contains(&id) {
+                        conflicting_ids.push(id.clone());
+                    }
+                    // Same ID + same payload = duplicate, skip
+                }
+                None => {
+                    seen.insert(id, json);
+                }
+            }
+        }
+    }
+
+    conflicting_ids.sort();
+
+    if !conflicting_ids.is_empty() {
+        return Ok(MergeDriverOutcome {
+            total_events: seen.len(),
+            duplicates_removed: total_input.saturating_sub(seen.len()),
+            conflict: true,
+            conflicting_ids,
+        });
+    }
+
+    let merged = interleave_sources(&all_sources);
+    let duplicates_removed = total_input.saturating_sub(merged.len());
+    let total_events = merged.len();
+
+    if let Err(e) = apply_events(&create_empty_state(), &merged) {
+        let standalone = all_sources
+            .iter()
+            .all(|events| apply_events(&create_empty_state(), events).is_ok());
+        if standalone {
+            return Err(TsqError::new(
+                "MERGE_REPLAY_FAILED",
+                format!("Merged event stream failed replay validation: {}", e),
+                2,
+            ));
+        }
+    }
+
+    // Write merged result to ours path (git expects result at %A)
+    let sealed = sealed_lines(&[ancestor, ours, theirs])?;
+    write_events_to_path(ours, &merged, &sealed)?;
+
+    Ok(MergeDriverOutcome {
+        total_events,
+        duplicates_removed,
+        conflict: false,
+        conflicting_ids: Vec::new(),
+    })
+}
+
+/// True when either side is an `events.manifest.json`, which git routes to
+/// the same driver as the event segments it lists.
+pub fn is_segment_manifest(ours: &Path, theirs: &Path) -> bool {
+    [ours, theirs].iter().any(|path| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .is_some_and(|value| value.get("segments").is_some())
+    })
+}
+
+/// Merges three versions of `events.manifest.json` into `ours`: every
+/// segment listed on any side, ordered by month. Returns the segment count.
+pub fn merge_segment_manifests(
+    ancestor: &Path,
+    ours: &Path,
+    theirs: &Path,
+) -> Result<usize, TsqError> {
+    let mut merged = EventSegmentManifest {
+        schema_version: SEGMENT_MANIFEST_SCHEMA_VERSION,
+        segments: Vec::new(),
+    };
+    for path in [ancestor, ours, theirs] {
+        let raw = fs::read_to_string(path).unwrap_or_default();
+        if raw.trim().is_empty() {
+            continue;
+        }
+        for segment in parse_manifest(&raw)?.segments {
+            if !merged.segments.contains(&segment) {
+                merged.segments.push(segment);
+            }
+        }
+    }
+    merged.segments.sort_by(|a, b| a.month.cmp(&b.month));
+    let payload = serde_json::to_string_pretty(&merged).map_err(|e| {
+        TsqError::new(
+            "MERGE_SERIALIZE_FAILED",
+            format!("Failed serializing merged segment manifest: {}", e),
+            2,
+        )
+    })?;
+    fs::write(ours, format!("{}\n", payload)).map_err(|e| {
+        TsqError::new(
+            "MERGE_WRITE_FAILED",
+            format!(
+                "Failed writing merged manifest to {}: {}",
+                ours.display(),
+                e
+            ),
+            2,
+        )
+    })?;
+    Ok(merged.segments.len())
+}
+
+/// K-way merge of event logs: repeatedly emit the smallest (ts, id) head.
+/// Each log keeps its own append order, so an event is never emitted before
+/// an event that preceded it in the same file, even under clock skew.
+/// Every ID is known to be present, so ties only occur for duplicates.
+pub(crate) fn interleave_sources(sources: &[Vec<EventRecord>]) -> Vec<EventRecord> {
+    let mut cursors = vec![0usize; sources.len()];
+    let mut emitted: HashSet<String> = HashSet::new();
+    let mut merged: Vec<EventRecord> = Vec::new();
+
+    loop {
+        let mut next: Option<&EventRecord> = None;
+        for (source, cursor) in sources.iter().zip(cursors.iter_mut()) {
+            while source
+                .get(*cursor)
+                .and_then(event_id)
+                .is_some_and(|id| emitted.contains(id))
+            {
+                *cursor += 1;
+            }
+            if let Some(head) = source.get(*cursor)
+                && next.is_none_or(|current| merge_key(head) < merge_key(current))
+            {
+                next = Some(head);
+            }
+        }
+        let Some(record) = next else {
+            break;
+        };
+        if let Some(id) = event_id(record) {
+            emitted.insert(id.to_string());
+        }
+        merged.push(record.clone());
+    }
+
+    merged
+}
+
+fn merge_key(record: &EventRecord) -> (&str, &str) {
+    (record.ts.as_str(), event_id(record).unwrap_or(""))
+}
+
+/// The sealed lines of the inputs keyed by event ID. Sealing uses a fresh
+/// nonce each time, so the merged log reuses each event's existing line
+/// instead of re-encrypting it and rewriting the whole file.
+fn sealed_lines(paths: &[&Path]) -> Result<HashMap<String, String>, TsqError> {
+    let mut sealed = HashMap::new();
+    for path in paths {
+        let raw = fs::read_to_string(path).unwrap_or_default();
+        for (index, line) in raw.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if !crypto::is_sealed(line) {
+                continue;
+            }
+            let record = decode_event_line(line, index + 1)?;
+            if let Some(id) = ["id", "event_id"]
+                .iter()
+                .find_map(|field| record.get(*field).and_then(|value| value.as_str()))
+            {
+                sealed
+                    .entry(id.to_string())
+                    .or_insert_with(|| line.to_string());
+            }
+        }
+    }
+    Ok(sealed)
+}
+
+/// Write merged events to a file as JSONL. When any input was encrypted,
+/// events keep their sealed line and plaintext ones are sealed with the same
+/// key.
+fn write_events_to_path(
+    path: &Path,
+    events: &[EventRecord],
+    sealed: &HashMap<String, String>,
+) -> Result<(), TsqError> {
+    let key = match sealed.values().next() {
+        Some(line) => Some(crypto::key_for(line)?),
+        None => None,
+    };
+
+    let mut file = fs::File::create(path).map_err(|e| {
+        TsqError::new(
+            "MERGE_WRITE_FAILED",
+            format!("Failed writing merged events to {}: {}", path.display(), e),
+            2,
+        )
+    })?;
+
+    for record in events {
+        let line = match event_id(record).and_then(|id| sealed.get(id)) {
+            Some(line) => line.clone(),
+            None => {
+                let line = serde_json::to_string(record).map_err(|e| {
+                    TsqError::new(
+                        "MERGE_SERIALIZE_FAILED",
+                        format!("Failed serializing merged event: {}", e),
+                        2,
+                    )
+                })?;
+                match key {
+                    Some(key) => crypto::seal_with(key, crypto::EVENTS, &line)?,
+                    None => line,
+                }
+            }
+        };
+        writeln!(file, "{}", line).map_err(|e| {
+            TsqError::new(
+                "MERGE_WRITE_FAILED",
+                format!("Failed writing merged event line: {}", e),
+                2,
+            )
+        })?;
+    }
+
+    file.sync_all().map_err(|e| {
+        TsqError::new(
+            "MERGE_WRITE_FAILED",
+            format!("Failed syncing merged events file: {}", e),
+            2,
+        )
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventType;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use serde_json::Map;
+    use tempfile::TempDir;
+
+    fn make_event(id: &str, title: &str) -> EventRecord {
+        let mut payload = Map::new();
+        payload.insert(
+            "title".to_string(),
+            serde_json::Value::String(title.to_string()),
+        );
+        EventRecord {
+            id: Some(id.to_string()),
+            event_id: Some(id.to_string()),
+            ts: "2026-01-01T00:00:00Z".to_string(),
+            actor: "test".to_string(),
+            event_type: EventType::TaskCreated,
+            task_id: format!("tsq-{}", id),
+            payload,
+        }
+    }
+
+    fn write_events(dir: &Path, name: &str, events: &[EventRecord]) -> std::path::PathBuf {
+        let path = dir.join(name);
+        let mut f = fs::File::create(&path).unwrap();
+        for ev in events {
+            writeln!(f, "{}", serde_json::to_string(ev).unwrap()).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_disjoint_merge() {
+        let tmp = TempDir::new().unwrap();
+        let base = vec![make_event("01A", "base1")];
+        let ours_events = vec![make_event("01A", "base1"), make_event("01B", "ours1")];
+        let theirs_events = vec![make_event("01A", "base1"), make_event("01C", "theirs1")];
+
+        let ancestor = write_events(tmp.path(), "ancestor.jsonl", &base);
+        let ours = write_events(tmp.path(), "ours.jsonl", &ours_events);
+        let theirs = write_events(tmp.path(), "theirs.jsonl", &theirs_events);
+
+        let result = merge_events_files(&ancestor, &ours, &theirs).unwrap();
+        assert!(!result.conflict);
+        assert_eq!(result.total_events, 3);
+        assert!(result.duplicates_removed > 0);
+
+        // Verify merged file content
+        let merged = read_events_from_path(&ours).unwrap();
+        assert_eq!(merged.events.len(), 3);
+        // Equal timestamps fall back to event ID order
+        let ids: Vec<&str> = merged
+            .events
+            .iter()
+            .map(|e| e.id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["01A", "01B", "01C"]);
+    }
+
+    #[test]
+    fn test_duplicate_dedup() {
+        let tmp = TempDir::new().unwrap();
+        let ev = make_event("01A", "same");
+        let base = vec![ev.clone()];
+        let ours_events = vec![ev.clone(), make_event("01B", "new")];
+        let theirs_events = vec![ev.clone()];
+
+        let ancestor = write_events(tmp.path(), "ancestor.jsonl", &base);
+        let ours = write_events(tmp.path(), "ours.jsonl", &ours_events);
+        let theirs = write_events(tmp.path(), "theirs.jsonl", &theirs_events);
+
+        let result = merge_events_files(&ancestor, &ours, &theirs).unwrap();
+        assert!(!result.conflict);
+        assert_eq!(result.total_events, 2); // 01A + 01B
+    }
+
+    #[test]
+    fn test_conflict_on_divergent_payload() {
+        let tmp = TempDir::new().unwrap();
+        let base = vec![make_event("01A", "original")];
+        let ours_events = vec![make_event("01A", "changed-ours")];
+        let theirs_events = vec![make_event("01A", "changed-theirs")];
+
+        let ancestor = write_events(tmp.path(), "ancestor.jsonl", &base);
+        let ours = write_events(tmp.path(), "ours.jsonl", &ours_events);
+        let theirs = write_events(tmp.path(), "theirs.jsonl", &theirs_events);
+
+        let result = merge_events_files(&ancestor, &ours, &theirs).unwrap();
+        assert!(result.conflict);
+        assert_eq!(result.conflicting_ids, vec!["01A"]);
+    }
+
+    #[test]
+    fn test_empty_ancestor() {
+        let tmp = TempDir::new().unwrap();
+        let ancestor = write_events(tmp.path(), "ancestor.jsonl", &[]);
+        let ours = write_events(tmp.path(), "ours.jsonl", &[make_event("01A", "a")]);
+        let theirs = write_events(tmp.path(), "theirs.jsonl", &[make_event("01B", "b")]);
+
+        let result = merge_events_files(&ancestor, &ours, &theirs).unwrap();
+        assert!(!result.conflict);
+        assert_eq!(result.total_events, 2);
+        assert_eq!(result.duplicates_removed, 0);
+    }
+
+    #[test]
+    fn test_segment_depending_on_earlier_segments_still_merges() {
+        let tmp = TempDir::new().unwrap();
+        let ours_events = vec![make_timed_event(
+            "01B",
+            "2026-02-01T00:00:00Z",
+            EventType::TaskUpdated,
+            "tsq-01A",
+        )];
+        let theirs_events = vec![make_timed_event(
+            "01C",
+            "2026-02-02T00:00:00Z",
+            EventType::TaskUpdated,
+            "tsq-01A",
+        )];
+        let ancestor = write_events(tmp.path(), "ancestor.jsonl", &[]);
+        let ours = write_events(tmp.path(), "ours.jsonl", &ours_events);
+        let theirs = write_events(tmp.path(), "theirs.jsonl", &theirs_events);
+
+        let result = merge_events_files(&ancestor, &ours, &theirs).unwrap();
+        assert!(!result.conflict);
+        assert_eq!(result.total_events, 2);
+    }
+
+    #[test]
+    fn test_segment_manifests_merge_as_a_month_ordered_union() {
+        let tmp = TempDir::new().unwrap();
+        let manifest = |months: &[&str]| {
+            let segments: Vec<_> = months
+                .iter()
+                .map(|month| serde_json::json!({"file": format!("events-{month}.jsonl"), "month": month}))
+                .collect();
+            serde_json::json!({"schema_version": 1, "segments": segments}).to_string()
+        };
+        let ancestor = tmp.path().join("ancestor.json");
+        let ours = tmp.path().join("ours.json");
+        let theirs = tmp.path().join("theirs.json");
+        fs::write(&ancestor, manifest(&["2026-01"])).unwrap();
+        fs::write(&ours, manifest(&["2026-01", "2026-03"])).unwrap();
+        fs::write(&theirs, manifest(&["2026-01", "2026-02"])).unwrap();
+
+        assert!(is_segment_manifest(&ours, &theirs));
+        assert_eq!(
+            merge_segment_manifests(&ancestor, &ours, &theirs).unwrap(),
+            3
+        );
+        let merged = parse_manifest(&fs::read_to_string(&ours).unwrap()).unwrap();
+        let months: Vec<_> = merged.segments.iter().map(|s| s.month.as_str()).collect();
+        assert_eq!(months, ["2026-01", "2026-02", "2026-03"]);
+    }
+
+    fn make_timed_event(id: &str, ts: &str, event_type: EventType, task_id: &str) -> EventRecord {
+        EventRecord {
+            ts: ts.to_string(),
+            event_type,
+            task_id: task_id.to_string(),
+            ..make_event(id, id)
+        }
+    }
+
+    /// Appends `count` random events to `log`: creates for fresh tasks or
+    /// updates of tasks the log already created, with jittery timestamps.
+    fn append_random_events(
+        rng: &mut StdRng,
+        log: &mut Vec<EventRecord>,
+        branch: &str,
+        count: usize,
+    ) {
+        for n in 0..count {
+            let id = format!("{}{:02}", branch, n);
+            let ts = format!("2026-01-01T00:00:{:02}Z", rng.random_range(0..60));
+            let created: Vec<String> = log
+                .iter()
+                .filter(|event| event.event_type == EventType::TaskCreated)
+                .map(|event| event.task_id.clone())
+                .collect();
+            let event = if !created.is_empty() && rng.random_range(0..3) == 0 {
+                let task_id = &created[rng.random_range(0..created.len())];
+                make_timed_event(&id, &ts, EventType::TaskUpdated, task_id)
+            } else {
+                make_timed_event(&id, &ts, EventType::TaskCreated, &format!("tsq-{}", id))
+            };
+            log.push(event);
+        }
+    }
+
+    fn merge_logs(
+        ancestor: &[EventRecord],
+        ours: &[EventRecord],
+        theirs: &[EventRecord],
+    ) -> Vec<String> {
+        let tmp = TempDir::new().unwrap();
+        let ancestor = write_events(tmp.path(), "ancestor.jsonl", ancestor);
+        let ours = write_events(tmp.path(), "ours.jsonl", ours);
+        let theirs = write_events(tmp.path(), "theirs.jsonl", theirs);
+        let result = merge_events_files(&ancestor, &ours, &theirs).unwrap();
+        assert!(!result.conflict);
+        fs::read_to_string(&ours)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn is_subsequence(needle: &[EventRecord], haystack: &[String]) -> bool {
+        let mut lines = haystack.iter();
+        needle.iter().all(|event| {
+            let line = serde_json::to_string(event).unwrap();
+            lines.any(|candidate| *candidate == line)
+        })
+    }
+
+    #[test]
+    fn property_concurrent_appends_merge_commutatively() {
+        let mut rng = StdRng::seed_from_u64(0x7a5c);
+        for _ in 0..200 {
+            let mut ancestor = Vec::new();
+            let ancestor_len = rng.random_range(0..4);
+            append_random_events(&mut rng, &mut ancestor, "A", ancestor_len);
+            let mut ours = ancestor.clone();
+            let ours_len = rng.random_range(0..6);
+            append_random_events(&mut rng, &mut ours, "O", ours_len);
+            let mut theirs = ancestor.clone();
+            let theirs_len = rng.random_range(0..6);
+            append_random_events(&mut rng, &mut theirs, "T", theirs_len);
+            if rng.random_range(0..4) == 0 {
+                // Both sides picked up the same commit, e.g. via a cherry-pick.
+                let shared = make_event("S00", "shared");
+                ours.push(shared.clone());
+                theirs.push(shared);
+            }
+
+            let merged = merge_logs(&ancestor, &ours, &theirs);
+            assert_eq!(merged, merge_logs(&ancestor, &theirs, &ours));
+            let unique: HashSet<&str> = ours.iter().chain(&theirs).filter_map(event_id).collect();
+            assert_eq!(merged.len(), unique.len());
+            // Events seen on only one side keep that side's append order.
+            let ours_only: Vec<EventRecord> = ours
+                .iter()
+                .filter(|event| !theirs.contains(event))
+                .cloned()
+                .collect();
+            let theirs_only: Vec<EventRecord> = theirs
+                .iter()
+                .filter(|event| !ours.contains(event))
+                .cloned()
+                .collect();
+            assert!(is_subsequence(&ours_only, &merged));
+            assert!(is_subsequence(&theirs_only, &merged));
+
+            let merged_events: Vec<EventRecord> = merged
+                .iter()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            assert_eq!(
+                merge_logs(&ancestor, &merged_events, &merged_events),
+                merged
+            );
+        }
+    }
+}
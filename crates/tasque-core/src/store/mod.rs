@@ -1,8 +1,12 @@
+pub mod blobs;
+pub mod compression;
 pub mod config;
+pub mod crypto;
 pub mod events;
 pub mod git;
 pub mod lock;
 pub mod merge_driver;
 pub mod paths;
+pub mod segments;
 pub mod snapshots;
 pub mod state;
@@ -1,37 +1,38 @@
+pub use crate::domain::validate::{is_task_spec_relative_path, task_spec_relative_path};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct TasquePaths {
     pub tasque_dir: PathBuf,
     pub events_file: PathBuf,
+    pub segment_manifest_file: PathBuf,
+    pub quarantine_file: PathBuf,
     pub config_file: PathBuf,
     pub state_file: PathBuf,
     pub lock_file: PathBuf,
     pub snapshots_dir: PathBuf,
     pub specs_dir: PathBuf,
+    pub blobs_dir: PathBuf,
+    pub spec_templates_dir: PathBuf,
 }
 
 pub fn get_paths(repo_root: impl AsRef<Path>) -> TasquePaths {
     let tasque_dir = repo_root.as_ref().join(".tasque");
     TasquePaths {
         events_file: tasque_dir.join("events.jsonl"),
+        segment_manifest_file: tasque_dir.join("events.manifest.json"),
+        quarantine_file: tasque_dir.join("quarantine.jsonl"),
         config_file: tasque_dir.join("config.json"),
         state_file: tasque_dir.join("state.json"),
         lock_file: tasque_dir.join(".lock"),
         snapshots_dir: tasque_dir.join("snapshots"),
         specs_dir: tasque_dir.join("specs"),
+        blobs_dir: tasque_dir.join("blobs"),
+        spec_templates_dir: tasque_dir.join("spec-templates"),
         tasque_dir,
     }
 }
 
-pub fn task_spec_relative_path(task_id: &str) -> String {
-    format!(".tasque/specs/{}/spec.md", task_id)
-}
-
-pub fn is_task_spec_relative_path(task_id: &str, spec_path: &str) -> bool {
-    spec_path == task_spec_relative_path(task_id)
-}
-
 pub fn task_spec_file(repo_root: impl AsRef<Path>, task_id: &str) -> PathBuf {
     repo_root
         .as_ref()
@@ -40,3 +41,18 @@ pub fn task_spec_file(repo_root: impl AsRef<Path>, task_id: &str) -> PathBuf {
         .join(task_id)
         .join("spec.md")
 }
+
+/// Content-addressed copy of an attached spec revision, keyed by fingerprint.
+pub fn task_spec_version_file(
+    repo_root: impl AsRef<Path>,
+    task_id: &str,
+    fingerprint: &str,
+) -> PathBuf {
+    repo_root
+        .as_ref()
+        .join(".tasque")
+        .join("specs")
+        .join(task_id)
+        .join("versions")
+        .join(format!("{}.md", fingerprint))
+}
@@ -0,0 +1,300 @@
+use crate::errors::TsqError;
+use crate::replay::EventLogSource;
+use crate::store::config::read_config;
+use crate::store::events::decode_event_line;
+use crate::store::paths::get_paths;
+use crate::types::{EventSegment, EventSegmentManifest, EventSegmentation};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::{read, read_to_string, remove_file, rename, write};
+use std::path::{Path, PathBuf};
+
+pub const SEGMENT_MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+pub fn segment_file_name(month: &str) -> String {
+    format!("events-{}.jsonl", month)
+}
+
+pub fn segmentation_enabled(repo_root: impl AsRef<Path>) -> Result<bool, TsqError> {
+    let repo_root = repo_root.as_ref();
+    if !get_paths(repo_root).config_file.exists() {
+        return Ok(false);
+    }
+    Ok(read_config(repo_root)?.event_segments == Some(EventSegmentation::Monthly))
+}
+
+/// The manifest, or an empty one when the log has never been rotated.
+pub fn read_manifest(repo_root: impl AsRef<Path>) -> Result<EventSegmentManifest, TsqError> {
+    let paths = get_paths(repo_root);
+    match read_to_string(&paths.segment_manifest_file) {
+        Ok(raw) => parse_manifest(&raw),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            Ok(EventSegmentManifest::default())
+        }
+        Err(error) => Err(
+            TsqError::new("EVENT_READ_FAILED", "Failed reading segment manifest", 2)
+                .with_details(io_error_value(&error)),
+        ),
+    }
+}
+
+/// Segment names come from git, so each must be the canonical file for its
+/// month; anything else could point outside `.tasque/`.
+pub fn parse_manifest(raw: &str) -> Result<EventSegmentManifest, TsqError> {
+    let invalid = || TsqError::new("SEGMENT_MANIFEST_INVALID", "Segment manifest is invalid", 2);
+    let manifest: EventSegmentManifest = serde_json::from_str(raw).map_err(|error| {
+        invalid().with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+    for segment in &manifest.segments {
+        if !is_month(&segment.month) || segment.file != segment_file_name(&segment.month) {
+            return Err(invalid().with_details(serde_json::json!({"file": segment.file})));
+        }
+    }
+    Ok(manifest)
+}
+
+fn is_month(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 7
+        && bytes[4] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(index, byte)| index == 4 || byte.is_ascii_digit())
+}
+
+fn write_manifest(repo_root: &Path, manifest: &EventSegmentManifest) -> Result<(), TsqError> {
+    let paths = get_paths(repo_root);
+    let payload = serde_json::to_string_pretty(manifest).map_err(|error| {
+        TsqError::new("EVENT_APPEND_FAILED", "Failed writing segment manifest", 2)
+            .with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+    write_atomic(
+        &paths.segment_manifest_file,
+        format!("{}\n", payload).as_bytes(),
+    )
+}
+
+/// A repo's on-disk log as a replay source.
+pub struct RepoEventLog(pub PathBuf);
+
+impl EventLogSource for RepoEventLog {
+    fn read_event_log(&self) -> Result<Vec<u8>, TsqError> {
+        read_event_log(&self.0)
+    }
+}
+
+/// Every segment in manifest order followed by `events.jsonl`, as one byte
+/// stream. A merge can leave an event both in a segment and in a later file;
+/// only its first copy is kept, so replay never sees the same id twice.
+pub fn read_event_log(repo_root: impl AsRef<Path>) -> Result<Vec<u8>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let paths = get_paths(repo_root);
+    let manifest = read_manifest(repo_root)?;
+    if manifest.segments.is_empty() {
+        return read_optional(&paths.events_file);
+    }
+
+    let mut files: Vec<_> = manifest
+        .segments
+        .iter()
+        .map(|segment| paths.tasque_dir.join(&segment.file))
+        .collect();
+    files.push(paths.events_file.clone());
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut stitched = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let raw = read_optional(file)?;
+        let mut ids = Vec::new();
+        for line in raw.split_inclusive(|byte| *byte == b'\n') {
+            if let Some(id) = line_id(line) {
+                if seen.contains(&id) {
+                    continue;
+                }
+                ids.push(id);
+            }
+            stitched.extend_from_slice(line);
+        }
+        seen.extend(ids);
+        if index + 1 < files.len() && !stitched.is_empty() && !stitched.ends_with(b"\n") {
+            stitched.push(b'\n');
+        }
+    }
+    Ok(stitched)
+}
+
+/// Moves every month older than the newest one in `events.jsonl` into its
+/// own segment. Months only move forward: an event stamped before the last
+/// rotated month (clock skew) rides along with the next segment. The stitched
+/// log is byte-for-byte unchanged, so state caches and snapshots stay valid.
+///
+/// Segments are written first, then the manifest, then the trimmed active
+/// file. A crash in between leaves events in two files, which
+/// [`read_event_log`] dedupes and the next rotation cleans up.
+pub fn rotate_event_segments(repo_root: impl AsRef<Path>) -> Result<Vec<EventSegment>, TsqError> {
+    let repo_root = repo_root.as_ref();
+    let paths = get_paths(repo_root);
+    let mut manifest = read_manifest(repo_root)?;
+    let active = read_optional(&paths.events_file)?;
+    let active = std::str::from_utf8(&active).map_err(|error| {
+        TsqError::new("EVENTS_CORRUPT", "Events file is not valid UTF-8", 2)
+            .with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+
+    let mut rotated_ids: HashSet<String> = HashSet::new();
+    for segment in &manifest.segments {
+        let raw = read_optional(&paths.tasque_dir.join(&segment.file))?;
+        rotated_ids.extend(
+            raw.split_inclusive(|byte| *byte == b'\n')
+                .filter_map(line_id),
+        );
+    }
+
+    let last_month = manifest
+        .segments
+        .last()
+        .map(|segment| segment.month.clone());
+    let lines: Vec<&str> = active.split_inclusive('\n').collect();
+    let mut months: Vec<Option<String>> = Vec::with_capacity(lines.len());
+    let mut keep: Vec<bool> = Vec::with_capacity(lines.len());
+    let mut running = last_month.clone();
+    for line in &lines {
+        let decoded = decode_line(line.as_bytes());
+        if let Some(month) = decoded.as_ref().and_then(event_month)
+            && running.as_ref().is_none_or(|current| month > *current)
+        {
+            running = Some(month);
+        }
+        months.push(running.clone());
+        keep.push(
+            decoded
+                .as_ref()
+                .and_then(value_id)
+                .is_none_or(|id| !rotated_ids.contains(&id)),
+        );
+    }
+
+    let newest = running;
+    let mut next = newest.clone();
+    for month in months.iter_mut().rev() {
+        if *month <= last_month {
+            *month = next.clone();
+        } else {
+            next = month.clone();
+        }
+    }
+    let split = months
+        .iter()
+        .position(|month| *month == newest)
+        .unwrap_or(lines.len());
+    if split == 0 && keep.iter().all(|kept| *kept) {
+        return Ok(Vec::new());
+    }
+
+    let mut written = Vec::new();
+    let mut start = 0;
+    while start < split {
+        let month = months[start].clone().unwrap_or_default();
+        let end = (start..split)
+            .find(|index| months[*index].as_deref() != Some(month.as_str()))
+            .unwrap_or(split);
+        let content: String = (start..end)
+            .filter(|index| keep[*index])
+            .map(|index| lines[index])
+            .collect();
+        let segment = EventSegment {
+            file: segment_file_name(&month),
+            month,
+        };
+        write_atomic(&paths.tasque_dir.join(&segment.file), content.as_bytes())?;
+        written.push(segment);
+        start = end;
+    }
+
+    manifest.schema_version = SEGMENT_MANIFEST_SCHEMA_VERSION;
+    manifest.segments.extend(written.iter().cloned());
+    write_manifest(repo_root, &manifest)?;
+
+    let remaining: String = (split..lines.len())
+        .filter(|index| keep[*index])
+        .map(|index| lines[index])
+        .collect();
+    write_atomic(&paths.events_file, remaining.as_bytes())?;
+    Ok(written)
+}
+
+/// Drops the manifest and every segment it lists, after their events were
+/// written back into `events.jsonl`.
+pub fn clear_segments(repo_root: impl AsRef<Path>) -> Result<(), TsqError> {
+    let repo_root = repo_root.as_ref();
+    let paths = get_paths(repo_root);
+    let manifest = read_manifest(repo_root)?;
+    if let Err(error) = remove_file(&paths.segment_manifest_file)
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        return Err(
+            TsqError::new("EVENT_APPEND_FAILED", "Failed removing segment manifest", 2)
+                .with_details(io_error_value(&error)),
+        );
+    }
+    for segment in &manifest.segments {
+        let _ = remove_file(paths.tasque_dir.join(&segment.file));
+    }
+    Ok(())
+}
+
+fn decode_line(line: &[u8]) -> Option<Value> {
+    let line = std::str::from_utf8(line)
+        .ok()?
+        .trim_end_matches(['\n', '\r']);
+    if line.trim().is_empty() {
+        return None;
+    }
+    decode_event_line(line, 0).ok()
+}
+
+fn line_id(line: &[u8]) -> Option<String> {
+    decode_line(line).as_ref().and_then(value_id)
+}
+
+fn value_id(value: &Value) -> Option<String> {
+    value
+        .get("id")
+        .or_else(|| value.get("event_id"))
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+fn event_month(value: &Value) -> Option<String> {
+    let ts = value.get("ts").and_then(Value::as_str)?;
+    let ts = DateTime::parse_from_rfc3339(ts).ok()?;
+    Some(ts.with_timezone(&Utc).format("%Y-%m").to_string())
+}
+
+fn read_optional(path: &Path) -> Result<Vec<u8>, TsqError> {
+    match read(path) {
+        Ok(raw) => Ok(raw),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(
+            TsqError::new("EVENT_READ_FAILED", "Failed reading events", 2)
+                .with_details(io_error_value(&error)),
+        ),
+    }
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), TsqError> {
+    let temp = format!("{}.tmp-{}", path.display(), std::process::id());
+    write(&temp, bytes)
+        .and_then(|_| rename(&temp, path))
+        .map_err(|error| {
+            let _ = remove_file(&temp);
+            TsqError::new("EVENT_APPEND_FAILED", "Failed rotating event segments", 2)
+                .with_details(io_error_value(&error))
+        })
+}
+
+fn io_error_value(error: &std::io::Error) -> Value {
+    serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()})
+}
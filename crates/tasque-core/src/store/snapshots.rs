@@ -1,9 +1,12 @@
 use crate::domain::state_invariants::validate_projected_state;
+use crate::domain::task_index::reindex_state;
 use crate::errors::TsqError;
+use crate::store::compression;
+use crate::store::crypto;
 use crate::store::paths::get_paths;
-use crate::types::{STATE_CACHE_SCHEMA_VERSION, Snapshot};
+use crate::types::{Compression, STATE_CACHE_SCHEMA_VERSION, Snapshot};
 use chrono::Utc;
-use std::fs::{OpenOptions, create_dir_all, read_dir, read_to_string, remove_file, rename};
+use std::fs::{OpenOptions, create_dir_all, read_dir, remove_file, rename};
 use std::io::Write;
 use std::path::Path;
 
@@ -14,9 +17,19 @@ pub struct LoadedSnapshot {
     pub warning: Option<String>,
 }
 
-fn snapshot_filename(snapshot: &Snapshot) -> String {
+fn snapshot_filename(snapshot: &Snapshot, codec: Option<Compression>) -> String {
     let ts = snapshot.taken_at.replace([':', '.'], "-");
-    format!("{}-{}.json", ts, snapshot.event_count)
+    format!(
+        "{}-{}.json{}",
+        ts,
+        snapshot.event_count,
+        compression::extension(codec)
+    )
+}
+
+/// Plain `.json` snapshots and compressed ones such as `.json.zst`.
+pub fn is_snapshot_file(name: &str) -> bool {
+    compression::strip_extension(name).ends_with(".json")
 }
 
 pub fn load_latest_snapshot(repo_root: impl AsRef<Path>) -> Result<Option<Snapshot>, TsqError> {
@@ -47,7 +60,7 @@ pub fn load_latest_snapshot_with_warning(
     let mut candidates = Vec::new();
     for entry in entries.flatten() {
         if let Some(name) = entry.file_name().to_str()
-            && name.ends_with(".json")
+            && is_snapshot_file(name)
         {
             candidates.push(name.to_string());
         }
@@ -64,10 +77,11 @@ pub fn load_latest_snapshot_with_warning(
     let mut invalid = Vec::new();
     for name in candidates.iter().rev() {
         let candidate = paths.snapshots_dir.join(name);
-        match read_to_string(&candidate) {
+        match compression::read_text(&candidate, crypto::SNAPSHOT) {
             Ok(raw) => match serde_json::from_str::<Snapshot>(&raw) {
-                Ok(snapshot) => {
+                Ok(mut snapshot) => {
                     if is_snapshot(&snapshot) {
+                        reindex_state(&mut snapshot.state);
                         return Ok(LoadedSnapshot {
                             snapshot: Some(snapshot),
                             warning: if invalid.is_empty() {
@@ -134,7 +148,7 @@ fn prune_snapshots(path: &Path) {
                 .raw_os_error()
                 .map(|value| value.to_string())
                 .unwrap_or_else(|| format!("{:?}", error.kind()));
-            eprintln!("Warning: failed to prune snapshots ({}): {}", code, error);
+            tracing::warn!("failed to prune snapshots ({}): {}", code, error);
             return;
         }
     };
@@ -142,7 +156,7 @@ fn prune_snapshots(path: &Path) {
     let mut snapshots = Vec::new();
     for entry in entries.flatten() {
         if let Some(name) = entry.file_name().to_str()
-            && name.ends_with(".json")
+            && is_snapshot_file(name)
         {
             snapshots.push(name.to_string());
         }
@@ -160,13 +174,14 @@ fn prune_snapshots(path: &Path) {
 }
 
 pub fn write_snapshot(repo_root: impl AsRef<Path>, snapshot: &Snapshot) -> Result<(), TsqError> {
-    let paths = get_paths(repo_root);
+    let paths = get_paths(repo_root.as_ref());
     create_dir_all(&paths.snapshots_dir).map_err(|error| {
         TsqError::new("SNAPSHOT_WRITE_FAILED", "Failed writing snapshot", 2)
             .with_details(io_error_value(&error))
     })?;
 
-    let target = paths.snapshots_dir.join(snapshot_filename(snapshot));
+    let codec = compression::repo_compression(repo_root.as_ref())?;
+    let target = paths.snapshots_dir.join(snapshot_filename(snapshot, codec));
     let temp = format!(
         "{}.tmp-{}-{}",
         target.display(),
@@ -177,6 +192,12 @@ pub fn write_snapshot(repo_root: impl AsRef<Path>, snapshot: &Snapshot) -> Resul
         TsqError::new("SNAPSHOT_WRITE_FAILED", "Failed writing snapshot", 2)
             .with_details(any_error_value(&error))
     })?;
+    let payload = crypto::seal_for_repo(repo_root, crypto::SNAPSHOT, payload)?;
+    let bytes =
+        compression::compress(codec, format!("{}\n", payload).as_bytes()).map_err(|error| {
+            TsqError::new("SNAPSHOT_WRITE_FAILED", "Failed writing snapshot", 2)
+                .with_details(io_error_value(&error))
+        })?;
 
     let mut handle = OpenOptions::new()
         .write(true)
@@ -187,7 +208,7 @@ pub fn write_snapshot(repo_root: impl AsRef<Path>, snapshot: &Snapshot) -> Resul
             TsqError::new("SNAPSHOT_WRITE_FAILED", "Failed writing snapshot", 2)
                 .with_details(io_error_value(&error))
         })?;
-    if let Err(error) = handle.write_all(format!("{}\n", payload).as_bytes()) {
+    if let Err(error) = handle.write_all(&bytes) {
         let _ = remove_file(&temp);
         return Err(
             TsqError::new("SNAPSHOT_WRITE_FAILED", "Failed writing snapshot", 2)
@@ -213,6 +234,35 @@ pub fn write_snapshot(repo_root: impl AsRef<Path>, snapshot: &Snapshot) -> Resul
     Ok(())
 }
 
+/// Deletes every snapshot file and returns the removed names, sorted.
+pub fn remove_snapshots(repo_root: impl AsRef<Path>) -> Result<Vec<String>, TsqError> {
+    let paths = get_paths(repo_root);
+    let mut removed = list_snapshots(&paths.snapshots_dir)?;
+    removed.retain(|name| remove_file(paths.snapshots_dir.join(name)).is_ok());
+    Ok(removed)
+}
+
+/// Snapshot file names, sorted oldest first.
+pub fn list_snapshots(snapshots_dir: &Path) -> Result<Vec<String>, TsqError> {
+    let entries = match read_dir(snapshots_dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(
+                TsqError::new("SNAPSHOT_READ_FAILED", "Failed listing snapshots", 2)
+                    .with_details(io_error_value(&error)),
+            );
+        }
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+        .filter(|name| is_snapshot_file(name))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
 fn io_error_value(error: &std::io::Error) -> serde_json::Value {
     serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()})
 }
@@ -1,4 +1,5 @@
 use crate::domain::state_invariants::validate_projected_state;
+use crate::domain::task_index::reindex_state;
 use crate::errors::TsqError;
 use crate::store::paths::get_paths;
 use crate::types::{
@@ -97,10 +98,11 @@ pub fn read_state_cache(repo_root: impl AsRef<Path>) -> Result<Option<StateCache
 }
 
 fn parse_state_cache_candidate(raw: &str, primary: bool) -> Result<Option<StateCache>, TsqError> {
-    if let Ok(cache) = serde_json::from_str::<StateCache>(raw) {
+    if let Ok(mut cache) = serde_json::from_str::<StateCache>(raw) {
         if cache.schema_version == STATE_CACHE_SCHEMA_VERSION
             && validate_projected_state(&cache.state).is_ok()
         {
+            reindex_state(&mut cache.state);
             return Ok(Some(cache));
         }
         return Ok(None);
@@ -110,12 +112,13 @@ fn parse_state_cache_candidate(raw: &str, primary: bool) -> Result<Option<StateC
         return Ok(None);
     }
 
-    let Ok(state) = serde_json::from_str::<State>(raw) else {
+    let Ok(mut state) = serde_json::from_str::<State>(raw) else {
         return Ok(None);
     };
     if validate_projected_state(&state).is_err() {
         return Ok(None);
     }
+    reindex_state(&mut state);
     Ok(Some(StateCache {
         schema_version: SCHEMA_VERSION,
         event_log: None,
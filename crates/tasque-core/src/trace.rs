@@ -0,0 +1,142 @@
+//! Per-command phase timing for `--timing`.
+//!
+//! Diagnostic spans and events go through the `tracing` crate; the CLI installs
+//! the subscriber. This module only sums named phases (`read_events`, `apply`,
+//! `filter`, `render`) for the whole command; see `phase` and `take_timings`.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static TIMING: AtomicBool = AtomicBool::new(false);
+static PHASES: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+static TIMING_START: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Total time spent in one phase, in first-entered order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: &'static str,
+    pub ms: f64,
+}
+
+/// Phase totals plus wall time since `enable_timing`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Timings {
+    pub phases: Vec<PhaseTiming>,
+    pub total_ms: f64,
+}
+
+/// Starts collecting phase timings and resets any collected so far.
+pub fn enable_timing() {
+    TIMING.store(true, Ordering::Relaxed);
+    if let Ok(mut phases) = PHASES.lock() {
+        phases.clear();
+    }
+    if let Ok(mut start) = TIMING_START.lock() {
+        *start = Some(Instant::now());
+    }
+}
+
+pub fn timing_enabled() -> bool {
+    TIMING.load(Ordering::Relaxed)
+}
+
+/// Times a phase until dropped; repeated phases add up. A no-op unless
+/// `enable_timing` was called.
+pub fn phase(name: &'static str) -> Phase {
+    Phase {
+        name,
+        start: timing_enabled().then(Instant::now),
+    }
+}
+
+pub struct Phase {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        let Ok(mut phases) = PHASES.lock() else {
+            return;
+        };
+        match phases.iter_mut().find(|(name, _)| *name == self.name) {
+            Some((_, total)) => *total += elapsed,
+            None => phases.push((self.name, elapsed)),
+        }
+    }
+}
+
+/// The phases collected so far; `None` when timing is off.
+pub fn take_timings() -> Option<Timings> {
+    if !timing_enabled() {
+        return None;
+    }
+    let phases = PHASES
+        .lock()
+        .map(|mut phases| std::mem::take(&mut *phases))
+        .unwrap_or_default();
+    let total = TIMING_START
+        .lock()
+        .ok()
+        .and_then(|start| *start)
+        .map(|start| start.elapsed())
+        .unwrap_or_default();
+    Some(Timings {
+        phases: phases
+            .into_iter()
+            .map(|(phase, elapsed)| PhaseTiming {
+                phase,
+                ms: round_ms(elapsed),
+            })
+            .collect(),
+        total_ms: round_ms(total),
+    })
+}
+
+/// Milliseconds rounded to microseconds, so JSON stays readable.
+pub fn round_ms(elapsed: Duration) -> f64 {
+    (elapsed.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+/// One stderr line: `timing read_events=1.204ms apply=0.310ms total=2.001ms`.
+pub fn format_timings(timings: &Timings) -> String {
+    let mut line = String::from("timing");
+    for phase in &timings.phases {
+        line.push_str(&format!(" {}={:.3}ms", phase.phase, phase.ms));
+    }
+    line.push_str(&format!(" total={:.3}ms", timings.total_ms));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_timings_lists_phases_then_total() {
+        let timings = Timings {
+            phases: vec![
+                PhaseTiming {
+                    phase: "read_events",
+                    ms: 1.5,
+                },
+                PhaseTiming {
+                    phase: "render",
+                    ms: 0.25,
+                },
+            ],
+            total_ms: 2.0,
+        };
+        assert_eq!(
+            format_timings(&timings),
+            "timing read_events=1.500ms render=0.250ms total=2.000ms"
+        );
+        assert_eq!(round_ms(Duration::from_micros(1234)), 1.234);
+    }
+}
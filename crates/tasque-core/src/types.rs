@@ -0,0 +1,413 @@
+#[path = "types_config.rs"]
+mod types_config;
+#[path = "types_policy.rs"]
+mod types_policy;
+#[path = "types_results.rs"]
+mod types_results;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub use types_config::{
+    AutoSyncConfig, Compression, Config, DEFAULT_MAX_PAYLOAD_BYTES, DEFAULT_OVERFLOW_BYTES,
+    EventSegment, EventSegmentManifest, EventSegmentation, LabelColor, ParentCompletionConfig,
+    PayloadLimitsConfig, PlanningWorkflowConfig, StatusWorkflowConfig, SyncRoute,
+};
+pub use types_policy::{
+    ActorIdentity, ActorResolutionConfig, ActorRole, ActorSource, ActorType, EscalationConfig,
+    HookConfig, HookMode, HooksConfig, LaneConfig, LaneFilter, PolicyConfig, PolicyRequirement,
+    PolicyTrigger, RedactionConfig, RolesConfig, SkillRegistry, StalePolicyConfig,
+};
+pub use types_results::{
+    BackupFile, BackupManifest, BackupResult, Envelope, EnvelopeErr, EnvelopeError, EnvelopeOk,
+    HookInstallResult, HookUninstallResult, MergeDriverOutcome, MigrateResult, QuarantinedEvent,
+    RepairDep, RepairLink, RepairPlan, RepairResult, RestoreResult, RuntimeOptions, SalvageReport,
+    SnapshotRebuild, SyncDaemonStatus, SyncDisableResult, SyncMergeDriverStatus, SyncRemoteResult,
+    SyncRunResult, SyncSetupResult, SyncStatusResult,
+};
+
+pub const SCHEMA_VERSION: u32 = 1;
+pub const STATE_CACHE_SCHEMA_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    Task,
+    Feature,
+    Epic,
+}
+
+/// The six builtin statuses, plus any extra ones `status_workflow` in config
+/// adds (for example `in_review`), serialized by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TaskStatus {
+    Open,
+    InProgress,
+    Blocked,
+    Closed,
+    Canceled,
+    Deferred,
+    Custom(String),
+}
+
+impl TaskStatus {
+    pub const BUILTIN: [TaskStatus; 6] = [
+        TaskStatus::Open,
+        TaskStatus::InProgress,
+        TaskStatus::Blocked,
+        TaskStatus::Closed,
+        TaskStatus::Canceled,
+        TaskStatus::Deferred,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskStatus::Open => "open",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Blocked => "blocked",
+            TaskStatus::Closed => "closed",
+            TaskStatus::Canceled => "canceled",
+            TaskStatus::Deferred => "deferred",
+            TaskStatus::Custom(name) => name,
+        }
+    }
+
+    /// Builtin names map to their variants; any other lowercase
+    /// `[a-z][a-z0-9_]*` name is a custom status, except the `todo` and
+    /// `done` input aliases.
+    pub fn from_name(raw: &str) -> Option<Self> {
+        if let Some(builtin) = TaskStatus::BUILTIN
+            .into_iter()
+            .find(|status| status.as_str() == raw)
+        {
+            return Some(builtin);
+        }
+        let alias = matches!(raw, "todo" | "done");
+        (!alias && is_workflow_name(raw)).then(|| TaskStatus::Custom(raw.to_string()))
+    }
+}
+
+impl TryFrom<String> for TaskStatus {
+    type Error = String;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        TaskStatus::from_name(&raw).ok_or_else(|| format!("invalid status: {}", raw))
+    }
+}
+
+impl From<TaskStatus> for String {
+    fn from(status: TaskStatus) -> Self {
+        status.as_str().to_string()
+    }
+}
+
+fn is_workflow_name(raw: &str) -> bool {
+    let mut chars = raw.chars();
+    chars.next().is_some_and(|first| first.is_ascii_lowercase())
+        && chars.all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+}
+
+/// `needs_planning` and `planned` are builtin; `planning_workflow` in config
+/// adds intermediate states between them, serialized by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PlanningState {
+    NeedsPlanning,
+    Planned,
+    Custom(String),
+}
+
+impl PlanningState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PlanningState::NeedsPlanning => "needs_planning",
+            PlanningState::Planned => "planned",
+            PlanningState::Custom(name) => name,
+        }
+    }
+
+    /// Builtin names map to their variants; any other lowercase
+    /// `[a-z][a-z0-9_]*` name is a custom state.
+    pub fn from_name(raw: &str) -> Option<Self> {
+        match raw {
+            "needs_planning" => Some(PlanningState::NeedsPlanning),
+            "planned" => Some(PlanningState::Planned),
+            _ => is_workflow_name(raw).then(|| PlanningState::Custom(raw.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for PlanningState {
+    type Error = String;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        PlanningState::from_name(&raw).ok_or_else(|| format!("invalid planning state: {}", raw))
+    }
+}
+
+impl From<PlanningState> for String {
+    fn from(state: PlanningState) -> Self {
+        state.as_str().to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyType {
+    Blocks,
+    StartsAfter,
+    /// The dependent may start any time but cannot close before the blocker.
+    FinishToFinish,
+    /// Informational edge; never gates readiness or closing.
+    Informs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationType {
+    RelatesTo,
+    RepliesTo,
+    Duplicates,
+    Supersedes,
+}
+
+pub type Priority = u8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskNote {
+    pub event_id: String,
+    pub ts: String,
+    pub actor: String,
+    pub text: String,
+}
+
+/// Lightweight to-do item on a task, addressed by its 1-based position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// Id of the `task.checklist_added` event; stable across reordering merges.
+    pub event_id: String,
+    pub text: String,
+    pub done: bool,
+    pub ts: String,
+    pub actor: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: String,
+    pub alias: String,
+    pub kind: TaskKind,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub notes: Vec<TaskNote>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_attached_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_attached_by: Option<String>,
+    pub status: TaskStatus,
+    /// Snooze deadline for a deferred task; list/ready reopen it once passed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defer_until: Option<String>,
+    /// What a blocked task is waiting on, often something outside the task graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_on: Option<String>,
+    pub priority: Priority,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discovered_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planning_state: Option<PlanningState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replies_to: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Explicit position among siblings set by `tsq order --before/--after`;
+    /// ranked children sort ahead of unranked ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<i64>,
+    /// Priority after escalation, filled in on read by list/ready when the
+    /// escalation policy raises it; never recorded in events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_priority: Option<Priority>,
+    /// Acceptance-criteria completion recorded by `tsq criteria check`, keyed
+    /// by normalized criterion text; overrides the spec's own checkboxes.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub criteria_marks: BTreeMap<String, bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<ChecklistItem>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyEdge {
+    pub blocker: String,
+    pub dep_type: DependencyType,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DependencyEdgeWire {
+    String(String),
+    Object {
+        blocker: String,
+        dep_type: Option<DependencyType>,
+    },
+}
+
+impl<'de> Deserialize<'de> for DependencyEdge {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let wire = DependencyEdgeWire::deserialize(deserializer)?;
+        match wire {
+            DependencyEdgeWire::String(blocker) => Ok(DependencyEdge {
+                blocker,
+                dep_type: DependencyType::Blocks,
+            }),
+            DependencyEdgeWire::Object { blocker, dep_type } => Ok(DependencyEdge {
+                blocker,
+                dep_type: dep_type.unwrap_or(DependencyType::Blocks),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyRef {
+    pub id: String,
+    pub dep_type: DependencyType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskTreeNode {
+    pub task: Task,
+    pub blockers: Vec<String>,
+    pub dependents: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocker_edges: Option<Vec<DependencyRef>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependent_edges: Option<Vec<DependencyRef>>,
+    pub children: Vec<TaskTreeNode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventType {
+    #[serde(rename = "task.created")]
+    TaskCreated,
+    #[serde(rename = "task.updated")]
+    TaskUpdated,
+    #[serde(rename = "task.status_set")]
+    TaskStatusSet,
+    #[serde(rename = "task.claimed")]
+    TaskClaimed,
+    #[serde(rename = "task.noted")]
+    TaskNoted,
+    #[serde(rename = "task.spec_attached")]
+    TaskSpecAttached,
+    #[serde(rename = "task.superseded")]
+    TaskSuperseded,
+    #[serde(rename = "task.criterion_set")]
+    TaskCriterionSet,
+    #[serde(rename = "task.checklist_added")]
+    TaskChecklistAdded,
+    #[serde(rename = "task.checklist_set")]
+    TaskChecklistSet,
+    #[serde(rename = "task.ranked")]
+    TaskRanked,
+    #[serde(rename = "dep.added")]
+    DepAdded,
+    #[serde(rename = "dep.removed")]
+    DepRemoved,
+    #[serde(rename = "link.added")]
+    LinkAdded,
+    #[serde(rename = "link.removed")]
+    LinkRemoved,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    pub ts: String,
+    pub actor: String,
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    pub task_id: String,
+    pub payload: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    pub tasks: HashMap<String, Task>,
+    pub deps: HashMap<String, Vec<DependencyEdge>>,
+    pub links: HashMap<String, HashMap<RelationType, Vec<String>>>,
+    /// Links of config-defined relation types, keyed by source then type name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_links: HashMap<String, BTreeMap<String, Vec<String>>>,
+    pub child_counters: HashMap<String, u32>,
+    pub created_order: Vec<String>,
+    pub applied_events: usize,
+    /// Derived from `tasks`; never persisted, rebuilt once per cache or
+    /// snapshot load (`reindex_state`).
+    #[serde(skip)]
+    pub indexes: TaskIndexes,
+}
+
+/// Task ids grouped by the fields list filters narrow on most, kept in step
+/// with `tasks` by the projector.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskIndexes {
+    pub by_status: HashMap<TaskStatus, BTreeSet<String>>,
+    pub by_assignee: HashMap<String, BTreeSet<String>>,
+    pub by_label: HashMap<String, BTreeSet<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLogMetadata {
+    pub event_count: usize,
+    pub byte_len: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateCache {
+    pub schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_log: Option<EventLogMetadata>,
+    pub state: State,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken_at: String,
+    pub event_count: usize,
+    #[serde(default)]
+    pub projection_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_log: Option<EventLogMetadata>,
+    pub state: State,
+}
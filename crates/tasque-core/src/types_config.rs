@@ -0,0 +1,335 @@
+use crate::types::{
+    ActorIdentity, ActorResolutionConfig, EscalationConfig, HooksConfig, LaneConfig, PlanningState,
+    PolicyConfig, RedactionConfig, RolesConfig, SCHEMA_VERSION, SkillRegistry, StalePolicyConfig,
+    TaskStatus,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub schema_version: u32,
+    pub snapshot_every: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policies: Vec<PolicyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation: Option<EscalationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale: Option<StalePolicyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_sync: Option<AutoSyncConfig>,
+    /// Set by `tsq sync disable` so git repos stay on local `.tasque/` storage
+    /// instead of migrating back to a sync worktree.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sync_disabled: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sync_routes: Vec<SyncRoute>,
+    /// Seal `events.jsonl`, snapshots, and specs with the key from
+    /// `TSQ_ENCRYPTION_KEY` (or `TSQ_ENCRYPTION_KEY_COMMAND`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub encrypt: bool,
+    /// Hex Argon2id salt for the encryption key, generated by the first
+    /// sealed write.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_salt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<RedactionConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<RolesConfig>,
+    /// User-defined link types beyond the builtins, mapped to their inverse
+    /// name (for example `implements` -> `implemented_by`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub relation_types: BTreeMap<String, String>,
+    /// How old a same-host lock whose process has exited must be before a
+    /// writer takes it over (default 30s).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_ttl_ms: Option<u64>,
+    /// Codec for new snapshot files; existing files are read by extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<Compression>,
+    /// Rotate completed months out of `events.jsonl` into segment files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_segments: Option<EventSegmentation>,
+    /// Display colors for labels, set with `tsq label color`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub label_colors: BTreeMap<String, LabelColor>,
+    /// Named git sources for `--source registry:<name>` skill installs.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub skill_registries: BTreeMap<String, SkillRegistry>,
+    /// Extra `find ready --lane` names; one named `planning` or `coding`
+    /// replaces the builtin lane.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lanes: Vec<LaneConfig>,
+    /// Known actors keyed by the name they act and get assigned as.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub actors: BTreeMap<String, ActorIdentity>,
+    /// Reject claims and assignments to actors missing from `actors`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_actors: bool,
+    /// Where the acting name comes from; unset keeps `TSQ_ACTOR`, then git
+    /// `user.name`, then the OS user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_resolution: Option<ActorResolutionConfig>,
+    /// Size caps for note and description bodies; defaults apply when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_limits: Option<PayloadLimitsConfig>,
+    /// Planning states and allowed moves between them; unset keeps the
+    /// builtin `needs_planning` / `planned` pair with no restrictions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planning_workflow: Option<PlanningWorkflowConfig>,
+    /// Statuses and allowed moves between them; unset keeps the six builtin
+    /// statuses with their usual lifecycle rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_workflow: Option<StatusWorkflowConfig>,
+    /// What happens to a feature or epic once its last open child closes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_completion: Option<ParentCompletionConfig>,
+}
+
+/// Ordered planning states, which must include `needs_planning` (the
+/// default for new tasks) and `planned` (the `coding` lane). `transitions`
+/// maps a state to the states it may move to; a state without an entry may
+/// move anywhere, and an empty map allows every move.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanningWorkflowConfig {
+    pub states: Vec<PlanningState>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transitions: BTreeMap<String, Vec<PlanningState>>,
+}
+
+impl PlanningWorkflowConfig {
+    pub fn allows(&self, from: &PlanningState, to: &PlanningState) -> bool {
+        from == to
+            || self
+                .transitions
+                .get(from.as_str())
+                .is_none_or(|targets| targets.contains(to))
+    }
+}
+
+/// Ordered statuses, which must include all six builtins; extra statuses
+/// count as active, unfinished work. `transitions` maps a status to the
+/// statuses it may move to; a status without an entry may move anywhere the
+/// builtin lifecycle allows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusWorkflowConfig {
+    pub statuses: Vec<TaskStatus>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transitions: BTreeMap<String, Vec<TaskStatus>>,
+}
+
+impl StatusWorkflowConfig {
+    pub fn allows(&self, from: &TaskStatus, to: &TaskStatus) -> bool {
+        from == to
+            || self
+                .transitions
+                .get(from.as_str())
+                .is_none_or(|targets| targets.contains(to))
+    }
+}
+
+/// Opt-in rollup for features and epics: once every child is closed or
+/// canceled, the parent moves to `status`, gains `label`, and gets `note`.
+/// At least one action is required.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParentCompletionConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Default for Config {
+    /// The config `tsq init` writes: only `schema_version` and
+    /// `snapshot_every` are set.
+    fn default() -> Self {
+        Config {
+            schema_version: SCHEMA_VERSION,
+            snapshot_every: 200,
+            sync_branch: None,
+            hooks: HooksConfig::default(),
+            policies: Vec::new(),
+            escalation: None,
+            stale: None,
+            auto_sync: None,
+            sync_disabled: false,
+            sync_routes: Vec::new(),
+            encrypt: false,
+            encryption_salt: None,
+            redaction: None,
+            roles: None,
+            relation_types: Default::default(),
+            lock_ttl_ms: None,
+            compression: None,
+            event_segments: None,
+            label_colors: Default::default(),
+            skill_registries: Default::default(),
+            lanes: Vec::new(),
+            actors: Default::default(),
+            strict_actors: false,
+            actor_resolution: None,
+            payload_limits: None,
+            planning_workflow: None,
+            status_workflow: None,
+            parent_completion: None,
+        }
+    }
+}
+
+impl Config {
+    /// Configured planning states, or the builtin pair.
+    pub fn planning_states(&self) -> Vec<PlanningState> {
+        match &self.planning_workflow {
+            Some(workflow) => workflow.states.clone(),
+            None => vec![PlanningState::NeedsPlanning, PlanningState::Planned],
+        }
+    }
+
+    /// Configured statuses, or the six builtins.
+    pub fn statuses(&self) -> Vec<TaskStatus> {
+        match &self.status_workflow {
+            Some(workflow) => workflow.statuses.clone(),
+            None => TaskStatus::BUILTIN.to_vec(),
+        }
+    }
+}
+
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 1024 * 1024;
+pub const DEFAULT_OVERFLOW_BYTES: usize = 16 * 1024;
+
+/// Byte limits on a single note, description, checklist item, or criterion.
+/// Bodies over `overflow_bytes` are stored in `.tasque/blobs/` and the event
+/// keeps a preview; bodies over `max_bytes` are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PayloadLimitsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overflow_bytes: Option<usize>,
+}
+
+impl PayloadLimitsConfig {
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes.unwrap_or(DEFAULT_MAX_PAYLOAD_BYTES)
+    }
+
+    pub fn overflow_bytes(&self) -> usize {
+        self.overflow_bytes.unwrap_or(DEFAULT_OVERFLOW_BYTES)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    Zstd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSegmentation {
+    Monthly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl LabelColor {
+    pub const ALL: [LabelColor; 8] = [
+        LabelColor::Red,
+        LabelColor::Green,
+        LabelColor::Yellow,
+        LabelColor::Blue,
+        LabelColor::Magenta,
+        LabelColor::Cyan,
+        LabelColor::White,
+        LabelColor::Gray,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LabelColor::Red => "red",
+            LabelColor::Green => "green",
+            LabelColor::Yellow => "yellow",
+            LabelColor::Blue => "blue",
+            LabelColor::Magenta => "magenta",
+            LabelColor::Cyan => "cyan",
+            LabelColor::White => "white",
+            LabelColor::Gray => "gray",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<LabelColor> {
+        let raw = raw.trim().to_lowercase();
+        LabelColor::ALL
+            .into_iter()
+            .find(|color| color.as_str() == raw)
+    }
+}
+
+/// `.tasque/events.manifest.json`: segments stitched, in order, before the
+/// active `events.jsonl`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSegmentManifest {
+    pub schema_version: u32,
+    pub segments: Vec<EventSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSegment {
+    pub file: String,
+    pub month: String,
+}
+
+/// Shards the event log per project: commands run under `path` (relative to
+/// the repo root) use `branch`'s worktree instead of `sync_branch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncRoute {
+    pub branch: String,
+    pub path: String,
+}
+
+/// What a sync worktree does after each mutation. Without this block every
+/// mutation is committed and nothing is pushed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoSyncConfig {
+    #[serde(default = "default_auto_sync_commit")]
+    pub commit: bool,
+    #[serde(default)]
+    pub push: bool,
+    /// Skip the commit when the branch tip is younger than this. It is a
+    /// throttle, not a debounce: nothing flushes when the window ends, so
+    /// skipped events stay uncommitted until the next mutation after the
+    /// window, `tsq sync`, or the sync daemon.
+    #[serde(default)]
+    pub throttle_secs: u64,
+}
+
+impl Default for AutoSyncConfig {
+    fn default() -> Self {
+        Self {
+            commit: default_auto_sync_commit(),
+            push: false,
+            throttle_secs: 0,
+        }
+    }
+}
+
+fn default_auto_sync_commit() -> bool {
+    true
+}
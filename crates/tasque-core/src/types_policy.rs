@@ -0,0 +1,294 @@
+use crate::types::{PlanningState, TaskKind, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorType {
+    #[default]
+    Human,
+    Agent,
+}
+
+/// How a registered actor is shown, and whether it is a person or an agent.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ActorIdentity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(rename = "type", default)]
+    pub actor_type: ActorType,
+}
+
+/// One place `get_actor` looks for the acting name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorSource {
+    /// `TSQ_ACTOR`
+    Env,
+    /// `git config user.email`
+    GitEmail,
+    /// `git config user.name`
+    GitName,
+    /// `USERNAME` or `USER`
+    OsUser,
+    /// `actor_resolution.actor`
+    Config,
+}
+
+impl ActorSource {
+    /// Used when `actor_resolution` is unset.
+    pub const DEFAULT_ORDER: [ActorSource; 3] =
+        [ActorSource::Env, ActorSource::GitName, ActorSource::OsUser];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ActorSource::Env => "env",
+            ActorSource::GitEmail => "git_email",
+            ActorSource::GitName => "git_name",
+            ActorSource::OsUser => "os_user",
+            ActorSource::Config => "config",
+        }
+    }
+}
+
+/// Sources tried in `order` until one yields a name; `actor` is the value
+/// of the `config` source, which must be listed when it is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActorResolutionConfig {
+    pub order: Vec<ActorSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+}
+
+/// A named slice of the ready queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaneConfig {
+    pub name: String,
+    #[serde(default)]
+    pub when: LaneFilter,
+}
+
+/// Every non-empty list must match: `planning_state` and `kind` are any-of
+/// (a task without a planning state counts as `needs_planning`), `labels`
+/// are all-of.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LaneFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub planning_state: Vec<PlanningState>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kind: Vec<TaskKind>,
+}
+
+/// A git repository of skills (`url`, optionally `url#subdir`) plus the
+/// `sha256:` content checksums installs from it must match, keyed by skill name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillRegistry {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checksums: BTreeMap<String, String>,
+}
+
+/// Opt-in priority aging: active tasks older than `after_days` gain one level
+/// of effective priority, plus one more every `every_days`, up to `max_boost`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationConfig {
+    pub after_days: u32,
+    #[serde(default = "default_escalation_every_days")]
+    pub every_days: u32,
+    #[serde(default = "default_escalation_max_boost")]
+    pub max_boost: u8,
+}
+
+fn default_escalation_every_days() -> u32 {
+    7
+}
+
+fn default_escalation_max_boost() -> u8 {
+    1
+}
+
+/// Backlog hygiene applied by `tsq stale enforce`: tasks untouched for
+/// `stale_days` get `label` and, with `auto_defer`, move to deferred.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StalePolicyConfig {
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub auto_defer: bool,
+}
+
+fn default_stale_days() -> u32 {
+    30
+}
+
+/// Actor permissions, ordered so a higher role includes the lower ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActorRole {
+    Reader,
+    Writer,
+    Admin,
+}
+
+/// Role lists keyed by actor name. Without this block every actor may do
+/// everything; with it, actors in no list get `default`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolesConfig {
+    #[serde(default)]
+    pub admins: Vec<String>,
+    #[serde(default)]
+    pub writers: Vec<String>,
+    #[serde(default)]
+    pub readers: Vec<String>,
+    #[serde(default = "default_actor_role")]
+    pub default: ActorRole,
+}
+
+fn default_actor_role() -> ActorRole {
+    ActorRole::Reader
+}
+
+/// Rules applied by `tsq export --redact`. `strip` and `hash` name task
+/// fields; `drop` holds regexes matched against field names.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    #[serde(default = "default_redact_strip")]
+    pub strip: Vec<String>,
+    #[serde(default = "default_redact_hash")]
+    pub hash: Vec<String>,
+    #[serde(default)]
+    pub drop: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            strip: default_redact_strip(),
+            hash: default_redact_hash(),
+            drop: Vec::new(),
+        }
+    }
+}
+
+fn default_redact_strip() -> Vec<String> {
+    vec!["description".to_string(), "notes".to_string()]
+}
+
+fn default_redact_hash() -> Vec<String> {
+    vec!["assignee".to_string()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookMode {
+    /// Failures are reported as warnings after the events are committed.
+    #[default]
+    Advisory,
+    /// Runs before the events are committed; a failure aborts the mutation.
+    Blocking,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookConfig {
+    pub command: String,
+    #[serde(default)]
+    pub mode: HookMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<HookConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_status_change: Option<HookConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_claim: Option<HookConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_close: Option<HookConfig>,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.on_create.is_none()
+            && self.on_status_change.is_none()
+            && self.on_claim.is_none()
+            && self.on_close.is_none()
+    }
+}
+
+/// A metadata rule checked when a task moves into `when.status` or
+/// `when.planning_state`, optionally only for one `when.kind`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    pub name: String,
+    pub when: PolicyTrigger,
+    pub require: Vec<PolicyRequirement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PolicyTrigger {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<TaskKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planning_state: Option<PlanningState>,
+}
+
+/// Serialized as `label`, `label:<name>` (a trailing `*` matches a prefix),
+/// `description`, `spec`, `assignee`, `external_ref`, `parent`, or `reason`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PolicyRequirement {
+    Label(Option<String>),
+    Description,
+    Spec,
+    Assignee,
+    ExternalRef,
+    Parent,
+    Reason,
+}
+
+impl TryFrom<String> for PolicyRequirement {
+    type Error = String;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        if let Some(pattern) = raw.strip_prefix("label:") {
+            if pattern.is_empty() || pattern == "*" {
+                return Ok(PolicyRequirement::Label(None));
+            }
+            return Ok(PolicyRequirement::Label(Some(pattern.to_string())));
+        }
+        match raw.as_str() {
+            "label" => Ok(PolicyRequirement::Label(None)),
+            "description" => Ok(PolicyRequirement::Description),
+            "spec" => Ok(PolicyRequirement::Spec),
+            "assignee" => Ok(PolicyRequirement::Assignee),
+            "external_ref" => Ok(PolicyRequirement::ExternalRef),
+            "parent" => Ok(PolicyRequirement::Parent),
+            "reason" => Ok(PolicyRequirement::Reason),
+            _ => Err(format!("unknown policy requirement: {}", raw)),
+        }
+    }
+}
+
+impl From<PolicyRequirement> for String {
+    fn from(requirement: PolicyRequirement) -> Self {
+        match requirement {
+            PolicyRequirement::Label(None) => "label".to_string(),
+            PolicyRequirement::Label(Some(pattern)) => format!("label:{}", pattern),
+            PolicyRequirement::Description => "description".to_string(),
+            PolicyRequirement::Spec => "spec".to_string(),
+            PolicyRequirement::Assignee => "assignee".to_string(),
+            PolicyRequirement::ExternalRef => "external_ref".to_string(),
+            PolicyRequirement::Parent => "parent".to_string(),
+            PolicyRequirement::Reason => "reason".to_string(),
+        }
+    }
+}
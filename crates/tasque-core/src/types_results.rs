@@ -0,0 +1,257 @@
+use crate::types::{DependencyType, RelationType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncSetupResult {
+    pub branch: String,
+    pub worktree_path: String,
+    pub created_branch: bool,
+    pub merge_driver_configured: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncDisableResult {
+    pub branch: String,
+    pub worktree_path: String,
+    pub repo_root: String,
+    pub events_migrated: usize,
+    pub specs_copied: usize,
+    pub branch_deleted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrateResult {
+    pub events_migrated: usize,
+    pub branch: String,
+    pub worktree_path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRunResult {
+    pub branch: String,
+    pub worktree_path: String,
+    pub committed: bool,
+    pub pushed: bool,
+    pub has_upstream: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRemoteResult {
+    pub branch: String,
+    pub worktree_path: String,
+    pub remote: String,
+    pub committed: bool,
+    /// Remote commits rebased under local ones; 0 when already up to date.
+    pub pulled_commits: usize,
+    pub pushed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncMergeDriverStatus {
+    /// `.gitattributes` routes `.tasque/events.jsonl` to the tasque-events driver.
+    pub gitattributes: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncStatusResult {
+    pub configured: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_path: Option<String>,
+    pub worktree_valid: bool,
+    /// Events appended since the last commit on the sync branch.
+    pub uncommitted_events: usize,
+    /// Any uncommitted change in the worktree, events or otherwise.
+    pub dirty: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    /// Relative to the last fetched remote-tracking ref; absent before a fetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ahead: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behind: Option<usize>,
+    pub merge_driver: SyncMergeDriverStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daemon: Option<SyncDaemonStatus>,
+    pub healthy: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<String>,
+}
+
+/// Last known state of `tsq sync daemon`, persisted in the worktree's git dir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncDaemonStatus {
+    pub branch: String,
+    pub worktree_path: String,
+    pub pid: u32,
+    pub updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync_at: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub consecutive_failures: u32,
+    /// Seconds until the next attempt, including any failure backoff.
+    pub next_attempt_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookInstallResult {
+    pub hook_path: String,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookUninstallResult {
+    pub hook_path: String,
+    pub removed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeOk<T> {
+    pub schema_version: u32,
+    pub command: String,
+    pub ok: bool,
+    pub data: T,
+    /// Diagnostics outside the payload, such as `--timing` phase totals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeErr {
+    pub schema_version: u32,
+    pub command: String,
+    pub ok: bool,
+    pub error: EnvelopeError,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeError {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Envelope<T> {
+    Ok(EnvelopeOk<T>),
+    Err(EnvelopeErr),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepairPlan {
+    pub orphaned_deps: Vec<RepairDep>,
+    pub orphaned_links: Vec<RepairLink>,
+    pub stale_temps: Vec<String>,
+    pub stale_lock: bool,
+    pub old_snapshots: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepairDep {
+    pub child: String,
+    pub blocker: String,
+    pub dep_type: DependencyType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepairLink {
+    pub src: String,
+    pub dst: String,
+    #[serde(rename = "type")]
+    pub rel_type: RelationType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepairResult {
+    pub plan: RepairPlan,
+    pub applied: bool,
+    pub events_appended: usize,
+    pub files_removed: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salvage: Option<SalvageReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshots: Option<SnapshotRebuild>,
+}
+
+/// `tasque-backup.json` at the root of a `tsq backup create` archive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub schema_version: u32,
+    pub created_at: String,
+    pub tsq_version: String,
+    pub files: Vec<BackupFile>,
+}
+
+/// One `.tasque/`-relative file in a backup, with its size and checksum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupFile {
+    pub path: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupResult {
+    pub path: String,
+    pub created_at: String,
+    pub files: usize,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestoreResult {
+    pub path: String,
+    pub created_at: String,
+    pub files: usize,
+    pub replaced: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotRebuild {
+    pub removed: Vec<String>,
+    pub rebuilt_at_event: Option<usize>,
+    pub verified: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SalvageReport {
+    pub kept_events: usize,
+    pub quarantined: Vec<QuarantinedEvent>,
+    pub quarantine_file: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantinedEvent {
+    pub line: usize,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeOptions {
+    pub repo_root: String,
+    pub actor: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeDriverOutcome {
+    pub total_events: usize,
+    pub duplicates_removed: usize,
+    pub conflict: bool,
+    pub conflicting_ids: Vec<String>,
+}
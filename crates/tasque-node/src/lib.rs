@@ -0,0 +1,209 @@
+//! Node.js bindings for `tasque-core`.
+//!
+//! `Tasque` wraps a `TasqueService` so Node tooling can call into the engine
+//! in-process instead of spawning `tsq --json`. Options and results are the
+//! snake_case JSON shapes the CLI prints under `data`; failures throw an
+//! `Error` whose message starts with the stable error code
+//! (`NOT_FOUND: ...`).
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tasque_core::app::runtime::{get_actor, now_iso};
+use tasque_core::app::service_types::{
+    ClaimInput, CloseInput, CreateInput, DepInput, HistoryInput, InitInput, ListFilter, LogInput,
+    NoteAddInput, ReopenInput, UpdateInput,
+};
+use tasque_core::app::sync::resolve_effective_root;
+use tasque_core::{TasqueService, TsqError};
+
+#[napi]
+pub struct Tasque {
+    service: TasqueService,
+}
+
+#[napi]
+impl Tasque {
+    /// Opens the repo at `repo_root`, following its sync worktree like `tsq`
+    /// does. `actor` defaults to the same resolution as the CLI.
+    #[napi(constructor)]
+    pub fn new(repo_root: String, actor: Option<String>) -> napi::Result<Self> {
+        let actor = actor.unwrap_or_else(|| get_actor(&repo_root));
+        let root = resolve_effective_root(&repo_root).map_err(to_napi)?;
+        Ok(Self {
+            service: TasqueService::new(root, actor, now_iso),
+        })
+    }
+
+    #[napi]
+    pub fn init(&self) -> napi::Result<Value> {
+        result(self.service.init(InitInput::default()))
+    }
+
+    /// `options`: `kind`, `priority`, `description`, `parent`,
+    /// `external_ref`, `discovered_from`, `planning_state`, `explicit_id`,
+    /// `ensure`, `force`.
+    #[napi]
+    pub fn create(&self, title: String, options: Option<Value>) -> napi::Result<Value> {
+        let input: CreateInput = input(
+            json!({
+                "title": title,
+                "kind": "task",
+                "priority": 2,
+                "exact_id": false,
+                "ensure": false,
+                "force": false,
+                "skip_duplicate_check": false,
+            }),
+            options,
+        )?;
+        result(self.service.create(input))
+    }
+
+    #[napi]
+    pub fn show(&self, id: String) -> napi::Result<Value> {
+        result(self.service.show(&id, false))
+    }
+
+    /// `filter` takes the `ListFilter` fields, e.g. `{statuses: ["open"]}`.
+    #[napi]
+    pub fn list(&self, filter: Option<Value>) -> napi::Result<Value> {
+        let filter: ListFilter = input(json!({ "unassigned": false }), filter)?;
+        result(self.service.list(&filter))
+    }
+
+    #[napi]
+    pub fn ready(&self, lane: Option<String>) -> napi::Result<Value> {
+        result(self.service.ready(lane.as_deref()))
+    }
+
+    /// `changes` takes the `UpdateInput` fields, e.g. `{status: "blocked"}`.
+    #[napi]
+    pub fn update(&self, id: String, changes: Value) -> napi::Result<Value> {
+        let input: UpdateInput = input(
+            json!({
+                "id": id,
+                "clear_description": false,
+                "clear_discovered_from": false,
+                "clear_external_ref": false,
+                "exact_id": false,
+            }),
+            Some(changes),
+        )?;
+        result(self.service.update(input))
+    }
+
+    #[napi]
+    pub fn claim(&self, id: String, assignee: Option<String>) -> napi::Result<Value> {
+        result(self.service.claim(ClaimInput {
+            id,
+            assignee,
+            require_spec: false,
+            exact_id: false,
+        }))
+    }
+
+    #[napi]
+    pub fn close(&self, ids: Vec<String>, reason: Option<String>) -> napi::Result<Value> {
+        result(self.service.close(CloseInput {
+            ids,
+            reason,
+            exact_id: false,
+        }))
+    }
+
+    /// `from` is `closed` (the default) or `canceled`.
+    #[napi]
+    pub fn reopen(&self, ids: Vec<String>, from: Option<String>) -> napi::Result<Value> {
+        let input: ReopenInput =
+            input(json!({ "ids": ids, "from": from, "exact_id": false }), None)?;
+        result(self.service.reopen(input))
+    }
+
+    #[napi]
+    pub fn note_add(&self, id: String, text: String) -> napi::Result<Value> {
+        result(self.service.note_add(NoteAddInput {
+            id,
+            text,
+            exact_id: false,
+        }))
+    }
+
+    /// Makes `child` wait on `blocker`; `dep_type` defaults to `blocks`.
+    #[napi]
+    pub fn dep_add(
+        &self,
+        child: String,
+        blocker: String,
+        dep_type: Option<String>,
+    ) -> napi::Result<Value> {
+        let dep_type = dep_type
+            .map(|raw| from_value(Value::String(raw)))
+            .transpose()?;
+        self.service
+            .dep_add(DepInput {
+                child,
+                blocker,
+                dep_type,
+                exact_id: false,
+            })
+            .map(|(child, blocker, dep_type)| {
+                json!({ "child": child, "blocker": blocker, "dep_type": dep_type })
+            })
+            .map_err(to_napi)
+    }
+
+    /// `options`: `limit`, `type`, `actor`, `since`.
+    #[napi]
+    pub fn history(&self, id: String, options: Option<Value>) -> napi::Result<Value> {
+        let input: HistoryInput = input(json!({ "id": id, "exact_id": false }), options)?;
+        result(self.service.history(input))
+    }
+
+    /// `options`: `limit`, `before_cursor`, `event_type`, `actor`.
+    #[napi]
+    pub fn log(&self, options: Option<Value>) -> napi::Result<Value> {
+        let input: LogInput = input(json!({}), options)?;
+        result(self.service.log(input))
+    }
+}
+
+/// Overlays caller options onto the defaults for fields JS may omit.
+fn input<T: DeserializeOwned>(defaults: Value, options: Option<Value>) -> napi::Result<T> {
+    let mut merged = defaults;
+    match options {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(fields)) => {
+            if let Value::Object(target) = &mut merged {
+                target.extend(fields);
+            }
+        }
+        Some(_) => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "VALIDATION_ERROR: options must be an object",
+            ));
+        }
+    }
+    from_value(merged)
+}
+
+fn from_value<T: DeserializeOwned>(value: Value) -> napi::Result<T> {
+    serde_json::from_value(value)
+        .map_err(|error| Error::new(Status::InvalidArg, format!("VALIDATION_ERROR: {}", error)))
+}
+
+fn result<T: Serialize>(value: Result<T, TsqError>) -> napi::Result<Value> {
+    let value = value.map_err(to_napi)?;
+    serde_json::to_value(value)
+        .map_err(|error| Error::new(Status::GenericFailure, format!("INTERNAL_ERROR: {}", error)))
+}
+
+fn to_napi(error: TsqError) -> Error {
+    Error::new(
+        Status::GenericFailure,
+        format!("{}: {}", error.code, error.message),
+    )
+}
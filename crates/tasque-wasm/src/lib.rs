@@ -0,0 +1,155 @@
+//! Browser build of the projection engine.
+//!
+//! A `Projection` replays a fetched `events.jsonl` once and answers
+//! read-only views from it. Views return JSON strings in the same snake_case
+//! task shape `tsq --json` prints; failures throw with the CLI error code as
+//! the message prefix (`EVENTS_CORRUPT: ...`).
+//!
+//! Build with `wasm-pack build crates/tasque-wasm --target web`.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use tasque_core::domain::query::{evaluate_query, parse_query};
+use tasque_core::domain::sort::{sort_child_tasks, sort_tasks};
+use tasque_core::domain::validate::list_ready;
+use tasque_core::replay::replay;
+use tasque_core::{State, Task, TaskStatus, TsqError};
+use wasm_bindgen::prelude::*;
+
+/// Board column order, matching `tsq report`.
+const BOARD_COLUMNS: [TaskStatus; 6] = [
+    TaskStatus::Open,
+    TaskStatus::InProgress,
+    TaskStatus::Blocked,
+    TaskStatus::Deferred,
+    TaskStatus::Closed,
+    TaskStatus::Canceled,
+];
+
+#[wasm_bindgen]
+pub struct Projection {
+    state: State,
+    warning: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BoardColumn {
+    status: TaskStatus,
+    tasks: Vec<Task>,
+}
+
+#[derive(Serialize)]
+struct TreeNode {
+    task: Task,
+    children: Vec<TreeNode>,
+}
+
+#[wasm_bindgen]
+impl Projection {
+    #[wasm_bindgen(constructor)]
+    pub fn new(events: &str) -> Result<Projection, JsError> {
+        let replayed = replay(events).map_err(js_error)?;
+        Ok(Projection {
+            state: replayed.state,
+            warning: replayed.warning,
+        })
+    }
+
+    /// Set when a torn trailing line was skipped.
+    #[wasm_bindgen(getter)]
+    pub fn warning(&self) -> Option<String> {
+        self.warning.clone()
+    }
+
+    /// All tasks in list order, optionally narrowed by `tsq find search`
+    /// query syntax.
+    pub fn tasks(&self, query: Option<String>) -> Result<String, JsError> {
+        let tasks = self.sorted_tasks();
+        let tasks = match query.as_deref().map(str::trim) {
+            Some(query) if !query.is_empty() => {
+                let filter = parse_query(query).map_err(js_error)?;
+                evaluate_query(&tasks, &filter, &self.state)
+            }
+            _ => tasks,
+        };
+        to_json(&tasks)
+    }
+
+    pub fn ready(&self) -> Result<String, JsError> {
+        to_json(&sort_tasks(&list_ready(&self.state)))
+    }
+
+    /// `[{status, tasks}]`, one column per status. Custom statuses in use
+    /// get columns after `in_progress`, by name.
+    pub fn board(&self) -> Result<String, JsError> {
+        let tasks = self.sorted_tasks();
+        let mut statuses = BOARD_COLUMNS.to_vec();
+        let mut custom: Vec<TaskStatus> = tasks
+            .iter()
+            .filter(|task| matches!(task.status, TaskStatus::Custom(_)))
+            .map(|task| task.status.clone())
+            .collect();
+        custom.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        custom.dedup();
+        statuses.splice(2..2, custom);
+        let columns: Vec<BoardColumn> = statuses
+            .iter()
+            .map(|status| BoardColumn {
+                status: status.clone(),
+                tasks: tasks
+                    .iter()
+                    .filter(|task| task.status == *status)
+                    .cloned()
+                    .collect(),
+            })
+            .collect();
+        to_json(&columns)
+    }
+
+    /// Parent/child hierarchy as nested `{task, children}` nodes.
+    pub fn tree(&self) -> Result<String, JsError> {
+        let mut children_by_parent: HashMap<Option<&str>, Vec<Task>> = HashMap::new();
+        for task in self.state.tasks.values() {
+            let parent = task
+                .parent_id
+                .as_deref()
+                .filter(|parent| self.state.tasks.contains_key(*parent));
+            children_by_parent
+                .entry(parent)
+                .or_default()
+                .push(task.clone());
+        }
+        to_json(&build_nodes(None, &children_by_parent))
+    }
+}
+
+impl Projection {
+    fn sorted_tasks(&self) -> Vec<Task> {
+        let tasks: Vec<Task> = self.state.tasks.values().cloned().collect();
+        sort_tasks(&tasks)
+    }
+}
+
+fn build_nodes(
+    parent: Option<&str>,
+    children_by_parent: &HashMap<Option<&str>, Vec<Task>>,
+) -> Vec<TreeNode> {
+    let Some(children) = children_by_parent.get(&parent) else {
+        return Vec::new();
+    };
+    sort_child_tasks(children)
+        .into_iter()
+        .map(|task| TreeNode {
+            children: build_nodes(Some(task.id.as_str()), children_by_parent),
+            task,
+        })
+        .collect()
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, JsError> {
+    serde_json::to_string(value).map_err(|error| JsError::new(&error.to_string()))
+}
+
+fn js_error(error: TsqError) -> JsError {
+    JsError::new(&format!("{}: {}", error.code, error.message))
+}
@@ -1,11 +1,25 @@
+use crate::app::transaction::{StagedHandle, lock_staged};
+use crate::cli::disambiguate;
+use crate::cli::porcelain::{self, PorcelainVersion};
+use crate::cli::render::print_dry_run_events;
 use crate::errors::TsqError;
 use crate::output::{err_envelope, ok_envelope};
+use crate::trace;
+use crate::types::{Envelope, EventRecord};
 use serde::Serialize;
+use std::cell::RefCell;
+
+thread_local! {
+    static DRY_RUN_PREVIEW: RefCell<Option<StagedHandle>> = const { RefCell::new(None) };
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct GlobalOpts {
     pub json: bool,
     pub exact_id: bool,
+    pub dry_run: bool,
+    /// Frozen output shape from `--porcelain`; implies `json`.
+    pub porcelain: Option<PorcelainVersion>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +34,38 @@ impl GlobalOpts {
     }
 }
 
+/// Registers the staged transaction whose events `run_action` reports when
+/// `--dry-run` is active.
+pub fn set_dry_run_preview(handle: Option<StagedHandle>) {
+    DRY_RUN_PREVIEW.with(|preview| *preview.borrow_mut() = handle);
+}
+
+fn dry_run_events() -> Vec<EventRecord> {
+    DRY_RUN_PREVIEW.with(|preview| {
+        preview
+            .borrow()
+            .as_ref()
+            .map(|handle| lock_staged(handle).events().to_vec())
+            .unwrap_or_default()
+    })
+}
+
+fn with_dry_run_events<J: Serialize>(data: J) -> serde_json::Value {
+    let events = dry_run_events();
+    let mut value = serde_json::to_value(data).unwrap_or(serde_json::Value::Null);
+    if !value.is_object() {
+        value = serde_json::json!({ "result": value });
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("dry_run".to_string(), serde_json::Value::Bool(true));
+        object.insert(
+            "events".to_string(),
+            serde_json::to_value(events).unwrap_or_default(),
+        );
+    }
+    value
+}
+
 pub fn run_action<T, J, F, M, H>(
     command_line: &str,
     opts: GlobalOpts,
@@ -33,23 +79,61 @@ where
     H: FnOnce(&T) -> Result<(), TsqError>,
     J: Serialize,
 {
+    if let Some(version) = opts.porcelain {
+        return match action() {
+            Ok(value) => {
+                let data = {
+                    let _phase = trace::phase("render");
+                    serde_json::to_value(map_json(&value)).unwrap_or_default()
+                };
+                println!("{}", porcelain::render(version, command_line, Ok(data)));
+                report_timings_to_stderr();
+                0
+            }
+            Err(error) => {
+                println!("{}", porcelain::render(version, command_line, Err(&error)));
+                report_timings_to_stderr();
+                error.exit_code
+            }
+        };
+    }
     match action() {
         Ok(value) => {
-            if opts.json() {
-                let envelope = ok_envelope(command_line, map_json(&value));
-                match serde_json::to_string_pretty(&envelope) {
-                    Ok(text) => println!("{}", text),
-                    Err(error) => {
-                        eprintln!("INTERNAL_ERROR: failed serializing json output: {}", error);
-                        return 2;
+            if opts.json() && opts.dry_run {
+                let data = {
+                    let _phase = trace::phase("render");
+                    with_dry_run_events(map_json(&value))
+                };
+                if let Err(error) = print_timed_envelope(command_line, data) {
+                    eprintln!("INTERNAL_ERROR: failed serializing json output: {}", error);
+                    return 2;
+                }
+            } else if opts.json() {
+                let data = {
+                    let _phase = trace::phase("render");
+                    map_json(&value)
+                };
+                if let Err(error) = print_timed_envelope(command_line, data) {
+                    eprintln!("INTERNAL_ERROR: failed serializing json output: {}", error);
+                    return 2;
+                }
+            } else {
+                let rendered = {
+                    let _phase = trace::phase("render");
+                    human(&value)
+                };
+                if let Err(error) = rendered {
+                    eprintln!("{}: {}", error.code, error.message);
+                    if let Some(details) = error.details {
+                        eprintln!("{}", details);
                     }
+                    report_timings_to_stderr();
+                    return error.exit_code;
                 }
-            } else if let Err(error) = human(&value) {
-                eprintln!("{}: {}", error.code, error.message);
-                if let Some(details) = error.details {
-                    eprintln!("{}", details);
+                if opts.dry_run {
+                    print_dry_run_events(&dry_run_events());
                 }
-                return error.exit_code;
+                report_timings_to_stderr();
             }
             0
         }
@@ -71,19 +155,44 @@ where
                         return 2;
                     }
                 }
+            } else if disambiguate::try_pick(&error) {
+                // The caller re-runs the command with the picked id.
             } else {
                 eprintln!("{}: {}", error.code, error.message);
                 if let Some(details) = error.details {
                     eprintln!("{}", details);
                 }
             }
+            report_timings_to_stderr();
             error.exit_code
         }
     }
 }
 
+/// Prints an ok envelope, with `meta.timing` when `--timing` is on.
+fn print_timed_envelope<J: Serialize>(
+    command_line: &str,
+    data: J,
+) -> Result<(), serde_json::Error> {
+    let mut envelope = ok_envelope(command_line, data);
+    if let (Envelope::Ok(ok), Some(timings)) = (&mut envelope, trace::take_timings()) {
+        ok.meta = Some(serde_json::json!({ "timing": timings }));
+    }
+    println!("{}", serde_json::to_string_pretty(&envelope)?);
+    Ok(())
+}
+
+/// Human, porcelain, and error output keep `--timing` off stdout.
+fn report_timings_to_stderr() {
+    if let Some(timings) = trace::take_timings() {
+        eprintln!("{}", trace::format_timings(&timings));
+    }
+}
+
 pub fn emit_error(command_line: &str, opts: GlobalOpts, error: TsqError) -> i32 {
-    if opts.json() {
+    if let Some(version) = opts.porcelain {
+        println!("{}", porcelain::render(version, command_line, Err(&error)));
+    } else if opts.json() {
         let envelope = err_envelope(
             command_line,
             error.code.clone(),
@@ -106,5 +215,6 @@ pub fn emit_error(command_line: &str, opts: GlobalOpts, error: TsqError) -> i32
             eprintln!("{}", details);
         }
     }
+    report_timings_to_stderr();
     error.exit_code
 }
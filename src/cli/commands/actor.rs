@@ -0,0 +1,164 @@
+use crate::app::runtime::{find_tasque_root, get_repo_root, resolve_actor};
+use crate::app::service::TasqueService;
+use crate::app::service_types::{ActorAddInput, ActorEntry, ActorResolution};
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::types::{ActorSource, ActorType};
+use clap::{Args, Subcommand, ValueEnum};
+
+#[derive(Debug, Subcommand)]
+pub enum ActorCommand {
+    /// Register an actor, or update the given fields of an existing one
+    Add(ActorAddArgs),
+    /// Remove an actor from the directory
+    Remove(ActorRemoveArgs),
+    /// List registered actors
+    List,
+}
+
+#[derive(Debug, Args)]
+#[command(
+    after_help = "With \"strict_actors\": true in config.json, assigning or claiming a task
+for an actor missing from this directory fails with UNKNOWN_ACTOR.
+
+Examples:
+  tsq actor add alice --display-name \"Alice Liddell\" --email alice@example.com
+  tsq actor add codex --type agent"
+)]
+pub struct ActorAddArgs {
+    /// Actor name as passed to --actor, --assignee, and TSQ_ACTOR
+    pub name: String,
+    #[arg(long = "display-name")]
+    pub display_name: Option<String>,
+    #[arg(long)]
+    pub email: Option<String>,
+    /// Whether the actor is a person or an agent (default: human)
+    #[arg(long = "type", value_enum)]
+    pub actor_type: Option<ActorTypeArg>,
+}
+
+#[derive(Debug, Args)]
+pub struct ActorRemoveArgs {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ActorTypeArg {
+    Human,
+    Agent,
+}
+
+impl From<ActorTypeArg> for ActorType {
+    fn from(value: ActorTypeArg) -> Self {
+        match value {
+            ActorTypeArg::Human => ActorType::Human,
+            ActorTypeArg::Agent => ActorType::Agent,
+        }
+    }
+}
+
+pub fn execute_actor(service: &TasqueService, command: ActorCommand, opts: GlobalOpts) -> i32 {
+    match command {
+        ActorCommand::Add(args) => run_action(
+            "tsq actor add",
+            opts,
+            || {
+                service.actor_add(ActorAddInput {
+                    name: args.name.clone(),
+                    display_name: args.display_name.clone(),
+                    email: args.email.clone(),
+                    actor_type: args.actor_type.map(ActorType::from),
+                })
+            },
+            |actor| serde_json::json!({ "actor": actor }),
+            |actor| {
+                print_actor(actor);
+                Ok(())
+            },
+        ),
+        ActorCommand::Remove(args) => run_action(
+            "tsq actor remove",
+            opts,
+            || service.actor_remove(&args.name),
+            |actor| serde_json::json!({ "actor": actor }),
+            |actor| {
+                println!("removed actor {}", actor.name);
+                Ok(())
+            },
+        ),
+        ActorCommand::List => run_action(
+            "tsq actor list",
+            opts,
+            || service.actor_list(),
+            |actors| serde_json::json!({ "actors": actors }),
+            |actors| {
+                if actors.is_empty() {
+                    println!("no actors");
+                }
+                for actor in actors {
+                    print_actor(actor);
+                }
+                Ok(())
+            },
+        ),
+    }
+}
+
+pub fn execute_whoami(service: &TasqueService, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq whoami",
+        opts,
+        || {
+            let mut resolution = resolve_actor(get_repo_root());
+            // Outside a repo there is no directory to look the actor up in.
+            if find_tasque_root().is_some() {
+                resolution.identity = service
+                    .actor_list()?
+                    .into_iter()
+                    .find(|entry| entry.name == resolution.actor)
+                    .map(|entry| entry.identity);
+            }
+            Ok(resolution)
+        },
+        |data| data.clone(),
+        |data| {
+            print_whoami(data);
+            Ok(())
+        },
+    )
+}
+
+/// The winning actor and source, then every source in chain order.
+fn print_whoami(data: &ActorResolution) {
+    let source = data.source.map_or("fallback", ActorSource::as_str);
+    match &data.identity {
+        Some(identity) => print_actor(&ActorEntry {
+            name: format!("{}  (from {})", data.actor, source),
+            identity: identity.clone(),
+        }),
+        None => println!("{}  (from {})", data.actor, source),
+    }
+    for candidate in &data.candidates {
+        println!(
+            "  {:<10} {}",
+            candidate.source.as_str(),
+            candidate.value.as_deref().unwrap_or("-")
+        );
+    }
+}
+
+/// `name  Display Name <email>  [agent]`, omitting unset fields.
+fn print_actor(actor: &ActorEntry) {
+    let mut line = actor.name.clone();
+    if let Some(display_name) = actor.identity.display_name.as_deref() {
+        line.push_str(&format!("  {}", display_name));
+    }
+    if let Some(email) = actor.identity.email.as_deref() {
+        line.push_str(&format!("  <{}>", email));
+    }
+    let kind = match actor.identity.actor_type {
+        ActorType::Human => "human",
+        ActorType::Agent => "agent",
+    };
+    line.push_str(&format!("  [{}]", kind));
+    println!("{}", line);
+}
@@ -0,0 +1,81 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::{AuditInput, AuditResult};
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::parsers::parse_since_timestamp;
+use crate::cli::style;
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq audit --actor bot-alpha
+  tsq audit --actor bot-alpha --since 2024-06-01")]
+pub struct AuditArgs {
+    /// Actor whose events to summarize
+    #[arg(long)]
+    pub actor: String,
+    /// Only include events at or after this date or ISO timestamp
+    #[arg(long)]
+    pub since: Option<String>,
+}
+
+pub fn execute_audit(service: &TasqueService, args: AuditArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq audit",
+        opts,
+        || {
+            let since = args
+                .since
+                .as_deref()
+                .map(|value| parse_since_timestamp(value, "since"))
+                .transpose()?;
+            service.audit(AuditInput {
+                actor: args.actor.clone(),
+                since,
+            })
+        },
+        |audit| serde_json::to_value(audit).unwrap_or_default(),
+        |audit| {
+            print_audit(audit);
+            Ok(())
+        },
+    )
+}
+
+fn print_audit(audit: &AuditResult) {
+    let mut header = format!("actor={}", audit.actor);
+    if let Some(display_name) = audit
+        .identity
+        .as_ref()
+        .and_then(|identity| identity.display_name.as_deref())
+    {
+        header.push_str(&format!(" ({})", display_name));
+    }
+    if let Some(since) = audit.since.as_deref() {
+        header.push_str(&format!(" since={}", since));
+    }
+    println!(
+        "{} events={} tasks={}",
+        header, audit.total, audit.tasks_touched
+    );
+    if audit.events.is_empty() {
+        println!("{}", style::muted("no events"));
+        return;
+    }
+    let counts = audit
+        .counts
+        .iter()
+        .map(|entry| format!("{}={}", entry.category, entry.count))
+        .collect::<Vec<_>>();
+    println!("{}", counts.join(" "));
+    println!();
+    for entry in &audit.events {
+        // Creates already quote the title in their summary.
+        let title = entry
+            .title
+            .as_deref()
+            .filter(|_| entry.category != "created")
+            .map(|title| format!(" {}", style::muted(&format!("\"{}\"", title))))
+            .unwrap_or_default();
+        println!("{} {} {}{}", entry.ts, entry.task_id, entry.summary, title);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::app::service::TasqueService;
+use crate::cli::action::{GlobalOpts, run_action};
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum BackupCommand {
+    /// Archive the whole `.tasque` directory with a checksum manifest
+    Create(BackupCreateArgs),
+    /// Verify a backup archive and restore it into `.tasque`
+    Restore(BackupRestoreArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BackupCreateArgs {
+    /// Archive path; a `.zst` extension compresses it (default tasque-backup-<time>.tar.zst)
+    #[arg(long)]
+    pub out: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct BackupRestoreArgs {
+    /// Archive written by `tsq backup create`
+    pub path: String,
+    /// Replace a repo that already has events
+    #[arg(long)]
+    pub force: bool,
+}
+
+pub fn execute_backup(service: &TasqueService, command: BackupCommand, opts: GlobalOpts) -> i32 {
+    match command {
+        BackupCommand::Create(args) => run_action(
+            "tsq backup create",
+            opts,
+            || service.backup_create(args.out.as_deref()),
+            |data| data.clone(),
+            |data| {
+                println!(
+                    "Backed up {} files to {} (sha256 {})",
+                    data.files, data.path, data.sha256
+                );
+                Ok(())
+            },
+        ),
+        BackupCommand::Restore(args) => run_action(
+            "tsq backup restore",
+            opts,
+            || service.backup_restore(&args.path, args.force),
+            |data| data.clone(),
+            |data| {
+                println!(
+                    "Restored {} files from {} (taken {})",
+                    data.files, data.path, data.created_at
+                );
+                Ok(())
+            },
+        ),
+    }
+}
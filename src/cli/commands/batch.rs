@@ -0,0 +1,117 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::{BatchInput, BatchResult};
+use crate::app::stdin::read_stdin_content;
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::style;
+use crate::errors::TsqError;
+use clap::Args;
+use serde_json::Value;
+
+#[derive(Debug, Args)]
+#[command(
+    after_help = "Operations are a JSON array or NDJSON (one object per line), each tagged by \"op\":
+  create, update, dep_add, dep_remove, link_add, link_remove,
+  label_add, label_remove, note, claim, status
+
+A create with \"ref\": \"name\" can be referenced later as \"$name\" in
+id/parent/discovered_from/child/blocker/src/dst fields.
+
+Examples:
+  printf '%s\\n' '{\"op\":\"create\",\"ref\":\"epic\",\"title\":\"Auth\",\"kind\":\"epic\"}' \\
+    '{\"op\":\"create\",\"title\":\"Login form\",\"parent\":\"$epic\"}' | tsq batch -
+  tsq batch ops.json --json"
+)]
+pub struct BatchArgs {
+    /// Operations file path, or `-` to read from stdin
+    pub source: String,
+}
+
+pub fn execute_batch(service: &TasqueService, args: BatchArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq batch",
+        opts,
+        || {
+            let content = read_batch_source(&args.source)?;
+            let operations = parse_batch_operations(&content)?;
+            service.batch(BatchInput {
+                operations,
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| data.clone(),
+        |data| {
+            print_batch_result(data);
+            Ok(())
+        },
+    )
+}
+
+fn read_batch_source(source: &str) -> Result<String, TsqError> {
+    if source == "-" {
+        return read_stdin_content();
+    }
+    std::fs::read_to_string(source).map_err(|error| {
+        TsqError::new(
+            "IO_ERROR",
+            format!("failed reading batch file: {}", source),
+            2,
+        )
+        .with_details(serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}))
+    })
+}
+
+/// Accepts either one JSON array of operations or NDJSON with one operation
+/// per non-blank line.
+fn parse_batch_operations(content: &str) -> Result<Vec<Value>, TsqError> {
+    let trimmed = content.trim();
+    if trimmed.starts_with('[') {
+        return serde_json::from_str::<Vec<Value>>(trimmed).map_err(|error| {
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!("invalid batch JSON array: {}", error),
+                1,
+            )
+        });
+    }
+    trimmed
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<Value>(line).map_err(|error| {
+                TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("invalid batch NDJSON at line {}: {}", index + 1, error),
+                    1,
+                )
+            })
+        })
+        .collect()
+}
+
+fn print_batch_result(result: &BatchResult) {
+    for item in &result.results {
+        let subject = item
+            .result
+            .get("task")
+            .and_then(|task| task.get("id"))
+            .or_else(|| item.result.get("task_id"))
+            .or_else(|| item.result.get("child"))
+            .or_else(|| item.result.get("src"))
+            .and_then(Value::as_str)
+            .unwrap_or("-");
+        println!(
+            "{} {} {}",
+            style::muted(&format!("#{}", item.index)),
+            item.op,
+            style::task_id(subject)
+        );
+    }
+    println!(
+        "{}",
+        style::success(&format!(
+            "applied {} operations ({} events)",
+            result.applied, result.events
+        ))
+    );
+}
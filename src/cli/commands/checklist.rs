@@ -0,0 +1,107 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::{ChecklistAddInput, ChecklistListInput, ChecklistSetInput};
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::render::print_checklist;
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum CheckCommand {
+    /// Append a checklist item to a task
+    #[command(after_help = "Examples:
+  tsq check add tsq-abc12345 \"write tests\"")]
+    Add(CheckAddArgs),
+    /// Mark a checklist item done
+    #[command(after_help = "Examples:
+  tsq check done tsq-abc12345 2")]
+    Done(CheckItemArgs),
+    /// Mark a checklist item not done
+    Undo(CheckItemArgs),
+    /// List checklist items
+    List(CheckListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CheckAddArgs {
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckItemArgs {
+    pub id: String,
+    /// 1-based item number from `tsq check list`
+    pub index: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckListArgs {
+    pub id: String,
+}
+
+pub fn execute_check(service: &TasqueService, command: CheckCommand, opts: GlobalOpts) -> i32 {
+    match command {
+        CheckCommand::Add(args) => run_action(
+            "tsq check add",
+            opts,
+            || {
+                service.checklist_add(ChecklistAddInput {
+                    id: args.id.clone(),
+                    text: args.text.clone(),
+                    exact_id: opts.exact_id,
+                })
+            },
+            |data| data.clone(),
+            |data| {
+                print_checklist(&data.checklist);
+                Ok(())
+            },
+        ),
+        CheckCommand::Done(args) => set_item(service, args, true, "tsq check done", opts),
+        CheckCommand::Undo(args) => set_item(service, args, false, "tsq check undo", opts),
+        CheckCommand::List(args) => run_action(
+            "tsq check list",
+            opts,
+            || {
+                service.checklist_list(ChecklistListInput {
+                    id: args.id.clone(),
+                    exact_id: opts.exact_id,
+                })
+            },
+            |data| data.clone(),
+            |data| {
+                if data.checklist.is_empty() {
+                    println!("no checklist items for {}", data.task_id);
+                } else {
+                    print_checklist(&data.checklist);
+                }
+                Ok(())
+            },
+        ),
+    }
+}
+
+fn set_item(
+    service: &TasqueService,
+    args: CheckItemArgs,
+    done: bool,
+    command_line: &str,
+    opts: GlobalOpts,
+) -> i32 {
+    run_action(
+        command_line,
+        opts,
+        || {
+            service.checklist_set(ChecklistSetInput {
+                id: args.id.clone(),
+                index: args.index,
+                done,
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| data.clone(),
+        |data| {
+            print_checklist(&data.checklist);
+            Ok(())
+        },
+    )
+}
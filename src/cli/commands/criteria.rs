@@ -0,0 +1,86 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::{CriteriaCheckInput, CriteriaListInput};
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::render::print_acceptance_criteria;
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum CriteriaCommand {
+    /// List acceptance criteria parsed from the attached spec
+    List(CriteriaListArgs),
+    /// Toggle (or set) completion of one criterion
+    Check(CriteriaCheckArgs),
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq criteria list tsq-abc12345")]
+pub struct CriteriaListArgs {
+    pub id: String,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq criteria check tsq-abc12345 2
+  tsq criteria check tsq-abc12345 2 --undo")]
+pub struct CriteriaCheckArgs {
+    pub id: String,
+    /// 1-based criterion number from `tsq criteria list`
+    pub index: usize,
+    /// Mark the criterion done regardless of its current state
+    #[arg(long, conflicts_with = "undo")]
+    pub done: bool,
+    /// Mark the criterion open regardless of its current state
+    #[arg(long)]
+    pub undo: bool,
+}
+
+pub fn execute_criteria(
+    service: &TasqueService,
+    command: CriteriaCommand,
+    opts: GlobalOpts,
+) -> i32 {
+    match command {
+        CriteriaCommand::List(args) => run_action(
+            "tsq criteria list",
+            opts,
+            || {
+                service.criteria_list(CriteriaListInput {
+                    id: args.id.clone(),
+                    exact_id: opts.exact_id,
+                })
+            },
+            |data| data.clone(),
+            |data| {
+                if data.criteria.is_empty() {
+                    println!("no acceptance criteria for {}", data.task_id);
+                } else {
+                    print_acceptance_criteria(&data.criteria);
+                }
+                Ok(())
+            },
+        ),
+        CriteriaCommand::Check(args) => run_action(
+            "tsq criteria check",
+            opts,
+            || {
+                let checked = match (args.done, args.undo) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                service.criteria_check(CriteriaCheckInput {
+                    id: args.id.clone(),
+                    index: args.index,
+                    checked,
+                    exact_id: opts.exact_id,
+                })
+            },
+            |data| data.clone(),
+            |data| {
+                print_acceptance_criteria(&data.criteria);
+                Ok(())
+            },
+        ),
+    }
+}
@@ -1,8 +1,14 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::{DepInput, DepTreeInput};
+use crate::app::service_types::{
+    ChildPlacement, DepImportEdge, DepImportInput, DepInput, DepTreeInput, DepWhyInput,
+    DepWhyResult, OrderChildInput,
+};
+use crate::app::stdin::read_stdin_content;
 use crate::cli::action::{GlobalOpts, run_action};
-use crate::cli::parsers::{parse_dep_direction, parse_dependency_type, parse_positive_int};
-use crate::cli::render::print_dep_tree_result;
+use crate::cli::parsers::{
+    parse_dep_direction, parse_dependency_type, parse_positive_int, parse_status_csv,
+};
+use crate::cli::render::{print_dep_graph, print_dep_tree_result};
 use crate::errors::TsqError;
 use crate::types::DependencyType;
 use clap::{Args, Subcommand};
@@ -38,13 +44,50 @@ pub struct DepTreeArgs {
     pub direction: String,
     #[arg(long)]
     pub depth: Option<String>,
+    /// Only show tasks in these statuses (comma-separated)
+    #[arg(long)]
+    pub statuses: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq why tsq-app00001 tsq-db000001
+  tsq why tsq-app00001 tsq-db000001 --limit 1")]
+pub struct WhyArgs {
+    /// Task that may be waiting
+    pub from: String,
+    /// Task that may be gating it
+    pub to: String,
+    /// Maximum number of paths to report
+    #[arg(long, default_value = "5")]
+    pub limit: String,
 }
 
 #[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq block tsq-child001 by tsq-blocker01
+  tsq block tsq-docs0001 by tsq-api00001 --type finish_to_finish
+  tsq block --from-file deps.txt
+  printf 'tsq-aaa blocks tsq-bbb\\ntsq-ccc after tsq-bbb\\n' | tsq block --from-file -
+
+deps.txt format, one edge per line (blank lines and # comments ignored):
+  <blocker> blocks <task>
+  <blocker> finish_to_finish <task>
+  <blocker> informs <task>
+  <later> after <earlier>")]
 pub struct BlockArgs {
-    pub child: String,
-    pub by: String,
-    pub blocker: String,
+    #[arg(required_unless_present = "from_file")]
+    pub child: Option<String>,
+    #[arg(required_unless_present = "from_file")]
+    pub by: Option<String>,
+    #[arg(required_unless_present = "from_file")]
+    pub blocker: Option<String>,
+    /// blocks (gates starting), finish_to_finish (gates closing), or informs (never gates)
+    #[arg(long = "type", default_value = "blocks")]
+    pub dep_type: String,
+    /// Add every edge listed in this file (or `-` for stdin) in one commit
+    #[arg(long = "from-file", conflicts_with_all = ["child", "by", "blocker"])]
+    pub from_file: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -52,13 +95,27 @@ pub struct UnblockArgs {
     pub child: String,
     pub by: String,
     pub blocker: String,
+    #[arg(long = "type", default_value = "blocks")]
+    pub dep_type: String,
 }
 
 #[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq order tsq-later0001 after tsq-early0001
+  tsq order tsq-epic0001 tsq-child002 --before tsq-child001")]
 pub struct OrderArgs {
+    /// Later task, or the parent when placing a child with --before/--after
     pub later: String,
+    /// The word `after`, or the child to place
     pub after: String,
-    pub earlier: String,
+    /// Earlier task (sentence form only)
+    pub earlier: Option<String>,
+    /// Place the child immediately before this sibling
+    #[arg(long, value_name = "SIBLING", conflicts_with = "place_after")]
+    pub before: Option<String>,
+    /// Place the child immediately after this sibling
+    #[arg(long = "after", value_name = "SIBLING")]
+    pub place_after: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -75,6 +132,12 @@ pub struct DepsArgs {
     pub direction: String,
     #[arg(long)]
     pub depth: Option<String>,
+    /// Only show tasks in these statuses (comma-separated)
+    #[arg(long)]
+    pub statuses: Option<String>,
+    /// Emit unique nodes and edges instead of a nested tree
+    #[arg(long, default_value_t = false)]
+    pub flat: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -155,6 +218,7 @@ pub fn execute_dep(service: &TasqueService, command: DepCommand, opts: GlobalOpt
                     id: args.id.clone(),
                     direction,
                     depth,
+                    statuses: args.statuses.as_deref().map(parse_status_csv).transpose()?,
                     exact_id: opts.exact_id,
                 })
             },
@@ -167,17 +231,52 @@ pub fn execute_dep(service: &TasqueService, command: DepCommand, opts: GlobalOpt
     }
 }
 
+fn print_dep_why(result: &DepWhyResult) {
+    if !result.connected {
+        println!(
+            "no dependency or relation path between {} and {}",
+            result.from, result.to
+        );
+        return;
+    }
+    if result.reversed {
+        println!(
+            "{} does not reach {}; showing paths from {} back to {}",
+            result.from, result.to, result.to, result.from
+        );
+    }
+    for path in &result.paths {
+        let mut line = path
+            .hops
+            .first()
+            .map(|hop| hop.from.clone())
+            .unwrap_or_default();
+        for hop in &path.hops {
+            line.push_str(&format!(" -[{}]-> {}", hop.via, hop.to));
+        }
+        let verdict = if path.gating { "gating" } else { "related" };
+        println!("{} ({})", line, verdict);
+    }
+}
+
 pub fn execute_block(service: &TasqueService, args: BlockArgs, opts: GlobalOpts) -> i32 {
+    if let Some(source) = args.from_file.as_deref() {
+        return execute_block_import(service, source, opts);
+    }
     run_dep_mutation(
         service,
         "tsq block",
         opts,
         || {
-            validate_sentence_token(&args.by, "by", "tsq block <task> by <blocker>")?;
+            validate_sentence_token(
+                args.by.as_deref().unwrap_or_default(),
+                "by",
+                "tsq block <task> by <blocker>",
+            )?;
             Ok(DepInput {
-                child: args.child.clone(),
-                blocker: args.blocker.clone(),
-                dep_type: Some(DependencyType::Blocks),
+                child: args.child.clone().unwrap_or_default(),
+                blocker: args.blocker.clone().unwrap_or_default(),
+                dep_type: Some(parse_block_type(&args.dep_type)?),
                 exact_id: opts.exact_id,
             })
         },
@@ -185,6 +284,99 @@ pub fn execute_block(service: &TasqueService, args: BlockArgs, opts: GlobalOpts)
     )
 }
 
+/// `starts_after` edges read as `tsq order <later> after <earlier>`, so
+/// block/unblock only take the remaining types.
+fn parse_block_type(raw: &str) -> Result<DependencyType, TsqError> {
+    match parse_dependency_type(raw)? {
+        DependencyType::StartsAfter => Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "use `tsq order <later> after <earlier>` for starts_after edges",
+            1,
+        )),
+        dep_type => Ok(dep_type),
+    }
+}
+
+fn execute_block_import(service: &TasqueService, source: &str, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq block",
+        opts,
+        || {
+            let content = read_dep_source(source)?;
+            service.dep_import(DepImportInput {
+                edges: parse_dep_lines(&content)?,
+                exact_id: opts.exact_id,
+            })
+        },
+        |result| serde_json::to_value(result).unwrap_or_default(),
+        |result| {
+            for edge in &result.added {
+                println!(
+                    "added dep {} -> {} ({})",
+                    edge.child,
+                    edge.blocker,
+                    dep_type_to_string(edge.dep_type)
+                );
+            }
+            println!("added={} skipped={}", result.added.len(), result.skipped);
+            Ok(())
+        },
+    )
+}
+
+fn read_dep_source(source: &str) -> Result<String, TsqError> {
+    if source == "-" {
+        return read_stdin_content();
+    }
+    std::fs::read_to_string(source).map_err(|error| {
+        TsqError::new(
+            "IO_ERROR",
+            format!("failed reading dependency file: {}", source),
+            2,
+        )
+        .with_details(serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}))
+    })
+}
+
+/// Parse `<blocker> blocks <task>` and `<later> after <earlier>` lines,
+/// skipping blanks and `#` comments.
+fn parse_dep_lines(content: &str) -> Result<Vec<DepImportEdge>, TsqError> {
+    let mut edges = Vec::new();
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        let edge = match tokens.as_slice() {
+            [blocker, "blocks", child] => (child, blocker, DependencyType::Blocks),
+            [blocker, "finish_to_finish", child] => {
+                (child, blocker, DependencyType::FinishToFinish)
+            }
+            [blocker, "informs", child] => (child, blocker, DependencyType::Informs),
+            [later, "after", earlier] => (later, earlier, DependencyType::StartsAfter),
+            _ => {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!(
+                        "line {}: expected `<blocker> blocks|finish_to_finish|informs <task>` or `<later> after <earlier>`",
+                        index + 1
+                    ),
+                    1,
+                )
+                .with_details(serde_json::json!({ "line": index + 1, "text": raw })));
+            }
+        };
+        edges.push(DepImportEdge {
+            line: index + 1,
+            child: edge.0.to_string(),
+            blocker: edge.1.to_string(),
+            dep_type: edge.2,
+        });
+    }
+    Ok(edges)
+}
+
 pub fn execute_unblock(service: &TasqueService, args: UnblockArgs, opts: GlobalOpts) -> i32 {
     run_dep_remove(
         service,
@@ -195,7 +387,7 @@ pub fn execute_unblock(service: &TasqueService, args: UnblockArgs, opts: GlobalO
             Ok(DepInput {
                 child: args.child.clone(),
                 blocker: args.blocker.clone(),
-                dep_type: Some(DependencyType::Blocks),
+                dep_type: Some(parse_block_type(&args.dep_type)?),
                 exact_id: opts.exact_id,
             })
         },
@@ -204,15 +396,31 @@ pub fn execute_unblock(service: &TasqueService, args: UnblockArgs, opts: GlobalO
 }
 
 pub fn execute_order(service: &TasqueService, args: OrderArgs, opts: GlobalOpts) -> i32 {
+    let placement = match (args.before.clone(), args.place_after.clone()) {
+        (Some(anchor), _) => Some((anchor, ChildPlacement::Before)),
+        (None, Some(anchor)) => Some((anchor, ChildPlacement::After)),
+        (None, None) => None,
+    };
+    if let Some((anchor, placement)) = placement {
+        return execute_order_child(service, args, anchor, placement, opts);
+    }
+
     run_dep_mutation(
         service,
         "tsq order",
         opts,
         || {
             validate_sentence_token(&args.after, "after", "tsq order <later> after <earlier>")?;
+            let earlier = args.earlier.clone().ok_or_else(|| {
+                TsqError::new(
+                    "VALIDATION_ERROR",
+                    "expected: tsq order <later> after <earlier> or tsq order <parent> <child> --before <sibling>",
+                    1,
+                )
+            })?;
             Ok(DepInput {
                 child: args.later.clone(),
-                blocker: args.earlier.clone(),
+                blocker: earlier,
                 dep_type: Some(DependencyType::StartsAfter),
                 exact_id: opts.exact_id,
             })
@@ -221,6 +429,45 @@ pub fn execute_order(service: &TasqueService, args: OrderArgs, opts: GlobalOpts)
     )
 }
 
+fn execute_order_child(
+    service: &TasqueService,
+    args: OrderArgs,
+    anchor: String,
+    placement: ChildPlacement,
+    opts: GlobalOpts,
+) -> i32 {
+    run_action(
+        "tsq order",
+        opts,
+        || {
+            if args.earlier.is_some() {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "expected: tsq order <parent> <child> --before|--after <sibling>",
+                    1,
+                ));
+            }
+            service.order_child(OrderChildInput {
+                parent: args.later.clone(),
+                child: args.after.clone(),
+                anchor: anchor.clone(),
+                placement,
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| data.clone(),
+        |data| {
+            println!(
+                "ordered {} under {}: {}",
+                data.child_id,
+                data.parent_id,
+                data.children.join(" ")
+            );
+            Ok(())
+        },
+    )
+}
+
 pub fn execute_unorder(service: &TasqueService, args: UnorderArgs, opts: GlobalOpts) -> i32 {
     run_dep_remove(
         service,
@@ -240,27 +487,63 @@ pub fn execute_unorder(service: &TasqueService, args: UnorderArgs, opts: GlobalO
 }
 
 pub fn execute_deps(service: &TasqueService, args: DepsArgs, opts: GlobalOpts) -> i32 {
+    if args.flat {
+        return run_action(
+            "tsq deps",
+            opts,
+            || service.dep_graph(deps_input(&args, opts)?),
+            |graph| serde_json::to_value(graph).unwrap_or_default(),
+            |graph| {
+                print_dep_graph(graph);
+                Ok(())
+            },
+        );
+    }
     run_action(
         "tsq deps",
         opts,
+        || service.dep_tree(deps_input(&args, opts)?),
+        |root| serde_json::json!({ "root": root }),
+        |root| {
+            print_dep_tree_result(root);
+            Ok(())
+        },
+    )
+}
+
+fn deps_input(args: &DepsArgs, opts: GlobalOpts) -> Result<DepTreeInput, TsqError> {
+    let direction = parse_dep_direction(Some(&args.direction))?;
+    let depth = args
+        .depth
+        .as_deref()
+        .map(|value| parse_positive_int(value, "depth", 1, 100))
+        .transpose()?
+        .map(|value| value as usize);
+    Ok(DepTreeInput {
+        id: args.id.clone(),
+        direction,
+        depth,
+        statuses: args.statuses.as_deref().map(parse_status_csv).transpose()?,
+        exact_id: opts.exact_id,
+    })
+}
+
+pub fn execute_why(service: &TasqueService, args: WhyArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq why",
+        opts,
         || {
-            let direction = parse_dep_direction(Some(&args.direction))?;
-            let depth = args
-                .depth
-                .as_deref()
-                .map(|value| parse_positive_int(value, "depth", 1, 100))
-                .transpose()?
-                .map(|value| value as usize);
-            service.dep_tree(DepTreeInput {
-                id: args.id.clone(),
-                direction,
-                depth,
+            let limit = parse_positive_int(&args.limit, "limit", 1, 100)? as usize;
+            service.dep_why(DepWhyInput {
+                from: args.from.clone(),
+                to: args.to.clone(),
+                limit: Some(limit),
                 exact_id: opts.exact_id,
             })
         },
-        |root| serde_json::json!({ "root": root }),
-        |root| {
-            print_dep_tree_result(root);
+        |result| serde_json::to_value(result).unwrap_or_default(),
+        |result| {
+            print_dep_why(result);
             Ok(())
         },
     )
@@ -349,5 +632,7 @@ fn dep_type_to_string(dep_type: DependencyType) -> &'static str {
     match dep_type {
         DependencyType::Blocks => "blocks",
         DependencyType::StartsAfter => "starts_after",
+        DependencyType::FinishToFinish => "finish_to_finish",
+        DependencyType::Informs => "informs",
     }
 }
@@ -0,0 +1,54 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::ExportInput;
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::html::write_html_file;
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq export --out tasks.json
+  tsq export --redact --out vendor-tasks.json
+
+--redact applies the `redaction` rules from .tasque/config.json
+({\"strip\": [fields], \"hash\": [fields], \"drop\": [field regexes]}); without
+them it strips description and notes and hashes assignee.")]
+pub struct ExportArgs {
+    /// Strip, hash, and drop task fields per the configured redaction rules
+    #[arg(long, default_value_t = false)]
+    pub redact: bool,
+    /// Write the export to a file instead of stdout
+    #[arg(long)]
+    pub out: Option<String>,
+}
+
+pub fn execute_export(service: &TasqueService, args: ExportArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq export",
+        opts,
+        || {
+            let export = service.export(ExportInput {
+                redact: args.redact,
+            })?;
+            let document = serde_json::to_string_pretty(&export).unwrap_or_default() + "\n";
+            if let Some(out) = args.out.as_deref() {
+                write_html_file(out, &document)?;
+            }
+            Ok((export, document))
+        },
+        |(export, document)| {
+            let mut value = serde_json::to_value(export).unwrap_or_default();
+            if let Some(out) = args.out.as_deref() {
+                value["out"] = serde_json::json!(out);
+                value["bytes"] = serde_json::json!(document.len());
+            }
+            value
+        },
+        |(export, document)| {
+            match args.out.as_deref() {
+                Some(out) => println!("export={} tasks={}", out, export.total),
+                None => print!("{}", document),
+            }
+            Ok(())
+        },
+    )
+}
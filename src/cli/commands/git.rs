@@ -0,0 +1,117 @@
+use crate::app::runtime::git_ref_timestamp;
+use crate::app::service::TasqueService;
+use crate::app::service_types::{GitCommitRef, GitLinksInput, GitLinksResult};
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::style;
+use crate::errors::TsqError;
+use crate::store::git;
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum GitCommand {
+    /// Match task ids in commit messages against tasks
+    Links(GitLinksArgs),
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq git links
+  tsq git links --since v0.6.0")]
+pub struct GitLinksArgs {
+    /// Only scan commits after this ref; closed tasks are checked from its commit time
+    #[arg(long)]
+    pub since: Option<String>,
+}
+
+pub fn execute_git(service: &TasqueService, command: GitCommand, opts: GlobalOpts) -> i32 {
+    match command {
+        GitCommand::Links(args) => run_action(
+            "tsq git links",
+            opts,
+            || {
+                let checkout = code_checkout()?;
+                let since_ref = args.since.as_deref().map(str::trim);
+                let commits = git::commit_messages(&checkout, since_ref)?
+                    .into_iter()
+                    .map(|(sha, message)| GitCommitRef {
+                        subject: message.lines().next().unwrap_or_default().to_string(),
+                        sha,
+                        message,
+                    })
+                    .collect();
+                service.git_links(GitLinksInput {
+                    commits,
+                    since_ref: since_ref.map(String::from),
+                    since: since_ref.and_then(|since| git_ref_timestamp(&checkout, since)),
+                })
+            },
+            |result| serde_json::to_value(result).unwrap_or_default(),
+            |result| {
+                print_git_links(result);
+                Ok(())
+            },
+        ),
+    }
+}
+
+/// The git checkout in the working directory, where code commits live (the
+/// service root may be the tasque sync worktree).
+fn code_checkout() -> Result<std::path::PathBuf, TsqError> {
+    let cwd = std::env::current_dir().map_err(|error| {
+        TsqError::new("IO_ERROR", "failed reading current directory", 2)
+            .with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+    if !git::is_git_repo(&cwd) {
+        return Err(TsqError::new(
+            "GIT_NOT_AVAILABLE",
+            "tsq git links needs a git repository",
+            2,
+        ));
+    }
+    Ok(cwd)
+}
+
+fn print_git_links(result: &GitLinksResult) {
+    println!(
+        "commits={} linked={} closed_without_commits={} unknown_ids={}",
+        result.commits_scanned,
+        result.linked.len(),
+        result.closed_without_commits.len(),
+        result.unknown_ids.len()
+    );
+    if !result.linked.is_empty() {
+        println!("{}", style::heading("linked"));
+        for task in &result.linked {
+            println!(
+                "  {} {} {}",
+                style::task_id(&task.id),
+                task.title,
+                style::muted(&short_shas(&task.commits))
+            );
+        }
+    }
+    if !result.closed_without_commits.is_empty() {
+        println!("{}", style::heading("closed without commits"));
+        for task in &result.closed_without_commits {
+            println!("  {} {}", style::warning(&task.id), task.title);
+        }
+    }
+    if !result.unknown_ids.is_empty() {
+        println!("{}", style::heading("unknown ids"));
+        for unknown in &result.unknown_ids {
+            println!(
+                "  {} {}",
+                style::error(&unknown.id),
+                style::muted(&short_shas(&unknown.commits))
+            );
+        }
+    }
+}
+
+fn short_shas(commits: &[GitCommitRef]) -> String {
+    commits
+        .iter()
+        .map(|commit| commit.sha.chars().take(7).collect::<String>())
+        .collect::<Vec<_>>()
+        .join(",")
+}
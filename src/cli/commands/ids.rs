@@ -0,0 +1,86 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::{IdResolutionStatus, IdsInput, IdsResult};
+use crate::app::stdin::read_stdin_content;
+use crate::cli::action::{GlobalOpts, run_action};
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(
+    after_help = "Each input is tried as an id, id prefix, alias, or alias prefix, then as an
+exact external ref. Unresolved inputs are reported per line, not as a failure.
+
+Examples:
+  printf '%s\\n' tsq-12 login-form gh-123 | tsq ids --json
+  tsq ids tsq-1 fix-auth"
+)]
+pub struct IdsArgs {
+    /// References to resolve; omit or pass `-` to read one per line from stdin
+    pub refs: Vec<String>,
+}
+
+pub fn execute_ids(service: &TasqueService, args: IdsArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq ids",
+        opts,
+        || {
+            let refs = if args.refs.is_empty() || args.refs.iter().any(|raw| raw == "-") {
+                let stdin = read_stdin_content()?;
+                expand_stdin_refs(&args.refs, &stdin)
+            } else {
+                args.refs.clone()
+            };
+            service.ids(IdsInput {
+                refs,
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| serde_json::to_value(data).unwrap_or_default(),
+        |data| {
+            print_ids_result(data);
+            Ok(())
+        },
+    )
+}
+
+/// Replaces `-` (or an empty argument list) with the non-blank stdin lines.
+fn expand_stdin_refs(args: &[String], stdin: &str) -> Vec<String> {
+    let lines = stdin
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string);
+    if args.is_empty() {
+        return lines.collect();
+    }
+    let lines: Vec<String> = lines.collect();
+    args.iter()
+        .flat_map(|raw| {
+            if raw == "-" {
+                lines.clone()
+            } else {
+                vec![raw.clone()]
+            }
+        })
+        .collect()
+}
+
+/// One tab-separated line per input: the resolved id, or `NOT_FOUND` /
+/// `AMBIGUOUS` followed by the candidate ids.
+fn print_ids_result(result: &IdsResult) {
+    for entry in &result.results {
+        match entry.status {
+            IdResolutionStatus::Resolved => {
+                println!("{}\t{}", entry.input, entry.id.as_deref().unwrap_or(""))
+            }
+            IdResolutionStatus::NotFound => println!("{}\tNOT_FOUND", entry.input),
+            IdResolutionStatus::Ambiguous => {
+                let candidates: Vec<&str> = entry
+                    .candidates
+                    .iter()
+                    .map(|candidate| candidate.id.as_str())
+                    .collect();
+                println!("{}\tAMBIGUOUS\t{}", entry.input, candidates.join(","));
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::LabelInput;
+use crate::app::service_types::{LabelColorInput, LabelColorResult, LabelInput};
 use crate::cli::action::{GlobalOpts, run_action};
 use crate::cli::render::{print_label_list, print_task};
+use crate::cli::style;
+use crate::errors::TsqError;
 use clap::{Args, Subcommand};
 
 #[derive(Debug, Subcommand)]
@@ -23,10 +25,17 @@ pub struct LabelRemoveArgs {
     pub label: String,
 }
 
+/// `tsq label <id> <label>`, or `tsq label color <label> <color|--clear>`.
 #[derive(Debug, Args)]
 pub struct LabelArgs {
     pub id: String,
     pub label: String,
+    /// Color for `tsq label color <label> <color>`: red, green, yellow, blue,
+    /// magenta, cyan, white, or gray
+    pub color: Option<String>,
+    /// Clear the color with `tsq label color <label> --clear`
+    #[arg(long, conflicts_with = "color")]
+    pub clear: bool,
 }
 
 #[derive(Debug, Args)]
@@ -83,6 +92,24 @@ pub fn execute_label(service: &TasqueService, command: LabelCommand, opts: Globa
 }
 
 pub fn execute_label_add(service: &TasqueService, args: LabelArgs, opts: GlobalOpts) -> i32 {
+    if args.id == "color" && (args.color.is_some() || args.clear) {
+        return execute_label_color(service, args, opts);
+    }
+    if args.color.is_some() || args.clear {
+        return run_action(
+            "tsq label",
+            opts,
+            || -> Result<(), TsqError> {
+                Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "use `tsq label color <label> <color>` to color a label",
+                    1,
+                ))
+            },
+            |_: &()| serde_json::json!({}),
+            |_: &()| Ok(()),
+        );
+    }
     run_action(
         "tsq label",
         opts,
@@ -101,6 +128,35 @@ pub fn execute_label_add(service: &TasqueService, args: LabelArgs, opts: GlobalO
     )
 }
 
+fn execute_label_color(service: &TasqueService, args: LabelArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq label color",
+        opts,
+        || {
+            service.label_color(LabelColorInput {
+                label: args.label.clone(),
+                color: args.color.clone(),
+            })
+        },
+        |result| serde_json::json!({ "label": result.label, "color": result.color }),
+        |result| {
+            print_label_color(result);
+            Ok(())
+        },
+    )
+}
+
+fn print_label_color(result: &LabelColorResult) {
+    match result.color {
+        Some(color) => println!(
+            "{} {}",
+            style::paint_label_color(&format!("[{}]", result.label), color),
+            color.as_str()
+        ),
+        None => println!("[{}] {}", result.label, style::muted("color cleared")),
+    }
+}
+
 pub fn execute_unlabel(service: &TasqueService, args: UnlabelArgs, opts: GlobalOpts) -> i32 {
     run_action(
         "tsq unlabel",
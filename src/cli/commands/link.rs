@@ -1,7 +1,9 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::LinkInput;
+use crate::app::service_types::{CustomLinkInput, LinkInput};
 use crate::cli::action::{GlobalOpts, run_action};
 use crate::cli::parsers::parse_relation_type;
+use crate::domain::event_payload_codecs::is_custom_relation_name;
+use crate::errors::TsqError;
 use crate::types::RelationType;
 use clap::{Args, Subcommand};
 use serde::Serialize;
@@ -32,12 +34,18 @@ pub struct LinkRemoveArgs {
 pub struct RelateArgs {
     pub a: String,
     pub b: String,
+    /// Builtin or config-defined relation type (default relates_to).
+    #[arg(long = "type")]
+    pub rel_type: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct UnrelateArgs {
     pub a: String,
     pub b: String,
+    /// Builtin or config-defined relation type (default relates_to).
+    #[arg(long = "type")]
+    pub rel_type: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -110,13 +118,21 @@ pub fn execute_relate(service: &TasqueService, args: RelateArgs, opts: GlobalOpt
     run_link_mutation(
         "tsq relate",
         opts,
-        || {
-            service.link_add(LinkInput {
+        || match relation_kind(args.rel_type.as_deref())? {
+            RelationKind::Builtin(rel_type) => service
+                .link_add(LinkInput {
+                    src: args.a.clone(),
+                    dst: args.b.clone(),
+                    rel_type,
+                    exact_id: opts.exact_id,
+                })
+                .map(builtin_result),
+            RelationKind::Custom(rel_type) => service.custom_link_add(CustomLinkInput {
                 src: args.a.clone(),
                 dst: args.b.clone(),
-                rel_type: RelationType::RelatesTo,
+                rel_type,
                 exact_id: opts.exact_id,
-            })
+            }),
         },
         "added",
     )
@@ -126,18 +142,56 @@ pub fn execute_unrelate(service: &TasqueService, args: UnrelateArgs, opts: Globa
     run_link_mutation(
         "tsq unrelate",
         opts,
-        || {
-            service.link_remove(LinkInput {
+        || match relation_kind(args.rel_type.as_deref())? {
+            RelationKind::Builtin(rel_type) => service
+                .link_remove(LinkInput {
+                    src: args.a.clone(),
+                    dst: args.b.clone(),
+                    rel_type,
+                    exact_id: opts.exact_id,
+                })
+                .map(builtin_result),
+            RelationKind::Custom(rel_type) => service.custom_link_remove(CustomLinkInput {
                 src: args.a.clone(),
                 dst: args.b.clone(),
-                rel_type: RelationType::RelatesTo,
+                rel_type,
                 exact_id: opts.exact_id,
-            })
+            }),
         },
         "removed",
     )
 }
 
+enum RelationKind {
+    Builtin(RelationType),
+    Custom(String),
+}
+
+/// Builtin names map to their enum; anything else must be defined in config,
+/// which the service checks.
+fn relation_kind(raw: Option<&str>) -> Result<RelationKind, TsqError> {
+    let Some(raw) = raw.map(str::trim) else {
+        return Ok(RelationKind::Builtin(RelationType::RelatesTo));
+    };
+    if let Ok(rel_type) = parse_relation_type(raw) {
+        return Ok(RelationKind::Builtin(rel_type));
+    }
+    if !is_custom_relation_name(raw) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!("invalid relation type: {}", raw),
+            1,
+        ));
+    }
+    Ok(RelationKind::Custom(raw.to_string()))
+}
+
+fn builtin_result(
+    (src, dst, rel_type): (String, String, RelationType),
+) -> (String, String, String) {
+    (src, dst, relation_type_to_string(rel_type).to_string())
+}
+
 fn run_link_mutation<F>(
     command_line: &'static str,
     opts: GlobalOpts,
@@ -145,7 +199,7 @@ fn run_link_mutation<F>(
     human_verb: &'static str,
 ) -> i32
 where
-    F: FnOnce() -> Result<(String, String, RelationType), crate::errors::TsqError>,
+    F: FnOnce() -> Result<(String, String, String), TsqError>,
 {
     run_action(
         command_line,
@@ -154,16 +208,10 @@ where
         |(src, dst, rel_type)| LinkMutationJson {
             src: src.clone(),
             dst: dst.clone(),
-            r#type: relation_type_to_string(*rel_type).to_string(),
+            r#type: rel_type.clone(),
         },
         |(src, dst, rel_type)| {
-            println!(
-                "{} link {}: {} -> {}",
-                human_verb,
-                relation_type_to_string(*rel_type),
-                src,
-                dst
-            );
+            println!("{} link {}: {} -> {}", human_verb, rel_type, src, dst);
             Ok(())
         },
     )
@@ -0,0 +1,57 @@
+use crate::app::service::TasqueService;
+use crate::cli::action::{GlobalOpts, run_action};
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum LockCommand {
+    /// Show who holds the write lock and whether it is stale
+    Status,
+    /// Remove a stale write lock (any lock with --force)
+    Break(LockBreakArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct LockBreakArgs {
+    /// Remove the lock even if its owner may still be running
+    #[arg(long)]
+    pub force: bool,
+}
+
+pub fn execute_lock(service: &TasqueService, command: LockCommand, opts: GlobalOpts) -> i32 {
+    match command {
+        LockCommand::Status => run_action(
+            "tsq lock status",
+            opts,
+            || service.lock_status(),
+            |data| data.clone(),
+            |data| {
+                match &data.owner {
+                    Some(owner) => println!(
+                        "locked by {} pid={} since {}{}",
+                        owner.host,
+                        owner.pid,
+                        owner.created_at,
+                        if data.stale { " (stale)" } else { "" }
+                    ),
+                    None if data.locked => println!("locked (unreadable lock file)"),
+                    None => println!("unlocked"),
+                }
+                Ok(())
+            },
+        ),
+        LockCommand::Break(args) => run_action(
+            "tsq lock break",
+            opts,
+            || service.lock_break(args.force),
+            |data| data.clone(),
+            |data| {
+                if data.broken {
+                    println!("Removed write lock");
+                } else {
+                    println!("No write lock found");
+                }
+                Ok(())
+            },
+        ),
+    }
+}
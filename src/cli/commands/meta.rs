@@ -1,17 +1,26 @@
+use crate::app::selftest::{SelftestInput, run_selftest};
 use crate::app::service::TasqueService;
-use crate::app::service_types::HistoryInput;
+use crate::app::service_types::{
+    BenchInput, DoctorSeverity, HistoryInput, LogInput, OrphansAdoptInput,
+};
 use crate::cli::action::{GlobalOpts, run_action};
 use crate::cli::init_flow::{
     InitCommandOptions, InitPlan, InitResolutionContext, resolve_init_plan, run_init_wizard,
 };
 use crate::cli::opentui::{launch_opentui, should_launch_opentui};
 use crate::cli::parsers::{as_optional_string, parse_positive_int, parse_status_csv};
-use crate::cli::render::{print_history, print_orphans_result, print_repair_result};
+use crate::cli::render::{
+    print_bench_result, print_history, print_log, print_orphans_adopt_result, print_orphans_result,
+    print_repair_result, print_selftest_result, print_skill_changes, print_skill_dry_run_banner,
+    print_skill_source,
+};
 use crate::cli::tui::{TuiOptions, TuiView, start_tui};
 use crate::cli::watch::{WatchOptions, start_watch};
+use crate::domain::query::parse_query;
 use crate::errors::TsqError;
 use crate::output::err_envelope;
-use clap::Args;
+use clap::{Args, Subcommand};
+use std::collections::BTreeMap;
 use std::io::IsTerminal;
 
 #[derive(Debug, Args)]
@@ -24,6 +33,9 @@ pub struct InitArgs {
     pub yes: bool,
     #[arg(long)]
     pub preset: Option<String>,
+    /// Seed labels, epics, and policies from a preset definition file (JSON)
+    #[arg(long = "preset-file")]
+    pub preset_file: Option<String>,
     #[arg(long = "install-skill", default_value_t = false)]
     pub install_skill: bool,
     #[arg(long = "uninstall-skill", default_value_t = false)]
@@ -34,6 +46,12 @@ pub struct InitArgs {
     pub skill_name: Option<String>,
     #[arg(long = "force-skill-overwrite", default_value_t = false)]
     pub force_skill_overwrite: bool,
+    /// Install from a local skills root, git URL (`url#path`), or `registry:<name>`
+    #[arg(long = "skill-source")]
+    pub skill_source: Option<String>,
+    /// Require the skill source to match this `sha256:<hex>` content checksum
+    #[arg(long = "skill-checksum")]
+    pub skill_checksum: Option<String>,
     #[arg(long = "skill-dir-claude")]
     pub skill_dir_claude: Option<String>,
     #[arg(long = "skill-dir-codex")]
@@ -42,9 +60,22 @@ pub struct InitArgs {
     pub skill_dir_copilot: Option<String>,
     #[arg(long = "skill-dir-opencode")]
     pub skill_dir_opencode: Option<String>,
+    #[arg(long = "skill-dir-cursor")]
+    pub skill_dir_cursor: Option<String>,
+    #[arg(long = "skill-dir-windsurf")]
+    pub skill_dir_windsurf: Option<String>,
+    #[arg(long = "skill-dir-gemini")]
+    pub skill_dir_gemini: Option<String>,
     /// Override the default sync branch/worktree used for git worktree-backed storage
     #[arg(long = "sync-branch", alias = "worktree-name")]
     pub sync_branch: Option<String>,
+    /// Keep tasks on the current branch instead of setting up a sync branch
+    #[arg(
+        long = "no-sync-branch",
+        default_value_t = false,
+        conflicts_with = "sync_branch"
+    )]
+    pub no_sync_branch: bool,
 }
 
 #[derive(Debug, Args)]
@@ -53,6 +84,55 @@ pub struct RepairArgs {
     pub fix: bool,
     #[arg(long = "force-unlock", default_value_t = false)]
     pub force_unlock: bool,
+    #[arg(long, default_value_t = false)]
+    pub salvage: bool,
+    #[arg(long = "rebuild-snapshots", default_value_t = false)]
+    pub rebuild_snapshots: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct OrphansArgs {
+    #[command(subcommand)]
+    pub action: Option<OrphansAction>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum OrphansAction {
+    /// Rewrite orphaned deps and links onto surviving tasks, dropping the rest
+    Adopt(OrphansAdoptArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct OrphansAdoptArgs {
+    /// Missing task id and the task that takes over its edges; repeatable
+    #[arg(long = "map", value_name = "OLD=NEW", required = true)]
+    pub map: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq bench
+  tsq bench --runs 20 --json")]
+pub struct BenchArgs {
+    /// How many times to read and replay the full event log
+    #[arg(long, default_value = "5")]
+    pub runs: String,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq selftest
+  tsq selftest --seed 42 --cases 1 --events 1000 --json")]
+pub struct SelftestArgs {
+    /// Seed of the first case; each failure reports the seed that replays it
+    #[arg(long)]
+    pub seed: Option<String>,
+    /// How many random event logs to generate and check
+    #[arg(long, default_value = "20")]
+    pub cases: String,
+    /// Events in each generated log
+    #[arg(long, default_value = "200")]
+    pub events: String,
 }
 
 #[derive(Debug, Args)]
@@ -68,6 +148,24 @@ pub struct HistoryArgs {
     pub since: Option<String>,
 }
 
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq log --limit 20
+  tsq log --type task.noted --actor codex --json
+  tsq log --before-cursor <next_cursor from the previous page>")]
+pub struct LogArgs {
+    #[arg(long)]
+    pub limit: Option<String>,
+    /// Show events older than this cursor (a previous page's next_cursor)
+    #[arg(long = "before-cursor")]
+    pub before_cursor: Option<String>,
+    /// Only this event type, e.g. task.noted or dep.added
+    #[arg(long = "type")]
+    pub event_type: Option<String>,
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
 #[derive(Debug, Args)]
 pub struct WatchArgs {
     #[arg(long, default_value = "2")]
@@ -76,14 +174,32 @@ pub struct WatchArgs {
     pub status: String,
     #[arg(long)]
     pub assignee: Option<String>,
+    /// Further filter tasks with search query syntax, e.g. 'label:backend priority<=1'
+    #[arg(long)]
+    pub query: Option<String>,
     /// Render task hierarchy. Default for human output.
     #[arg(long, default_value_t = false, conflicts_with = "flat")]
     pub tree: bool,
     /// Render compact flat task list instead of the default tree.
     #[arg(long, default_value_t = false)]
     pub flat: bool,
+    /// Merge every sync shard into one flat list with a workspace column
+    #[arg(long, default_value_t = false, conflicts_with_all = ["tree", "deltas", "tasks"])]
+    pub workspace: bool,
     #[arg(long, default_value_t = false)]
     pub once: bool,
+    /// Frames a created, moved, or claimed task stays highlighted (0 disables)
+    #[arg(long, default_value = "3")]
+    pub fade: String,
+    /// With --json, emit only added/changed/removed tasks after the first frame
+    #[arg(long, default_value_t = false)]
+    pub deltas: bool,
+    /// Include each task's full notes array in JSON output (default: note_count)
+    #[arg(long = "with-notes", default_value_t = false)]
+    pub with_notes: bool,
+    /// Follow these tasks and print their new events instead of a task list
+    #[arg(long = "task", value_delimiter = ',', conflicts_with_all = ["query", "deltas", "assignee"])]
+    pub tasks: Vec<String>,
 }
 
 #[derive(Debug, Args)]
@@ -94,12 +210,18 @@ pub struct TuiArgs {
     pub status: String,
     #[arg(long)]
     pub assignee: Option<String>,
+    /// Further filter tasks with search query syntax, e.g. 'label:backend priority<=1'
+    #[arg(long)]
+    pub query: Option<String>,
     #[arg(long, default_value_t = false)]
     pub board: bool,
     #[arg(long, default_value_t = false)]
     pub epics: bool,
     #[arg(long, default_value_t = false)]
     pub once: bool,
+    /// Include each task's full notes array in JSON output (default: note_count)
+    #[arg(long = "with-notes", default_value_t = false)]
+    pub with_notes: bool,
 }
 
 impl Default for TuiArgs {
@@ -108,17 +230,23 @@ impl Default for TuiArgs {
             interval: "2".to_string(),
             status: "open,in_progress".to_string(),
             assignee: None,
+            query: None,
             board: false,
             epics: false,
             once: false,
+            with_notes: false,
         }
     }
 }
 
 pub fn execute_init(service: &TasqueService, args: InitArgs, opts: GlobalOpts) -> i32 {
+    // A skill --dry-run is reported through the result, not the staged event preview.
     run_action(
         "tsq init",
-        opts,
+        GlobalOpts {
+            dry_run: false,
+            ..opts
+        },
         || {
             let options = InitCommandOptions {
                 install_skill: args.install_skill,
@@ -127,6 +255,7 @@ pub fn execute_init(service: &TasqueService, args: InitArgs, opts: GlobalOpts) -
                 no_wizard: args.no_wizard,
                 yes: args.yes,
                 preset: args.preset.clone(),
+                preset_file: args.preset_file.clone(),
                 skill_targets: args.skill_targets.clone(),
                 skill_name: args.skill_name.clone(),
                 force_skill_overwrite: args.force_skill_overwrite,
@@ -134,7 +263,13 @@ pub fn execute_init(service: &TasqueService, args: InitArgs, opts: GlobalOpts) -
                 skill_dir_codex: args.skill_dir_codex.clone(),
                 skill_dir_copilot: args.skill_dir_copilot.clone(),
                 skill_dir_opencode: args.skill_dir_opencode.clone(),
+                skill_dir_cursor: args.skill_dir_cursor.clone(),
+                skill_dir_windsurf: args.skill_dir_windsurf.clone(),
+                skill_dir_gemini: args.skill_dir_gemini.clone(),
                 sync_branch: args.sync_branch.clone(),
+                no_sync_branch: args.no_sync_branch,
+                skill_source: args.skill_source.clone(),
+                skill_checksum: args.skill_checksum.clone(),
             };
             let raw_args: Vec<String> = std::env::args().skip(1).collect();
             let plan = resolve_init_plan(
@@ -145,13 +280,12 @@ pub fn execute_init(service: &TasqueService, args: InitArgs, opts: GlobalOpts) -
                     json: opts.json(),
                 },
             )?;
-            match plan {
-                InitPlan::NonInteractive { input } => service.init(input),
-                InitPlan::Wizard { auto_accept, seed } => {
-                    let input = run_init_wizard(seed, auto_accept)?;
-                    service.init(input)
-                }
-            }
+            let mut input = match plan {
+                InitPlan::NonInteractive { input } => input,
+                InitPlan::Wizard { auto_accept, seed } => run_init_wizard(seed, auto_accept)?,
+            };
+            input.dry_run = opts.dry_run;
+            service.init(input)
         },
         |data| data.clone(),
         |data| {
@@ -172,6 +306,22 @@ pub fn execute_init(service: &TasqueService, args: InitArgs, opts: GlobalOpts) -
                         result.path,
                         message
                     );
+                    print_skill_changes(&result.changes);
+                }
+                print_skill_source(skill_operation);
+                if skill_operation.dry_run {
+                    print_skill_dry_run_banner();
+                }
+            }
+            if let Some(seeded) = &data.seeded {
+                for label in &seeded.labels {
+                    println!("seeded label {}", label);
+                }
+                for id in &seeded.epics {
+                    println!("seeded epic {}", id);
+                }
+                for policy in &seeded.policies {
+                    println!("seeded policy {}", policy);
                 }
             }
             if let Some(sync) = &data.sync_setup {
@@ -205,11 +355,15 @@ pub fn execute_doctor(service: &TasqueService, opts: GlobalOpts) -> i32 {
             if let Some(warning) = &data.warning {
                 println!("warning={}", warning);
             }
-            if data.issues.is_empty() {
+            if data.findings.is_empty() {
                 println!("issues=none");
             } else {
-                for issue in &data.issues {
-                    println!("issue={}", issue);
+                for finding in &data.findings {
+                    let severity = match finding.severity {
+                        DoctorSeverity::Error => "error",
+                        DoctorSeverity::Warning => "warning",
+                    };
+                    println!("issue=[{}] {} {}", severity, finding.code, finding.message);
                 }
             }
             Ok(())
@@ -221,7 +375,14 @@ pub fn execute_repair(service: &TasqueService, args: RepairArgs, opts: GlobalOpt
     run_action(
         "tsq repair",
         opts,
-        || service.repair(args.fix, args.force_unlock),
+        || {
+            service.repair(
+                args.fix,
+                args.force_unlock,
+                args.salvage,
+                args.rebuild_snapshots,
+            )
+        },
         |data| data.clone(),
         |data| {
             print_repair_result(data);
@@ -230,7 +391,24 @@ pub fn execute_repair(service: &TasqueService, args: RepairArgs, opts: GlobalOpt
     )
 }
 
-pub fn execute_orphans(service: &TasqueService, opts: GlobalOpts) -> i32 {
+pub fn execute_orphans(service: &TasqueService, args: OrphansArgs, opts: GlobalOpts) -> i32 {
+    if let Some(OrphansAction::Adopt(adopt)) = args.action {
+        return run_action(
+            "tsq orphans adopt",
+            opts,
+            || {
+                service.orphans_adopt(OrphansAdoptInput {
+                    map: parse_adopt_map(&adopt.map)?,
+                    exact_id: opts.exact_id,
+                })
+            },
+            |data| data.clone(),
+            |data| {
+                print_orphans_adopt_result(data);
+                Ok(())
+            },
+        );
+    }
     run_action(
         "tsq orphans",
         opts,
@@ -243,6 +421,86 @@ pub fn execute_orphans(service: &TasqueService, opts: GlobalOpts) -> i32 {
     )
 }
 
+fn parse_adopt_map(pairs: &[String]) -> Result<BTreeMap<String, String>, TsqError> {
+    let mut map = BTreeMap::new();
+    for pair in pairs {
+        let parsed = pair
+            .split_once('=')
+            .map(|(old, new)| (old.trim(), new.trim()))
+            .filter(|(old, new)| !old.is_empty() && !new.is_empty());
+        let Some((old, new)) = parsed else {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!("--map expects <old-id>=<new-id>, got {}", pair),
+                1,
+            ));
+        };
+        if map.insert(old.to_string(), new.to_string()).is_some() {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                format!("{} is mapped more than once", old),
+                1,
+            ));
+        }
+    }
+    Ok(map)
+}
+
+pub fn execute_bench(service: &TasqueService, args: BenchArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq bench",
+        opts,
+        || {
+            let runs = parse_positive_int(&args.runs, "runs", 1, 1000)? as usize;
+            service.bench(BenchInput { runs })
+        },
+        |data| data.clone(),
+        |data| {
+            print_bench_result(data);
+            Ok(())
+        },
+    )
+}
+
+pub fn execute_selftest(args: SelftestArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq selftest",
+        opts,
+        || {
+            let seed = match args.seed.as_deref() {
+                Some(raw) => Some(raw.trim().parse::<u64>().map_err(|_| {
+                    TsqError::new("VALIDATION_ERROR", "seed must be a non-negative integer", 1)
+                })?),
+                None => None,
+            };
+            let input = SelftestInput {
+                seed,
+                cases: parse_positive_int(&args.cases, "cases", 1, 10_000)? as usize,
+                events: parse_positive_int(&args.events, "events", 1, 100_000)? as usize,
+            };
+            let result = run_selftest(&input);
+            if !result.failures.is_empty() {
+                return Err(TsqError::new(
+                    "SELFTEST_FAILED",
+                    format!(
+                        "{} of {} cases broke a projector invariant",
+                        result.failures.len(),
+                        result.cases
+                    ),
+                    1,
+                )
+                .with_details(serde_json::to_value(&result).unwrap_or_default()));
+            }
+            Ok(result)
+        },
+        |data| data.clone(),
+        |data| {
+            print_selftest_result(data);
+            Ok(())
+        },
+    )
+}
+
 pub fn execute_history(service: &TasqueService, args: HistoryArgs, opts: GlobalOpts) -> i32 {
     run_action(
         "tsq history",
@@ -271,8 +529,34 @@ pub fn execute_history(service: &TasqueService, args: HistoryArgs, opts: GlobalO
     )
 }
 
+pub fn execute_log(service: &TasqueService, args: LogArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq log",
+        opts,
+        || {
+            let limit = args
+                .limit
+                .as_deref()
+                .map(|value| parse_positive_int(value, "limit", 1, 10000))
+                .transpose()?
+                .map(|value| value as usize);
+            service.log(LogInput {
+                limit,
+                before_cursor: as_optional_string(args.before_cursor.as_deref()),
+                event_type: as_optional_string(args.event_type.as_deref()),
+                actor: as_optional_string(args.actor.as_deref()),
+            })
+        },
+        |data| serde_json::to_value(data).unwrap_or_default(),
+        |data| {
+            print_log(data);
+            Ok(())
+        },
+    )
+}
+
 pub fn execute_watch(service: &TasqueService, args: WatchArgs, opts: GlobalOpts) -> i32 {
-    let watch_options = match build_watch_options(args, opts.json()) {
+    let watch_options = match build_watch_options(args, opts) {
         Ok(options) => options,
         Err(error) => {
             if opts.json() {
@@ -329,16 +613,38 @@ pub fn execute_tui(service: &TasqueService, args: TuiArgs, opts: GlobalOpts) ->
     start_tui(service, tui_options)
 }
 
-fn build_watch_options(args: WatchArgs, json: bool) -> Result<WatchOptions, TsqError> {
+fn build_watch_options(args: WatchArgs, opts: GlobalOpts) -> Result<WatchOptions, TsqError> {
+    let json = opts.json();
     let interval = parse_positive_int(&args.interval, "interval", 1, 60)?;
     let statuses = parse_status_csv(&args.status)?;
+    let fade = parse_positive_int(&args.fade, "fade", 0, 100)?;
+    let query = parse_view_query(args.query.as_deref())?;
+    if args.deltas && !json {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "--deltas requires --json",
+            1,
+        ));
+    }
     Ok(WatchOptions {
         interval,
         statuses,
         assignee: as_optional_string(args.assignee.as_deref()),
-        tree: args.tree || !args.flat,
+        query,
+        tree: (args.tree || !args.flat) && !args.workspace,
         once: args.once,
         json,
+        fade: fade as usize,
+        deltas: args.deltas,
+        with_notes: args.with_notes,
+        tasks: args
+            .tasks
+            .iter()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect(),
+        workspace: args.workspace,
+        exact_id: opts.exact_id,
     })
 }
 
@@ -352,6 +658,7 @@ fn build_tui_options(args: TuiArgs, json: bool) -> Result<TuiOptions, TsqError>
     }
     let interval = parse_positive_int(&args.interval, "interval", 1, 60)?;
     let statuses = parse_status_csv(&args.status)?;
+    let query = parse_view_query(args.query.as_deref())?;
     let view = if args.board {
         TuiView::Board
     } else if args.epics {
@@ -363,18 +670,32 @@ fn build_tui_options(args: TuiArgs, json: bool) -> Result<TuiOptions, TsqError>
         interval,
         statuses,
         assignee: as_optional_string(args.assignee.as_deref()),
+        query,
         once: args.once,
         json,
         view,
+        with_notes: args.with_notes,
     })
 }
 
+/// Validate a `--query` up front so a typo fails once instead of on every frame.
+fn parse_view_query(raw: Option<&str>) -> Result<Option<String>, TsqError> {
+    let Some(query) = as_optional_string(raw) else {
+        return Ok(None);
+    };
+    parse_query(&query)?;
+    Ok(Some(query))
+}
+
 fn skill_target_to_string(target: crate::skills::types::SkillTarget) -> &'static str {
     match target {
         crate::skills::types::SkillTarget::Claude => "claude",
         crate::skills::types::SkillTarget::Codex => "codex",
         crate::skills::types::SkillTarget::Copilot => "copilot",
         crate::skills::types::SkillTarget::Opencode => "opencode",
+        crate::skills::types::SkillTarget::Cursor => "cursor",
+        crate::skills::types::SkillTarget::Windsurf => "windsurf",
+        crate::skills::types::SkillTarget::Gemini => "gemini",
     }
 }
 
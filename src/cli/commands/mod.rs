@@ -1,9 +1,22 @@
+pub mod actor;
+pub mod audit;
+pub mod backup;
+pub mod batch;
+pub mod checklist;
+pub mod criteria;
 pub mod dep;
+pub mod export;
+pub mod git;
 pub mod hooks;
+pub mod ids;
 pub mod label;
 pub mod link;
+pub mod lock;
 pub mod meta;
 pub mod note;
+pub mod release_notes;
+pub mod report;
+pub mod serve;
 pub mod skills;
 pub mod spec;
 pub mod sync;
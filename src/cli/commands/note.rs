@@ -1,6 +1,7 @@
 use crate::app::service::TasqueService;
 use crate::app::service_types::{NoteAddInput, NoteListInput};
 use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::editor::compose_in_editor;
 use crate::cli::render::{print_task_note, print_task_notes};
 use crate::errors::TsqError;
 use clap::{Args, Subcommand};
@@ -26,12 +27,16 @@ pub struct NoteListArgs {
 #[command(after_help = "Examples:
   tsq note tsq-abc12345 \"blocked on API decision\"
   printf 'multi-line note' | tsq note tsq-abc12345 --stdin
+  tsq note tsq-abc12345 --edit
   tsq notes tsq-abc12345")]
 pub struct NoteArgs {
     pub id: String,
     pub text: Option<String>,
     #[arg(long)]
     pub stdin: bool,
+    /// Compose the note in $VISUAL/$EDITOR, seeded with the note text if given
+    #[arg(long, default_value_t = false, conflicts_with = "stdin")]
+    pub edit: bool,
 }
 
 pub fn execute_note(service: &TasqueService, command: NoteCommand, opts: GlobalOpts) -> i32 {
@@ -109,6 +114,9 @@ pub fn execute_notes_verb(service: &TasqueService, args: NoteListArgs, opts: Glo
 }
 
 fn note_text(args: &NoteArgs) -> Result<String, TsqError> {
+    if args.edit {
+        return compose_in_editor(args.text.as_deref().unwrap_or_default());
+    }
     match (&args.text, args.stdin) {
         (Some(_), true) => Err(TsqError::new(
             "VALIDATION_ERROR",
@@ -119,7 +127,7 @@ fn note_text(args: &NoteArgs) -> Result<String, TsqError> {
         (None, true) => crate::app::stdin::read_stdin_content(),
         (None, false) => Err(TsqError::new(
             "VALIDATION_ERROR",
-            "note text is required unless --stdin is provided",
+            "note text is required unless --stdin or --edit is provided",
             1,
         )),
     }
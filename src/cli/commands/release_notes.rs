@@ -0,0 +1,135 @@
+use crate::app::runtime::git_ref_timestamp;
+use crate::app::service::TasqueService;
+use crate::app::service_types::{ReleaseNotesGrouping, ReleaseNotesInput, ReleaseNotesResult};
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::parsers::parse_since_timestamp;
+use crate::cli::style;
+use crate::errors::TsqError;
+use clap::{Args, ValueEnum};
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq release-notes --since v0.6.0 --format markdown
+  tsq release-notes --since 2024-06-01 --group-by label")]
+pub struct ReleaseNotesArgs {
+    /// Git tag, date, or ISO timestamp; tasks closed at or after it are listed
+    #[arg(long)]
+    pub since: String,
+    /// Section the notes by task kind or by label
+    #[arg(long = "group-by", value_enum, default_value_t = GroupByArg::Kind)]
+    pub group_by: GroupByArg,
+    /// Set from the global `--format markdown`
+    #[arg(skip)]
+    pub markdown: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GroupByArg {
+    Kind,
+    Label,
+}
+
+pub fn execute_release_notes(
+    service: &TasqueService,
+    args: ReleaseNotesArgs,
+    opts: GlobalOpts,
+) -> i32 {
+    run_action(
+        "tsq release-notes",
+        opts,
+        || {
+            let (since, tag) = resolve_since(service, &args.since)?;
+            service.release_notes(ReleaseNotesInput {
+                since,
+                tag,
+                group_by: match args.group_by {
+                    GroupByArg::Kind => ReleaseNotesGrouping::Kind,
+                    GroupByArg::Label => ReleaseNotesGrouping::Label,
+                },
+            })
+        },
+        |notes| serde_json::to_value(notes).unwrap_or_default(),
+        |notes| {
+            if args.markdown {
+                print!("{}", release_notes_markdown(notes));
+            } else {
+                print_release_notes(notes);
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Dates and timestamps are taken as-is; anything else is looked up as a git
+/// tag and replaced by its commit time.
+fn resolve_since(service: &TasqueService, raw: &str) -> Result<(String, Option<String>), TsqError> {
+    if let Ok(since) = parse_since_timestamp(raw, "since") {
+        return Ok((since, None));
+    }
+    match git_ref_timestamp(service.repo_root(), raw.trim()) {
+        Some(since) => Ok((since, Some(raw.trim().to_string()))),
+        None => Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "--since must be a git tag, date, or ISO timestamp",
+            1,
+        )
+        .with_details(serde_json::json!({ "since": raw }))),
+    }
+}
+
+fn release_notes_markdown(notes: &ReleaseNotesResult) -> String {
+    let mut out = String::from("# Release notes\n\n");
+    let window = match notes.tag.as_deref() {
+        Some(tag) => format!("Changes since {} ({})", tag, notes.since),
+        None => format!("Changes since {}", notes.since),
+    };
+    out.push_str(&format!("{} · {} tasks\n", window, notes.total));
+    if notes.groups.is_empty() {
+        out.push_str("\nNo tasks closed in this window.\n");
+        return out;
+    }
+    for group in &notes.groups {
+        out.push_str(&format!("\n## {}\n\n", group.name));
+        for entry in &group.tasks {
+            let reference = entry
+                .external_ref
+                .as_deref()
+                .map(|value| format!(" ({})", value))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {}{} `{}`\n",
+                entry.title.replace('\n', " "),
+                reference,
+                entry.id
+            ));
+        }
+    }
+    out
+}
+
+fn print_release_notes(notes: &ReleaseNotesResult) {
+    let mut header = format!("since={}", notes.since);
+    if let Some(tag) = notes.tag.as_deref() {
+        header.push_str(&format!(" tag={}", tag));
+    }
+    println!(
+        "{} tasks={} excluded={}",
+        header, notes.total, notes.excluded
+    );
+    if notes.groups.is_empty() {
+        println!("{}", style::muted("no tasks closed in this window"));
+        return;
+    }
+    for group in &notes.groups {
+        println!();
+        println!("{}", style::heading(&group.name));
+        for entry in &group.tasks {
+            let reference = entry
+                .external_ref
+                .as_deref()
+                .map(|value| format!(" {}", style::muted(value)))
+                .unwrap_or_default();
+            println!("{} {}{}", style::task_id(&entry.id), entry.title, reference);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::ReportResult;
+use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::html::{render_markdown_page, write_html_file};
+use crate::cli::parsers::parse_as_of;
+use crate::types::{TaskKind, TaskStatus};
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq report
+  tsq report --out report.md
+  tsq report --format html --out report.html")]
+pub struct ReportArgs {
+    /// Write the report to a file instead of stdout
+    #[arg(long)]
+    pub out: Option<String>,
+    /// Summarize the backlog as it stood at this date or ISO timestamp
+    #[arg(long = "as-of")]
+    pub as_of: Option<String>,
+    /// Set from the global `--format html`
+    #[arg(skip)]
+    pub html: bool,
+}
+
+pub fn execute_report(service: &TasqueService, args: ReportArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq report",
+        opts,
+        || {
+            let scoped = parse_as_of(args.as_of.as_deref())?.map(|as_of| service.as_of(as_of));
+            let report = scoped.as_ref().unwrap_or(service).report()?;
+            let markdown = report_markdown(&report);
+            let document = if args.html {
+                render_markdown_page("Task report", &markdown)
+            } else {
+                markdown
+            };
+            if let Some(out) = args.out.as_deref() {
+                write_html_file(out, &document)?;
+            }
+            Ok((report, document))
+        },
+        |(report, document)| {
+            let mut value = serde_json::to_value(report).unwrap_or_default();
+            if let Some(out) = args.out.as_deref() {
+                value["out"] = serde_json::json!(out);
+                value["bytes"] = serde_json::json!(document.len());
+            }
+            value
+        },
+        |(_, document)| {
+            match args.out.as_deref() {
+                Some(out) => println!("report={}", out),
+                None => print!("{}", document),
+            }
+            Ok(())
+        },
+    )
+}
+
+fn report_markdown(report: &ReportResult) -> String {
+    let mut out = String::from("# Task report\n\n");
+    out.push_str(&format!(
+        "Generated {} · {} tasks\n\n",
+        report.generated_at, report.total
+    ));
+    if report.status_counts.is_empty() {
+        out.push_str("No tasks recorded.\n");
+        return out;
+    }
+
+    out.push_str("| Status | Count |\n| --- | --- |\n");
+    for entry in &report.status_counts {
+        out.push_str(&format!(
+            "| {} | {} |\n",
+            entry.status.as_str(),
+            entry.count
+        ));
+    }
+
+    for entry in &report.status_counts {
+        out.push_str(&format!("\n## {}\n\n", status_heading(&entry.status)));
+        for task in report
+            .tasks
+            .iter()
+            .filter(|task| task.status == entry.status)
+        {
+            let mut meta = vec![
+                format!("P{}", task.priority),
+                kind_label(task.kind).to_string(),
+            ];
+            if let Some(assignee) = task.assignee.as_deref() {
+                meta.push(format!("@{}", escape_markdown(assignee)));
+            }
+            out.push_str(&format!(
+                "- **{}** {} — {}\n",
+                task.id,
+                escape_markdown(&task.title),
+                meta.join(" · ")
+            ));
+        }
+    }
+    out
+}
+
+fn status_heading(status: &TaskStatus) -> String {
+    let heading = match status {
+        TaskStatus::InProgress => "In progress",
+        TaskStatus::Blocked => "Blocked",
+        TaskStatus::Open => "Open",
+        TaskStatus::Deferred => "Deferred",
+        TaskStatus::Closed => "Closed",
+        TaskStatus::Canceled => "Canceled",
+        TaskStatus::Custom(name) => {
+            let mut words = name.replace('_', " ");
+            if let Some(first) = words.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+            return words;
+        }
+    };
+    heading.to_string()
+}
+
+fn kind_label(kind: TaskKind) -> &'static str {
+    match kind {
+        TaskKind::Task => "task",
+        TaskKind::Feature => "feature",
+        TaskKind::Epic => "epic",
+    }
+}
+
+/// Titles are free text; escape anything markdown or the HTML renderer
+/// would otherwise interpret.
+fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
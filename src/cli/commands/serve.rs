@@ -0,0 +1,66 @@
+use crate::app::service::TasqueService;
+use crate::cli::action::GlobalOpts;
+use crate::cli::parsers::{parse_positive_int, parse_status_csv};
+use crate::cli::serve::{ServeOptions, start_serve};
+use crate::errors::TsqError;
+use crate::output::err_envelope;
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(after_help = "Endpoints (read-only):
+  /api/frame  current frame as a JSON envelope (tasks, summary, epics)
+  /events     Server-Sent Events; a `frame` event whenever the frame changes
+  /metrics    Prometheus gauges: task counts, ready depth, event log size,
+              projection latency
+  /           dashboard page with board, list, and epic views (--ui only)
+
+Examples:
+  tsq serve --ui
+  tsq serve --port 8080 --status open,in_progress,blocked,closed")]
+pub struct ServeArgs {
+    /// Also serve the web dashboard at /
+    #[arg(long, default_value_t = false)]
+    pub ui: bool,
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+    #[arg(long, default_value = "7878")]
+    pub port: String,
+    /// Seconds between frame checks for /events
+    #[arg(long, default_value = "2")]
+    pub interval: String,
+    #[arg(long, default_value = "open,in_progress,blocked")]
+    pub status: String,
+}
+
+pub fn execute_serve(service: &TasqueService, args: ServeArgs, opts: GlobalOpts) -> i32 {
+    match build_serve_options(args).and_then(|options| start_serve(service, options)) {
+        Ok(()) => 0,
+        Err(error) => {
+            if opts.json() {
+                let envelope = err_envelope(
+                    "tsq serve",
+                    error.code.clone(),
+                    error.message.clone(),
+                    error.details.clone(),
+                );
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&envelope).unwrap_or_else(|_| "{}".to_string())
+                );
+            } else {
+                eprintln!("{}: {}", error.code, error.message);
+            }
+            error.exit_code
+        }
+    }
+}
+
+fn build_serve_options(args: ServeArgs) -> Result<ServeOptions, TsqError> {
+    Ok(ServeOptions {
+        host: args.host,
+        port: parse_positive_int(&args.port, "port", 0, 65535)? as u16,
+        ui: args.ui,
+        interval: parse_positive_int(&args.interval, "interval", 1, 60)?,
+        statuses: parse_status_csv(&args.status)?,
+    })
+}
@@ -1,24 +1,148 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::SkillsRefreshInput;
+use crate::app::service_types::{SkillsDiffInput, SkillsRefreshInput};
 use crate::cli::action::{GlobalOpts, run_action};
-use clap::Subcommand;
+use crate::cli::render::{print_skill_changes, print_skill_dry_run_banner, print_skill_source};
+use clap::{Args, Subcommand};
 
 #[derive(Debug, Subcommand)]
 pub enum SkillsCommand {
-    /// Refresh managed skill files across all targets
-    Refresh,
+    /// Show where the tasque skill is installed and whether tsq manages it
+    List,
+    /// Compare installed managed skills against the bundled source
+    Status(SkillSourceArgs),
+    /// Show content differences between installed copies and the managed source
+    Diff(SkillDiffArgs),
+    /// Reinstall managed skill files in place across all targets (supports --dry-run)
+    #[command(visible_alias = "update")]
+    Refresh(SkillRefreshArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SkillRefreshArgs {
+    /// Upgrade only managed installs whose version is behind the source
+    #[arg(long)]
+    pub all: bool,
+    #[command(flatten)]
+    pub source: SkillSourceArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct SkillSourceArgs {
+    /// Skill source: local skills root, git URL (`url#path`), or `registry:<name>`
+    #[arg(long)]
+    pub source: Option<String>,
+    /// Require the source to match this `sha256:<hex>` content checksum
+    #[arg(long)]
+    pub checksum: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SkillDiffArgs {
+    /// Skill name under each target's skills directory
+    #[arg(default_value = "tasque")]
+    pub name: String,
+    #[command(flatten)]
+    pub source: SkillSourceArgs,
+}
+
+fn source_input(args: &SkillSourceArgs, dry_run: bool) -> SkillsRefreshInput {
+    SkillsRefreshInput {
+        dry_run,
+        source: args.source.clone(),
+        checksum: args.checksum.clone(),
+        ..Default::default()
+    }
 }
 
 pub fn execute_skills(service: &TasqueService, command: SkillsCommand, opts: GlobalOpts) -> i32 {
     match command {
-        SkillsCommand::Refresh => run_action(
-            "tsq skills refresh",
+        SkillsCommand::List => run_action(
+            "tsq skills list",
+            opts,
+            || service.skills_list(SkillsRefreshInput::default()),
+            |data| serde_json::json!({ "skills": data }),
+            |data| {
+                for entry in data {
+                    let state = match (entry.installed, entry.managed) {
+                        (false, _) => "missing",
+                        (true, true) => "managed",
+                        (true, false) => "unmanaged",
+                    };
+                    match &entry.version {
+                        Some(version) => println!(
+                            "skill {} {} {} {}",
+                            entry.target, state, version, entry.path
+                        ),
+                        None => println!("skill {} {} {}", entry.target, state, entry.path),
+                    }
+                }
+                Ok(())
+            },
+        ),
+        SkillsCommand::Status(args) => run_action(
+            "tsq skills status",
+            opts,
+            || service.skills_status(source_input(&args, false)),
+            |data| serde_json::json!({ "skills": data }),
+            |data| {
+                for entry in data {
+                    let version = match (&entry.installed_version, &entry.source_version) {
+                        (installed, Some(source)) if entry.upgrade_available => format!(
+                            " {} -> {}",
+                            installed.as_deref().unwrap_or("unversioned"),
+                            source
+                        ),
+                        (Some(installed), _) => format!(" {}", installed),
+                        _ => String::new(),
+                    };
+                    println!(
+                        "skill {} {}{} {}",
+                        entry.target, entry.status, version, entry.path
+                    );
+                    print_skill_changes(&entry.changes);
+                }
+                Ok(())
+            },
+        ),
+        SkillsCommand::Diff(args) => run_action(
+            "tsq skills diff",
             opts,
+            || {
+                service.skills_diff(SkillsDiffInput {
+                    skill_name: args.name.clone(),
+                    source: args.source.source.clone(),
+                    checksum: args.source.checksum.clone(),
+                    ..Default::default()
+                })
+            },
+            |data| serde_json::json!({ "skills": data }),
+            |data| {
+                for entry in data {
+                    let state = match (entry.installed, entry.changes.is_empty()) {
+                        (false, _) => "missing",
+                        (true, true) => "identical",
+                        (true, false) => "differs",
+                    };
+                    println!("skill {} {} {}", entry.target, state, entry.path);
+                    if !entry.patch.is_empty() {
+                        print!("{}", entry.patch);
+                    }
+                }
+                Ok(())
+            },
+        ),
+        // --dry-run here previews file changes instead of staging events, so
+        // the generic event preview in run_action stays off.
+        SkillsCommand::Refresh(args) => run_action(
+            "tsq skills refresh",
+            GlobalOpts {
+                dry_run: false,
+                ..opts
+            },
             || {
                 service.skills_refresh(SkillsRefreshInput {
-                    source_root_dir: None,
-                    home_dir: None,
-                    codex_home: None,
+                    only_outdated: args.all,
+                    ..source_input(&args.source, opts.dry_run)
                 })
             },
             // run_action's JSON mapper returns an owned serializable value; returning
@@ -37,6 +161,11 @@ pub fn execute_skills(service: &TasqueService, command: SkillsCommand, opts: Glo
                             .map(|m| format!(" ({})", m))
                             .unwrap_or_default()
                     );
+                    print_skill_changes(&result.changes);
+                }
+                print_skill_source(data);
+                if data.dry_run {
+                    print_skill_dry_run_banner();
                 }
                 Ok(())
             },
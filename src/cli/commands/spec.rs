@@ -1,9 +1,12 @@
 use crate::app::service::TasqueService;
 use crate::app::service_types::{
-    SpecAttachInput, SpecCheckInput, SpecContentInput, SpecContentResult, SpecPatchInput,
-    SpecUpdateInput, SpecUpdateResult,
+    SpecAttachInput, SpecAttachResult, SpecCheckInput, SpecContentInput, SpecContentResult,
+    SpecDiffInput, SpecDiffResult, SpecHistoryInput, SpecHistoryResult, SpecPatchInput,
+    SpecReattachInput, SpecTemplateInput, SpecUpdateInput, SpecUpdateResult,
 };
 use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::editor::compose_in_editor;
+use crate::cli::html::{render_markdown_page, write_html_file};
 use crate::cli::parsers::as_optional_string;
 use crate::cli::render::{print_spec_content, print_task};
 use crate::errors::TsqError;
@@ -34,16 +37,88 @@ pub struct SpecCheckArgs {
     pub id: String,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum SpecVerbCommand {
+    /// Scaffold a spec from a template, edit it, and attach it
+    New(SpecNewArgs),
+    /// Print the live spec, or an earlier attached revision with --version
+    Show(SpecShowArgs),
+    /// List every attached revision of a task's spec
+    History(SpecIdArgs),
+    /// Render the spec to a standalone HTML page
+    Render(SpecRenderArgs),
+    /// Show a unified diff between the attached spec and the file on disk
+    Diff(SpecIdArgs),
+    /// Accept the on-disk spec content as the new attached revision
+    Reattach(SpecIdArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SpecIdArgs {
+    pub id: String,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq spec render tsq-abc12345 --out spec.html
+  tsq spec render tsq-abc12345 --version 1 > spec-v1.html")]
+pub struct SpecRenderArgs {
+    pub id: String,
+    /// Write the HTML to a file instead of stdout
+    #[arg(long)]
+    pub out: Option<String>,
+    /// Attachment number from `tsq spec history`
+    #[arg(long)]
+    pub version: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+pub struct SpecShowArgs {
+    pub id: String,
+    /// Attachment number from `tsq spec history`
+    #[arg(long)]
+    pub version: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq spec new tsq-abc12345
+  tsq spec new tsq-abc12345 --template feature --no-edit
+
+Templates default to the task kind (task, feature, epic); add
+.tasque/spec-templates/<name>.md to customize or define new ones.")]
+pub struct SpecNewArgs {
+    pub id: String,
+    /// Template name; defaults to the task kind
+    #[arg(long)]
+    pub template: Option<String>,
+    /// Attach the scaffold as-is without opening $VISUAL/$EDITOR
+    #[arg(long = "no-edit")]
+    pub no_edit: bool,
+    #[arg(long)]
+    pub force: bool,
+}
+
 #[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 #[command(after_help = "Examples:
   tsq spec tsq-abc12345 --file docs/spec.md
   tsq spec tsq-abc12345 --update --stdin
   tsq spec tsq-abc12345 --patch --file spec.patch
   tsq spec tsq-abc12345 --text '# Context\n...'
   tsq spec tsq-abc12345 --show
-  tsq spec tsq-abc12345 --check")]
+  tsq spec tsq-abc12345 --check
+  tsq spec new tsq-abc12345 --template feature
+  tsq spec history tsq-abc12345
+  tsq spec tsq-abc12345 --show --version 2
+  tsq spec render tsq-abc12345 --out spec.html
+  tsq spec diff tsq-abc12345
+  tsq spec reattach tsq-abc12345")]
 pub struct SpecArgs {
-    pub id: String,
+    #[command(subcommand)]
+    pub command: Option<SpecVerbCommand>,
+    #[arg(required = true)]
+    pub id: Option<String>,
     #[arg(long)]
     pub file: Option<String>,
     #[arg(long)]
@@ -60,6 +135,9 @@ pub struct SpecArgs {
     pub show: bool,
     #[arg(long)]
     pub check: bool,
+    /// With --show, print an earlier attached revision (see `tsq spec history`)
+    #[arg(long, requires = "show")]
+    pub version: Option<usize>,
 }
 
 pub fn execute_spec(service: &TasqueService, command: SpecCommand, opts: GlobalOpts) -> i32 {
@@ -125,6 +203,74 @@ pub fn execute_spec(service: &TasqueService, command: SpecCommand, opts: GlobalO
 }
 
 pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOpts) -> i32 {
+    if let Some(command) = args.command {
+        return match command {
+            SpecVerbCommand::New(new_args) => execute_spec_new(service, new_args, opts),
+            SpecVerbCommand::Show(show_args) => run_action(
+                "tsq spec show",
+                opts,
+                || {
+                    service.spec_content(SpecContentInput {
+                        id: show_args.id.clone(),
+                        version: show_args.version,
+                        exact_id: opts.exact_id,
+                    })
+                },
+                spec_content_json,
+                |data| {
+                    print_spec_content(data);
+                    Ok(())
+                },
+            ),
+            SpecVerbCommand::History(id_args) => run_action(
+                "tsq spec history",
+                opts,
+                || {
+                    service.spec_history(SpecHistoryInput {
+                        id: id_args.id.clone(),
+                        exact_id: opts.exact_id,
+                    })
+                },
+                |data| data.clone(),
+                |data| {
+                    print_spec_history(data);
+                    Ok(())
+                },
+            ),
+            SpecVerbCommand::Render(render_args) => execute_spec_render(service, render_args, opts),
+            SpecVerbCommand::Diff(id_args) => run_action(
+                "tsq spec diff",
+                opts,
+                || {
+                    service.spec_diff(SpecDiffInput {
+                        id: id_args.id.clone(),
+                        exact_id: opts.exact_id,
+                    })
+                },
+                |data| data.clone(),
+                |data| {
+                    print_spec_diff(data);
+                    Ok(())
+                },
+            ),
+            SpecVerbCommand::Reattach(id_args) => run_action(
+                "tsq spec reattach",
+                opts,
+                || {
+                    service.spec_reattach(SpecReattachInput {
+                        id: id_args.id.clone(),
+                        exact_id: opts.exact_id,
+                    })
+                },
+                |data| data.clone(),
+                |data| {
+                    print_spec_update_result(data);
+                    Ok(())
+                },
+            ),
+        };
+    }
+    let id = args.id.clone().unwrap_or_default();
     let action = match classify_spec_action(&args) {
         Ok(action) => action,
         Err(error) => {
@@ -144,7 +290,7 @@ pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOp
             opts,
             || {
                 service.spec_attach(SpecAttachInput {
-                    id: args.id.clone(),
+                    id: id.clone(),
                     source: None,
                     file: as_optional_string(args.file.as_deref()),
                     stdin: args.stdin,
@@ -166,7 +312,8 @@ pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOp
             opts,
             || {
                 service.spec_content(SpecContentInput {
-                    id: args.id.clone(),
+                    id: id.clone(),
+                    version: args.version,
                     exact_id: opts.exact_id,
                 })
             },
@@ -181,7 +328,7 @@ pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOp
             opts,
             || {
                 service.spec_update(SpecUpdateInput {
-                    id: args.id.clone(),
+                    id: id.clone(),
                     file: as_optional_string(args.file.as_deref()),
                     stdin: args.stdin,
                     text: args.text.clone(),
@@ -199,7 +346,7 @@ pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOp
             opts,
             || {
                 service.spec_patch(SpecPatchInput {
-                    id: args.id.clone(),
+                    id: id.clone(),
                     file: as_optional_string(args.file.as_deref()),
                     stdin: args.stdin,
                     text: args.text.clone(),
@@ -217,7 +364,7 @@ pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOp
             opts,
             || {
                 service.spec_check(SpecCheckInput {
-                    id: args.id.clone(),
+                    id: id.clone(),
                     exact_id: opts.exact_id,
                 })
             },
@@ -250,6 +397,93 @@ pub fn execute_spec_verb(service: &TasqueService, args: SpecArgs, opts: GlobalOp
     }
 }
 
+fn execute_spec_new(service: &TasqueService, args: SpecNewArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq spec new",
+        opts,
+        || {
+            let scaffold = service.spec_template(SpecTemplateInput {
+                id: args.id.clone(),
+                template: args.template.clone(),
+                exact_id: opts.exact_id,
+            })?;
+            let content = if args.no_edit {
+                scaffold.content
+            } else {
+                compose_in_editor(&scaffold.content)?
+            };
+            let attached = service.spec_attach(SpecAttachInput {
+                id: scaffold.task_id,
+                source: None,
+                file: None,
+                stdin: false,
+                text: Some(content),
+                force: args.force,
+                exact_id: true,
+            })?;
+            Ok((scaffold.template, attached))
+        },
+        |(template, data): &(String, SpecAttachResult)| {
+            serde_json::json!({
+                "task": data.task,
+                "spec": data.spec,
+                "template": template,
+            })
+        },
+        |(template, data)| {
+            print_task(&data.task);
+            println!("template={}", template);
+            println!("spec={}", data.spec.spec_path);
+            println!("spec_sha256={}", data.spec.spec_fingerprint);
+            Ok(())
+        },
+    )
+}
+
+fn execute_spec_render(service: &TasqueService, args: SpecRenderArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq spec render",
+        opts,
+        || {
+            let spec = service.spec_content(SpecContentInput {
+                id: args.id.clone(),
+                version: args.version,
+                exact_id: opts.exact_id,
+            })?;
+            let task = service.show(&spec.task_id, true)?.task;
+            let document =
+                render_markdown_page(&format!("{}: {}", task.id, task.title), &spec.content);
+            if let Some(out) = args.out.as_deref() {
+                write_html_file(out, &document)?;
+            }
+            Ok((spec, document))
+        },
+        |(spec, document)| {
+            let mut value = serde_json::json!({
+                "task_id": spec.task_id,
+                "spec_path": spec.spec_path,
+                "spec_fingerprint": spec.spec_fingerprint,
+                "bytes": document.len(),
+            });
+            if let Some(version) = spec.version {
+                value["version"] = serde_json::json!(version);
+            }
+            match args.out.as_deref() {
+                Some(out) => value["out"] = serde_json::json!(out),
+                None => value["html"] = serde_json::json!(document),
+            }
+            value
+        },
+        |(_, document)| {
+            match args.out.as_deref() {
+                Some(out) => println!("html={}", out),
+                None => print!("{}", document),
+            }
+            Ok(())
+        },
+    )
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SpecAction {
     Attach,
@@ -318,13 +552,44 @@ fn classify_spec_action(args: &SpecArgs) -> Result<SpecAction, TsqError> {
 }
 
 fn spec_content_json(data: &SpecContentResult) -> serde_json::Value {
-    serde_json::json!({
-        "spec": {
-            "path": data.spec_path.as_str(),
-            "fingerprint": data.spec_fingerprint.as_str(),
-            "content": data.content.as_str(),
+    let mut spec = serde_json::json!({
+        "path": data.spec_path.as_str(),
+        "fingerprint": data.spec_fingerprint.as_str(),
+        "content": data.content.as_str(),
+    });
+    if let Some(version) = data.version {
+        spec["version"] = serde_json::json!(version);
+    }
+    serde_json::json!({ "spec": spec })
+}
+
+fn print_spec_history(data: &SpecHistoryResult) {
+    if data.versions.is_empty() {
+        println!("task={} has no attached spec revisions", data.task_id);
+        return;
+    }
+    for version in &data.versions {
+        let mut flags = Vec::new();
+        if version.current {
+            flags.push("current");
+        }
+        if !version.content_available {
+            flags.push("content-unavailable");
         }
-    })
+        let short = version
+            .fingerprint
+            .get(..12)
+            .unwrap_or(&version.fingerprint);
+        let suffix = if flags.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", flags.join(","))
+        };
+        println!(
+            "v{} {} {} by {}{}",
+            version.version, short, version.attached_at, version.attached_by, suffix
+        );
+    }
 }
 
 fn print_spec_update_result(data: &SpecUpdateResult) {
@@ -334,6 +599,25 @@ fn print_spec_update_result(data: &SpecUpdateResult) {
     println!("spec_sha256_new={}", data.spec.new_fingerprint);
 }
 
+fn print_spec_diff(data: &SpecDiffResult) {
+    if !data.drifted {
+        println!("spec={} matches attached fingerprint", data.spec_path);
+        return;
+    }
+    match data.diff.as_deref() {
+        Some(diff) => print!("{}", diff),
+        None => {
+            println!("spec={}", data.spec_path);
+            println!("spec_sha256_expected={}", data.expected_fingerprint);
+            println!("spec_sha256_actual={}", data.actual_fingerprint);
+            println!(
+                "attached content unavailable; use `tsq spec reattach {}` to accept the file on disk",
+                data.task_id
+            );
+        }
+    }
+}
+
 fn spec_diagnostic_code_to_string(
     code: &crate::app::storage::SpecCheckDiagnosticCode,
 ) -> &'static str {
@@ -1,8 +1,16 @@
 use crate::app::service::TasqueService;
 use crate::app::sync::DEFAULT_SYNC_BRANCH;
+use crate::app::sync_daemon::SyncDaemonOptions;
 use crate::cli::action::{GlobalOpts, run_action};
-use crate::store::merge_driver::merge_events_files;
-use clap::Args;
+use crate::cli::parsers::parse_positive_int;
+use crate::cli::serve::with_metrics_server;
+use crate::errors::TsqError;
+use crate::output::ok_envelope;
+use crate::store::merge_driver::{
+    is_segment_manifest, merge_events_files, merge_segment_manifests,
+};
+use crate::types::{SyncDaemonStatus, SyncStatusResult};
+use clap::{Args, Subcommand};
 use std::path::Path;
 
 #[derive(Debug, Args)]
@@ -23,16 +31,64 @@ pub struct MigrateArgs {
 }
 
 #[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct SyncArgs {
+    #[command(subcommand)]
+    pub action: Option<SyncAction>,
     /// Commit changes but skip pushing to upstream
     #[arg(long = "no-push")]
     pub no_push: bool,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum SyncAction {
+    /// Commit, fetch and rebase onto the remote sync branch, then push
+    Push,
+    /// Commit, then fetch and rebase onto the remote sync branch
+    Pull,
+    /// Move task data back into the repo root `.tasque/` and remove the worktree
+    Disable(SyncDisableArgs),
+    /// Report worktree health, pending events, ahead/behind, and merge-driver setup
+    Status(SyncStatusArgs),
+    /// Keep syncing in the foreground whenever events change or the interval passes
+    Daemon(SyncDaemonArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SyncDisableArgs {
+    /// Also delete the local sync branch (the remote branch is kept)
+    #[arg(long = "delete-branch", default_value_t = false)]
+    pub delete_branch: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SyncStatusArgs {
+    /// Fetch the remote sync branch first so ahead/behind is current
+    #[arg(long, default_value_t = false)]
+    pub fetch: bool,
+    /// Exit non-zero with SYNC_UNHEALTHY when any problem is found
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SyncDaemonArgs {
+    /// Seconds between syncs when nothing changed; failures back off from here
+    #[arg(long, default_value = "30")]
+    pub interval: String,
+    /// Run one sync cycle, record the status file, and exit
+    #[arg(long, default_value_t = false)]
+    pub once: bool,
+    /// Also serve Prometheus gauges at http://<addr>/metrics (e.g. 127.0.0.1:9464)
+    #[arg(long, value_name = "ADDR", conflicts_with = "once")]
+    pub metrics: Option<String>,
+}
+
 /// Execute the merge-driver command.
 ///
 /// This is invoked by git during a merge when the `.gitattributes` file
-/// specifies `merge=tasque-events` for `events.jsonl`.
+/// specifies `merge=tasque-events` for `events.jsonl`, its segments, or the
+/// segment manifest.
 ///
 /// Does NOT need TasqueService -- operates directly on raw files.
 /// Returns 0 on success, 1 on conflict, or `error.exit_code` on merge errors.
@@ -41,6 +97,19 @@ pub fn execute_merge_driver(args: MergeDriverArgs) -> i32 {
     let ours = Path::new(&args.ours);
     let theirs = Path::new(&args.theirs);
 
+    if is_segment_manifest(ours, theirs) {
+        return match merge_segment_manifests(ancestor, ours, theirs) {
+            Ok(segments) => {
+                eprintln!("Merged segment manifest ({} segments)", segments);
+                0
+            }
+            Err(error) => {
+                eprintln!("{}: {}", error.code, error.message);
+                error.exit_code
+            }
+        };
+    }
+
     match merge_events_files(ancestor, ours, theirs) {
         Ok(outcome) => {
             if outcome.conflict {
@@ -87,6 +156,51 @@ pub fn execute_migrate(service: &TasqueService, args: MigrateArgs, opts: GlobalO
 }
 
 pub fn execute_sync(service: &TasqueService, args: SyncArgs, opts: GlobalOpts) -> i32 {
+    if let Some(action) = args.action {
+        let (command, push) = match action {
+            SyncAction::Push => ("tsq sync push", true),
+            SyncAction::Pull => ("tsq sync pull", false),
+            SyncAction::Disable(disable_args) => {
+                return execute_sync_disable(service, disable_args, opts);
+            }
+            SyncAction::Status(status_args) => {
+                return execute_sync_status(service, status_args, opts);
+            }
+            SyncAction::Daemon(daemon_args) => {
+                return execute_sync_daemon(service, daemon_args, opts);
+            }
+        };
+        return run_action(
+            command,
+            opts,
+            || {
+                if push {
+                    service.sync_push()
+                } else {
+                    service.sync_pull()
+                }
+            },
+            |data| data.clone(),
+            |data| {
+                if data.committed {
+                    println!("Committed task updates on '{}'", data.branch);
+                }
+                if data.pulled_commits > 0 {
+                    println!(
+                        "Rebased onto {} incoming commit(s) from {}/{}",
+                        data.pulled_commits, data.remote, data.branch
+                    );
+                } else {
+                    println!("Already up to date with {}/{}", data.remote, data.branch);
+                }
+                if data.pushed {
+                    println!("Pushed '{}' to {}", data.branch, data.remote);
+                }
+                Ok(())
+            },
+        );
+    }
+
     run_action(
         "tsq sync",
         opts,
@@ -113,3 +227,161 @@ pub fn execute_sync(service: &TasqueService, args: SyncArgs, opts: GlobalOpts) -
         },
     )
 }
+
+fn execute_sync_disable(service: &TasqueService, args: SyncDisableArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq sync disable",
+        opts,
+        || service.sync_disable(args.delete_branch),
+        |data| data.clone(),
+        |data| {
+            println!(
+                "Moved {} events and {} spec file(s) into {}/.tasque",
+                data.events_migrated, data.specs_copied, data.repo_root
+            );
+            println!("Removed worktree {}", data.worktree_path);
+            if data.branch_deleted {
+                println!("Deleted branch '{}'", data.branch);
+            } else {
+                println!("Kept branch '{}'", data.branch);
+            }
+            Ok(())
+        },
+    )
+}
+
+fn execute_sync_status(service: &TasqueService, args: SyncStatusArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq sync status",
+        opts,
+        || {
+            let status = service.sync_status(args.fetch)?;
+            if args.check && !status.healthy {
+                return Err(TsqError::new(
+                    "SYNC_UNHEALTHY",
+                    format!("sync has {} problem(s)", status.problems.len()),
+                    1,
+                )
+                .with_details(serde_json::to_value(&status).unwrap_or_default()));
+            }
+            Ok(status)
+        },
+        |data| data.clone(),
+        |data| {
+            print_sync_status(data);
+            Ok(())
+        },
+    )
+}
+
+fn print_sync_status(status: &SyncStatusResult) {
+    let Some(branch) = status.branch.as_deref() else {
+        println!("sync: not configured");
+        return;
+    };
+    println!(
+        "sync: {} ({})",
+        branch,
+        if status.healthy {
+            "healthy"
+        } else {
+            "unhealthy"
+        }
+    );
+    if let Some(path) = status.worktree_path.as_deref() {
+        let validity = if status.worktree_valid {
+            "ok"
+        } else {
+            "invalid"
+        };
+        println!("worktree: {} [{}]", path, validity);
+    }
+    println!(
+        "uncommitted: {} event(s){}",
+        status.uncommitted_events,
+        if status.dirty { ", worktree dirty" } else { "" }
+    );
+    match (status.remote.as_deref(), status.ahead, status.behind) {
+        (Some(remote), Some(ahead), Some(behind)) => {
+            println!("remote: {remote} ahead={ahead} behind={behind}")
+        }
+        (Some(remote), _, _) => println!("remote: {remote} (not fetched)"),
+        (None, _, _) => println!("remote: none"),
+    }
+    println!(
+        "merge driver: gitattributes={} driver={}",
+        if status.merge_driver.gitattributes {
+            "yes"
+        } else {
+            "no"
+        },
+        status.merge_driver.driver.as_deref().unwrap_or("unset")
+    );
+    if let Some(daemon) = &status.daemon {
+        println!("daemon: {}", format_daemon_status(daemon));
+    }
+    for problem in &status.problems {
+        println!("problem: {problem}");
+    }
+}
+
+fn execute_sync_daemon(service: &TasqueService, args: SyncDaemonArgs, opts: GlobalOpts) -> i32 {
+    let json = opts.json();
+    run_action(
+        "tsq sync daemon",
+        opts,
+        || {
+            let interval = parse_positive_int(&args.interval, "interval", 1, 3600)?;
+            let options = SyncDaemonOptions {
+                interval_secs: interval as u64,
+                once: args.once,
+            };
+            let run = || {
+                service
+                    .sync_daemon(options, |status| {
+                        if args.once {
+                            return;
+                        }
+                        if json {
+                            let envelope = ok_envelope("tsq sync daemon", status);
+                            println!(
+                                "{}",
+                                serde_json::to_string(&envelope)
+                                    .unwrap_or_else(|_| "{}".to_string())
+                            );
+                        } else {
+                            println!("{}", format_daemon_status(status));
+                        }
+                    })
+                    .and_then(|status| match status.last_error.as_deref() {
+                        // Only reachable with --once: surface the failed cycle in the exit code.
+                        Some(error) => Err(TsqError::new("SYNC_FAILED", error.to_string(), 2)
+                            .with_details(serde_json::to_value(&status).unwrap_or_default())),
+                        None => Ok(status),
+                    })
+            };
+            match args.metrics.as_deref() {
+                Some(address) => with_metrics_server(service, address, run),
+                None => run(),
+            }
+        },
+        |data| data.clone(),
+        |data| {
+            println!("{}", format_daemon_status(data));
+            Ok(())
+        },
+    )
+}
+
+fn format_daemon_status(status: &SyncDaemonStatus) -> String {
+    match status.last_error.as_deref() {
+        Some(error) => format!(
+            "[{}] sync failed ({}x): {}; retrying in {}s",
+            status.updated_at, status.consecutive_failures, error, status.next_attempt_secs
+        ),
+        None => format!(
+            "[{}] synced '{}' ahead={} behind={}; next in {}s",
+            status.updated_at, status.branch, status.ahead, status.behind, status.next_attempt_secs
+        ),
+    }
+}
@@ -1,18 +1,21 @@
 use crate::app::runtime::normalize_status;
 use crate::app::service::TasqueService;
 use crate::app::service_types::{
-    ClaimInput, DuplicateInput, MergeInput, SpecContentInput, SpecContentResult, StaleInput,
-    SupersedeInput, UpdateInput,
+    ClaimInput, CloneInput, ConvertInput, DuplicateInput, HandoffInput, MergeInput,
+    SpecContentInput, SpecContentResult, SplitInput, StaleInput, SupersedeInput, UpdateInput,
 };
 use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::editor::compose_in_editor;
 use crate::cli::parsers::{
-    as_optional_string, parse_non_negative_int, parse_positive_int, parse_priority_value,
+    as_optional_string, parse_as_of, parse_kind, parse_merge_strategy, parse_non_negative_int,
+    parse_planning_state, parse_positive_int, parse_priority_value,
 };
 use crate::cli::render::{
     print_merge_result, print_show_result, print_spec_content, print_task, print_task_list,
 };
+use crate::cli::style;
 use crate::errors::TsqError;
-use clap::Args;
+use clap::{Args, Subcommand};
 
 #[path = "task_create.rs"]
 mod task_create;
@@ -22,9 +25,10 @@ mod task_find;
 mod task_lifecycle;
 
 pub use task_create::{CreateArgs, execute_create};
-pub use task_find::{FindArgs, execute_find};
+pub use task_find::{FindArgs, FindCommand, TreeArgs, execute_find, execute_tree};
 pub use task_lifecycle::{
-    MultiStatusArgs, NoteStatusArgs, execute_cancel, execute_defer, execute_done, execute_reopen,
+    CancelArgs, DoneArgs, NoteStatusArgs, ReopenArgs, execute_cancel, execute_defer, execute_done,
+    execute_reopen,
 };
 
 #[derive(Debug, Args)]
@@ -32,10 +36,15 @@ pub struct ShowArgs {
     pub id: String,
     #[arg(long = "with-spec", default_value_t = false)]
     pub with_spec: bool,
+    /// Show the task as it stood at this date or ISO timestamp
+    #[arg(long = "as-of", conflicts_with = "with_spec")]
+    pub as_of: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct StaleArgs {
+    #[command(subcommand)]
+    pub action: Option<StaleAction>,
     #[arg(long, default_value = "30")]
     pub days: String,
     #[arg(long)]
@@ -44,6 +53,15 @@ pub struct StaleArgs {
     pub assignee: Option<String>,
     #[arg(long)]
     pub limit: Option<String>,
+    /// Add this label to every stale task that lacks it
+    #[arg(long = "apply-label")]
+    pub apply_label: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StaleAction {
+    /// Apply the config.json stale policy (label and/or defer) in bulk
+    Enforce,
 }
 
 #[derive(Debug, Args)]
@@ -55,6 +73,9 @@ pub struct EditArgs {
     pub description: Option<String>,
     #[arg(long = "clear-description", default_value_t = false)]
     pub clear_description: bool,
+    /// Compose the description in $VISUAL/$EDITOR, seeded with the current description
+    #[arg(long, default_value_t = false, conflicts_with_all = ["description", "clear_description"])]
+    pub edit: bool,
     #[arg(long = "external-ref")]
     pub external_ref: Option<String>,
     #[arg(long = "discovered-from")]
@@ -65,6 +86,13 @@ pub struct EditArgs {
     pub clear_external_ref: bool,
     #[arg(long)]
     pub priority: Option<String>,
+    /// Move to a planning state (`needs_planning`, `planned`, or one from
+    /// `planning_workflow` in config)
+    #[arg(long)]
+    pub planning: Option<String>,
+    /// Move to a status, including one from `status_workflow` in config
+    #[arg(long)]
+    pub status: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -85,11 +113,34 @@ pub struct AssignArgs {
     pub assignee: String,
 }
 
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq handoff tsq-12 --to reviewer-bot --note \"implementation done, needs review\"")]
+pub struct HandoffArgs {
+    pub id: String,
+    /// Actor that takes over the task
+    #[arg(long)]
+    pub to: String,
+    /// Context for the next actor, recorded in the handoff note
+    #[arg(long)]
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Args)]
 pub struct TaskIdArgs {
     pub id: String,
 }
 
+#[derive(Debug, Args)]
+pub struct BlockedArgs {
+    #[arg(required_unless_present = "external")]
+    pub id: Option<String>,
+    #[arg(long = "blocked-on", conflicts_with = "external")]
+    pub blocked_on: Option<String>,
+    #[arg(long, default_value_t = false)]
+    pub external: bool,
+}
+
 #[derive(Debug, Args)]
 pub struct DuplicateArgs {
     pub id: String,
@@ -114,6 +165,39 @@ pub struct SupersedeArgs {
     pub note: Option<String>,
 }
 
+#[derive(Debug, Args)]
+pub struct CloneArgs {
+    pub id: String,
+    /// Parent for the copy (defaults to the original's parent).
+    #[arg(long)]
+    pub into: Option<String>,
+    #[arg(long, default_value_t = false)]
+    pub with_children: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConvertArgs {
+    pub id: String,
+    #[arg(long)]
+    pub kind: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SplitArgs {
+    pub id: String,
+    /// Title of a new part; repeat for each part.
+    #[arg(long = "into", required = true)]
+    pub into: Vec<String>,
+    /// Create parts next to the original (under its parent) instead of as its children.
+    #[arg(long, default_value_t = false)]
+    pub siblings: bool,
+    /// Close the original as superseded by the first part.
+    #[arg(long, default_value_t = false)]
+    pub close: bool,
+    #[arg(long, requires = "close")]
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Args)]
 pub struct MergeArgs {
     pub sources: Vec<String>,
@@ -123,8 +207,9 @@ pub struct MergeArgs {
     pub reason: Option<String>,
     #[arg(long, default_value_t = false)]
     pub force: bool,
-    #[arg(long = "dry-run", default_value_t = false)]
-    pub dry_run: bool,
+    /// Per-field strategies, e.g. `description=concat,labels=union,priority=min`.
+    #[arg(long = "strategy")]
+    pub strategy: Vec<String>,
 }
 
 pub fn execute_show(service: &TasqueService, args: ShowArgs, opts: GlobalOpts) -> i32 {
@@ -132,10 +217,15 @@ pub fn execute_show(service: &TasqueService, args: ShowArgs, opts: GlobalOpts) -
         "tsq show",
         opts,
         || {
-            let show = service.show(&args.id, opts.exact_id)?;
+            let scoped = parse_as_of(args.as_of.as_deref())?.map(|as_of| service.as_of(as_of));
+            let show = scoped
+                .as_ref()
+                .unwrap_or(service)
+                .show(&args.id, opts.exact_id)?;
             let spec = if args.with_spec {
                 Some(service.spec_content(SpecContentInput {
                     id: args.id.clone(),
+                    version: None,
                     exact_id: opts.exact_id,
                 })?)
             } else {
@@ -155,6 +245,24 @@ pub fn execute_show(service: &TasqueService, args: ShowArgs, opts: GlobalOpts) -
 }
 
 pub fn execute_stale(service: &TasqueService, args: StaleArgs, opts: GlobalOpts) -> i32 {
+    if let Some(StaleAction::Enforce) = args.action {
+        return run_action(
+            "tsq stale enforce",
+            opts,
+            || service.stale_enforce(),
+            |data| data.clone(),
+            |data| {
+                println!(
+                    "stale={} labeled={} deferred={} cutoff={}",
+                    data.stale.len(),
+                    data.labeled.len(),
+                    data.deferred.len(),
+                    data.cutoff
+                );
+                Ok(())
+            },
+        );
+    }
     run_action(
         "tsq stale",
         opts,
@@ -172,11 +280,51 @@ pub fn execute_stale(service: &TasqueService, args: StaleArgs, opts: GlobalOpts)
                 status,
                 assignee: as_optional_string(args.assignee.as_deref()),
                 limit,
+                apply_label: as_optional_string(args.apply_label.as_deref()),
             })
         },
         |data| data.clone(),
         |data| {
             print_task_list(&data.tasks);
+            if !data.labeled.is_empty() {
+                println!("labeled={}", data.labeled.join(","));
+            }
+            Ok(())
+        },
+    )
+}
+
+pub fn execute_escalations(service: &TasqueService, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq escalations",
+        opts,
+        || service.escalations(),
+        |data| data.clone(),
+        |data| {
+            if !data.enabled {
+                println!(
+                    "{}",
+                    style::muted(
+                        "escalation policy is not configured (config.json \"escalation\")"
+                    )
+                );
+                return Ok(());
+            }
+            if data.escalations.is_empty() {
+                println!("{}", style::muted("no escalated tasks"));
+                return Ok(());
+            }
+            for entry in &data.escalations {
+                println!(
+                    "{} p{}→p{} age={}d {} {}",
+                    style::task_id(&entry.id),
+                    entry.priority,
+                    entry.effective_priority,
+                    entry.age_days,
+                    entry.status.as_str(),
+                    entry.title
+                );
+            }
             Ok(())
         },
     )
@@ -215,24 +363,35 @@ pub fn execute_edit(service: &TasqueService, args: EditArgs, opts: GlobalOpts) -
                     1,
                 ));
             }
+            let description = if args.edit {
+                let current = service.show(&args.id, opts.exact_id)?.task.description;
+                Some(compose_in_editor(current.as_deref().unwrap_or_default())?)
+            } else {
+                as_optional_string(args.description.as_deref())
+            };
             service.update(UpdateInput {
                 id: args.id.clone(),
                 title: as_optional_string(args.title.as_deref()),
-                description: as_optional_string(args.description.as_deref()),
+                description,
                 clear_description,
                 external_ref: as_optional_string(args.external_ref.as_deref()),
                 discovered_from: as_optional_string(args.discovered_from.as_deref()),
                 clear_discovered_from,
                 clear_external_ref,
-                status: None,
+                status: args.status.as_deref().map(normalize_status).transpose()?,
                 priority: args
                     .priority
                     .as_deref()
                     .map(parse_priority_value)
                     .transpose()?,
                 exact_id: opts.exact_id,
-                planning_state: None,
+                planning_state: args
+                    .planning
+                    .as_deref()
+                    .map(parse_planning_state)
+                    .transpose()?,
                 assignee: None,
+                blocked_on: None,
             })
         },
         |task| serde_json::json!({ "task": task }),
@@ -314,6 +473,76 @@ pub fn execute_supersede(service: &TasqueService, args: SupersedeArgs, opts: Glo
     )
 }
 
+pub fn execute_clone(service: &TasqueService, args: CloneArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq clone",
+        opts,
+        || {
+            service.clone_task(CloneInput {
+                id: args.id.clone(),
+                into: args.into.clone(),
+                with_children: args.with_children,
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| data.clone(),
+        |data| {
+            println!("cloned {} into {} task(s)", data.source, data.tasks.len());
+            for task in &data.tasks {
+                print_task(task);
+            }
+            Ok(())
+        },
+    )
+}
+
+pub fn execute_convert(service: &TasqueService, args: ConvertArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq convert",
+        opts,
+        || {
+            service.convert(ConvertInput {
+                id: args.id.clone(),
+                kind: parse_kind(&args.kind)?,
+                exact_id: opts.exact_id,
+            })
+        },
+        |task| serde_json::json!({ "task": task }),
+        |task| {
+            print_task(task);
+            Ok(())
+        },
+    )
+}
+
+pub fn execute_split(service: &TasqueService, args: SplitArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq split",
+        opts,
+        || {
+            service.split(SplitInput {
+                id: args.id.clone(),
+                parts: args.into.clone(),
+                siblings: args.siblings,
+                close: args.close,
+                reason: args.reason.clone(),
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| data.clone(),
+        |data| {
+            println!("split {} into {} part(s)", data.source.id, data.parts.len());
+            for part in &data.parts {
+                print_task(part);
+            }
+            if let Some(with) = data.source.superseded_by.as_ref() {
+                println!("closed {} (superseded by {})", data.source.id, with);
+            }
+            Ok(())
+        },
+    )
+}
+
 pub fn execute_merge(service: &TasqueService, args: MergeArgs, opts: GlobalOpts) -> i32 {
     run_action(
         "tsq merge",
@@ -324,8 +553,9 @@ pub fn execute_merge(service: &TasqueService, args: MergeArgs, opts: GlobalOpts)
                 into: args.into.clone(),
                 reason: args.reason.clone(),
                 force: args.force,
-                dry_run: args.dry_run,
+                dry_run: opts.dry_run,
                 exact_id: opts.exact_id,
+                strategy: parse_merge_strategy(&args.strategy)?,
             })
         },
         |data| data.clone(),
@@ -357,6 +587,27 @@ pub fn execute_claim(service: &TasqueService, args: ClaimArgs, opts: GlobalOpts)
     )
 }
 
+pub fn execute_handoff(service: &TasqueService, args: HandoffArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq handoff",
+        opts,
+        || {
+            service.handoff(HandoffInput {
+                id: args.id.clone(),
+                to: args.to.clone(),
+                note: as_optional_string(args.note.as_deref()),
+                exact_id: opts.exact_id,
+            })
+        },
+        |data| serde_json::to_value(data).unwrap_or_default(),
+        |data| {
+            print_task(&data.task);
+            println!("{}", style::muted(&data.note.text));
+            Ok(())
+        },
+    )
+}
+
 pub fn execute_assign(service: &TasqueService, args: AssignArgs, opts: GlobalOpts) -> i32 {
     run_action(
         "tsq assign",
@@ -376,6 +627,7 @@ pub fn execute_assign(service: &TasqueService, args: AssignArgs, opts: GlobalOpt
                 exact_id: opts.exact_id,
                 planning_state: None,
                 assignee: as_optional_string(Some(&args.assignee)),
+                blocked_on: None,
             })
         },
         |task| serde_json::json!({ "task": task }),
@@ -411,6 +663,63 @@ pub fn execute_set_status(
                 exact_id: opts.exact_id,
                 planning_state: None,
                 assignee: None,
+                blocked_on: None,
+            })
+        },
+        |task| serde_json::json!({ "task": task }),
+        |task| {
+            print_task(task);
+            Ok(())
+        },
+    )
+}
+
+pub fn execute_blocked(service: &TasqueService, args: BlockedArgs, opts: GlobalOpts) -> i32 {
+    if args.external {
+        return run_action(
+            "tsq blocked",
+            opts,
+            || service.externally_blocked(),
+            |tasks| serde_json::json!({ "tasks": tasks }),
+            |tasks| {
+                if tasks.is_empty() {
+                    println!("{}", style::muted("no externally blocked tasks"));
+                    return Ok(());
+                }
+                for task in tasks {
+                    println!(
+                        "{} {} {}",
+                        style::task_id(&task.id),
+                        task.title,
+                        style::muted(&format!(
+                            "blocked_on={}",
+                            task.blocked_on.as_deref().unwrap_or_default()
+                        ))
+                    );
+                }
+                Ok(())
+            },
+        );
+    }
+    run_action(
+        "tsq blocked",
+        opts,
+        || {
+            service.update(UpdateInput {
+                id: args.id.clone().unwrap_or_default(),
+                title: None,
+                description: None,
+                clear_description: false,
+                external_ref: None,
+                discovered_from: None,
+                clear_discovered_from: false,
+                clear_external_ref: false,
+                status: Some(crate::types::TaskStatus::Blocked),
+                priority: None,
+                exact_id: opts.exact_id,
+                planning_state: None,
+                assignee: None,
+                blocked_on: as_optional_string(args.blocked_on.as_deref()),
             })
         },
         |task| serde_json::json!({ "task": task }),
@@ -446,6 +755,7 @@ pub fn execute_set_planning(
                 exact_id: opts.exact_id,
                 planning_state: Some(planning_state),
                 assignee: None,
+                blocked_on: None,
             })
         },
         |task| serde_json::json!({ "task": task }),
@@ -1,11 +1,13 @@
 use crate::app::service::TasqueService;
 use crate::app::service_types::{CreateBatchInput, CreateBatchItem, CreateInput};
 use crate::cli::action::{GlobalOpts, run_action};
+use crate::cli::editor::compose_in_editor;
 use crate::cli::parsers::{
     as_optional_string, parse_kind, parse_priority_value, validate_explicit_id,
 };
 use crate::cli::render::print_task;
 use crate::errors::TsqError;
+use crate::store::git;
 use clap::Args;
 use std::fs;
 
@@ -21,6 +23,9 @@ struct ParsedTaskBullet {
   tsq create \"Investigate flaky sync test\"
   tsq create \"Add release checklist\" --kind feature --priority 1 --planned
   tsq create --from-file tasks.md
+  tsq create \"Write migration plan\" --edit
+  tsq create --from-branch
+  tsq create --from-commit HEAD~1 --kind feature
 
 tasks.md format:
   - Parent task
@@ -41,6 +46,12 @@ pub struct CreateArgs {
     pub description: Option<String>,
     #[arg(long = "external-ref")]
     pub external_ref: Option<String>,
+    /// Title from the current git branch name; external_ref `branch:<name>`
+    #[arg(long = "from-branch", default_value_t = false, conflicts_with_all = ["from_file", "from_commit", "external_ref"])]
+    pub from_branch: bool,
+    /// Title from the commit subject (default HEAD); external_ref `commit:<sha>`
+    #[arg(long = "from-commit", num_args = 0..=1, default_missing_value = "HEAD", conflicts_with_all = ["from_file", "external_ref"])]
+    pub from_commit: Option<String>,
     #[arg(long = "discovered-from")]
     pub discovered_from: Option<String>,
     #[arg(long = "planned", default_value_t = false)]
@@ -51,6 +62,9 @@ pub struct CreateArgs {
     pub explicit_id: Option<String>,
     #[arg(long = "body-file")]
     pub body_file: Option<String>,
+    /// Compose the description in $VISUAL/$EDITOR, seeded with --description if given
+    #[arg(long, default_value_t = false, conflicts_with_all = ["body_file", "from_file"])]
+    pub edit: bool,
     #[arg(long, default_value_t = false)]
     pub ensure: bool,
     #[arg(long, default_value_t = false)]
@@ -115,12 +129,25 @@ pub fn execute_create(service: &TasqueService, args: CreateArgs, opts: GlobalOpt
                 ));
             }
 
+            let git_origin = git_origin(&args)?;
+            if git_origin.is_some() && args.titles.len() > 1 {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "--from-branch and --from-commit create exactly one task",
+                    1,
+                ));
+            }
+
             let parsed_file_tasks = if let Some(path) = args.from_file.as_deref() {
                 Some(parse_task_bullets(path)?)
             } else {
                 None
             };
-            let positional_titles = if parsed_file_tasks.is_none() {
+            let positional_titles = if let Some(origin) = git_origin.as_ref()
+                && args.titles.is_empty()
+            {
+                vec![origin.title.clone()]
+            } else if parsed_file_tasks.is_none() {
                 args.titles
                     .iter()
                     .map(|value| {
@@ -145,11 +172,12 @@ pub fn execute_create(service: &TasqueService, args: CreateArgs, opts: GlobalOpt
             }
             let single_only = as_optional_string(args.description.as_deref()).is_some()
                 || args.body_file.is_some()
-                || args.explicit_id.is_some();
+                || args.explicit_id.is_some()
+                || args.edit;
             if single_only && create_count > 1 {
                 return Err(TsqError::new(
                     "VALIDATION_ERROR",
-                    "--description, --body-file, and --id require exactly one task title",
+                    "--description, --body-file, --edit, and --id require exactly one task title",
                     1,
                 ));
             }
@@ -188,8 +216,17 @@ pub fn execute_create(service: &TasqueService, args: CreateArgs, opts: GlobalOpt
                 .as_deref()
                 .map(validate_explicit_id)
                 .transpose()?;
-            let description = as_optional_string(args.description.as_deref());
-            let external_ref = as_optional_string(args.external_ref.as_deref());
+            let description = if args.edit {
+                Some(compose_in_editor(
+                    args.description.as_deref().unwrap_or_default(),
+                )?)
+            } else {
+                as_optional_string(args.description.as_deref())
+            };
+            let external_ref = match git_origin {
+                Some(origin) => Some(origin.external_ref),
+                None => as_optional_string(args.external_ref.as_deref()),
+            };
             let discovered_from = as_optional_string(args.discovered_from.as_deref());
 
             // Single create: keep existing service.create path.
@@ -269,6 +306,64 @@ pub fn execute_create(service: &TasqueService, args: CreateArgs, opts: GlobalOpt
     )
 }
 
+/// Title and external_ref derived from `--from-branch`/`--from-commit`.
+struct GitOrigin {
+    title: String,
+    external_ref: String,
+}
+
+/// Reads the branch or commit from the git checkout in the working directory,
+/// which is where the work already started (not the sync worktree).
+fn git_origin(args: &CreateArgs) -> Result<Option<GitOrigin>, TsqError> {
+    if !args.from_branch && args.from_commit.is_none() {
+        return Ok(None);
+    }
+    let cwd = std::env::current_dir().map_err(|error| {
+        TsqError::new("IO_ERROR", "failed reading current directory", 2)
+            .with_details(serde_json::json!({"message": error.to_string()}))
+    })?;
+    if !git::is_git_repo(&cwd) {
+        return Err(TsqError::new(
+            "GIT_NOT_AVAILABLE",
+            "--from-branch and --from-commit need a git repository",
+            2,
+        ));
+    }
+    if let Some(rev) = args.from_commit.as_deref() {
+        let (sha, subject) = git::commit_summary(&cwd, rev)?;
+        return Ok(Some(GitOrigin {
+            title: subject,
+            external_ref: format!("commit:{}", sha),
+        }));
+    }
+    let branch = git::current_branch(&cwd)?.ok_or_else(|| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            "HEAD is detached; use --from-commit instead of --from-branch",
+            1,
+        )
+    })?;
+    Ok(Some(GitOrigin {
+        title: title_from_branch(&branch),
+        external_ref: format!("branch:{}", branch),
+    }))
+}
+
+/// `feature/add-login_page` -> `Add login page`.
+fn title_from_branch(branch: &str) -> String {
+    let last = branch.rsplit('/').next().unwrap_or(branch);
+    let words = last
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => branch.to_string(),
+    }
+}
+
 fn parse_task_bullets(path: &str) -> Result<Vec<ParsedTaskBullet>, TsqError> {
     let content = fs::read_to_string(path).map_err(|error| {
         TsqError::new(
@@ -1,18 +1,39 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::{ListFilter, SearchInput, SimilarInput};
+use crate::app::service_types::{ListFilter, RollupTask, SearchInput, SimilarInput, SubtreeInput};
+use crate::app::service_utils::compare_tasks;
 use crate::cli::action::{GlobalOpts, run_action};
-use crate::cli::parsers::{ListParseInput, apply_tree_defaults, parse_lane, parse_list_filter};
-use crate::cli::render::{print_task, print_task_list, print_task_tree};
+use crate::cli::parsers::{
+    ListParseInput, apply_tree_defaults, parse_as_of, parse_list_filter, parse_non_negative_int,
+    parse_positive_int,
+};
+use crate::cli::render::{
+    print_count_result, print_rollup_list, print_subtree, print_task, print_task_list,
+    print_task_tree,
+};
+use crate::cli::style;
+use crate::domain::aggregate::{CountResult, count_by, parse_count_field};
+use crate::domain::query::split_count_clause;
 use crate::errors::TsqError;
+use crate::output::{
+    compact_task_list, compact_task_notes, compact_task_tree, ok_envelope, with_label_colors,
+};
+use crate::types::Task;
 use clap::{Args, Subcommand};
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::HashSet;
+use std::io::Write;
 
 #[derive(Debug, Args)]
 #[command(after_help = "Examples:
   tsq find ready --lane planning
   tsq find ready --lane coding --label cli
   tsq find open --planning needs_planning --tree
-  tsq find search \"sync branch\" --full")]
+  tsq find in-progress --spec missing
+  tsq find status in_review
+  tsq find open --count-by label
+  tsq find search \"sync branch\" --full
+  tsq find search \"priority<=1 | count by assignee\"")]
 pub struct FindArgs {
     #[command(subcommand)]
     pub command: FindCommand,
@@ -27,14 +48,24 @@ pub enum FindCommand {
     Deferred(FindListArgs),
     Done(FindListArgs),
     Canceled(FindListArgs),
+    /// Tasks in any status, including one from `status_workflow` in config
+    Status(FindStatusArgs),
     Search(FindSearchArgs),
     Similar(FindSimilarArgs),
 }
 
 #[derive(Debug, Args)]
 pub struct FindReadyArgs {
+    /// `planning`, `coding`, or a lane defined under `lanes` in config
     #[arg(long)]
     pub lane: Option<String>,
+    /// Keep running and report each task that becomes ready once its last
+    /// blocker closes
+    #[arg(long, default_value_t = false, conflicts_with_all = ["tree", "rollup", "workspace", "as_of", "count_by"])]
+    pub watch: bool,
+    /// Seconds between checks with --watch (default 2)
+    #[arg(long, requires = "watch")]
+    pub interval: Option<String>,
     #[command(flatten)]
     pub filter: FindListArgs,
 }
@@ -61,25 +92,80 @@ pub struct FindListArgs {
     pub updated_after: Option<String>,
     #[arg(long = "closed-after")]
     pub closed_after: Option<String>,
+    /// Priority as `1`, `<=1`, or an inclusive range such as `0..1`
+    #[arg(long)]
+    pub priority: Option<String>,
+    /// Estimate from `estimate:<n>` labels: `none`, `>5`, or a range such as `2..5`
+    #[arg(long)]
+    pub estimate: Option<String>,
     #[arg(long = "id", value_delimiter = ',', action = clap::ArgAction::Append)]
     pub ids: Vec<String>,
     #[arg(long, default_value_t = false)]
     pub tree: bool,
     #[arg(long, default_value_t = false)]
     pub full: bool,
+    /// Annotate epics and features with done/total child counts
+    #[arg(long, default_value_t = false, conflicts_with = "tree")]
+    pub rollup: bool,
     #[arg(long = "planning")]
     pub planning: Option<String>,
     #[arg(long = "dep-type")]
     pub dep_type: Option<String>,
     #[arg(long = "dep-direction")]
     pub dep_direction: Option<String>,
+    /// `attached`, `missing`, or `drifted` (attached, but the file no longer
+    /// matches its fingerprint)
+    #[arg(long)]
+    pub spec: Option<String>,
+    /// How `--spec drifted` checks files: `hash` (default) or `mtime`, which
+    /// only hashes files modified since the spec was attached
+    #[arg(long = "drift-check")]
+    pub drift_check: Option<String>,
+    /// Merge results from every sync shard (`sync_branch` plus `sync_routes`)
+    #[arg(long, default_value_t = false, conflicts_with = "tree")]
+    pub workspace: bool,
+    /// Query the backlog as it stood at this date or ISO timestamp
+    #[arg(long = "as-of")]
+    pub as_of: Option<String>,
+    /// Include each task's full notes array in JSON output (default: note_count)
+    #[arg(long = "with-notes", default_value_t = false)]
+    pub with_notes: bool,
+    /// Print task counts grouped by status|label|kind|assignee|priority|planning
+    #[arg(long = "count-by", conflicts_with_all = ["tree", "rollup"])]
+    pub count_by: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct FindStatusArgs {
+    pub status: String,
+    #[command(flatten)]
+    pub filter: FindListArgs,
 }
 
 #[derive(Debug, Args)]
 pub struct FindSearchArgs {
+    /// Search terms, optionally ending in `| count by <field>`
     pub query: String,
     #[arg(long, default_value_t = false)]
     pub full: bool,
+    /// Print match counts grouped by status|label|kind|assignee|priority|planning
+    #[arg(long = "count-by", conflicts_with = "full")]
+    pub count_by: Option<String>,
+    /// Include each task's full notes array in JSON output (default: note_count)
+    #[arg(long = "with-notes", default_value_t = false)]
+    pub with_notes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TreeArgs {
+    /// Epic, feature, or task to root the tree at
+    pub id: String,
+    /// Levels of descendants to show below the root (default: all)
+    #[arg(long)]
+    pub depth: Option<String>,
+    /// Include each task's full notes array in JSON output (default: note_count)
+    #[arg(long = "with-notes", default_value_t = false)]
+    pub with_notes: bool,
 }
 
 #[derive(Debug, Args)]
@@ -112,25 +198,39 @@ pub fn execute_find(service: &TasqueService, args: FindArgs, opts: GlobalOpts) -
         FindCommand::Canceled(args) => {
             execute_find_list(service, args, Some("canceled"), "tsq find canceled", opts)
         }
+        FindCommand::Status(args) => execute_find_list(
+            service,
+            args.filter,
+            Some(&args.status),
+            "tsq find status",
+            opts,
+        ),
         FindCommand::Search(args) => execute_find_search(service, args, opts),
         FindCommand::Similar(args) => execute_find_similar(service, args, opts),
     }
 }
 
 fn execute_find_ready(service: &TasqueService, args: FindReadyArgs, opts: GlobalOpts) -> i32 {
+    if args.watch {
+        return watch_ready(service, &args, opts);
+    }
+    let scoped = match parse_as_of(args.filter.as_of.as_deref()) {
+        Ok(as_of) => as_of.map(|as_of| service.as_of(as_of)),
+        Err(error) => return fail_early("tsq find ready", opts, error),
+    };
+    let service = scoped.as_ref().unwrap_or(service);
     if args.filter.tree {
         return run_action(
             "tsq find ready",
             opts,
             || {
-                let lane = args.lane.as_deref().map(parse_lane).transpose()?;
-                let ready = service.ready(lane)?;
+                let ready = service.ready(args.lane.as_deref())?;
                 let ready_ids = ready.into_iter().map(|task| task.id).collect::<Vec<_>>();
                 let filter = parse_find_list_filter(&args.filter, None)?;
                 let filter = filter_to_ready_ids(filter, ready_ids);
                 service.list_tree(&apply_tree_defaults(filter, args.filter.full))
             },
-            |tree| serde_json::json!({ "tree": tree }),
+            |tree| tree_json(tree, args.filter.with_notes),
             |tree| {
                 print_task_tree(tree);
                 Ok(())
@@ -138,15 +238,30 @@ fn execute_find_ready(service: &TasqueService, args: FindReadyArgs, opts: Global
         );
     }
 
+    if let Some(raw) = args.filter.count_by.as_deref() {
+        return run_count("tsq find ready", opts, || {
+            let lane = args.lane.as_deref();
+            let field = parse_count_field(raw)?;
+            let filter = parse_find_list_filter(&args.filter, None)?;
+            let rows = across_workspace(service, args.filter.workspace, |shard| {
+                let ready = shard.ready(lane)?;
+                let ready_ids = ready.into_iter().map(|task| task.id).collect::<Vec<_>>();
+                with_rollup(
+                    shard,
+                    shard.list(&filter_to_ready_ids(filter.clone(), ready_ids))?,
+                    false,
+                )
+            })?;
+            Ok(count_by(rows.iter().map(|row| &row.task), field))
+        });
+    }
+
     run_action(
         "tsq find ready",
         opts,
         || {
-            let lane = args.lane.as_deref().map(parse_lane).transpose()?;
-            let ready = service.ready(lane)?;
-            let ready_ids = ready.into_iter().map(|task| task.id).collect::<Vec<_>>();
+            let lane = args.lane.as_deref();
             let filter = parse_find_list_filter(&args.filter, None)?;
-            let filter = filter_to_ready_ids(filter, ready_ids);
             if args.filter.full {
                 return Err(TsqError::new(
                     "VALIDATION_ERROR",
@@ -154,11 +269,16 @@ fn execute_find_ready(service: &TasqueService, args: FindReadyArgs, opts: Global
                     1,
                 ));
             }
-            service.list(&filter)
+            across_workspace(service, args.filter.workspace, |shard| {
+                let ready = shard.ready(lane)?;
+                let ready_ids = ready.into_iter().map(|task| task.id).collect::<Vec<_>>();
+                let filter = filter_to_ready_ids(filter.clone(), ready_ids);
+                with_rollup(shard, shard.list(&filter)?, args.filter.rollup)
+            })
         },
-        |tasks| serde_json::json!({ "tasks": tasks }),
-        |tasks| {
-            print_task_list(tasks);
+        |rows| tasks_json(rows, args.filter.with_notes),
+        |rows| {
+            print_rollup_list(rows, args.filter.rollup);
             Ok(())
         },
     )
@@ -173,23 +293,30 @@ fn execute_find_list(
 ) -> i32 {
     let filter = match parse_find_list_filter(&args, status) {
         Ok(filter) => filter,
-        Err(error) => {
-            return run_action(
-                command_line,
-                opts,
-                || -> Result<(), TsqError> { Err(error) },
-                |_: &()| serde_json::json!({}),
-                |_: &()| Ok(()),
-            );
-        }
+        Err(error) => return fail_early(command_line, opts, error),
     };
+    let scoped = match parse_as_of(args.as_of.as_deref()) {
+        Ok(as_of) => as_of.map(|as_of| service.as_of(as_of)),
+        Err(error) => return fail_early(command_line, opts, error),
+    };
+    let service = scoped.as_ref().unwrap_or(service);
+
+    if let Some(raw) = args.count_by.as_deref() {
+        return run_count(command_line, opts, || {
+            let field = parse_count_field(raw)?;
+            let rows = across_workspace(service, args.workspace, |shard| {
+                with_rollup(shard, shard.list(&filter)?, false)
+            })?;
+            Ok(count_by(rows.iter().map(|row| &row.task), field))
+        });
+    }
 
     if args.tree {
         run_action(
             command_line,
             opts,
             || service.list_tree(&apply_tree_defaults(filter.clone(), args.full)),
-            |tree| serde_json::json!({ "tree": tree }),
+            |tree| tree_json(tree, args.with_notes),
             |tree| {
                 print_task_tree(tree);
                 Ok(())
@@ -207,18 +334,213 @@ fn execute_find_list(
                         1,
                     ));
                 }
-                service.list(&filter)
+                across_workspace(service, args.workspace, |shard| {
+                    with_rollup(shard, shard.list(&filter)?, args.rollup)
+                })
             },
-            |tasks| serde_json::json!({ "tasks": tasks }),
-            |tasks| {
-                print_task_list(tasks);
+            |rows| tasks_json(rows, args.with_notes),
+            |rows| {
+                print_rollup_list(rows, args.rollup);
                 Ok(())
             },
         )
     }
 }
 
+/// `tsq find ready --watch`: prints the ready set once, then one line per
+/// task that joins it after all of its blockers are done. Tasks that become
+/// ready for other reasons (creation, reopening) are not reported.
+fn watch_ready(service: &TasqueService, args: &FindReadyArgs, opts: GlobalOpts) -> i32 {
+    let command_line = "tsq find ready";
+    let interval =
+        match parse_positive_int(args.interval.as_deref().unwrap_or("2"), "interval", 1, 60) {
+            Ok(interval) => interval,
+            Err(error) => return fail_early(command_line, opts, error),
+        };
+    let filter = match parse_find_list_filter(&args.filter, None) {
+        Ok(filter) => filter,
+        Err(error) => return fail_early(command_line, opts, error),
+    };
+    let ready_now = || -> Result<Vec<Task>, TsqError> {
+        let ready = service.ready(args.lane.as_deref())?;
+        let ready_ids = ready.into_iter().map(|task| task.id).collect::<Vec<_>>();
+        service.list(&filter_to_ready_ids(filter.clone(), ready_ids))
+    };
+    let initial = match ready_now() {
+        Ok(tasks) => tasks,
+        Err(error) => return fail_early(command_line, opts, error),
+    };
+    let mut known: HashSet<String> = initial.iter().map(|task| task.id.clone()).collect();
+    if opts.json() {
+        let ids = initial.iter().map(|task| &task.id).collect::<Vec<_>>();
+        emit_json_line(command_line, serde_json::json!({ "ready": ids }));
+    } else {
+        println!(
+            "{}",
+            style::muted(&format!(
+                "watching for newly ready tasks ({} ready now; Ctrl-C to stop)",
+                initial.len()
+            ))
+        );
+    }
+    let _ = std::io::stdout().flush();
+
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(interval as u64));
+        let tasks = match ready_now() {
+            Ok(tasks) => tasks,
+            Err(error) => {
+                eprintln!("{}: {}", error.code, error.message);
+                continue;
+            }
+        };
+        let current: HashSet<String> = tasks.iter().map(|task| task.id.clone()).collect();
+        for task in tasks.iter().filter(|task| !known.contains(&task.id)) {
+            let Ok(shown) = service.show(&task.id, true) else {
+                continue;
+            };
+            if shown.blockers.is_empty() {
+                continue;
+            }
+            if opts.json() {
+                let mut payload = serde_json::json!({
+                    "task": task,
+                    "unblocked_by": shown.blockers,
+                });
+                compact_task_notes(&mut payload["task"]);
+                emit_json_line(command_line, payload);
+            } else {
+                println!(
+                    "ready {} {} (unblocked by {})",
+                    task.id,
+                    task.title,
+                    shown.blockers.join(", ")
+                );
+            }
+        }
+        let _ = std::io::stdout().flush();
+        known = current;
+    }
+}
+
+fn emit_json_line(command_line: &str, data: Value) {
+    let envelope = ok_envelope(command_line, data);
+    println!(
+        "{}",
+        serde_json::to_string(&envelope).unwrap_or_else(|_| "{}".to_string())
+    );
+}
+
+fn tasks_json(rows: &impl Serialize, with_notes: bool) -> Value {
+    let mut tasks = serde_json::to_value(rows).unwrap_or_default();
+    if !with_notes {
+        compact_task_list(&mut tasks);
+    }
+    let mut payload = serde_json::json!({ "tasks": tasks });
+    with_label_colors(&mut payload, style::label_colors());
+    payload
+}
+
+fn tree_json(tree: &impl Serialize, with_notes: bool) -> Value {
+    let mut tree = serde_json::to_value(tree).unwrap_or_default();
+    if !with_notes {
+        compact_task_tree(&mut tree);
+    }
+    let mut payload = serde_json::json!({ "tree": tree });
+    with_label_colors(&mut payload, style::label_colors());
+    payload
+}
+
+fn run_count(
+    command_line: &str,
+    opts: GlobalOpts,
+    count: impl FnOnce() -> Result<CountResult, TsqError>,
+) -> i32 {
+    run_action(
+        command_line,
+        opts,
+        count,
+        |result| serde_json::to_value(result).unwrap_or_default(),
+        |result| {
+            print_count_result(result);
+            Ok(())
+        },
+    )
+}
+
+fn fail_early(command_line: &str, opts: GlobalOpts, error: TsqError) -> i32 {
+    run_action(
+        command_line,
+        opts,
+        || -> Result<(), TsqError> { Err(error) },
+        |_: &()| serde_json::json!({}),
+        |_: &()| Ok(()),
+    )
+}
+
+/// Runs `rows` against the current shard, or against every workspace shard
+/// when `workspace` is set, merging the results in list order.
+fn across_workspace(
+    service: &TasqueService,
+    workspace: bool,
+    rows: impl Fn(&TasqueService) -> Result<Vec<RollupTask>, TsqError>,
+) -> Result<Vec<RollupTask>, TsqError> {
+    if !workspace {
+        return rows(service);
+    }
+    let mut merged = Vec::new();
+    for (branch, shard) in service.workspace_services()? {
+        merged.extend(rows(&shard)?.into_iter().map(|row| RollupTask {
+            workspace: Some(branch.clone()),
+            ..row
+        }));
+    }
+    merged.sort_by(|a, b| compare_tasks(&a.task, &b.task));
+    Ok(merged)
+}
+
+fn with_rollup(
+    service: &TasqueService,
+    tasks: Vec<Task>,
+    rollup: bool,
+) -> Result<Vec<RollupTask>, TsqError> {
+    if rollup {
+        return service.rollup(tasks);
+    }
+    Ok(tasks
+        .into_iter()
+        .map(|task| RollupTask {
+            task,
+            rollup: None,
+            workspace: None,
+        })
+        .collect())
+}
+
 pub fn execute_find_search(service: &TasqueService, args: FindSearchArgs, opts: GlobalOpts) -> i32 {
+    let (query, piped) = match split_count_clause(&args.query) {
+        Ok(split) => split,
+        Err(error) => return fail_early("tsq find search", opts, error),
+    };
+    if piped.is_some() || args.count_by.is_some() {
+        return run_count("tsq find search", opts, || {
+            let field = match (piped, args.count_by.as_deref()) {
+                (Some(_), Some(_)) => {
+                    return Err(TsqError::new(
+                        "VALIDATION_ERROR",
+                        "use either --count-by or `| count by`, not both",
+                        1,
+                    ));
+                }
+                (Some(field), None) => field,
+                (None, raw) => parse_count_field(raw.unwrap_or_default())?,
+            };
+            let tasks = service.search(&SearchInput {
+                query: query.trim().to_string(),
+            })?;
+            Ok(count_by(&tasks, field))
+        });
+    }
     run_action(
         "tsq find search",
         opts,
@@ -227,7 +549,7 @@ pub fn execute_find_search(service: &TasqueService, args: FindSearchArgs, opts:
                 query: args.query.clone(),
             })
         },
-        |tasks| serde_json::json!({ "tasks": tasks }),
+        |tasks| tasks_json(tasks, args.with_notes),
         |tasks| {
             if args.full {
                 for task in tasks {
@@ -270,6 +592,38 @@ pub fn execute_find_similar(
     )
 }
 
+pub fn execute_tree(service: &TasqueService, args: TreeArgs, opts: GlobalOpts) -> i32 {
+    run_action(
+        "tsq tree",
+        opts,
+        || {
+            let depth = args
+                .depth
+                .as_deref()
+                .map(|raw| parse_non_negative_int(raw, "depth"))
+                .transpose()?;
+            service.subtree(SubtreeInput {
+                id: args.id.clone(),
+                exact_id: opts.exact_id,
+                depth: depth.map(|depth| depth as usize),
+            })
+        },
+        |result| {
+            let mut payload = serde_json::to_value(result).unwrap_or_default();
+            if !args.with_notes {
+                compact_task_notes(&mut payload["tree"]["task"]);
+                compact_task_tree(&mut payload["tree"]["children"]);
+            }
+            with_label_colors(&mut payload, style::label_colors());
+            payload
+        },
+        |result| {
+            print_subtree(result);
+            Ok(())
+        },
+    )
+}
+
 fn parse_find_list_filter(
     args: &FindListArgs,
     status: Option<&str>,
@@ -291,6 +645,10 @@ fn parse_find_list_filter(
         planning: args.planning.clone(),
         dep_type: args.dep_type.clone(),
         dep_direction: args.dep_direction.clone(),
+        spec: args.spec.clone(),
+        drift_check: args.drift_check.clone(),
+        priority: args.priority.clone(),
+        estimate: args.estimate.clone(),
     })
 }
 
@@ -1,56 +1,152 @@
+use crate::app::runtime::normalize_status;
 use crate::app::service::TasqueService;
-use crate::app::service_types::LifecycleStatusInput;
-use crate::cli::action::{GlobalOpts, run_action};
-use crate::cli::render::print_task;
+use crate::app::service_types::CriteriaResult;
+use crate::app::service_types::{LifecycleStatusInput, LifecycleStatusResult};
+use crate::cli::action::{GlobalOpts, run_action, set_dry_run_preview};
+use crate::cli::parsers::parse_date_or_timestamp;
+use crate::cli::render::{dep_type_to_string, print_task};
+use crate::domain::dep_tree::DependentEffect;
 use crate::errors::TsqError;
 use clap::Args;
 
 #[derive(Debug, Args)]
 #[command(after_help = "Examples:
   tsq defer tsq-abc12345 --note \"waiting on design\"
+  tsq defer tsq-abc12345 --until 2026-07-01
   tsq open tsq-abc12345")]
 pub struct NoteStatusArgs {
     pub id: String,
     #[arg(long)]
     pub note: Option<String>,
+    /// Snooze until a date (YYYY-MM-DD, UTC) or ISO timestamp; list/ready reopen it afterwards
+    #[arg(long)]
+    pub until: Option<String>,
 }
 
 #[derive(Debug, Args)]
 #[command(after_help = "Examples:
   tsq done tsq-abc12345 --note \"merged\"
+  tsq done tsq-abc12345 --reason \"shipped in v1.4\"
+  tsq done tsq-abc12345 --require-criteria
+  tsq done --filter 'label:spike status:open updated<2024-01-01' --reason \"stale spikes\"
+  tsq done --filter 'label:spike status:open updated<2024-01-01' --reason \"stale spikes\" --yes")]
+pub struct DoneArgs {
+    pub ids: Vec<String>,
+    /// Close every open task matching a search query instead of listed ids
+    #[arg(long, conflicts_with = "ids")]
+    pub filter: Option<String>,
+    /// Apply a --filter close; without it the matches are only previewed
+    #[arg(long, requires = "filter")]
+    pub yes: bool,
+    #[arg(long)]
+    pub note: Option<String>,
+    /// Record why the task was closed
+    #[arg(long)]
+    pub reason: Option<String>,
+    /// Fail instead of warning when acceptance criteria are still open
+    #[arg(long)]
+    pub require_criteria: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  tsq cancel tsq-abc12345 --note \"superseded\"
+  tsq cancel tsq-abc12345 --cascade-defer")]
+pub struct CancelArgs {
+    pub ids: Vec<String>,
+    #[arg(long)]
+    pub note: Option<String>,
+    /// Defer open dependents that were waiting on the canceled tasks
+    #[arg(long)]
+    pub cascade_defer: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
   tsq reopen tsq-abc12345 --note \"regression found\"
-  tsq cancel tsq-abc12345 --note \"superseded\"")]
-pub struct MultiStatusArgs {
+  tsq reopen tsq-abc12345 --from canceled --note \"canceled too early\"")]
+pub struct ReopenArgs {
     pub ids: Vec<String>,
     #[arg(long)]
     pub note: Option<String>,
+    /// Status the tasks are reopened from: `closed` (default) or `canceled`
+    #[arg(long)]
+    pub from: Option<String>,
 }
 
-pub fn execute_done(service: &TasqueService, args: MultiStatusArgs, opts: GlobalOpts) -> i32 {
+pub fn execute_done(service: &TasqueService, args: DoneArgs, opts: GlobalOpts) -> i32 {
+    // A filter can sweep up far more than intended, so it previews until --yes.
+    let unconfirmed = args.filter.is_some() && !args.yes;
+    if unconfirmed && !opts.dry_run {
+        let (preview, handle) = service.preview();
+        set_dry_run_preview(Some(handle));
+        let code = execute_done(
+            &preview,
+            args,
+            GlobalOpts {
+                dry_run: true,
+                ..opts
+            },
+        );
+        set_dry_run_preview(None);
+        return code;
+    }
     run_action(
         "tsq done",
         opts,
         || {
-            validate_multi_status_ids(&args.ids)?;
+            if args.filter.is_none() {
+                validate_multi_status_ids(&args.ids)?;
+            }
             service.set_lifecycle_status(LifecycleStatusInput {
                 ids: args.ids.clone(),
                 status: crate::types::TaskStatus::Closed,
                 note: args.note.clone(),
-                reason: None,
+                reason: args.reason.clone(),
+                require_criteria: args.require_criteria,
+                defer_until: None,
+                reopen_from: None,
+                filter: args.filter.clone(),
+                cascade_defer: false,
                 exact_id: opts.exact_id,
             })
         },
-        |data| serde_json::json!({ "tasks": data.tasks, "notes": data.notes }),
         |data| {
+            warn_open_criteria(&data.open_criteria);
+            warn_dependents("closing", data);
+            let mut value = with_dependents(
+                serde_json::json!({ "tasks": data.tasks, "notes": data.notes }),
+                data,
+            );
+            if !data.open_criteria.is_empty() {
+                value["open_criteria"] = serde_json::json!(data.open_criteria);
+            }
+            if args.filter.is_some() {
+                value["matched"] = serde_json::json!(data.tasks.len());
+                value["confirmed"] = serde_json::json!(!unconfirmed);
+            }
+            value
+        },
+        |data| {
+            warn_open_criteria(&data.open_criteria);
+            warn_dependents("closing", data);
             for task in &data.tasks {
                 print_task(task);
             }
+            if args.filter.is_some() && data.tasks.is_empty() {
+                println!("no open tasks match the filter");
+            } else if unconfirmed {
+                eprintln!(
+                    "{} task(s) match; re-run with --yes to close them",
+                    data.tasks.len()
+                );
+            }
             Ok(())
         },
     )
 }
 
-pub fn execute_reopen(service: &TasqueService, args: MultiStatusArgs, opts: GlobalOpts) -> i32 {
+pub fn execute_reopen(service: &TasqueService, args: ReopenArgs, opts: GlobalOpts) -> i32 {
     run_action(
         "tsq reopen",
         opts,
@@ -61,6 +157,11 @@ pub fn execute_reopen(service: &TasqueService, args: MultiStatusArgs, opts: Glob
                 status: crate::types::TaskStatus::Open,
                 note: args.note.clone(),
                 reason: None,
+                require_criteria: false,
+                defer_until: None,
+                reopen_from: args.from.as_deref().map(normalize_status).transpose()?,
+                filter: None,
+                cascade_defer: false,
                 exact_id: opts.exact_id,
             })
         },
@@ -79,11 +180,21 @@ pub fn execute_defer(service: &TasqueService, args: NoteStatusArgs, opts: Global
         "tsq defer",
         opts,
         || {
+            let defer_until = args
+                .until
+                .as_deref()
+                .map(|raw| parse_date_or_timestamp(raw, "until"))
+                .transpose()?;
             service.set_lifecycle_status(LifecycleStatusInput {
                 ids: vec![args.id.clone()],
                 status: crate::types::TaskStatus::Deferred,
                 note: args.note.clone(),
                 reason: None,
+                require_criteria: false,
+                defer_until,
+                reopen_from: None,
+                filter: None,
+                cascade_defer: false,
                 exact_id: opts.exact_id,
             })
         },
@@ -97,7 +208,7 @@ pub fn execute_defer(service: &TasqueService, args: NoteStatusArgs, opts: Global
     )
 }
 
-pub fn execute_cancel(service: &TasqueService, args: MultiStatusArgs, opts: GlobalOpts) -> i32 {
+pub fn execute_cancel(service: &TasqueService, args: CancelArgs, opts: GlobalOpts) -> i32 {
     run_action(
         "tsq cancel",
         opts,
@@ -108,11 +219,23 @@ pub fn execute_cancel(service: &TasqueService, args: MultiStatusArgs, opts: Glob
                 status: crate::types::TaskStatus::Canceled,
                 note: args.note.clone(),
                 reason: None,
+                require_criteria: false,
+                defer_until: None,
+                reopen_from: None,
+                filter: None,
+                cascade_defer: args.cascade_defer,
                 exact_id: opts.exact_id,
             })
         },
-        |data| serde_json::json!({ "tasks": data.tasks, "notes": data.notes }),
         |data| {
+            warn_dependents("canceling", data);
+            with_dependents(
+                serde_json::json!({ "tasks": data.tasks, "notes": data.notes }),
+                data,
+            )
+        },
+        |data| {
+            warn_dependents("canceling", data);
             for task in &data.tasks {
                 print_task(task);
             }
@@ -131,3 +254,47 @@ fn validate_multi_status_ids(ids: &[String]) -> Result<(), TsqError> {
     }
     Ok(())
 }
+
+fn warn_open_criteria(open_criteria: &[CriteriaResult]) {
+    for entry in open_criteria {
+        eprintln!(
+            "WARN: closed {} with {} of {} acceptance criteria still open",
+            entry.task_id,
+            entry.open,
+            entry.criteria.len()
+        );
+    }
+}
+
+fn warn_dependents(verb: &str, data: &LifecycleStatusResult) {
+    for impact in &data.dependents {
+        let effect = match impact.effect {
+            DependentEffect::Unblocked => "unblocks",
+            DependentEffect::Orphaned if data.deferred.contains(&impact.task_id) => {
+                "orphans and defers"
+            }
+            DependentEffect::Orphaned => "orphans",
+        };
+        eprintln!(
+            "WARN: {} {} {} {} ({})",
+            verb,
+            impact.blocker,
+            effect,
+            impact.task_id,
+            dep_type_to_string(impact.dep_type)
+        );
+    }
+}
+
+fn with_dependents(
+    mut value: serde_json::Value,
+    data: &LifecycleStatusResult,
+) -> serde_json::Value {
+    if !data.dependents.is_empty() {
+        value["dependents"] = serde_json::json!(data.dependents);
+    }
+    if !data.deferred.is_empty() {
+        value["deferred"] = serde_json::json!(data.deferred);
+    }
+    value
+}
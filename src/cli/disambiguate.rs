@@ -0,0 +1,99 @@
+use crate::errors::TsqError;
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+thread_local! {
+    static PICK_ARGS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    static RETRY_ARGS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Enables the pick list for the command about to run; `args` is its full
+/// argv, which is rewritten with the chosen id when the user picks one.
+pub fn set_pick_args(args: Option<Vec<String>>) {
+    PICK_ARGS.with(|current| *current.borrow_mut() = args);
+}
+
+/// The rewritten argv to re-run after a pick, if one was made.
+pub fn take_retry_args() -> Option<Vec<String>> {
+    RETRY_ARGS.with(|retry| retry.borrow_mut().take())
+}
+
+/// On `TASK_ID_AMBIGUOUS` for an input given as its own argument, lists the
+/// candidates on stderr and asks for one. Returns true when a retry was
+/// queued; false leaves the error to be reported as usual.
+pub fn try_pick(error: &TsqError) -> bool {
+    if error.code != "TASK_ID_AMBIGUOUS" {
+        return false;
+    }
+    let Some(args) = PICK_ARGS.with(|current| current.borrow().clone()) else {
+        return false;
+    };
+    let Some(details) = error.details.as_ref() else {
+        return false;
+    };
+    let Some(input) = details.get("input").and_then(|value| value.as_str()) else {
+        return false;
+    };
+    let Some(position) = args.iter().skip(1).position(|arg| arg == input) else {
+        return false;
+    };
+    let candidates: Vec<(&str, &str, &str)> = details
+        .get("candidates")
+        .and_then(|value| value.as_array())
+        .map(|candidates| {
+            candidates
+                .iter()
+                .filter_map(|candidate| {
+                    Some((
+                        candidate.get("id")?.as_str()?,
+                        candidate
+                            .get("title")
+                            .and_then(|value| value.as_str())
+                            .unwrap_or(""),
+                        candidate
+                            .get("status")
+                            .and_then(|value| value.as_str())
+                            .unwrap_or(""),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if candidates.is_empty() {
+        return false;
+    }
+
+    eprintln!("\"{}\" matches {} tasks:", input, candidates.len());
+    for (index, (id, title, status)) in candidates.iter().enumerate() {
+        eprintln!("  {}) {}  {}  [{}]", index + 1, id, title, status);
+    }
+    let Some(choice) = ask_choice(candidates.len()) else {
+        return false;
+    };
+
+    let mut retry = args;
+    retry[position + 1] = candidates[choice].0.to_string();
+    RETRY_ARGS.with(|slot| *slot.borrow_mut() = Some(retry));
+    true
+}
+
+/// Re-prompts until a number in `1..=count` is entered; empty input or `q`
+/// gives up.
+fn ask_choice(count: usize) -> Option<usize> {
+    loop {
+        eprint!("Pick a task [1-{}] (q=cancel) ", count);
+        io::stderr().flush().ok()?;
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer).ok()? == 0 {
+            return None;
+        }
+        let answer = answer.trim();
+        if answer.is_empty() || answer.eq_ignore_ascii_case("q") {
+            return None;
+        }
+        match answer.parse::<usize>() {
+            Ok(number) if (1..=count).contains(&number) => return Some(number - 1),
+            _ => eprintln!("Invalid choice."),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::errors::TsqError;
+use std::io::Write;
+use std::process::Command;
+
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Opens `$VISUAL`/`$EDITOR` on a scratch markdown file seeded with `template`
+/// and returns the saved content with trailing whitespace trimmed.
+pub fn compose_in_editor(template: &str) -> Result<String, TsqError> {
+    let editor = resolve_editor();
+    let mut parts = editor.split_whitespace();
+    let Some(program) = parts.next() else {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "editor command must not be empty",
+            1,
+        ));
+    };
+    let extra_args: Vec<&str> = parts.collect();
+
+    let mut file = tempfile::Builder::new()
+        .prefix("tsq-edit-")
+        .suffix(".md")
+        .tempfile()
+        .map_err(|error| {
+            TsqError::new("IO_ERROR", "failed creating editor scratch file", 2)
+                .with_details(io_error_value(&error))
+        })?;
+    file.write_all(template.as_bytes())
+        .and_then(|_| file.flush())
+        .map_err(|error| {
+            TsqError::new("IO_ERROR", "failed writing editor scratch file", 2)
+                .with_details(io_error_value(&error))
+        })?;
+
+    let status = Command::new(program)
+        .args(&extra_args)
+        .arg(file.path())
+        .status()
+        .map_err(|error| {
+            TsqError::new(
+                "IO_ERROR",
+                format!("failed launching editor: {}", editor),
+                2,
+            )
+            .with_details(io_error_value(&error))
+        })?;
+    if !status.success() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!("editor exited with status {}", status.code().unwrap_or(-1)),
+            1,
+        ));
+    }
+
+    let content = std::fs::read_to_string(file.path()).map_err(|error| {
+        TsqError::new("IO_ERROR", "failed reading editor scratch file", 2)
+            .with_details(io_error_value(&error))
+    })?;
+    let content = content.trim_end().to_string();
+    if content.trim().is_empty() {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "editor content must not be empty",
+            1,
+        ));
+    }
+    Ok(content)
+}
+
+fn resolve_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+fn io_error_value(error: &std::io::Error) -> serde_json::Value {
+    serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()})
+}
@@ -0,0 +1,95 @@
+use crate::errors::TsqError;
+use pulldown_cmark::{Options, Parser, html};
+use std::path::Path;
+
+const STYLESHEET: &str = r#"
+:root { color-scheme: light dark; }
+body {
+  max-width: 52rem;
+  margin: 2rem auto;
+  padding: 0 1.25rem;
+  font: 16px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif;
+  color: #1f2328;
+  background: #ffffff;
+}
+h1, h2, h3 { line-height: 1.25; margin-top: 1.6em; }
+h1 { border-bottom: 1px solid #d0d7de; padding-bottom: 0.3em; }
+h2 { border-bottom: 1px solid #d8dee4; padding-bottom: 0.2em; }
+code, pre { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 0.9em; }
+code { background: #f6f8fa; padding: 0.15em 0.35em; border-radius: 4px; }
+pre { background: #f6f8fa; padding: 0.9em 1em; border-radius: 6px; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #d0d7de; padding: 0.35em 0.75em; text-align: left; }
+th { background: #f6f8fa; }
+blockquote { margin: 0; padding: 0 1em; color: #59636e; border-left: 0.25em solid #d0d7de; }
+ul.contains-task-list { list-style: none; padding-left: 1.2em; }
+footer { margin-top: 3em; color: #59636e; font-size: 0.85em; }
+@media (prefers-color-scheme: dark) {
+  body { color: #e6edf3; background: #0d1117; }
+  h1, h2 { border-color: #30363d; }
+  code, pre, th { background: #161b22; }
+  th, td { border-color: #30363d; }
+  blockquote, footer { color: #8d96a0; border-color: #30363d; }
+}
+"#;
+
+/// Renders markdown into a standalone HTML document with an embedded
+/// stylesheet, so the file can be shared without any other assets.
+pub fn render_markdown_page(title: &str, markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let mut body = String::new();
+    html::push_html(&mut body, Parser::new_ext(markdown, options));
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n{}</main>\n\
+         <footer>Generated by tsq {}</footer>\n</body>\n</html>\n",
+        escape_html(title),
+        STYLESHEET,
+        body,
+        env!("CARGO_PKG_VERSION"),
+    )
+}
+
+pub fn write_html_file(path: &str, document: &str) -> Result<(), TsqError> {
+    if let Some(parent) = Path::new(path).parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            TsqError::new(
+                "IO_ERROR",
+                format!("failed creating directory for {}", path),
+                2,
+            )
+            .with_details(io_error_value(&error))
+        })?;
+    }
+    std::fs::write(path, document).map_err(|error| {
+        TsqError::new("IO_ERROR", format!("failed writing {}", path), 2)
+            .with_details(io_error_value(&error))
+    })
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn io_error_value(error: &std::io::Error) -> serde_json::Value {
+    serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()})
+}
@@ -1,14 +1,20 @@
-use crate::app::service_types::InitInput;
+use crate::app::init_preset::{builtin_seed, load_seed_file};
+use crate::app::service_types::{InitInput, InitSeed};
+use crate::app::sync::DEFAULT_SYNC_BRANCH;
 use crate::cli::parsers::{InitPreset, as_optional_string, parse_init_preset, parse_skill_targets};
 use crate::errors::TsqError;
 use crate::skills::types::SkillTarget;
+use crate::store::git::validate_branch_name;
 use std::io::{self, Write};
 
-const ALL_SKILL_TARGETS: [SkillTarget; 4] = [
+const ALL_SKILL_TARGETS: [SkillTarget; 7] = [
     SkillTarget::Claude,
     SkillTarget::Codex,
     SkillTarget::Copilot,
     SkillTarget::Opencode,
+    SkillTarget::Cursor,
+    SkillTarget::Windsurf,
+    SkillTarget::Gemini,
 ];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -33,6 +39,7 @@ pub struct InitCommandOptions {
     pub no_wizard: bool,
     pub yes: bool,
     pub preset: Option<String>,
+    pub preset_file: Option<String>,
     pub skill_targets: Option<String>,
     pub skill_name: Option<String>,
     pub force_skill_overwrite: bool,
@@ -40,7 +47,13 @@ pub struct InitCommandOptions {
     pub skill_dir_codex: Option<String>,
     pub skill_dir_copilot: Option<String>,
     pub skill_dir_opencode: Option<String>,
+    pub skill_dir_cursor: Option<String>,
+    pub skill_dir_windsurf: Option<String>,
+    pub skill_dir_gemini: Option<String>,
     pub sync_branch: Option<String>,
+    pub no_sync_branch: bool,
+    pub skill_source: Option<String>,
+    pub skill_checksum: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,7 +66,14 @@ pub struct WizardSeed {
     pub skill_dir_codex: Option<String>,
     pub skill_dir_copilot: Option<String>,
     pub skill_dir_opencode: Option<String>,
+    pub skill_dir_cursor: Option<String>,
+    pub skill_dir_windsurf: Option<String>,
+    pub skill_dir_gemini: Option<String>,
     pub sync_branch: Option<String>,
+    pub no_sync_branch: bool,
+    pub skill_source: Option<String>,
+    pub skill_checksum: Option<String>,
+    pub preset_seed: Option<InitSeed>,
 }
 
 #[derive(Debug, Clone)]
@@ -95,6 +115,13 @@ pub fn resolve_init_plan(
             1,
         ));
     }
+    if options.sync_branch.is_some() && options.no_sync_branch {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "cannot combine --sync-branch with --no-sync-branch",
+            1,
+        ));
+    }
     if !context.is_tty && has_wizard {
         return Err(TsqError::new(
             "VALIDATION_ERROR",
@@ -142,7 +169,7 @@ pub fn resolve_init_plan(
 pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput, TsqError> {
     let mut state = seed.clone();
     if auto_accept {
-        print_header(4, 4);
+        print_header(5, 5);
         print_plan_summary(&state);
         println!("\n--yes enabled: applying defaults and confirmation automatically.");
         return Ok(build_init_input_from_seed(&state));
@@ -151,7 +178,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
     let mut step = 1;
     loop {
         if step == 1 {
-            print_header(1, 4);
+            print_header(1, 5);
             println!(
                 "This will initialize: .tasque/config.json, .tasque/events.jsonl, .tasque/.gitignore"
             );
@@ -161,7 +188,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
                 continue;
             }
             if decision == "s" {
-                step = 4;
+                step = 5;
                 continue;
             }
             if is_no(&decision) || decision == "q" {
@@ -176,7 +203,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
         }
 
         if step == 2 {
-            print_header(2, 4);
+            print_header(2, 5);
             println!("Select skill action:");
             println!("  1) install");
             println!("  2) uninstall");
@@ -191,7 +218,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
                 continue;
             }
             if answer == "s" {
-                step = 4;
+                step = 5;
                 continue;
             }
             if answer == "q" {
@@ -229,7 +256,11 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
                 step = 4;
                 continue;
             }
-            print_header(3, 4);
+            print_header(3, 5);
+            println!(
+                "Available targets: {}",
+                format_targets_list(&ALL_SKILL_TARGETS)
+            );
             let default_targets = format_targets(&state.skill_targets);
             let targets_answer = ask_token(&format!(
                 "Skill targets [all or csv] (default {}, b=back, s=skip, q=quit) ",
@@ -240,7 +271,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
                 continue;
             }
             if targets_answer == "s" {
-                step = 4;
+                step = 5;
                 continue;
             }
             if targets_answer == "q" {
@@ -269,7 +300,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
                 continue;
             }
             if name_answer == "s" {
-                step = 4;
+                step = 5;
                 continue;
             }
             if name_answer == "q" {
@@ -304,7 +335,7 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
                     continue;
                 }
                 if force_answer == "s" {
-                    step = 4;
+                    step = 5;
                     continue;
                 }
                 if is_yes(&force_answer) {
@@ -318,18 +349,72 @@ pub fn run_init_wizard(seed: WizardSeed, auto_accept: bool) -> Result<InitInput,
             continue;
         }
 
-        print_header(4, 4);
+        if step == 4 {
+            print_header(4, 5);
+            println!(
+                "A sync branch keeps task history off your code branches, with a merge driver for events.jsonl and a worktree checkout (git repositories only)."
+            );
+            let default_sync = match (&state.sync_branch, state.no_sync_branch) {
+                (_, true) => "n".to_string(),
+                (Some(branch), false) => branch.clone(),
+                // init only picks the default branch itself when no skill action runs.
+                (None, false) if state.action == SkillAction::None => {
+                    DEFAULT_SYNC_BRANCH.to_string()
+                }
+                (None, false) => "n".to_string(),
+            };
+            let answer = ask_token(&format!(
+                "Sync branch [name or n] (default {}, b=back, s=skip, q=quit) ",
+                default_sync
+            ))?;
+            if answer == "b" {
+                step = if state.action == SkillAction::None {
+                    2
+                } else {
+                    3
+                };
+                continue;
+            }
+            if answer == "s" {
+                step = 5;
+                continue;
+            }
+            if answer == "q" {
+                return Err(TsqError::new(
+                    "VALIDATION_ERROR",
+                    "init canceled by user",
+                    1,
+                ));
+            }
+            if answer.is_empty() {
+                // Keep the default shown in the prompt.
+            } else if is_no(&answer) || answer == "none" {
+                state.sync_branch = None;
+                state.no_sync_branch = true;
+            } else if is_yes(&answer) {
+                state.no_sync_branch = false;
+                state
+                    .sync_branch
+                    .get_or_insert_with(|| DEFAULT_SYNC_BRANCH.to_string());
+            } else if let Err(error) = validate_branch_name(&answer) {
+                println!("{}: {}", error.code, error.message);
+                continue;
+            } else {
+                state.sync_branch = Some(answer);
+                state.no_sync_branch = false;
+            }
+            step = 5;
+            continue;
+        }
+
+        print_header(5, 5);
         print_plan_summary(&state);
         let confirm = ask_token("Apply this setup? [Y/n/b/s/q] ")?;
         if is_yes(&confirm) || confirm == "s" {
             return Ok(build_init_input_from_seed(&state));
         }
         if confirm == "b" {
-            step = if state.action == SkillAction::None {
-                2
-            } else {
-                3
-            };
+            step = 4;
             continue;
         }
         if is_no(&confirm) || confirm == "q" {
@@ -376,7 +461,17 @@ fn resolve_non_interactive_input(options: &InitCommandOptions) -> Result<InitInp
         skill_dir_codex: as_optional_string(options.skill_dir_codex.as_deref()),
         skill_dir_copilot: as_optional_string(options.skill_dir_copilot.as_deref()),
         skill_dir_opencode: as_optional_string(options.skill_dir_opencode.as_deref()),
+        skill_dir_cursor: as_optional_string(options.skill_dir_cursor.as_deref()),
+        skill_dir_windsurf: as_optional_string(options.skill_dir_windsurf.as_deref()),
+        skill_dir_gemini: as_optional_string(options.skill_dir_gemini.as_deref()),
         sync_branch: as_optional_string(options.sync_branch.as_deref()),
+        no_sync_branch: options.no_sync_branch,
+        dry_run: false,
+        skill_source: as_optional_string(options.skill_source.as_deref()),
+        skill_checksum: as_optional_string(options.skill_checksum.as_deref()),
+        seed: as_optional_string(options.preset_file.as_deref())
+            .map(|path| load_seed_file(&path))
+            .transpose()?,
     })
 }
 
@@ -403,6 +498,16 @@ fn resolve_wizard_seed(
         as_optional_string(options.skill_name.as_deref()).unwrap_or(defaults.skill_name);
     let force_skill_overwrite = options.force_skill_overwrite || defaults.force_skill_overwrite;
 
+    // A preset file replaces the bundled definition for the chosen preset.
+    let preset_seed = match as_optional_string(options.preset_file.as_deref()) {
+        Some(path) => Some(load_seed_file(&path)?),
+        None => match preset {
+            Some(InitPreset::Standard) => builtin_seed("standard")?,
+            Some(InitPreset::Full) => builtin_seed("full")?,
+            _ => None,
+        },
+    };
+
     if action == SkillAction::None && has_skill_scoped_flags(options) {
         return Err(TsqError::new(
             "VALIDATION_ERROR",
@@ -420,7 +525,14 @@ fn resolve_wizard_seed(
         skill_dir_codex: as_optional_string(options.skill_dir_codex.as_deref()),
         skill_dir_copilot: as_optional_string(options.skill_dir_copilot.as_deref()),
         skill_dir_opencode: as_optional_string(options.skill_dir_opencode.as_deref()),
+        skill_dir_cursor: as_optional_string(options.skill_dir_cursor.as_deref()),
+        skill_dir_windsurf: as_optional_string(options.skill_dir_windsurf.as_deref()),
+        skill_dir_gemini: as_optional_string(options.skill_dir_gemini.as_deref()),
         sync_branch: as_optional_string(options.sync_branch.as_deref()),
+        no_sync_branch: options.no_sync_branch,
+        skill_source: as_optional_string(options.skill_source.as_deref()),
+        skill_checksum: as_optional_string(options.skill_checksum.as_deref()),
+        preset_seed,
     })
 }
 
@@ -435,7 +547,14 @@ fn resolve_preset_defaults(preset: Option<InitPreset>) -> WizardSeed {
             skill_dir_codex: None,
             skill_dir_copilot: None,
             skill_dir_opencode: None,
+            skill_dir_cursor: None,
+            skill_dir_windsurf: None,
+            skill_dir_gemini: None,
             sync_branch: None,
+            no_sync_branch: false,
+            skill_source: None,
+            skill_checksum: None,
+            preset_seed: None,
         },
         Some(InitPreset::Full) => WizardSeed {
             action: SkillAction::Install,
@@ -446,7 +565,14 @@ fn resolve_preset_defaults(preset: Option<InitPreset>) -> WizardSeed {
             skill_dir_codex: None,
             skill_dir_copilot: None,
             skill_dir_opencode: None,
+            skill_dir_cursor: None,
+            skill_dir_windsurf: None,
+            skill_dir_gemini: None,
             sync_branch: None,
+            no_sync_branch: false,
+            skill_source: None,
+            skill_checksum: None,
+            preset_seed: None,
         },
         _ => WizardSeed {
             action: SkillAction::None,
@@ -457,7 +583,14 @@ fn resolve_preset_defaults(preset: Option<InitPreset>) -> WizardSeed {
             skill_dir_codex: None,
             skill_dir_copilot: None,
             skill_dir_opencode: None,
+            skill_dir_cursor: None,
+            skill_dir_windsurf: None,
+            skill_dir_gemini: None,
             sync_branch: None,
+            no_sync_branch: false,
+            skill_source: None,
+            skill_checksum: None,
+            preset_seed: None,
         },
     }
 }
@@ -486,7 +619,15 @@ fn build_init_input_from_seed(seed: &WizardSeed) -> InitInput {
         skill_dir_codex: seed.skill_dir_codex.clone(),
         skill_dir_copilot: seed.skill_dir_copilot.clone(),
         skill_dir_opencode: seed.skill_dir_opencode.clone(),
+        skill_dir_cursor: seed.skill_dir_cursor.clone(),
+        skill_dir_windsurf: seed.skill_dir_windsurf.clone(),
+        skill_dir_gemini: seed.skill_dir_gemini.clone(),
         sync_branch: seed.sync_branch.clone(),
+        no_sync_branch: seed.no_sync_branch,
+        dry_run: false,
+        skill_source: seed.skill_source.clone(),
+        skill_checksum: seed.skill_checksum.clone(),
+        seed: seed.preset_seed.clone(),
     }
 }
 
@@ -498,6 +639,11 @@ fn has_skill_scoped_flags(options: &InitCommandOptions) -> bool {
         || as_optional_string(options.skill_dir_codex.as_deref()).is_some()
         || as_optional_string(options.skill_dir_copilot.as_deref()).is_some()
         || as_optional_string(options.skill_dir_opencode.as_deref()).is_some()
+        || as_optional_string(options.skill_dir_cursor.as_deref()).is_some()
+        || as_optional_string(options.skill_dir_windsurf.as_deref()).is_some()
+        || as_optional_string(options.skill_dir_gemini.as_deref()).is_some()
+        || options.skill_source.is_some()
+        || options.skill_checksum.is_some()
 }
 
 fn has_flag(raw_args: &[String], flag: &str) -> bool {
@@ -513,8 +659,26 @@ fn print_plan_summary(seed: &WizardSeed) {
     println!("- create .tasque/config.json");
     println!("- create .tasque/events.jsonl");
     println!("- create .tasque/.gitignore");
-    if let Some(sync_branch) = &seed.sync_branch {
-        println!("- configure sync branch \"{}\"", sync_branch);
+    match (&seed.sync_branch, seed.no_sync_branch) {
+        (_, true) => println!("- skip sync branch setup"),
+        (Some(sync_branch), false) => println!("- configure sync branch \"{}\"", sync_branch),
+        (None, false) if seed.action == SkillAction::None => println!(
+            "- configure sync branch \"{}\" (git repositories only)",
+            DEFAULT_SYNC_BRANCH
+        ),
+        (None, false) => {}
+    }
+    if let Some(preset) = &seed.preset_seed {
+        if !preset.labels.is_empty() {
+            let labels: Vec<&str> = preset.labels.keys().map(String::as_str).collect();
+            println!("- seed labels: {}", labels.join(", "));
+        }
+        for epic in &preset.epics {
+            println!("- seed epic \"{}\"", epic.title);
+        }
+        for policy in &preset.policies {
+            println!("- seed policy \"{}\"", policy.name);
+        }
     }
     match seed.action {
         SkillAction::Install => {
@@ -575,17 +739,31 @@ fn format_targets(targets: &[SkillTarget]) -> String {
             SkillTarget::Codex => "codex",
             SkillTarget::Copilot => "copilot",
             SkillTarget::Opencode => "opencode",
+            SkillTarget::Cursor => "cursor",
+            SkillTarget::Windsurf => "windsurf",
+            SkillTarget::Gemini => "gemini",
         })
         .collect::<Vec<_>>()
         .join(",")
 }
 
+fn format_targets_list(targets: &[SkillTarget]) -> String {
+    targets
+        .iter()
+        .map(|target| target.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn target_rank(target: SkillTarget) -> usize {
     match target {
         SkillTarget::Claude => 0,
         SkillTarget::Codex => 1,
         SkillTarget::Copilot => 2,
         SkillTarget::Opencode => 3,
+        SkillTarget::Cursor => 4,
+        SkillTarget::Windsurf => 5,
+        SkillTarget::Gemini => 6,
     }
 }
 
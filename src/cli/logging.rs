@@ -0,0 +1,73 @@
+//! Installs the `tracing` subscriber that prints warnings and diagnostic spans
+//! to stderr.
+//!
+//! The core crate never prints; its warnings (failed auto-sync push, advisory
+//! hook failure) are `warn` events, shown by default. `TSQ_LOG` takes
+//! `EnvFilter` directives (`debug`, `tasque_core::store=trace`, `off`, ...);
+//! `--verbose` raises the filter to at least `debug`. Spans log their busy and
+//! idle time when they close, so wrapping a block is enough to time it.
+
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::time::uptime;
+
+pub const LOG_ENV: &str = "TSQ_LOG";
+
+/// Installs the stderr subscriber unless the resolved filter is `off`.
+pub fn init(verbose: bool) {
+    let raw = std::env::var(LOG_ENV).unwrap_or_default();
+    let filter = build_filter(&raw, verbose);
+    if filter.max_level_hint() == Some(LevelFilter::OFF) {
+        return;
+    }
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .with_timer(uptime())
+        .with_target(false)
+        .with_span_events(FmtSpan::CLOSE)
+        .try_init();
+}
+
+pub fn build_filter(raw: &str, verbose: bool) -> EnvFilter {
+    let directives = match raw.trim().to_ascii_lowercase().as_str() {
+        "" => "warn".to_string(),
+        "0" | "false" => "off".to_string(),
+        "1" | "true" => "debug".to_string(),
+        _ => raw.trim().to_string(),
+    };
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::OFF.into())
+        .parse_lossy(directives);
+    let below_debug = filter
+        .max_level_hint()
+        .is_none_or(|max| max < LevelFilter::DEBUG);
+    if verbose && below_debug {
+        filter.add_directive(LevelFilter::DEBUG.into())
+    } else {
+        filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_filter_accepts_levels_flags_and_directives() {
+        let hint = |raw: &str, verbose: bool| build_filter(raw, verbose).max_level_hint();
+        assert_eq!(hint("", false), Some(LevelFilter::WARN));
+        assert_eq!(hint("off", false), Some(LevelFilter::OFF));
+        assert_eq!(hint("1", false), Some(LevelFilter::DEBUG));
+        assert_eq!(hint("WARN", false), Some(LevelFilter::WARN));
+        assert_eq!(
+            hint("tasque_core::store=trace", false),
+            Some(LevelFilter::TRACE)
+        );
+        assert_eq!(hint("", true), Some(LevelFilter::DEBUG));
+        assert_eq!(hint("warn", true), Some(LevelFilter::DEBUG));
+        assert_eq!(hint("trace", true), Some(LevelFilter::TRACE));
+    }
+}
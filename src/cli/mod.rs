@@ -1,12 +1,20 @@
 pub mod action;
 pub mod commands;
+pub mod disambiguate;
+pub mod editor;
+pub mod html;
 pub mod init_flow;
+pub mod logging;
 pub mod opentui;
 pub mod parsers;
+pub mod plugin;
+pub mod porcelain;
 pub mod program;
 pub mod render;
+pub mod serve;
 pub mod style;
 pub mod terminal;
+pub mod timestamps;
 pub mod tui;
 pub mod watch;
 
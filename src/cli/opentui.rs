@@ -19,8 +19,10 @@ pub fn should_launch_opentui(options: &TuiOptions) -> bool {
     {
         return false;
     }
+    // The OpenTUI app has no query support, so --query keeps the built-in renderer.
     if options.json
         || options.once
+        || options.query.is_some()
         || !std::io::stdin().is_terminal()
         || !std::io::stdout().is_terminal()
     {
@@ -184,14 +186,7 @@ fn status_csv(options: &TuiOptions) -> String {
     options
         .statuses
         .iter()
-        .map(|status| match status {
-            TaskStatus::Open => "open",
-            TaskStatus::InProgress => "in_progress",
-            TaskStatus::Blocked => "blocked",
-            TaskStatus::Deferred => "deferred",
-            TaskStatus::Closed => "closed",
-            TaskStatus::Canceled => "canceled",
-        })
+        .map(TaskStatus::as_str)
         .collect::<Vec<_>>()
         .join(",")
 }
@@ -1,14 +1,17 @@
 use crate::app::runtime::{normalize_status, parse_priority};
-use crate::app::service_types::{DepDirectionFilter, ListFilter};
+use crate::app::service_types::{
+    DepDirectionFilter, ListFilter, MergeField, MergeFieldStrategy, SpecDriftCheck, SpecStateFilter,
+};
 use crate::domain::dep_tree::DepDirection;
 use crate::domain::ids::is_valid_root_id;
 use crate::domain::labels::normalize_label;
-use crate::domain::validate::PlanningLane;
+use crate::domain::query::parse_query;
 use crate::errors::TsqError;
 use crate::skills::types::SkillTarget;
 use crate::types::{DependencyType, PlanningState, RelationType, TaskKind, TaskStatus};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::BTreeMap;
 
 static ISO_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d{1,9})?(?:Z|[+-]\d{2}:\d{2})$")
@@ -42,6 +45,10 @@ pub struct ListParseInput {
     pub planning: Option<String>,
     pub dep_type: Option<String>,
     pub dep_direction: Option<String>,
+    pub spec: Option<String>,
+    pub drift_check: Option<String>,
+    pub priority: Option<String>,
+    pub estimate: Option<String>,
 }
 
 pub fn as_optional_string(value: Option<&str>) -> Option<String> {
@@ -77,13 +84,38 @@ pub fn parse_relation_type(raw: &str) -> Result<RelationType, TsqError> {
     }
 }
 
+/// Any well-formed name parses; the service checks it against
+/// `planning_workflow`.
 pub fn parse_planning_state(raw: &str) -> Result<PlanningState, TsqError> {
+    PlanningState::from_name(raw).ok_or_else(|| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            "planning state must be needs_planning|planned or a planning_workflow state",
+            1,
+        )
+    })
+}
+
+pub fn parse_spec_state_filter(raw: &str) -> Result<SpecStateFilter, TsqError> {
+    match raw {
+        "attached" => Ok(SpecStateFilter::Attached),
+        "missing" => Ok(SpecStateFilter::Missing),
+        "drifted" => Ok(SpecStateFilter::Drifted),
+        _ => Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "spec must be attached|missing|drifted",
+            1,
+        )),
+    }
+}
+
+pub fn parse_spec_drift_check(raw: &str) -> Result<SpecDriftCheck, TsqError> {
     match raw {
-        "needs_planning" => Ok(PlanningState::NeedsPlanning),
-        "planned" => Ok(PlanningState::Planned),
+        "hash" => Ok(SpecDriftCheck::Hash),
+        "mtime" => Ok(SpecDriftCheck::Mtime),
         _ => Err(TsqError::new(
             "VALIDATION_ERROR",
-            "planning state must be needs_planning|planned",
+            "drift-check must be hash|mtime",
             1,
         )),
     }
@@ -114,18 +146,6 @@ pub fn validate_explicit_id(raw: &str) -> Result<String, TsqError> {
     Ok(trimmed.to_string())
 }
 
-pub fn parse_lane(raw: &str) -> Result<PlanningLane, TsqError> {
-    match raw {
-        "planning" => Ok(PlanningLane::Planning),
-        "coding" => Ok(PlanningLane::Coding),
-        _ => Err(TsqError::new(
-            "VALIDATION_ERROR",
-            "lane must be planning|coding",
-            1,
-        )),
-    }
-}
-
 pub fn parse_skill_targets(raw: &str) -> Result<Vec<SkillTarget>, TsqError> {
     let tokens: Vec<String> = raw
         .split(',')
@@ -145,6 +165,9 @@ pub fn parse_skill_targets(raw: &str) -> Result<Vec<SkillTarget>, TsqError> {
             SkillTarget::Codex,
             SkillTarget::Copilot,
             SkillTarget::Opencode,
+            SkillTarget::Cursor,
+            SkillTarget::Windsurf,
+            SkillTarget::Gemini,
         ]);
     }
 
@@ -155,10 +178,13 @@ pub fn parse_skill_targets(raw: &str) -> Result<Vec<SkillTarget>, TsqError> {
             "codex" => SkillTarget::Codex,
             "copilot" => SkillTarget::Copilot,
             "opencode" => SkillTarget::Opencode,
+            "cursor" => SkillTarget::Cursor,
+            "windsurf" => SkillTarget::Windsurf,
+            "gemini" => SkillTarget::Gemini,
             _ => {
                 return Err(TsqError::new(
                     "VALIDATION_ERROR",
-                    "skill targets must be comma-separated values of claude,codex,copilot,opencode,all",
+                    "skill targets must be comma-separated values of claude,codex,copilot,opencode,cursor,windsurf,gemini,all",
                     1,
                 ));
             }
@@ -188,9 +214,11 @@ pub fn parse_dependency_type(raw: &str) -> Result<DependencyType, TsqError> {
     match raw {
         "blocks" => Ok(DependencyType::Blocks),
         "starts_after" => Ok(DependencyType::StartsAfter),
+        "finish_to_finish" => Ok(DependencyType::FinishToFinish),
+        "informs" => Ok(DependencyType::Informs),
         _ => Err(TsqError::new(
             "VALIDATION_ERROR",
-            "dependency type must be blocks|starts_after",
+            "dependency type must be blocks|starts_after|finish_to_finish|informs",
             1,
         )),
     }
@@ -256,6 +284,9 @@ pub fn parse_list_filter(input: ListParseInput) -> Result<ListFilter, TsqError>
         planning_state: None,
         dep_type: None,
         dep_direction: None,
+        spec: None,
+        drift_check: SpecDriftCheck::Hash,
+        query: None,
     };
 
     if let Some(status) = input.status.as_deref() {
@@ -319,9 +350,56 @@ pub fn parse_list_filter(input: ListParseInput) -> Result<ListFilter, TsqError>
         ));
     }
 
+    if let Some(spec) = input.spec.as_deref() {
+        filter.spec = Some(parse_spec_state_filter(spec)?);
+    }
+    if let Some(drift_check) = input.drift_check.as_deref() {
+        if filter.spec != Some(SpecStateFilter::Drifted) {
+            return Err(TsqError::new(
+                "VALIDATION_ERROR",
+                "--drift-check requires --spec drifted",
+                1,
+            ));
+        }
+        filter.drift_check = parse_spec_drift_check(drift_check)?;
+    }
+    let comparisons = [
+        ("priority", input.priority.as_deref()),
+        ("estimate", input.estimate.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(field, raw)| raw.map(|raw| parse_comparison_flag(field, raw)))
+    .collect::<Result<Vec<_>, _>>()?;
+    if !comparisons.is_empty() {
+        filter.query = Some(comparisons.join(" "));
+    }
+
     Ok(filter)
 }
 
+/// Turns `--priority <=1` or `--estimate 2..5` into the matching search
+/// term, so list flags and queries share one comparison syntax.
+fn parse_comparison_flag(field: &str, raw: &str) -> Result<String, TsqError> {
+    let value = raw.trim();
+    let term = if value.starts_with(['<', '>']) {
+        format!("{}{}", field, value)
+    } else {
+        format!("{}:{}", field, value)
+    };
+    let parsed = parse_query(&term)?;
+    if value.is_empty() || parsed.terms.len() != 1 || parsed.terms[0].field != field {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            format!(
+                "--{} must be a single comparison such as <=1 or 2..5",
+                field
+            ),
+            1,
+        ));
+    }
+    Ok(term)
+}
+
 pub fn apply_tree_defaults(filter: ListFilter, full: bool) -> ListFilter {
     if full || filter.statuses.is_some() {
         return filter;
@@ -342,10 +420,84 @@ pub fn parse_status_csv(raw: &str) -> Result<Vec<TaskStatus>, TsqError> {
     Ok(statuses)
 }
 
+/// Parses repeated or comma-separated `field=strategy` pairs for `tsq merge`.
+pub fn parse_merge_strategy(
+    raw: &[String],
+) -> Result<BTreeMap<MergeField, MergeFieldStrategy>, TsqError> {
+    let mut strategy = BTreeMap::new();
+    for token in raw
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
+        let invalid = || {
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!(
+                    "invalid merge strategy {}; expected description=keep|concat, labels=keep|union, or priority=keep|min|max",
+                    token
+                ),
+                1,
+            )
+        };
+        let (field, chosen) = token.split_once('=').ok_or_else(invalid)?;
+        let field = match field.trim() {
+            "description" => MergeField::Description,
+            "labels" => MergeField::Labels,
+            "priority" => MergeField::Priority,
+            _ => return Err(invalid()),
+        };
+        let chosen = match chosen.trim() {
+            "keep" => MergeFieldStrategy::Keep,
+            "concat" => MergeFieldStrategy::Concat,
+            "union" => MergeFieldStrategy::Union,
+            "min" => MergeFieldStrategy::Min,
+            "max" => MergeFieldStrategy::Max,
+            _ => return Err(invalid()),
+        };
+        strategy.insert(field, chosen);
+    }
+    Ok(strategy)
+}
+
 pub fn parse_priority_value(raw: &str) -> Result<u8, TsqError> {
     parse_priority(raw)
 }
 
+/// Accepts a calendar date (midnight UTC) or a full ISO timestamp.
+pub fn parse_date_or_timestamp(raw: &str, field: &str) -> Result<String, TsqError> {
+    let trimmed = raw.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        return Ok(date
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc()
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true));
+    }
+    parse_iso_timestamp(trimmed, field).map_err(|_| {
+        TsqError::new(
+            "VALIDATION_ERROR",
+            format!("--{} must be a date (YYYY-MM-DD) or ISO timestamp", field),
+            1,
+        )
+    })
+}
+
+/// Parses `--as-of` for time-travel reads.
+pub fn parse_as_of(raw: Option<&str>) -> Result<Option<String>, TsqError> {
+    raw.map(|value| parse_since_timestamp(value, "as-of"))
+        .transpose()
+}
+
+/// Accepts a bare `YYYY-MM-DD` (midnight UTC) or a full ISO timestamp.
+pub fn parse_since_timestamp(raw: &str, field: &str) -> Result<String, TsqError> {
+    let trimmed = raw.trim();
+    if chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d").is_ok() {
+        return parse_iso_timestamp(&format!("{}T00:00:00Z", trimmed), field);
+    }
+    parse_iso_timestamp(trimmed, field)
+}
+
 fn parse_iso_timestamp(raw: &str, field: &str) -> Result<String, TsqError> {
     let trimmed = raw.trim();
     if trimmed.is_empty() || !ISO_PATTERN.is_match(trimmed) {
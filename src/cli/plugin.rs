@@ -0,0 +1,109 @@
+use crate::cli::action::GlobalOpts;
+use crate::errors::TsqError;
+use crate::types::SCHEMA_VERSION;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const PLUGIN_PREFIX: &str = "tsq-";
+
+/// Context handed to external `tsq-<name>` plugins through the environment.
+#[derive(Debug, Clone)]
+pub struct PluginEnv<'a> {
+    pub repo_root: &'a str,
+    pub actor: &'a str,
+    pub opts: GlobalOpts,
+}
+
+/// Finds an executable `tsq-<name>` on `PATH`.
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    if !is_plugin_name(name) {
+        return None;
+    }
+    let path = std::env::var_os("PATH")?;
+    let file_name = format!("{}{}", PLUGIN_PREFIX, name);
+    std::env::split_paths(&path).find_map(|dir| {
+        plugin_candidates(&dir, &file_name)
+            .into_iter()
+            .find(|candidate| is_executable(candidate))
+    })
+}
+
+/// Runs the plugin with inherited stdio and returns its exit code.
+pub fn run_plugin(plugin: &Path, args: &[String], env: &PluginEnv<'_>) -> Result<i32, TsqError> {
+    let tsq_bin = std::env::current_exe()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| "tsq".to_string());
+    let status = Command::new(plugin)
+        .args(args)
+        .env("TSQ_REPO_ROOT", env.repo_root)
+        .env("TSQ_ACTOR", env.actor)
+        .env("TSQ_FORMAT", if env.opts.json() { "json" } else { "human" })
+        .env("TSQ_EXACT_ID", flag_value(env.opts.exact_id))
+        .env("TSQ_DRY_RUN", flag_value(env.opts.dry_run))
+        .env("TSQ_SCHEMA_VERSION", SCHEMA_VERSION.to_string())
+        .env("TSQ_BIN", tsq_bin)
+        .status()
+        .map_err(|error| {
+            TsqError::new(
+                "IO_ERROR",
+                format!("failed launching plugin: {}", plugin.display()),
+                2,
+            )
+            .with_details(
+                serde_json::json!({"kind": format!("{:?}", error.kind()), "message": error.to_string()}),
+            )
+        })?;
+    Ok(status.code().unwrap_or(1))
+}
+
+fn is_plugin_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn flag_value(value: bool) -> &'static str {
+    if value { "1" } else { "0" }
+}
+
+#[cfg(windows)]
+fn plugin_candidates(dir: &Path, file_name: &str) -> Vec<PathBuf> {
+    ["exe", "cmd", "bat"]
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", file_name, ext)))
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn plugin_candidates(dir: &Path, file_name: &str) -> Vec<PathBuf> {
+    vec![dir.join(file_name)]
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugin_names_reject_paths_and_flags() {
+        assert!(is_plugin_name("report"));
+        assert!(is_plugin_name("jira_sync-2"));
+        assert!(!is_plugin_name("../evil"));
+        assert!(!is_plugin_name("--help"));
+        assert!(!is_plugin_name(""));
+    }
+}
@@ -0,0 +1,179 @@
+//! `--porcelain v1`: a frozen JSON shape for `show`, `find ready`,
+//! `find <status>`, and `find search`.
+//!
+//! The default `--json` payload grows as features land. Porcelain output is
+//! projected from it field by field, so every key listed here is always
+//! present (null when unset), in this order, and nothing else appears.
+//! Breaking changes get a new version instead of editing v1.
+
+use crate::errors::TsqError;
+use clap::ValueEnum;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PorcelainVersion {
+    V1,
+}
+
+impl PorcelainVersion {
+    fn as_str(self) -> &'static str {
+        match self {
+            PorcelainVersion::V1 => "v1",
+        }
+    }
+}
+
+const TASK_FIELDS_V1: [&str; 17] = [
+    "id",
+    "alias",
+    "kind",
+    "title",
+    "status",
+    "priority",
+    "assignee",
+    "parent_id",
+    "labels",
+    "planning_state",
+    "spec_path",
+    "blocked_on",
+    "defer_until",
+    "external_ref",
+    "created_at",
+    "updated_at",
+    "closed_at",
+];
+
+/// Keys serialize in insertion order, unlike `serde_json::Map`, so nested
+/// records stay `Record`s instead of round-tripping through `Value`.
+struct Record(Vec<(&'static str, Field)>);
+
+enum Field {
+    Value(Value),
+    Record(Record),
+    Records(Vec<Record>),
+}
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Field::Value(value) => value.serialize(serializer),
+            Field::Record(record) => record.serialize(serializer),
+            Field::Records(records) => records.serialize(serializer),
+        }
+    }
+}
+
+fn field(object: &Value, key: &str) -> Field {
+    Field::Value(object.get(key).cloned().unwrap_or(Value::Null))
+}
+
+fn task_v1(task: &Value) -> Record {
+    Record(
+        TASK_FIELDS_V1
+            .iter()
+            .map(|key| match (*key, field(task, key)) {
+                ("labels", Field::Value(Value::Null)) => {
+                    (*key, Field::Value(Value::Array(Vec::new())))
+                }
+                (_, value) => (*key, value),
+            })
+            .collect(),
+    )
+}
+
+fn tasks_v1(tasks: &Value) -> Vec<Record> {
+    tasks
+        .as_array()
+        .map(|tasks| tasks.iter().map(task_v1).collect())
+        .unwrap_or_default()
+}
+
+/// Projects the default JSON payload of a supported command onto v1.
+fn data_v1(command_line: &str, data: &Value) -> Record {
+    if command_line == "tsq show" {
+        let task = task_v1(data.get("task").unwrap_or(&Value::Null));
+        return Record(vec![
+            ("task", Field::Record(task)),
+            ("blockers", field(data, "blockers")),
+            ("dependents", field(data, "dependents")),
+            ("ready", field(data, "ready")),
+        ]);
+    }
+    let tasks = tasks_v1(data.get("tasks").unwrap_or(&Value::Null));
+    Record(vec![("tasks", Field::Records(tasks))])
+}
+
+/// One compact JSON line: `{"porcelain","command","ok","data"|"error"}`.
+pub fn render(
+    version: PorcelainVersion,
+    command_line: &str,
+    result: Result<Value, &TsqError>,
+) -> String {
+    let (ok, body) = match result {
+        Ok(data) => (true, ("data", Field::Record(data_v1(command_line, &data)))),
+        Err(error) => (
+            false,
+            (
+                "error",
+                Field::Record(Record(vec![
+                    ("code", Field::Value(Value::from(error.code.clone()))),
+                    ("message", Field::Value(Value::from(error.message.clone()))),
+                    (
+                        "details",
+                        Field::Value(error.details.clone().unwrap_or(Value::Null)),
+                    ),
+                ])),
+            ),
+        ),
+    };
+    let envelope = Record(vec![
+        ("porcelain", Field::Value(Value::from(version.as_str()))),
+        ("command", Field::Value(Value::from(command_line))),
+        ("ok", Field::Value(Value::Bool(ok))),
+        body,
+    ]);
+    serde_json::to_string(&envelope).unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_task_keeps_frozen_fields_in_order() {
+        let data = serde_json::json!({
+            "tasks": [{
+                "id": "tsq-aaaaaaaa",
+                "title": "One",
+                "status": "open",
+                "note_count": 2,
+                "some_future_field": true,
+            }]
+        });
+        let line = render(PorcelainVersion::V1, "tsq find open", Ok(data));
+        let expected_keys = TASK_FIELDS_V1
+            .iter()
+            .map(|key| format!("\"{}\":", key))
+            .collect::<Vec<_>>();
+        let mut position = 0;
+        for key in &expected_keys {
+            let found = line[position..].find(key.as_str()).expect(key);
+            position += found + key.len();
+        }
+        assert!(line.starts_with(r#"{"porcelain":"v1","command":"tsq find open","ok":true"#));
+        assert!(!line.contains("note_count"));
+        assert!(!line.contains("some_future_field"));
+        assert!(line.contains(r#""labels":[]"#));
+    }
+}
@@ -1,7 +1,16 @@
 use crate::app::runtime::find_tasque_root;
 use crate::app::service::TasqueService;
-use crate::cli::action::{GlobalOpts, OutputFormat, emit_error};
-use crate::cli::commands::{dep, hooks, label, link, meta, note, skills, spec, sync, task};
+use crate::cli::action::{GlobalOpts, OutputFormat, emit_error, set_dry_run_preview};
+use crate::cli::commands::{
+    actor, audit, backup, batch, checklist, criteria, dep, export, git, hooks, ids, label, link,
+    lock, meta, note, release_notes, report, serve, skills, spec, sync, task,
+};
+use crate::cli::disambiguate;
+use crate::cli::plugin;
+use crate::cli::porcelain::PorcelainVersion;
+use crate::cli::style;
+use crate::cli::timestamps::{self, TimestampMode};
+use crate::domain::query::split_count_clause;
 use crate::errors::TsqError;
 use crate::output::err_envelope;
 use clap::error::ErrorKind;
@@ -18,6 +27,7 @@ use std::io::IsTerminal;
   tsq find ready --lane coding
   tsq note tsq-abc12345 \"blocked on API decision\"
   tsq spec tsq-abc12345 --file docs/spec.md
+  tsq report --format html --out report.html
   tsq done tsq-abc12345 --note \"merged\"")]
 pub struct Cli {
     #[arg(long, global = true)]
@@ -26,6 +36,24 @@ pub struct Cli {
     pub format: Option<FormatArg>,
     #[arg(long = "exact-id", global = true)]
     pub exact_id: bool,
+    /// Preview a mutation: report the events it would append without writing them
+    #[arg(long = "dry-run", global = true)]
+    pub dry_run: bool,
+    /// Frozen JSON for scripts (show, find ready/<status>/search); stays the same across upgrades
+    #[arg(long, global = true, value_enum, value_name = "VERSION")]
+    pub porcelain: Option<PorcelainVersion>,
+    /// Print tracing spans (storage, lock, git, projection) to stderr; see TSQ_LOG
+    #[arg(short = 'v', long, global = true)]
+    pub verbose: bool,
+    /// Report phase durations (read_events, apply, filter, render): in JSON as meta.timing, otherwise on stderr
+    #[arg(long, global = true)]
+    pub timing: bool,
+    /// How human output shows created/updated/event times (default relative)
+    #[arg(long, global = true, value_enum)]
+    pub timestamps: Option<TimestampMode>,
+    /// Fail on ambiguous id prefixes instead of offering a pick list in a TTY
+    #[arg(long = "no-interactive", global = true)]
+    pub no_interactive: bool,
     #[command(subcommand)]
     pub command: CommandKind,
 }
@@ -34,42 +62,78 @@ pub struct Cli {
 pub enum FormatArg {
     Human,
     Json,
+    /// Standalone HTML document; only supported by `tsq report`
+    Html,
+    /// Markdown changelog; only supported by `tsq release-notes`
+    Markdown,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum CommandKind {
     Init(meta::InitArgs),
     Doctor,
+    /// Replay the event log repeatedly and report read/apply timings
+    Bench(meta::BenchArgs),
+    /// Replay random event logs and check projector invariants
+    Selftest(meta::SelftestArgs),
     Repair(meta::RepairArgs),
-    Orphans,
+    Orphans(meta::OrphansArgs),
+    /// Summarize tasks by status as markdown, or HTML with --format html
+    Report(report::ReportArgs),
+    /// List tasks closed since a tag or date, grouped for a changelog
+    ReleaseNotes(release_notes::ReleaseNotesArgs),
+    /// Dump every task as JSON, optionally redacted for sharing
+    Export(export::ExportArgs),
     History(meta::HistoryArgs),
+    /// Show events across all tasks, newest first
+    Log(meta::LogArgs),
+    /// Summarize everything one actor did, with counts and a detail listing
+    Audit(audit::AuditArgs),
     Watch(meta::WatchArgs),
     Tui(meta::TuiArgs),
+    /// Serve frames over HTTP, with a read-only web dashboard under --ui
+    Serve(serve::ServeArgs),
     Create(task::CreateArgs),
     Show(task::ShowArgs),
+    /// Resolve many id prefixes, aliases, or external refs to canonical ids
+    Ids(ids::IdsArgs),
     Find(task::FindArgs),
+    /// Show one epic or feature with its descendants, blockers, and rollup
+    Tree(task::TreeArgs),
     Stale(task::StaleArgs),
+    /// List tasks whose effective priority was raised by the escalation policy
+    Escalations,
     Edit(task::EditArgs),
     Claim(task::ClaimArgs),
     Assign(task::AssignArgs),
+    /// Pass a claimed task to another actor with a handoff note
+    Handoff(task::HandoffArgs),
     Start(task::TaskIdArgs),
     Open(task::TaskIdArgs),
-    Blocked(task::TaskIdArgs),
+    Blocked(task::BlockedArgs),
     Planned(task::TaskIdArgs),
     NeedsPlan(task::TaskIdArgs),
     Defer(task::NoteStatusArgs),
-    Done(task::MultiStatusArgs),
+    Done(task::DoneArgs),
     Duplicate(task::DuplicateArgs),
     Duplicates(task::DuplicatesArgs),
     Supersede(task::SupersedeArgs),
     Merge(task::MergeArgs),
-    Reopen(task::MultiStatusArgs),
-    Cancel(task::MultiStatusArgs),
+    /// Split a task into new parts that inherit its labels, assignee, and relations
+    Split(task::SplitArgs),
+    /// Change a task's kind (task, feature, epic)
+    Convert(task::ConvertArgs),
+    /// Copy a task (and optionally its children) as new open tasks
+    Clone(task::CloneArgs),
+    Reopen(task::ReopenArgs),
+    Cancel(task::CancelArgs),
     Block(dep::BlockArgs),
     Unblock(dep::UnblockArgs),
     Order(dep::OrderArgs),
     Unorder(dep::UnorderArgs),
     Deps(dep::DepsArgs),
+    /// Explain the dependency or relation paths connecting two tasks
+    Why(dep::WhyArgs),
     Relate(link::RelateArgs),
     Unrelate(link::UnrelateArgs),
     Label(label::LabelArgs),
@@ -78,12 +142,47 @@ pub enum CommandKind {
     Note(note::NoteArgs),
     Notes(note::NoteListArgs),
     Spec(spec::SpecArgs),
+    /// Manage lightweight checklist items on a task
+    Check {
+        #[command(subcommand)]
+        command: checklist::CheckCommand,
+    },
+    /// Track acceptance criteria from a task's attached spec
+    Criteria {
+        #[command(subcommand)]
+        command: criteria::CriteriaCommand,
+    },
+    /// Apply a JSON array or NDJSON stream of operations in one transaction
+    Batch(batch::BatchArgs),
     Sync(sync::SyncArgs),
     Hooks {
         #[command(subcommand)]
         command: hooks::HooksCommand,
     },
+    /// Create or restore a checksummed archive of `.tasque`
+    Backup {
+        #[command(subcommand)]
+        command: backup::BackupCommand,
+    },
+    /// Cross-reference git history with tasks
+    Git {
+        #[command(subcommand)]
+        command: git::GitCommand,
+    },
+    /// Register actors with a display name, email, and type
+    Actor {
+        #[command(subcommand)]
+        command: actor::ActorCommand,
+    },
+    /// Show the acting name and which source in the resolution chain set it
+    Whoami,
+    /// Inspect or break the `.tasque` write lock
+    Lock {
+        #[command(subcommand)]
+        command: lock::LockCommand,
+    },
     /// Manage skills across AI coding targets
+    #[command(visible_alias = "skill")]
     Skills {
         #[command(subcommand)]
         command: skills::SkillsCommand,
@@ -113,64 +212,173 @@ pub fn run_cli(service: &TasqueService) -> i32 {
             GlobalOpts {
                 json: false,
                 exact_id: false,
+                dry_run: false,
+                porcelain: None,
             },
         );
     }
 
-    let cli = match Cli::try_parse() {
-        Ok(parsed) => parsed,
-        Err(error) => return handle_parse_error(service, error),
-    };
-    let opts = match global_opts(cli.json, cli.format, cli.exact_id) {
-        Ok(opts) => opts,
-        Err(error) => {
-            let fallback_opts = GlobalOpts {
-                json: true,
-                exact_id: cli.exact_id,
-            };
-            return emit_error("tsq", fallback_opts, error);
+    // An ambiguous id picked interactively re-runs the command with that id.
+    let mut args = raw_args;
+    loop {
+        let mut cli = match Cli::try_parse_from(&args) {
+            Ok(parsed) => parsed,
+            Err(error) => return handle_parse_error(service, error),
+        };
+        timestamps::set_mode(cli.timestamps.unwrap_or_default());
+        let opts = match global_opts(
+            cli.json,
+            cli.format,
+            cli.exact_id,
+            cli.dry_run,
+            cli.porcelain,
+        ) {
+            Ok(opts) => opts,
+            Err(error) => {
+                let fallback_opts = GlobalOpts {
+                    json: true,
+                    exact_id: cli.exact_id,
+                    dry_run: cli.dry_run,
+                    porcelain: cli.porcelain,
+                };
+                return emit_error("tsq", fallback_opts, error);
+            }
+        };
+        if let Some(format @ (FormatArg::Html | FormatArg::Markdown)) = cli.format {
+            match (format, &mut cli.command) {
+                (FormatArg::Html, CommandKind::Report(args)) => args.html = true,
+                (FormatArg::Markdown, CommandKind::ReleaseNotes(args)) => args.markdown = true,
+                (_, command) => {
+                    let command_line = format!("tsq {}", root_command_name(command));
+                    let name = match format {
+                        FormatArg::Markdown => "markdown",
+                        _ => "html",
+                    };
+                    return emit_error(
+                        &command_line,
+                        opts,
+                        TsqError::new(
+                            "VALIDATION_ERROR",
+                            format!("{} does not support --format {}", command_line, name),
+                            1,
+                        ),
+                    );
+                }
+            }
         }
-    };
-    execute_command(service, cli.command, opts)
+        let interactive = !opts.json()
+            && !cli.no_interactive
+            && std::io::stdin().is_terminal()
+            && std::io::stdout().is_terminal();
+        disambiguate::set_pick_args(interactive.then(|| args.clone()));
+        let code = execute_command(service, cli.command, opts);
+        disambiguate::set_pick_args(None);
+        match disambiguate::take_retry_args() {
+            Some(retry) => args = retry,
+            None => return code,
+        }
+    }
 }
 
 fn execute_command(service: &TasqueService, command: CommandKind, opts: GlobalOpts) -> i32 {
     if !is_init_safe_command(&command) && find_tasque_root().is_none() {
-        let code = "NOT_INITIALIZED";
-        let message = "No .tasque directory found. Run 'tsq init' first.";
-        if opts.json() {
-            let command_line = format!("tsq {}", root_command_name(&command));
-            let envelope = err_envelope(
-                command_line,
-                code,
-                message,
-                Option::<serde_json::Value>::None,
+        let command_line = format!("tsq {}", root_command_name(&command));
+        return emit_error(
+            &command_line,
+            opts,
+            TsqError::new(
+                "NOT_INITIALIZED",
+                "No .tasque directory found. Run 'tsq init' first.",
+                2,
+            ),
+        );
+    }
+    style::set_label_colors(service.label_colors());
+
+    if opts.porcelain.is_some() && !supports_porcelain(&command) {
+        let command_line = format!("tsq {}", root_command_name(&command));
+        return emit_error(
+            &command_line,
+            opts,
+            TsqError::new(
+                "VALIDATION_ERROR",
+                format!("{} does not support --porcelain", command_line),
+                1,
+            ),
+        );
+    }
+
+    if opts.dry_run && previews_skill_files(&command) {
+        return dispatch_command(service, command, opts);
+    }
+    if opts.dry_run {
+        if let Some(subcommand) = writes_outside_event_log(&command) {
+            let command_line = format!("tsq {}", subcommand);
+            return emit_error(
+                &command_line,
+                opts,
+                TsqError::new(
+                    "DRY_RUN_UNSUPPORTED",
+                    format!(
+                        "{} has effects outside the event log and cannot be previewed with --dry-run",
+                        command_line
+                    ),
+                    1,
+                ),
             );
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&envelope).unwrap_or_else(|_| "{}".to_string())
+        }
+        if !supports_dry_run(&command) {
+            let command_line = format!("tsq {}", root_command_name(&command));
+            return emit_error(
+                &command_line,
+                opts,
+                TsqError::new(
+                    "VALIDATION_ERROR",
+                    format!("{} does not support --dry-run", command_line),
+                    1,
+                ),
             );
-        } else {
-            eprintln!("{}: {}", code, message);
         }
-        return 2;
+        let (preview, handle) = service.preview();
+        set_dry_run_preview(Some(handle));
+        let code = dispatch_command(&preview, command, opts);
+        set_dry_run_preview(None);
+        return code;
     }
 
+    dispatch_command(service, command, opts)
+}
+
+fn dispatch_command(service: &TasqueService, command: CommandKind, opts: GlobalOpts) -> i32 {
     match command {
         CommandKind::Init(args) => meta::execute_init(service, args, opts),
         CommandKind::Doctor => meta::execute_doctor(service, opts),
         CommandKind::Repair(args) => meta::execute_repair(service, args, opts),
-        CommandKind::Orphans => meta::execute_orphans(service, opts),
+        CommandKind::Orphans(args) => meta::execute_orphans(service, args, opts),
+        CommandKind::Report(args) => report::execute_report(service, args, opts),
+        CommandKind::Export(args) => export::execute_export(service, args, opts),
+        CommandKind::Bench(args) => meta::execute_bench(service, args, opts),
+        CommandKind::Selftest(args) => meta::execute_selftest(args, opts),
         CommandKind::History(args) => meta::execute_history(service, args, opts),
+        CommandKind::Log(args) => meta::execute_log(service, args, opts),
+        CommandKind::Audit(args) => audit::execute_audit(service, args, opts),
+        CommandKind::ReleaseNotes(args) => {
+            release_notes::execute_release_notes(service, args, opts)
+        }
         CommandKind::Watch(args) => meta::execute_watch(service, args, opts),
         CommandKind::Tui(args) => meta::execute_tui(service, args, opts),
+        CommandKind::Serve(args) => serve::execute_serve(service, args, opts),
         CommandKind::Create(args) => task::execute_create(service, args, opts),
         CommandKind::Show(args) => task::execute_show(service, args, opts),
+        CommandKind::Ids(args) => ids::execute_ids(service, args, opts),
         CommandKind::Find(args) => task::execute_find(service, args, opts),
+        CommandKind::Tree(args) => task::execute_tree(service, args, opts),
         CommandKind::Stale(args) => task::execute_stale(service, args, opts),
+        CommandKind::Escalations => task::execute_escalations(service, opts),
         CommandKind::Edit(args) => task::execute_edit(service, args, opts),
         CommandKind::Claim(args) => task::execute_claim(service, args, opts),
         CommandKind::Assign(args) => task::execute_assign(service, args, opts),
+        CommandKind::Handoff(args) => task::execute_handoff(service, args, opts),
         CommandKind::Start(args) => task::execute_set_status(
             service,
             args,
@@ -185,13 +393,7 @@ fn execute_command(service: &TasqueService, command: CommandKind, opts: GlobalOp
             "tsq open",
             opts,
         ),
-        CommandKind::Blocked(args) => task::execute_set_status(
-            service,
-            args,
-            crate::types::TaskStatus::Blocked,
-            "tsq blocked",
-            opts,
-        ),
+        CommandKind::Blocked(args) => task::execute_blocked(service, args, opts),
         CommandKind::Planned(args) => task::execute_set_planning(
             service,
             args,
@@ -212,6 +414,9 @@ fn execute_command(service: &TasqueService, command: CommandKind, opts: GlobalOp
         CommandKind::Duplicates(args) => task::execute_duplicates(service, args, opts),
         CommandKind::Supersede(args) => task::execute_supersede(service, args, opts),
         CommandKind::Merge(args) => task::execute_merge(service, args, opts),
+        CommandKind::Split(args) => task::execute_split(service, args, opts),
+        CommandKind::Convert(args) => task::execute_convert(service, args, opts),
+        CommandKind::Clone(args) => task::execute_clone(service, args, opts),
         CommandKind::Reopen(args) => task::execute_reopen(service, args, opts),
         CommandKind::Cancel(args) => task::execute_cancel(service, args, opts),
         CommandKind::Block(args) => dep::execute_block(service, args, opts),
@@ -219,6 +424,7 @@ fn execute_command(service: &TasqueService, command: CommandKind, opts: GlobalOp
         CommandKind::Order(args) => dep::execute_order(service, args, opts),
         CommandKind::Unorder(args) => dep::execute_unorder(service, args, opts),
         CommandKind::Deps(args) => dep::execute_deps(service, args, opts),
+        CommandKind::Why(args) => dep::execute_why(service, args, opts),
         CommandKind::Relate(args) => link::execute_relate(service, args, opts),
         CommandKind::Unrelate(args) => link::execute_unrelate(service, args, opts),
         CommandKind::Label(args) => label::execute_label_add(service, args, opts),
@@ -227,8 +433,16 @@ fn execute_command(service: &TasqueService, command: CommandKind, opts: GlobalOp
         CommandKind::Note(args) => note::execute_note_verb(service, args, opts),
         CommandKind::Notes(args) => note::execute_notes_verb(service, args, opts),
         CommandKind::Spec(args) => spec::execute_spec_verb(service, args, opts),
+        CommandKind::Batch(args) => batch::execute_batch(service, args, opts),
         CommandKind::Sync(args) => sync::execute_sync(service, args, opts),
+        CommandKind::Check { command } => checklist::execute_check(service, command, opts),
+        CommandKind::Criteria { command } => criteria::execute_criteria(service, command, opts),
         CommandKind::Hooks { command } => hooks::execute_hooks(service, command, opts),
+        CommandKind::Backup { command } => backup::execute_backup(service, command, opts),
+        CommandKind::Git { command } => git::execute_git(service, command, opts),
+        CommandKind::Actor { command } => actor::execute_actor(service, command, opts),
+        CommandKind::Whoami => actor::execute_whoami(service, opts),
+        CommandKind::Lock { command } => lock::execute_lock(service, command, opts),
         CommandKind::Skills { command } => skills::execute_skills(service, command, opts),
         CommandKind::Migrate(args) => sync::execute_migrate(service, args, opts),
         CommandKind::MergeDriver(args) => sync::execute_merge_driver(args),
@@ -241,6 +455,12 @@ fn handle_parse_error(service: &TasqueService, error: clap::Error) -> i32 {
         return 0;
     }
 
+    if error.kind() == ErrorKind::InvalidSubcommand
+        && let Some(exit_code) = try_run_plugin(service)
+    {
+        return exit_code;
+    }
+
     if is_missing_subcommand_error(error.kind()) {
         let opts = parse_global_opts_from_env();
         if opts.json() {
@@ -280,6 +500,25 @@ fn handle_parse_error(service: &TasqueService, error: clap::Error) -> i32 {
     exit_code
 }
 
+/// Dispatches an unknown subcommand to an executable `tsq-<name>` on PATH.
+fn try_run_plugin(service: &TasqueService) -> Option<i32> {
+    let args: Vec<String> = std::env::args().collect();
+    let (index, name) = first_command_token(&args)?;
+    let plugin_path = plugin::find_plugin(name)?;
+    let opts = parse_global_opts_from_args(&args);
+    let env = plugin::PluginEnv {
+        repo_root: service.repo_root(),
+        actor: service.actor(),
+        opts,
+    };
+    Some(
+        match plugin::run_plugin(&plugin_path, &args[index + 1..], &env) {
+            Ok(code) => code,
+            Err(error) => emit_error(&format!("tsq {}", name), opts, error),
+        },
+    )
+}
+
 fn parse_global_opts_from_env() -> GlobalOpts {
     let args: Vec<String> = std::env::args().collect();
     parse_global_opts_from_args(&args)
@@ -288,16 +527,28 @@ fn parse_global_opts_from_env() -> GlobalOpts {
 fn parse_global_opts_from_args(args: &[String]) -> GlobalOpts {
     let mut json = false;
     let mut exact_id = false;
+    let mut dry_run = false;
+    let mut porcelain = None;
     let mut format = None;
     let mut iter = args.iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
             "--json" => json = true,
             "--exact-id" => exact_id = true,
+            "--dry-run" => dry_run = true,
+            "--porcelain" => {
+                porcelain = match iter.next().map(String::as_str) {
+                    Some("v1") => Some(PorcelainVersion::V1),
+                    _ => porcelain,
+                };
+            }
+            "--porcelain=v1" => porcelain = Some(PorcelainVersion::V1),
             "--format" => {
                 format = match iter.next().map(String::as_str) {
                     Some("json") => Some(FormatArg::Json),
                     Some("human") => Some(FormatArg::Human),
+                    Some("html") => Some(FormatArg::Html),
+                    Some("markdown") => Some(FormatArg::Markdown),
                     _ => format,
                 };
             }
@@ -305,20 +556,43 @@ fn parse_global_opts_from_args(args: &[String]) -> GlobalOpts {
                 format = match value.strip_prefix("--format=") {
                     Some("json") => Some(FormatArg::Json),
                     Some("human") => Some(FormatArg::Human),
+                    Some("html") => Some(FormatArg::Html),
+                    Some("markdown") => Some(FormatArg::Markdown),
                     _ => format,
                 };
             }
             _ => {}
         }
     }
-    global_opts(json, format, exact_id).unwrap_or(GlobalOpts { json, exact_id })
+    global_opts(json, format, exact_id, dry_run, porcelain).unwrap_or(GlobalOpts {
+        json: json || porcelain.is_some(),
+        exact_id,
+        dry_run,
+        porcelain,
+    })
 }
 
 fn global_opts(
     json: bool,
     format: Option<FormatArg>,
     exact_id: bool,
+    dry_run: bool,
+    porcelain: Option<PorcelainVersion>,
 ) -> Result<GlobalOpts, TsqError> {
+    if porcelain.is_some() && matches!(format, Some(FormatArg::Html | FormatArg::Markdown)) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "cannot combine --porcelain with --format html or markdown",
+            1,
+        ));
+    }
+    if porcelain.is_some() && matches!(format, Some(FormatArg::Human)) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "cannot combine --porcelain with --format human",
+            1,
+        ));
+    }
     if json && matches!(format, Some(FormatArg::Human)) {
         return Err(TsqError::new(
             "VALIDATION_ERROR",
@@ -326,14 +600,30 @@ fn global_opts(
             1,
         ));
     }
+    if json && matches!(format, Some(FormatArg::Html)) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "cannot combine --json with --format html",
+            1,
+        ));
+    }
+    if json && matches!(format, Some(FormatArg::Markdown)) {
+        return Err(TsqError::new(
+            "VALIDATION_ERROR",
+            "cannot combine --json with --format markdown",
+            1,
+        ));
+    }
     let format = if json || matches!(format, Some(FormatArg::Json)) {
         OutputFormat::Json
     } else {
         OutputFormat::Human
     };
     Ok(GlobalOpts {
-        json: json || matches!(format, OutputFormat::Json),
+        json: json || porcelain.is_some() || matches!(format, OutputFormat::Json),
         exact_id,
+        dry_run,
+        porcelain,
     })
 }
 
@@ -344,7 +634,16 @@ fn removed_command_hint(args: &[String]) -> Option<&'static str> {
         "ready" => Some("use `tsq find ready --lane coding`"),
         "search" => Some("use `tsq find search \"query\"`"),
         "update" => Some("use `tsq edit <id> ...` or lifecycle verbs like `tsq done <id>`"),
-        "close" => Some("use `tsq done <id>`"),
+        "close" => Some("use `tsq done <id>` or `tsq done --filter <query> --yes`"),
+        "dep" if args.get(root_index + 1).map(String::as_str) == Some("why") => {
+            Some("use `tsq why <task> <other>`")
+        }
+        "dep" if args.get(root_index + 1).map(String::as_str) == Some("import") => {
+            Some("use `tsq block --from-file <file|->`")
+        }
+        "dep" if args.get(root_index + 1).map(String::as_str) == Some("export") => {
+            Some("use `tsq deps <id> --flat --json`")
+        }
         "dep" => Some("use `tsq block <task> by <blocker>` or `tsq order <later> after <earlier>`"),
         "link" => Some("use `tsq relate <a> <b>`"),
         "label" if args.get(root_index + 1).map(String::as_str) == Some("add") => {
@@ -380,7 +679,7 @@ fn first_command_token(args: &[String]) -> Option<(usize, &str)> {
             "--json" | "--exact-id" => {
                 index += 1;
             }
-            "--format" => {
+            "--format" | "--timestamps" | "--porcelain" => {
                 index += 2;
             }
             _ if arg.starts_with("--format=") => {
@@ -414,26 +713,147 @@ fn is_init_safe_command(command: &CommandKind) -> bool {
         CommandKind::Init(_)
             | CommandKind::Doctor
             | CommandKind::MergeDriver(_)
+            | CommandKind::Selftest(_)
+            | CommandKind::Whoami
             | CommandKind::Skills { .. }
     )
 }
 
+/// Skill install/uninstall/refresh: `--dry-run` reports per-target file
+/// changes instead of staged events.
+fn previews_skill_files(command: &CommandKind) -> bool {
+    match command {
+        CommandKind::Init(args) => args.install_skill || args.uninstall_skill,
+        CommandKind::Skills { command } => matches!(command, skills::SkillsCommand::Refresh(_)),
+        _ => false,
+    }
+}
+
+/// Commands with a frozen `--porcelain` shape: task listings and `show`.
+fn supports_porcelain(command: &CommandKind) -> bool {
+    match command {
+        CommandKind::Show(_) => true,
+        CommandKind::Find(args) => match &args.command {
+            task::FindCommand::Ready(args) => {
+                !args.watch && !args.filter.tree && args.filter.count_by.is_none()
+            }
+            task::FindCommand::Open(args)
+            | task::FindCommand::InProgress(args)
+            | task::FindCommand::Blocked(args)
+            | task::FindCommand::Deferred(args)
+            | task::FindCommand::Done(args)
+            | task::FindCommand::Canceled(args) => !args.tree && args.count_by.is_none(),
+            task::FindCommand::Status(args) => !args.filter.tree && args.filter.count_by.is_none(),
+            task::FindCommand::Search(args) => {
+                args.count_by.is_none()
+                    && split_count_clause(&args.query).is_ok_and(|(_, field)| field.is_none())
+            }
+            task::FindCommand::Similar(_) => false,
+        },
+        _ => false,
+    }
+}
+
+/// Mutating commands whose side effects bypass the staged service (an editor,
+/// an output file), so a preview would not be side-effect free.
+fn writes_outside_event_log(command: &CommandKind) -> Option<&'static str> {
+    match command {
+        CommandKind::Spec(spec::SpecArgs {
+            command: Some(spec::SpecVerbCommand::New(_)),
+            ..
+        }) => Some("spec new"),
+        CommandKind::Spec(spec::SpecArgs {
+            command: Some(spec::SpecVerbCommand::Render(_)),
+            ..
+        }) => Some("spec render"),
+        _ => None,
+    }
+}
+
+/// Commands whose writes go through the event log and can be staged.
+fn supports_dry_run(command: &CommandKind) -> bool {
+    matches!(
+        command,
+        CommandKind::Create(_)
+            | CommandKind::Edit(_)
+            | CommandKind::Claim(_)
+            | CommandKind::Assign(_)
+            | CommandKind::Handoff(_)
+            | CommandKind::Start(_)
+            | CommandKind::Open(_)
+            | CommandKind::Blocked(_)
+            | CommandKind::Planned(_)
+            | CommandKind::NeedsPlan(_)
+            | CommandKind::Defer(_)
+            | CommandKind::Stale(_)
+            | CommandKind::Orphans(_)
+            | CommandKind::Done(_)
+            | CommandKind::Duplicate(_)
+            | CommandKind::Supersede(_)
+            | CommandKind::Merge(_)
+            | CommandKind::Split(_)
+            | CommandKind::Convert(_)
+            | CommandKind::Clone(_)
+            | CommandKind::Reopen(_)
+            | CommandKind::Cancel(_)
+            | CommandKind::Block(_)
+            | CommandKind::Unblock(_)
+            | CommandKind::Order(_)
+            | CommandKind::Unorder(_)
+            | CommandKind::Relate(_)
+            | CommandKind::Unrelate(_)
+            | CommandKind::Label(_)
+            | CommandKind::Unlabel(_)
+            | CommandKind::Note(_)
+            | CommandKind::Spec(spec::SpecArgs {
+                command: None | Some(spec::SpecVerbCommand::Reattach(_)),
+                show: false,
+                check: false,
+                ..
+            })
+            | CommandKind::Check {
+                command: checklist::CheckCommand::Add(_)
+                    | checklist::CheckCommand::Done(_)
+                    | checklist::CheckCommand::Undo(_)
+            }
+            | CommandKind::Criteria {
+                command: criteria::CriteriaCommand::Check(_)
+            }
+            | CommandKind::Batch(_)
+            | CommandKind::Actor {
+                command: actor::ActorCommand::Add(_) | actor::ActorCommand::Remove(_)
+            }
+    )
+}
+
 fn root_command_name(command: &CommandKind) -> &'static str {
     match command {
         CommandKind::Init(_) => "init",
         CommandKind::Doctor => "doctor",
         CommandKind::Repair(_) => "repair",
-        CommandKind::Orphans => "orphans",
+        CommandKind::Orphans(_) => "orphans",
+        CommandKind::Report(_) => "report",
+        CommandKind::Export(_) => "export",
+        CommandKind::Bench(_) => "bench",
+        CommandKind::Selftest(_) => "selftest",
         CommandKind::History(_) => "history",
+        CommandKind::Log(_) => "log",
+        CommandKind::Audit(_) => "audit",
+        CommandKind::ReleaseNotes(_) => "release-notes",
         CommandKind::Watch(_) => "watch",
         CommandKind::Tui(_) => "tui",
+        CommandKind::Serve(_) => "serve",
         CommandKind::Create(_) => "create",
         CommandKind::Show(_) => "show",
+        CommandKind::Ids(_) => "ids",
         CommandKind::Find(_) => "find",
+        CommandKind::Tree(_) => "tree",
         CommandKind::Stale(_) => "stale",
+        CommandKind::Escalations => "escalations",
         CommandKind::Edit(_) => "edit",
         CommandKind::Claim(_) => "claim",
         CommandKind::Assign(_) => "assign",
+        CommandKind::Handoff(_) => "handoff",
         CommandKind::Start(_) => "start",
         CommandKind::Open(_) => "open",
         CommandKind::Blocked(_) => "blocked",
@@ -445,6 +865,9 @@ fn root_command_name(command: &CommandKind) -> &'static str {
         CommandKind::Duplicates(_) => "duplicates",
         CommandKind::Supersede(_) => "supersede",
         CommandKind::Merge(_) => "merge",
+        CommandKind::Split(_) => "split",
+        CommandKind::Convert(_) => "convert",
+        CommandKind::Clone(_) => "clone",
         CommandKind::Reopen(_) => "reopen",
         CommandKind::Cancel(_) => "cancel",
         CommandKind::Block(_) => "block",
@@ -452,6 +875,7 @@ fn root_command_name(command: &CommandKind) -> &'static str {
         CommandKind::Order(_) => "order",
         CommandKind::Unorder(_) => "unorder",
         CommandKind::Deps(_) => "deps",
+        CommandKind::Why(_) => "why",
         CommandKind::Relate(_) => "relate",
         CommandKind::Unrelate(_) => "unrelate",
         CommandKind::Label(_) => "label",
@@ -460,8 +884,16 @@ fn root_command_name(command: &CommandKind) -> &'static str {
         CommandKind::Note(_) => "note",
         CommandKind::Notes(_) => "notes",
         CommandKind::Spec(_) => "spec",
+        CommandKind::Check { .. } => "check",
+        CommandKind::Criteria { .. } => "criteria",
+        CommandKind::Batch(_) => "batch",
         CommandKind::Sync(_) => "sync",
         CommandKind::Hooks { .. } => "hooks",
+        CommandKind::Backup { .. } => "backup",
+        CommandKind::Git { .. } => "git",
+        CommandKind::Actor { .. } => "actor",
+        CommandKind::Whoami => "whoami",
+        CommandKind::Lock { .. } => "lock",
         CommandKind::Skills { .. } => "skills",
         CommandKind::Migrate(_) => "migrate",
         CommandKind::MergeDriver(_) => "merge-driver",
@@ -1,9 +1,20 @@
+use crate::app::selftest::SelftestResult;
 use crate::app::service_query::ShowResult;
-use crate::app::service_types::{HistoryResult, MergeResult, OrphansResult, SpecContentResult};
+use crate::app::service_types::{
+    BenchResult, BenchStats, HistoryResult, MergeResult, OrphansAdoptResult, OrphansResult,
+    RollupTask, SpecContentResult, SubtreeResult,
+};
 use crate::cli::style;
 use crate::cli::terminal::{Density, resolve_density, resolve_width};
-use crate::domain::dep_tree::DepTreeNode;
-use crate::types::{RepairResult, Task, TaskNote, TaskStatus, TaskTreeNode};
+use crate::cli::timestamps::format_timestamp;
+use crate::domain::aggregate::CountResult;
+use crate::domain::criteria::AcceptanceCriterion;
+use crate::domain::dep_tree::{DepGraph, DepTreeNode};
+use crate::domain::rollup::ChildProgress;
+use crate::skills::types::{SkillFileChange, SkillFileDiff, SkillOperationSummary};
+use crate::types::{
+    ChecklistItem, EventRecord, RepairResult, Task, TaskNote, TaskStatus, TaskTreeNode,
+};
 use std::collections::HashMap;
 
 pub struct TreeRenderOptions {
@@ -12,25 +23,72 @@ pub struct TreeRenderOptions {
 
 const MAX_NARROW_TREE_PREFIX_WIDTH: usize = 24;
 
+type TableRow<'a> = (&'a Task, Option<&'a ChildProgress>, Option<&'a str>);
+
 pub fn print_task_list(tasks: &[Task]) {
+    let rows: Vec<TableRow> = tasks.iter().map(|task| (task, None, None)).collect();
+    print_task_table(&rows, false);
+}
+
+/// Task table with a PROGRESS column when `show_progress` is set and a
+/// WORKSPACE column when rows come from `find --workspace`.
+pub fn print_rollup_list(rows: &[RollupTask], show_progress: bool) {
+    let rows: Vec<TableRow> = rows
+        .iter()
+        .map(|row| (&row.task, row.rollup.as_ref(), row.workspace.as_deref()))
+        .collect();
+    print_task_table(&rows, show_progress);
+}
+
+fn print_task_table(tasks: &[TableRow], show_progress: bool) {
     if tasks.is_empty() {
         println!("{}", style::muted("no tasks"));
         return;
     }
 
-    let header = ["ID", "ALIAS", "P", "KIND", "STATUS", "ASSIGNEE", "TITLE"];
+    let show_workspace = tasks.iter().any(|(_, _, workspace)| workspace.is_some());
+    let show_labels = tasks.iter().any(|(task, _, _)| !task.labels.is_empty());
+    let mut header = vec!["ID", "ALIAS", "P", "KIND", "STATUS", "ASSIGNEE"];
+    if show_progress {
+        header.push("PROGRESS");
+    }
+    if show_workspace {
+        header.push("WORKSPACE");
+    }
+    if show_labels {
+        header.push("LABELS");
+    }
+    let labels_index = show_labels.then(|| header.len() - 1);
+    header.push("UPDATED");
+    header.push("TITLE");
     let rows: Vec<Vec<String>> = tasks
         .iter()
-        .map(|task| {
-            vec![
+        .map(|(task, progress, workspace)| {
+            let mut row = vec![
                 task.id.clone(),
                 task.alias.clone(),
-                task.priority.to_string(),
+                priority_label(task),
                 task_kind_to_string(task.kind).to_string(),
-                status_to_string(task.status).to_string(),
+                task.status.as_str().to_string(),
                 task.assignee.clone().unwrap_or_else(|| "-".to_string()),
-                task.title.clone(),
-            ]
+            ];
+            if show_progress {
+                row.push(match progress {
+                    Some(progress) => {
+                        format!("{}/{} {}%", progress.done, progress.total, progress.percent)
+                    }
+                    None => "-".to_string(),
+                });
+            }
+            if show_workspace {
+                row.push(workspace.unwrap_or("-").to_string());
+            }
+            if show_labels {
+                row.push(label_pills_text(&task.labels));
+            }
+            row.push(format_timestamp(&task.updated_at));
+            row.push(task.title.clone());
+            row
         })
         .collect();
 
@@ -56,7 +114,7 @@ pub fn print_task_list(tasks: &[Task]) {
         )
     );
 
-    for row in &rows {
+    for ((task, _, _), row) in tasks.iter().zip(&rows) {
         let formatted_cells = row
             .iter()
             .enumerate()
@@ -64,10 +122,16 @@ pub fn print_task_list(tasks: &[Task]) {
                 let padded = format!("{:width$}", cell, width = widths[index]);
                 if index == 0 {
                     style::task_id(&padded)
+                } else if Some(index) == labels_index && !task.labels.is_empty() {
+                    format!(
+                        "{}{}",
+                        style::label_pills(&task.labels),
+                        " ".repeat(widths[index] - cell.len())
+                    )
                 } else if index == 4 {
                     style::status(
                         &padded,
-                        parse_status_label(cell.as_str()).unwrap_or(TaskStatus::Open),
+                        &TaskStatus::from_name(cell).unwrap_or(TaskStatus::Open),
                     )
                 } else {
                     padded
@@ -85,16 +149,18 @@ pub fn print_task(task: &Task) {
         style::key("kind"),
         task_kind_to_string(task.kind),
         style::key("status"),
-        status_to_string(task.status),
+        task.status.as_str(),
         style::key("priority"),
         task.priority
     );
-    if let Some(planning_state) = task.planning_state {
-        println!(
-            "{}={}",
-            style::key("planning"),
-            planning_state_to_string(planning_state)
-        );
+    if let Some(planning_state) = &task.planning_state {
+        println!("{}={}", style::key("planning"), planning_state.as_str());
+    }
+    if let Some(defer_until) = &task.defer_until {
+        println!("{}={}", style::key("defer_until"), defer_until);
+    }
+    if let Some(blocked_on) = &task.blocked_on {
+        println!("{}={}", style::key("blocked_on"), blocked_on);
     }
     if let Some(assignee) = &task.assignee {
         println!("{}={}", style::key("assignee"), assignee);
@@ -176,6 +242,38 @@ pub fn print_show_result(data: &ShowResult) {
     if !data.history.is_empty() {
         println!("{}={}", style::key("history_events"), data.history.len());
     }
+    if !data.task.checklist.is_empty() {
+        print_checklist(&data.task.checklist);
+    }
+    if !data.acceptance_criteria.is_empty() {
+        print_acceptance_criteria(&data.acceptance_criteria);
+    }
+}
+
+pub fn print_checklist(items: &[ChecklistItem]) {
+    let done = items.iter().filter(|item| item.done).count();
+    println!("{}={}/{}", style::key("checklist"), done, items.len());
+    for (position, item) in items.iter().enumerate() {
+        let mark = if item.done { "x" } else { " " };
+        println!("  {}. [{}] {}", position + 1, mark, item.text);
+    }
+}
+
+pub fn print_acceptance_criteria(criteria: &[AcceptanceCriterion]) {
+    let done = criteria
+        .iter()
+        .filter(|criterion| criterion.checked)
+        .count();
+    println!(
+        "{}={}/{}",
+        style::key("acceptance_criteria"),
+        done,
+        criteria.len()
+    );
+    for criterion in criteria {
+        let mark = if criterion.checked { "x" } else { " " };
+        println!("  {}. [{}] {}", criterion.index, mark, criterion.text);
+    }
 }
 
 pub fn print_spec_content(data: &SpecContentResult) {
@@ -193,6 +291,27 @@ pub fn print_task_tree(nodes: &[TaskTreeNode]) {
     }
 }
 
+/// `tsq tree`: the rooted tree, then completion across all descendants,
+/// including any `--depth` left out.
+pub fn print_subtree(result: &SubtreeResult) {
+    print_task_tree(std::slice::from_ref(&result.tree));
+    let progress = &result.rollup;
+    let mut line = format!(
+        "{}={}/{} {}%",
+        style::key("rollup"),
+        progress.done,
+        progress.total,
+        progress.percent
+    );
+    if result.hidden > 0 {
+        line.push_str(&format!(
+            " {}",
+            style::muted(&format!("hidden={} (beyond --depth)", result.hidden))
+        ));
+    }
+    println!("{}", line);
+}
+
 pub fn render_task_tree(nodes: &[TaskTreeNode], options: TreeRenderOptions) -> Vec<String> {
     if nodes.is_empty() {
         return vec![style::muted("no tasks")];
@@ -224,6 +343,23 @@ pub fn render_task_tree(nodes: &[TaskTreeNode], options: TreeRenderOptions) -> V
         totals.get("closed").copied().unwrap_or(0),
         totals.get("canceled").copied().unwrap_or(0)
     ));
+    let mut custom: Vec<(&str, usize)> = totals
+        .iter()
+        .filter(|(key, _)| {
+            **key != "total"
+                && !TaskStatus::BUILTIN
+                    .iter()
+                    .any(|status| status.as_str() == **key)
+        })
+        .map(|(key, count)| (*key, *count))
+        .collect();
+    if !custom.is_empty() {
+        custom.sort();
+        let last = lines.last_mut().expect("totals line");
+        for (status, count) in custom {
+            last.push_str(&format!(" {}={}", status, count));
+        }
+    }
     lines
 }
 
@@ -262,8 +398,8 @@ fn render_tree_node(
         child_prefix.clone()
     };
 
-    let status = format_status(node.task.status);
-    let status_text = format_status_text(node.task.status);
+    let status = format_status(&node.task.status);
+    let status_text = format_status_text(&node.task.status);
     let flow = format_flow(node);
     let meta_text = format_meta_badge_text(&node.task);
     let meta = style::meta(&meta_text);
@@ -299,6 +435,10 @@ fn render_tree_node(
         Density::Medium | Density::Wide => {
             primary_parts.push(node.task.title.clone());
             primary_parts.push(meta);
+            if !node.task.labels.is_empty() {
+                primary_parts.push(style::label_pills(&node.task.labels));
+            }
+            primary_parts.push(style::muted(&format_timestamp(&node.task.updated_at)));
             if density == Density::Wide
                 && let Some(flow) = &flow
             {
@@ -381,6 +521,36 @@ pub fn print_repair_result(result: &RepairResult) {
             ""
         }
     );
+    if let Some(salvage) = &result.salvage {
+        println!(
+            "salvage_kept={} quarantined={}{}",
+            salvage.kept_events,
+            salvage.quarantined.len(),
+            if result.applied && !salvage.quarantined.is_empty() {
+                format!(" (moved to {})", salvage.quarantine_file)
+            } else {
+                String::new()
+            }
+        );
+        for lost in &salvage.quarantined {
+            println!("  line {}: {}", lost.line, lost.reason);
+        }
+    }
+    if let Some(snapshots) = &result.snapshots {
+        println!(
+            "snapshots_removed={} rebuilt_at_event={}{}",
+            snapshots.removed.len(),
+            snapshots
+                .rebuilt_at_event
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+            if snapshots.verified {
+                " (verified)"
+            } else {
+                ""
+            }
+        );
+    }
     if result.applied {
         println!("events_appended={}", result.events_appended);
         println!("files_removed={}", result.files_removed);
@@ -402,6 +572,16 @@ pub fn print_merge_result(result: &MergeResult) {
         result.target.title,
         result.target.status
     );
+    if !result.strategy.is_empty() {
+        let pairs = serde_json::to_value(&result.strategy)
+            .ok()
+            .and_then(|value| value.as_object().cloned())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(field, chosen)| format!("{}={}", field, chosen.as_str().unwrap_or_default()))
+            .collect::<Vec<_>>();
+        println!("{}={}", style::key("strategy"), pairs.join(","));
+    }
     if let Some(summary) = &result.plan_summary {
         println!(
             "plan=requested:{} merged:{} skipped:{} events:{}",
@@ -419,18 +599,18 @@ pub fn print_merge_result(result: &MergeResult) {
         println!(
             "projected_target={} [{}] planning={}",
             projected.target.id,
-            status_to_string(projected.target.status),
+            projected.target.status.as_str(),
             projected
                 .target
                 .planning_state
-                .map(planning_state_to_string)
-                .unwrap_or("needs_planning")
+                .as_ref()
+                .map_or("needs_planning", crate::types::PlanningState::as_str)
         );
         for source in &projected.sources {
             println!(
                 "  projected_source={} [{}] duplicate_of={}",
                 source.id,
-                status_to_string(source.status),
+                source.status.as_str(),
                 source.duplicate_of.as_deref().unwrap_or("-")
             );
         }
@@ -440,6 +620,18 @@ pub fn print_merge_result(result: &MergeResult) {
     }
 }
 
+/// Uncolored `[a] [b]` text, for measuring columns before painting.
+fn label_pills_text(labels: &[String]) -> String {
+    if labels.is_empty() {
+        return "-".to_string();
+    }
+    labels
+        .iter()
+        .map(|label| format!("[{}]", label))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn format_meta_badge(task: &Task) -> String {
     style::meta(&format_meta_badge_text(task))
 }
@@ -450,7 +642,17 @@ fn format_meta_badge_text(task: &Task) -> String {
         .as_ref()
         .map(|value| format!(" @{}", value))
         .unwrap_or_default();
-    format!("[p{}{}]", task.priority, assignee)
+    format!("[p{}{}]", priority_label(task), assignee)
+}
+
+/// Stored priority, followed by the escalated one when the policy raised it.
+pub fn priority_label(task: &Task) -> String {
+    match task.effective_priority {
+        Some(effective) if effective != task.priority => {
+            format!("{}→{}", task.priority, effective)
+        }
+        _ => task.priority.to_string(),
+    }
 }
 
 fn format_flow(node: &TaskTreeNode) -> Option<String> {
@@ -564,42 +766,35 @@ pub fn truncate_with_ellipsis(value: &str, max_length: usize) -> String {
     format!("{}...", truncated)
 }
 
-pub fn format_status_text(status: TaskStatus) -> &'static str {
-    match status {
+pub fn format_status_text(status: &TaskStatus) -> String {
+    let text = match status {
         TaskStatus::Open => "○ open",
         TaskStatus::InProgress => "◐ in_progress",
         TaskStatus::Blocked => "● blocked",
         TaskStatus::Closed => "✓ closed",
         TaskStatus::Canceled => "✕ canceled",
         TaskStatus::Deferred => "◇ deferred",
-    }
+        TaskStatus::Custom(name) => return format!("◆ {}", name),
+    };
+    text.to_string()
 }
 
-pub fn format_status(status: TaskStatus) -> String {
-    style::status(format_status_text(status), status)
+pub fn format_status(status: &TaskStatus) -> String {
+    style::status(&format_status_text(status), status)
 }
 
-fn summarize_tree(nodes: &[TaskTreeNode]) -> HashMap<&'static str, usize> {
+/// Task counts keyed by status name, plus `total`. Builtin statuses are
+/// always present; configured statuses appear once a task uses them.
+fn summarize_tree(nodes: &[TaskTreeNode]) -> HashMap<&str, usize> {
     let mut summary = HashMap::new();
     summary.insert("total", 0);
-    summary.insert("open", 0);
-    summary.insert("in_progress", 0);
-    summary.insert("blocked", 0);
-    summary.insert("deferred", 0);
-    summary.insert("closed", 0);
-    summary.insert("canceled", 0);
-
-    fn visit(node: &TaskTreeNode, summary: &mut HashMap<&'static str, usize>) {
+    for status in &TaskStatus::BUILTIN {
+        summary.insert(status.as_str(), 0);
+    }
+
+    fn visit<'a>(node: &'a TaskTreeNode, summary: &mut HashMap<&'a str, usize>) {
         *summary.get_mut("total").expect("total exists") += 1;
-        let key = match node.task.status {
-            TaskStatus::Open => "open",
-            TaskStatus::InProgress => "in_progress",
-            TaskStatus::Blocked => "blocked",
-            TaskStatus::Deferred => "deferred",
-            TaskStatus::Closed => "closed",
-            TaskStatus::Canceled => "canceled",
-        };
-        *summary.get_mut(key).expect("status key exists") += 1;
+        *summary.entry(node.task.status.as_str()).or_default() += 1;
         for child in &node.children {
             visit(child, summary);
         }
@@ -611,6 +806,74 @@ fn summarize_tree(nodes: &[TaskTreeNode]) -> HashMap<&'static str, usize> {
     summary
 }
 
+pub fn print_skill_changes(changes: &[SkillFileDiff]) {
+    for change in changes {
+        let marker = match change.change {
+            SkillFileChange::Added => style::success("+"),
+            SkillFileChange::Removed => style::error("-"),
+            SkillFileChange::Modified => style::warning("~"),
+        };
+        println!("  {} {}", marker, change.path);
+    }
+}
+
+/// The explicit `--source` and the checksum to pin it with.
+pub fn print_skill_source(summary: &SkillOperationSummary) {
+    if let (Some(source), Some(checksum)) = (&summary.source, &summary.source_checksum) {
+        println!(
+            "{}={} {}",
+            style::key("source"),
+            source,
+            style::muted(checksum)
+        );
+    }
+}
+
+pub fn print_skill_dry_run_banner() {
+    println!("{}", style::warning("dry run: no skill files were written"));
+}
+
+pub fn print_dry_run_events(events: &[EventRecord]) {
+    println!(
+        "{}",
+        style::warning(&format!(
+            "dry run: {} event(s) would be appended; nothing was written",
+            events.len()
+        ))
+    );
+    for event in events {
+        println!(
+            "  {} {}",
+            style::flow(event_type_to_string(event.event_type)),
+            style::task_id(&event.task_id)
+        );
+    }
+}
+
+pub fn print_bench_result(data: &BenchResult) {
+    println!(
+        "runs={} events={} tasks={}",
+        data.runs, data.events, data.tasks
+    );
+    print_bench_stats("read_events", &data.read_events);
+    print_bench_stats("apply", &data.apply);
+    println!("throughput={} events/s", data.events_per_sec);
+}
+
+pub fn print_selftest_result(data: &SelftestResult) {
+    println!(
+        "selftest ok: {} cases of {} events (seed {})",
+        data.cases, data.events, data.seed
+    );
+}
+
+fn print_bench_stats(phase: &str, stats: &BenchStats) {
+    println!(
+        "{:<12} min={:.3}ms median={:.3}ms max={:.3}ms",
+        phase, stats.min_ms, stats.median_ms, stats.max_ms
+    );
+}
+
 pub fn print_history(data: &HistoryResult) {
     if data.events.is_empty() {
         println!("{}", style::muted("no events"));
@@ -623,12 +886,19 @@ pub fn print_history(data: &HistoryResult) {
             .or(event.event_id.as_ref())
             .cloned()
             .unwrap_or_else(|| "unknown".to_string());
+        let display_name = data
+            .actors
+            .get(&event.actor)
+            .and_then(|identity| identity.display_name.as_deref())
+            .map(|name| format!(" ({})", name))
+            .unwrap_or_default();
         println!(
-            "{} {} {}={} [{}]",
-            event.ts,
+            "{} {} {}={}{} [{}]",
+            format_timestamp(&event.ts),
             style::flow(event_type_to_string(event.event_type)),
             style::key("by"),
             event.actor,
+            display_name,
             style::muted(&event_id)
         );
     }
@@ -643,13 +913,52 @@ pub fn print_history(data: &HistoryResult) {
     }
 }
 
+/// `<ts> <task> <type> by=<actor> <summary>`, as printed by `log` and
+/// `watch --task`.
+pub fn format_activity_line(entry: &crate::app::service_types::TaskActivityEntry) -> String {
+    format!(
+        "{} {} {} {}={} {}",
+        format_timestamp(&entry.ts),
+        entry.task_id,
+        style::flow(event_type_to_string(entry.event_type)),
+        style::key("by"),
+        entry.actor,
+        entry.summary
+    )
+}
+
+pub fn print_log(data: &crate::app::service_types::LogResult) {
+    if data.events.is_empty() {
+        println!("{}", style::muted("no events"));
+        return;
+    }
+    for entry in &data.events {
+        println!("{}", format_activity_line(entry));
+    }
+    if let Some(cursor) = data.next_cursor.as_deref() {
+        println!(
+            "{}",
+            style::muted(&format!("(more: --before-cursor {})", cursor))
+        );
+    }
+}
+
 pub fn print_label_list(labels: &[crate::app::service_types::LabelCount]) {
     if labels.is_empty() {
         println!("{}", style::muted("no labels"));
         return;
     }
     for entry in labels {
-        println!("{} ({})", style::meta(&entry.label), entry.count);
+        let color = entry
+            .color
+            .map(|color| format!(" {}", style::muted(color.as_str())))
+            .unwrap_or_default();
+        println!(
+            "{} ({}){}",
+            style::label_pill(&entry.label),
+            entry.count,
+            color
+        );
     }
 }
 
@@ -661,7 +970,7 @@ pub fn print_task_note(task_id: &str, note: &TaskNote) {
     );
     println!(
         "{} {}={} [{}]",
-        note.ts,
+        format_timestamp(&note.ts),
         style::key("by"),
         note.actor,
         style::muted(&note.event_id)
@@ -683,7 +992,7 @@ pub fn print_task_notes(task_id: &str, notes: &[TaskNote]) {
     for note in notes {
         println!(
             "{} {}={} [{}]",
-            note.ts,
+            format_timestamp(&note.ts),
             style::key("by"),
             note.actor,
             style::muted(&note.event_id)
@@ -692,6 +1001,22 @@ pub fn print_task_notes(task_id: &str, notes: &[TaskNote]) {
     }
 }
 
+pub fn print_count_result(result: &CountResult) {
+    println!(
+        "{:<24} {:>6}",
+        result.count_by.as_str().to_uppercase(),
+        "COUNT"
+    );
+    for group in &result.groups {
+        println!(
+            "{:<24} {:>6}",
+            group.key.as_deref().unwrap_or("(none)"),
+            group.count
+        );
+    }
+    println!("total={}", result.total);
+}
+
 pub fn print_orphans_result(result: &OrphansResult) {
     if result.total == 0 {
         println!("{}", style::success("clean — no orphaned deps or links"));
@@ -717,10 +1042,87 @@ pub fn print_orphans_result(result: &OrphansResult) {
     println!("total={}", result.total);
 }
 
+pub fn print_orphans_adopt_result(result: &OrphansAdoptResult) {
+    for (old, new) in &result.map {
+        println!("{} -> {}", old, style::task_id(new));
+    }
+    println!(
+        "adopted_deps={} adopted_links={}",
+        result.adopted_deps.len(),
+        result.adopted_links.len()
+    );
+    for dep in &result.adopted_deps {
+        println!(
+            "  {} -> {} ({})",
+            dep.child,
+            dep.blocker,
+            dep_type_to_string(dep.dep_type)
+        );
+    }
+    for link in &result.adopted_links {
+        println!(
+            "  {} -[{}]-> {}",
+            link.src,
+            relation_type_to_string(link.rel_type),
+            link.dst
+        );
+    }
+    if result.removed_deps.is_empty() && result.removed_links.is_empty() {
+        return;
+    }
+    println!(
+        "removed_deps={} removed_links={}",
+        result.removed_deps.len(),
+        result.removed_links.len()
+    );
+    for dep in &result.removed_deps {
+        println!(
+            "  {} -> {} ({})",
+            dep.child,
+            dep.blocker,
+            dep_type_to_string(dep.dep_type)
+        );
+    }
+    for link in &result.removed_links {
+        println!(
+            "  {} -[{}]-> {}",
+            link.src,
+            relation_type_to_string(link.rel_type),
+            link.dst
+        );
+    }
+}
+
 pub fn print_dep_tree_result(root: &DepTreeNode) {
     print_dep_node(root, "", true, true);
 }
 
+pub fn print_dep_graph(graph: &DepGraph) {
+    println!(
+        "{} nodes={} edges={}",
+        style::heading("deps"),
+        graph.nodes.len(),
+        graph.edges.len()
+    );
+    for node in &graph.nodes {
+        println!(
+            "{} {} {} {}",
+            format_status(&node.status),
+            style::task_id(&node.id),
+            node.title,
+            style::meta(&format!("depth={}", node.depth))
+        );
+    }
+    for edge in &graph.edges {
+        println!(
+            "{} by {} ({})",
+            style::task_id(&edge.child),
+            style::task_id(&edge.blocker),
+            dep_type_to_string(edge.dep_type)
+        );
+    }
+}
+
 fn print_dep_node(node: &DepTreeNode, prefix: &str, is_last: bool, is_root: bool) {
     let connector = if is_root {
         ""
@@ -738,15 +1140,21 @@ fn print_dep_node(node: &DepTreeNode, prefix: &str, is_last: bool, is_root: bool
         .dep_type
         .map(|value| format!(" ({})", dep_type_to_string(value)))
         .unwrap_or_default();
+    let seen_tag = if node.seen {
+        format!(" {}", style::muted("(seen)"))
+    } else {
+        String::new()
+    };
     println!(
-        "{}{}{} {} {}{}{}",
+        "{}{}{} {} {}{}{}{}",
         style::tree_prefix(prefix),
         style::tree_prefix(connector),
-        format_status(node.task.status),
+        format_status(&node.task.status),
         style::task_id(&node.task.id),
         node.task.title,
         dir_tag,
-        type_tag
+        type_tag,
+        seen_tag
     );
     let child_prefix = if is_root {
         prefix.to_string()
@@ -765,18 +1173,6 @@ fn print_dep_node(node: &DepTreeNode, prefix: &str, is_last: bool, is_root: bool
     }
 }
 
-fn parse_status_label(value: &str) -> Option<TaskStatus> {
-    match value {
-        "open" => Some(TaskStatus::Open),
-        "in_progress" => Some(TaskStatus::InProgress),
-        "blocked" => Some(TaskStatus::Blocked),
-        "closed" => Some(TaskStatus::Closed),
-        "canceled" => Some(TaskStatus::Canceled),
-        "deferred" => Some(TaskStatus::Deferred),
-        _ => None,
-    }
-}
-
 fn task_kind_to_string(kind: crate::types::TaskKind) -> &'static str {
     match kind {
         crate::types::TaskKind::Task => "task",
@@ -785,28 +1181,12 @@ fn task_kind_to_string(kind: crate::types::TaskKind) -> &'static str {
     }
 }
 
-pub fn status_to_string(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Open => "open",
-        TaskStatus::InProgress => "in_progress",
-        TaskStatus::Blocked => "blocked",
-        TaskStatus::Closed => "closed",
-        TaskStatus::Canceled => "canceled",
-        TaskStatus::Deferred => "deferred",
-    }
-}
-
-fn planning_state_to_string(state: crate::types::PlanningState) -> &'static str {
-    match state {
-        crate::types::PlanningState::NeedsPlanning => "needs_planning",
-        crate::types::PlanningState::Planned => "planned",
-    }
-}
-
-fn dep_type_to_string(dep_type: crate::types::DependencyType) -> &'static str {
+pub fn dep_type_to_string(dep_type: crate::types::DependencyType) -> &'static str {
     match dep_type {
         crate::types::DependencyType::Blocks => "blocks",
         crate::types::DependencyType::StartsAfter => "starts_after",
+        crate::types::DependencyType::FinishToFinish => "finish_to_finish",
+        crate::types::DependencyType::Informs => "informs",
     }
 }
 
@@ -836,6 +1216,10 @@ fn event_type_to_string(event_type: crate::types::EventType) -> &'static str {
         crate::types::EventType::TaskNoted => "task.noted",
         crate::types::EventType::TaskSpecAttached => "task.spec_attached",
         crate::types::EventType::TaskSuperseded => "task.superseded",
+        crate::types::EventType::TaskCriterionSet => "task.criterion_set",
+        crate::types::EventType::TaskChecklistAdded => "task.checklist_added",
+        crate::types::EventType::TaskChecklistSet => "task.checklist_set",
+        crate::types::EventType::TaskRanked => "task.ranked",
         crate::types::EventType::DepAdded => "dep.added",
         crate::types::EventType::DepRemoved => "dep.removed",
         crate::types::EventType::LinkAdded => "link.added",
@@ -971,6 +1355,12 @@ mod tests {
             planning_state: Some(PlanningState::NeedsPlanning),
             replies_to: None,
             labels: Vec::new(),
+            criteria_marks: Default::default(),
+            checklist: Vec::new(),
+            rank: None,
+            effective_priority: None,
+            defer_until: None,
+            blocked_on: None,
             created_at: "2026-05-11T00:00:00Z".to_string(),
             updated_at: "2026-05-11T00:00:00Z".to_string(),
             closed_at: None,
@@ -0,0 +1,399 @@
+use crate::app::service::TasqueService;
+use crate::app::service_types::{MetricsResult, RollupTask};
+use crate::cli::style;
+use crate::cli::watch::{WatchFrameFilters, WatchOptions, WatchSummary, load_frame_data};
+use crate::errors::TsqError;
+use crate::output::{compact_task_list, err_envelope, ok_envelope, with_label_colors};
+use crate::types::{Task, TaskKind, TaskStatus};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const DASHBOARD_HTML: &str = include_str!("serve_ui.html");
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+/// How long a peer may take to send its request before it is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long one write may block on a peer that stopped reading.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Connections handled at once; more are answered 503 and closed.
+const MAX_CONNECTIONS: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    pub host: String,
+    pub port: u16,
+    /// Serve the dashboard page at `/` in addition to the JSON endpoints.
+    pub ui: bool,
+    pub interval: i64,
+    pub statuses: Vec<TaskStatus>,
+}
+
+/// What the dashboard renders: the watch frame plus epic progress.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardFrame {
+    pub frame_ts: String,
+    pub interval_s: i64,
+    pub filters: WatchFrameFilters,
+    pub summary: WatchSummary,
+    pub tasks: Vec<Task>,
+    pub epics: Vec<RollupTask>,
+}
+
+/// Blocks serving read-only HTTP: `/api/frame` (one frame), `/events`
+/// (Server-Sent Events, a `frame` event whenever the frame changes),
+/// `/metrics` (Prometheus gauges), and with `ui` the dashboard page at `/`.
+/// Each connection runs on its own thread, up to [`MAX_CONNECTIONS`].
+pub fn start_serve(service: &TasqueService, options: ServeOptions) -> Result<(), TsqError> {
+    let (listener, bound) = bind(&format!("{}:{}", options.host, options.port))?;
+    eprintln!(
+        "serving {} on http://{}{} (Ctrl-C to stop)",
+        if options.ui { "dashboard" } else { "frame API" },
+        bound,
+        if options.ui { "/" } else { "/api/frame" }
+    );
+
+    let active = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for stream in listener.incoming().map_while(Result::ok) {
+            let Some((stream, slot)) = admit(stream, &active) else {
+                continue;
+            };
+            let options = &options;
+            scope.spawn(move || {
+                handle_connection(service, options, stream);
+                drop(slot);
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Runs `run` while answering `/metrics` on `address` from a background
+/// thread; `tsq sync daemon --metrics` uses this next to the sync loop.
+pub fn with_metrics_server<T>(
+    service: &TasqueService,
+    address: &str,
+    run: impl FnOnce() -> Result<T, TsqError>,
+) -> Result<T, TsqError> {
+    let (listener, bound) = bind(address)?;
+    eprintln!("serving metrics on http://{}/metrics", bound);
+    let stop = AtomicBool::new(false);
+    let active = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            for stream in listener.incoming().map_while(Result::ok) {
+                if stop.load(Ordering::SeqCst) {
+                    return;
+                }
+                let Some((stream, slot)) = admit(stream, &active) else {
+                    continue;
+                };
+                scope.spawn(move || {
+                    handle_metrics_connection(service, stream);
+                    drop(slot);
+                });
+            }
+        });
+        let result = run();
+        // Wake the blocked accept so the thread sees `stop` and the scope ends.
+        stop.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect(&bound);
+        result
+    })
+}
+
+fn bind(address: &str) -> Result<(TcpListener, String), TsqError> {
+    let listener = TcpListener::bind(address).map_err(|error| {
+        TsqError::new(
+            "SERVE_BIND_FAILED",
+            format!("failed binding {}: {}", address, error),
+            2,
+        )
+    })?;
+    let bound = listener
+        .local_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|_| address.to_string());
+    Ok((listener, bound))
+}
+
+/// Marks one of the [`MAX_CONNECTIONS`] handler slots as taken until dropped.
+struct ConnectionSlot<'a>(&'a AtomicUsize);
+
+impl Drop for ConnectionSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Applies the socket timeouts and takes a handler slot, so a slow or idle
+/// peer can only ever hold its own thread. A full server answers 503.
+fn admit(mut stream: TcpStream, active: &AtomicUsize) -> Option<(TcpStream, ConnectionSlot<'_>)> {
+    stream.set_read_timeout(Some(READ_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT)).ok()?;
+    if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+        active.fetch_sub(1, Ordering::SeqCst);
+        let _ = write_response(
+            &mut stream,
+            "503 Service Unavailable",
+            "application/json",
+            &error_body("SERVE_BUSY", "too many open connections"),
+        );
+        return None;
+    }
+    Some((stream, ConnectionSlot(active)))
+}
+
+fn handle_metrics_connection(service: &TasqueService, mut stream: TcpStream) {
+    let _ = match read_request_line(&stream) {
+        Some((method, path)) if method == "GET" && path.split('?').next() == Some("/metrics") => {
+            write_metrics(service, &mut stream)
+        }
+        Some(_) => write_response(
+            &mut stream,
+            "404 Not Found",
+            "application/json",
+            &error_body("NOT_FOUND", "only /metrics is served"),
+        ),
+        None => Ok(()),
+    };
+}
+
+fn handle_connection(service: &TasqueService, options: &ServeOptions, mut stream: TcpStream) {
+    let Some((method, path)) = read_request_line(&stream) else {
+        return;
+    };
+    if method != "GET" {
+        let _ = write_response(
+            &mut stream,
+            "405 Method Not Allowed",
+            "application/json",
+            &error_body("METHOD_NOT_ALLOWED", "the server is read-only"),
+        );
+        return;
+    }
+    let path = path.split('?').next().unwrap_or("/");
+    let _ = match path {
+        "/" | "/index.html" if options.ui => write_response(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            DASHBOARD_HTML,
+        ),
+        "/api/frame" => match load_dashboard_frame(service, options) {
+            Ok(frame) => write_response(
+                &mut stream,
+                "200 OK",
+                "application/json",
+                &serde_json::to_string(&ok_envelope("tsq serve", frame_json(&frame)))
+                    .unwrap_or_default(),
+            ),
+            Err(error) => write_response(
+                &mut stream,
+                "500 Internal Server Error",
+                "application/json",
+                &error_body(&error.code, &error.message),
+            ),
+        },
+        "/events" => stream_events(service, options, stream),
+        "/metrics" => write_metrics(service, &mut stream),
+        _ => write_response(
+            &mut stream,
+            "404 Not Found",
+            "application/json",
+            &error_body("NOT_FOUND", &format!("no route for {}", path)),
+        ),
+    };
+}
+
+/// Method and path from the request line; headers are read and ignored.
+fn read_request_line(stream: &TcpStream) -> Option<(String, String)> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut header = String::new();
+    while reader.read_line(&mut header).ok()? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    Some((parts.next()?.to_string(), parts.next()?.to_string()))
+}
+
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn write_metrics(service: &TasqueService, stream: &mut TcpStream) -> std::io::Result<()> {
+    match service.metrics() {
+        Ok(metrics) => write_response(
+            stream,
+            "200 OK",
+            METRICS_CONTENT_TYPE,
+            &render_metrics(&metrics),
+        ),
+        Err(error) => write_response(
+            stream,
+            "500 Internal Server Error",
+            "application/json",
+            &error_body(&error.code, &error.message),
+        ),
+    }
+}
+
+/// Prometheus text exposition format; every metric is a gauge.
+fn render_metrics(metrics: &MetricsResult) -> String {
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, samples: &[(&str, String)]| {
+        out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} gauge\n"));
+        for (labels, value) in samples {
+            out.push_str(&format!("{name}{labels} {value}\n"));
+        }
+    };
+    gauge(
+        "tasque_tasks",
+        "Tasks by status.",
+        &[
+            ("{status=\"open\"}", metrics.open.to_string()),
+            ("{status=\"in_progress\"}", metrics.in_progress.to_string()),
+            ("{status=\"blocked\"}", metrics.blocked.to_string()),
+        ],
+    );
+    gauge(
+        "tasque_ready_tasks",
+        "Tasks in the ready queue.",
+        &[("", metrics.ready.to_string())],
+    );
+    gauge(
+        "tasque_event_log_events",
+        "Events in the event log.",
+        &[("", metrics.event_count.to_string())],
+    );
+    gauge(
+        "tasque_event_log_bytes",
+        "Size of the event log in bytes.",
+        &[("", metrics.event_log_bytes.to_string())],
+    );
+    gauge(
+        "tasque_projection_seconds",
+        "Time taken to load the projected state.",
+        &[("", format!("{:.6}", metrics.projection_seconds))],
+    );
+    out
+}
+
+fn error_body(code: &str, message: &str) -> String {
+    serde_json::to_string(&err_envelope("tsq serve", code, message, None)).unwrap_or_default()
+}
+
+/// Sends a `frame` event whenever the frame changes and a comment line as a
+/// keepalive otherwise; returns once the client disconnects.
+fn stream_events(
+    service: &TasqueService,
+    options: &ServeOptions,
+    mut stream: TcpStream,
+) -> std::io::Result<()> {
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    stream.flush()?;
+    let mut last: Option<Value> = None;
+    loop {
+        match load_dashboard_frame(service, options) {
+            Ok(frame) => {
+                let data = frame_json(&frame);
+                // Only the contents decide whether clients need a new frame.
+                let key = data.as_object().map(|obj| {
+                    let mut key = obj.clone();
+                    key.remove("frame_ts");
+                    Value::Object(key)
+                });
+                if key != last {
+                    writeln!(
+                        stream,
+                        "event: frame\ndata: {}\n",
+                        serde_json::to_string(&data).unwrap_or_default()
+                    )?;
+                    last = key;
+                } else {
+                    stream.write_all(b": keepalive\n\n")?;
+                }
+            }
+            Err(error) => writeln!(
+                stream,
+                "event: error\ndata: {}\n",
+                error_body(&error.code, &error.message)
+            )?,
+        }
+        stream.flush()?;
+        thread::sleep(Duration::from_secs(options.interval as u64));
+    }
+}
+
+pub fn load_dashboard_frame(
+    service: &TasqueService,
+    options: &ServeOptions,
+) -> Result<DashboardFrame, TsqError> {
+    let frame = load_frame_data(
+        service,
+        &WatchOptions {
+            interval: options.interval,
+            statuses: options.statuses.clone(),
+            assignee: None,
+            query: None,
+            tree: false,
+            once: true,
+            json: true,
+            fade: 0,
+            deltas: false,
+            with_notes: false,
+            tasks: Vec::new(),
+            workspace: false,
+            exact_id: false,
+        },
+    )?;
+    let epics = service.rollup(
+        frame
+            .tasks
+            .iter()
+            .filter(|task| task.kind == TaskKind::Epic)
+            .cloned()
+            .collect(),
+    )?;
+    Ok(DashboardFrame {
+        frame_ts: frame.frame_ts,
+        interval_s: frame.interval_s,
+        filters: frame.filters,
+        summary: frame.summary,
+        tasks: frame.tasks,
+        epics,
+    })
+}
+
+fn frame_json(frame: &DashboardFrame) -> Value {
+    let mut value = serde_json::to_value(frame).unwrap_or_default();
+    if let Some(tasks) = value.get_mut("tasks") {
+        compact_task_list(tasks);
+    }
+    if let Some(epics) = value.get_mut("epics") {
+        compact_task_list(epics);
+    }
+    with_label_colors(&mut value, style::label_colors());
+    value
+}
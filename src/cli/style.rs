@@ -1,9 +1,23 @@
-use crate::types::TaskStatus;
+use crate::types::{LabelColor, TaskStatus};
+use std::collections::BTreeMap;
 use std::env;
 use std::io::IsTerminal;
+use std::sync::OnceLock;
 
 const ANSI_RESET: &str = "\x1b[0m";
 
+static LABEL_COLORS: OnceLock<BTreeMap<String, LabelColor>> = OnceLock::new();
+
+/// Installs the repo's `label_colors` once per process, before rendering.
+pub fn set_label_colors(colors: BTreeMap<String, LabelColor>) {
+    let _ = LABEL_COLORS.set(colors);
+}
+
+pub fn label_colors() -> &'static BTreeMap<String, LabelColor> {
+    static EMPTY: BTreeMap<String, LabelColor> = BTreeMap::new();
+    LABEL_COLORS.get().unwrap_or(&EMPTY)
+}
+
 pub fn use_color() -> bool {
     if let Ok(force) = env::var("CLICOLOR_FORCE")
         && force != "0"
@@ -59,7 +73,7 @@ pub fn muted(value: &str) -> String {
     paint(value, "90")
 }
 
-pub fn status(value: &str, status: TaskStatus) -> String {
+pub fn status(value: &str, status: &TaskStatus) -> String {
     let code = match status {
         TaskStatus::Open => "1;34",
         TaskStatus::InProgress => "1;36",
@@ -67,6 +81,39 @@ pub fn status(value: &str, status: TaskStatus) -> String {
         TaskStatus::Closed => "1;32",
         TaskStatus::Canceled => "90",
         TaskStatus::Deferred => "1;33",
+        TaskStatus::Custom(_) => "1;35",
+    };
+    paint(value, code)
+}
+
+/// `[label]`, painted in the label's configured color when it has one.
+pub fn label_pill(label: &str) -> String {
+    let pill = format!("[{}]", label);
+    match label_colors().get(label) {
+        Some(color) => paint_label_color(&pill, *color),
+        None => meta(&pill),
+    }
+}
+
+/// Pills for every label, space separated.
+pub fn label_pills(labels: &[String]) -> String {
+    labels
+        .iter()
+        .map(|label| label_pill(label))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn paint_label_color(value: &str, color: LabelColor) -> String {
+    let code = match color {
+        LabelColor::Red => "1;31",
+        LabelColor::Green => "1;32",
+        LabelColor::Yellow => "1;33",
+        LabelColor::Blue => "1;34",
+        LabelColor::Magenta => "1;35",
+        LabelColor::Cyan => "1;36",
+        LabelColor::White => "1;37",
+        LabelColor::Gray => "90",
     };
     paint(value, code)
 }
@@ -25,6 +25,26 @@ pub fn resolve_width(raw: Option<usize>) -> usize {
     120
 }
 
+pub fn resolve_height(raw: Option<usize>) -> usize {
+    if let Some(height) = raw
+        && height > 0
+    {
+        return height;
+    }
+    if let Ok(value) = std::env::var("LINES")
+        && let Ok(height) = value.parse::<usize>()
+        && height > 0
+    {
+        return height;
+    }
+    if let Some((_, terminal_size::Height(height))) = terminal_size::terminal_size()
+        && height > 0
+    {
+        return height as usize;
+    }
+    40
+}
+
 pub fn resolve_density(width: usize) -> Density {
     if width >= 120 {
         Density::Wide
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimestampMode {
+    /// Local time, e.g. `2026-05-11 14:03:00 +02:00`
+    Absolute,
+    /// Age, e.g. `3h ago`
+    #[default]
+    Relative,
+    /// Local time followed by the age in parentheses
+    Both,
+}
+
+static MODE: OnceLock<TimestampMode> = OnceLock::new();
+
+/// Installs the `--timestamps` choice once per process, before rendering.
+pub fn set_mode(mode: TimestampMode) {
+    let _ = MODE.set(mode);
+}
+
+pub fn mode() -> TimestampMode {
+    MODE.get().copied().unwrap_or_default()
+}
+
+/// An RFC3339 timestamp as human output should show it. Values that do not
+/// parse are printed as stored.
+pub fn format_timestamp(raw: &str) -> String {
+    format_timestamp_at(raw, mode(), Utc::now())
+}
+
+pub fn format_timestamp_at(raw: &str, mode: TimestampMode, now: DateTime<Utc>) -> String {
+    let Ok(ts) = DateTime::parse_from_rfc3339(raw) else {
+        return raw.to_string();
+    };
+    let ts = ts.with_timezone(&Utc);
+    let absolute = || {
+        ts.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S %:z")
+            .to_string()
+    };
+    match mode {
+        TimestampMode::Absolute => absolute(),
+        TimestampMode::Relative => relative(ts, now),
+        TimestampMode::Both => format!("{} ({})", absolute(), relative(ts, now)),
+    }
+}
+
+fn relative(ts: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - ts).num_seconds();
+    let age = seconds.unsigned_abs();
+    if age < 45 {
+        return "just now".to_string();
+    }
+    let amount = match age {
+        0..3_600 => format!("{}m", (age / 60).max(1)),
+        3_600..86_400 => format!("{}h", age / 3_600),
+        86_400..2_592_000 => format!("{}d", age / 86_400),
+        2_592_000..31_536_000 => format!("{}mo", age / 2_592_000),
+        _ => format!("{}y", age / 31_536_000),
+    };
+    if seconds < 0 {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn relative_formats_scale_with_age() {
+        let now = at("2026-05-11T12:00:00Z");
+        let cases = [
+            ("2026-05-11T11:59:50Z", "just now"),
+            ("2026-05-11T11:50:00Z", "10m ago"),
+            ("2026-05-11T09:00:00Z", "3h ago"),
+            ("2026-05-09T12:00:00Z", "2d ago"),
+            ("2026-03-01T12:00:00Z", "2mo ago"),
+            ("2024-05-11T12:00:00Z", "2y ago"),
+            ("2026-05-11T14:00:00Z", "in 2h"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(
+                format_timestamp_at(raw, TimestampMode::Relative, now),
+                expected,
+                "{}",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn both_appends_the_age_to_local_time() {
+        let now = at("2026-05-11T12:00:00Z");
+        let formatted = format_timestamp_at("2026-05-11T09:00:00Z", TimestampMode::Both, now);
+        assert!(formatted.ends_with(" (3h ago)"), "{}", formatted);
+        assert!(formatted.starts_with("2026-05-1"), "{}", formatted);
+    }
+
+    #[test]
+    fn unparseable_values_are_kept() {
+        let now = at("2026-05-11T12:00:00Z");
+        assert_eq!(
+            format_timestamp_at("yesterday", TimestampMode::Relative, now),
+            "yesterday"
+        );
+    }
+}
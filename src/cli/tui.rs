@@ -9,13 +9,16 @@ use std::time::Duration;
 
 #[path = "tui_data.rs"]
 mod tui_data;
+#[path = "tui_form.rs"]
+mod tui_form;
 #[path = "tui_model.rs"]
 mod tui_model;
 #[path = "tui_render.rs"]
 mod tui_render;
-use tui_data::load_frame;
-use tui_model::apply_selection;
+use tui_data::{load_frame, step_epic};
+use tui_form::{CreateForm, FormOutcome};
 use tui_model::validate_options;
+use tui_model::{apply_scroll, apply_selection, page_rows};
 use tui_render::output_frame;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,9 +34,12 @@ pub struct TuiOptions {
     pub interval: i64,
     pub statuses: Vec<TaskStatus>,
     pub assignee: Option<String>,
+    pub query: Option<String>,
     pub once: bool,
     pub json: bool,
     pub view: TuiView,
+    /// Keep each task's `notes` array in JSON frames instead of `note_count`.
+    pub with_notes: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +55,8 @@ pub struct TuiFrameFilters {
     pub status: Vec<TaskStatus>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +67,9 @@ pub struct TuiEpicProgress {
     pub total: usize,
     pub open: usize,
     pub in_progress: usize,
+    /// Checklist items across the epic and its children.
+    pub checklist_done: usize,
+    pub checklist_total: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +91,11 @@ pub struct TuiFrameData {
     pub epic_progress: Option<TuiEpicProgress>,
     #[serde(skip_serializing, skip_deserializing, default)]
     visible_task_ids: Vec<String>,
+    /// First visible table row when the list is paged to the terminal height.
+    #[serde(skip_serializing, skip_deserializing, default)]
+    scroll_offset: usize,
+    #[serde(skip_serializing, skip_deserializing, default)]
+    with_notes: bool,
 }
 
 enum FrameResult {
@@ -98,6 +114,16 @@ enum TaskSpecState {
     InvalidMetadata,
 }
 
+/// Interactive cursor state carried across refreshes.
+#[derive(Debug, Default)]
+struct TuiSelection {
+    index: usize,
+    /// Scroll position of the table viewport.
+    offset: usize,
+    /// Epic shown on the Epics tab; kept while it is still listed.
+    epic_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TuiTab {
     Tasks,
@@ -132,12 +158,13 @@ pub fn start_tui(service: &TasqueService, options: TuiOptions) -> i32 {
         std::io::stdout().is_terminal() && std::io::stdin().is_terminal() && !options.json;
     let mut tab = initial_tab(options.view);
     let mut paused = false;
-    let mut selected_index = 0usize;
+    let mut selection = TuiSelection::default();
+    let mut create_form: Option<CreateForm> = None;
     let interval = Duration::from_secs(options.interval as u64);
     let mut last_good_frame: Option<TuiFrameData> = None;
 
     if options.once {
-        let frame = load_frame(service, &options, tab, selected_index);
+        let frame = load_frame(service, &options, tab, selection.index, None);
         output_frame(&frame, options.json, false, false);
         return match frame {
             FrameResult::Ok(_) => 0,
@@ -167,7 +194,7 @@ pub fn start_tui(service: &TasqueService, options: TuiOptions) -> i32 {
         service,
         &options,
         tab,
-        &mut selected_index,
+        &mut selection,
         can_clear,
         paused,
         &mut last_good_frame,
@@ -178,15 +205,73 @@ pub fn start_tui(service: &TasqueService, options: TuiOptions) -> i32 {
             match event::poll(interval) {
                 Ok(true) => match event::read() {
                     Ok(Event::Key(key)) => {
+                        if let Some(form) = create_form.as_mut() {
+                            if !is_press_like(&key) {
+                                continue;
+                            }
+                            if key.modifiers.contains(KeyModifiers::CONTROL)
+                                && key.code == KeyCode::Char('c')
+                            {
+                                break;
+                            }
+                            let created = match form.handle_key(&key) {
+                                FormOutcome::Editing => None,
+                                FormOutcome::Cancel => Some(None),
+                                FormOutcome::Submit => match form.to_input().and_then(|input| {
+                                    service.create(input).map_err(|error| error.message)
+                                }) {
+                                    Ok(task) => Some(Some(task.id)),
+                                    Err(message) => {
+                                        form.error = Some(message);
+                                        None
+                                    }
+                                },
+                            };
+                            match created {
+                                None => draw_create_form(
+                                    last_good_frame.as_ref(),
+                                    form,
+                                    can_clear,
+                                    paused,
+                                ),
+                                Some(created_id) => {
+                                    create_form = None;
+                                    refresh_frame(
+                                        service,
+                                        &options,
+                                        tab,
+                                        &mut selection,
+                                        can_clear,
+                                        paused,
+                                        &mut last_good_frame,
+                                    );
+                                    if let Some(id) = created_id {
+                                        println!("created {}", id);
+                                    }
+                                }
+                            }
+                            continue;
+                        }
                         if should_quit_on_key(&key) {
                             break;
                         }
+                        if is_create_key(&key) {
+                            let parent = if tab == TuiTab::Epics {
+                                selection.epic_id.clone()
+                            } else {
+                                None
+                            };
+                            let form = CreateForm::new(parent);
+                            draw_create_form(last_good_frame.as_ref(), &form, can_clear, paused);
+                            create_form = Some(form);
+                            continue;
+                        }
                         if is_refresh_key(&key) {
                             refresh_frame(
                                 service,
                                 &options,
                                 tab,
-                                &mut selected_index,
+                                &mut selection,
                                 can_clear,
                                 paused,
                                 &mut last_good_frame,
@@ -207,29 +292,50 @@ pub fn start_tui(service: &TasqueService, options: TuiOptions) -> i32 {
                         }
                         if is_switch_view_key(&key) {
                             tab = cycle_tab(tab);
-                            selected_index = 0;
+                            selection.index = 0;
                             refresh_frame(
                                 service,
                                 &options,
                                 tab,
-                                &mut selected_index,
+                                &mut selection,
                                 can_clear,
                                 paused,
                                 &mut last_good_frame,
                             );
                             continue;
                         }
-                        if (is_select_up_key(&key) || is_select_down_key(&key))
-                            && let Some(frame) = last_good_frame.as_mut()
+                        if tab == TuiTab::Epics
+                            && (is_prev_epic_key(&key) || is_next_epic_key(&key))
+                            && let Some(frame) = last_good_frame.as_ref()
                         {
+                            let step = if is_prev_epic_key(&key) { -1 } else { 1 };
+                            let next = step_epic(frame, step);
+                            if next.is_some() && next != selection.epic_id {
+                                selection.epic_id = next;
+                                selection.index = 0;
+                                refresh_frame(
+                                    service,
+                                    &options,
+                                    tab,
+                                    &mut selection,
+                                    can_clear,
+                                    paused,
+                                    &mut last_good_frame,
+                                );
+                            }
+                            continue;
+                        }
+                        if let Some(frame) = last_good_frame.as_mut() {
                             let visible_count = frame.visible_task_ids.len();
-                            if visible_count > 0 {
-                                if is_select_up_key(&key) {
-                                    selected_index = selected_index.saturating_sub(1);
-                                } else {
-                                    selected_index = (selected_index + 1).min(visible_count - 1);
-                                }
-                                apply_selection(frame, selected_index);
+                            let page = page_rows(tab);
+                            if visible_count > 0
+                                && let Some(index) =
+                                    selection_target(&key, selection.index, visible_count, page)
+                            {
+                                selection.index = index;
+                                apply_selection(frame, selection.index);
+                                apply_scroll(frame, page, selection.offset);
+                                selection.offset = frame.scroll_offset;
                                 output_frame(
                                     &FrameResult::Ok(Box::new(frame.clone())),
                                     options.json,
@@ -250,12 +356,12 @@ pub fn start_tui(service: &TasqueService, options: TuiOptions) -> i32 {
                     }
                 },
                 Ok(false) => {
-                    if !paused {
+                    if !paused && create_form.is_none() {
                         refresh_frame(
                             service,
                             &options,
                             tab,
-                            &mut selected_index,
+                            &mut selection,
                             can_clear,
                             paused,
                             &mut last_good_frame,
@@ -282,7 +388,7 @@ pub fn start_tui(service: &TasqueService, options: TuiOptions) -> i32 {
             service,
             &options,
             tab,
-            &mut selected_index,
+            &mut selection,
             can_clear,
             paused,
             &mut last_good_frame,
@@ -294,19 +400,27 @@ fn refresh_frame(
     service: &TasqueService,
     options: &TuiOptions,
     tab: TuiTab,
-    selected_index: &mut usize,
+    selection: &mut TuiSelection,
     clear_screen: bool,
     paused: bool,
     last_good_frame: &mut Option<TuiFrameData>,
 ) {
-    match load_frame(service, options, tab, *selected_index) {
+    match load_frame(
+        service,
+        options,
+        tab,
+        selection.index,
+        selection.epic_id.as_deref(),
+    ) {
         FrameResult::Ok(data) => {
             let data = *data;
-            if let Some(index) = data.selected_index {
-                *selected_index = index;
-            } else {
-                *selected_index = 0;
+            if data.selected_epic_id.is_some() {
+                selection.epic_id = data.selected_epic_id.clone();
             }
+            selection.index = data.selected_index.unwrap_or(0);
+            let mut data = data;
+            apply_scroll(&mut data, page_rows(tab), selection.offset);
+            selection.offset = data.scroll_offset;
             *last_good_frame = Some(data.clone());
             output_frame(
                 &FrameResult::Ok(Box::new(data)),
@@ -344,6 +458,26 @@ fn refresh_frame(
     }
 }
 
+/// Redraws the last frame with the create-task modal underneath it.
+fn draw_create_form(
+    frame: Option<&TuiFrameData>,
+    form: &CreateForm,
+    clear_screen: bool,
+    paused: bool,
+) {
+    if let Some(frame) = frame {
+        output_frame(
+            &FrameResult::Ok(Box::new(frame.clone())),
+            false,
+            clear_screen,
+            paused,
+        );
+    }
+    for line in form.render() {
+        println!("{}", line);
+    }
+}
+
 fn output_tui_error(json: bool, error: String, code: &str, paused: bool) {
     output_frame(
         &FrameResult::Err {
@@ -371,6 +505,13 @@ fn should_quit_on_key(key: &KeyEvent) -> bool {
     }
 }
 
+fn is_create_key(key: &KeyEvent) -> bool {
+    if !is_press_like(key) || key.modifiers.contains(KeyModifiers::CONTROL) {
+        return false;
+    }
+    matches!(key.code, KeyCode::Char('c'))
+}
+
 fn is_refresh_key(key: &KeyEvent) -> bool {
     if !is_press_like(key) {
         return false;
@@ -392,18 +533,36 @@ fn is_switch_view_key(key: &KeyEvent) -> bool {
     matches!(key.code, KeyCode::Tab)
 }
 
-fn is_select_up_key(key: &KeyEvent) -> bool {
+fn is_prev_epic_key(key: &KeyEvent) -> bool {
     if !is_press_like(key) {
         return false;
     }
-    matches!(key.code, KeyCode::Up)
+    matches!(key.code, KeyCode::Left | KeyCode::Char('['))
 }
 
-fn is_select_down_key(key: &KeyEvent) -> bool {
+fn is_next_epic_key(key: &KeyEvent) -> bool {
     if !is_press_like(key) {
         return false;
     }
-    matches!(key.code, KeyCode::Down)
+    matches!(key.code, KeyCode::Right | KeyCode::Char(']'))
+}
+
+/// Row a navigation key moves the selection to, or `None` for other keys.
+fn selection_target(key: &KeyEvent, current: usize, total: usize, page: usize) -> Option<usize> {
+    if !is_press_like(key) || total == 0 {
+        return None;
+    }
+    let last = total - 1;
+    let target = match key.code {
+        KeyCode::Up => current.saturating_sub(1),
+        KeyCode::Down => current + 1,
+        KeyCode::PageUp => current.saturating_sub(page),
+        KeyCode::PageDown => current + page,
+        KeyCode::Home => 0,
+        KeyCode::End => last,
+        _ => return None,
+    };
+    Some(target.min(last))
 }
 
 fn is_press_like(key: &KeyEvent) -> bool {
@@ -480,7 +639,34 @@ mod tests {
             state: KeyEventState::NONE,
         };
 
-        assert!(is_select_down_key(&press));
-        assert!(!is_select_down_key(&release));
+        assert_eq!(selection_target(&press, 0, 3, 10), Some(1));
+        assert_eq!(selection_target(&release, 0, 3, 10), None);
+    }
+
+    #[test]
+    fn page_and_edge_keys_clamp_to_the_list() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(
+            selection_target(&key(KeyCode::PageDown), 5, 12, 10),
+            Some(11)
+        );
+        assert_eq!(selection_target(&key(KeyCode::PageUp), 5, 12, 10), Some(0));
+        assert_eq!(selection_target(&key(KeyCode::End), 0, 12, 10), Some(11));
+        assert_eq!(selection_target(&key(KeyCode::Home), 7, 12, 10), Some(0));
+        assert_eq!(selection_target(&key(KeyCode::Char('x')), 7, 12, 10), None);
+    }
+
+    #[test]
+    fn epic_navigation_accepts_arrows_and_brackets() {
+        for code in [KeyCode::Left, KeyCode::Char('[')] {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            assert!(is_prev_epic_key(&key));
+            assert!(!is_next_epic_key(&key));
+        }
+        for code in [KeyCode::Right, KeyCode::Char(']')] {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            assert!(is_next_epic_key(&key));
+            assert!(!is_prev_epic_key(&key));
+        }
     }
 }
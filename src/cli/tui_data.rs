@@ -1,6 +1,8 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::ListFilter;
-use crate::types::{Task, TaskKind, TaskStatus};
+use crate::app::service_types::{ListFilter, SpecDriftCheck};
+use crate::app::service_utils::sort_child_tasks;
+use crate::domain::rollup::child_progress;
+use crate::types::{Task, TaskKind};
 use chrono::Utc;
 
 use super::tui_model::{compute_summary, sort_tui_tasks};
@@ -14,6 +16,7 @@ pub(super) fn load_frame(
     options: &TuiOptions,
     tab: TuiTab,
     selected_index: usize,
+    selected_epic_id: Option<&str>,
 ) -> FrameResult {
     let filter = ListFilter {
         statuses: Some(options.statuses.clone()),
@@ -31,6 +34,9 @@ pub(super) fn load_frame(
         planning_state: None,
         dep_type: None,
         dep_direction: None,
+        spec: None,
+        drift_check: SpecDriftCheck::Hash,
+        query: options.query.clone(),
     };
 
     match service.list(&filter) {
@@ -38,7 +44,7 @@ pub(super) fn load_frame(
             let sorted = sort_tui_tasks(tasks);
             let summary = compute_summary(&sorted);
             let (visible_task_ids, selected_epic_id, epic_progress) =
-                build_view_state(tab, &sorted);
+                build_view_state(tab, &sorted, selected_epic_id);
             let selected = if visible_task_ids.is_empty() {
                 None
             } else {
@@ -52,6 +58,7 @@ pub(super) fn load_frame(
                 filters: TuiFrameFilters {
                     status: options.statuses.clone(),
                     assignee: options.assignee.clone(),
+                    query: options.query.clone(),
                 },
                 summary,
                 tasks: sorted,
@@ -61,6 +68,8 @@ pub(super) fn load_frame(
                 selected_epic_id,
                 epic_progress,
                 visible_task_ids,
+                scroll_offset: 0,
+                with_notes: options.with_notes,
             }))
         }
         Err(error) => FrameResult::Err {
@@ -74,6 +83,7 @@ pub(super) fn load_frame(
 fn build_view_state(
     tab: TuiTab,
     tasks: &[Task],
+    selected_epic_id: Option<&str>,
 ) -> (Vec<String>, Option<String>, Option<TuiEpicProgress>) {
     match tab {
         TuiTab::Tasks | TuiTab::Board => (
@@ -82,34 +92,34 @@ fn build_view_state(
             None,
         ),
         TuiTab::Epics => {
-            let epics: Vec<&Task> = tasks
-                .iter()
-                .filter(|task| task.kind == TaskKind::Epic)
-                .collect();
+            let epics = epic_tasks(tasks);
             if epics.is_empty() {
                 return (Vec::new(), None, None);
             }
 
-            let selected_epic = epics[0];
-            let children: Vec<&Task> = tasks
-                .iter()
-                .filter(|task| task.parent_id.as_deref() == Some(selected_epic.id.as_str()))
-                .collect();
+            // Keep the remembered epic while it is still listed.
+            let selected_epic = selected_epic_id
+                .and_then(|id| epics.iter().copied().find(|task| task.id == id))
+                .unwrap_or(epics[0]);
+            let children = sort_child_tasks(
+                &tasks
+                    .iter()
+                    .filter(|task| task.parent_id.as_deref() == Some(selected_epic.id.as_str()))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
             let visible_task_ids = if children.is_empty() {
                 epics.iter().map(|task| task.id.clone()).collect()
             } else {
                 children.iter().map(|task| task.id.clone()).collect()
             };
 
-            let mut done = 0usize;
-            let mut open = 0usize;
-            let mut in_progress = 0usize;
-            for task in &children {
-                match task.status {
-                    TaskStatus::Closed | TaskStatus::Canceled => done += 1,
-                    TaskStatus::InProgress | TaskStatus::Blocked => in_progress += 1,
-                    _ => open += 1,
-                }
+            let progress = child_progress(&children);
+            let mut checklist_done = 0usize;
+            let mut checklist_total = 0usize;
+            for task in std::iter::once(selected_epic).chain(children.iter()) {
+                checklist_done += task.checklist.iter().filter(|item| item.done).count();
+                checklist_total += task.checklist.len();
             }
 
             (
@@ -118,12 +128,37 @@ fn build_view_state(
                 Some(TuiEpicProgress {
                     epic_id: selected_epic.id.clone(),
                     epic_title: selected_epic.title.clone(),
-                    done,
-                    total: children.len(),
-                    open,
-                    in_progress,
+                    done: progress.done,
+                    total: progress.total,
+                    open: progress.open,
+                    in_progress: progress.in_progress,
+                    checklist_done,
+                    checklist_total,
                 }),
             )
         }
     }
 }
+
+/// Epics in frame order, the sequence the Epics tab steps through.
+pub(super) fn epic_tasks(tasks: &[Task]) -> Vec<&Task> {
+    tasks
+        .iter()
+        .filter(|task| task.kind == TaskKind::Epic)
+        .collect()
+}
+
+/// The epic `step` places away from the selected one, clamped to the ends.
+pub(super) fn step_epic(data: &TuiFrameData, step: isize) -> Option<String> {
+    let epics = epic_tasks(&data.tasks);
+    if epics.is_empty() {
+        return None;
+    }
+    let current = data
+        .selected_epic_id
+        .as_deref()
+        .and_then(|id| epics.iter().position(|task| task.id == id))
+        .unwrap_or(0);
+    let next = current.saturating_add_signed(step).min(epics.len() - 1);
+    Some(epics[next].id.clone())
+}
@@ -0,0 +1,233 @@
+use crate::app::service_types::CreateInput;
+use crate::cli::style;
+use crate::types::{Priority, TaskKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+const KINDS: [TaskKind; 3] = [TaskKind::Task, TaskKind::Feature, TaskKind::Epic];
+const MAX_PRIORITY: Priority = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FormField {
+    Title,
+    Kind,
+    Priority,
+    Parent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FormOutcome {
+    Editing,
+    Cancel,
+    Submit,
+}
+
+/// The `c` create-task modal: title, kind, priority, and parent.
+#[derive(Debug, Clone)]
+pub(super) struct CreateForm {
+    pub field: FormField,
+    pub title: String,
+    pub kind: TaskKind,
+    pub priority: Priority,
+    pub parent: String,
+    pub error: Option<String>,
+}
+
+impl CreateForm {
+    pub fn new(parent: Option<String>) -> Self {
+        Self {
+            field: FormField::Title,
+            title: String::new(),
+            kind: TaskKind::Task,
+            priority: 2,
+            parent: parent.unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> FormOutcome {
+        match key.code {
+            KeyCode::Esc => return FormOutcome::Cancel,
+            KeyCode::Enter => return FormOutcome::Submit,
+            KeyCode::Tab | KeyCode::Down => self.field = next_field(self.field),
+            KeyCode::BackTab | KeyCode::Up => self.field = previous_field(self.field),
+            KeyCode::Left => self.cycle(-1),
+            KeyCode::Right => self.cycle(1),
+            KeyCode::Backspace => {
+                if let Some(text) = self.text_mut() {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(value) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                match self.field {
+                    FormField::Priority => {
+                        if let Some(digit) = value.to_digit(10)
+                            && digit <= u32::from(MAX_PRIORITY)
+                        {
+                            self.priority = digit as Priority;
+                        }
+                    }
+                    FormField::Kind => {}
+                    FormField::Title | FormField::Parent => {
+                        if let Some(text) = self.text_mut() {
+                            text.push(value);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        FormOutcome::Editing
+    }
+
+    /// Builds the service input, or the message to show when the form is incomplete.
+    pub fn to_input(&self) -> Result<CreateInput, String> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err("title is required".to_string());
+        }
+        let parent = self.parent.trim();
+        Ok(CreateInput {
+            title: title.to_string(),
+            kind: self.kind,
+            priority: self.priority,
+            description: None,
+            external_ref: None,
+            discovered_from: None,
+            parent: (!parent.is_empty()).then(|| parent.to_string()),
+            exact_id: false,
+            planning_state: None,
+            explicit_id: None,
+            body_file: None,
+            ensure: false,
+            force: false,
+            skip_duplicate_check: false,
+        })
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        let row = |field: FormField, label: &str, value: String| {
+            let marker = if self.field == field { ">" } else { " " };
+            format!("{} {:9}{}", marker, label, value)
+        };
+        let mut lines = vec![
+            style::heading("create task"),
+            row(FormField::Title, "title", format!("{}_", self.title)),
+            row(
+                FormField::Kind,
+                "kind",
+                format!("< {} >", kind_label(self.kind)),
+            ),
+            row(
+                FormField::Priority,
+                "priority",
+                format!("< P{} >", self.priority),
+            ),
+            row(
+                FormField::Parent,
+                "parent",
+                if self.parent.is_empty() {
+                    "-".to_string()
+                } else {
+                    self.parent.clone()
+                },
+            ),
+        ];
+        if let Some(error) = self.error.as_deref() {
+            lines.push(style::error(error));
+        }
+        lines.push(style::muted(
+            "Enter create  Esc cancel  Tab/Up/Down field  Left/Right change",
+        ));
+        lines
+    }
+
+    fn cycle(&mut self, step: isize) {
+        match self.field {
+            FormField::Kind => {
+                let index = KINDS
+                    .iter()
+                    .position(|kind| *kind == self.kind)
+                    .unwrap_or(0);
+                let next = (index as isize + step).rem_euclid(KINDS.len() as isize);
+                self.kind = KINDS[next as usize];
+            }
+            FormField::Priority => {
+                self.priority =
+                    (self.priority as isize + step).clamp(0, MAX_PRIORITY as isize) as Priority;
+            }
+            FormField::Title | FormField::Parent => {}
+        }
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.field {
+            FormField::Title => Some(&mut self.title),
+            FormField::Parent => Some(&mut self.parent),
+            FormField::Kind | FormField::Priority => None,
+        }
+    }
+}
+
+fn next_field(field: FormField) -> FormField {
+    match field {
+        FormField::Title => FormField::Kind,
+        FormField::Kind => FormField::Priority,
+        FormField::Priority => FormField::Parent,
+        FormField::Parent => FormField::Title,
+    }
+}
+
+fn previous_field(field: FormField) -> FormField {
+    match field {
+        FormField::Title => FormField::Parent,
+        FormField::Kind => FormField::Title,
+        FormField::Priority => FormField::Kind,
+        FormField::Parent => FormField::Priority,
+    }
+}
+
+fn kind_label(kind: TaskKind) -> &'static str {
+    match kind {
+        TaskKind::Task => "task",
+        TaskKind::Feature => "feature",
+        TaskKind::Epic => "epic",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn form_collects_fields_and_defaults_parent() {
+        let mut form = CreateForm::new(Some("tsq-1".to_string()));
+        for value in "Fix q".chars() {
+            assert_eq!(
+                form.handle_key(&press(KeyCode::Char(value))),
+                FormOutcome::Editing
+            );
+        }
+        form.handle_key(&press(KeyCode::Tab));
+        form.handle_key(&press(KeyCode::Left));
+        form.handle_key(&press(KeyCode::Tab));
+        form.handle_key(&press(KeyCode::Char('0')));
+        assert_eq!(form.handle_key(&press(KeyCode::Enter)), FormOutcome::Submit);
+
+        let input = form.to_input().expect("complete form");
+        assert_eq!(input.title, "Fix q");
+        assert_eq!(input.kind, TaskKind::Epic);
+        assert_eq!(input.priority, 0);
+        assert_eq!(input.parent.as_deref(), Some("tsq-1"));
+    }
+
+    #[test]
+    fn form_requires_a_title() {
+        let mut form = CreateForm::new(None);
+        assert_eq!(form.handle_key(&press(KeyCode::Esc)), FormOutcome::Cancel);
+        assert!(form.to_input().is_err());
+    }
+}
@@ -1,7 +1,14 @@
 use crate::errors::TsqError;
 use crate::types::{Task, TaskStatus};
 
-use super::{TuiFrameData, TuiOptions, TuiSummary};
+use crate::cli::terminal::resolve_height;
+
+use super::{TuiFrameData, TuiOptions, TuiSummary, TuiTab};
+
+/// Lines the shell draws around the task table: header, tabs, filter,
+/// summary, separators, inspector, and footer.
+const TABLE_CHROME_LINES: usize = 24;
+const MIN_PAGE_ROWS: usize = 5;
 
 pub(super) fn apply_selection(frame: &mut TuiFrameData, selected_index: usize) {
     if frame.visible_task_ids.is_empty() {
@@ -14,6 +21,36 @@ pub(super) fn apply_selection(frame: &mut TuiFrameData, selected_index: usize) {
     frame.selected_task_id = frame.visible_task_ids.get(clamped).cloned();
 }
 
+/// Table rows that fit on screen for the tab; the Epics tab also shows a
+/// progress line above its table.
+pub(super) fn page_rows(tab: TuiTab) -> usize {
+    let chrome = match tab {
+        TuiTab::Epics => TABLE_CHROME_LINES + 2,
+        TuiTab::Tasks | TuiTab::Board => TABLE_CHROME_LINES,
+    };
+    resolve_height(None)
+        .saturating_sub(chrome)
+        .max(MIN_PAGE_ROWS)
+}
+
+/// Moves the viewport as little as possible to keep `selected` on screen.
+pub(super) fn scroll_offset(selected: usize, total: usize, page: usize, previous: usize) -> usize {
+    let page = page.max(1);
+    let offset = if selected < previous {
+        selected
+    } else if selected >= previous + page {
+        selected + 1 - page
+    } else {
+        previous
+    };
+    offset.min(total.saturating_sub(page))
+}
+
+pub(super) fn apply_scroll(frame: &mut TuiFrameData, page: usize, previous: usize) {
+    let selected = frame.selected_index.unwrap_or(0);
+    frame.scroll_offset = scroll_offset(selected, frame.visible_task_ids.len(), page, previous);
+}
+
 pub(super) fn validate_options(options: &TuiOptions) -> Result<(), TsqError> {
     if options.interval < 1 || options.interval > 60 {
         return Err(TsqError::new(
@@ -27,8 +64,8 @@ pub(super) fn validate_options(options: &TuiOptions) -> Result<(), TsqError> {
 
 pub(super) fn sort_tui_tasks(mut tasks: Vec<Task>) -> Vec<Task> {
     tasks.sort_by(|a, b| {
-        let sa = status_order(a.status);
-        let sb = status_order(b.status);
+        let sa = status_order(&a.status);
+        let sb = status_order(&b.status);
         if sa != sb {
             return sa.cmp(&sb);
         }
@@ -53,7 +90,7 @@ pub(super) fn compute_summary(tasks: &[Task]) -> TuiSummary {
     for task in tasks {
         match task.status {
             TaskStatus::Open => summary.open += 1,
-            TaskStatus::InProgress => summary.in_progress += 1,
+            TaskStatus::InProgress | TaskStatus::Custom(_) => summary.in_progress += 1,
             TaskStatus::Blocked => summary.blocked += 1,
             _ => {}
         }
@@ -61,13 +98,29 @@ pub(super) fn compute_summary(tasks: &[Task]) -> TuiSummary {
     summary
 }
 
-fn status_order(status: TaskStatus) -> usize {
+fn status_order(status: &TaskStatus) -> usize {
     match status {
         TaskStatus::InProgress => 0,
-        TaskStatus::Open => 1,
-        TaskStatus::Blocked => 2,
-        TaskStatus::Deferred => 3,
-        TaskStatus::Closed => 4,
-        TaskStatus::Canceled => 5,
+        TaskStatus::Custom(_) => 1,
+        TaskStatus::Open => 2,
+        TaskStatus::Blocked => 3,
+        TaskStatus::Deferred => 4,
+        TaskStatus::Closed => 5,
+        TaskStatus::Canceled => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scroll_offset;
+
+    #[test]
+    fn scroll_offset_keeps_selection_visible_with_minimal_movement() {
+        assert_eq!(scroll_offset(3, 100, 10, 0), 0);
+        assert_eq!(scroll_offset(10, 100, 10, 0), 1);
+        assert_eq!(scroll_offset(15, 100, 10, 8), 8);
+        assert_eq!(scroll_offset(4, 100, 10, 8), 4);
+        assert_eq!(scroll_offset(99, 100, 10, 0), 90);
+        assert_eq!(scroll_offset(2, 3, 10, 5), 0);
     }
 }
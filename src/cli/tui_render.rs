@@ -1,10 +1,13 @@
 use crate::cli::render::truncate_with_ellipsis;
 use crate::cli::style;
 use crate::cli::terminal::resolve_width;
-use crate::output::{err_envelope, ok_envelope};
+use crate::cli::timestamps::format_timestamp;
+use crate::output::{compact_task_list, err_envelope, ok_envelope, with_label_colors};
 use crate::types::{Task, TaskKind, TaskStatus};
 use std::io::IsTerminal;
 
+use super::tui_data::epic_tasks;
+use super::tui_model::page_rows;
 use super::{
     BoardLane, FrameResult, TaskSpecState, TuiEpicProgress, TuiFrameData, TuiTab, tab_from_data,
 };
@@ -22,7 +25,14 @@ pub(super) fn output_frame(frame: &FrameResult, json: bool, clear_screen: bool,
 fn output_json_frame(frame: &FrameResult) {
     match frame {
         FrameResult::Ok(data) => {
-            let envelope = ok_envelope("tsq tui", data);
+            let mut value = serde_json::to_value(data).unwrap_or_default();
+            if !data.with_notes
+                && let Some(tasks) = value.get_mut("tasks")
+            {
+                compact_task_list(tasks);
+            }
+            with_label_colors(&mut value, style::label_colors());
+            let envelope = ok_envelope("tsq tui", value);
             println!(
                 "{}",
                 serde_json::to_string(&envelope).unwrap_or_else(|_| "{}".to_string())
@@ -75,11 +85,16 @@ fn output_human_frame(frame: &FrameResult, clear_screen: bool, paused: bool) {
             lines.extend(render_inspector(data, width));
             lines.push(style::muted(&"-".repeat(width)));
             if std::io::stdout().is_terminal() {
-                lines.push(if paused {
-                    style::muted("q quit  Tab view  r refresh  p resume  Up/Down select")
+                let pause = if paused { "p resume" } else { "p pause" };
+                let epic_keys = if tab == TuiTab::Epics {
+                    "  Left/Right epic"
                 } else {
-                    style::muted("q quit  Tab view  r refresh  p pause  Up/Down select")
-                });
+                    ""
+                };
+                lines.push(style::muted(&format!(
+                    "q quit  Tab view  r refresh  {}  Up/Down select  c create{}",
+                    pause, epic_keys
+                )));
             }
             for line in lines {
                 println!("{}", line);
@@ -124,17 +139,22 @@ fn render_tabs_line(tab: TuiTab) -> String {
 
 fn render_filter_line(data: &TuiFrameData, width: usize) -> String {
     let text = format!(
-        "status:{}{}",
+        "status:{}{}{}",
         data.filters
             .status
             .iter()
-            .map(|status| status_to_string(*status))
+            .map(|status| status.as_str())
             .collect::<Vec<_>>()
             .join(","),
         data.filters
             .assignee
             .as_ref()
             .map(|assignee| format!(" assignee:{}", assignee))
+            .unwrap_or_default(),
+        data.filters
+            .query
+            .as_ref()
+            .map(|query| format!(" query:{}", query))
             .unwrap_or_default()
     );
     format!(
@@ -161,19 +181,29 @@ fn render_tasks_table(data: &TuiFrameData, width: usize) -> Vec<String> {
     lines.push(render_table_header());
 
     let title_width = table_title_width(width);
-    for task in visible_tasks(data) {
+    let (rows, indicator) = paged_tasks(data, tab_from_data(data));
+    for task in rows {
         lines.push(render_table_row(
             task,
             data.selected_task_id.as_deref() == Some(task.id.as_str()),
             title_width,
         ));
     }
+    lines.extend(indicator);
     lines
 }
 
 fn render_epics_view(data: &TuiFrameData, width: usize) -> Vec<String> {
     let mut lines = Vec::new();
-    lines.push(style::heading("epics"));
+    let epics = epic_tasks(&data.tasks);
+    let position = data
+        .selected_epic_id
+        .as_deref()
+        .and_then(|id| epics.iter().position(|task| task.id == id));
+    lines.push(style::heading(&match position {
+        Some(index) => format!("epics {}/{}", index + 1, epics.len()),
+        None => "epics".to_string(),
+    }));
 
     if let Some(progress) = data.epic_progress.as_ref() {
         lines.push(render_epic_progress(progress, width));
@@ -182,20 +212,22 @@ fn render_epics_view(data: &TuiFrameData, width: usize) -> Vec<String> {
 
     lines.push(render_table_header());
     let title_width = table_title_width(width);
-    for task in visible_tasks(data) {
+    let (rows, indicator) = paged_tasks(data, tab_from_data(data));
+    for task in rows {
         lines.push(render_table_row(
             task,
             data.selected_task_id.as_deref() == Some(task.id.as_str()),
             title_width,
         ));
     }
+    lines.extend(indicator);
 
     lines
 }
 
 fn render_epic_progress(progress: &TuiEpicProgress, width: usize) -> String {
     let meter = render_progress_meter(progress.done, progress.total, 12);
-    let summary = format!(
+    let mut summary = format!(
         "progress: {} {} {} {}/{} open={} in_progress={}",
         progress.epic_id,
         truncate_with_ellipsis(&progress.epic_title, 24),
@@ -205,6 +237,12 @@ fn render_epic_progress(progress: &TuiEpicProgress, width: usize) -> String {
         progress.open,
         progress.in_progress
     );
+    if progress.checklist_total > 0 {
+        summary.push_str(&format!(
+            " checklist={}/{}",
+            progress.checklist_done, progress.checklist_total
+        ));
+    }
     truncate_with_ellipsis(&summary, width.max(24))
 }
 
@@ -214,7 +252,7 @@ fn render_board_view(data: &TuiFrameData, width: usize) -> Vec<String> {
     let mut done_cards = Vec::new();
 
     for task in visible_tasks(data) {
-        match board_lane_for_status(task.status) {
+        match board_lane_for_status(&task.status) {
             BoardLane::Open => open_cards.push(render_board_card(task)),
             BoardLane::InProgress => in_progress_cards.push(render_board_card(task)),
             BoardLane::Done => done_cards.push(render_board_card(task)),
@@ -255,7 +293,7 @@ fn render_board_card(task: &Task) -> String {
     format!(
         "{} {} {} {}",
         style::task_id(&task.id),
-        status_pill(task.status),
+        status_pill(&task.status),
         priority_pill(task.priority),
         spec_pill(task)
     ) + &format!(" {}", title)
@@ -275,12 +313,12 @@ fn render_inspector(data: &TuiFrameData, width: usize) -> Vec<String> {
     let labels = if task.labels.is_empty() {
         "-".to_string()
     } else {
-        task.labels.join(",")
+        style::label_pills(&task.labels)
     };
     let planning = task
         .planning_state
-        .map(planning_state_to_string)
-        .unwrap_or("needs_planning");
+        .as_ref()
+        .map_or("needs_planning", crate::types::PlanningState::as_str);
 
     lines.push(format!("id={}", style::task_id(&task.id)));
     lines.push(format!(
@@ -289,7 +327,7 @@ fn render_inspector(data: &TuiFrameData, width: usize) -> Vec<String> {
     ));
     lines.push(format!(
         "status={} kind={} priority={} planning={}",
-        status_to_string(task.status),
+        task.status.as_str(),
         task_kind_to_string(task.kind),
         task.priority,
         planning
@@ -302,8 +340,19 @@ fn render_inspector(data: &TuiFrameData, width: usize) -> Vec<String> {
     ));
     lines.push(format!(
         "updated={} created={}",
-        task.updated_at, task.created_at
+        format_timestamp(&task.updated_at),
+        format_timestamp(&task.created_at)
     ));
+    if !task.checklist.is_empty() {
+        let done = task.checklist.iter().filter(|item| item.done).count();
+        lines.push(format!("checklist={}/{}", done, task.checklist.len()));
+    }
+    if let Some(blocked_on) = task.blocked_on.as_deref() {
+        lines.push(format!(
+            "blocked_on={}",
+            truncate_with_ellipsis(blocked_on, width.saturating_sub(12).max(12))
+        ));
+    }
     lines.push(render_spec_inspector_line(task, width));
     lines
 }
@@ -335,32 +384,14 @@ fn type_pill(kind: TaskKind) -> String {
     format!("[{}]", task_kind_to_string(kind))
 }
 
-fn status_to_string(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Open => "open",
-        TaskStatus::InProgress => "in_progress",
-        TaskStatus::Blocked => "blocked",
-        TaskStatus::Closed => "closed",
-        TaskStatus::Canceled => "canceled",
-        TaskStatus::Deferred => "deferred",
-    }
-}
-
-fn status_pill(status: TaskStatus) -> String {
-    format!("[{}]", status_to_string(status))
+fn status_pill(status: &TaskStatus) -> String {
+    format!("[{}]", status.as_str())
 }
 
 fn priority_pill(priority: u8) -> String {
     format!("[P{}]", priority)
 }
 
-fn planning_state_to_string(state: crate::types::PlanningState) -> &'static str {
-    match state {
-        crate::types::PlanningState::NeedsPlanning => "needs_planning",
-        crate::types::PlanningState::Planned => "planned",
-    }
-}
-
 fn spec_state(task: &Task) -> TaskSpecState {
     match (task.spec_path.as_deref(), task.spec_fingerprint.as_deref()) {
         (Some(_), Some(_)) => TaskSpecState::Attached,
@@ -393,10 +424,13 @@ fn short_spec_fingerprint(fingerprint: &str) -> &str {
     &fingerprint[..byte_end]
 }
 
-fn board_lane_for_status(status: TaskStatus) -> BoardLane {
+/// Configured statuses are active work, so they share the in-progress lane.
+fn board_lane_for_status(status: &TaskStatus) -> BoardLane {
     match status {
         TaskStatus::Open | TaskStatus::Deferred => BoardLane::Open,
-        TaskStatus::InProgress | TaskStatus::Blocked => BoardLane::InProgress,
+        TaskStatus::InProgress | TaskStatus::Blocked | TaskStatus::Custom(_) => {
+            BoardLane::InProgress
+        }
         TaskStatus::Closed | TaskStatus::Canceled => BoardLane::Done,
     }
 }
@@ -417,17 +451,44 @@ fn table_title_width(width: usize) -> usize {
 fn render_table_row(task: &Task, selected: bool, title_width: usize) -> String {
     let marker = if selected { ">" } else { " " };
     let assignee = task.assignee.as_deref().unwrap_or("unassigned");
-    format!(
+    let row = format!(
         "{} {:<12} {:<8} {:<24} {:<13} {:<12} {:<9} {:<9}",
         marker,
         task.id,
         type_pill(task.kind),
         truncate_with_ellipsis(&task.title, title_width),
-        status_pill(task.status),
+        status_pill(&task.status),
         truncate_with_ellipsis(assignee, 12),
         priority_pill(task.priority),
         spec_pill(task),
-    )
+    );
+    if task.labels.is_empty() {
+        return row;
+    }
+    format!("{} {}", row, style::label_pills(&task.labels))
+}
+
+/// Rows inside the scroll viewport plus a position line when the table is
+/// longer than the terminal. Non-terminal output (e.g. `--once` piped) is
+/// never paged.
+fn paged_tasks(data: &TuiFrameData, tab: TuiTab) -> (Vec<&Task>, Option<String>) {
+    let tasks = visible_tasks(data);
+    if !std::io::stdout().is_terminal() {
+        return (tasks, None);
+    }
+    let page = page_rows(tab);
+    if tasks.len() <= page {
+        return (tasks, None);
+    }
+    let start = data.scroll_offset.min(tasks.len() - page);
+    let end = start + page;
+    let indicator = style::muted(&format!(
+        "rows {}-{} of {}  PgUp/PgDn Home/End",
+        start + 1,
+        end,
+        tasks.len()
+    ));
+    (tasks[start..end].to_vec(), Some(indicator))
 }
 
 fn visible_tasks(data: &TuiFrameData) -> Vec<&Task> {
@@ -1,5 +1,5 @@
 use crate::app::service::TasqueService;
-use crate::app::service_types::ListFilter;
+use crate::app::service_types::{ListFilter, SpecDriftCheck};
 use crate::cli::render::{
     TreeRenderOptions, format_meta_badge, format_status, format_status_text, render_task_tree,
     truncate_with_ellipsis,
@@ -7,16 +7,26 @@ use crate::cli::render::{
 use crate::cli::style;
 use crate::cli::terminal::{Density, resolve_density, resolve_width};
 use crate::errors::TsqError;
-use crate::output::{err_envelope, ok_envelope};
+use crate::output::{
+    compact_task_list, compact_task_notes, compact_task_tree, err_envelope, ok_envelope,
+};
 use crate::types::{Task, TaskStatus, TaskTreeNode};
 use chrono::Utc;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::io::{IsTerminal, Write};
 use std::thread;
 use std::time::Duration;
 
+#[path = "watch_changes.rs"]
+mod watch_changes;
+use watch_changes::{ChangeTracker, DeltaTracker, change_label};
+pub use watch_changes::{WatchChange, WatchChangeKind, WatchDelta, WatchDeltaKind};
+#[path = "watch_tasks.rs"]
+mod watch_tasks;
+
 const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
 
 #[derive(Debug, Clone)]
@@ -24,9 +34,21 @@ pub struct WatchOptions {
     pub interval: i64,
     pub statuses: Vec<TaskStatus>,
     pub assignee: Option<String>,
+    pub query: Option<String>,
     pub tree: bool,
     pub once: bool,
     pub json: bool,
+    /// Frames a changed task stays highlighted; `0` turns highlighting off.
+    pub fade: usize,
+    /// With `json`, emit only task deltas after the first full frame.
+    pub deltas: bool,
+    /// Keep each task's `notes` array in JSON frames instead of `note_count`.
+    pub with_notes: bool,
+    /// Follow these tasks' events instead of rendering the filtered list.
+    pub tasks: Vec<String>,
+    /// Merge every sync shard into one flat list with a workspace column.
+    pub workspace: bool,
+    pub exact_id: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +64,8 @@ pub struct WatchFrameFilters {
     pub status: Vec<TaskStatus>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,10 +77,30 @@ pub struct WatchFrameData {
     pub tasks: Vec<Task>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tree: Option<Vec<TaskTreeNode>>,
+    /// Tasks created, moved, or claimed since recent refreshes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<WatchChange>,
+    /// Set in `--deltas` mode once a full frame has been sent.
+    #[serde(skip)]
+    pub deltas: Option<Vec<WatchDelta>>,
+    /// Sync branch of each entry in `tasks` under `--workspace`; JSON frames
+    /// carry it as a `workspace` field on each task.
+    #[serde(skip)]
+    pub workspaces: Vec<String>,
+}
+
+/// A `--deltas` frame: the summary plus only what changed since the last frame.
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchDeltaFrame<'a> {
+    pub frame_ts: &'a str,
+    pub interval_s: i64,
+    pub filters: &'a WatchFrameFilters,
+    pub summary: &'a WatchSummary,
+    pub deltas: &'a [WatchDelta],
 }
 
 enum FrameResult {
-    Ok(WatchFrameData),
+    Ok(Box<WatchFrameData>),
     Err {
         error: String,
         code: String,
@@ -79,6 +123,10 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
         return 1;
     }
 
+    if !options.tasks.is_empty() {
+        return watch_tasks::follow_tasks(service, &options);
+    }
+
     if options.once {
         let frame = load_frame(service, &options);
         output_frame(&frame, &options, false, false);
@@ -93,6 +141,10 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
         std::io::stdout().is_terminal() && std::io::stdin().is_terminal() && !options.json;
     let mut paused = false;
     let mut last_good_frame: Option<WatchFrameData> = None;
+    let mut tracker = WatchTrackers {
+        changes: ChangeTracker::new(options.fade),
+        deltas: DeltaTracker::default(),
+    };
     let interval = Duration::from_secs(options.interval as u64);
 
     let _raw_mode = if can_interact {
@@ -113,7 +165,14 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
     };
     let interactive = can_interact && _raw_mode.is_some();
 
-    refresh_frame(service, &options, can_clear, paused, &mut last_good_frame);
+    refresh_frame(
+        service,
+        &options,
+        can_clear,
+        paused,
+        &mut tracker,
+        &mut last_good_frame,
+    );
 
     if interactive {
         loop {
@@ -129,6 +188,7 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
                                 &options,
                                 can_clear,
                                 paused,
+                                &mut tracker,
                                 &mut last_good_frame,
                             );
                             continue;
@@ -136,7 +196,12 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
                         if is_pause_toggle_key(&key) {
                             paused = !paused;
                             if let Some(frame) = last_good_frame.clone() {
-                                output_frame(&FrameResult::Ok(frame), &options, can_clear, paused);
+                                output_frame(
+                                    &FrameResult::Ok(Box::new(frame)),
+                                    &options,
+                                    can_clear,
+                                    paused,
+                                );
                             }
                         }
                     }
@@ -152,7 +217,14 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
                 },
                 Ok(false) => {
                     if !paused {
-                        refresh_frame(service, &options, can_clear, paused, &mut last_good_frame);
+                        refresh_frame(
+                            service,
+                            &options,
+                            can_clear,
+                            paused,
+                            &mut tracker,
+                            &mut last_good_frame,
+                        );
                     }
                 }
                 Err(error) => {
@@ -171,20 +243,39 @@ pub fn start_watch(service: &TasqueService, options: WatchOptions) -> i32 {
 
     loop {
         thread::sleep(interval);
-        refresh_frame(service, &options, can_clear, paused, &mut last_good_frame);
+        refresh_frame(
+            service,
+            &options,
+            can_clear,
+            paused,
+            &mut tracker,
+            &mut last_good_frame,
+        );
     }
 }
 
+struct WatchTrackers {
+    changes: ChangeTracker,
+    deltas: DeltaTracker,
+}
+
 fn refresh_frame(
     service: &TasqueService,
     options: &WatchOptions,
     clear_screen: bool,
     paused: bool,
+    tracker: &mut WatchTrackers,
     last_good_frame: &mut Option<WatchFrameData>,
 ) {
     match load_frame(service, options) {
-        FrameResult::Ok(data) => {
-            *last_good_frame = Some(data.clone());
+        FrameResult::Ok(mut data) => {
+            data.changes = tracker
+                .changes
+                .observe(&data.frame_ts, &data.tasks, &data.workspaces);
+            if options.deltas {
+                data.deltas = tracker.deltas.diff(&data.tasks);
+            }
+            *last_good_frame = Some((*data).clone());
             output_frame(&FrameResult::Ok(data), options, clear_screen, paused);
         }
         FrameResult::Err {
@@ -195,7 +286,12 @@ fn refresh_frame(
             if !options.json
                 && let Some(previous) = last_good_frame.clone()
             {
-                output_frame(&FrameResult::Ok(previous), options, clear_screen, paused);
+                output_frame(
+                    &FrameResult::Ok(Box::new(previous)),
+                    options,
+                    clear_screen,
+                    paused,
+                );
             }
             output_frame(
                 &FrameResult::Err {
@@ -258,6 +354,22 @@ impl Drop for RawModeGuard {
     }
 }
 
+/// One frame without change tracking, for other live views such as the
+/// `tsq serve` dashboard.
+pub fn load_frame_data(
+    service: &TasqueService,
+    options: &WatchOptions,
+) -> Result<WatchFrameData, TsqError> {
+    match load_frame(service, options) {
+        FrameResult::Ok(data) => Ok(*data),
+        FrameResult::Err {
+            error,
+            code,
+            exit_code,
+        } => Err(TsqError::new(code, error, exit_code)),
+    }
+}
+
 fn load_frame(service: &TasqueService, options: &WatchOptions) -> FrameResult {
     let filter = ListFilter {
         statuses: Some(options.statuses.clone()),
@@ -275,8 +387,32 @@ fn load_frame(service: &TasqueService, options: &WatchOptions) -> FrameResult {
         planning_state: None,
         dep_type: None,
         dep_direction: None,
+        spec: None,
+        drift_check: SpecDriftCheck::Hash,
+        query: options.query.clone(),
     };
 
+    if options.workspace {
+        return match load_workspace_tasks(service, &filter) {
+            Ok((tasks, workspaces)) => FrameResult::Ok(Box::new(WatchFrameData {
+                frame_ts: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                interval_s: options.interval,
+                filters: frame_filters(options),
+                summary: compute_summary(&tasks),
+                tasks,
+                tree: None,
+                changes: Vec::new(),
+                deltas: None,
+                workspaces,
+            })),
+            Err(error) => FrameResult::Err {
+                error: error.message,
+                code: error.code,
+                exit_code: error.exit_code,
+            },
+        };
+    }
+
     match service.list(&filter) {
         Ok(tasks) => {
             let sorted = sort_watch_tasks(tasks);
@@ -295,17 +431,17 @@ fn load_frame(service: &TasqueService, options: &WatchOptions) -> FrameResult {
             } else {
                 None
             };
-            FrameResult::Ok(WatchFrameData {
+            FrameResult::Ok(Box::new(WatchFrameData {
                 frame_ts: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
                 interval_s: options.interval,
-                filters: WatchFrameFilters {
-                    status: options.statuses.clone(),
-                    assignee: options.assignee.clone(),
-                },
+                filters: frame_filters(options),
                 summary,
                 tasks: sorted,
                 tree,
-            })
+                changes: Vec::new(),
+                deltas: None,
+                workspaces: Vec::new(),
+            }))
         }
         Err(error) => FrameResult::Err {
             error: error.message,
@@ -315,18 +451,80 @@ fn load_frame(service: &TasqueService, options: &WatchOptions) -> FrameResult {
     }
 }
 
+fn frame_filters(options: &WatchOptions) -> WatchFrameFilters {
+    WatchFrameFilters {
+        status: options.statuses.clone(),
+        assignee: options.assignee.clone(),
+        query: options.query.clone(),
+    }
+}
+
+/// Lists `filter` on every workspace shard, merged in watch order, with the
+/// shard branch of each task alongside.
+fn load_workspace_tasks(
+    service: &TasqueService,
+    filter: &ListFilter,
+) -> Result<(Vec<Task>, Vec<String>), TsqError> {
+    let mut merged = Vec::new();
+    for (branch, shard) in service.workspace_services()? {
+        merged.extend(
+            shard
+                .list(filter)?
+                .into_iter()
+                .map(|task| (task, branch.clone())),
+        );
+    }
+    merged.sort_by(|(a, a_branch), (b, b_branch)| {
+        compare_watch_tasks(a, b).then_with(|| a_branch.cmp(b_branch))
+    });
+    Ok(merged.into_iter().unzip())
+}
+
 fn output_frame(frame: &FrameResult, options: &WatchOptions, clear_screen: bool, paused: bool) {
     if options.json {
-        output_json_frame(frame);
+        output_json_frame(frame, options.with_notes);
         return;
     }
     output_human_frame(frame, options, clear_screen, paused);
 }
 
-fn output_json_frame(frame: &FrameResult) {
+fn compact_frame_notes(frame: &mut Value) {
+    if let Some(tasks) = frame.get_mut("tasks") {
+        compact_task_list(tasks);
+    }
+    if let Some(tree) = frame.get_mut("tree") {
+        compact_task_tree(tree);
+    }
+    if let Some(deltas) = frame.get_mut("deltas").and_then(Value::as_array_mut) {
+        for task in deltas.iter_mut().filter_map(|delta| delta.get_mut("task")) {
+            compact_task_notes(task);
+        }
+    }
+}
+
+fn output_json_frame(frame: &FrameResult, with_notes: bool) {
     match frame {
         FrameResult::Ok(data) => {
-            let envelope = ok_envelope("tsq watch", data);
+            let mut value = match data.deltas.as_deref() {
+                Some(deltas) => serde_json::to_value(WatchDeltaFrame {
+                    frame_ts: &data.frame_ts,
+                    interval_s: data.interval_s,
+                    filters: &data.filters,
+                    summary: &data.summary,
+                    deltas,
+                })
+                .unwrap_or_default(),
+                None => serde_json::to_value(data).unwrap_or_default(),
+            };
+            if !with_notes {
+                compact_frame_notes(&mut value);
+            }
+            if let Some(tasks) = value.get_mut("tasks").and_then(Value::as_array_mut) {
+                for (task, workspace) in tasks.iter_mut().zip(&data.workspaces) {
+                    task["workspace"] = Value::String(workspace.clone());
+                }
+            }
+            let envelope = ok_envelope("tsq watch", value);
             println!(
                 "{}",
                 serde_json::to_string(&envelope).unwrap_or_else(|_| "{}".to_string())
@@ -384,8 +582,11 @@ fn output_human_frame(
                         .into_iter()
                         .filter(|line| !line.starts_with("total=")),
                 );
+                if !data.changes.is_empty() {
+                    lines.push(render_changes_line(&data.changes, width));
+                }
             } else {
-                lines.extend(render_flat_tasks(&data.tasks, width));
+                lines.extend(render_flat_tasks(data, width));
             }
             lines.push(style::muted(&"─".repeat(width)));
             if is_tty {
@@ -413,17 +614,22 @@ fn print_watch_line(line: &str, is_tty: bool) {
 
 fn render_header(data: &WatchFrameData, paused: bool, width: usize) -> String {
     let filter_str = format!(
-        "status:{}{}",
+        "status:{}{}{}",
         data.filters
             .status
             .iter()
-            .map(|status| status_to_string(*status))
+            .map(|status| status.as_str())
             .collect::<Vec<_>>()
             .join(","),
         data.filters
             .assignee
             .as_ref()
             .map(|value| format!(" assignee:{}", value))
+            .unwrap_or_default(),
+        data.filters
+            .query
+            .as_ref()
+            .map(|query| format!(" query:{}", query))
             .unwrap_or_default()
     );
     let pause_tag = if paused { " ⏸ paused" } else { "" };
@@ -452,13 +658,37 @@ fn render_summary(summary: &WatchSummary) -> String {
     )
 }
 
-fn render_flat_tasks(tasks: &[Task], width: usize) -> Vec<String> {
+fn render_changes_line(changes: &[WatchChange], width: usize) -> String {
+    let text = changes
+        .iter()
+        .map(|change| format!("{} {}", change.id, change_label(change.kind)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    style::warning(&truncate_with_ellipsis(
+        &format!("changed: {}", text),
+        width.max(24),
+    ))
+}
+
+fn render_flat_tasks(data: &WatchFrameData, width: usize) -> Vec<String> {
     let density = resolve_density(width);
+    let workspace_width = data.workspaces.iter().map(String::len).max().unwrap_or(0);
     let mut lines = Vec::new();
-    for task in tasks {
-        let status = format_status(task.status);
-        let status_text = format_status_text(task.status);
-        let meta = format_meta_badge(task);
+    for (index, task) in data.tasks.iter().enumerate() {
+        let workspace = data.workspaces.get(index);
+        let status = format_status(&task.status);
+        let status_text = format_status_text(&task.status);
+        let mut meta = format_meta_badge(task);
+        if let Some(change) = data
+            .changes
+            .iter()
+            .find(|change| change.id == task.id && change.workspace.as_ref() == workspace)
+        {
+            meta.push_str(&format!(
+                " {}",
+                style::warning(&format!("[{}]", change_label(change.kind)))
+            ));
+        }
         if density == Density::Narrow {
             let title_width =
                 (width as isize - status_text.len() as isize - 1 - task.id.len() as isize - 1)
@@ -469,11 +699,23 @@ fn render_flat_tasks(tasks: &[Task], width: usize) -> Vec<String> {
                 style::task_id(&task.id),
                 truncate_with_ellipsis(&task.title, title_width)
             ));
-            lines.push(format!("  {}", meta));
+            match workspace {
+                Some(workspace) => lines.push(format!("  {}  {}", style::muted(workspace), meta)),
+                None => lines.push(format!("  {}", meta)),
+            }
         } else {
+            let column = workspace
+                .map(|workspace| {
+                    format!(
+                        "{}  ",
+                        style::muted(&format!("{:<workspace_width$}", workspace))
+                    )
+                })
+                .unwrap_or_default();
             lines.push(format!(
-                "{}  {}  {}  {}",
+                "{}  {}{}  {}  {}",
                 status,
+                column,
                 style::task_id(&task.id),
                 task.title,
                 meta
@@ -495,31 +737,34 @@ fn validate_options(options: &WatchOptions) -> Result<(), TsqError> {
 }
 
 fn sort_watch_tasks(mut tasks: Vec<Task>) -> Vec<Task> {
-    tasks.sort_by(|a, b| {
-        let sa = status_order(a.status);
-        let sb = status_order(b.status);
-        if sa != sb {
-            return sa.cmp(&sb);
-        }
-        if a.priority != b.priority {
-            return a.priority.cmp(&b.priority);
-        }
-        if a.created_at != b.created_at {
-            return a.created_at.cmp(&b.created_at);
-        }
-        a.id.cmp(&b.id)
-    });
+    tasks.sort_by(compare_watch_tasks);
     tasks
 }
 
-fn status_order(status: TaskStatus) -> usize {
+fn compare_watch_tasks(a: &Task, b: &Task) -> std::cmp::Ordering {
+    let sa = status_order(&a.status);
+    let sb = status_order(&b.status);
+    if sa != sb {
+        return sa.cmp(&sb);
+    }
+    if a.priority != b.priority {
+        return a.priority.cmp(&b.priority);
+    }
+    if a.created_at != b.created_at {
+        return a.created_at.cmp(&b.created_at);
+    }
+    a.id.cmp(&b.id)
+}
+
+fn status_order(status: &TaskStatus) -> usize {
     match status {
         TaskStatus::InProgress => 0,
-        TaskStatus::Open => 1,
-        TaskStatus::Blocked => 2,
-        TaskStatus::Deferred => 3,
-        TaskStatus::Closed => 4,
-        TaskStatus::Canceled => 5,
+        TaskStatus::Custom(_) => 1,
+        TaskStatus::Open => 2,
+        TaskStatus::Blocked => 3,
+        TaskStatus::Deferred => 4,
+        TaskStatus::Closed => 5,
+        TaskStatus::Canceled => 6,
     }
 }
 
@@ -533,21 +778,10 @@ fn compute_summary(tasks: &[Task]) -> WatchSummary {
     for task in tasks {
         match task.status {
             TaskStatus::Open => summary.open += 1,
-            TaskStatus::InProgress => summary.in_progress += 1,
+            TaskStatus::InProgress | TaskStatus::Custom(_) => summary.in_progress += 1,
             TaskStatus::Blocked => summary.blocked += 1,
             _ => {}
         }
     }
     summary
 }
-
-fn status_to_string(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Open => "open",
-        TaskStatus::InProgress => "in_progress",
-        TaskStatus::Blocked => "blocked",
-        TaskStatus::Closed => "closed",
-        TaskStatus::Canceled => "canceled",
-        TaskStatus::Deferred => "deferred",
-    }
-}
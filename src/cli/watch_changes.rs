@@ -0,0 +1,347 @@
+use crate::types::{Task, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchChangeKind {
+    Created,
+    StatusChanged,
+    Claimed,
+}
+
+/// A task that changed within the last `fade` frames.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchChange {
+    pub id: String,
+    /// Sync branch of the task under `watch --workspace`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    pub kind: WatchChangeKind,
+    /// Frames left before the highlight fades, counting this one.
+    pub frames_left: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Seen {
+    status: TaskStatus,
+    assignee: Option<String>,
+}
+
+/// Diffs consecutive watch frames and keeps each change highlighted for
+/// `fade` frames. The first frame only records a baseline.
+#[derive(Debug, Default)]
+pub struct ChangeTracker {
+    fade: usize,
+    previous: Option<(String, HashMap<String, Seen>)>,
+    active: HashMap<String, WatchChange>,
+}
+
+impl ChangeTracker {
+    pub fn new(fade: usize) -> Self {
+        Self {
+            fade,
+            ..Self::default()
+        }
+    }
+
+    /// Records a new frame and returns the highlighted changes in task order.
+    /// `workspaces[i]` names the shard of `tasks[i]` so the same id on two
+    /// shards is tracked separately; empty means a single shard.
+    pub fn observe(
+        &mut self,
+        frame_ts: &str,
+        tasks: &[Task],
+        workspaces: &[String],
+    ) -> Vec<WatchChange> {
+        let workspace_of = |index: usize| workspaces.get(index).cloned();
+        let key_of = |index: usize, task: &Task| match workspaces.get(index) {
+            Some(workspace) => format!("{}/{}", workspace, task.id),
+            None => task.id.clone(),
+        };
+        for change in self.active.values_mut() {
+            change.frames_left = change.frames_left.saturating_sub(1);
+        }
+        self.active.retain(|_, change| change.frames_left > 0);
+
+        if self.fade > 0
+            && let Some((previous_ts, previous)) = self.previous.as_ref()
+        {
+            for (index, task) in tasks.iter().enumerate() {
+                let key = key_of(index, task);
+                let kind = match previous.get(&key) {
+                    None if task.created_at.as_str() > previous_ts.as_str() => {
+                        Some(WatchChangeKind::Created)
+                    }
+                    None => Some(WatchChangeKind::StatusChanged),
+                    Some(seen) if task.assignee.is_some() && task.assignee != seen.assignee => {
+                        Some(WatchChangeKind::Claimed)
+                    }
+                    Some(seen) if seen.status != task.status => {
+                        Some(WatchChangeKind::StatusChanged)
+                    }
+                    Some(_) => None,
+                };
+                if let Some(kind) = kind {
+                    self.active.insert(
+                        key,
+                        WatchChange {
+                            id: task.id.clone(),
+                            workspace: workspace_of(index),
+                            kind,
+                            frames_left: self.fade,
+                        },
+                    );
+                }
+            }
+        }
+
+        self.previous = Some((
+            frame_ts.to_string(),
+            tasks
+                .iter()
+                .enumerate()
+                .map(|(index, task)| {
+                    (
+                        key_of(index, task),
+                        Seen {
+                            status: task.status.clone(),
+                            assignee: task.assignee.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        ));
+        tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, task)| self.active.get(&key_of(index, task)).cloned())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchDeltaKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// One task-level difference between consecutive frames; removed entries
+/// carry only the id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchDelta {
+    #[serde(rename = "type")]
+    pub kind: WatchDeltaKind,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<Task>,
+}
+
+/// Remembers the last frame's tasks so `--deltas` can emit only differences.
+#[derive(Debug, Default)]
+pub struct DeltaTracker {
+    previous: Option<HashMap<String, Task>>,
+}
+
+impl DeltaTracker {
+    /// Deltas against the previous frame, or `None` for the first (full) frame.
+    pub fn diff(&mut self, tasks: &[Task]) -> Option<Vec<WatchDelta>> {
+        let current: HashMap<String, Task> = tasks
+            .iter()
+            .map(|task| (task.id.clone(), task.clone()))
+            .collect();
+        let previous = self.previous.replace(current)?;
+
+        let mut deltas: Vec<WatchDelta> = tasks
+            .iter()
+            .filter_map(|task| {
+                let kind = match previous.get(&task.id) {
+                    None => WatchDeltaKind::Added,
+                    Some(before) if before != task => WatchDeltaKind::Changed,
+                    Some(_) => return None,
+                };
+                Some(WatchDelta {
+                    kind,
+                    id: task.id.clone(),
+                    task: Some(task.clone()),
+                })
+            })
+            .collect();
+        let mut removed: Vec<&String> = previous
+            .keys()
+            .filter(|id| !tasks.iter().any(|task| &task.id == *id))
+            .collect();
+        removed.sort();
+        deltas.extend(removed.into_iter().map(|id| WatchDelta {
+            kind: WatchDeltaKind::Removed,
+            id: id.clone(),
+            task: None,
+        }));
+        Some(deltas)
+    }
+}
+
+pub fn change_label(kind: WatchChangeKind) -> &'static str {
+    match kind {
+        WatchChangeKind::Created => "new",
+        WatchChangeKind::StatusChanged => "status",
+        WatchChangeKind::Claimed => "claimed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, status: &str, assignee: Option<&str>, created_at: &str) -> Task {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "alias": id,
+            "kind": "task",
+            "title": id,
+            "status": status,
+            "priority": 2,
+            "assignee": assignee,
+            "created_at": created_at,
+            "updated_at": created_at,
+        }))
+        .expect("task fixture")
+    }
+
+    #[test]
+    fn first_frame_is_a_baseline() {
+        let mut tracker = ChangeTracker::new(2);
+        let tasks = [task("tsq-1", "open", None, "2026-01-01T00:00:00.000Z")];
+        assert!(
+            tracker
+                .observe("2026-01-02T00:00:00.000Z", &tasks, &[])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn changes_are_classified_and_fade() {
+        let mut tracker = ChangeTracker::new(2);
+        let old = "2026-01-01T00:00:00.000Z";
+        tracker.observe(
+            "2026-01-02T00:00:00.000Z",
+            &[
+                task("tsq-1", "open", None, old),
+                task("tsq-2", "open", None, old),
+            ],
+            &[],
+        );
+
+        let next = [
+            task("tsq-1", "in_progress", Some("ada"), old),
+            task("tsq-2", "blocked", None, old),
+            task("tsq-3", "open", None, "2026-01-03T00:00:00.000Z"),
+        ];
+        let kinds: Vec<(String, WatchChangeKind)> = tracker
+            .observe("2026-01-04T00:00:00.000Z", &next, &[])
+            .into_iter()
+            .map(|change| (change.id, change.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("tsq-1".to_string(), WatchChangeKind::Claimed),
+                ("tsq-2".to_string(), WatchChangeKind::StatusChanged),
+                ("tsq-3".to_string(), WatchChangeKind::Created),
+            ]
+        );
+
+        let still = tracker.observe("2026-01-05T00:00:00.000Z", &next, &[]);
+        assert_eq!(still.len(), 3);
+        assert!(still.iter().all(|change| change.frames_left == 1));
+        assert!(
+            tracker
+                .observe("2026-01-06T00:00:00.000Z", &next, &[])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn the_same_id_on_two_shards_is_tracked_separately() {
+        let mut tracker = ChangeTracker::new(2);
+        let old = "2026-01-01T00:00:00.000Z";
+        let tasks = [
+            task("tsq-1", "open", None, old),
+            task("tsq-1", "blocked", None, old),
+        ];
+        let shards = ["tsq-sync".to_string(), "tsq-sync-web".to_string()];
+        tracker.observe("2026-01-02T00:00:00.000Z", &tasks, &shards);
+        assert!(
+            tracker
+                .observe("2026-01-03T00:00:00.000Z", &tasks, &shards)
+                .is_empty()
+        );
+
+        let moved = [
+            task("tsq-1", "open", None, old),
+            task("tsq-1", "in_progress", None, old),
+        ];
+        let changes = tracker.observe("2026-01-04T00:00:00.000Z", &moved, &shards);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].workspace.as_deref(), Some("tsq-sync-web"));
+    }
+
+    #[test]
+    fn deltas_report_added_changed_and_removed_tasks() {
+        let mut tracker = DeltaTracker::default();
+        let old = "2026-01-01T00:00:00.000Z";
+        assert!(
+            tracker
+                .diff(&[
+                    task("tsq-1", "open", None, old),
+                    task("tsq-2", "open", None, old),
+                ])
+                .is_none()
+        );
+
+        let deltas = tracker
+            .diff(&[
+                task("tsq-1", "in_progress", None, old),
+                task("tsq-3", "open", None, old),
+            ])
+            .expect("second frame diff");
+        let summary: Vec<(WatchDeltaKind, &str, bool)> = deltas
+            .iter()
+            .map(|delta| (delta.kind, delta.id.as_str(), delta.task.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (WatchDeltaKind::Changed, "tsq-1", true),
+                (WatchDeltaKind::Added, "tsq-3", true),
+                (WatchDeltaKind::Removed, "tsq-2", false),
+            ]
+        );
+        assert_eq!(
+            tracker.diff(&[
+                task("tsq-1", "in_progress", None, old),
+                task("tsq-3", "open", None, old),
+            ]),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn zero_fade_disables_highlighting() {
+        let mut tracker = ChangeTracker::new(0);
+        let old = "2026-01-01T00:00:00.000Z";
+        tracker.observe(
+            "2026-01-02T00:00:00.000Z",
+            &[task("tsq-1", "open", None, old)],
+            &[],
+        );
+        let changed = [task("tsq-1", "blocked", None, old)];
+        assert!(
+            tracker
+                .observe("2026-01-03T00:00:00.000Z", &changed, &[])
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use super::WatchOptions;
+use crate::app::service::TasqueService;
+use crate::app::service_types::{TaskActivityEntry, TaskActivityInput, TaskActivityResult};
+use crate::cli::render::format_activity_line;
+use crate::cli::style;
+use crate::errors::TsqError;
+use crate::output::{err_envelope, ok_envelope};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+/// One `--task` frame: the events recorded since the previous poll.
+#[derive(Debug, Serialize)]
+struct TaskEventsFrame<'a> {
+    frame_ts: String,
+    interval_s: i64,
+    tasks: &'a [String],
+    events: &'a [TaskActivityEntry],
+}
+
+/// `tsq watch --task`: prints new events on the followed tasks as they are
+/// recorded. Events that already exist when the watch starts are only
+/// printed with `--once`.
+pub fn follow_tasks(service: &TasqueService, options: &WatchOptions) -> i32 {
+    let initial = match load_activity(service, options) {
+        Ok(activity) => activity,
+        Err(error) => {
+            output_error(options, &error);
+            return error.exit_code;
+        }
+    };
+    if options.once {
+        output_events(options, &initial.task_ids, &initial.events);
+        return 0;
+    }
+
+    let mut seen: HashSet<String> = initial
+        .events
+        .iter()
+        .map(|entry| entry.event_id.clone())
+        .collect();
+    if options.json {
+        output_events(options, &initial.task_ids, &[]);
+    } else {
+        println!(
+            "{}",
+            style::muted(&format!(
+                "following {} (Ctrl-C to stop)",
+                initial.task_ids.join(", ")
+            ))
+        );
+        let _ = std::io::stdout().flush();
+    }
+
+    let interval = Duration::from_secs(options.interval as u64);
+    loop {
+        thread::sleep(interval);
+        match load_activity(service, options) {
+            Ok(activity) => {
+                let fresh: Vec<TaskActivityEntry> = activity
+                    .events
+                    .into_iter()
+                    .filter(|entry| seen.insert(entry.event_id.clone()))
+                    .collect();
+                if !fresh.is_empty() {
+                    output_events(options, &activity.task_ids, &fresh);
+                }
+            }
+            Err(error) => output_error(options, &error),
+        }
+    }
+}
+
+fn load_activity(
+    service: &TasqueService,
+    options: &WatchOptions,
+) -> Result<TaskActivityResult, TsqError> {
+    service.task_activity(TaskActivityInput {
+        ids: options.tasks.clone(),
+        exact_id: options.exact_id,
+    })
+}
+
+fn output_events(options: &WatchOptions, task_ids: &[String], events: &[TaskActivityEntry]) {
+    if options.json {
+        let frame = TaskEventsFrame {
+            frame_ts: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            interval_s: options.interval,
+            tasks: task_ids,
+            events,
+        };
+        let envelope = ok_envelope("tsq watch", frame);
+        println!(
+            "{}",
+            serde_json::to_string(&envelope).unwrap_or_else(|_| "{}".to_string())
+        );
+    } else {
+        for entry in events {
+            println!("{}", format_activity_line(entry));
+        }
+    }
+    let _ = std::io::stdout().flush();
+}
+
+fn output_error(options: &WatchOptions, error: &TsqError) {
+    if options.json {
+        let envelope = err_envelope(
+            "tsq watch",
+            error.code.clone(),
+            error.message.clone(),
+            error.details.clone(),
+        );
+        println!(
+            "{}",
+            serde_json::to_string(&envelope).unwrap_or_else(|_| "{}".to_string())
+        );
+    } else {
+        eprintln!("{}: {}", error.code, error.message);
+    }
+}
@@ -1,14 +0,0 @@
-pub mod alias;
-pub mod dep_tree;
-pub mod deps;
-pub mod event_payload_codecs;
-pub mod events;
-pub mod ids;
-pub mod labels;
-pub mod projector;
-pub mod query;
-pub mod resolve;
-pub mod similarity;
-pub mod state;
-pub(crate) mod state_invariants;
-pub mod validate;
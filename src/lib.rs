@@ -1,11 +1,6 @@
-pub mod app;
 pub mod cli;
-pub mod domain;
-pub mod errors;
-pub mod output;
-pub mod skills;
-pub mod store;
-pub mod types;
+
+pub use tasque_core::{app, domain, errors, output, skills, store, trace, types};
 
 pub use errors::TsqError;
 pub use types::*;
@@ -1,10 +1,22 @@
+use clap::Parser;
 use tasque::app::TasqueService;
 use tasque::app::runtime::{get_actor, get_repo_root, now_iso};
 use tasque::app::sync;
 use tasque::cli::action::{GlobalOpts, emit_error};
+use tasque::cli::program::Cli;
 use tasque::cli::run_cli;
 
 fn main() {
+    // clap decides what is a flag, so `-v` after `--` or as an option value
+    // is left alone. Invocations that fail to parse (plugins, typos) get the
+    // defaults; run_cli reports the parse error itself.
+    let (verbose, timing) = Cli::try_parse()
+        .map(|cli| (cli.verbose, cli.timing))
+        .unwrap_or_default();
+    tasque::cli::logging::init(verbose);
+    if timing {
+        tasque::trace::enable_timing();
+    }
     let repo_root = if should_initialize_cwd() {
         std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
     } else {
@@ -24,6 +36,8 @@ fn main() {
                     GlobalOpts {
                         json: wants_json,
                         exact_id: false,
+                        dry_run: false,
+                        porcelain: None,
                     },
                     error,
                 );
@@ -47,7 +61,7 @@ fn should_use_repo_root() -> bool {
     };
     matches!(
         command.as_str(),
-        "init" | "migrate" | "merge-driver" | "skills"
+        "init" | "migrate" | "merge-driver" | "skills" | "skill"
     )
 }
 
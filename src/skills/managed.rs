@@ -1 +0,0 @@
-pub const MANAGED_MARKER: &str = "tsq-managed-skill:v1";
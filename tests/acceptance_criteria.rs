@@ -0,0 +1,86 @@
+mod common;
+
+use common::{assert_validation_error, create_task, init_repo, ok_data, run_json};
+use serde_json::Value;
+
+const SPEC: &str = "# Login\n\n## Overview\nx\n\n## Constraints / Non-goals\nx\n\n## Interfaces (CLI/API)\nx\n\n## Data model / schema changes\nx\n\n## Acceptance criteria\n- [ ] Redirects to dashboard\n- [x] Shows error on bad password\n\n```\n- [ ] not a criterion\n```\n\n## Test plan\n- [ ] unrelated checkbox\n";
+
+fn attach(repo: &std::path::Path, task_id: &str) {
+    let attach = run_json(repo, ["spec", task_id, "--text", SPEC, "--force"]);
+    assert_eq!(attach.cli.code, 0, "{}", attach.cli.stderr);
+}
+
+fn checked_flags(data: &Value) -> Vec<bool> {
+    data.get("criteria")
+        .or_else(|| data.get("acceptance_criteria"))
+        .and_then(Value::as_array)
+        .expect("criteria array")
+        .iter()
+        .map(|criterion| criterion["checked"].as_bool().expect("checked"))
+        .collect()
+}
+
+#[test]
+fn show_exposes_acceptance_criteria_from_spec() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Criteria show");
+    attach(repo.path(), &task_id);
+
+    let show = run_json(repo.path(), ["show", &task_id]);
+    let data = ok_data(&show.envelope);
+    let criteria = data["acceptance_criteria"].as_array().expect("criteria");
+    assert_eq!(criteria.len(), 2);
+    assert_eq!(criteria[0]["index"], 1);
+    assert_eq!(criteria[0]["text"], "Redirects to dashboard");
+    assert_eq!(checked_flags(data), vec![false, true]);
+}
+
+#[test]
+fn criteria_check_toggles_through_events() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Criteria toggle");
+    attach(repo.path(), &task_id);
+
+    let first = run_json(repo.path(), ["criteria", "check", &task_id, "1"]);
+    assert_eq!(first.cli.code, 0, "{}", first.cli.stderr);
+    assert_eq!(checked_flags(ok_data(&first.envelope)), vec![true, true]);
+    assert_eq!(ok_data(&first.envelope)["open"], 0);
+
+    let undo = run_json(repo.path(), ["criteria", "check", &task_id, "2", "--undo"]);
+    assert_eq!(checked_flags(ok_data(&undo.envelope)), vec![true, false]);
+
+    let history = run_json(repo.path(), ["history", &task_id]);
+    let events = ok_data(&history.envelope)["events"]
+        .as_array()
+        .expect("events")
+        .iter()
+        .filter(|event| event["type"] == "task.criterion_set")
+        .count();
+    assert_eq!(events, 2);
+
+    let missing = run_json(repo.path(), ["criteria", "check", &task_id, "9"]);
+    assert_validation_error(&missing);
+}
+
+#[test]
+fn done_warns_or_refuses_with_open_criteria() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Criteria close");
+    attach(repo.path(), &task_id);
+
+    let refused = run_json(repo.path(), ["done", &task_id, "--require-criteria"]);
+    assert_validation_error(&refused);
+
+    let done = run_json(repo.path(), ["done", &task_id]);
+    assert_eq!(done.cli.code, 0, "{}", done.cli.stderr);
+    assert!(
+        done.cli.stderr.contains("WARN: closed ")
+            && done.cli.stderr.contains("1 of 2 acceptance criteria"),
+        "stderr: {}",
+        done.cli.stderr
+    );
+    assert_eq!(ok_data(&done.envelope)["open_criteria"][0]["open"], 1);
+}
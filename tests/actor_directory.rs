@@ -0,0 +1,145 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json, set_config_key};
+use serde_json::json;
+
+#[test]
+fn actor_add_updates_given_fields_and_remove_drops_the_entry() {
+    let repo = make_repo();
+    init_repo(repo.path());
+
+    let added = run_json(
+        repo.path(),
+        [
+            "actor",
+            "add",
+            "alice",
+            "--display-name",
+            "Alice Liddell",
+            "--email",
+            "alice@example.com",
+        ],
+    );
+    assert_eq!(added.cli.code, 0, "{:?}", added.envelope);
+    assert_eq!(
+        added.envelope["data"]["actor"],
+        json!({"name": "alice", "display_name": "Alice Liddell", "email": "alice@example.com", "type": "human"})
+    );
+    run_json(repo.path(), ["actor", "add", "alice", "--type", "agent"]);
+    run_json(repo.path(), ["actor", "add", "bob"]);
+
+    let listed = run_json(repo.path(), ["actor", "list"]);
+    let actors = listed.envelope["data"]["actors"]
+        .as_array()
+        .expect("actors");
+    assert_eq!(actors.len(), 2);
+    assert_eq!(actors[0]["display_name"], "Alice Liddell");
+    assert_eq!(actors[0]["type"], "agent");
+    assert_eq!(actors[1]["name"], "bob");
+
+    let bad_email = run_json(repo.path(), ["actor", "add", "carol", "--email", "nope"]);
+    assert_eq!(bad_email.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    run_json(repo.path(), ["actor", "remove", "bob"]);
+    let missing = run_json(repo.path(), ["actor", "remove", "bob"]);
+    assert_eq!(missing.envelope["error"]["code"], "NOT_FOUND");
+    let listed = run_json(repo.path(), ["actor", "list"]);
+    assert_eq!(
+        listed.envelope["data"]["actors"].as_array().unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn strict_actors_rejects_assignments_to_unregistered_actors() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Strict task");
+    run_json(repo.path(), ["actor", "add", "alice"]);
+    set_config_key(repo.path(), "strict_actors", json!(true));
+
+    let assigned = run_json(repo.path(), ["assign", &id, "--assignee", "mallory"]);
+    assert_eq!(assigned.cli.code, 1);
+    assert_eq!(assigned.envelope["error"]["code"], "UNKNOWN_ACTOR");
+    assert_eq!(
+        assigned.envelope["error"]["details"],
+        json!({"actor": "mallory", "known": ["alice"]})
+    );
+
+    let claimed = run_json(repo.path(), ["claim", &id]);
+    assert_eq!(claimed.envelope["error"]["code"], "UNKNOWN_ACTOR");
+
+    let ok = run_json(repo.path(), ["claim", &id, "--assignee", "alice"]);
+    assert_eq!(ok.cli.code, 0, "{:?}", ok.envelope);
+    assert_eq!(ok.envelope["data"]["task"]["assignee"], "alice");
+}
+
+#[test]
+fn history_and_audit_show_registered_display_names() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    run_json(
+        repo.path(),
+        ["actor", "add", "rust-test", "--display-name", "Test Runner"],
+    );
+    let id = create_task(repo.path(), "Tracked");
+
+    let history = run_json(repo.path(), ["history", &id]);
+    assert_eq!(
+        history.envelope["data"]["actors"]["rust-test"]["display_name"],
+        "Test Runner"
+    );
+    let human = run_cli(repo.path(), ["history", &id]);
+    assert!(
+        human.stdout.contains("rust-test (Test Runner)"),
+        "{}",
+        human.stdout
+    );
+
+    let audit = run_json(repo.path(), ["audit", "--actor", "rust-test"]);
+    assert_eq!(
+        audit.envelope["data"]["identity"]["display_name"],
+        "Test Runner"
+    );
+}
+
+#[test]
+fn whoami_reports_the_source_that_won_the_actor_chain() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    run_json(
+        repo.path(),
+        ["actor", "add", "build-bot", "--type", "agent"],
+    );
+    set_config_key(
+        repo.path(),
+        "actor_resolution",
+        json!({"order": ["config", "env"], "actor": "build-bot"}),
+    );
+
+    let whoami = run_json(repo.path(), ["whoami"]);
+    assert_eq!(whoami.cli.code, 0, "{:?}", whoami.envelope);
+    let data = &whoami.envelope["data"];
+    assert_eq!(data["actor"], "build-bot");
+    assert_eq!(data["source"], "config");
+    assert_eq!(
+        data["candidates"],
+        json!([
+            {"source": "config", "value": "build-bot"},
+            {"source": "env", "value": "rust-test"}
+        ])
+    );
+    assert_eq!(data["identity"]["type"], "agent");
+
+    let id = create_task(repo.path(), "Attributed");
+    let history = run_json(repo.path(), ["history", &id]);
+    assert_eq!(history.envelope["data"]["events"][0]["actor"], "build-bot");
+
+    set_config_key(
+        repo.path(),
+        "actor_resolution",
+        json!({"order": ["config"]}),
+    );
+    let rejected = run_json(repo.path(), ["find", "open"]);
+    assert_ne!(rejected.cli.code, 0);
+}
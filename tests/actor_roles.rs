@@ -0,0 +1,80 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json, run_json_with_env};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn set_roles(repo: &Path, roles: Value) {
+    let path = repo.join(".tasque/config.json");
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    config["roles"] = roles;
+    fs::write(&path, config.to_string()).unwrap();
+}
+
+#[test]
+fn roles_gate_writes_and_admin_only_commands() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let target = create_task(repo.path(), "Target");
+    let source = create_task(repo.path(), "Source");
+    set_roles(
+        repo.path(),
+        serde_json::json!({"admins": ["ana"], "writers": ["wes"]}),
+    );
+
+    let reader = run_json_with_env(
+        repo.path(),
+        ["create", "Nope", "--json"],
+        &[("TSQ_ACTOR", "rui")],
+    );
+    assert_eq!(reader.envelope["error"]["code"], "PERMISSION_DENIED");
+    assert_eq!(reader.envelope["error"]["details"]["role"], "reader");
+    let listed = run_json_with_env(
+        repo.path(),
+        ["find", "open", "--json"],
+        &[("TSQ_ACTOR", "rui")],
+    );
+    assert_eq!(listed.cli.code, 0, "stderr:\n{}", listed.cli.stderr);
+
+    let writer_create = run_json_with_env(
+        repo.path(),
+        ["create", "Allowed", "--json"],
+        &[("TSQ_ACTOR", "wes")],
+    );
+    assert_eq!(writer_create.cli.code, 0);
+    let writer_merge = run_json_with_env(
+        repo.path(),
+        ["merge", &source, "--into", &target, "--json"],
+        &[("TSQ_ACTOR", "wes")],
+    );
+    assert_eq!(writer_merge.envelope["error"]["code"], "PERMISSION_DENIED");
+    assert_eq!(
+        writer_merge.envelope["error"]["details"]["required"],
+        "admin"
+    );
+
+    let admin_merge = run_json_with_env(
+        repo.path(),
+        ["merge", &source, "--into", &target, "--json"],
+        &[("TSQ_ACTOR", "ana")],
+    );
+    assert_eq!(
+        admin_merge.cli.code, 0,
+        "stderr:\n{}",
+        admin_merge.cli.stderr
+    );
+}
+
+#[test]
+fn roles_default_applies_to_unlisted_actors() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_roles(
+        repo.path(),
+        serde_json::json!({"admins": ["ana"], "default": "writer"}),
+    );
+
+    let created = run_json(repo.path(), ["create", "Anyone may write"]);
+    assert_eq!(created.cli.code, 0, "stderr:\n{}", created.cli.stderr);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[test]
+fn as_of_replays_only_events_up_to_the_timestamp() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let first = create_task(repo.path(), "Before the weekend");
+    let shown = run_json(repo.path(), ["show", &first]);
+    let as_of = shown.envelope["data"]["task"]["created_at"]
+        .as_str()
+        .expect("created_at")
+        .to_string();
+    sleep(Duration::from_millis(20));
+    run_cli(repo.path(), ["done", &first]);
+    let later = create_task(repo.path(), "After the weekend");
+
+    let open_now = run_json(repo.path(), ["find", "open"]);
+    let ids_now = task_ids(&open_now.envelope["data"]["tasks"]);
+    assert_eq!(ids_now, [later.as_str()]);
+
+    let open_then = run_json(repo.path(), ["find", "open", "--as-of", &as_of]);
+    assert_eq!(open_then.cli.code, 0, "stderr:\n{}", open_then.cli.stderr);
+    assert_eq!(
+        task_ids(&open_then.envelope["data"]["tasks"]),
+        [first.as_str()]
+    );
+
+    let show_then = run_json(repo.path(), ["show", &first, "--as-of", &as_of]);
+    assert_eq!(show_then.envelope["data"]["task"]["status"], "open");
+    let report_then = run_json(repo.path(), ["report", "--as-of", &as_of]);
+    assert_eq!(report_then.envelope["data"]["total"], 1);
+
+    let before_all = run_json(repo.path(), ["show", &first, "--as-of", "2000-01-01"]);
+    assert_eq!(before_all.envelope["ok"], false);
+    let invalid = run_json(repo.path(), ["find", "open", "--as-of", "last friday"]);
+    assert_eq!(invalid.envelope["error"]["code"], "VALIDATION_ERROR");
+}
+
+fn task_ids(tasks: &serde_json::Value) -> Vec<&str> {
+    tasks
+        .as_array()
+        .expect("tasks")
+        .iter()
+        .map(|task| task["id"].as_str().expect("id"))
+        .collect()
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli_with_env, run_json};
+
+const BOT: &[(&str, &str)] = &[("TSQ_ACTOR", "bot-alpha")];
+
+#[test]
+fn audit_summarizes_one_actor_chronologically() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let blocker = create_task(repo.path(), "Human task");
+    let created = run_cli_with_env(repo.path(), ["create", "Bot task", "--json"], BOT);
+    let bot_task =
+        serde_json::from_str::<serde_json::Value>(&created.stdout).unwrap()["data"]["task"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+    run_cli_with_env(repo.path(), ["claim", &bot_task], BOT);
+    run_cli_with_env(repo.path(), ["note", &bot_task, "investigated"], BOT);
+    run_cli_with_env(repo.path(), ["block", &bot_task, "by", &blocker], BOT);
+    run_cli_with_env(repo.path(), ["done", &bot_task], BOT);
+
+    let audit = run_json(repo.path(), ["audit", "--actor", "bot-alpha"]);
+    let data = &audit.envelope["data"];
+    assert_eq!(data["actor"], "bot-alpha");
+    assert_eq!(data["tasks_touched"], 1);
+    let categories = data["counts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["category"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        categories,
+        ["created", "claimed", "status_changes", "notes", "deps"]
+    );
+    let events = data["events"].as_array().unwrap();
+    assert_eq!(events.first().unwrap()["summary"], "created \"Bot task\"");
+    assert!(
+        events
+            .iter()
+            .all(|event| event["task_id"] == bot_task.as_str())
+    );
+    assert!(
+        events
+            .windows(2)
+            .all(|pair| pair[0]["ts"].as_str() <= pair[1]["ts"].as_str())
+    );
+
+    let future = run_json(
+        repo.path(),
+        ["audit", "--actor", "bot-alpha", "--since", "2999-01-01"],
+    );
+    assert_eq!(future.envelope["data"]["total"], 0);
+    assert_eq!(future.envelope["data"]["since"], "2999-01-01T00:00:00.000Z");
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json};
+use std::fs;
+
+fn open_titles(repo: &std::path::Path) -> Vec<String> {
+    let found = run_json(repo, ["find", "open"]);
+    assert_eq!(found.cli.code, 0, "stderr:\n{}", found.cli.stderr);
+    let mut titles: Vec<String> = found.envelope["data"]["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["title"].as_str().unwrap().to_string())
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[test]
+fn backup_round_trips_and_restore_requires_force_over_existing_events() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    create_task(repo.path(), "Alpha");
+    create_task(repo.path(), "Beta");
+    let archive = repo.path().join("backup.tar.zst");
+    let archive_arg = archive.to_str().unwrap();
+
+    let created = run_json(repo.path(), ["backup", "create", "--out", archive_arg]);
+    assert_eq!(created.cli.code, 0, "stderr:\n{}", created.cli.stderr);
+    assert!(archive.exists());
+    assert!(created.envelope["data"]["files"].as_u64().unwrap() >= 2);
+    assert_eq!(
+        created.envelope["data"]["sha256"].as_str().unwrap().len(),
+        64
+    );
+
+    create_task(repo.path(), "After backup");
+    let refused = run_json(repo.path(), ["backup", "restore", archive_arg]);
+    assert_eq!(refused.cli.code, 1);
+    assert_eq!(refused.envelope["error"]["code"], "RESTORE_WOULD_OVERWRITE");
+
+    let restored = run_json(repo.path(), ["backup", "restore", archive_arg, "--force"]);
+    assert_eq!(restored.cli.code, 0, "stderr:\n{}", restored.cli.stderr);
+    assert_eq!(restored.envelope["data"]["replaced"], true);
+    assert_eq!(open_titles(repo.path()), ["Alpha", "Beta"]);
+
+    let fresh = make_repo();
+    let into_fresh = run_json(fresh.path(), ["backup", "restore", archive_arg]);
+    assert_eq!(into_fresh.cli.code, 0, "stderr:\n{}", into_fresh.cli.stderr);
+    assert_eq!(into_fresh.envelope["data"]["replaced"], false);
+    assert_eq!(open_titles(fresh.path()), ["Alpha", "Beta"]);
+}
+
+#[test]
+fn restore_rejects_an_archive_whose_files_fail_their_checksums() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    create_task(repo.path(), "Alpha");
+    let archive = repo.path().join("backup.tar");
+    let archive_arg = archive.to_str().unwrap();
+    let created = run_json(repo.path(), ["backup", "create", "--out", archive_arg]);
+    assert_eq!(created.cli.code, 0, "stderr:\n{}", created.cli.stderr);
+
+    let mut bytes = fs::read(&archive).unwrap();
+    let at = bytes
+        .windows(5)
+        .position(|window| window == b"Alpha")
+        .expect("plain tar holds the event log");
+    bytes[at + 4] = b'b';
+    fs::write(&archive, bytes).unwrap();
+
+    let fresh = make_repo();
+    let restored = run_json(fresh.path(), ["backup", "restore", archive_arg]);
+    assert_eq!(restored.cli.code, 2);
+    assert_eq!(restored.envelope["error"]["code"], "BACKUP_VERIFY_FAILED");
+    assert!(!fresh.path().join(".tasque/events.jsonl").exists());
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use common::{create_task, init_repo, ok_data, run_json, run_json_with_stdin};
+
+#[test]
+fn batch_ndjson_creates_epic_with_refs() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let ops = [
+        r#"{"op":"create","ref":"epic","title":"Auth epic","kind":"epic"}"#,
+        r#"{"op":"create","ref":"form","title":"Login form","parent":"$epic","labels":["ui"]}"#,
+        r#"{"op":"create","ref":"store","title":"Session store","parent":"$epic","priority":1}"#,
+        r#"{"op":"dep_add","child":"$form","blocker":"$store"}"#,
+        r#"{"op":"note","id":"$form","text":"wire to store"}"#,
+    ]
+    .join("\n");
+
+    let result = run_json_with_stdin(repo.path(), ["batch", "-", "--json"], &ops);
+
+    assert_eq!(result.cli.code, 0, "stderr: {}", result.cli.stderr);
+    let data = ok_data(&result.envelope);
+    assert_eq!(data["applied"].as_u64(), Some(5));
+    let results = data["results"].as_array().expect("results");
+    let epic_id = results[0]["result"]["task"]["id"]
+        .as_str()
+        .expect("epic id");
+    let form = &results[1]["result"]["task"];
+    let store_id = results[2]["result"]["task"]["id"]
+        .as_str()
+        .expect("store id");
+    assert_eq!(results[1]["ref"].as_str(), Some("form"));
+    assert_eq!(form["parent_id"].as_str(), Some(epic_id));
+    assert_eq!(form["labels"][0].as_str(), Some("ui"));
+    assert_eq!(results[3]["result"]["blocker"].as_str(), Some(store_id));
+    assert_eq!(
+        results[4]["result"]["note"]["text"].as_str(),
+        Some("wire to store")
+    );
+
+    let shown = run_json(repo.path(), ["show", form["id"].as_str().unwrap()]);
+    assert_eq!(
+        ok_data(&shown.envelope)["task"]["notes"]
+            .as_array()
+            .map(Vec::len),
+        Some(1)
+    );
+}
+
+#[test]
+fn batch_json_array_updates_existing_tasks() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Existing batch target");
+    let ops = format!(
+        r#"[{{"op":"update","id":"{id}","title":"Renamed in batch","priority":0}},
+            {{"op":"label_add","id":"{id}","label":"backend"}},
+            {{"op":"status","id":"{id}","status":"in_progress"}}]"#
+    );
+
+    let result = run_json_with_stdin(repo.path(), ["batch", "-", "--json"], &ops);
+
+    assert_eq!(result.cli.code, 0, "stderr: {}", result.cli.stderr);
+    let shown = run_json(repo.path(), ["show", &id]);
+    let task = &ok_data(&shown.envelope)["task"];
+    assert_eq!(task["title"].as_str(), Some("Renamed in batch"));
+    assert_eq!(task["priority"].as_u64(), Some(0));
+    assert_eq!(task["labels"][0].as_str(), Some("backend"));
+    assert_eq!(task["status"].as_str(), Some("in_progress"));
+}
+
+#[test]
+fn batch_failure_rolls_back_every_operation() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let ops = [
+        r#"{"op":"create","title":"Should not persist"}"#,
+        r#"{"op":"label_add","id":"$missing","label":"x"}"#,
+    ]
+    .join("\n");
+
+    let result = run_json_with_stdin(repo.path(), ["batch", "-", "--json"], &ops);
+
+    assert_eq!(result.cli.code, 1);
+    let error = &result.envelope["error"];
+    assert_eq!(error["code"].as_str(), Some("VALIDATION_ERROR"));
+    assert_eq!(error["details"]["index"].as_u64(), Some(1));
+    assert_eq!(error["details"]["op"].as_str(), Some("label_add"));
+    let listed = run_json(repo.path(), ["find", "open"]);
+    assert_eq!(
+        ok_data(&listed.envelope)["tasks"].as_array().map(Vec::len),
+        Some(0)
+    );
+}
+
+#[test]
+fn batch_rejects_unknown_operation() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let result = run_json_with_stdin(
+        repo.path(),
+        ["batch", "-", "--json"],
+        r#"{"op":"explode","id":"tsq-1"}"#,
+    );
+
+    assert_eq!(result.cli.code, 1);
+    common::assert_validation_error(&result);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::{create_task, init_repo, ok_data, run_json};
+
+#[test]
+fn blocked_on_is_recorded_and_listed_as_external() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let external = create_task(repo.path(), "Waiting on vendor");
+    let internal = create_task(repo.path(), "Plain blocked");
+
+    let blocked = run_json(
+        repo.path(),
+        [
+            "blocked",
+            &external,
+            "--blocked-on",
+            "waiting for vendor API key",
+        ],
+    );
+    assert_eq!(blocked.cli.code, 0, "{}", blocked.cli.stderr);
+    run_json(repo.path(), ["blocked", &internal]);
+
+    let show = run_json(repo.path(), ["show", &external]);
+    let task = &ok_data(&show.envelope)["task"];
+    assert_eq!(task["status"], "blocked");
+    assert_eq!(task["blocked_on"], "waiting for vendor API key");
+
+    let listing = run_json(repo.path(), ["blocked", "--external"]);
+    assert_eq!(listing.cli.code, 0, "{}", listing.cli.stderr);
+    let ids: Vec<&str> = ok_data(&listing.envelope)["tasks"]
+        .as_array()
+        .expect("tasks")
+        .iter()
+        .map(|task| task["id"].as_str().expect("id"))
+        .collect();
+    assert_eq!(ids, vec![external.as_str()]);
+}
+
+#[test]
+fn leaving_blocked_status_clears_blocked_on() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Unblocks later");
+    run_json(
+        repo.path(),
+        ["blocked", &id, "--blocked-on", "legal review"],
+    );
+
+    let started = run_json(repo.path(), ["start", &id]);
+    assert_eq!(started.cli.code, 0, "{}", started.cli.stderr);
+    let task = &ok_data(&started.envelope)["task"];
+    assert_eq!(task["status"], "in_progress");
+    assert!(task.get("blocked_on").is_none());
+}
+
+#[test]
+fn blocked_requires_id_unless_external() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let missing_id = run_json(repo.path(), ["blocked"]);
+    assert_ne!(missing_id.cli.code, 0);
+
+    let conflicting = run_json(
+        repo.path(),
+        ["blocked", "--external", "--blocked-on", "vendor"],
+    );
+    assert_ne!(conflicting.cli.code, 0);
+}
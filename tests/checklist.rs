@@ -0,0 +1,77 @@
+mod common;
+
+use common::{
+    assert_validation_error, create_task, create_task_with_args, init_repo, ok_data, run_cli,
+    run_json,
+};
+use serde_json::Value;
+
+fn done_flags(data: &Value) -> Vec<bool> {
+    data["checklist"]
+        .as_array()
+        .expect("checklist array")
+        .iter()
+        .map(|item| item["done"].as_bool().expect("done"))
+        .collect()
+}
+
+#[test]
+fn check_add_done_and_undo_replay_through_events() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Checklist task");
+
+    for text in ["write tests", "update docs"] {
+        let added = run_json(repo.path(), ["check", "add", &task_id, text]);
+        assert_eq!(added.cli.code, 0, "{}", added.cli.stderr);
+    }
+    let done = run_json(repo.path(), ["check", "done", &task_id, "2"]);
+    assert_eq!(done_flags(ok_data(&done.envelope)), vec![false, true]);
+    assert_eq!(ok_data(&done.envelope)["done"], 1);
+    assert_eq!(ok_data(&done.envelope)["total"], 2);
+
+    let undo = run_json(repo.path(), ["check", "undo", &task_id, "2"]);
+    assert_eq!(done_flags(ok_data(&undo.envelope)), vec![false, false]);
+    run_json(repo.path(), ["check", "done", &task_id, "1"]);
+
+    let show = run_json(repo.path(), ["show", &task_id]);
+    let task = &ok_data(&show.envelope)["task"];
+    assert_eq!(task["checklist"][0]["text"], "write tests");
+    assert_eq!(done_flags(task), vec![true, false]);
+
+    let human = run_cli(repo.path(), ["show", &task_id]);
+    assert!(human.stdout.contains("checklist=1/2"), "{}", human.stdout);
+    assert!(
+        human.stdout.contains("1. [x] write tests"),
+        "{}",
+        human.stdout
+    );
+
+    let missing = run_json(repo.path(), ["check", "done", &task_id, "3"]);
+    assert_validation_error(&missing);
+    let empty = run_json(repo.path(), ["check", "add", &task_id, "  "]);
+    assert_validation_error(&empty);
+}
+
+#[test]
+fn epic_progress_counts_checklist_items() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let epic_id = create_task_with_args(repo.path(), "Checklist epic", &["--kind", "epic"]);
+    let child_id = create_task_with_args(
+        repo.path(),
+        "Checklist child",
+        &["--parent", epic_id.as_str()],
+    );
+    run_json(repo.path(), ["check", "add", &epic_id, "announce"]);
+    run_json(repo.path(), ["check", "add", &child_id, "migrate"]);
+    run_json(repo.path(), ["check", "done", &child_id, "1"]);
+
+    let frame = run_json(repo.path(), ["tui", "--once", "--epics"]);
+    let progress = &ok_data(&frame.envelope)["epic_progress"];
+    assert_eq!(progress["checklist_done"], 1);
+    assert_eq!(progress["checklist_total"], 2);
+
+    let human = run_cli(repo.path(), ["tui", "--once", "--epics"]);
+    assert!(human.stdout.contains("checklist=1/2"), "{}", human.stdout);
+}
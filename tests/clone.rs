@@ -0,0 +1,62 @@
+mod common;
+
+use common::{
+    create_task, create_task_with_args, init_repo, label_add, make_repo, run_cli, run_json,
+};
+
+#[test]
+fn clone_copies_fields_and_spec_with_a_fresh_fingerprint() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let original = create_task_with_args(
+        repo.path(),
+        "Monthly release",
+        &["-p", "0", "--description", "Cut and ship"],
+    );
+    label_add(repo.path(), &original, "ops");
+    run_cli(
+        repo.path(),
+        ["spec", &original, "--text", "# Steps\n- tag\n"],
+    );
+    run_cli(repo.path(), ["done", &original]);
+
+    let cloned = run_json(repo.path(), ["clone", &original]);
+    assert_eq!(cloned.cli.code, 0, "stderr:\n{}", cloned.cli.stderr);
+    let copy = &cloned.envelope["data"]["tasks"][0];
+    let copy_id = copy["id"].as_str().unwrap();
+    assert_ne!(copy_id, original);
+    assert_eq!(copy["title"], "Monthly release");
+    assert_eq!(copy["description"], "Cut and ship");
+    assert_eq!(copy["labels"], serde_json::json!(["ops"]));
+    assert_eq!(copy["priority"], 0);
+    assert_eq!(copy["status"], "open");
+    assert_eq!(copy["discovered_from"], original);
+    assert!(copy["spec_path"].as_str().unwrap().contains(copy_id));
+    assert!(copy["spec_fingerprint"].is_string());
+
+    let spec = run_json(repo.path(), ["show", copy_id, "--with-spec"]);
+    assert_eq!(spec.envelope["data"]["spec"]["content"], "# Steps\n- tag\n");
+}
+
+#[test]
+fn clone_with_children_rebuilds_the_subtree_under_a_new_parent() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let template = create_task_with_args(repo.path(), "Onboarding", &["--kind", "epic"]);
+    let step = create_task_with_args(repo.path(), "Laptop", &["--parent", &template]);
+    create_task_with_args(repo.path(), "Accounts", &["--parent", &step]);
+    let holder = create_task(repo.path(), "Q3 hires");
+
+    let cloned = run_json(
+        repo.path(),
+        ["clone", &template, "--into", &holder, "--with-children"],
+    );
+    assert_eq!(cloned.cli.code, 0, "stderr:\n{}", cloned.cli.stderr);
+    let tasks = cloned.envelope["data"]["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 3);
+    assert_eq!(tasks[0]["parent_id"], holder);
+    assert_eq!(tasks[0]["kind"], "epic");
+    assert_eq!(tasks[1]["parent_id"], tasks[0]["id"]);
+    assert_eq!(tasks[1]["discovered_from"], step);
+    assert_eq!(tasks[2]["parent_id"], tasks[1]["id"]);
+}
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
 use serde_json::Value;
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::sync::OnceLock;
 use tasque::types::SCHEMA_VERSION;
 use tempfile::{Builder, TempDir};
@@ -68,6 +69,46 @@ pub fn create_task_with_args(repo: &Path, title: &str, extra_args: &[&str]) -> S
         .expect("create response did not include data.task.id")
 }
 
+/// Sets one top-level key in the repo's `.tasque/config.json`.
+pub fn set_config_key(repo: &Path, key: &str, value: Value) {
+    let config_path = repo.join(".tasque").join("config.json");
+    let raw = std::fs::read_to_string(&config_path).expect("read config");
+    let mut config: Value = serde_json::from_str(&raw).expect("parse config");
+    config[key] = value;
+    std::fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&config).expect("serialize config"),
+    )
+    .expect("write config");
+}
+
+/// Runs git in `repo` and returns its trimmed stdout, failing the test with
+/// git's output when it exits non-zero.
+pub fn git(repo: &Path, args: &[&str]) -> String {
+    git_with_env(repo, args, &[])
+}
+
+pub fn git_with_env(repo: &Path, args: &[&str], envs: &[(&str, &str)]) -> String {
+    let output = Command::new("git")
+        // Per-invocation config keeps bare-repo/worktree tests portable when
+        // global Git safety config requires explicit bare repository trust.
+        .arg("-c")
+        .arg("safe.bareRepository=all")
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(repo)
+        .output()
+        .expect("git command failed");
+    assert!(
+        output.status.success(),
+        "git {:?} failed\nstdout:{}\nstderr:{}",
+        args,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
 pub fn label_add(repo: &Path, id: &str, label: &str) -> JsonOutput {
     run_json(repo, ["label", id, label])
 }
@@ -132,6 +173,14 @@ pub fn ids_from_task_list(envelope: &Value) -> Vec<String> {
 }
 
 pub fn run_cli<I, S>(repo: &Path, args: I) -> CliOutput
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    run_cli_with_env(repo, args, &[])
+}
+
+pub fn run_cli_with_env<I, S>(repo: &Path, args: I, envs: &[(&str, &str)]) -> CliOutput
 where
     I: IntoIterator<Item = S>,
     S: AsRef<str>,
@@ -141,6 +190,7 @@ where
         .args(&args_vec)
         .current_dir(repo)
         .env("TSQ_ACTOR", "rust-test")
+        .envs(envs.iter().copied())
         .output()
         .expect("failed executing tsq binary");
 
@@ -162,12 +212,20 @@ where
 }
 
 pub fn run_json_explicit<I, S>(repo: &Path, args: I) -> JsonOutput
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    run_json_with_env(repo, args, &[])
+}
+
+pub fn run_json_with_env<I, S>(repo: &Path, args: I, envs: &[(&str, &str)]) -> JsonOutput
 where
     I: IntoIterator<Item = S>,
     S: AsRef<str>,
 {
     let args_vec = normalize_args(args);
-    let cli = run_cli(repo, &args_vec);
+    let cli = run_cli_with_env(repo, &args_vec, envs);
     let trimmed = cli.stdout.trim();
     assert!(
         !trimmed.is_empty(),
@@ -184,6 +242,43 @@ where
     JsonOutput { cli, envelope }
 }
 
+pub fn run_json_with_stdin<I, S>(repo: &Path, args: I, stdin: &str) -> JsonOutput
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args_vec = normalize_args(args);
+    let mut child = Command::new(tsq_bin())
+        .args(&args_vec)
+        .current_dir(repo)
+        .env("TSQ_ACTOR", "rust-test")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed executing tsq binary");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(stdin.as_bytes())
+        .expect("write stdin");
+    let output = child.wait_with_output().expect("wait for tsq");
+    let cli = CliOutput {
+        code: output.status.code().unwrap_or(-1),
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    };
+    let envelope = serde_json::from_str::<Value>(cli.stdout.trim()).unwrap_or_else(|error| {
+        panic!(
+            "failed parsing JSON envelope: {error}\nstdout:\n{}\nstderr:\n{}",
+            cli.stdout, cli.stderr
+        )
+    });
+    assert_envelope_shape(&envelope);
+    JsonOutput { cli, envelope }
+}
+
 fn normalize_args<I, S>(args: I) -> Vec<String>
 where
     I: IntoIterator<Item = S>,
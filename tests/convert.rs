@@ -0,0 +1,41 @@
+mod common;
+
+use common::{create_task, create_task_with_args, init_repo, make_repo, run_json};
+
+#[test]
+fn convert_promotes_a_task_and_guards_parents_from_becoming_tasks() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let grown = create_task(repo.path(), "Search");
+    create_task_with_args(repo.path(), "Indexing", &["--parent", &grown]);
+
+    let promoted = run_json(repo.path(), ["convert", &grown, "--kind", "epic"]);
+    assert_eq!(promoted.cli.code, 0, "stderr:\n{}", promoted.cli.stderr);
+    assert_eq!(promoted.envelope["data"]["task"]["kind"], "epic");
+
+    let same = run_json(repo.path(), ["convert", &grown, "--kind", "epic"]);
+    assert_eq!(same.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    let demoted = run_json(repo.path(), ["convert", &grown, "--kind", "task"]);
+    assert_eq!(demoted.envelope["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(
+        demoted.envelope["error"]["details"]["children"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let feature = run_json(repo.path(), ["convert", &grown, "--kind", "feature"]);
+    assert_eq!(feature.envelope["data"]["task"]["kind"], "feature");
+    let history = run_json(repo.path(), ["history", &grown]);
+    assert!(
+        history.envelope["data"]["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|event| event["payload"]["converted_from"] == "epic"),
+        "{}",
+        history.envelope["data"]
+    );
+}
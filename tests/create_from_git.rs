@@ -0,0 +1,62 @@
+mod common;
+
+use common::{git, init_repo, make_repo, run_json};
+use std::path::Path;
+
+fn git_repo_with_commit(repo: &Path) {
+    git(repo, &["init", "-q", "-b", "main"]);
+    git(repo, &["config", "user.name", "rust-test"]);
+    git(repo, &["config", "user.email", "rust-test@example.com"]);
+    git(
+        repo,
+        &[
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "Fix flaky sync retry",
+        ],
+    );
+}
+
+#[test]
+fn create_from_branch_and_commit_derive_title_and_external_ref() {
+    let repo = make_repo();
+    git_repo_with_commit(repo.path());
+    init_repo(repo.path());
+    git(
+        repo.path(),
+        &["checkout", "-q", "-b", "feature/add-login_page"],
+    );
+
+    let from_branch = run_json(repo.path(), ["create", "--from-branch"]);
+    let task = &from_branch.envelope["data"]["task"];
+    assert_eq!(task["title"], "Add login page", "{}", from_branch.envelope);
+    assert_eq!(task["external_ref"], "branch:feature/add-login_page");
+
+    let sha = git(repo.path(), &["rev-parse", "HEAD"]);
+    let from_commit = run_json(
+        repo.path(),
+        ["create", "--from-commit", "--kind", "feature"],
+    );
+    let task = &from_commit.envelope["data"]["task"];
+    assert_eq!(
+        task["title"], "Fix flaky sync retry",
+        "{}",
+        from_commit.envelope
+    );
+    assert_eq!(task["external_ref"], format!("commit:{}", sha));
+    assert_eq!(task["kind"], "feature");
+
+    let titled = run_json(
+        repo.path(),
+        ["create", "Login page polish", "--from-commit", "HEAD"],
+    );
+    assert_eq!(
+        titled.envelope["data"]["task"]["title"],
+        "Login page polish"
+    );
+
+    let missing = run_json(repo.path(), ["create", "--from-commit", "nope"]);
+    assert_eq!(missing.envelope["error"]["code"], "VALIDATION_ERROR");
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn set_relation_types(repo: &Path, types: Value) {
+    let path = repo.join(".tasque/config.json");
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    config["relation_types"] = types;
+    fs::write(&path, config.to_string()).unwrap();
+}
+
+#[test]
+fn custom_relation_records_both_directions_and_shows_them() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let spec = create_task(repo.path(), "Spec");
+    let code = create_task(repo.path(), "Code");
+    set_relation_types(
+        repo.path(),
+        serde_json::json!({"implements": "implemented_by"}),
+    );
+
+    let added = run_json(
+        repo.path(),
+        ["relate", &code, &spec, "--type", "implements"],
+    );
+    assert_eq!(added.cli.code, 0, "stderr:\n{}", added.cli.stderr);
+    assert_eq!(added.envelope["data"]["type"], "implements");
+
+    let shown_code = run_json(repo.path(), ["show", &code]);
+    assert_eq!(
+        shown_code.envelope["data"]["links"]["implements"],
+        serde_json::json!([spec])
+    );
+    let shown_spec = run_json(repo.path(), ["show", &spec]);
+    assert_eq!(
+        shown_spec.envelope["data"]["links"]["implemented_by"],
+        serde_json::json!([code])
+    );
+
+    let why = run_json(repo.path(), ["why", &code, &spec]);
+    assert_eq!(
+        why.envelope["data"]["paths"][0]["hops"][0]["via"],
+        "implements"
+    );
+
+    // The inverse name removes the same pair from the other side.
+    let removed = run_json(
+        repo.path(),
+        ["unrelate", &spec, &code, "--type", "implemented_by"],
+    );
+    assert_eq!(removed.cli.code, 0, "stderr:\n{}", removed.cli.stderr);
+    let cleared = run_json(repo.path(), ["show", &code]);
+    assert!(
+        cleared.envelope["data"]["links"]
+            .get("implements")
+            .is_none()
+    );
+
+    let human = run_cli(
+        repo.path(),
+        ["relate", &spec, &code, "--type", "replies_to"],
+    );
+    assert!(
+        human.stdout.contains("added link replies_to"),
+        "{}",
+        human.stdout
+    );
+}
+
+#[test]
+fn unknown_or_invalid_custom_relation_types_are_rejected() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let a = create_task(repo.path(), "A");
+    let b = create_task(repo.path(), "B");
+
+    let unknown = run_json(repo.path(), ["relate", &a, &b, "--type", "implements"]);
+    assert_eq!(unknown.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    set_relation_types(repo.path(), serde_json::json!({"duplicates": "dup_of"}));
+    let shadowed = run_json(repo.path(), ["relate", &a, &b]);
+    assert_eq!(shadowed.cli.code, 2, "stderr:\n{}", shadowed.cli.stderr);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json, run_json_with_stdin};
+use std::fs;
+
+#[test]
+fn block_from_file_adds_every_edge_in_one_commit() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let design = create_task(repo.path(), "Design");
+    let build = create_task(repo.path(), "Build");
+    let ship = create_task(repo.path(), "Ship");
+    let path = repo.path().join("deps.txt");
+    fs::write(
+        &path,
+        format!(
+            "# plan\n{design} blocks {build}\n\n{build} blocks {ship}\n{ship} after {design}\n{design} blocks {build}\n"
+        ),
+    )
+    .unwrap();
+
+    let imported = run_json(
+        repo.path(),
+        ["block", "--from-file", path.to_str().unwrap()],
+    );
+    assert_eq!(imported.cli.code, 0, "stderr:\n{}", imported.cli.stderr);
+    let data = &imported.envelope["data"];
+    assert_eq!(data["added"].as_array().unwrap().len(), 3);
+    assert_eq!(data["skipped"], 1);
+    assert_eq!(data["added"][2]["dep_type"], "starts_after");
+
+    let shown = run_json(repo.path(), ["show", &ship]);
+    assert_eq!(
+        shown.envelope["data"]["blocker_edges"],
+        serde_json::json!([
+            {"id": design, "dep_type": "starts_after"},
+            {"id": build, "dep_type": "blocks"},
+        ])
+    );
+}
+
+#[test]
+fn block_from_stdin_rejects_the_whole_import_when_any_edge_fails() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let first = create_task(repo.path(), "First");
+    let second = create_task(repo.path(), "Second");
+    let input =
+        format!("{first} blocks {second}\n{second} blocks {first}\ntsq-missing blocks {first}\n");
+
+    let rejected =
+        run_json_with_stdin(repo.path(), ["block", "--from-file", "-", "--json"], &input);
+    assert_eq!(rejected.envelope["error"]["code"], "VALIDATION_ERROR");
+    let lines = rejected.envelope["error"]["details"]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["line"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [2, 3]);
+
+    let untouched = run_json(repo.path(), ["show", &second]);
+    assert_eq!(
+        untouched.envelope["data"]["blocker_edges"],
+        serde_json::json!([])
+    );
+
+    let malformed = run_json_with_stdin(
+        repo.path(),
+        ["block", "--from-file", "-", "--json"],
+        &format!("{first} needs {second}\n"),
+    );
+    assert_eq!(malformed.envelope["error"]["details"]["line"], 1);
+}
@@ -0,0 +1,230 @@
+mod common;
+
+use common::{create_task, ids_from_task_list, init_repo, make_repo, run_json};
+
+#[test]
+fn finish_to_finish_and_informs_edges_never_gate_readiness() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let api = create_task(repo.path(), "API");
+    let docs = create_task(repo.path(), "Docs");
+    let notes = create_task(repo.path(), "Release notes");
+    let ff = run_json(
+        repo.path(),
+        ["block", &docs, "by", &api, "--type", "finish_to_finish"],
+    );
+    assert_eq!(ff.envelope["data"]["dep_type"], "finish_to_finish");
+    run_json(
+        repo.path(),
+        ["block", &notes, "by", &api, "--type", "informs"],
+    );
+
+    let ready = ids_from_task_list(&run_json(repo.path(), ["find", "ready"]).envelope);
+    assert!(ready.contains(&docs) && ready.contains(&notes));
+
+    let tree = run_json(repo.path(), ["deps", &docs, "--direction", "up"]);
+    assert_eq!(
+        tree.envelope["data"]["root"]["children"][0]["dep_type"],
+        "finish_to_finish"
+    );
+    let informed = ids_from_task_list(
+        &run_json(
+            repo.path(),
+            [
+                "find",
+                "open",
+                "--dep-type",
+                "informs",
+                "--dep-direction",
+                "out",
+            ],
+        )
+        .envelope,
+    );
+    assert_eq!(informed, std::slice::from_ref(&notes));
+
+    let cycle = run_json(
+        repo.path(),
+        ["block", &api, "by", &docs, "--type", "finish_to_finish"],
+    );
+    assert_eq!(cycle.envelope["error"]["code"], "DEPENDENCY_CYCLE");
+    let loop_ok = run_json(
+        repo.path(),
+        ["block", &api, "by", &notes, "--type", "informs"],
+    );
+    assert_eq!(loop_ok.cli.code, 0, "stderr:\n{}", loop_ok.cli.stderr);
+    let starts_after = run_json(
+        repo.path(),
+        ["block", &api, "by", &docs, "--type", "starts_after"],
+    );
+    assert_eq!(starts_after.envelope["error"]["code"], "VALIDATION_ERROR");
+}
+
+#[test]
+fn finish_to_finish_blocks_closing_until_the_blocker_finishes() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let api = create_task(repo.path(), "API");
+    let docs = create_task(repo.path(), "Docs");
+    run_json(
+        repo.path(),
+        ["block", &docs, "by", &api, "--type", "finish_to_finish"],
+    );
+
+    let early = run_json(repo.path(), ["done", &docs]);
+    assert_eq!(early.envelope["error"]["code"], "FINISH_DEPENDENCY_OPEN");
+    assert_eq!(
+        early.envelope["error"]["details"]["blockers"],
+        serde_json::json!([api])
+    );
+
+    let together = run_json(repo.path(), ["done", &docs, &api]);
+    assert_eq!(together.cli.code, 0, "stderr:\n{}", together.cli.stderr);
+}
+
+#[test]
+fn closing_and_canceling_report_affected_dependents() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let api = create_task(repo.path(), "API");
+    let ui = create_task(repo.path(), "UI");
+    let docs = create_task(repo.path(), "Docs");
+    let schema = create_task(repo.path(), "Schema");
+    let client = create_task(repo.path(), "Client");
+    run_json(repo.path(), ["block", &ui, "by", &api]);
+    run_json(repo.path(), ["order", &docs, "after", &api]);
+    run_json(repo.path(), ["block", &client, "by", &api]);
+    run_json(repo.path(), ["block", &client, "by", &schema]);
+
+    let done = run_json(repo.path(), ["done", &api]);
+    assert_eq!(done.cli.code, 0, "stderr:\n{}", done.cli.stderr);
+    let dependents = done.envelope["data"]["dependents"]
+        .as_array()
+        .expect("dependents")
+        .clone();
+    let unblocked: Vec<(&str, &str)> = dependents
+        .iter()
+        .map(|impact| {
+            assert_eq!(impact["blocker"], api.as_str());
+            assert_eq!(impact["effect"], "unblocked");
+            (
+                impact["task_id"].as_str().unwrap(),
+                impact["dep_type"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    let mut expected = vec![(ui.as_str(), "blocks"), (docs.as_str(), "starts_after")];
+    expected.sort();
+    assert_eq!(unblocked, expected);
+    assert!(done.cli.stderr.contains("unblocks"));
+
+    let canceled = run_json(repo.path(), ["cancel", &schema, "--cascade-defer"]);
+    assert_eq!(canceled.cli.code, 0, "stderr:\n{}", canceled.cli.stderr);
+    assert_eq!(
+        canceled.envelope["data"]["dependents"][0]["effect"],
+        "orphaned"
+    );
+    assert_eq!(
+        canceled.envelope["data"]["deferred"],
+        serde_json::json!([client])
+    );
+    let shown = run_json(repo.path(), ["show", &client]);
+    assert_eq!(shown.envelope["data"]["task"]["status"], "deferred");
+
+    let invalid = run_json(repo.path(), ["done", &ui, "--cascade-defer"]);
+    assert_ne!(invalid.cli.code, 0);
+}
+
+#[test]
+fn deps_marks_repeated_blockers_and_filters_by_status() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let app = create_task(repo.path(), "App");
+    let api = create_task(repo.path(), "API");
+    let ui = create_task(repo.path(), "UI");
+    let db = create_task(repo.path(), "Schema");
+    for (child, blocker) in [(&app, &api), (&app, &ui), (&api, &db), (&ui, &db)] {
+        run_json(repo.path(), ["block", child, "by", blocker]);
+    }
+
+    let tree = run_json(repo.path(), ["deps", &app, "--direction", "up"]);
+    let children = tree.envelope["data"]["root"]["children"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let shared: Vec<_> = children.iter().map(|child| &child["children"][0]).collect();
+    assert!(shared.iter().all(|node| node["id"] == db.as_str()));
+    assert_eq!(shared[0].get("seen"), None);
+    assert_eq!(shared[1]["seen"], true);
+    assert_eq!(shared[1]["children"], serde_json::json!([]));
+
+    let shallow = run_json(repo.path(), ["deps", &app, "--depth", "1"]);
+    let root = &shallow.envelope["data"]["root"];
+    assert_eq!(root["children"].as_array().unwrap().len(), 2);
+    assert_eq!(root["children"][0]["children"], serde_json::json!([]));
+
+    run_json(repo.path(), ["done", &db]);
+    let open = run_json(
+        repo.path(),
+        ["deps", &app, "--direction", "up", "--statuses", "open"],
+    );
+    let root = &open.envelope["data"]["root"];
+    assert_eq!(root["children"].as_array().unwrap().len(), 2);
+    assert_eq!(root["children"][0]["children"], serde_json::json!([]));
+}
+
+#[test]
+fn deps_flat_lists_shared_blockers_once_with_every_edge() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let app = create_task(repo.path(), "App");
+    let api = create_task(repo.path(), "API");
+    let ui = create_task(repo.path(), "UI");
+    let db = create_task(repo.path(), "Schema");
+    for (child, blocker) in [(&app, &api), (&app, &ui), (&api, &db), (&ui, &db)] {
+        run_json(repo.path(), ["block", child, "by", blocker]);
+    }
+
+    let flat = run_json(repo.path(), ["deps", &app, "--direction", "up", "--flat"]);
+    let data = &flat.envelope["data"];
+    assert_eq!(data["root"], app.as_str());
+    let nodes: Vec<_> = data["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| {
+            (
+                node["id"].as_str().unwrap(),
+                node["depth"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            (app.as_str(), 0),
+            (api.as_str(), 1),
+            (db.as_str(), 2),
+            (ui.as_str(), 1)
+        ]
+    );
+    assert_eq!(data["edges"].as_array().unwrap().len(), 4);
+    assert_eq!(
+        data["edges"][3],
+        serde_json::json!({"child": ui, "blocker": db, "dep_type": "blocks"})
+    );
+
+    let down = run_json(repo.path(), ["deps", &db, "--direction", "down", "--flat"]);
+    let edges = down.envelope["data"]["edges"].as_array().unwrap();
+    assert!(
+        edges.contains(&serde_json::json!({"child": api, "blocker": db, "dep_type": "blocks"}))
+    );
+
+    let retired = run_json(repo.path(), ["dep", "export", &app]);
+    assert!(
+        retired.envelope["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("tsq deps <id> --flat")
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json};
+use serde_json::Value;
+
+fn path_ids(path: &Value) -> Vec<&str> {
+    let hops = path["hops"].as_array().expect("hops");
+    let mut ids = vec![hops[0]["from"].as_str().expect("from")];
+    ids.extend(hops.iter().map(|hop| hop["to"].as_str().expect("to")));
+    ids
+}
+
+#[test]
+fn dep_why_explains_transitive_gating_and_relations() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let app = create_task(repo.path(), "App");
+    let api = create_task(repo.path(), "API");
+    let db = create_task(repo.path(), "Database");
+    let ui = create_task(repo.path(), "UI polish");
+    let other = create_task(repo.path(), "Unrelated");
+    run_cli(repo.path(), ["block", &app, "by", &api]);
+    run_cli(repo.path(), ["block", &api, "by", &db]);
+    run_cli(repo.path(), ["relate", &ui, &app]);
+
+    let gating = run_json(repo.path(), ["why", &app, &db]);
+    let data = &gating.envelope["data"];
+    assert_eq!(data["connected"], true);
+    assert_eq!(data["reversed"], false);
+    assert_eq!(path_ids(&data["paths"][0]), [app.as_str(), &api, &db]);
+    assert_eq!(data["paths"][0]["gating"], true);
+    assert_eq!(data["paths"][0]["hops"][0]["via"], "blocks");
+
+    let reversed = run_json(repo.path(), ["why", &db, &app]);
+    assert_eq!(reversed.envelope["data"]["reversed"], true);
+    assert_eq!(
+        path_ids(&reversed.envelope["data"]["paths"][0]),
+        [app.as_str(), &api, &db]
+    );
+
+    let related = run_json(repo.path(), ["why", &ui, &db]);
+    let path = &related.envelope["data"]["paths"][0];
+    assert_eq!(path_ids(path), [ui.as_str(), &app, &api, &db]);
+    assert_eq!(path["hops"][0]["via"], "relates_to");
+    assert_eq!(path["gating"], false);
+
+    let none = run_json(repo.path(), ["why", &app, &other]);
+    assert_eq!(none.envelope["data"]["connected"], false);
+    assert_eq!(none.envelope["data"]["paths"], serde_json::json!([]));
+    let human = run_cli(repo.path(), ["why", &app, &db]);
+    assert!(human.stdout.contains("-[blocks]->"), "{}", human.stdout);
+
+    let same = run_json(repo.path(), ["why", &app, &app]);
+    assert_eq!(same.envelope["error"]["code"], "VALIDATION_ERROR");
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use common::{
+    create_task, create_task_with_args, init_repo, make_repo, run_cli, run_json, run_json_with_env,
+};
+use serde_json::{Value, json};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+fn append_raw(repo: &Path, events: &[Value]) {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(repo.join(".tasque/events.jsonl"))
+        .expect("events file");
+    for event in events {
+        writeln!(file, "{}", event).expect("append event");
+    }
+}
+
+fn raw_event(id: &str, ts: &str, event_type: &str, task_id: &str, payload: Value) -> Value {
+    json!({
+        "id": id,
+        "ts": ts,
+        "actor": "test",
+        "type": event_type,
+        "task_id": task_id,
+        "payload": payload,
+    })
+}
+
+fn codes(envelope: &Value) -> Vec<String> {
+    envelope["data"]["findings"]
+        .as_array()
+        .expect("findings")
+        .iter()
+        .map(|finding| finding["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn doctor_reports_task_state_and_timestamp_pathologies() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let parent = create_task(repo.path(), "Parent");
+    let child = create_task_with_args(repo.path(), "Child", &["--parent", &parent]);
+    run_cli(repo.path(), ["done", &parent]);
+    let worker = create_task(repo.path(), "Worker");
+    let a = create_task(repo.path(), "Dup A");
+    let b = create_task(repo.path(), "Dup B");
+    append_raw(
+        repo.path(),
+        &[
+            raw_event(
+                "01HX0000000000000000000000",
+                "2099-01-01T00:00:00.000Z",
+                "task.status_set",
+                &worker,
+                json!({"status": "in_progress"}),
+            ),
+            raw_event(
+                "01HX0000000000000000000001",
+                "2099-01-01T00:00:00.000Z",
+                "task.updated",
+                &a,
+                json!({"duplicate_of": b}),
+            ),
+            raw_event(
+                "01HX0000000000000000000002",
+                "2000-01-01T00:00:00.000Z",
+                "task.updated",
+                &b,
+                json!({"duplicate_of": a}),
+            ),
+        ],
+    );
+
+    let doctor = run_json(repo.path(), ["doctor"]);
+    assert_eq!(doctor.cli.code, 0, "stderr:\n{}", doctor.cli.stderr);
+    let found = codes(&doctor.envelope);
+    for code in [
+        "CHILD_OF_CLOSED_PARENT",
+        "IN_PROGRESS_UNASSIGNED",
+        "DUPLICATE_CYCLE",
+        "NON_MONOTONIC_TIMESTAMP",
+    ] {
+        assert!(
+            found.contains(&code.to_string()),
+            "{code} missing: {found:?}"
+        );
+    }
+    let findings = doctor.envelope["data"]["findings"].as_array().unwrap();
+    let orphan = findings
+        .iter()
+        .find(|finding| finding["code"] == "CHILD_OF_CLOSED_PARENT")
+        .unwrap();
+    assert_eq!(orphan["task_id"], child);
+    assert_eq!(orphan["severity"], "warning");
+    let cycles = found
+        .iter()
+        .filter(|code| *code == "DUPLICATE_CYCLE")
+        .count();
+    assert_eq!(cycles, 1);
+    assert_eq!(
+        doctor.envelope["data"]["issues"].as_array().unwrap().len(),
+        findings.len()
+    );
+}
+
+#[test]
+fn doctor_still_reports_when_unknown_event_types_block_replay() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let task = create_task(repo.path(), "Task");
+    let dup = raw_event(
+        "01HX0000000000000000000009",
+        "2099-01-01T00:00:00.000Z",
+        "task.noted",
+        &task,
+        json!({"text": "hi"}),
+    );
+    append_raw(
+        repo.path(),
+        &[
+            dup.clone(),
+            dup,
+            raw_event(
+                "01HX000000000000000000000A",
+                "2099-01-02T00:00:00.000Z",
+                "task.teleported",
+                &task,
+                json!({}),
+            ),
+        ],
+    );
+
+    // Point skill targets at an empty home so only event findings show up.
+    let home = repo.path().join("home");
+    let home = home.to_str().unwrap();
+    let doctor = run_json_with_env(
+        repo.path(),
+        ["doctor", "--json"],
+        &[("HOME", home), ("USERPROFILE", home), ("CODEX_HOME", home)],
+    );
+    assert_eq!(doctor.cli.code, 0, "stderr:\n{}", doctor.cli.stderr);
+    let found = codes(&doctor.envelope);
+    assert_eq!(
+        found,
+        [
+            "DUPLICATE_EVENT_ID",
+            "UNKNOWN_EVENT_TYPE",
+            "EVENTS_UNREADABLE"
+        ]
+    );
+    let unknown = &doctor.envelope["data"]["findings"][1];
+    assert_eq!(unknown["severity"], "error");
+    assert_eq!(unknown["line"], 4);
+}
+
+#[test]
+fn doctor_warns_about_managed_skills_behind_the_source_version() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let home = repo.path().join("home");
+    let skill = home.join(".claude").join("skills").join("tasque");
+    std::fs::create_dir_all(&skill).expect("skill dir");
+    std::fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: tasque\nmetadata:\n  version: \"0.0.1\"\n---\n<!-- tsq-managed-skill:v1 -->\n",
+    )
+    .expect("write SKILL.md");
+
+    let home = home.to_str().unwrap();
+    let doctor = run_json_with_env(
+        repo.path(),
+        ["doctor", "--json"],
+        &[("HOME", home), ("USERPROFILE", home), ("CODEX_HOME", home)],
+    );
+    assert_eq!(doctor.cli.code, 0, "stderr:\n{}", doctor.cli.stderr);
+    let findings = doctor.envelope["data"]["findings"].as_array().unwrap();
+    let outdated: Vec<&Value> = findings
+        .iter()
+        .filter(|finding| finding["code"] == "SKILL_OUTDATED")
+        .collect();
+    assert_eq!(outdated.len(), 1, "{findings:?}");
+    assert_eq!(outdated[0]["severity"], "warning");
+    assert!(
+        outdated[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("claude skill at ")
+    );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::{create_task, init_repo, ok_data, run_json};
+
+fn event_log_len(repo: &std::path::Path) -> usize {
+    std::fs::read_to_string(repo.join(".tasque/events.jsonl"))
+        .expect("read events")
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count()
+}
+
+#[test]
+fn create_dry_run_reports_events_without_writing() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let before = event_log_len(repo.path());
+
+    let result = run_json(repo.path(), ["create", "Preview only", "--dry-run"]);
+
+    let data = ok_data(&result.envelope);
+    assert_eq!(data["dry_run"].as_bool(), Some(true));
+    assert_eq!(data["task"]["title"].as_str(), Some("Preview only"));
+    let events = data["events"].as_array().expect("events");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["type"].as_str(), Some("task.created"));
+    assert_eq!(event_log_len(repo.path()), before);
+    let listed = run_json(repo.path(), ["find", "open"]);
+    assert_eq!(
+        ok_data(&listed.envelope)["tasks"].as_array().map(Vec::len),
+        Some(0)
+    );
+}
+
+#[test]
+fn lifecycle_dry_run_projects_status_but_keeps_log() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Dry run close target");
+    let before = event_log_len(repo.path());
+
+    let result = run_json(repo.path(), ["done", &id, "--dry-run"]);
+
+    let data = ok_data(&result.envelope);
+    assert_eq!(data["tasks"][0]["status"].as_str(), Some("closed"));
+    assert_eq!(data["events"][0]["type"].as_str(), Some("task.status_set"));
+    assert_eq!(event_log_len(repo.path()), before);
+    let shown = run_json(repo.path(), ["show", &id]);
+    assert_eq!(
+        ok_data(&shown.envelope)["task"]["status"].as_str(),
+        Some("open")
+    );
+}
+
+#[test]
+fn spec_dry_run_does_not_write_spec_file() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Dry run spec target");
+
+    let result = run_json(
+        repo.path(),
+        ["spec", &id, "--text", "# Overview\n", "--dry-run"],
+    );
+
+    let data = ok_data(&result.envelope);
+    assert_eq!(
+        data["events"][0]["type"].as_str(),
+        Some("task.spec_attached")
+    );
+    assert!(!repo.path().join(".tasque/specs").join(&id).exists());
+}
+
+#[test]
+fn merge_dry_run_keeps_plan_and_reports_events() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let target = create_task(repo.path(), "Merge target task");
+    let source = common::create_task_with_args(repo.path(), "Merge source item", &["--force"]);
+    let before = event_log_len(repo.path());
+
+    let result = run_json(
+        repo.path(),
+        ["merge", &source, "--into", &target, "--dry-run"],
+    );
+
+    let data = ok_data(&result.envelope);
+    assert_eq!(data["dry_run"].as_bool(), Some(true));
+    assert_eq!(data["plan_summary"]["merged_sources"].as_u64(), Some(1));
+    assert!(!data["events"].as_array().expect("events").is_empty());
+    assert_eq!(event_log_len(repo.path()), before);
+}
+
+#[test]
+fn read_only_commands_reject_dry_run() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let result = run_json(repo.path(), ["find", "open", "--dry-run"]);
+
+    common::assert_validation_error(&result);
+    assert_eq!(
+        result.envelope["error"]["message"].as_str(),
+        Some("tsq find does not support --dry-run")
+    );
+}
+
+#[test]
+fn spec_render_dry_run_is_unsupported_and_writes_nothing() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Render dry run target");
+    let before = event_log_len(repo.path());
+
+    let result = run_json(
+        repo.path(),
+        ["spec", "render", &id, "--out", "x.html", "--dry-run"],
+    );
+
+    assert_eq!(result.cli.code, 1);
+    assert_eq!(
+        result.envelope["error"]["code"].as_str(),
+        Some("DRY_RUN_UNSUPPORTED")
+    );
+    assert!(!repo.path().join("x.html").exists());
+    assert_eq!(event_log_len(repo.path()), before);
+}
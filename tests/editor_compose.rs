@@ -0,0 +1,102 @@
+mod common;
+
+use common::{create_task, init_repo, run_json_with_env};
+use std::path::Path;
+
+fn write_editor_script(repo: &Path, body: &str) -> String {
+    let script = repo.join("fake-editor.sh");
+    std::fs::write(&script, body).expect("write fake editor");
+    format!("sh {}", script.display())
+}
+
+#[test]
+fn note_edit_uses_editor_content() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Editor note target");
+    let editor = write_editor_script(
+        repo.path(),
+        "printf '## Decision\\n\\n- keep JSONL\\n' > \"$1\"\n",
+    );
+
+    let result = run_json_with_env(
+        repo.path(),
+        ["note", &id, "--edit", "--json"],
+        &[("VISUAL", ""), ("EDITOR", &editor)],
+    );
+
+    assert_eq!(result.cli.code, 0, "stderr: {}", result.cli.stderr);
+    assert_eq!(
+        result.envelope["data"]["note"]["text"].as_str(),
+        Some("## Decision\n\n- keep JSONL")
+    );
+}
+
+#[test]
+fn edit_seeds_editor_with_current_description() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Editor description target");
+    let seeded = run_json_with_env(
+        repo.path(),
+        ["edit", &id, "--description", "original body", "--json"],
+        &[],
+    );
+    assert_eq!(seeded.cli.code, 0);
+    let editor = write_editor_script(repo.path(), "printf '\\nappended line\\n' >> \"$1\"\n");
+
+    let result = run_json_with_env(
+        repo.path(),
+        ["edit", &id, "--edit", "--json"],
+        &[("VISUAL", ""), ("EDITOR", &editor)],
+    );
+
+    assert_eq!(result.cli.code, 0, "stderr: {}", result.cli.stderr);
+    assert_eq!(
+        result.envelope["data"]["task"]["description"].as_str(),
+        Some("original body\nappended line")
+    );
+}
+
+#[test]
+fn create_edit_rejects_empty_editor_content() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let editor = write_editor_script(repo.path(), ": > \"$1\"\n");
+
+    let result = run_json_with_env(
+        repo.path(),
+        ["create", "Empty editor body", "--edit", "--json"],
+        &[("VISUAL", ""), ("EDITOR", &editor)],
+    );
+
+    assert_eq!(result.cli.code, 1);
+    assert_eq!(
+        result.envelope["error"]["message"].as_str(),
+        Some("editor content must not be empty")
+    );
+}
+
+#[test]
+fn editor_failure_aborts_create() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let editor = write_editor_script(repo.path(), "exit 3\n");
+
+    let result = run_json_with_env(
+        repo.path(),
+        ["create", "Aborted editor body", "--edit", "--json"],
+        &[("VISUAL", ""), ("EDITOR", &editor)],
+    );
+
+    assert_eq!(result.cli.code, 1);
+    assert_eq!(
+        result.envelope["error"]["message"].as_str(),
+        Some("editor exited with status 3")
+    );
+    let listed = run_json_with_env(repo.path(), ["find", "open", "--json"], &[]);
+    assert_eq!(
+        listed.envelope["data"]["tasks"].as_array().map(Vec::len),
+        Some(0)
+    );
+}
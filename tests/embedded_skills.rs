@@ -46,6 +46,48 @@ fn embedded_skills_fallback_installs_skill_when_disk_sources_missing() {
     assert!(contents.contains("tsq spec <id> --show"));
 }
 
+#[test]
+fn install_skill_covers_cursor_windsurf_and_gemini_targets() {
+    let repo = make_repo();
+    let repo_path = repo.path();
+    let gemini_override = repo_path.join("gemini-skills");
+
+    let output = Command::new(tsq_bin())
+        .args([
+            "init",
+            "--install-skill",
+            "--no-wizard",
+            "--skill-targets",
+            "cursor,windsurf,gemini",
+            "--skill-dir-gemini",
+        ])
+        .arg(&gemini_override)
+        .current_dir(repo_path)
+        .env("TSQ_ACTOR", "rust-test")
+        .env("TSQ_SKILLS_DIR", repo_path.join("missing-skills"))
+        .env("HOME", repo_path)
+        .env("USERPROFILE", repo_path)
+        .output()
+        .expect("failed executing tsq binary");
+
+    assert!(
+        output.status.success(),
+        "stdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for skills_dir in [
+        repo_path.join(".cursor").join("skills"),
+        repo_path.join(".windsurf").join("skills"),
+        gemini_override,
+    ] {
+        let skill_md = skills_dir.join("tasque").join("SKILL.md");
+        assert!(skill_md.exists(), "missing {}", skill_md.display());
+    }
+    assert!(!repo_path.join(".gemini").exists());
+    assert!(!repo_path.join(".claude").exists());
+}
+
 fn tsq_bin() -> PathBuf {
     if let Ok(path) = std::env::var("CARGO_BIN_EXE_tsq") {
         let candidate = PathBuf::from(path);
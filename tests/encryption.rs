@@ -0,0 +1,150 @@
+mod common;
+
+use common::{init_repo, make_repo, run_cli_with_env, run_json_with_env};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+const KEY: &[(&str, &str)] = &[("TSQ_ENCRYPTION_KEY", "correct horse battery staple")];
+
+fn enable_encryption(repo: &Path) {
+    let path = repo.join(".tasque/config.json");
+    let mut config: Value =
+        serde_json::from_str(&fs::read_to_string(&path).expect("read config")).expect("config");
+    config["encrypt"] = Value::Bool(true);
+    fs::write(&path, serde_json::to_string_pretty(&config).unwrap()).expect("write config");
+}
+
+#[test]
+fn encrypted_repo_seals_events_and_specs_but_commands_read_them() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    enable_encryption(repo.path());
+
+    let created = run_json_with_env(
+        repo.path(),
+        ["create", "Rotate payroll credentials", "--json"],
+        KEY,
+    );
+    assert_eq!(created.cli.code, 0, "stderr:\n{}", created.cli.stderr);
+    let task_id = created.envelope["data"]["task"]["id"]
+        .as_str()
+        .expect("task id")
+        .to_string();
+    let attach = run_json_with_env(
+        repo.path(),
+        [
+            "spec",
+            &task_id,
+            "--text",
+            "# Spec\n\nVault path is secret.\n",
+            "--json",
+        ],
+        KEY,
+    );
+    assert_eq!(attach.cli.code, 0, "stderr:\n{}", attach.cli.stderr);
+
+    let events = fs::read_to_string(repo.path().join(".tasque/events.jsonl")).expect("events");
+    assert!(!events.contains("payroll"));
+    assert!(events.lines().all(|line| line.starts_with("tsq-enc:v1:")));
+    let spec_path = repo.path().join(format!(".tasque/specs/{task_id}/spec.md"));
+    let spec = fs::read_to_string(spec_path).expect("spec");
+    assert!(spec.starts_with("tsq-enc:v1:") && !spec.contains("Vault"));
+
+    fs::remove_file(repo.path().join(".tasque/state.json")).expect("drop cache");
+    let shown = run_json_with_env(repo.path(), ["show", &task_id, "--json"], KEY);
+    assert_eq!(shown.cli.code, 0, "stderr:\n{}", shown.cli.stderr);
+    assert_eq!(
+        shown.envelope["data"]["task"]["title"],
+        "Rotate payroll credentials"
+    );
+    let spec = run_json_with_env(repo.path(), ["spec", &task_id, "--show", "--json"], KEY);
+    assert_eq!(
+        spec.envelope["data"]["spec"]["content"],
+        "# Spec\n\nVault path is secret.\n"
+    );
+
+    let missing = run_json_with_env(repo.path(), ["find", "open", "--json"], &[]);
+    assert_eq!(missing.envelope["error"]["code"], "ENCRYPTION_KEY_MISSING");
+    let wrong = run_json_with_env(
+        repo.path(),
+        ["find", "open", "--json"],
+        &[("TSQ_ENCRYPTION_KEY", "wrong horse")],
+    );
+    assert_eq!(wrong.envelope["error"]["code"], "DECRYPT_FAILED");
+}
+
+#[test]
+fn key_command_supplies_the_key_from_a_keyring_lookup() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    enable_encryption(repo.path());
+
+    let created = run_json_with_env(
+        repo.path(),
+        ["create", "Keyring task", "--json"],
+        &[(
+            "TSQ_ENCRYPTION_KEY_COMMAND",
+            "echo correct horse battery staple",
+        )],
+    );
+    assert_eq!(created.cli.code, 0, "stderr:\n{}", created.cli.stderr);
+
+    let listed = run_json_with_env(repo.path(), ["find", "open", "--json"], KEY);
+    assert_eq!(listed.envelope["data"]["tasks"][0]["title"], "Keyring task");
+}
+
+#[test]
+fn merge_driver_keeps_sealed_lines_and_seals_plaintext_events() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    enable_encryption(repo.path());
+    let events_path = repo.path().join(".tasque/events.jsonl");
+
+    let first = run_json_with_env(repo.path(), ["create", "Base task", "--json"], KEY);
+    assert_eq!(first.cli.code, 0, "stderr:\n{}", first.cli.stderr);
+    let config: Value = serde_json::from_str(
+        &fs::read_to_string(repo.path().join(".tasque/config.json")).expect("read config"),
+    )
+    .expect("config");
+    let salt = config["encryption_salt"].as_str().expect("salt written");
+    assert_eq!(salt.len(), 32);
+    let base = fs::read_to_string(&events_path).expect("events");
+    let ancestor = repo.path().join("ancestor.jsonl");
+    fs::write(&ancestor, &base).expect("write ancestor");
+
+    let second = run_json_with_env(repo.path(), ["create", "Ours task", "--json"], KEY);
+    assert_eq!(second.cli.code, 0, "stderr:\n{}", second.cli.stderr);
+    let ours_raw = fs::read_to_string(&events_path).expect("events");
+    let ours = repo.path().join("ours.jsonl");
+    fs::write(&ours, &ours_raw).expect("write ours");
+
+    let theirs = repo.path().join("theirs.jsonl");
+    let plaintext = serde_json::json!({
+        "id": "01TZZZZZZZZZZZZZZZZZZZZZZZ",
+        "event_id": "01TZZZZZZZZZZZZZZZZZZZZZZZ",
+        "ts": "2099-01-01T00:00:00.000Z",
+        "actor": "test",
+        "type": "task.created",
+        "task_id": "tsq-theirs01",
+        "payload": {"title": "Theirs plaintext task"},
+    });
+    fs::write(&theirs, format!("{base}{plaintext}\n")).expect("write theirs");
+
+    let merged = run_cli_with_env(
+        repo.path(),
+        [
+            "merge-driver",
+            ancestor.to_str().unwrap(),
+            ours.to_str().unwrap(),
+            theirs.to_str().unwrap(),
+        ],
+        KEY,
+    );
+    assert_eq!(merged.code, 0, "stderr:\n{}", merged.stderr);
+    let merged = fs::read_to_string(&ours).expect("merged");
+    let lines: Vec<&str> = merged.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[..2], ours_raw.lines().collect::<Vec<_>>()[..]);
+    assert!(lines[2].starts_with("tsq-enc:v1:") && !lines[2].contains("Theirs"));
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{create_task_with_args, init_repo, ok_data, run_cli, run_json, set_config_key};
+use tasque::app::service::TasqueService;
+use tasque::app::service_types::CreateInput;
+use tasque::types::TaskKind;
+
+#[test]
+fn escalation_is_off_without_policy() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    create_task_with_args(repo.path(), "Plain", &["--priority", "2"]);
+
+    let report = run_json(repo.path(), ["escalations"]);
+    assert_eq!(ok_data(&report.envelope)["enabled"], false);
+    let list = run_json(repo.path(), ["find", "open"]);
+    assert!(ok_data(&list.envelope)["tasks"][0]["effective_priority"].is_null());
+}
+
+#[test]
+fn aged_tasks_sort_by_effective_priority_without_changing_stored_priority() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let aged = create_task_with_args(repo.path(), "Aged", &["--priority", "3"]);
+    set_config_key(
+        repo.path(),
+        "escalation",
+        serde_json::json!({"after_days": 10, "every_days": 5, "max_boost": 2}),
+    );
+
+    // Twenty days later the aged task has been escalated two levels and
+    // outranks a fresh P2 task.
+    let later = chrono::Utc::now() + chrono::Duration::days(20);
+    let service = TasqueService::new(repo.path().display().to_string(), "rust-test", move || {
+        later.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+    });
+    let fresh = service
+        .create(CreateInput {
+            title: "Fresh".to_string(),
+            kind: TaskKind::Task,
+            priority: 2,
+            description: None,
+            external_ref: None,
+            discovered_from: None,
+            parent: None,
+            exact_id: false,
+            planning_state: None,
+            explicit_id: None,
+            body_file: None,
+            ensure: false,
+            force: false,
+            skip_duplicate_check: true,
+        })
+        .expect("create fresh task");
+
+    let ready = service.ready(None).expect("ready");
+    assert_eq!(
+        ready
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>(),
+        vec![aged.as_str(), fresh.id.as_str()]
+    );
+    assert_eq!(ready[0].priority, 3);
+    assert_eq!(ready[0].effective_priority, Some(1));
+    assert_eq!(ready[1].effective_priority, None);
+
+    let report = service.escalations().expect("escalations");
+    assert!(report.enabled);
+    assert_eq!(report.escalations.len(), 1);
+    assert_eq!(report.escalations[0].age_days, 20);
+
+    let stored = run_json(repo.path(), ["show", &aged]);
+    assert_eq!(ok_data(&stored.envelope)["task"]["priority"], 3);
+}
+
+#[test]
+fn escalations_report_lists_raised_tasks() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task_with_args(repo.path(), "Waiting", &["--priority", "2"]);
+    set_config_key(
+        repo.path(),
+        "escalation",
+        serde_json::json!({"after_days": 0}),
+    );
+
+    let report = run_json(repo.path(), ["escalations"]);
+    let data = ok_data(&report.envelope);
+    assert_eq!(data["enabled"], true);
+    assert_eq!(data["escalations"][0]["id"], id.as_str());
+    assert_eq!(data["escalations"][0]["priority"], 2);
+    assert_eq!(data["escalations"][0]["effective_priority"], 1);
+
+    let human = run_cli(repo.path(), ["escalations"]);
+    assert!(human.stdout.contains("p2→p1"), "{}", human.stdout);
+}
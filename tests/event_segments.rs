@@ -0,0 +1,106 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json, set_config_key};
+use serde_json::{Value, json};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+fn append_raw(repo: &Path, lines: &[String]) {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(repo.join(".tasque/events.jsonl"))
+        .expect("events file");
+    for line in lines {
+        writeln!(file, "{}", line).expect("append event");
+    }
+}
+
+fn raw_event(id: &str, ts: &str, event_type: &str, task_id: &str, payload: Value) -> String {
+    json!({
+        "id": id,
+        "ts": ts,
+        "actor": "test",
+        "type": event_type,
+        "task_id": task_id,
+        "payload": payload,
+    })
+    .to_string()
+}
+
+fn line_count(path: &Path) -> usize {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count()
+}
+
+fn open_count(repo: &Path) -> usize {
+    let found = run_json(repo, ["find", "open"]);
+    assert_eq!(found.cli.code, 0, "stderr:\n{}", found.cli.stderr);
+    found.envelope["data"]["tasks"].as_array().unwrap().len()
+}
+
+#[test]
+fn monthly_segments_rotate_on_append_and_stitch_back_in_order() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "event_segments", json!("monthly"));
+    let may = raw_event(
+        "01HX0000000000000000000001",
+        "2024-05-10T00:00:00.000Z",
+        "task.created",
+        "tsq-1",
+        json!({"title": "From May", "id": "tsq-1"}),
+    );
+    append_raw(
+        repo.path(),
+        &[
+            may.clone(),
+            raw_event(
+                "01HX0000000000000000000002",
+                "2024-06-03T00:00:00.000Z",
+                "task.created",
+                "tsq-2",
+                json!({"title": "From June", "id": "tsq-2"}),
+            ),
+            raw_event(
+                "01HX0000000000000000000003",
+                "2024-06-04T00:00:00.000Z",
+                "task.noted",
+                "tsq-1",
+                json!({"text": "still open"}),
+            ),
+        ],
+    );
+
+    create_task(repo.path(), "Current");
+
+    let tasque = repo.path().join(".tasque");
+    assert_eq!(line_count(&tasque.join("events-2024-05.jsonl")), 1);
+    assert_eq!(line_count(&tasque.join("events-2024-06.jsonl")), 2);
+    assert_eq!(line_count(&tasque.join("events.jsonl")), 1);
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(tasque.join("events.manifest.json")).unwrap())
+            .unwrap();
+    assert_eq!(manifest["segments"][0]["file"], "events-2024-05.jsonl");
+    assert_eq!(manifest["segments"][1]["month"], "2024-06");
+    assert_eq!(open_count(repo.path()), 3);
+
+    // A merge can leave a rotated event behind in the active file.
+    append_raw(repo.path(), &[may]);
+    fs::remove_file(tasque.join("state.json")).unwrap();
+    assert_eq!(open_count(repo.path()), 3);
+
+    create_task(repo.path(), "Later");
+    assert_eq!(line_count(&tasque.join("events.jsonl")), 2);
+    assert_eq!(open_count(repo.path()), 4);
+
+    let history = run_json(repo.path(), ["history", "tsq-1"]);
+    assert_eq!(history.cli.code, 0, "stderr:\n{}", history.cli.stderr);
+    assert_eq!(
+        history.envelope["data"]["events"].as_array().unwrap().len(),
+        2
+    );
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json, set_config_key};
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn export_redact_applies_default_rules() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let alpha = create_task(repo.path(), "Alpha");
+    let beta = create_task(repo.path(), "Beta");
+    run_cli(
+        repo.path(),
+        ["edit", &alpha, "--description", "internal host names"],
+    );
+    run_cli(repo.path(), ["assign", &alpha, "--assignee", "alice"]);
+    run_cli(repo.path(), ["note", &alpha, "customer called"]);
+    run_cli(repo.path(), ["block", &beta, "by", &alpha]);
+
+    let plain = run_json(repo.path(), ["export"]);
+    let tasks = &plain.envelope["data"]["tasks"];
+    assert_eq!(plain.envelope["data"]["redacted"], false);
+    assert_eq!(tasks[0]["description"], "internal host names");
+    assert_eq!(tasks[0]["assignee"], "alice");
+
+    let redacted = run_json(repo.path(), ["export", "--redact"]);
+    let data = &redacted.envelope["data"];
+    assert_eq!(data["redacted"], true);
+    assert_eq!(data["total"], 2);
+    let task = &data["tasks"][0];
+    assert_eq!(task["title"], "Alpha");
+    assert!(task.get("description").is_none());
+    assert!(task.get("notes").is_none());
+    let assignee = task["assignee"].as_str().expect("hashed assignee");
+    assert!(assignee.starts_with("anon-") && assignee != "alice");
+    assert_eq!(data["dependencies"][0]["task_id"], beta.as_str());
+    assert_eq!(data["dependencies"][0]["blocker"], alpha.as_str());
+}
+
+#[test]
+fn export_redact_uses_configured_rules_and_writes_out_file() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Vendor visible");
+    run_cli(repo.path(), ["edit", &task_id, "--external-ref", "ACME-42"]);
+    run_cli(repo.path(), ["label", &task_id, "customer:acme"]);
+
+    set_config_key(
+        repo.path(),
+        "redaction",
+        serde_json::json!({
+            "strip": ["title"],
+            "hash": ["labels"],
+            "drop": ["^external_"],
+        }),
+    );
+
+    let out = repo.path().join("export.json");
+    let result = run_json(
+        repo.path(),
+        ["export", "--redact", "--out", out.to_str().unwrap()],
+    );
+    assert_eq!(result.cli.code, 0, "stderr:\n{}", result.cli.stderr);
+    let written: Value = serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+    let task = &written["tasks"][0];
+    assert!(task.get("title").is_none());
+    assert!(task.get("external_ref").is_none());
+    let label = task["labels"][0].as_str().expect("hashed label");
+    assert!(label.starts_with("anon-"));
+    assert!(!fs::read_to_string(&out).unwrap().contains("acme"));
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{create_task, create_task_with_args, git, init_repo, make_repo, run_cli, run_json};
+use std::path::Path;
+
+fn commit(repo: &Path, message: &str) {
+    git(repo, &["commit", "-q", "--allow-empty", "-m", message]);
+}
+
+#[test]
+fn git_links_reports_linked_unlinked_and_unknown_ids() {
+    let repo = make_repo();
+    git(repo.path(), &["init", "-q", "-b", "main"]);
+    git(repo.path(), &["config", "user.name", "rust-test"]);
+    git(
+        repo.path(),
+        &["config", "user.email", "rust-test@example.com"],
+    );
+    commit(repo.path(), "Initial commit");
+    git(repo.path(), &["tag", "base"]);
+    init_repo(repo.path());
+
+    let linked = create_task(repo.path(), "Linked work");
+    let silent = create_task(repo.path(), "Closed quietly");
+    let epic = create_task_with_args(repo.path(), "Epic", &["--kind", "epic"]);
+    let child = create_task_with_args(repo.path(), "Child", &["--parent", epic.as_str()]);
+    for id in [&linked, &silent, &child, &epic] {
+        run_cli(repo.path(), ["done", id.as_str()]);
+    }
+    commit(
+        repo.path(),
+        &format!("Fix parser\n\nRefs {} and tsq-999", linked),
+    );
+    commit(repo.path(), &format!("Finish {}", child));
+
+    let report = run_json(repo.path(), ["git", "links"]);
+    let data = &report.envelope["data"];
+    assert_eq!(data["commits_scanned"], 3, "{}", report.envelope);
+    let linked_ids: Vec<&str> = data["linked"]
+        .as_array()
+        .expect("linked")
+        .iter()
+        .map(|task| task["id"].as_str().expect("id"))
+        .collect();
+    assert_eq!(linked_ids, [linked.as_str(), child.as_str()]);
+    assert_eq!(data["linked"][0]["commits"][0]["subject"], "Fix parser");
+    assert_eq!(data["closed_without_commits"][0]["id"], silent.as_str());
+    assert_eq!(
+        data["closed_without_commits"].as_array().map(Vec::len),
+        Some(1)
+    );
+    assert_eq!(data["unknown_ids"][0]["id"], "tsq-999");
+
+    let since = run_json(repo.path(), ["git", "links", "--since", "HEAD~1"]);
+    assert_eq!(since.envelope["data"]["commits_scanned"], 1);
+    assert_eq!(since.envelope["data"]["since"], "HEAD~1");
+
+    let bad = run_json(repo.path(), ["git", "links", "--since", "nope"]);
+    assert_eq!(bad.envelope["error"]["code"], "VALIDATION_ERROR");
+}
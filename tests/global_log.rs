@@ -0,0 +1,61 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_cli_with_env, run_json};
+
+#[test]
+fn log_pages_newest_first_across_tasks_with_a_cursor() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let first = create_task(repo.path(), "First");
+    let second = create_task(repo.path(), "Second");
+    run_json(repo.path(), ["note", first.as_str(), "checked logs"]);
+
+    let page = run_json(repo.path(), ["log", "--limit", "2"]);
+    assert_eq!(page.cli.code, 0, "{:?}", page.envelope);
+    let data = &page.envelope["data"];
+    assert_eq!(data["count"], 2);
+    let events = data["events"].as_array().expect("events");
+    assert_eq!(events[0]["type"], "task.noted");
+    assert_eq!(events[0]["task_id"], first.as_str());
+    assert_eq!(events[1]["task_id"], second.as_str());
+    let cursor = data["next_cursor"]
+        .as_str()
+        .expect("next_cursor")
+        .to_string();
+    assert_eq!(events[1]["event_id"], cursor.as_str());
+
+    let rest = run_json(repo.path(), ["log", "--before-cursor", cursor.as_str()]);
+    let rest_events = rest.envelope["data"]["events"].as_array().expect("events");
+    assert_eq!(rest_events.len(), 1);
+    assert_eq!(rest_events[0]["task_id"], first.as_str());
+    assert!(rest.envelope["data"].get("next_cursor").is_none());
+}
+
+#[test]
+fn log_filters_by_type_and_actor_and_prints_one_line_per_event() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Shared");
+    run_cli_with_env(
+        repo.path(),
+        ["note", id.as_str(), "from the bot"],
+        &[("TSQ_ACTOR", "bot-alpha")],
+    );
+    run_json(repo.path(), ["note", id.as_str(), "from a human"]);
+
+    let notes = run_json(
+        repo.path(),
+        ["log", "--type", "task.noted", "--actor", "bot-alpha"],
+    );
+    let events = notes.envelope["data"]["events"].as_array().expect("events");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["summary"], "note: from the bot");
+
+    let unknown = run_json(repo.path(), ["log", "--type", "task.exploded"]);
+    assert_eq!(unknown.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    let human = run_cli(repo.path(), ["log"]);
+    assert_eq!(human.code, 0, "{}", human.stderr);
+    assert_eq!(human.stdout.lines().count(), 3);
+    assert!(human.stdout.contains("by=bot-alpha note: from the bot"));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json};
+
+#[test]
+fn handoff_reassigns_a_claimed_task_and_records_a_note() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Implement parser");
+    run_json(repo.path(), ["claim", &id, "--assignee", "coder-bot"]);
+
+    let handed = run_json(
+        repo.path(),
+        [
+            "handoff",
+            &id,
+            "--to",
+            "reviewer-bot",
+            "--note",
+            "tests pass, needs review",
+        ],
+    );
+    assert_eq!(handed.cli.code, 0, "{:?}", handed.envelope);
+    let data = &handed.envelope["data"];
+    assert_eq!(data["from"], "coder-bot");
+    assert_eq!(data["to"], "reviewer-bot");
+    assert_eq!(data["task"]["assignee"], "reviewer-bot");
+    assert_eq!(data["task"]["status"], "in_progress");
+    assert_eq!(
+        data["note"]["text"],
+        "handoff coder-bot -> reviewer-bot: tests pass, needs review"
+    );
+
+    let history = run_json(repo.path(), ["history", &id]);
+    let claimed = history.envelope["data"]["events"]
+        .as_array()
+        .expect("events")
+        .iter()
+        .find(|event| event["payload"]["handoff_from"] == "coder-bot")
+        .cloned()
+        .expect("handoff claim event");
+    assert_eq!(claimed["type"], "task.claimed");
+    assert_eq!(claimed["payload"]["assignee"], "reviewer-bot");
+}
+
+#[test]
+fn handoff_requires_a_claimed_task_and_a_new_assignee() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Unclaimed");
+
+    let unclaimed = run_json(repo.path(), ["handoff", &id, "--to", "reviewer-bot"]);
+    assert_eq!(unclaimed.cli.code, 1);
+    assert_eq!(unclaimed.envelope["error"]["code"], "NOT_CLAIMED");
+
+    run_json(repo.path(), ["claim", &id, "--assignee", "coder-bot"]);
+    let same = run_json(repo.path(), ["handoff", &id, "--to", "coder-bot"]);
+    assert_eq!(same.envelope["error"]["code"], "VALIDATION_ERROR");
+}
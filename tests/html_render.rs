@@ -0,0 +1,77 @@
+mod common;
+
+use common::{create_task, init_repo, ok_data, run_cli, run_json};
+use serde_json::Value;
+
+#[test]
+fn spec_render_writes_standalone_html() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Render <target>");
+    let attach = run_json(
+        repo.path(),
+        [
+            "spec",
+            &id,
+            "--text",
+            "# Overview\n\n| a | b |\n| - | - |\n| 1 | 2 |\n",
+        ],
+    );
+    assert_eq!(attach.cli.code, 0, "stderr: {}", attach.cli.stderr);
+
+    let out = repo.path().join("out/spec.html");
+    let out_arg = out.to_string_lossy().to_string();
+    let rendered = run_json(repo.path(), ["spec", "render", &id, "--out", &out_arg]);
+
+    assert_eq!(rendered.cli.code, 0, "stderr: {}", rendered.cli.stderr);
+    assert_eq!(
+        ok_data(&rendered.envelope)
+            .get("out")
+            .and_then(Value::as_str),
+        Some(out_arg.as_str())
+    );
+    let html = std::fs::read_to_string(&out).expect("read rendered html");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<h1>Overview</h1>"));
+    assert!(html.contains("<table>"));
+    assert!(html.contains("Render &lt;target&gt;"));
+}
+
+#[test]
+fn report_renders_markdown_and_html() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Report *item*");
+    let started = run_json(repo.path(), ["start", &id]);
+    assert_eq!(started.cli.code, 0);
+    create_task(repo.path(), "Second item");
+
+    let markdown = run_cli(repo.path(), ["report"]);
+    assert_eq!(markdown.code, 0, "stderr: {}", markdown.stderr);
+    assert!(markdown.stdout.starts_with("# Task report"));
+    assert!(markdown.stdout.contains("| in_progress | 1 |"));
+    assert!(markdown.stdout.contains("Report \\*item\\*"));
+
+    let html = run_cli(repo.path(), ["report", "--format", "html"]);
+    assert_eq!(html.code, 0, "stderr: {}", html.stderr);
+    assert!(html.stdout.contains("<h2>In progress</h2>"));
+    assert!(html.stdout.contains("Report *item*"));
+
+    let data = run_json(repo.path(), ["report"]);
+    assert_eq!(
+        ok_data(&data.envelope).get("total").and_then(Value::as_u64),
+        Some(2)
+    );
+}
+
+#[test]
+fn format_html_is_rejected_outside_report() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let result = run_cli(repo.path(), ["labels", "--format", "html"]);
+
+    assert_eq!(result.code, 1);
+    assert!(result.stderr.contains("does not support --format html"));
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{
+    create_task, create_task_with_args, init_repo, make_repo, run_cli, run_json,
+    run_json_with_stdin,
+};
+use serde_json::json;
+
+#[test]
+fn ids_resolves_prefixes_aliases_and_external_refs_from_stdin() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let form = create_task(repo.path(), "Login form");
+    let page = create_task(repo.path(), "Login page");
+    let tracked = create_task_with_args(repo.path(), "Tracked", &["--external-ref", "gh-123"]);
+    let form_alias =
+        run_json(repo.path(), ["show", form.as_str()]).envelope["data"]["task"]["alias"]
+            .as_str()
+            .expect("alias")
+            .to_string();
+
+    let result = run_json_with_stdin(
+        repo.path(),
+        ["ids", "--json"],
+        &format!("{}\n\n{}\ngh-123\nlogin\nnope-404\n", page, form_alias),
+    );
+    assert_eq!(result.cli.code, 0, "{:?}", result.envelope);
+    let data = &result.envelope["data"];
+    assert_eq!(data["resolved"], 3);
+    assert_eq!(data["unresolved"], 2);
+    let results = data["results"].as_array().expect("results");
+    assert_eq!(
+        results[0],
+        json!({"input": page, "status": "resolved", "id": page})
+    );
+    assert_eq!(results[1]["id"], json!(form));
+    assert_eq!(results[2]["id"], json!(tracked));
+    assert_eq!(results[3]["status"], "ambiguous");
+    let mut candidates: Vec<&str> = results[3]["candidates"]
+        .as_array()
+        .expect("candidates")
+        .iter()
+        .map(|candidate| candidate["id"].as_str().expect("id"))
+        .collect();
+    candidates.sort();
+    let mut expected = vec![form.as_str(), page.as_str()];
+    expected.sort();
+    assert_eq!(candidates, expected);
+    assert_eq!(
+        results[4],
+        json!({"input": "nope-404", "status": "not_found"})
+    );
+}
+
+#[test]
+fn ids_prints_tab_separated_lines_for_arguments() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Only task");
+
+    let human = run_cli(repo.path(), ["ids", id.as_str(), "missing-ref"]);
+    assert_eq!(human.code, 0, "{}", human.stderr);
+    assert_eq!(
+        human.stdout,
+        format!("{}\t{}\nmissing-ref\tNOT_FOUND\n", id, id)
+    );
+}
@@ -208,3 +208,32 @@ fn sync_branch_does_not_require_skill_action() {
         InitPlan::Wizard { .. } => panic!("expected non-interactive plan"),
     }
 }
+
+#[test]
+fn resolve_init_plan_carries_no_sync_branch_and_rejects_conflict() {
+    let context = InitResolutionContext {
+        raw_args: vec!["init".to_string(), "--no-sync-branch".to_string()],
+        is_tty: true,
+        json: false,
+    };
+    let options = InitCommandOptions {
+        no_sync_branch: true,
+        ..InitCommandOptions::default()
+    };
+
+    let plan = resolve_init_plan(&options, &context).expect("expected init plan");
+    let InitPlan::Wizard { seed, .. } = plan else {
+        panic!("expected wizard plan");
+    };
+    let input = run_init_wizard(seed, true).expect("expected wizard input");
+    assert!(input.no_sync_branch);
+    assert!(input.sync_branch.is_none());
+
+    let conflicting = InitCommandOptions {
+        sync_branch: Some("tasque-sync".to_string()),
+        no_sync_branch: true,
+        ..InitCommandOptions::default()
+    };
+    let error = resolve_init_plan(&conflicting, &context).expect_err("expected conflict");
+    assert_eq!(error.code, "VALIDATION_ERROR");
+}
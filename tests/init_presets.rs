@@ -0,0 +1,75 @@
+mod common;
+
+use common::{make_repo, run_json};
+use serde_json::json;
+
+const TEAM_PRESET: &str = r#"{
+  "labels": {"bug": "red", "Infra": "cyan"},
+  "epics": [{"title": "Backlog", "description": "Unsorted work", "priority": 3}],
+  "policies": [
+    {"name": "feature-needs-spec", "when": {"kind": "feature", "status": "in_progress"}, "require": ["spec"]}
+  ]
+}"#;
+
+#[test]
+fn preset_file_seeds_labels_epics_and_policies_idempotently() {
+    let repo = make_repo();
+    let preset = repo.path().join("team-preset.json");
+    std::fs::write(&preset, TEAM_PRESET).expect("write preset");
+    let preset = preset.to_string_lossy().to_string();
+
+    let first = run_json(
+        repo.path(),
+        ["init", "--no-wizard", "--preset-file", preset.as_str()],
+    );
+    assert_eq!(first.cli.code, 0, "{:?}", first.envelope);
+    let seeded = &first.envelope["data"]["seeded"];
+    assert_eq!(seeded["labels"], json!(["bug", "infra"]));
+    assert_eq!(seeded["policies"], json!(["feature-needs-spec"]));
+    let epic_id = seeded["epics"][0].as_str().expect("epic id").to_string();
+
+    let config: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(repo.path().join(".tasque/config.json")).expect("config"),
+    )
+    .expect("config json");
+    assert_eq!(
+        config["label_colors"],
+        json!({"bug": "red", "infra": "cyan"})
+    );
+    assert_eq!(config["policies"][0]["name"], "feature-needs-spec");
+
+    let epic = run_json(repo.path(), ["show", epic_id.as_str()]);
+    assert_eq!(epic.envelope["data"]["task"]["kind"], "epic");
+    assert_eq!(epic.envelope["data"]["task"]["title"], "Backlog");
+    assert_eq!(epic.envelope["data"]["task"]["priority"], 3);
+
+    let second = run_json(
+        repo.path(),
+        ["init", "--no-wizard", "--preset-file", preset.as_str()],
+    );
+    assert_eq!(second.cli.code, 0, "{:?}", second.envelope);
+    let reseeded = &second.envelope["data"]["seeded"];
+    assert_eq!(reseeded["labels"], json!([]));
+    assert_eq!(reseeded["policies"], json!([]));
+    assert_eq!(reseeded["epics"], json!([epic_id]));
+}
+
+#[test]
+fn preset_file_with_invalid_policy_is_rejected_before_init() {
+    let repo = make_repo();
+    let preset = repo.path().join("bad-preset.json");
+    std::fs::write(
+        &preset,
+        r#"{"policies": [{"name": "no-trigger", "when": {}, "require": ["spec"]}]}"#,
+    )
+    .expect("write preset");
+    let preset = preset.to_string_lossy().to_string();
+
+    let result = run_json(
+        repo.path(),
+        ["init", "--no-wizard", "--preset-file", preset.as_str()],
+    );
+    assert_eq!(result.cli.code, 1, "{:?}", result.envelope);
+    assert_eq!(result.envelope["error"]["code"], "VALIDATION_ERROR");
+    assert!(!repo.path().join(".tasque").exists());
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json};
+use serde_json::json;
+
+#[test]
+fn label_color_is_stored_listed_and_attached_to_task_json() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Colored");
+    run_json(repo.path(), ["label", id.as_str(), "backend"]);
+
+    let set = run_json(repo.path(), ["label", "color", "backend", "Blue"]);
+    assert_eq!(set.cli.code, 0, "{:?}", set.envelope);
+    assert_eq!(
+        set.envelope["data"],
+        json!({"label": "backend", "color": "blue"})
+    );
+
+    let config: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(repo.path().join(".tasque/config.json")).expect("config"),
+    )
+    .expect("config json");
+    assert_eq!(config["label_colors"], json!({"backend": "blue"}));
+
+    let labels = run_json(repo.path(), ["labels"]);
+    assert_eq!(
+        labels.envelope["data"]["labels"],
+        json!([{"label": "backend", "count": 1, "color": "blue"}])
+    );
+
+    let found = run_json(repo.path(), ["find", "open"]);
+    assert_eq!(
+        found.envelope["data"]["label_colors"],
+        json!({"backend": "blue"})
+    );
+
+    let human = run_cli(repo.path(), ["find", "open"]);
+    assert!(human.stdout.contains("LABELS"), "{}", human.stdout);
+    assert!(human.stdout.contains("[backend]"), "{}", human.stdout);
+
+    let cleared = run_json(repo.path(), ["label", "color", "backend", "--clear"]);
+    assert_eq!(cleared.envelope["data"]["color"], serde_json::Value::Null);
+    let found = run_json(repo.path(), ["find", "open"]);
+    assert!(found.envelope["data"].get("label_colors").is_none());
+}
+
+#[test]
+fn label_color_rejects_unknown_colors() {
+    let repo = make_repo();
+    init_repo(repo.path());
+
+    let result = run_json(repo.path(), ["label", "color", "backend", "purple"]);
+    assert_eq!(result.cli.code, 1);
+    assert_eq!(result.envelope["error"]["code"], "VALIDATION_ERROR");
+}
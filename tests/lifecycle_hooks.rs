@@ -0,0 +1,141 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{create_task, init_repo, ok_data, run_json, set_config_key};
+use serde_json::Value;
+
+#[test]
+fn advisory_hooks_receive_event_payload_on_stdin() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "hooks",
+        serde_json::json!({
+            "on_create": "cat > created.json",
+            "on_status_change": "printf '%s %s' \"$TSQ_HOOK\" \"$TSQ_EVENT_TYPE\" > status.txt; exit 3"
+        }),
+    );
+
+    let id = create_task(repo.path(), "Hooked task");
+    let created: Value = serde_json::from_str(
+        &std::fs::read_to_string(repo.path().join("created.json")).expect("hook output"),
+    )
+    .expect("hook payload json");
+    assert_eq!(created["type"], "task.created");
+    assert_eq!(created["task_id"], id.as_str());
+    assert_eq!(created["payload"]["title"], "Hooked task");
+
+    let started = run_json(repo.path(), ["start", id.as_str()]);
+    assert_eq!(started.cli.code, 0, "stderr: {}", started.cli.stderr);
+    assert!(
+        started
+            .cli
+            .stderr
+            .contains("advisory hook on_status_change failed"),
+        "stderr: {}",
+        started.cli.stderr
+    );
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("status.txt")).expect("status hook output"),
+        "on_status_change task.status_set"
+    );
+}
+
+#[test]
+fn blocking_hook_failure_aborts_mutation() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Guarded task");
+    set_config_key(
+        repo.path(),
+        "hooks",
+        serde_json::json!({
+            "on_close": {"command": "echo 'tests must pass' >&2; exit 1", "mode": "blocking"}
+        }),
+    );
+
+    let closed = run_json(repo.path(), ["done", id.as_str()]);
+    assert_eq!(closed.cli.code, 1);
+    assert_eq!(closed.envelope["error"]["code"], "HOOK_FAILED");
+    assert_eq!(closed.envelope["error"]["details"]["hook"], "on_close");
+    assert!(
+        closed.envelope["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("tests must pass")
+    );
+
+    let shown = run_json(repo.path(), ["show", id.as_str()]);
+    assert_eq!(ok_data(&shown.envelope)["task"]["status"], "open");
+}
+
+#[test]
+fn blocking_hooks_can_read_but_not_write() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let tsq = env!("CARGO_BIN_EXE_tsq");
+    set_config_key(
+        repo.path(),
+        "hooks",
+        serde_json::json!({
+            "on_create": {
+                "command": format!(
+                    "'{tsq}' find open --json > read.json; '{tsq}' create Nested --json > write.json; exit 0"
+                ),
+                "mode": "blocking"
+            }
+        }),
+    );
+
+    let created = run_json(repo.path(), ["create", "Outer"]);
+    assert_eq!(created.cli.code, 0, "stderr: {}", created.cli.stderr);
+
+    let read: Value = serde_json::from_str(
+        &std::fs::read_to_string(repo.path().join("read.json")).expect("read output"),
+    )
+    .expect("read json");
+    assert_eq!(read["ok"], true);
+    let write: Value = serde_json::from_str(
+        &std::fs::read_to_string(repo.path().join("write.json")).expect("write output"),
+    )
+    .expect("write json");
+    assert_eq!(write["error"]["code"], "HOOK_WRITE_FORBIDDEN");
+}
+
+#[test]
+fn large_payloads_do_not_deadlock_hooks_that_write_before_reading() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "hooks",
+        serde_json::json!({
+            "on_create": {
+                "command": "head -c 262144 /dev/zero; cat > created.json",
+                "mode": "blocking"
+            }
+        }),
+    );
+
+    // Keep the description inline so the hook sees it in the event payload.
+    set_config_key(
+        repo.path(),
+        "payload_limits",
+        serde_json::json!({"overflow_bytes": 512 * 1024}),
+    );
+    let description = "x".repeat(100 * 1024);
+    let created = run_json(
+        repo.path(),
+        [
+            "create",
+            "Large payload",
+            "--description",
+            description.as_str(),
+        ],
+    );
+    assert_eq!(created.cli.code, 0, "stderr: {}", created.cli.stderr);
+    let payload = std::fs::read_to_string(repo.path().join("created.json")).expect("hook stdin");
+    assert!(payload.len() > description.len());
+}
@@ -1,8 +1,8 @@
 mod common;
 
 use common::{
-    assert_validation_error, create_task, ids_from_task_list, init_repo, label_add, run_json,
-    run_json_explicit,
+    assert_validation_error, create_task, create_task_with_args, ids_from_task_list, init_repo,
+    label_add, run_json, run_json_explicit,
 };
 use serde_json::Value;
 
@@ -190,6 +190,166 @@ fn search_rejects_invalid_dep_type_direction_value() {
     assert_validation_error(&result);
 }
 
+#[test]
+fn search_supports_priority_comparisons() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let urgent = create_task_with_args(repo.path(), "Urgent", &["--priority", "0"]);
+    let high = create_task_with_args(repo.path(), "High", &["--priority", "1"]);
+    let low = create_task_with_args(repo.path(), "Low", &["--priority", "3"]);
+
+    let result = run_json(repo.path(), ["find", "search", "priority<=1"]);
+    assert_eq!(result.cli.code, 0);
+    assert_eq!(
+        ids_from_task_list(&result.envelope),
+        vec![urgent, high.clone()]
+    );
+
+    let result = run_json(repo.path(), ["find", "search", "priority>1"]);
+    assert_eq!(ids_from_task_list(&result.envelope), vec![low]);
+
+    let result = run_json(repo.path(), ["find", "search", "priority:1"]);
+    assert_eq!(ids_from_task_list(&result.envelope), vec![high]);
+
+    let result = run_json(repo.path(), ["find", "search", "priority<high"]);
+    assert_eq!(result.cli.code, 1);
+    assert_validation_error(&result);
+}
+
+#[test]
+fn list_and_search_compare_priority_and_estimate_ranges() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let urgent = create_task_with_args(repo.path(), "Urgent", &["--priority", "0"]);
+    let sized = create_task_with_args(repo.path(), "Sized", &["--priority", "1"]);
+    let large = create_task_with_args(repo.path(), "Large", &["--priority", "2"]);
+    label_add(repo.path(), &sized, "estimate:3");
+    label_add(repo.path(), &large, "estimate:8d");
+
+    let result = run_json(
+        repo.path(),
+        ["find", "open", "--priority", "<=1", "--estimate", "none"],
+    );
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stdout);
+    assert_eq!(ids_from_task_list(&result.envelope), vec![urgent.clone()]);
+
+    let result = run_json(repo.path(), ["find", "open", "--priority", "1..2"]);
+    assert_eq!(
+        ids_from_task_list(&result.envelope),
+        vec![sized.clone(), large.clone()]
+    );
+
+    let result = run_json(repo.path(), ["find", "search", "estimate>5"]);
+    assert_eq!(ids_from_task_list(&result.envelope), vec![large]);
+
+    let result = run_json(
+        repo.path(),
+        ["find", "search", "estimate:2..5 priority:0..1"],
+    );
+    assert_eq!(ids_from_task_list(&result.envelope), vec![sized]);
+
+    for (flag, bad) in [
+        ("--priority", "0..4"),
+        ("--priority", "2..1"),
+        ("--estimate", "big"),
+        ("--estimate", "1 2"),
+    ] {
+        let result = run_json(repo.path(), ["find", "open", flag, bad]);
+        assert_eq!(result.cli.code, 1, "{flag} {bad} should be rejected");
+        assert_validation_error(&result);
+    }
+}
+
+#[test]
+fn count_by_returns_groups_instead_of_tasks() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let bug = create_task(repo.path(), "Crash on save");
+    let both = create_task(repo.path(), "Slow sync");
+    create_task(repo.path(), "Unlabeled");
+    label_add(repo.path(), &bug, "bug");
+    label_add(repo.path(), &both, "bug");
+    label_add(repo.path(), &both, "perf");
+    run_json(repo.path(), ["start", &both]);
+
+    let result = run_json(
+        repo.path(),
+        ["find", "status", "open", "--count-by", "label"],
+    );
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stdout);
+    let data = &result.envelope["data"];
+    assert!(data.get("tasks").is_none());
+    assert_eq!(data["count_by"], "label");
+    assert_eq!(data["total"], 2);
+    assert_eq!(
+        data["groups"],
+        serde_json::json!([{"key": "bug", "count": 1}, {"key": null, "count": 1}])
+    );
+
+    let result = run_json(
+        repo.path(),
+        ["find", "search", "label:bug | count by status"],
+    );
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stdout);
+    assert_eq!(
+        result.envelope["data"]["groups"],
+        serde_json::json!([{"key": "in_progress", "count": 1}, {"key": "open", "count": 1}])
+    );
+
+    let result = run_json(
+        repo.path(),
+        ["find", "search", "label:bug", "--count-by", "label"],
+    );
+    assert_eq!(
+        result.envelope["data"]["groups"],
+        serde_json::json!([{"key": "bug", "count": 2}, {"key": "perf", "count": 1}])
+    );
+
+    for query in ["label:bug | count by color", "label:bug | sort by title"] {
+        let result = run_json(repo.path(), ["find", "search", query]);
+        assert_eq!(result.cli.code, 1, "{query} should be rejected");
+        assert_validation_error(&result);
+    }
+}
+
+#[test]
+fn search_supports_relative_dates() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let open = create_task(repo.path(), "Still open");
+    let closed = create_task(repo.path(), "Wrapped up");
+    run_json(repo.path(), ["done", &closed]);
+
+    let result = run_json(repo.path(), ["find", "search", "created>=-1w"]);
+    assert_eq!(result.cli.code, 0);
+    assert_eq!(
+        ids_from_task_list(&result.envelope),
+        vec![open.clone(), closed.clone()]
+    );
+
+    let result = run_json(repo.path(), ["find", "search", "updated<-14d"]);
+    assert_eq!(ids_from_task_list(&result.envelope), Vec::<String>::new());
+
+    let result = run_json(repo.path(), ["find", "search", "closed:this-month"]);
+    assert_eq!(ids_from_task_list(&result.envelope), vec![closed]);
+
+    let result = run_json(
+        repo.path(),
+        ["find", "search", "created:today -closed:today"],
+    );
+    assert_eq!(ids_from_task_list(&result.envelope), vec![open]);
+
+    for bad in ["updated<-14y", "updated<-d", "closed:next-month"] {
+        let result = run_json(repo.path(), ["find", "search", bad]);
+        assert_eq!(result.cli.code, 1, "{bad} should be rejected");
+        assert_validation_error(&result);
+    }
+}
+
 #[test]
 fn search_matches_incoming_and_outgoing_dependency_types() {
     let repo = common::make_repo();
@@ -0,0 +1,37 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli, run_json};
+
+#[test]
+fn listings_report_note_counts_unless_with_notes_is_set() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Chatty");
+    run_cli(repo.path(), ["note", &id, "first"]);
+    run_cli(repo.path(), ["note", &id, "second"]);
+
+    let listed = run_json(repo.path(), ["find", "open"]);
+    let task = &listed.envelope["data"]["tasks"][0];
+    assert_eq!(task["note_count"], 2);
+    assert!(task.get("notes").is_none());
+
+    let full = run_json(repo.path(), ["find", "open", "--with-notes"]);
+    let task = &full.envelope["data"]["tasks"][0];
+    assert_eq!(task["notes"].as_array().unwrap().len(), 2);
+    assert!(task.get("note_count").is_none());
+
+    let tree = run_json(repo.path(), ["find", "open", "--tree"]);
+    assert_eq!(tree.envelope["data"]["tree"][0]["task"]["note_count"], 2);
+
+    let watch = run_json(repo.path(), ["watch", "--once", "--flat"]);
+    assert_eq!(watch.envelope["data"]["tasks"][0]["note_count"], 2);
+
+    let shown = run_json(repo.path(), ["show", &id]);
+    assert_eq!(
+        shown.envelope["data"]["task"]["notes"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{init_repo, make_repo, run_json, set_config_key};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use sysinfo::System;
+
+fn write_lock(repo: &Path, pid: u32, created_at: &str) {
+    let host = System::host_name().unwrap_or_else(|| "unknown".to_string());
+    fs::write(
+        repo.join(".tasque/.lock"),
+        format!(
+            "{}\n",
+            json!({"host": host, "pid": pid, "created_at": created_at})
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn lock_status_and_break_handle_stale_and_live_locks() {
+    let repo = make_repo();
+    init_repo(repo.path());
+
+    let idle = run_json(repo.path(), ["lock", "status"]);
+    assert_eq!(idle.envelope["data"]["locked"], false);
+    assert_eq!(idle.envelope["data"]["ttl_ms"], 30_000);
+
+    write_lock(repo.path(), u32::MAX, "2020-01-01T00:00:00Z");
+    let stale = run_json(repo.path(), ["lock", "status"]);
+    assert_eq!(stale.envelope["data"]["locked"], true);
+    assert_eq!(stale.envelope["data"]["owner_alive"], false);
+    assert_eq!(stale.envelope["data"]["stale"], true);
+    let broken = run_json(repo.path(), ["lock", "break"]);
+    assert_eq!(broken.cli.code, 0, "stderr:\n{}", broken.cli.stderr);
+    assert_eq!(broken.envelope["data"]["broken"], true);
+    assert!(!repo.path().join(".tasque/.lock").exists());
+
+    write_lock(repo.path(), std::process::id(), "2020-01-01T00:00:00Z");
+    let held = run_json(repo.path(), ["lock", "break"]);
+    assert_eq!(held.envelope["error"]["code"], "LOCK_HELD");
+    assert!(repo.path().join(".tasque/.lock").exists());
+    let forced = run_json(repo.path(), ["lock", "break", "--force"]);
+    assert_eq!(forced.envelope["data"]["forced"], true);
+    assert!(!repo.path().join(".tasque/.lock").exists());
+}
+
+#[test]
+fn lock_ttl_from_config_delays_takeover_of_a_dead_writer() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "lock_ttl_ms", json!(3_600_000));
+
+    let recent = (chrono::Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+    write_lock(repo.path(), u32::MAX, &recent);
+    let status = run_json(repo.path(), ["lock", "status"]);
+    assert_eq!(status.envelope["data"]["ttl_ms"], 3_600_000);
+    assert_eq!(status.envelope["data"]["stale"], false);
+}
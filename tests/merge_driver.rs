@@ -112,7 +112,7 @@ fn test_merge_driver_disjoint_events() {
         merged.len()
     );
 
-    // Verify stable source union order: ancestor, ours-only, theirs-only.
+    // Equal timestamps order by event ID.
     let ids: Vec<&str> = merged
         .iter()
         .map(|v| v.get("id").unwrap().as_str().unwrap())
@@ -307,7 +307,9 @@ fn test_merge_driver_preserves_causal_source_order_when_ids_sort_backwards() {
         .iter()
         .map(|v| v.get("id").unwrap().as_str().unwrap())
         .collect();
-    assert_eq!(ids, vec!["02CREATE", "01UPDATE", "03THEIRS"]);
+    // The earlier theirs event slots in by timestamp, but the update still
+    // follows the create it depends on even though its ID sorts first.
+    assert_eq!(ids, vec!["02CREATE", "03THEIRS", "01UPDATE"]);
 }
 
 #[test]
@@ -343,3 +345,49 @@ fn test_merge_driver_mixed_event_types() {
     let merged = read_jsonl(&ours_path);
     assert_eq!(merged.len(), 3);
 }
+
+#[test]
+fn test_merge_driver_orders_concurrent_appends_by_timestamp() {
+    let repo = make_repo();
+    let dir = repo.path();
+
+    let base_events = vec![make_event("01AAA", "base")];
+    let mut ours_events = base_events.clone();
+    ours_events.push(make_update_event("01ZZZ", "tsq-01AAA", "ours late"));
+    let mut theirs_events = base_events.clone();
+    theirs_events.push(make_status_event("01BBB", "tsq-01AAA", "in_progress"));
+
+    let ancestor_path = dir.join("ancestor.jsonl");
+    let ours_path = dir.join("ours.jsonl");
+    let theirs_path = dir.join("theirs.jsonl");
+    let mut merged_ids = Vec::new();
+
+    // Merging in either direction yields the same (ts, id) ordered log.
+    for (ours, theirs) in [
+        (&ours_events, &theirs_events),
+        (&theirs_events, &ours_events),
+    ] {
+        write_jsonl(&ancestor_path, &base_events);
+        write_jsonl(&ours_path, ours);
+        write_jsonl(&theirs_path, theirs);
+        let result = run_cli(
+            dir,
+            [
+                "merge-driver",
+                ancestor_path.to_str().unwrap(),
+                ours_path.to_str().unwrap(),
+                theirs_path.to_str().unwrap(),
+            ],
+        );
+        assert_eq!(result.code, 0, "stderr: {}", result.stderr);
+        merged_ids.push(
+            read_jsonl(&ours_path)
+                .iter()
+                .map(|v| v.get("id").unwrap().as_str().unwrap().to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    assert_eq!(merged_ids[0], vec!["01AAA", "01BBB", "01ZZZ"]);
+    assert_eq!(merged_ids[0], merged_ids[1]);
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{create_task_with_args, init_repo, label_add, make_repo, run_json};
+use serde_json::Value;
+
+fn target_update_payload(repo: &std::path::Path, target: &str) -> Value {
+    std::fs::read_to_string(repo.join(".tasque/events.jsonl"))
+        .expect("events")
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).expect("event json"))
+        .rfind(|event| event["type"] == "task.updated" && event["task_id"] == target)
+        .expect("target update event")["payload"]
+        .clone()
+}
+
+#[test]
+fn merge_strategies_fold_source_fields_into_the_target() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let target = create_task_with_args(
+        repo.path(),
+        "Login bug",
+        &["-p", "2", "--description", "Fails on Safari"],
+    );
+    let source = create_task_with_args(
+        repo.path(),
+        "Cannot log in",
+        &["-p", "0", "--description", "Also fails on Firefox"],
+    );
+    label_add(repo.path(), &target, "auth");
+    label_add(repo.path(), &source, "browser");
+
+    let merged = run_json(
+        repo.path(),
+        [
+            "merge",
+            &source,
+            "--into",
+            &target,
+            "--strategy",
+            "description=concat,labels=union",
+            "--strategy",
+            "priority=min",
+        ],
+    );
+    assert_eq!(merged.cli.code, 0, "stderr:\n{}", merged.cli.stderr);
+    assert_eq!(
+        merged.envelope["data"]["strategy"],
+        serde_json::json!({"description": "concat", "labels": "union", "priority": "min"})
+    );
+
+    let task = &run_json(repo.path(), ["show", &target]).envelope["data"]["task"];
+    assert_eq!(
+        task["description"],
+        "Fails on Safari\n\nAlso fails on Firefox"
+    );
+    assert_eq!(task["labels"], serde_json::json!(["auth", "browser"]));
+    assert_eq!(task["priority"], 0);
+
+    let payload = target_update_payload(repo.path(), &target);
+    assert_eq!(payload["merge_strategy"]["priority"], "min");
+    assert_eq!(payload["merged_from"], serde_json::json!([source]));
+}
+
+#[test]
+fn merge_strategy_rejects_unsupported_field_pairs() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let target = create_task_with_args(repo.path(), "Target", &[]);
+    let source = create_task_with_args(repo.path(), "Source", &[]);
+
+    let mismatched = run_json(
+        repo.path(),
+        [
+            "merge",
+            &source,
+            "--into",
+            &target,
+            "--strategy",
+            "labels=min",
+        ],
+    );
+    assert_eq!(mismatched.envelope["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(
+        mismatched.envelope["error"]["details"]["allowed"],
+        serde_json::json!(["keep", "union"])
+    );
+
+    let unknown = run_json(
+        repo.path(),
+        [
+            "merge",
+            &source,
+            "--into",
+            &target,
+            "--strategy",
+            "title=concat",
+        ],
+    );
+    assert_eq!(unknown.envelope["error"]["code"], "VALIDATION_ERROR");
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::{create_task_with_args, init_repo, ok_data, run_json, set_config_key};
+use serde_json::Value;
+use std::path::Path;
+
+fn read_events(repo: &Path) -> String {
+    std::fs::read_to_string(repo.join(".tasque").join("events.jsonl")).expect("events")
+}
+
+fn show_task(repo: &Path, id: &str) -> Value {
+    let shown = run_json(repo, ["show", id]);
+    ok_data(&shown.envelope)["task"].clone()
+}
+
+#[test]
+fn parents_roll_up_once_their_last_child_closes() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "status_workflow",
+        serde_json::json!({
+            "statuses": [
+                "open", "in_progress", "ready_to_close",
+                "blocked", "deferred", "closed", "canceled"
+            ]
+        }),
+    );
+    set_config_key(
+        repo.path(),
+        "parent_completion",
+        serde_json::json!({
+            "status": "ready_to_close",
+            "label": "ready-to-close",
+            "note": "All children closed"
+        }),
+    );
+    let epic = create_task_with_args(repo.path(), "Billing", &["--kind", "epic"]);
+    let first = create_task_with_args(repo.path(), "Invoices", &["--parent", &epic]);
+    let second = create_task_with_args(repo.path(), "Refunds", &["--parent", &epic]);
+
+    run_json(repo.path(), ["done", &first]);
+    assert_eq!(show_task(repo.path(), &epic)["status"], "open");
+
+    run_json(repo.path(), ["cancel", &second]);
+    let parent = show_task(repo.path(), &epic);
+    assert_eq!(parent["status"], "ready_to_close");
+    assert_eq!(parent["labels"], serde_json::json!(["ready-to-close"]));
+    assert_eq!(parent["notes"][0]["text"], "All children closed");
+
+    // A parent moved on by hand is not pulled back.
+    let moved = run_json(repo.path(), ["edit", &epic, "--status", "in_progress"]);
+    assert_eq!(moved.cli.code, 0, "{}", moved.cli.stdout);
+    run_json(repo.path(), ["find", "open"]);
+    assert_eq!(show_task(repo.path(), &epic)["status"], "in_progress");
+}
+
+#[test]
+fn parent_completion_applies_to_existing_histories() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let feature = create_task_with_args(repo.path(), "Search", &["--kind", "feature"]);
+    let child = create_task_with_args(repo.path(), "Indexer", &["--parent", &feature]);
+    run_json(repo.path(), ["done", &child]);
+    assert_eq!(
+        show_task(repo.path(), &feature)["labels"],
+        serde_json::json!([])
+    );
+
+    set_config_key(
+        repo.path(),
+        "parent_completion",
+        serde_json::json!({"label": "ready-to-close"}),
+    );
+    let events_before = read_events(repo.path());
+    let listed = run_json(repo.path(), ["find", "open"]);
+    assert_eq!(listed.cli.code, 0, "{}", listed.cli.stdout);
+    show_task(repo.path(), &feature);
+    assert_eq!(
+        read_events(repo.path()),
+        events_before,
+        "reads must not write"
+    );
+
+    // The next write records the roll-up for the older history.
+    create_task_with_args(repo.path(), "Unrelated", &[]);
+    let parent = show_task(repo.path(), &feature);
+    assert_eq!(parent["status"], "open");
+    assert_eq!(parent["labels"], serde_json::json!(["ready-to-close"]));
+
+    set_config_key(repo.path(), "parent_completion", serde_json::json!({}));
+    let rejected = run_json(repo.path(), ["find", "open"]);
+    assert_ne!(rejected.cli.code, 0);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{
+    create_task, init_repo, make_repo, ok_data, run_json, run_json_with_stdin, set_config_key,
+};
+use std::path::Path;
+
+fn blob_files(repo: &Path) -> Vec<std::path::PathBuf> {
+    match std::fs::read_dir(repo.join(".tasque").join("blobs")) {
+        Ok(entries) => entries.map(|entry| entry.expect("entry").path()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[test]
+fn oversized_notes_overflow_to_blobs_and_read_back_in_full() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "payload_limits",
+        serde_json::json!({"max_bytes": 4096, "overflow_bytes": 256}),
+    );
+    let id = create_task(repo.path(), "Long notes");
+    let body = "stack frame\n".repeat(100);
+
+    let noted = run_json_with_stdin(repo.path(), ["note", &id, "--stdin", "--json"], &body);
+    assert_eq!(noted.cli.code, 0, "{}", noted.cli.stderr);
+    run_json(repo.path(), ["note", &id, "short"]);
+
+    let blobs = blob_files(repo.path());
+    assert_eq!(blobs.len(), 1);
+    let log = std::fs::read_to_string(repo.path().join(".tasque").join("events.jsonl"))
+        .expect("read events");
+    assert!(log.contains("text_blob"));
+    assert!(log.len() < body.len() + 1024, "{}", log);
+
+    let shown = run_json(repo.path(), ["show", &id]);
+    let notes = ok_data(&shown.envelope)["task"]["notes"]
+        .as_array()
+        .expect("notes")
+        .clone();
+    assert_eq!(notes[0]["text"], body.trim_end());
+    assert_eq!(notes[1]["text"], "short");
+
+    // A missing blob degrades to the stored preview instead of failing reads.
+    std::fs::remove_file(&blobs[0]).expect("remove blob");
+    let shown = run_json(repo.path(), ["show", &id]);
+    assert_eq!(shown.cli.code, 0, "{}", shown.cli.stderr);
+    let preview = ok_data(&shown.envelope)["task"]["notes"][0]["text"]
+        .as_str()
+        .expect("preview")
+        .to_string();
+    assert!(preview.ends_with('…'));
+    assert!(preview.len() < body.len());
+}
+
+#[test]
+fn bodies_over_max_bytes_are_rejected() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "payload_limits",
+        serde_json::json!({"max_bytes": 512, "overflow_bytes": 256}),
+    );
+    let id = create_task(repo.path(), "Bounded");
+
+    let rejected = run_json_with_stdin(
+        repo.path(),
+        ["note", &id, "--stdin", "--json"],
+        &"x".repeat(600),
+    );
+    assert_eq!(rejected.cli.code, 1);
+    assert_eq!(rejected.envelope["error"]["code"], "PAYLOAD_TOO_LARGE");
+    assert_eq!(rejected.envelope["error"]["details"]["field"], "text");
+    assert_eq!(rejected.envelope["error"]["details"]["max_bytes"], 512);
+
+    let shown = run_json(repo.path(), ["show", &id]);
+    assert_eq!(
+        ok_data(&shown.envelope)["task"]["notes"]
+            .as_array()
+            .map(Vec::len),
+        Some(0)
+    );
+}
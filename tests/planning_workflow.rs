@@ -0,0 +1,75 @@
+mod common;
+
+use common::{create_task, ids_from_task_list, init_repo, ok_data, run_json, set_config_key};
+use serde_json::Value;
+
+fn review_workflow() -> Value {
+    serde_json::json!({
+        "states": ["needs_planning", "drafted", "reviewed", "planned"],
+        "transitions": {
+            "needs_planning": ["drafted"],
+            "drafted": ["reviewed", "needs_planning"],
+            "reviewed": ["planned", "drafted"]
+        }
+    })
+}
+
+#[test]
+fn configured_planning_states_follow_transitions_and_feed_lanes() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "planning_workflow", review_workflow());
+    set_config_key(
+        repo.path(),
+        "lanes",
+        serde_json::json!([{"name": "review", "when": {"planning_state": ["reviewed"]}}]),
+    );
+    let id = create_task(repo.path(), "Payment retries");
+
+    let skipped = run_json(repo.path(), ["edit", &id, "--planning", "planned"]);
+    assert_eq!(skipped.cli.code, 1);
+    assert_eq!(skipped.envelope["error"]["code"], "INVALID_TRANSITION");
+    assert_eq!(
+        skipped.envelope["error"]["details"]["allowed"],
+        serde_json::json!(["drafted"])
+    );
+
+    let unknown = run_json(repo.path(), ["edit", &id, "--planning", "approved"]);
+    assert_eq!(unknown.cli.code, 1);
+    assert_eq!(unknown.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    let drafted = run_json(repo.path(), ["edit", &id, "--planning", "drafted"]);
+    assert_eq!(drafted.cli.code, 0, "{}", drafted.cli.stdout);
+    assert_eq!(
+        ok_data(&drafted.envelope)["task"]["planning_state"],
+        "drafted"
+    );
+    let planning = run_json(repo.path(), ["find", "ready", "--lane", "planning"]);
+    assert_eq!(ids_from_task_list(&planning.envelope), vec![id.clone()]);
+
+    run_json(repo.path(), ["edit", &id, "--planning", "reviewed"]);
+    let review = run_json(repo.path(), ["find", "ready", "--lane", "review"]);
+    assert_eq!(ids_from_task_list(&review.envelope), vec![id.clone()]);
+    let filtered = run_json(repo.path(), ["find", "open", "--planning", "reviewed"]);
+    assert_eq!(ids_from_task_list(&filtered.envelope), vec![id.clone()]);
+
+    let planned = run_json(repo.path(), ["planned", &id]);
+    assert_eq!(planned.cli.code, 0, "{}", planned.cli.stdout);
+    let coding = run_json(repo.path(), ["find", "ready", "--lane", "coding"]);
+    assert_eq!(ids_from_task_list(&coding.envelope), vec![id]);
+}
+
+#[test]
+fn planning_workflow_must_keep_builtin_states() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "planning_workflow",
+        serde_json::json!({"states": ["needs_planning", "drafted"]}),
+    );
+
+    let listed = run_json(repo.path(), ["find", "open"]);
+    assert_ne!(listed.cli.code, 0);
+    assert_eq!(listed.envelope["ok"], false);
+}
@@ -0,0 +1,64 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{init_repo, run_cli_with_env};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+fn install_plugin(dir: &Path, name: &str, body: &str) {
+    let path = dir.join(format!("tsq-{}", name));
+    std::fs::write(&path, body).expect("write plugin");
+    let mut permissions = std::fs::metadata(&path)
+        .expect("plugin metadata")
+        .permissions();
+    permissions.set_mode(0o755);
+    std::fs::set_permissions(&path, permissions).expect("chmod plugin");
+}
+
+fn path_with(dir: &Path) -> String {
+    let existing = std::env::var("PATH").unwrap_or_default();
+    format!("{}:{}", dir.display(), existing)
+}
+
+#[test]
+fn unknown_subcommand_runs_plugin_with_context_env() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let bin_dir = tempfile::tempdir().expect("bin dir");
+    install_plugin(
+        bin_dir.path(),
+        "digest",
+        "#!/bin/sh\nprintf '%s|%s|%s|%s\\n' \"$TSQ_ACTOR\" \"$TSQ_FORMAT\" \"$TSQ_SCHEMA_VERSION\" \"$*\"\nprintf '%s' \"$TSQ_REPO_ROOT\" > root.txt\nexit 7\n",
+    );
+
+    let result = run_cli_with_env(
+        repo.path(),
+        ["--json", "digest", "weekly", "--team", "core"],
+        &[("PATH", &path_with(bin_dir.path()))],
+    );
+
+    assert_eq!(result.code, 7, "stderr: {}", result.stderr);
+    assert_eq!(result.stdout.trim(), "rust-test|json|1|weekly --team core");
+    let root = std::fs::read_to_string(repo.path().join("root.txt")).expect("root file");
+    assert_eq!(
+        std::fs::canonicalize(root).expect("canonical root"),
+        std::fs::canonicalize(repo.path()).expect("canonical repo")
+    );
+}
+
+#[test]
+fn unknown_subcommand_without_plugin_is_usage_error() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let bin_dir = tempfile::tempdir().expect("bin dir");
+
+    let result = run_cli_with_env(
+        repo.path(),
+        ["definitely-not-a-command"],
+        &[("PATH", &path_with(bin_dir.path()))],
+    );
+
+    assert_ne!(result.code, 0);
+    assert!(result.stderr.contains("unrecognized subcommand"));
+}
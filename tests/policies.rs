@@ -0,0 +1,86 @@
+mod common;
+
+use common::{create_task, create_task_with_args, init_repo, ok_data, run_json, set_config_key};
+
+#[test]
+fn feature_cannot_leave_needs_planning_without_required_metadata() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "policies",
+        serde_json::json!([{
+            "name": "feature-planning",
+            "when": {"kind": "feature", "planning_state": "planned"},
+            "require": ["label", "label:estimate:*"]
+        }]),
+    );
+    let feature = create_task_with_args(repo.path(), "Search revamp", &["--kind", "feature"]);
+    let task = create_task(repo.path(), "Small fix");
+
+    let blocked = run_json(repo.path(), ["planned", feature.as_str()]);
+    assert_eq!(blocked.cli.code, 1);
+    let error = &blocked.envelope["error"];
+    assert_eq!(error["code"], "POLICY_VIOLATION");
+    assert_eq!(error["details"]["policy"], "feature-planning");
+    assert_eq!(
+        error["details"]["missing"],
+        serde_json::json!(["label", "label:estimate:*"])
+    );
+    assert_eq!(
+        error["details"]["hints"][1],
+        format!("tsq label {} estimate:<value>", feature)
+    );
+
+    let planned_task = run_json(repo.path(), ["planned", task.as_str()]);
+    assert_eq!(
+        planned_task.cli.code, 0,
+        "stderr: {}",
+        planned_task.cli.stderr
+    );
+
+    let labeled = run_json(repo.path(), ["label", feature.as_str(), "estimate:3d"]);
+    assert_eq!(labeled.cli.code, 0, "stderr: {}", labeled.cli.stderr);
+    let planned = run_json(repo.path(), ["planned", feature.as_str()]);
+    assert_eq!(planned.cli.code, 0, "stderr: {}", planned.cli.stderr);
+    assert_eq!(
+        ok_data(&planned.envelope)["task"]["planning_state"],
+        "planned"
+    );
+}
+
+#[test]
+fn close_requires_reason_policy_accepts_reason_or_note() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "policies",
+        serde_json::json!([{
+            "name": "close-reason",
+            "when": {"status": "closed"},
+            "require": ["reason"]
+        }]),
+    );
+    let first = create_task(repo.path(), "First");
+    let second = create_task(repo.path(), "Second");
+
+    let blocked = run_json(repo.path(), ["done", first.as_str()]);
+    assert_eq!(blocked.cli.code, 1);
+    assert_eq!(blocked.envelope["error"]["code"], "POLICY_VIOLATION");
+    assert!(
+        blocked.envelope["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("status=closed")
+    );
+
+    let with_reason = run_json(repo.path(), ["done", first.as_str(), "--reason", "shipped"]);
+    assert_eq!(
+        with_reason.cli.code, 0,
+        "stderr: {}",
+        with_reason.cli.stderr
+    );
+    let with_note = run_json(repo.path(), ["done", second.as_str(), "--note", "merged"]);
+    assert_eq!(with_note.cli.code, 0, "stderr: {}", with_note.cli.stderr);
+}
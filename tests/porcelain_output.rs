@@ -0,0 +1,138 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli};
+use serde_json::Value;
+
+const TASK_KEYS_V1: [&str; 17] = [
+    "id",
+    "alias",
+    "kind",
+    "title",
+    "status",
+    "priority",
+    "assignee",
+    "parent_id",
+    "labels",
+    "planning_state",
+    "spec_path",
+    "blocked_on",
+    "defer_until",
+    "external_ref",
+    "created_at",
+    "updated_at",
+    "closed_at",
+];
+
+/// Top-level keys of a JSON object in the order they were printed.
+fn key_order(line: &str, object_start: &str) -> Vec<String> {
+    let start = line.find(object_start).expect("object start") + object_start.len();
+    let mut keys = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut current = String::new();
+    let mut after_brace_or_comma = true;
+    for ch in line[start..].chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+                if depth == 0 && after_brace_or_comma {
+                    keys.push(std::mem::take(&mut current));
+                    after_brace_or_comma = false;
+                }
+                continue;
+            }
+            if depth == 0 && after_brace_or_comma {
+                current.push(ch);
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' if depth == 0 => break,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => after_brace_or_comma = true,
+            _ => {}
+        }
+    }
+    keys
+}
+
+fn porcelain(repo: &std::path::Path, args: &[&str]) -> (i32, String, Value) {
+    let mut args = args.to_vec();
+    args.extend(["--porcelain", "v1"]);
+    let result = run_cli(repo, &args);
+    let line = result.stdout.trim().to_string();
+    assert_eq!(line.lines().count(), 1, "one line: {}", result.stdout);
+    let value = serde_json::from_str(&line).expect("porcelain json");
+    (result.code, line, value)
+}
+
+#[test]
+fn porcelain_v1_freezes_list_ready_and_show_shapes() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Porcelain target");
+
+    for args in [
+        &["find", "open"][..],
+        &["find", "ready"][..],
+        &["find", "search", "Porcelain"][..],
+    ] {
+        let (code, line, value) = porcelain(repo.path(), args);
+        assert_eq!(code, 0, "{}", line);
+        assert_eq!(
+            key_order(&line, "{"),
+            ["porcelain", "command", "ok", "data"]
+        );
+        assert_eq!(value["porcelain"], "v1");
+        assert_eq!(value["data"]["tasks"][0]["id"], id.as_str());
+        assert_eq!(key_order(&line, r#""tasks":[{"#), TASK_KEYS_V1);
+        assert!(value.get("schema_version").is_none());
+    }
+
+    let (code, line, value) = porcelain(repo.path(), &["show", &id]);
+    assert_eq!(code, 0, "{}", line);
+    assert_eq!(value["command"], "tsq show");
+    assert_eq!(
+        key_order(&line, r#""data":{"#),
+        ["task", "blockers", "dependents", "ready"]
+    );
+    assert_eq!(key_order(&line, r#""task":{"#), TASK_KEYS_V1);
+    assert_eq!(value["data"]["task"]["assignee"], Value::Null);
+    assert_eq!(value["data"]["ready"], true);
+}
+
+#[test]
+fn porcelain_v1_reports_errors_and_rejects_unsupported_commands() {
+    let repo = make_repo();
+    init_repo(repo.path());
+
+    let (code, line, value) = porcelain(repo.path(), &["show", "tsq-zzzzzzzz"]);
+    assert_ne!(code, 0);
+    assert_eq!(
+        key_order(&line, "{"),
+        ["porcelain", "command", "ok", "error"]
+    );
+    assert_eq!(value["ok"], false);
+    assert_eq!(value["error"]["code"], "TASK_NOT_FOUND");
+
+    for args in [
+        &["find", "open", "--tree"][..],
+        &["find", "open", "--count-by", "status"][..],
+        &["find", "search", "open | count by label"][..],
+    ] {
+        let (code, _, value) = porcelain(repo.path(), args);
+        assert_eq!(code, 1);
+        assert_eq!(value["error"]["code"], "VALIDATION_ERROR");
+    }
+
+    let (code, _, value) = porcelain(repo.path(), &["labels"]);
+    assert_eq!(code, 1);
+    assert_eq!(value["error"]["code"], "VALIDATION_ERROR");
+}
@@ -193,6 +193,28 @@ fn id_prefix_ambiguity_returns_id_and_alias_candidates() {
     );
 }
 
+#[test]
+fn ambiguity_candidates_carry_title_and_status_for_pick_lists() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let first = create_task(repo.path(), "First task");
+    create_task(repo.path(), "Second task");
+
+    let result = run_json(repo.path(), ["show", "tsq-", "--no-interactive"]);
+
+    assert_eq!(result.cli.code, 1);
+    let candidates = result.envelope["error"]["details"]["candidates"]
+        .as_array()
+        .expect("candidates");
+    let candidate = candidates
+        .iter()
+        .find(|candidate| candidate["id"].as_str() == Some(first.as_str()))
+        .expect("first task candidate");
+    assert_eq!(candidate["title"], "First task");
+    assert_eq!(candidate["status"], "open");
+}
+
 #[test]
 fn sequential_allocation_after_u64_max_returns_error() {
     let repo = common::make_repo();
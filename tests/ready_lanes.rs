@@ -0,0 +1,68 @@
+mod common;
+
+use common::{
+    create_task, create_task_with_args, ids_from_task_list, init_repo, run_json, set_config_key,
+};
+
+#[test]
+fn configured_lane_filters_ready_tasks_by_state_labels_and_kind() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "lanes",
+        serde_json::json!([{
+            "name": "review",
+            "when": {"planning_state": ["planned"], "labels": ["needs-review"], "kind": ["feature"]}
+        }]),
+    );
+    let review = create_task_with_args(
+        repo.path(),
+        "Search revamp",
+        &["--kind", "feature", "--planned"],
+    );
+    let unplanned = create_task_with_args(repo.path(), "Login flow", &["--kind", "feature"]);
+    let plain = create_task(repo.path(), "Small fix");
+    for id in [&review, &unplanned, &plain] {
+        run_json(repo.path(), ["label", id.as_str(), "needs-review"]);
+    }
+
+    let lane = run_json(repo.path(), ["find", "ready", "--lane", "review"]);
+    assert_eq!(lane.cli.code, 0, "{:?}", lane.envelope);
+    assert_eq!(ids_from_task_list(&lane.envelope), vec![review.clone()]);
+
+    let planning = run_json(repo.path(), ["find", "ready", "--lane", "planning"]);
+    let planning_ids = ids_from_task_list(&planning.envelope);
+    assert!(planning_ids.contains(&unplanned));
+    assert!(planning_ids.contains(&plain));
+    assert!(!planning_ids.contains(&review));
+}
+
+#[test]
+fn configured_lane_overrides_builtin_and_unknown_lane_lists_choices() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(
+        repo.path(),
+        "lanes",
+        serde_json::json!([
+            {"name": "coding", "when": {"kind": ["task"]}},
+            {"name": "review", "when": {"labels": ["needs-review"]}}
+        ]),
+    );
+    let task = create_task(repo.path(), "Unplanned task");
+    let epic = create_task_with_args(repo.path(), "Epic", &["--kind", "epic", "--planned"]);
+
+    let coding = run_json(repo.path(), ["find", "ready", "--lane", "coding"]);
+    let coding_ids = ids_from_task_list(&coding.envelope);
+    assert!(coding_ids.contains(&task));
+    assert!(!coding_ids.contains(&epic));
+
+    let unknown = run_json(repo.path(), ["find", "ready", "--lane", "qa"]);
+    assert_eq!(unknown.cli.code, 1);
+    assert_eq!(unknown.envelope["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(
+        unknown.envelope["error"]["details"]["available"],
+        serde_json::json!(["planning", "coding", "review"])
+    );
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json};
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[test]
+fn ready_watch_reports_a_task_once_its_last_blocker_closes() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let blocker = create_task(repo.path(), "Blocker");
+    let waiting = create_task(repo.path(), "Waiting");
+    run_json(
+        repo.path(),
+        ["block", waiting.as_str(), "by", blocker.as_str()],
+    );
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tsq"))
+        .args(["find", "ready", "--watch", "--interval", "1", "--json"])
+        .current_dir(repo.path())
+        .env("TSQ_ACTOR", "rust-test")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn tsq");
+    let stdout = child.stdout.take().expect("stdout");
+    let (sender, lines) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let next_frame = || -> Value {
+        let line = lines
+            .recv_timeout(Duration::from_secs(20))
+            .expect("watch frame");
+        serde_json::from_str(&line).expect("json line")
+    };
+
+    let start = next_frame();
+    assert_eq!(start["command"], "tsq find ready");
+    assert_eq!(start["data"]["ready"], serde_json::json!([blocker.clone()]));
+
+    create_task(repo.path(), "Unrelated new task");
+    run_json(repo.path(), ["done", blocker.as_str()]);
+    let frame = next_frame();
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(frame["data"]["task"]["id"], waiting.as_str());
+    assert_eq!(
+        frame["data"]["unblocked_by"],
+        serde_json::json!([blocker.clone()])
+    );
+}
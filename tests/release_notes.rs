@@ -0,0 +1,110 @@
+mod common;
+
+use common::{
+    create_task, create_task_with_args, git, git_with_env, init_repo, make_repo, run_cli, run_json,
+};
+use serde_json::Value;
+
+fn group_names(data: &Value) -> Vec<&str> {
+    data["groups"]
+        .as_array()
+        .expect("groups")
+        .iter()
+        .map(|group| group["name"].as_str().expect("name"))
+        .collect()
+}
+
+#[test]
+fn release_notes_group_closed_work_and_skip_superseded_tasks() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let feature = create_task_with_args(repo.path(), "Search revamp", &["--kind", "feature"]);
+    let fix = create_task(repo.path(), "Fix login redirect");
+    let old = create_task(repo.path(), "Old search idea");
+    let open = create_task(repo.path(), "Still open");
+    run_cli(repo.path(), ["edit", &fix, "--external-ref", "GH-12"]);
+    run_cli(repo.path(), ["label", &fix, "auth"]);
+    run_cli(repo.path(), ["done", &feature]);
+    run_cli(repo.path(), ["done", &fix]);
+    run_cli(repo.path(), ["supersede", &old, "with", &feature]);
+
+    let by_kind = run_json(repo.path(), ["release-notes", "--since", "2000-01-01"]);
+    let data = &by_kind.envelope["data"];
+    assert_eq!(data["total"], 2);
+    assert_eq!(data["excluded"], 1);
+    assert_eq!(group_names(data), ["feature", "task"]);
+    assert_eq!(data["groups"][1]["tasks"][0]["external_ref"], "GH-12");
+    let listed = data.to_string();
+    assert!(!listed.contains(&old) && !listed.contains(&open));
+
+    let by_label = run_json(
+        repo.path(),
+        [
+            "release-notes",
+            "--since",
+            "2000-01-01",
+            "--group-by",
+            "label",
+        ],
+    );
+    assert_eq!(
+        group_names(&by_label.envelope["data"]),
+        ["auth", "unlabeled"]
+    );
+
+    let markdown = run_cli(
+        repo.path(),
+        [
+            "release-notes",
+            "--since",
+            "2000-01-01",
+            "--format",
+            "markdown",
+        ],
+    );
+    assert_eq!(markdown.code, 0, "stderr:\n{}", markdown.stderr);
+    assert!(markdown.stdout.starts_with("# Release notes"));
+    assert!(markdown.stdout.contains("## feature"));
+    assert!(
+        markdown
+            .stdout
+            .contains(&format!("- Fix login redirect (GH-12) `{}`", fix))
+    );
+
+    let future = run_json(repo.path(), ["release-notes", "--since", "2999-01-01"]);
+    assert_eq!(future.envelope["data"]["total"], 0);
+}
+
+#[test]
+fn release_notes_resolve_since_from_a_git_tag() {
+    let repo = make_repo();
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["config", "user.name", "rust-test"]);
+    git(
+        repo.path(),
+        &["config", "user.email", "rust-test@example.com"],
+    );
+    git_with_env(
+        repo.path(),
+        &["commit", "-q", "--allow-empty", "-m", "seed"],
+        &[
+            ("GIT_AUTHOR_DATE", "2001-02-03T04:05:06Z"),
+            ("GIT_COMMITTER_DATE", "2001-02-03T04:05:06Z"),
+        ],
+    );
+    git(repo.path(), &["tag", "v0.1.0"]);
+    init_repo(repo.path());
+    let task = create_task(repo.path(), "Shipped after tag");
+    run_cli(repo.path(), ["done", &task]);
+
+    let notes = run_json(repo.path(), ["release-notes", "--since", "v0.1.0"]);
+    let data = &notes.envelope["data"];
+    assert_eq!(data["tag"], "v0.1.0");
+    assert_eq!(data["since"], "2001-02-03T04:05:06.000Z");
+    assert_eq!(data["groups"][0]["tasks"][0]["id"], task.as_str());
+
+    let missing = run_json(repo.path(), ["release-notes", "--since", "v9.9.9"]);
+    assert_eq!(missing.envelope["error"]["code"], "VALIDATION_ERROR");
+    let unsupported = run_json(repo.path(), ["find", "open", "--format", "markdown"]);
+    assert_eq!(unsupported.cli.code, 1);
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json, set_config_key};
+use std::fs;
+
+#[test]
+fn repair_salvage_quarantines_bad_lines_and_rebuilds_the_projection() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let kept = create_task(repo.path(), "Survives");
+    let events_file = repo.path().join(".tasque/events.jsonl");
+    let original = fs::read_to_string(&events_file).unwrap();
+    let orphan_note = serde_json::json!({
+        "id": "01HX0000000000000000000001",
+        "ts": "2099-01-01T00:00:00.000Z",
+        "actor": "test",
+        "type": "task.noted",
+        "task_id": "tsq-missing",
+        "payload": {"text": "lost"},
+    });
+    fs::write(
+        &events_file,
+        format!(
+            "<<<<<<< HEAD\n{}{}\n>>>>>>> theirs\n",
+            original, orphan_note
+        ),
+    )
+    .unwrap();
+
+    let broken = run_json(repo.path(), ["show", &kept]);
+    assert_eq!(broken.envelope["error"]["code"], "EVENTS_CORRUPT");
+
+    let preview = run_json(repo.path(), ["repair", "--salvage"]);
+    assert_eq!(preview.cli.code, 0, "stderr:\n{}", preview.cli.stderr);
+    let salvage = &preview.envelope["data"]["salvage"];
+    assert_eq!(salvage["kept_events"], 1);
+    let lost: Vec<u64> = salvage["quarantined"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["line"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lost, [1, 3, 4]);
+    assert_eq!(salvage["quarantined"][1]["task_id"], "tsq-missing");
+    assert!(!repo.path().join(".tasque/quarantine.jsonl").exists());
+
+    let fixed = run_json(repo.path(), ["repair", "--salvage", "--fix"]);
+    assert_eq!(fixed.cli.code, 0, "stderr:\n{}", fixed.cli.stderr);
+    assert_eq!(fixed.envelope["data"]["applied"], true);
+    assert_eq!(fs::read_to_string(&events_file).unwrap(), original);
+    let quarantine = fs::read_to_string(repo.path().join(".tasque/quarantine.jsonl")).unwrap();
+    let entries: Vec<serde_json::Value> = quarantine
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0]["line"], 1);
+    assert_eq!(entries[0]["raw"], "<<<<<<< HEAD");
+
+    let shown = run_json(repo.path(), ["show", &kept]);
+    assert_eq!(shown.cli.code, 0, "stderr:\n{}", shown.cli.stderr);
+    assert_eq!(shown.envelope["data"]["task"]["title"], "Survives");
+}
+
+#[test]
+fn repair_rebuild_snapshots_replaces_corrupt_snapshots_with_a_verified_one() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "snapshot_every", serde_json::json!(2));
+    for title in ["One", "Two", "Three"] {
+        create_task(repo.path(), title);
+    }
+    let snapshots_dir = repo.path().join(".tasque/snapshots");
+    fs::write(snapshots_dir.join("9999-corrupt-2.json"), "{not json").unwrap();
+
+    let preview = run_json(repo.path(), ["repair", "--rebuild-snapshots"]);
+    assert_eq!(preview.cli.code, 0, "stderr:\n{}", preview.cli.stderr);
+    let planned = &preview.envelope["data"]["snapshots"];
+    assert_eq!(planned["removed"].as_array().unwrap().len(), 2);
+    assert_eq!(planned["rebuilt_at_event"], 2);
+    assert_eq!(planned["verified"], false);
+
+    let rebuilt = run_json(repo.path(), ["repair", "--rebuild-snapshots", "--fix"]);
+    assert_eq!(rebuilt.cli.code, 0, "stderr:\n{}", rebuilt.cli.stderr);
+    let report = &rebuilt.envelope["data"]["snapshots"];
+    assert_eq!(report["verified"], true);
+    assert_eq!(report["rebuilt_at_event"], 2);
+    let remaining: Vec<_> = fs::read_dir(&snapshots_dir).unwrap().flatten().collect();
+    assert_eq!(remaining.len(), 1);
+    assert!(
+        remaining[0]
+            .file_name()
+            .to_str()
+            .unwrap()
+            .ends_with("-2.json")
+    );
+}
+
+#[test]
+fn orphans_adopt_only_accepts_missing_ids_with_a_surviving_target() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let child = create_task(repo.path(), "Ship billing");
+    let successor = create_task(repo.path(), "New schema");
+    run_json(repo.path(), ["block", &child, "by", &successor]);
+
+    let malformed = run_json(repo.path(), ["orphans", "adopt", "--map", "tsq-gone"]);
+    assert_eq!(malformed.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    // Nothing dangles at tsq-gone, so there is nothing to adopt.
+    let clean = run_json(
+        repo.path(),
+        [
+            "orphans",
+            "adopt",
+            "--map",
+            &format!("tsq-gone={}", successor),
+        ],
+    );
+    assert_eq!(clean.cli.code, 1);
+    assert_eq!(clean.envelope["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(
+        clean.envelope["error"]["details"]["missing"],
+        serde_json::json!([])
+    );
+
+    let live = run_json(
+        repo.path(),
+        [
+            "orphans",
+            "adopt",
+            "--map",
+            &format!("{}={}", successor, child),
+        ],
+    );
+    assert_eq!(live.envelope["error"]["code"], "VALIDATION_ERROR");
+    let shown = run_json(repo.path(), ["show", &child]);
+    assert_eq!(
+        shown.envelope["data"]["blockers"],
+        serde_json::json!([successor])
+    );
+}
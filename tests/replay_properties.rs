@@ -0,0 +1,47 @@
+mod common;
+
+use common::{make_repo, run_json};
+use proptest::prelude::*;
+use tasque::app::selftest::{check_events, generate_events};
+
+fn events_from(choices: &[usize], count: usize) -> Vec<tasque::types::EventRecord> {
+    let mut next = choices.iter().copied().cycle();
+    generate_events(&mut |_| next.next().unwrap_or_default(), count)
+}
+
+proptest! {
+    #[test]
+    fn generated_logs_keep_projector_invariants(
+        choices in prop::collection::vec(any::<usize>(), 1..64),
+        count in 1usize..120,
+    ) {
+        let events = events_from(&choices, count);
+        prop_assert_eq!(events.len(), count);
+        if let Err(violation) = check_events(&events) {
+            prop_assert!(false, "{}: {}", violation.invariant, violation.message);
+        }
+    }
+}
+
+#[test]
+fn check_events_reports_a_broken_log() {
+    let mut events = events_from(&[0, 1, 2, 3, 4, 5, 6, 7], 20);
+    let duplicate = events[0].clone();
+    events.push(duplicate);
+    let violation = check_events(&events).expect_err("duplicate create must fail");
+    assert_eq!(violation.invariant, "replay");
+}
+
+#[test]
+fn selftest_runs_without_a_tasque_repo() {
+    let dir = make_repo();
+    let result = run_json(
+        dir.path(),
+        ["selftest", "--seed", "42", "--cases", "3", "--events", "80"],
+    );
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stdout);
+    let data = &result.envelope["data"];
+    assert_eq!(data["seed"], 42);
+    assert_eq!(data["cases"], 3);
+    assert_eq!(data["failures"], serde_json::json!([]));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::{create_task_with_args, init_repo, ok_data, run_cli, run_json};
+
+#[test]
+fn rollup_annotates_epics_with_child_progress() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let epic = create_task_with_args(repo.path(), "Launch", &["--kind", "epic"]);
+    let first = create_task_with_args(repo.path(), "Write docs", &["--parent", &epic]);
+    create_task_with_args(repo.path(), "Ship build", &["--parent", &epic]);
+    create_task_with_args(repo.path(), "Announce", &["--parent", &epic]);
+    let done = run_json(repo.path(), ["done", &first]);
+    assert_eq!(done.cli.code, 0, "{}", done.cli.stderr);
+
+    let listing = run_json(repo.path(), ["find", "open", "--kind", "epic", "--rollup"]);
+    assert_eq!(listing.cli.code, 0, "{}", listing.cli.stderr);
+    let task = &ok_data(&listing.envelope)["tasks"][0];
+    assert_eq!(task["id"], epic.as_str());
+    assert_eq!(
+        task["rollup"],
+        serde_json::json!({"done": 1, "total": 3, "open": 2, "in_progress": 0, "percent": 33})
+    );
+
+    let human = run_cli(repo.path(), ["find", "open", "--kind", "epic", "--rollup"]);
+    assert!(human.stdout.contains("PROGRESS"), "{}", human.stdout);
+    assert!(human.stdout.contains("1/3 33%"), "{}", human.stdout);
+}
+
+#[test]
+fn rollup_leaves_plain_tasks_unannotated() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    create_task_with_args(repo.path(), "Leaf", &[]);
+
+    let plain = run_json(repo.path(), ["find", "open"]);
+    assert!(ok_data(&plain.envelope)["tasks"][0].get("rollup").is_none());
+    let rolled = run_json(repo.path(), ["find", "open", "--rollup"]);
+    assert!(
+        ok_data(&rolled.envelope)["tasks"][0]
+            .get("rollup")
+            .is_none()
+    );
+}
+
+#[test]
+fn tree_roots_one_initiative_with_depth_and_descendant_rollup() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let epic = create_task_with_args(repo.path(), "Billing", &["--kind", "epic"]);
+    let feature = create_task_with_args(
+        repo.path(),
+        "Invoices",
+        &["--kind", "feature", "--parent", &epic],
+    );
+    let leaf = create_task_with_args(repo.path(), "PDF export", &["--parent", &feature]);
+    let sibling = create_task_with_args(repo.path(), "Refunds", &["--parent", &epic]);
+    let blocker = create_task_with_args(repo.path(), "Tax tables", &[]);
+    run_json(repo.path(), ["block", &leaf, "by", &blocker]);
+    run_json(repo.path(), ["done", &sibling]);
+
+    let full = run_json(repo.path(), ["tree", &epic]);
+    assert_eq!(full.cli.code, 0, "{}", full.cli.stderr);
+    let data = ok_data(&full.envelope);
+    assert_eq!(data["tree"]["task"]["id"], epic.as_str());
+    let children = data["tree"]["children"].as_array().expect("children");
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0]["children"][0]["task"]["id"], leaf.as_str());
+    assert_eq!(
+        children[0]["children"][0]["blockers"],
+        serde_json::json!([blocker])
+    );
+    assert_eq!(
+        data["rollup"],
+        serde_json::json!({"done": 1, "total": 3, "open": 2, "in_progress": 0, "percent": 33})
+    );
+    assert_eq!(data["hidden"], 0);
+
+    let shallow = run_json(repo.path(), ["tree", &epic, "--depth", "1"]);
+    let data = ok_data(&shallow.envelope);
+    assert_eq!(
+        data["tree"]["children"][0]["children"],
+        serde_json::json!([])
+    );
+    assert_eq!(data["rollup"]["total"], 3);
+    assert_eq!(data["hidden"], 1);
+
+    let human = run_cli(repo.path(), ["tree", &epic, "--depth", "1"]);
+    assert!(!human.stdout.contains("PDF export"), "{}", human.stdout);
+    assert!(human.stdout.contains("rollup=1/3 33%"), "{}", human.stdout);
+    assert!(human.stdout.contains("hidden=1"), "{}", human.stdout);
+
+    let missing = run_json(repo.path(), ["tree", "tsq-zzzzzzzz"]);
+    assert_eq!(missing.envelope["error"]["code"], "TASK_NOT_FOUND");
+}
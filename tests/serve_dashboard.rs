@@ -0,0 +1,151 @@
+mod common;
+
+use common::{create_task, create_task_with_args, init_repo, make_repo, run_json};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+fn start_server(repo: &std::path::Path, args: &[&str]) -> Server {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tsq"))
+        .arg("serve")
+        .args(args)
+        .args(["--port", "0"])
+        .current_dir(repo)
+        .env("TSQ_ACTOR", "rust-test")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn tsq serve");
+    let mut banner = String::new();
+    BufReader::new(child.stderr.take().expect("stderr"))
+        .read_line(&mut banner)
+        .expect("read banner");
+    let address = banner
+        .split("http://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .expect("bound address")
+        .to_string();
+    Server { child, address }
+}
+
+fn get(server: &Server, path: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(&server.address).expect("connect");
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).expect("send");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("read response");
+    let (head, body) = response.split_once("\r\n\r\n").expect("http response");
+    (
+        head.lines().next().unwrap_or("").to_string(),
+        body.to_string(),
+    )
+}
+
+#[test]
+fn serve_returns_frames_with_epic_progress_and_the_dashboard_page() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let epic = create_task_with_args(repo.path(), "Launch", &["--kind", "epic"]);
+    create_task_with_args(repo.path(), "Landing page", &["--parent", epic.as_str()]);
+    create_task(repo.path(), "Loose task");
+    let server = start_server(repo.path(), &["--ui"]);
+
+    let (status, body) = get(&server, "/api/frame");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    let envelope: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(envelope["command"], "tsq serve");
+    assert_eq!(envelope["data"]["summary"]["total"], 3);
+    let epics = envelope["data"]["epics"].as_array().expect("epics");
+    assert_eq!(epics.len(), 1);
+    assert_eq!(epics[0]["id"], epic.as_str());
+    assert_eq!(epics[0]["rollup"]["total"], 1);
+
+    let (status, page) = get(&server, "/");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(page.contains("new EventSource(\"/events\")"));
+
+    let (status, _) = get(&server, "/nope");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+}
+
+#[test]
+fn serve_streams_frames_as_server_sent_events_and_hides_ui_without_flag() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Streamed");
+    let server = start_server(repo.path(), &["--interval", "1"]);
+
+    let (status, _) = get(&server, "/");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+
+    let mut stream = TcpStream::connect(&server.address).expect("connect");
+    write!(stream, "GET /events HTTP/1.1\r\nHost: localhost\r\n\r\n").expect("send");
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    let data = loop {
+        line.clear();
+        assert!(reader.read_line(&mut line).expect("read event") > 0);
+        if let Some(data) = line.strip_prefix("data: ") {
+            break data.trim().to_string();
+        }
+    };
+    let frame: Value = serde_json::from_str(&data).expect("frame json");
+    assert_eq!(frame["tasks"][0]["id"], id.as_str());
+}
+
+#[test]
+fn serve_exposes_prometheus_gauges_at_metrics() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let blocker = create_task(repo.path(), "Blocker");
+    let blocked = create_task(repo.path(), "Blocked");
+    run_json(repo.path(), ["block", &blocked, "by", &blocker]);
+    let server = start_server(repo.path(), &[]);
+
+    let (status, body) = get(&server, "/metrics");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("# TYPE tasque_tasks gauge"), "{}", body);
+    assert!(body.contains("tasque_tasks{status=\"open\"} 2"), "{}", body);
+    assert!(
+        body.contains("tasque_tasks{status=\"blocked\"} 0"),
+        "{}",
+        body
+    );
+    assert!(body.contains("\ntasque_ready_tasks 1\n"), "{}", body);
+    assert!(body.contains("\ntasque_event_log_events 3\n"), "{}", body);
+    assert!(body.contains("\ntasque_projection_seconds "), "{}", body);
+}
+
+#[test]
+fn serve_answers_while_an_idle_peer_is_connected_and_then_drops_it() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let server = start_server(repo.path(), &[]);
+
+    let mut idle = TcpStream::connect(&server.address).expect("connect idle peer");
+    let (status, _) = get(&server, "/metrics");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+
+    idle.set_read_timeout(Some(Duration::from_secs(30)))
+        .expect("client timeout");
+    let started = Instant::now();
+    let mut response = String::new();
+    idle.read_to_string(&mut response)
+        .expect("server closes idle peer");
+    assert!(response.is_empty(), "{}", response);
+    assert!(started.elapsed() < Duration::from_secs(20));
+}
@@ -0,0 +1,145 @@
+//! Skill installs from git sources and configured registries (non-Windows:
+//! the skill targets are redirected with a HOME override).
+#![cfg(not(target_os = "windows"))]
+
+mod common;
+
+use common::{git, git_with_env, init_repo, make_repo, run_cli_with_env};
+use std::fs;
+use std::path::Path;
+
+fn write_skill(source: &Path, body: &str) {
+    let skill = source.join("agents").join("tasque");
+    fs::create_dir_all(&skill).expect("skill dir");
+    fs::write(
+        skill.join("SKILL.md"),
+        format!("<!-- tsq-managed-skill:v1 -->\n{}\n", body),
+    )
+    .expect("write SKILL.md");
+    git(source, &["add", "-A"]);
+    git_with_env(
+        source,
+        &["commit", "-q", "-m", body],
+        &[
+            ("GIT_AUTHOR_NAME", "Test"),
+            ("GIT_AUTHOR_EMAIL", "test@example.com"),
+            ("GIT_COMMITTER_NAME", "Test"),
+            ("GIT_COMMITTER_EMAIL", "test@example.com"),
+        ],
+    );
+}
+
+fn envelope(stdout: &str) -> serde_json::Value {
+    serde_json::from_str(stdout.trim()).expect("json envelope")
+}
+
+#[test]
+fn registry_source_installs_and_pins_checksum() {
+    let tmp = make_repo();
+    let root = tmp.path();
+    let repo = root.join("repo");
+    let source = root.join("source");
+    let home = root.join("home");
+    let cache = root.join("cache");
+    for dir in [&repo, &source, &home] {
+        fs::create_dir_all(dir).expect("dir");
+    }
+    git(&source, &["init", "-q"]);
+    write_skill(&source, "# Remote v1");
+
+    init_repo(&repo);
+    let config_path = repo.join(".tasque").join("config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).expect("config")).expect("json");
+    config["skill_registries"] = serde_json::json!({
+        "team": { "url": format!("file://{}#agents", source.display()) }
+    });
+    fs::write(&config_path, config.to_string()).expect("write config");
+
+    let home_str = home.display().to_string();
+    let cache_str = cache.display().to_string();
+    let envs = [
+        ("HOME", home_str.as_str()),
+        ("TSQ_SKILL_CACHE_DIR", cache_str.as_str()),
+    ];
+    let installed = run_cli_with_env(
+        &repo,
+        [
+            "init",
+            "--install-skill",
+            "--skill-targets",
+            "claude",
+            "--skill-source",
+            "registry:team",
+            "--json",
+        ],
+        &envs,
+    );
+    assert_eq!(installed.code, 0, "stderr: {}", installed.stderr);
+    let data = &envelope(&installed.stdout)["data"]["skill_operation"];
+    assert_eq!(data["source"], "registry:team");
+    let checksum = data["source_checksum"]
+        .as_str()
+        .expect("checksum")
+        .to_string();
+    assert!(checksum.starts_with("sha256:"), "{}", checksum);
+    let skill_md = home
+        .join(".claude")
+        .join("skills")
+        .join("tasque")
+        .join("SKILL.md");
+    assert!(
+        fs::read_to_string(&skill_md)
+            .expect("installed")
+            .contains("# Remote v1")
+    );
+
+    // Pin the registry to the installed content, then move the remote on.
+    config["skill_registries"]["team"]["checksums"] = serde_json::json!({ "tasque": checksum });
+    fs::write(&config_path, config.to_string()).expect("write config");
+    write_skill(&source, "# Remote v2");
+
+    let refreshed = run_cli_with_env(
+        &repo,
+        ["skills", "refresh", "--source", "registry:team", "--json"],
+        &envs,
+    );
+    assert_eq!(refreshed.code, 2, "stdout: {}", refreshed.stdout);
+    let error = envelope(&refreshed.stdout);
+    assert_eq!(error["error"]["code"], "SKILL_CHECKSUM_MISMATCH");
+    assert_eq!(error["error"]["details"]["expected"], checksum.as_str());
+    assert!(
+        fs::read_to_string(&skill_md)
+            .expect("still installed")
+            .contains("# Remote v1")
+    );
+
+    // A direct git URL is not pinned, so it picks up the new commit.
+    let direct = run_cli_with_env(
+        &repo,
+        [
+            "skills",
+            "update",
+            "--source",
+            &format!("file://{}#agents", source.display()),
+        ],
+        &envs,
+    );
+    assert_eq!(direct.code, 0, "stderr: {}", direct.stderr);
+    assert!(
+        fs::read_to_string(&skill_md)
+            .expect("refreshed")
+            .contains("# Remote v2")
+    );
+
+    let unknown = run_cli_with_env(
+        &repo,
+        ["skills", "status", "--source", "registry:nope", "--json"],
+        &envs,
+    );
+    assert_eq!(unknown.code, 1);
+    assert_eq!(
+        envelope(&unknown.stdout)["error"]["code"],
+        "VALIDATION_ERROR"
+    );
+}
@@ -72,7 +72,7 @@ fn parse_refresh_envelope(stdout: &str) -> (serde_json::Value, Vec<serde_json::V
         .and_then(|v| v.as_array())
         .expect("data.results must be an array")
         .clone();
-    assert_eq!(results.len(), 4, "expected 4 target results");
+    assert_eq!(results.len(), 7, "expected 7 target results");
     (envelope, results)
 }
 
@@ -139,7 +139,9 @@ fn skills_refresh_json_works_without_tasque_and_creates_no_repo_state() {
 
     let (_envelope, results) = parse_refresh_envelope(&stdout);
 
-    for target in ["claude", "codex", "copilot", "opencode"] {
+    for target in [
+        "claude", "codex", "copilot", "opencode", "cursor", "windsurf", "gemini",
+    ] {
         assert_eq!(
             status_for(&results, target),
             "not_found",
@@ -159,6 +161,9 @@ fn skills_refresh_json_works_without_tasque_and_creates_no_repo_state() {
     assert!(!codex_home.join("skills").exists());
     assert!(!home_dir.join(".copilot").exists());
     assert!(!home_dir.join(".opencode").exists());
+    assert!(!home_dir.join(".cursor").exists());
+    assert!(!home_dir.join(".windsurf").exists());
+    assert!(!home_dir.join(".gemini").exists());
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -6,8 +6,10 @@ use std::fs;
 use tempfile::Builder;
 
 use tasque::app::service::TasqueService;
-use tasque::app::service_types::SkillsRefreshInput;
-use tasque::skills::types::SkillResultStatus;
+use tasque::app::service_types::{SkillsDiffInput, SkillsRefreshInput};
+use tasque::skills::types::{
+    SkillDriftStatus, SkillFileChange, SkillResultStatus, SkillStatusEntry, SkillTarget,
+};
 
 /// Build a TasqueService with a dummy repo root (skills refresh
 /// does not need a real .tasque repo).
@@ -64,11 +66,13 @@ fn service_refresh_missing_targets_not_found_and_no_repo_state() {
             source_root_dir: Some(source_root.display().to_string()),
             home_dir: Some(home.display().to_string()),
             codex_home: Some(codex_home.display().to_string()),
+            dry_run: false,
+            ..Default::default()
         })
         .expect("refresh should succeed");
 
-    // Verify all 4 targets present
-    assert_eq!(summary.results.len(), 4);
+    // Verify all 7 targets present
+    assert_eq!(summary.results.len(), 7);
     for r in &summary.results {
         assert_eq!(
             r.status,
@@ -80,7 +84,9 @@ fn service_refresh_missing_targets_not_found_and_no_repo_state() {
 
     // JSON serialization uses snake_case
     let json = serde_json::to_value(&summary).expect("serialize");
-    for target in ["claude", "codex", "copilot", "opencode"] {
+    for target in [
+        "claude", "codex", "copilot", "opencode", "cursor", "windsurf", "gemini",
+    ] {
         assert_eq!(json_status_for(&json, target), "not_found");
     }
 
@@ -136,6 +142,8 @@ fn service_refresh_skip_update_cases() {
             source_root_dir: Some(source_root.display().to_string()),
             home_dir: Some(home.display().to_string()),
             codex_home: Some(codex_home.display().to_string()),
+            dry_run: false,
+            ..Default::default()
         })
         .expect("refresh should succeed");
 
@@ -192,6 +200,8 @@ fn service_refresh_embedded_fallback_when_source_missing() {
             source_root_dir: Some(empty_source.display().to_string()),
             home_dir: Some(home.display().to_string()),
             codex_home: Some(codex_home.display().to_string()),
+            dry_run: false,
+            ..Default::default()
         })
         .expect("refresh with embedded fallback");
 
@@ -248,6 +258,8 @@ fn service_refresh_propagates_source_copy_error() {
             source_root_dir: Some(t.join("skills-source").display().to_string()),
             home_dir: Some(home.display().to_string()),
             codex_home: Some(codex_home.display().to_string()),
+            dry_run: false,
+            ..Default::default()
         })
         .expect_err("unsupported source entry should propagate as error");
 
@@ -256,3 +268,176 @@ fn service_refresh_propagates_source_copy_error() {
     let skill_md = fs::read_to_string(claude_skill.join("SKILL.md")).expect("read");
     assert!(skill_md.contains("old-v1"));
 }
+
+/// list/status report managed, unmanaged, and missing installs, and status
+/// names the files that differ from the bundled source until refresh runs.
+#[test]
+fn service_list_and_status_report_drift() {
+    let tmp = Builder::new()
+        .prefix("tsq-svc-skill-status-")
+        .tempdir()
+        .expect("tempdir");
+    let t = tmp.path();
+
+    let repo_root = t.join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let source_root = t.join("skills-source");
+    let skill_dir = source_root.join("tasque");
+    fs::create_dir_all(skill_dir.join("references")).expect("skill src dir");
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "<!-- tsq-managed-skill:v1 -->\n# Tasque Skill v2\n",
+    )
+    .expect("write SKILL.md");
+    fs::write(skill_dir.join("references").join("new.md"), "new\n").expect("write ref");
+
+    let home = t.join("home");
+    let codex_home = t.join("codex");
+    let claude_skill = home.join(".claude").join("skills").join("tasque");
+    fs::create_dir_all(&claude_skill).expect("claude skill");
+    fs::write(
+        claude_skill.join("SKILL.md"),
+        "<!-- tsq-managed-skill:v1 -->\n# Tasque Skill v1\n",
+    )
+    .expect("write claude SKILL.md");
+    fs::write(claude_skill.join("stale.md"), "old\n").expect("write stale");
+    let codex_skill = codex_home.join("skills").join("tasque");
+    fs::create_dir_all(&codex_skill).expect("codex skill");
+    fs::write(codex_skill.join("SKILL.md"), "# hand written\n").expect("write codex SKILL.md");
+
+    let input = || SkillsRefreshInput {
+        source_root_dir: Some(source_root.display().to_string()),
+        home_dir: Some(home.display().to_string()),
+        codex_home: Some(codex_home.display().to_string()),
+        dry_run: false,
+        ..Default::default()
+    };
+    let svc = make_service(&repo_root);
+
+    let listed = svc.skills_list(input()).expect("list");
+    let claude = listed
+        .iter()
+        .find(|e| e.target == SkillTarget::Claude)
+        .expect("claude entry");
+    assert!(claude.installed && claude.managed);
+    let codex = listed
+        .iter()
+        .find(|e| e.target == SkillTarget::Codex)
+        .expect("codex entry");
+    assert!(codex.installed && !codex.managed);
+    let copilot = listed
+        .iter()
+        .find(|e| e.target == SkillTarget::Copilot)
+        .expect("copilot entry");
+    assert!(!copilot.installed);
+
+    let status_for = |entries: &[SkillStatusEntry], target: SkillTarget| {
+        entries
+            .iter()
+            .find(|e| e.target == target)
+            .cloned()
+            .expect("status entry")
+    };
+    let status = svc.skills_status(input()).expect("status");
+    let claude = status_for(&status, SkillTarget::Claude);
+    assert_eq!(claude.status, SkillDriftStatus::Outdated);
+    let changes: Vec<(String, SkillFileChange)> = claude
+        .changes
+        .iter()
+        .map(|c| (c.path.clone(), c.change))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("SKILL.md".to_string(), SkillFileChange::Modified),
+            ("references/new.md".to_string(), SkillFileChange::Added),
+            ("stale.md".to_string(), SkillFileChange::Removed),
+        ]
+    );
+    assert_eq!(
+        status_for(&status, SkillTarget::Codex).status,
+        SkillDriftStatus::Unmanaged
+    );
+    assert_eq!(
+        status_for(&status, SkillTarget::Opencode).status,
+        SkillDriftStatus::NotInstalled
+    );
+
+    svc.skills_refresh(input()).expect("refresh");
+    let status = svc.skills_status(input()).expect("status after refresh");
+    let claude = status_for(&status, SkillTarget::Claude);
+    assert_eq!(claude.status, SkillDriftStatus::Current);
+    assert!(claude.changes.is_empty());
+    assert_eq!(
+        fs::read_to_string(codex_skill.join("SKILL.md")).expect("codex untouched"),
+        "# hand written\n"
+    );
+}
+
+/// diff reports unmanaged copies too and renders a unified patch from the
+/// installed copy to the source.
+#[test]
+fn service_diff_renders_patch_for_installed_copies() {
+    let tmp = Builder::new()
+        .prefix("tsq-svc-skill-diff-")
+        .tempdir()
+        .expect("tempdir");
+    let t = tmp.path();
+
+    let repo_root = t.join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    let source_root = t.join("skills-source");
+    let skill_dir = source_root.join("tasque");
+    fs::create_dir_all(&skill_dir).expect("skill src dir");
+    fs::write(skill_dir.join("SKILL.md"), "# Tasque\nnew line\n").expect("write SKILL.md");
+
+    let home = t.join("home");
+    let codex_home = t.join("codex");
+    let claude_skill = home.join(".claude").join("skills").join("tasque");
+    fs::create_dir_all(&claude_skill).expect("claude skill");
+    fs::write(claude_skill.join("SKILL.md"), "# Tasque\nold line\n").expect("write SKILL.md");
+
+    let svc = make_service(&repo_root);
+    let entries = svc
+        .skills_diff(SkillsDiffInput {
+            skill_name: "tasque".to_string(),
+            source_root_dir: Some(source_root.display().to_string()),
+            home_dir: Some(home.display().to_string()),
+            codex_home: Some(codex_home.display().to_string()),
+            ..Default::default()
+        })
+        .expect("diff");
+
+    let claude = entries
+        .iter()
+        .find(|e| e.target == SkillTarget::Claude)
+        .expect("claude entry");
+    assert!(claude.installed && !claude.managed);
+    assert!(
+        claude.patch.contains("--- a/claude/SKILL.md"),
+        "{}",
+        claude.patch
+    );
+    assert!(
+        claude.patch.contains("-old line\n+new line"),
+        "{}",
+        claude.patch
+    );
+    let codex = entries
+        .iter()
+        .find(|e| e.target == SkillTarget::Codex)
+        .expect("codex entry");
+    assert!(!codex.installed && codex.patch.is_empty());
+
+    let error = svc
+        .skills_diff(SkillsDiffInput {
+            skill_name: "no-such-skill".to_string(),
+            source_root_dir: Some(source_root.display().to_string()),
+            home_dir: Some(home.display().to_string()),
+            codex_home: Some(codex_home.display().to_string()),
+            ..Default::default()
+        })
+        .expect_err("unknown skill has no managed source");
+    assert_eq!(error.code, "VALIDATION_ERROR");
+}
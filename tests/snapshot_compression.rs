@@ -0,0 +1,59 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_json, set_config_key};
+use std::fs;
+use std::path::Path;
+
+fn snapshot_names(repo: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(repo.join(".tasque/snapshots"))
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name().to_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn zstd_snapshots_are_written_alongside_readable_plain_ones() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "snapshot_every", serde_json::json!(2));
+    create_task(repo.path(), "One");
+    create_task(repo.path(), "Two");
+    set_config_key(repo.path(), "compression", serde_json::json!("zstd"));
+    create_task(repo.path(), "Three");
+    create_task(repo.path(), "Four");
+
+    let names = snapshot_names(repo.path());
+    assert_eq!(names.len(), 2, "{names:?}");
+    assert!(names[0].ends_with("-2.json"), "{names:?}");
+    assert!(names[1].ends_with("-4.json.zst"), "{names:?}");
+    let compressed = fs::read(repo.path().join(".tasque/snapshots").join(&names[1])).unwrap();
+    let snapshot: serde_json::Value =
+        serde_json::from_slice(&zstd::decode_all(compressed.as_slice()).unwrap()).unwrap();
+    assert_eq!(snapshot["event_count"], 4);
+
+    let rebuilt = run_json(repo.path(), ["repair", "--rebuild-snapshots", "--fix"]);
+    assert_eq!(rebuilt.cli.code, 0, "stderr:\n{}", rebuilt.cli.stderr);
+    let report = &rebuilt.envelope["data"]["snapshots"];
+    assert_eq!(report["removed"].as_array().unwrap().len(), 2);
+    assert_eq!(report["verified"], true);
+    assert!(snapshot_names(repo.path())[0].ends_with(".json.zst"));
+
+    fs::remove_file(repo.path().join(".tasque/state.json")).unwrap();
+    let found = run_json(repo.path(), ["find", "open"]);
+    assert_eq!(found.cli.code, 0, "stderr:\n{}", found.cli.stderr);
+    assert_eq!(found.envelope["data"]["tasks"].as_array().unwrap().len(), 4);
+}
+
+#[test]
+fn unknown_compression_codec_is_rejected_as_invalid_config() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "compression", serde_json::json!("lz4"));
+
+    let found = run_json(repo.path(), ["find", "open"]);
+    assert_eq!(found.cli.code, 2);
+    assert_eq!(found.envelope["error"]["code"], "CONFIG_INVALID");
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{assert_validation_error, create_task, init_repo, ok_data, run_json};
+use serde_json::Value;
+
+fn status_of(repo: &std::path::Path, id: &str) -> (String, Value) {
+    let show = run_json(repo, ["show", id]);
+    let task = ok_data(&show.envelope)["task"].clone();
+    (task["status"].as_str().expect("status").to_string(), task)
+}
+
+fn wake_events(repo: &std::path::Path, id: &str) -> usize {
+    let history = run_json(repo, ["history", id]);
+    ok_data(&history.envelope)["events"]
+        .as_array()
+        .expect("events")
+        .iter()
+        .filter(|event| event["payload"]["woke_from"] == "2000-01-01T09:30:00.000Z")
+        .count()
+}
+
+#[test]
+fn defer_until_future_date_stays_deferred() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Snoozed later");
+
+    let deferred = run_json(repo.path(), ["defer", &id, "--until", "2999-07-01"]);
+    assert_eq!(deferred.cli.code, 0, "{}", deferred.cli.stderr);
+    run_json(repo.path(), ["find", "ready"]);
+
+    let (status, task) = status_of(repo.path(), &id);
+    assert_eq!(status, "deferred");
+    assert_eq!(task["defer_until"], "2999-07-01T00:00:00.000Z");
+}
+
+#[test]
+fn expired_snooze_shows_open_and_is_recorded_by_the_next_write() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Snoozed earlier");
+    run_json(
+        repo.path(),
+        ["defer", &id, "--until", "2000-01-01T09:30:00Z"],
+    );
+
+    let ready = run_json(repo.path(), ["find", "ready"]);
+    let ids: Vec<&str> = ok_data(&ready.envelope)["tasks"]
+        .as_array()
+        .expect("tasks")
+        .iter()
+        .map(|task| task["id"].as_str().expect("id"))
+        .collect();
+    assert_eq!(ids, vec![id.as_str()]);
+
+    let (status, task) = status_of(repo.path(), &id);
+    assert_eq!(status, "open");
+    assert!(task.get("defer_until").is_none());
+    assert_eq!(wake_events(repo.path(), &id), 0, "reads must not write");
+
+    create_task(repo.path(), "Any write");
+    assert_eq!(wake_events(repo.path(), &id), 1);
+    assert_eq!(status_of(repo.path(), &id).0, "open");
+}
+
+#[test]
+fn defer_until_rejects_malformed_dates() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Bad snooze");
+
+    let result = run_json(repo.path(), ["defer", &id, "--until", "next week"]);
+    assert_validation_error(&result);
+}
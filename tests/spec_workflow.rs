@@ -1,6 +1,6 @@
 mod common;
 
-use common::{create_task, init_repo, run_json};
+use common::{create_task, create_task_with_args, init_repo, run_json, run_json_with_env};
 use serde_json::Value;
 use std::fs;
 use std::path::Path;
@@ -343,6 +343,241 @@ fn update_claim_require_spec_rejects_missing_or_invalid_spec_and_accepts_valid_s
     );
 }
 
+#[test]
+fn spec_new_scaffolds_required_sections_and_checks_ok() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task_with_args(repo.path(), "Scaffolded feature", &["--kind", "feature"]);
+
+    let created = run_json(repo.path(), ["spec", "new", &task_id, "--no-edit"]);
+    assert_eq!(
+        created.cli.code, 0,
+        "spec new failed\nstdout:\n{}\nstderr:\n{}",
+        created.cli.stdout, created.cli.stderr
+    );
+    let created_data = data(&created.envelope);
+    assert_eq!(
+        created_data.get("template").and_then(Value::as_str),
+        Some("feature")
+    );
+    let content = fs::read_to_string(attached_spec_path(repo.path(), created_data))
+        .expect("read scaffolded spec");
+    assert!(content.starts_with("# Scaffolded feature\n"));
+
+    let check = run_json(repo.path(), ["spec", &task_id, "--check"]);
+    assert_eq!(
+        data(&check.envelope).get("ok").and_then(Value::as_bool),
+        Some(true)
+    );
+}
+
+#[test]
+fn spec_new_uses_custom_template_and_editor_output() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Custom template target");
+    let templates = repo.path().join(".tasque/spec-templates");
+    fs::create_dir_all(&templates).expect("create templates dir");
+    fs::write(
+        templates.join("rfc.md"),
+        "# RFC: {{title}}\n\n## Overview\n",
+    )
+    .expect("write template");
+    let editor = repo.path().join("fake-editor.sh");
+    fs::write(&editor, "printf '\\nedited\\n' >> \"$1\"\n").expect("write fake editor");
+    let editor = format!("sh {}", editor.display());
+
+    let created = run_json_with_env(
+        repo.path(),
+        ["spec", "new", &task_id, "--template", "rfc", "--json"],
+        &[("VISUAL", ""), ("EDITOR", &editor)],
+    );
+
+    assert_eq!(created.cli.code, 0, "stderr: {}", created.cli.stderr);
+    let content = fs::read_to_string(attached_spec_path(repo.path(), data(&created.envelope)))
+        .expect("read attached spec");
+    assert_eq!(
+        content,
+        "# RFC: Custom template target\n\n## Overview\n\nedited"
+    );
+}
+
+#[test]
+fn spec_new_rejects_unknown_template() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Unknown template target");
+
+    let created = run_json(
+        repo.path(),
+        [
+            "spec",
+            "new",
+            &task_id,
+            "--template",
+            "missing",
+            "--no-edit",
+        ],
+    );
+
+    assert_eq!(created.cli.code, 1);
+    assert_eq!(error_code(&created.envelope), Some("VALIDATION_ERROR"));
+}
+
+#[test]
+fn spec_diff_shows_drift_and_reattach_accepts_disk_content() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Spec diff drift");
+    let attach = run_json(repo.path(), ["spec", &task_id, "--text", complete_spec()]);
+    assert_eq!(attach.cli.code, 0);
+    let spec_path = attached_spec_path(repo.path(), data(&attach.envelope));
+
+    let clean = run_json(repo.path(), ["spec", "diff", &task_id]);
+    assert_eq!(
+        data(&clean.envelope)
+            .get("drifted")
+            .and_then(Value::as_bool),
+        Some(false)
+    );
+
+    fs::write(&spec_path, format!("{}\nExtra drift.\n", complete_spec())).expect("edit spec");
+    let diff = run_json(repo.path(), ["spec", "diff", &task_id]);
+    assert_eq!(diff.cli.code, 0);
+    let diff_data = data(&diff.envelope);
+    assert_eq!(
+        diff_data.get("drifted").and_then(Value::as_bool),
+        Some(true)
+    );
+    assert_eq!(
+        diff_data.get("baseline_available").and_then(Value::as_bool),
+        Some(true)
+    );
+    let patch = diff_data
+        .get("diff")
+        .and_then(Value::as_str)
+        .expect("diff text");
+    assert!(patch.contains("+Extra drift."), "unexpected diff:\n{patch}");
+
+    let reattach = run_json(repo.path(), ["spec", "reattach", &task_id]);
+    assert_eq!(reattach.cli.code, 0, "stderr: {}", reattach.cli.stderr);
+    let check = run_json(repo.path(), ["spec", &task_id, "--check"]);
+    assert_eq!(
+        data(&check.envelope).get("ok").and_then(Value::as_bool),
+        Some(true)
+    );
+
+    let again = run_json(repo.path(), ["spec", "reattach", &task_id]);
+    assert_eq!(again.cli.code, 1);
+    assert_eq!(error_code(&again.envelope), Some("VALIDATION_ERROR"));
+}
+
+#[test]
+fn spec_history_recovers_overwritten_revisions() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Spec history target");
+    let attach = run_json(repo.path(), ["spec", &task_id, "--text", complete_spec()]);
+    assert_eq!(attach.cli.code, 0);
+    let update = run_json(
+        repo.path(),
+        ["spec", &task_id, "--update", "--text", updated_spec()],
+    );
+    assert_eq!(update.cli.code, 0, "stderr: {}", update.cli.stderr);
+
+    let history = run_json(repo.path(), ["spec", "history", &task_id]);
+    assert_eq!(history.cli.code, 0);
+    let versions = data(&history.envelope)
+        .get("versions")
+        .and_then(Value::as_array)
+        .expect("versions array");
+    assert_eq!(versions.len(), 2);
+    assert_eq!(
+        versions[0].get("current").and_then(Value::as_bool),
+        Some(false)
+    );
+    assert_eq!(
+        versions[1].get("current").and_then(Value::as_bool),
+        Some(true)
+    );
+
+    let first = run_json(repo.path(), ["spec", "show", &task_id, "--version", "1"]);
+    assert_eq!(first.cli.code, 0, "stderr: {}", first.cli.stderr);
+    let spec = data(&first.envelope).get("spec").expect("spec");
+    assert_eq!(
+        spec.get("content").and_then(Value::as_str),
+        Some(complete_spec())
+    );
+    assert_eq!(spec.get("version").and_then(Value::as_u64), Some(1));
+
+    let verb = run_json(repo.path(), ["spec", &task_id, "--show", "--version", "2"]);
+    assert_eq!(
+        data(&verb.envelope)
+            .get("spec")
+            .and_then(|value| value.get("content"))
+            .and_then(Value::as_str),
+        Some(updated_spec())
+    );
+
+    let missing = run_json(repo.path(), ["spec", "show", &task_id, "--version", "3"]);
+    assert_eq!(missing.cli.code, 1);
+    assert_eq!(error_code(&missing.envelope), Some("VALIDATION_ERROR"));
+}
+
+#[test]
+fn find_filters_by_spec_state_including_drift() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let bare = create_task(repo.path(), "No spec yet");
+    let clean = create_task(repo.path(), "Spec untouched");
+    let drifted = create_task(repo.path(), "Spec edited");
+    for id in [&clean, &drifted] {
+        let attach = run_json(repo.path(), ["spec", id, "--text", complete_spec()]);
+        assert_eq!(attach.cli.code, 0);
+        if id == &drifted {
+            let spec_path = attached_spec_path(repo.path(), data(&attach.envelope));
+            fs::write(spec_path, format!("{}\nEdited.\n", complete_spec())).expect("edit spec");
+        }
+    }
+
+    let find = |args: &[&str]| {
+        let result = run_json(repo.path(), args);
+        assert_eq!(result.cli.code, 0, "{}", result.cli.stderr);
+        let mut ids = common::ids_from_task_list(&result.envelope);
+        ids.sort();
+        ids
+    };
+    let mut attached = vec![clean.clone(), drifted.clone()];
+    attached.sort();
+
+    assert_eq!(find(&["find", "open", "--spec", "missing"]), vec![bare]);
+    assert_eq!(find(&["find", "open", "--spec", "attached"]), attached);
+    assert_eq!(
+        find(&["find", "open", "--spec", "drifted"]),
+        vec![drifted.clone()]
+    );
+    assert_eq!(
+        find(&[
+            "find",
+            "open",
+            "--spec",
+            "drifted",
+            "--drift-check",
+            "mtime"
+        ]),
+        vec![drifted.clone()]
+    );
+    assert_eq!(
+        find(&["find", "search", "spec:drifted"]),
+        vec![drifted.clone()]
+    );
+
+    let rejected = run_json(repo.path(), ["find", "open", "--drift-check", "mtime"]);
+    assert_eq!(rejected.cli.code, 1);
+    let rejected = run_json(repo.path(), ["find", "search", "spec:stale"]);
+    assert_eq!(rejected.cli.code, 1);
+}
+
 fn complete_spec() -> &'static str {
     r#"# Spec
 
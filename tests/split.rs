@@ -0,0 +1,75 @@
+mod common;
+
+use common::{
+    create_task, create_task_with_args, init_repo, label_add, make_repo, run_cli, run_json,
+};
+
+#[test]
+fn split_creates_children_that_inherit_from_the_original() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let original = create_task_with_args(repo.path(), "Checkout revamp", &["-p", "0"]);
+    let related = create_task(repo.path(), "Payments");
+    label_add(repo.path(), &original, "web");
+    run_cli(repo.path(), ["assign", &original, "--assignee", "ana"]);
+    run_cli(repo.path(), ["relate", &original, &related]);
+
+    let split = run_json(
+        repo.path(),
+        [
+            "split",
+            &original,
+            "--into",
+            "Cart UI",
+            "--into",
+            "Address form",
+        ],
+    );
+    assert_eq!(split.cli.code, 0, "stderr:\n{}", split.cli.stderr);
+    let parts = split.envelope["data"]["parts"].as_array().unwrap();
+    assert_eq!(parts.len(), 2);
+    for part in parts {
+        assert_eq!(part["parent_id"], original);
+        assert_eq!(part["discovered_from"], original);
+        assert_eq!(part["labels"], serde_json::json!(["web"]));
+        assert_eq!(part["assignee"], "ana");
+        assert_eq!(part["priority"], 0);
+    }
+    assert_eq!(split.envelope["data"]["source"]["status"], "open");
+
+    let first = parts[0]["id"].as_str().unwrap();
+    let shown = run_json(repo.path(), ["show", first]);
+    assert_eq!(
+        shown.envelope["data"]["links"]["relates_to"],
+        serde_json::json!([related])
+    );
+}
+
+#[test]
+fn split_as_siblings_can_close_the_original_as_superseded() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let original = create_task(repo.path(), "Big task");
+
+    let split = run_json(
+        repo.path(),
+        [
+            "split",
+            &original,
+            "--into",
+            "Half one",
+            "--into",
+            "Half two",
+            "--siblings",
+            "--close",
+        ],
+    );
+    assert_eq!(split.cli.code, 0, "stderr:\n{}", split.cli.stderr);
+    let data = &split.envelope["data"];
+    assert!(data["parts"][0]["parent_id"].is_null());
+    assert_eq!(data["source"]["status"], "closed");
+    assert_eq!(data["source"]["superseded_by"], data["parts"][0]["id"]);
+
+    let again = run_json(repo.path(), ["split", &original, "--into", "Third"]);
+    assert_eq!(again.envelope["error"]["code"], "INVALID_STATUS");
+}
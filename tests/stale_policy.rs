@@ -0,0 +1,65 @@
+mod common;
+
+use common::{assert_validation_error, create_task, init_repo, ok_data, run_json, set_config_key};
+use tasque::app::service::TasqueService;
+
+#[test]
+fn stale_apply_label_tags_each_stale_task_once() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Forgotten");
+
+    let first = run_json(
+        repo.path(),
+        ["stale", "--days", "0", "--apply-label", "stale"],
+    );
+    assert_eq!(first.cli.code, 0, "{}", first.cli.stderr);
+    let data = ok_data(&first.envelope);
+    assert_eq!(data["labeled"], serde_json::json!([id]));
+    assert_eq!(data["tasks"][0]["labels"], serde_json::json!(["stale"]));
+
+    let second = run_json(
+        repo.path(),
+        ["stale", "--days", "0", "--apply-label", "stale"],
+    );
+    assert!(ok_data(&second.envelope).get("labeled").is_none());
+}
+
+#[test]
+fn stale_enforce_requires_a_policy() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let result = run_json(repo.path(), ["stale", "enforce"]);
+    assert_validation_error(&result);
+}
+
+#[test]
+fn stale_enforce_labels_and_defers_tasks_past_the_window() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Old backlog item");
+    set_config_key(
+        repo.path(),
+        "stale",
+        serde_json::json!({"stale_days": 14, "label": "stale", "auto_defer": true}),
+    );
+
+    let now = run_json(repo.path(), ["stale", "enforce"]);
+    assert_eq!(now.cli.code, 0, "{}", now.cli.stderr);
+    assert_eq!(ok_data(&now.envelope)["stale"], serde_json::json!([]));
+
+    let later = chrono::Utc::now() + chrono::Duration::days(15);
+    let service = TasqueService::new(repo.path().display().to_string(), "rust-test", move || {
+        later.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+    });
+    let result = service.stale_enforce().expect("enforce stale policy");
+    assert_eq!(result.stale, vec![id.clone()]);
+    assert_eq!(result.labeled, vec![id.clone()]);
+    assert_eq!(result.deferred, vec![id.clone()]);
+
+    let show = run_json(repo.path(), ["show", &id]);
+    let task = &ok_data(&show.envelope)["task"];
+    assert_eq!(task["status"], "deferred");
+    assert_eq!(task["labels"], serde_json::json!(["stale"]));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{create_task, ids_from_task_list, init_repo, ok_data, run_json, set_config_key};
+use serde_json::Value;
+
+fn review_workflow() -> Value {
+    serde_json::json!({
+        "statuses": [
+            "open", "in_progress", "in_review", "qa",
+            "blocked", "deferred", "closed", "canceled"
+        ],
+        "transitions": {
+            "in_progress": ["in_review", "blocked", "open"],
+            "in_review": ["qa", "in_progress"],
+            "qa": ["closed", "in_review"]
+        }
+    })
+}
+
+#[test]
+fn configured_statuses_follow_transitions_and_show_in_reports() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "status_workflow", review_workflow());
+    let id = create_task(repo.path(), "Payment retries");
+
+    let started = run_json(repo.path(), ["start", &id]);
+    assert_eq!(started.cli.code, 0, "{}", started.cli.stdout);
+
+    let skipped = run_json(repo.path(), ["done", &id]);
+    assert_eq!(skipped.cli.code, 1);
+    assert_eq!(skipped.envelope["error"]["code"], "INVALID_TRANSITION");
+    assert_eq!(
+        skipped.envelope["error"]["details"]["allowed"],
+        serde_json::json!(["in_review", "blocked", "open"])
+    );
+
+    let unknown = run_json(repo.path(), ["edit", &id, "--status", "approved"]);
+    assert_eq!(unknown.cli.code, 1);
+    assert_eq!(unknown.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    let review = run_json(repo.path(), ["edit", &id, "--status", "in_review"]);
+    assert_eq!(review.cli.code, 0, "{}", review.cli.stdout);
+    assert_eq!(ok_data(&review.envelope)["task"]["status"], "in_review");
+
+    let listed = run_json(repo.path(), ["find", "status", "in_review"]);
+    assert_eq!(ids_from_task_list(&listed.envelope), vec![id.clone()]);
+    let searched = run_json(repo.path(), ["find", "search", "status:in_review"]);
+    assert_eq!(ids_from_task_list(&searched.envelope), vec![id.clone()]);
+
+    let report = run_json(repo.path(), ["report"]);
+    assert_eq!(
+        ok_data(&report.envelope)["status_counts"],
+        serde_json::json!([{"status": "in_review", "count": 1}])
+    );
+
+    run_json(repo.path(), ["edit", &id, "--status", "qa"]);
+    let done = run_json(repo.path(), ["done", &id]);
+    assert_eq!(done.cli.code, 0, "{}", done.cli.stdout);
+    assert_eq!(ok_data(&done.envelope)["tasks"][0]["status"], "closed");
+}
+
+#[test]
+fn custom_statuses_need_a_status_workflow() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Unconfigured");
+
+    let without = run_json(repo.path(), ["edit", &id, "--status", "in_review"]);
+    assert_eq!(without.cli.code, 1);
+    assert_eq!(without.envelope["error"]["code"], "VALIDATION_ERROR");
+
+    set_config_key(
+        repo.path(),
+        "status_workflow",
+        serde_json::json!({"statuses": ["open", "in_progress", "in_review"]}),
+    );
+    let listed = run_json(repo.path(), ["find", "open"]);
+    assert_ne!(listed.cli.code, 0);
+    assert_eq!(listed.envelope["ok"], false);
+}
+
+#[test]
+fn replay_accepts_a_forbidden_transition_and_doctor_reports_it() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    set_config_key(repo.path(), "status_workflow", review_workflow());
+    let id = create_task(repo.path(), "Synced shortcut");
+    let started = run_json(repo.path(), ["start", &id]);
+    assert_eq!(started.cli.code, 0, "{}", started.cli.stdout);
+
+    // A clone without the workflow closed the task straight from in_progress.
+    let events_file = repo.path().join(".tasque").join("events.jsonl");
+    let mut log = std::fs::read_to_string(&events_file).expect("events");
+    let ts = "2999-01-01T00:00:00.000Z";
+    log.push_str(
+        &serde_json::json!({
+            "id": "01ZZZZZZZZZZZZZZZZZZZZZZZZ",
+            "event_id": "01ZZZZZZZZZZZZZZZZZZZZZZZZ",
+            "ts": ts,
+            "actor": "other-clone",
+            "type": "task.status_set",
+            "task_id": id,
+            "payload": {"status": "closed", "closed_at": ts},
+        })
+        .to_string(),
+    );
+    log.push('\n');
+    std::fs::write(&events_file, log).expect("append event");
+
+    let shown = run_json(repo.path(), ["show", &id]);
+    assert_eq!(shown.cli.code, 0, "{}", shown.cli.stdout);
+    assert_eq!(ok_data(&shown.envelope)["task"]["status"], "closed");
+
+    let doctor = run_json(repo.path(), ["doctor"]);
+    let findings = ok_data(&doctor.envelope)["findings"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let finding = findings
+        .iter()
+        .find(|finding| finding["code"] == "INVALID_TRANSITION")
+        .expect("INVALID_TRANSITION finding");
+    assert_eq!(finding["task_id"], id.as_str());
+    assert_eq!(finding["event_id"], "01ZZZZZZZZZZZZZZZZZZZZZZZZ");
+}
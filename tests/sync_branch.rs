@@ -1,6 +1,6 @@
 mod common;
 
-use common::{make_repo, run_cli};
+use common::{git, make_repo, run_cli, run_json};
 use serde_json::Value;
 use std::fs;
 
@@ -29,3 +29,18 @@ fn sync_branch_requires_git_repo() {
         .and_then(Value::as_str);
     assert_eq!(code, Some("GIT_NOT_AVAILABLE"));
 }
+
+#[test]
+fn init_no_sync_branch_skips_default_sync_setup_in_git_repo() {
+    let repo = make_repo();
+    git(repo.path(), &["init", "-q", "-b", "main"]);
+
+    let result = run_json(repo.path(), ["init", "--no-sync-branch"]);
+    assert_eq!(result.cli.code, 0, "{:?}", result.envelope);
+    assert!(result.envelope["data"].get("sync_setup").is_none());
+
+    let config: Value =
+        serde_json::from_str(&fs::read_to_string(repo.path().join(".tasque/config.json")).unwrap())
+            .unwrap();
+    assert!(config.get("sync_branch").is_none_or(Value::is_null));
+}
@@ -1,48 +1,8 @@
 mod common;
 
-use common::{make_repo, run_cli, run_json};
+use common::{git, make_repo, run_cli, run_json, set_config_key};
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
-use std::process::Command;
-
-fn git(repo: &Path, args: &[&str]) {
-    let output = Command::new("git")
-        // Per-invocation config keeps bare-repo/worktree tests portable when
-        // global Git safety config requires explicit bare repository trust.
-        .arg("-c")
-        .arg("safe.bareRepository=all")
-        .args(args)
-        .current_dir(repo)
-        .output()
-        .expect("git command failed");
-    assert!(
-        output.status.success(),
-        "git {:?} failed\nstdout:{}\nstderr:{}",
-        args,
-        String::from_utf8_lossy(&output.stdout),
-        String::from_utf8_lossy(&output.stderr)
-    );
-}
-
-fn git_out(repo: &Path, args: &[&str]) -> String {
-    let output = Command::new("git")
-        // Same per-command safety override as git(); this helper captures stdout.
-        .arg("-c")
-        .arg("safe.bareRepository=all")
-        .args(args)
-        .current_dir(repo)
-        .output()
-        .expect("git command failed");
-    assert!(
-        output.status.success(),
-        "git {:?} failed\nstdout:{}\nstderr:{}",
-        args,
-        String::from_utf8_lossy(&output.stdout),
-        String::from_utf8_lossy(&output.stderr)
-    );
-    String::from_utf8_lossy(&output.stdout).trim().to_string()
-}
 
 #[test]
 fn hooks_install_and_uninstall_manage_pre_push_hook() {
@@ -150,14 +110,14 @@ fn custom_worktree_name_auto_commits_and_syncs_pending_changes() {
     );
 
     let wt = root.join(".git").join("custom-sync");
-    let before = git_out(&wt, &["rev-list", "--count", "HEAD"])
+    let before = git(&wt, &["rev-list", "--count", "HEAD"])
         .parse::<u64>()
         .expect("commit count");
 
     let create = run_cli(root, ["create", "Sync auto commit test"]);
     assert_eq!(create.code, 0, "stderr: {}", create.stderr);
 
-    let after = git_out(&wt, &["rev-list", "--count", "HEAD"])
+    let after = git(&wt, &["rev-list", "--count", "HEAD"])
         .parse::<u64>()
         .expect("commit count");
     assert!(after > before, "expected auto-commit after create");
@@ -169,7 +129,7 @@ fn custom_worktree_name_auto_commits_and_syncs_pending_changes() {
     let sync_result = run_cli(root, ["sync", "--no-push"]);
     assert_eq!(sync_result.code, 0, "stderr: {}", sync_result.stderr);
 
-    let status = git_out(&wt, &["status", "--porcelain"]);
+    let status = git(&wt, &["status", "--porcelain"]);
     assert!(status.is_empty(), "expected clean worktree after sync");
 }
 
@@ -219,7 +179,7 @@ fn sync_without_upstream_pushes_branch_to_origin_and_sets_upstream() {
 
     let wt = root.join(".git").join("tsq-sync");
     assert_eq!(
-        git_out(
+        git(
             &wt,
             &[
                 "rev-parse",
@@ -230,7 +190,7 @@ fn sync_without_upstream_pushes_branch_to_origin_and_sets_upstream() {
         ),
         "origin/tsq-sync"
     );
-    assert!(!git_out(&remote, &["show-ref", "--heads", "tsq-sync"]).is_empty());
+    assert!(!git(&remote, &["show-ref", "--heads", "tsq-sync"]).is_empty());
 }
 
 #[test]
@@ -299,7 +259,7 @@ fn migrate_pushes_sync_branch_to_main_upstream_before_clearing_root_events() {
 
     let wt = root.join(".git").join("tsq-sync");
     assert_eq!(
-        git_out(
+        git(
             &wt,
             &[
                 "rev-parse",
@@ -310,7 +270,7 @@ fn migrate_pushes_sync_branch_to_main_upstream_before_clearing_root_events() {
         ),
         "origin/tsq-sync"
     );
-    assert!(!git_out(&remote, &["show-ref", "--heads", "tsq-sync"]).is_empty());
+    assert!(!git(&remote, &["show-ref", "--heads", "tsq-sync"]).is_empty());
     let root_events =
         fs::read_to_string(root.join(".tasque").join("events.jsonl")).expect("events");
     assert!(
@@ -371,12 +331,451 @@ fn fresh_clone_fetches_remote_sync_branch_and_creates_worktree() {
             .join("events.jsonl")
             .exists()
     );
-    assert_eq!(git_out(&clone, &["branch", "--show-current"]), "main");
+    assert_eq!(git(&clone, &["branch", "--show-current"]), "main");
     assert!(
-        git_out(
+        git(
             &clone.join(".git").join("tsq-sync"),
             &["branch", "--show-current"]
         )
         .contains("tsq-sync")
     );
 }
+
+#[test]
+fn sync_push_and_pull_rebase_concurrent_events_through_remote() {
+    let repo = make_repo();
+    let base = repo.path();
+    let alice = base.join("alice");
+    fs::create_dir(&alice).expect("alice dir");
+
+    git(&alice, &["init", "-b", "main"]);
+    git(&alice, &["config", "user.name", "alice"]);
+    git(&alice, &["config", "user.email", "alice@example.com"]);
+    let init = run_cli(&alice, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    git(&alice, &["add", ".tasque/config.json", ".gitattributes"]);
+    git(&alice, &["commit", "-m", "seed main config"]);
+
+    let remote = base.join("origin.git");
+    let remote_arg = remote.to_string_lossy().to_string();
+    git(base, &["init", "--bare", remote_arg.as_str()]);
+    git(&remote, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+    git(&alice, &["remote", "add", "origin", remote_arg.as_str()]);
+    git(&alice, &["push", "-u", "origin", "main"]);
+    let shared = run_json(&alice, ["create", "Shared task"]);
+    assert_eq!(shared.cli.code, 0, "stderr: {}", shared.cli.stderr);
+    let shared_id = shared.envelope["data"]["task"]["id"]
+        .as_str()
+        .expect("task id")
+        .to_string();
+    let first_push = run_cli(&alice, ["sync", "push"]);
+    assert_eq!(first_push.code, 0, "stderr: {}", first_push.stderr);
+
+    let bob = base.join("bob");
+    let bob_arg = bob.to_string_lossy().to_string();
+    git(base, &["clone", remote_arg.as_str(), bob_arg.as_str()]);
+    git(&bob, &["config", "user.name", "bob"]);
+    git(&bob, &["config", "user.email", "bob@example.com"]);
+
+    // Concurrent appends to the same event log on both clones.
+    let alice_start = run_cli(&alice, ["start", shared_id.as_str()]);
+    assert_eq!(alice_start.code, 0, "stderr: {}", alice_start.stderr);
+    let bob_label = run_cli(&bob, ["label", shared_id.as_str(), "backend"]);
+    assert_eq!(bob_label.code, 0, "stderr: {}", bob_label.stderr);
+
+    let alice_push = run_json(&alice, ["sync", "push"]);
+    assert_eq!(alice_push.cli.code, 0, "stderr: {}", alice_push.cli.stderr);
+    let bob_push = run_json(&bob, ["sync", "push"]);
+    assert_eq!(
+        bob_push.cli.code, 0,
+        "stdout: {}\nstderr: {}",
+        bob_push.cli.stdout, bob_push.cli.stderr
+    );
+    assert_eq!(bob_push.envelope["data"]["pulled_commits"], 1);
+    assert_eq!(bob_push.envelope["data"]["pushed"], true);
+
+    let alice_pull = run_json(&alice, ["sync", "pull"]);
+    assert_eq!(alice_pull.cli.code, 0, "stderr: {}", alice_pull.cli.stderr);
+    assert_eq!(alice_pull.envelope["data"]["pushed"], false);
+
+    for root in [&alice, &bob] {
+        let show = run_json(root, ["show", shared_id.as_str()]);
+        assert_eq!(show.cli.code, 0, "stderr: {}", show.cli.stderr);
+        let task = &show.envelope["data"]["task"];
+        assert_eq!(task["status"], "in_progress");
+        assert_eq!(task["labels"], serde_json::json!(["backend"]));
+    }
+}
+
+#[test]
+fn sync_pull_without_remote_reports_error() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+
+    let pull = run_json(root, ["sync", "pull"]);
+    assert_eq!(pull.cli.code, 1);
+    assert_eq!(pull.envelope["error"]["code"], "SYNC_NO_REMOTE");
+}
+
+#[test]
+fn auto_sync_push_publishes_each_mutation_to_origin() {
+    let repo = make_repo();
+    let base = repo.path();
+    let root = base.join("repo");
+    fs::create_dir(&root).expect("repo dir");
+    git(&root, &["init", "-b", "main"]);
+    git(&root, &["config", "user.name", "rust-test"]);
+    git(&root, &["config", "user.email", "rust-test@example.com"]);
+    let remote = base.join("origin.git");
+    let remote_arg = remote.to_string_lossy().to_string();
+    git(base, &["init", "--bare", remote_arg.as_str()]);
+    git(&root, &["remote", "add", "origin", remote_arg.as_str()]);
+
+    let init = run_cli(&root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    let wt = root.join(".git").join("tsq-sync");
+    set_config_key(&wt, "auto_sync", serde_json::json!({"push": true}));
+
+    let create = run_cli(&root, ["create", "Auto pushed task"]);
+    assert_eq!(create.code, 0, "stderr: {}", create.stderr);
+
+    assert_eq!(
+        git(&remote, &["rev-parse", "refs/heads/tsq-sync"]),
+        git(&wt, &["rev-parse", "HEAD"])
+    );
+}
+
+#[test]
+fn auto_sync_throttle_leaves_events_uncommitted_until_sync() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    let wt = root.join(".git").join("tsq-sync");
+    set_config_key(&wt, "auto_sync", serde_json::json!({"throttle_secs": 3600}));
+    let before = git(&wt, &["rev-parse", "HEAD"]);
+
+    let create = run_cli(root, ["create", "Throttled task"]);
+    assert_eq!(create.code, 0, "stderr: {}", create.stderr);
+
+    assert_eq!(git(&wt, &["rev-parse", "HEAD"]), before);
+    assert!(!git(&wt, &["status", "--porcelain"]).is_empty());
+
+    let sync_result = run_cli(root, ["sync", "--no-push"]);
+    assert_eq!(sync_result.code, 0, "stderr: {}", sync_result.stderr);
+    assert!(git(&wt, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn sync_daemon_once_syncs_and_records_status_file() {
+    let repo = make_repo();
+    let base = repo.path();
+    let root = base.join("repo");
+    fs::create_dir(&root).expect("repo dir");
+    git(&root, &["init", "-b", "main"]);
+    git(&root, &["config", "user.name", "rust-test"]);
+    git(&root, &["config", "user.email", "rust-test@example.com"]);
+    let remote = base.join("origin.git");
+    let remote_arg = remote.to_string_lossy().to_string();
+    git(base, &["init", "--bare", remote_arg.as_str()]);
+    git(&root, &["remote", "add", "origin", remote_arg.as_str()]);
+    let init = run_cli(&root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    let create = run_cli(&root, ["create", "Daemon synced task"]);
+    assert_eq!(create.code, 0, "stderr: {}", create.stderr);
+
+    let daemon = run_json(&root, ["sync", "daemon", "--once"]);
+    assert_eq!(daemon.cli.code, 0, "stderr: {}", daemon.cli.stderr);
+    let data = &daemon.envelope["data"];
+    assert_eq!(data["consecutive_failures"], 0);
+    assert_eq!(data["ahead"], 0);
+    assert_eq!(data["behind"], 0);
+    assert!(data["last_sync_at"].is_string());
+
+    let wt = root.join(".git").join("tsq-sync");
+    assert_eq!(
+        git(&remote, &["rev-parse", "refs/heads/tsq-sync"]),
+        git(&wt, &["rev-parse", "HEAD"])
+    );
+    let status_file = root
+        .join(".git")
+        .join("worktrees")
+        .join("tsq-sync")
+        .join("tsq-sync-status.json");
+    let status: Value =
+        serde_json::from_str(&fs::read_to_string(status_file).expect("status file")).unwrap();
+    assert_eq!(status["branch"], "tsq-sync");
+    assert!(git(&wt, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn sync_daemon_once_reports_failures_with_backoff() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+
+    let daemon = run_json(root, ["sync", "daemon", "--once", "--interval", "10"]);
+    assert_eq!(daemon.cli.code, 2);
+    assert_eq!(daemon.envelope["error"]["code"], "SYNC_FAILED");
+    let details = &daemon.envelope["error"]["details"];
+    assert_eq!(details["consecutive_failures"], 1);
+    assert_eq!(details["next_attempt_secs"], 20);
+    assert!(
+        details["last_error"]
+            .as_str()
+            .is_some_and(|error| error.starts_with("SYNC_NO_REMOTE"))
+    );
+}
+
+#[test]
+fn sync_status_reports_pending_events_and_remote_counts() {
+    let repo = make_repo();
+    let base = repo.path();
+    let root = base.join("repo");
+    fs::create_dir(&root).expect("repo dir");
+    git(&root, &["init", "-b", "main"]);
+    git(&root, &["config", "user.name", "rust-test"]);
+    git(&root, &["config", "user.email", "rust-test@example.com"]);
+    let remote = base.join("origin.git");
+    let remote_arg = remote.to_string_lossy().to_string();
+    git(base, &["init", "--bare", remote_arg.as_str()]);
+    git(&root, &["remote", "add", "origin", remote_arg.as_str()]);
+    let init = run_cli(&root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    let sync = run_cli(&root, ["sync"]);
+    assert_eq!(sync.code, 0, "stderr: {}", sync.stderr);
+
+    let wt = root.join(".git").join("tsq-sync");
+    set_config_key(&wt, "auto_sync", serde_json::json!({"commit": false}));
+    let create = run_cli(&root, ["create", "Pending task"]);
+    assert_eq!(create.code, 0, "stderr: {}", create.stderr);
+
+    let status = run_json(&root, ["sync", "status", "--check"]);
+    assert_eq!(status.cli.code, 0, "stderr: {}", status.cli.stderr);
+    let data = &status.envelope["data"];
+    assert_eq!(data["configured"], true);
+    assert_eq!(data["branch"], "tsq-sync");
+    assert_eq!(data["worktree_valid"], true);
+    assert_eq!(data["uncommitted_events"], 1);
+    assert_eq!(data["dirty"], true);
+    assert_eq!(data["remote"], "origin");
+    assert_eq!(data["ahead"], 0);
+    assert_eq!(data["behind"], 0);
+    assert_eq!(data["merge_driver"]["gitattributes"], true);
+    assert!(data["merge_driver"]["driver"].is_string());
+    assert_eq!(data["healthy"], true);
+
+    let sync = run_cli(&root, ["sync", "--no-push"]);
+    assert_eq!(sync.code, 0, "stderr: {}", sync.stderr);
+    let status = run_json(&root, ["sync", "status"]);
+    let data = &status.envelope["data"];
+    assert_eq!(data["uncommitted_events"], 0);
+    assert_eq!(data["ahead"], 1);
+    assert_eq!(data["behind"], 0);
+}
+
+#[test]
+fn sync_status_check_fails_without_remote() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+
+    let status = run_json(root, ["sync", "status"]);
+    assert_eq!(status.cli.code, 0, "stderr: {}", status.cli.stderr);
+    assert_eq!(status.envelope["data"]["healthy"], false);
+
+    let check = run_json(root, ["sync", "status", "--check"]);
+    assert_eq!(check.cli.code, 1);
+    assert_eq!(check.envelope["error"]["code"], "SYNC_UNHEALTHY");
+    assert_eq!(
+        check.envelope["error"]["details"]["problems"][0],
+        "no remote configured for the sync branch"
+    );
+}
+
+#[test]
+fn sync_disable_moves_data_back_to_root_and_stays_local() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    let create = run_json(root, ["create", "Survives teardown"]);
+    assert_eq!(create.cli.code, 0, "stderr: {}", create.cli.stderr);
+    let id = create.envelope["data"]["task"]["id"]
+        .as_str()
+        .expect("task id")
+        .to_string();
+    let spec = run_cli(root, ["spec", id.as_str(), "--text", "# Spec\n\nbody\n"]);
+    assert_eq!(spec.code, 0, "stderr: {}", spec.stderr);
+
+    let disable = run_json(root, ["sync", "disable", "--delete-branch"]);
+    assert_eq!(disable.cli.code, 0, "stderr: {}", disable.cli.stderr);
+    let data = &disable.envelope["data"];
+    assert_eq!(data["branch"], "tsq-sync");
+    assert_eq!(data["branch_deleted"], true);
+    assert!(data["events_migrated"].as_u64().unwrap_or(0) >= 2);
+    assert!(data["specs_copied"].as_u64().unwrap_or(0) >= 1);
+
+    assert!(!root.join(".git").join("tsq-sync").exists());
+    assert!(git(root, &["branch", "--list", "tsq-sync"]).is_empty());
+    assert!(
+        root.join(".tasque")
+            .join("specs")
+            .join(&id)
+            .join("spec.md")
+            .exists()
+    );
+    let config: Value = serde_json::from_str(
+        &fs::read_to_string(root.join(".tasque").join("config.json")).expect("config"),
+    )
+    .expect("config json");
+    assert!(config.get("sync_branch").is_none());
+    assert_eq!(config["sync_disabled"], true);
+
+    let show = run_json(root, ["show", id.as_str()]);
+    assert_eq!(show.cli.code, 0, "stderr: {}", show.cli.stderr);
+    assert_eq!(show.envelope["data"]["task"]["title"], "Survives teardown");
+    let next = run_cli(root, ["create", "Local only"]);
+    assert_eq!(next.code, 0, "stderr: {}", next.stderr);
+    assert!(!root.join(".git").join("tsq-sync").exists());
+    let events = fs::read_to_string(root.join(".tasque").join("events.jsonl")).expect("events");
+    assert!(events.contains("Local only"));
+
+    let status = run_json(root, ["sync", "status"]);
+    assert_eq!(status.envelope["data"]["configured"], false);
+}
+
+#[test]
+fn sync_routes_shard_by_project_path_and_find_workspace_merges() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    set_config_key(
+        root,
+        "sync_routes",
+        serde_json::json!([{"branch": "tsq-sync-web", "path": "web/"}]),
+    );
+    let web = root.join("web").join("src");
+    fs::create_dir_all(&web).expect("web dir");
+
+    let core = run_cli(root, ["create", "Core task"]);
+    assert_eq!(core.code, 0, "stderr: {}", core.stderr);
+    let routed = run_cli(&web, ["create", "Web task"]);
+    assert_eq!(routed.code, 0, "stderr: {}", routed.stderr);
+
+    let shard = root.join(".git").join("tsq-sync-web");
+    let shard_events = fs::read_to_string(shard.join(".tasque").join("events.jsonl")).unwrap();
+    assert!(shard_events.contains("Web task"));
+    assert!(!shard_events.contains("Core task"));
+    assert_eq!(git(&shard, &["branch", "--show-current"]), "tsq-sync-web");
+
+    let titles = |envelope: &Value| -> Vec<String> {
+        envelope["data"]["tasks"]
+            .as_array()
+            .expect("tasks")
+            .iter()
+            .map(|task| task["title"].as_str().unwrap_or_default().to_string())
+            .collect()
+    };
+    assert_eq!(
+        titles(&run_json(root, ["find", "open"]).envelope),
+        ["Core task"]
+    );
+    assert_eq!(
+        titles(&run_json(&web, ["find", "open"]).envelope),
+        ["Web task"]
+    );
+
+    let merged = run_json(root, ["find", "open", "--workspace"]);
+    assert_eq!(merged.cli.code, 0, "stderr: {}", merged.cli.stderr);
+    let tasks = merged.envelope["data"]["tasks"].as_array().expect("tasks");
+    assert_eq!(tasks.len(), 2);
+    let workspace_of = |title: &str| {
+        tasks
+            .iter()
+            .find(|task| task["title"] == title)
+            .map(|task| task["workspace"].clone())
+    };
+    assert_eq!(workspace_of("Core task"), Some(Value::from("tsq-sync")));
+    assert_eq!(workspace_of("Web task"), Some(Value::from("tsq-sync-web")));
+
+    let human = run_cli(&web, ["find", "ready", "--workspace"]);
+    assert_eq!(human.code, 0, "stderr: {}", human.stderr);
+    assert!(human.stdout.contains("WORKSPACE"));
+    assert!(human.stdout.contains("tsq-sync-web"));
+}
+
+#[test]
+fn watch_workspace_merges_every_shard_with_a_workspace_column() {
+    let repo = make_repo();
+    let root = repo.path();
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.name", "rust-test"]);
+    git(root, &["config", "user.email", "rust-test@example.com"]);
+    let init = run_cli(root, ["init"]);
+    assert_eq!(init.code, 0, "stderr: {}", init.stderr);
+    set_config_key(
+        root,
+        "sync_routes",
+        serde_json::json!([{"branch": "tsq-sync-api", "path": "api/"}]),
+    );
+    let api = root.join("api");
+    fs::create_dir_all(&api).expect("api dir");
+    assert_eq!(run_cli(root, ["create", "Core task"]).code, 0);
+    assert_eq!(run_cli(&api, ["create", "Api task"]).code, 0);
+
+    let frame = run_json(root, ["watch", "--workspace", "--once"]);
+    assert_eq!(frame.cli.code, 0, "stderr: {}", frame.cli.stderr);
+    let data = &frame.envelope["data"];
+    assert_eq!(data["summary"]["total"], 2);
+    let mut tagged: Vec<(String, String)> = data["tasks"]
+        .as_array()
+        .expect("tasks")
+        .iter()
+        .map(|task| {
+            (
+                task["title"].as_str().unwrap_or_default().to_string(),
+                task["workspace"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+    tagged.sort();
+    assert_eq!(
+        tagged,
+        [
+            ("Api task".to_string(), "tsq-sync-api".to_string()),
+            ("Core task".to_string(), "tsq-sync".to_string()),
+        ]
+    );
+
+    let human = run_cli(&api, ["watch", "--workspace", "--once"]);
+    assert_eq!(human.code, 0, "stderr: {}", human.stderr);
+    assert!(human.stdout.contains("tsq-sync-api"));
+    assert!(human.stdout.contains("Core task"));
+
+    let deltas = run_json(root, ["watch", "--workspace", "--deltas", "--once"]);
+    assert_ne!(deltas.cli.code, 0);
+}
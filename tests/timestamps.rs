@@ -0,0 +1,30 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, run_cli};
+
+#[test]
+fn timestamps_flag_switches_between_relative_and_absolute() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Timed");
+
+    let relative = run_cli(repo.path(), ["history", id.as_str()]);
+    assert_eq!(relative.code, 0, "{}", relative.stderr);
+    assert!(
+        relative.stdout.starts_with("just now "),
+        "{}",
+        relative.stdout
+    );
+
+    let absolute = run_cli(
+        repo.path(),
+        ["--timestamps", "absolute", "history", id.as_str()],
+    );
+    assert_eq!(absolute.code, 0, "{}", absolute.stderr);
+    assert!(!absolute.stdout.contains("just now"), "{}", absolute.stdout);
+    assert_eq!(absolute.stdout.as_bytes()[10], b' ', "{}", absolute.stdout);
+
+    let both = run_cli(repo.path(), ["find", "open", "--timestamps", "both"]);
+    assert!(both.stdout.contains("UPDATED"), "{}", both.stdout);
+    assert!(both.stdout.contains("(just now)"), "{}", both.stdout);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{create_task, init_repo, make_repo, ok_data, run_cli, run_cli_with_env, run_json};
+
+#[test]
+fn timing_reports_phases_in_meta_or_on_stderr() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    create_task(repo.path(), "Slow repo");
+
+    let timed = run_json(repo.path(), ["find", "open", "--timing"]);
+    assert_eq!(timed.cli.code, 0, "{}", timed.cli.stderr);
+    let timing = &timed.envelope["meta"]["timing"];
+    let phases: Vec<&str> = timing["phases"]
+        .as_array()
+        .expect("phases")
+        .iter()
+        .map(|phase| phase["phase"].as_str().unwrap())
+        .collect();
+    assert!(phases.contains(&"filter") && phases.contains(&"render"));
+    assert!(timing["total_ms"].as_f64().unwrap() > 0.0);
+
+    let plain = run_json(repo.path(), ["find", "open"]);
+    assert!(plain.envelope.get("meta").is_none());
+
+    let human = run_cli(repo.path(), ["find", "open", "--timing"]);
+    assert_eq!(human.code, 0, "{}", human.stderr);
+    let line = human.stderr.lines().last().unwrap_or_default();
+    assert!(line.starts_with("timing "), "{}", human.stderr);
+    assert!(line.contains(" render=") && line.contains(" total="));
+    assert!(!human.stdout.contains("timing"));
+}
+
+#[test]
+fn flag_lookalikes_in_positional_values_do_not_enable_diagnostics() {
+    let repo = make_repo();
+    init_repo(repo.path());
+
+    let created = run_cli_with_env(
+        repo.path(),
+        ["create", "--", "-v", "--timing"],
+        &[("TSQ_LOG", "")],
+    );
+    assert_eq!(created.code, 0, "{}", created.stderr);
+    assert!(created.stderr.is_empty(), "{}", created.stderr);
+
+    let verbose = run_cli_with_env(repo.path(), ["find", "open", "-v"], &[("TSQ_LOG", "")]);
+    assert!(
+        verbose.stderr.contains("storage.load"),
+        "{}",
+        verbose.stderr
+    );
+}
+
+#[test]
+fn bench_replays_the_log_the_requested_number_of_times() {
+    let repo = make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "First");
+    create_task(repo.path(), "Second");
+    run_json(repo.path(), ["start", &id]);
+
+    let bench = run_json(repo.path(), ["bench", "--runs", "3"]);
+    assert_eq!(bench.cli.code, 0, "{}", bench.cli.stderr);
+    let data = ok_data(&bench.envelope);
+    assert_eq!(data["runs"], 3);
+    assert_eq!(data["tasks"], 2);
+    assert!(data["events"].as_u64().unwrap() >= 3);
+    let apply = &data["apply"];
+    assert!(apply["min_ms"].as_f64() <= apply["median_ms"].as_f64());
+    assert!(apply["median_ms"].as_f64() <= apply["max_ms"].as_f64());
+
+    let human = run_cli(repo.path(), ["bench", "--runs", "1"]);
+    assert!(human.stdout.contains("throughput="), "{}", human.stdout);
+
+    let rejected = run_json(repo.path(), ["bench", "--runs", "0"]);
+    assert_eq!(rejected.envelope["error"]["code"], "VALIDATION_ERROR");
+}
@@ -342,3 +342,27 @@ fn strip_ansi(value: &str) -> String {
     }
     out
 }
+
+#[test]
+fn tui_once_json_applies_query_filter() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let urgent = create_task_with_args(repo.path(), "Urgent task", &["--priority", "0"]);
+    create_task_with_args(repo.path(), "Routine task", &["--priority", "2"]);
+
+    let result = run_json(repo.path(), ["tui", "--once", "--query", "priority<1"]);
+
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stderr);
+    let ids: Vec<&str> = result.envelope["data"]["tasks"]
+        .as_array()
+        .expect("tasks array")
+        .iter()
+        .filter_map(|task| task["id"].as_str())
+        .collect();
+    assert_eq!(ids, vec![urgent.as_str()]);
+    assert_eq!(
+        result.envelope["data"]["filters"]["query"].as_str(),
+        Some("priority<1")
+    );
+}
@@ -1,6 +1,6 @@
 mod common;
 
-use common::{create_task, init_repo};
+use common::{create_task, create_task_with_args, init_repo, ok_data, run_json};
 use tasque::app::service::TasqueService;
 use tasque::app::service_types::DepTreeInput;
 use tasque::cli::action::GlobalOpts;
@@ -26,9 +26,11 @@ fn block_and_unblock_mutate_blocking_dependency() {
     let code = execute_block(
         &service,
         BlockArgs {
-            child: child.clone(),
-            by: "by".to_string(),
-            blocker: blocker.clone(),
+            child: Some(child.clone()),
+            by: Some("by".to_string()),
+            blocker: Some(blocker.clone()),
+            dep_type: "blocks".to_string(),
+            from_file: None,
         },
         opts,
     );
@@ -39,6 +41,7 @@ fn block_and_unblock_mutate_blocking_dependency() {
             id: child.clone(),
             direction: None,
             depth: None,
+            statuses: None,
             exact_id: false,
         })
         .expect("dep tree");
@@ -51,6 +54,7 @@ fn block_and_unblock_mutate_blocking_dependency() {
             child: child.clone(),
             by: "by".to_string(),
             blocker,
+            dep_type: "blocks".to_string(),
         },
         opts,
     );
@@ -61,6 +65,7 @@ fn block_and_unblock_mutate_blocking_dependency() {
             id: child,
             direction: None,
             depth: None,
+            statuses: None,
             exact_id: false,
         })
         .expect("dep tree after unblock");
@@ -81,7 +86,9 @@ fn order_unorder_and_deps_use_starts_after_dependency() {
         OrderArgs {
             later: later.clone(),
             after: "after".to_string(),
-            earlier: earlier.clone(),
+            earlier: Some(earlier.clone()),
+            before: None,
+            place_after: None,
         },
         opts,
     );
@@ -102,6 +109,8 @@ fn order_unorder_and_deps_use_starts_after_dependency() {
                 id: later.clone(),
                 direction: "up".to_string(),
                 depth: Some("1".to_string()),
+                statuses: None,
+                flat: false,
             },
             opts,
         ),
@@ -128,6 +137,67 @@ fn order_unorder_and_deps_use_starts_after_dependency() {
     );
 }
 
+#[test]
+fn order_before_and_after_rank_children_within_parent() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let parent = create_task_with_args(repo.path(), "Parent", &["--kind", "epic"]);
+    let children: Vec<String> = ["First", "Second", "Third"]
+        .iter()
+        .map(|title| create_task_with_args(repo.path(), title, &["--parent", parent.as_str()]))
+        .collect();
+
+    let moved = run_json(
+        repo.path(),
+        ["order", &parent, &children[2], "--before", &children[0]],
+    );
+    assert_eq!(moved.cli.code, 0, "{}", moved.cli.stderr);
+    let expected = [&children[2], &children[0], &children[1]];
+    assert_eq!(
+        ok_data(&moved.envelope)["children"]
+            .as_array()
+            .expect("children")
+            .iter()
+            .map(|id| id.as_str().expect("id"))
+            .collect::<Vec<_>>(),
+        expected.iter().map(|id| id.as_str()).collect::<Vec<_>>()
+    );
+
+    let moved = run_json(
+        repo.path(),
+        ["order", &parent, &children[0], "--after", &children[1]],
+    );
+    assert_eq!(moved.cli.code, 0, "{}", moved.cli.stderr);
+
+    let tree = run_json(repo.path(), ["find", "open", "--tree"]);
+    let roots = ok_data(&tree.envelope)["tree"].as_array().expect("tree");
+    let parent_node = roots
+        .iter()
+        .find(|node| node["task"]["id"] == parent.as_str())
+        .expect("parent node");
+    let order = parent_node["children"]
+        .as_array()
+        .expect("children")
+        .iter()
+        .map(|node| node["task"]["id"].as_str().expect("id").to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        order,
+        vec![
+            children[2].clone(),
+            children[1].clone(),
+            children[0].clone()
+        ]
+    );
+
+    let stranger = create_task(repo.path(), "Not a child");
+    let rejected = run_json(
+        repo.path(),
+        ["order", &parent, &stranger, "--before", &children[0]],
+    );
+    assert_eq!(rejected.cli.code, 1);
+}
+
 #[test]
 fn relate_and_unrelate_mutate_bidirectional_relation() {
     let repo = common::make_repo();
@@ -143,6 +213,7 @@ fn relate_and_unrelate_mutate_bidirectional_relation() {
             RelateArgs {
                 a: a.clone(),
                 b: b.clone(),
+                rel_type: None,
             },
             opts,
         ),
@@ -157,6 +228,7 @@ fn relate_and_unrelate_mutate_bidirectional_relation() {
             UnrelateArgs {
                 a: a.clone(),
                 b: b.clone(),
+                rel_type: None,
             },
             opts,
         ),
@@ -186,6 +258,8 @@ fn label_unlabel_and_labels_use_existing_label_service() {
             LabelArgs {
                 id: id.clone(),
                 label: "design".to_string(),
+                color: None,
+                clear: false,
             },
             opts,
         ),
@@ -221,9 +295,11 @@ fn malformed_sentence_tokens_return_validation_error_with_example() {
     let code = execute_block(
         &service,
         BlockArgs {
-            child: "tsq-aaaaaaaa".to_string(),
-            by: "from".to_string(),
-            blocker: "tsq-bbbbbbbb".to_string(),
+            child: Some("tsq-aaaaaaaa".to_string()),
+            by: Some("from".to_string()),
+            blocker: Some("tsq-bbbbbbbb".to_string()),
+            dep_type: "blocks".to_string(),
+            from_file: None,
         },
         opts(),
     );
@@ -249,5 +325,7 @@ fn opts() -> GlobalOpts {
     GlobalOpts {
         json: true,
         exact_id: false,
+        dry_run: false,
+        porcelain: None,
     }
 }
@@ -340,6 +340,92 @@ fn defer_note_json_includes_note_event_id() {
     assert!(notes[0]["note"]["event_id"].is_string());
 }
 
+#[test]
+fn reopen_revives_canceled_tasks_only_with_from_canceled() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let id = create_task(repo.path(), "Canceled too early");
+    let cancel = run_json(repo.path(), ["cancel", &id]);
+    assert_eq!(cancel.cli.code, 0);
+
+    let plain = run_json(repo.path(), ["reopen", &id]);
+    assert_eq!(plain.cli.code, 1);
+    assert_validation_error(&plain);
+    assert!(
+        plain.envelope["error"]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("--from canceled"))
+    );
+
+    let revived = run_json(
+        repo.path(),
+        [
+            "reopen",
+            &id,
+            "--from",
+            "canceled",
+            "--note",
+            "still needed",
+        ],
+    );
+    assert_eq!(revived.cli.code, 0, "{}", revived.cli.stdout);
+    assert_eq!(revived.envelope["data"]["tasks"][0]["status"], "open");
+
+    let events = std::fs::read_to_string(repo.path().join(".tasque/events.jsonl")).unwrap();
+    let reopened = events
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .rfind(|event| event["type"] == "task.status_set")
+        .expect("status event");
+    assert_eq!(reopened["payload"]["status"], "open");
+    assert_eq!(reopened["payload"]["reopened_from"], "canceled");
+
+    let closed_only = run_json(repo.path(), ["reopen", &id, "--from", "canceled"]);
+    assert_eq!(closed_only.cli.code, 1);
+    assert_validation_error(&closed_only);
+}
+
+#[test]
+fn done_filter_previews_until_yes_then_closes_matches() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let stale = create_task(repo.path(), "Old spike");
+    let other = create_task(repo.path(), "Other spike");
+    let keep = create_task(repo.path(), "Not a spike");
+    run_json(repo.path(), ["label", &stale, "spike"]);
+    run_json(repo.path(), ["label", &other, "spike"]);
+    run_json(repo.path(), ["cancel", &other]);
+    let query = "label:spike status:open updated<2999-01-01";
+
+    let preview = run_json(
+        repo.path(),
+        ["done", "--filter", query, "--reason", "stale"],
+    );
+    assert_eq!(preview.cli.code, 0, "{}", preview.cli.stdout);
+    assert_eq!(preview.envelope["data"]["confirmed"], false);
+    assert_eq!(preview.envelope["data"]["matched"], 1);
+    assert_eq!(preview.envelope["data"]["tasks"][0]["id"], stale);
+    let shown = run_json(repo.path(), ["show", &stale]);
+    assert_eq!(shown.envelope["data"]["task"]["status"], "open");
+
+    let closed = run_json(
+        repo.path(),
+        ["done", "--filter", query, "--reason", "stale", "--yes"],
+    );
+    assert_eq!(closed.cli.code, 0, "{}", closed.cli.stdout);
+    assert_eq!(closed.envelope["data"]["confirmed"], true);
+    assert_eq!(closed.envelope["data"]["tasks"][0]["status"], "closed");
+    let shown = run_json(repo.path(), ["show", &keep]);
+    assert_eq!(shown.envelope["data"]["task"]["status"], "open");
+
+    let none = run_json(repo.path(), ["done", "--filter", query, "--yes"]);
+    assert_eq!(none.cli.code, 0, "{}", none.cli.stdout);
+    assert_eq!(none.envelope["data"]["matched"], 0);
+
+    let bad_date = run_json(repo.path(), ["done", "--filter", "updated<someday"]);
+    assert_validation_error(&bad_date);
+}
+
 #[test]
 fn lifecycle_multi_id_verbs_reject_empty_ids() {
     let repo = common::make_repo();
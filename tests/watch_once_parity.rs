@@ -256,3 +256,110 @@ fn watch_once_json_reports_events_corruption_with_error_envelope() {
         Some("EVENTS_CORRUPT")
     );
 }
+
+#[test]
+fn watch_once_json_deltas_starts_with_a_full_frame() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let task_id = create_task(repo.path(), "Watch delta baseline");
+
+    let result = run_json(repo.path(), ["watch", "--once", "--deltas"]);
+
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stderr);
+    assert!(result.envelope["data"].get("deltas").is_none());
+    assert_eq!(ids_from_task_list(&result.envelope), vec![task_id]);
+}
+
+#[test]
+fn watch_deltas_requires_json() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let result = run_cli(repo.path(), ["watch", "--once", "--deltas"]);
+
+    assert_eq!(result.code, 1);
+    assert!(
+        result.stderr.contains("--deltas requires --json"),
+        "stderr:\n{}",
+        result.stderr
+    );
+}
+
+#[test]
+fn watch_once_json_applies_query_filter() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let urgent = create_task_with_args(repo.path(), "Urgent backend", &["--priority", "0"]);
+    let labeled = run_json(repo.path(), ["label", urgent.as_str(), "backend"]);
+    assert_eq!(labeled.cli.code, 0);
+    let low = create_task_with_args(repo.path(), "Low backend", &["--priority", "3"]);
+    let labeled = run_json(repo.path(), ["label", low.as_str(), "backend"]);
+    assert_eq!(labeled.cli.code, 0);
+    create_task_with_args(repo.path(), "Urgent frontend", &["--priority", "0"]);
+
+    let result = run_json(
+        repo.path(),
+        ["watch", "--once", "--query", "label:backend priority<=1"],
+    );
+
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stderr);
+    assert_eq!(ids_from_task_list(&result.envelope), vec![urgent]);
+    assert_eq!(
+        result.envelope["data"]["filters"]["query"].as_str(),
+        Some("label:backend priority<=1")
+    );
+}
+
+#[test]
+fn watch_once_invalid_query_returns_validation_error_envelope() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+
+    let result = run_json(repo.path(), ["watch", "--once", "--query", "priority<=9"]);
+
+    assert_eq!(result.cli.code, 1);
+    assert_validation_error(&result);
+}
+
+#[test]
+fn watch_task_once_lists_only_the_followed_tasks_events() {
+    let repo = common::make_repo();
+    init_repo(repo.path());
+    let followed = create_task(repo.path(), "Followed task");
+    let other = create_task(repo.path(), "Other task");
+    run_json(
+        repo.path(),
+        ["note", followed.as_str(), "picked up parsing"],
+    );
+    run_json(repo.path(), ["start", followed.as_str()]);
+    run_json(repo.path(), ["note", other.as_str(), "unrelated"]);
+
+    let result = run_json(
+        repo.path(),
+        ["watch", "--once", "--task", followed.as_str()],
+    );
+    assert_eq!(result.cli.code, 0, "{}", result.cli.stderr);
+    let data = &result.envelope["data"];
+    assert_eq!(data["tasks"], serde_json::json!([followed.clone()]));
+    let events = data["events"].as_array().expect("events");
+    let types: Vec<&str> = events
+        .iter()
+        .map(|event| event["type"].as_str().expect("type"))
+        .collect();
+    assert_eq!(types, ["task.created", "task.noted", "task.status_set"]);
+    assert_eq!(events[1]["summary"], "note: picked up parsing");
+    assert!(
+        events
+            .iter()
+            .all(|event| event["task_id"] == followed.as_str())
+    );
+
+    let human = run_cli(
+        repo.path(),
+        ["watch", "--once", "--task", followed.as_str()],
+    );
+    assert_eq!(human.code, 0, "{}", human.stderr);
+    assert_eq!(human.stdout.lines().count(), 3);
+    assert!(human.stdout.contains("note: picked up parsing"));
+}